@@ -5,28 +5,36 @@
 // - Useful for post-session analysis even when the TUI is active.
 use chrono::Local;
 use clap::{builder::BoolishValueParser, Parser, ValueEnum};
+use coldvox_app::audio::dump::{AudioDump, DumpFormat, DumpOptions};
+use coldvox_app::black_box::BlackBoxConfig;
 use coldvox_app::runtime::{self as app_runtime, ActivationMode};
+use coldvox_app::stt::compare::ComparisonResult;
+use coldvox_app::stt::failover::FailoverNotice;
 #[cfg(any(feature = "moonshine", feature = "parakeet"))]
 use coldvox_app::stt::TranscriptionEvent;
+use coldvox_app::text_injection::{
+    ClipboardInjector, GuardEvent, HeldTranscript, HoldReason, InjectionConfig, InjectionEvent,
+    PasteProgress,
+};
+use coldvox_audio::{DeviceInfo, DeviceManager};
+use coldvox_audio_quality::BandSpectrum;
+use coldvox_foundation::DeviceEvent;
 use coldvox_vad::types::VadEvent;
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use hound::{SampleFormat, WavSpec, WavWriter};
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Gauge, Paragraph, Sparkline},
+    widgets::{Block, Borders, Clear, Gauge, Paragraph, Sparkline, Wrap},
     Frame, Terminal,
 };
 use std::collections::VecDeque;
-use std::fs;
 use std::io;
-use std::io::Write as _;
 use std::path::PathBuf;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -102,9 +110,22 @@ struct Cli {
     #[arg(long = "dump-dir")]
     dump_dir: Option<String>,
 
-    /// Dump format: pcm or wav
+    /// Dump format: pcm, wav, opus or flac (opus and flac need the
+    /// dump-opus and dump-flac features)
     #[arg(long = "dump-format", value_enum, default_value = "pcm")]
     dump_format: DumpFormat,
+
+    /// Start a new dump file after this many megabytes (0 = no limit)
+    #[arg(long = "dump-max-mb", default_value_t = 64)]
+    dump_max_mb: u64,
+
+    /// Start a new dump file after this many minutes of audio (0 = no limit)
+    #[arg(long = "dump-max-minutes", default_value_t = 10)]
+    dump_max_minutes: u64,
+
+    /// Dump files to keep, deleting the oldest (0 = keep all)
+    #[arg(long = "dump-keep", default_value_t = 0)]
+    dump_keep: usize,
 }
 
 #[derive(Clone, Copy, Debug, ValueEnum)]
@@ -113,25 +134,149 @@ enum CliActivationMode {
     Hotkey,
 }
 
-#[derive(Clone, Copy, Debug, ValueEnum)]
-enum DumpFormat {
-    Pcm,
-    Wav,
-}
-
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum Tab {
     Audio,
     Logs,
+    Transcripts,
     Plugins,
 }
 
+/// Input device picker popup
+struct DevicePicker {
+    devices: Vec<DeviceInfo>,
+    selected: usize,
+}
+
+/// Transcripts kept for the Transcripts tab
+const TRANSCRIPT_HISTORY: usize = 1000;
+/// Rows moved by PageUp/PageDown in the Transcripts tab
+const TRANSCRIPT_PAGE: isize = 10;
+
+/// One utterance in the Transcripts tab; its partial is updated in place
+/// until the final arrives
+struct TranscriptEntry {
+    utterance_id: u64,
+    received: chrono::DateTime<Local>,
+    text: String,
+    is_final: bool,
+}
+
+/// The session's transcripts with search and selection
+#[derive(Default)]
+struct TranscriptLog {
+    entries: VecDeque<TranscriptEntry>,
+    /// Case-insensitive filter; empty shows everything
+    search: String,
+    /// Keys go to `search` until Enter or Esc
+    searching: bool,
+    /// Index into the visible entries; None follows the newest
+    selected: Option<usize>,
+}
+
+#[cfg_attr(
+    not(any(feature = "moonshine", feature = "parakeet")),
+    allow(dead_code)
+)]
+impl TranscriptLog {
+    /// Add a partial or final for `utterance_id`, replacing its partial
+    fn record(&mut self, utterance_id: u64, text: String, is_final: bool) {
+        if text.trim().is_empty() {
+            return;
+        }
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .rev()
+            .find(|e| e.utterance_id == utterance_id && !e.is_final)
+        {
+            entry.text = text;
+            entry.is_final = is_final;
+            entry.received = Local::now();
+            return;
+        }
+        if self.entries.len() == TRANSCRIPT_HISTORY {
+            self.entries.pop_front();
+            self.selected = self.selected.map(|s| s.saturating_sub(1));
+        }
+        self.entries.push_back(TranscriptEntry {
+            utterance_id,
+            received: Local::now(),
+            text,
+            is_final,
+        });
+    }
+
+    /// Entries matching the search, oldest first
+    fn visible(&self) -> Vec<&TranscriptEntry> {
+        let needle = self.search.to_lowercase();
+        self.entries
+            .iter()
+            .filter(|e| needle.is_empty() || e.text.to_lowercase().contains(&needle))
+            .collect()
+    }
+
+    /// Selected position among `visible_len` visible entries
+    fn selected_index(&self, visible_len: usize) -> Option<usize> {
+        let last = visible_len.checked_sub(1)?;
+        Some(self.selected.unwrap_or(last).min(last))
+    }
+
+    /// Move the selection; reaching the newest entry follows new ones again
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.visible().len();
+        let Some(current) = self.selected_index(len) else {
+            return;
+        };
+        let next = (current as isize + delta).clamp(0, len as isize - 1) as usize;
+        self.selected = (next + 1 < len).then_some(next);
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        let visible = self.visible();
+        let index = self.selected_index(visible.len())?;
+        Some(visible[index].text.clone())
+    }
+
+    fn search_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.searching = false,
+            KeyCode::Esc => {
+                self.search.clear();
+                self.searching = false;
+            }
+            KeyCode::Backspace => {
+                self.search.pop();
+            }
+            KeyCode::Char(c) => self.search.push(c),
+            _ => return,
+        }
+        self.selected = None;
+    }
+}
+
 #[allow(dead_code)]
 enum AppEvent {
     Log(LogLevel, String),
     Vad(VadEvent),
+    /// Band levels (dBFS, lowest first) of the latest audio frame
+    Spectrum(Vec<f32>),
     /// Internal control signal: runtime replaced (after restart)
-    AppReplaced(app_runtime::AppHandle),
+    AppReplaced(Box<app_runtime::AppHandle>),
+    /// STT plugin failover that the user should see
+    Failover(FailoverNotice),
+    /// Both transcripts of an utterance in A/B comparison mode
+    Comparison(ComparisonResult),
+    /// Chunked paste of a long transcript
+    PasteProgress(PasteProgress),
+    /// Final transcript held back for low confidence
+    LowConfidence(HeldTranscript),
+    /// Injection size or rate limit triggered
+    Guard(GuardEvent),
+    /// Injection outcome
+    Injection(InjectionEvent),
+    /// Capture device hotplug, failover or switch
+    Device(DeviceEvent),
     #[cfg(any(feature = "moonshine", feature = "parakeet"))]
     Transcription(TranscriptionEvent),
     #[cfg(any(feature = "moonshine", feature = "parakeet"))]
@@ -165,6 +310,8 @@ struct PipelineMetricsSnapshot {
 struct DashboardState {
     level_history: VecDeque<u8>,
     peak_history: VecDeque<u8>,
+    /// Recent band levels, oldest first, for the spectrogram
+    spectrogram: VecDeque<Vec<f32>>,
     is_speaking: bool,
     speech_segments: u64,
     last_vad_event: Option<String>,
@@ -182,6 +329,10 @@ struct DashboardState {
     /// Last final transcript (if STT enabled)
     #[cfg(any(feature = "moonshine", feature = "parakeet"))]
     last_transcript: Option<String>,
+    /// Every partial and final of the session, for the Transcripts tab
+    transcripts: TranscriptLog,
+    /// Open device picker
+    device_picker: Option<DevicePicker>,
 
     #[cfg(any(feature = "moonshine", feature = "parakeet"))]
     plugin_manager:
@@ -202,10 +353,15 @@ struct DashboardState {
     #[cfg(any(feature = "moonshine", feature = "parakeet"))]
     plugin_failures: u64,
 
+    /// Most recent STT failover, shown as a banner until it expires
+    failover_banner: Option<(FailoverNotice, Instant)>,
+
+    /// Latest chunked-paste progress
+    paste_progress: Option<PasteProgress>,
+
     // Audio dump options
     dump_audio: bool,
-    dump_dir: Option<String>,
-    dump_format: DumpFormat,
+    dump_options: DumpOptions,
 }
 
 #[derive(Clone)]
@@ -244,6 +400,7 @@ impl Default for DashboardState {
         Self {
             level_history,
             peak_history,
+            spectrogram: VecDeque::with_capacity(SPECTROGRAM_HISTORY),
             is_speaking: false,
             speech_segments: 0,
             last_vad_event: None,
@@ -274,8 +431,12 @@ impl Default for DashboardState {
             },
             has_metrics_snapshot: false,
             current_tab: Tab::Audio,
+            failover_banner: None,
+            paste_progress: None,
             #[cfg(any(feature = "moonshine", feature = "parakeet"))]
             last_transcript: None,
+            transcripts: TranscriptLog::default(),
+            device_picker: None,
             #[cfg(any(feature = "moonshine", feature = "parakeet"))]
             plugin_manager: None,
             #[cfg(any(feature = "moonshine", feature = "parakeet"))]
@@ -290,13 +451,69 @@ impl Default for DashboardState {
             plugin_failures: 0,
 
             dump_audio: true,
-            dump_dir: None,
-            dump_format: DumpFormat::Pcm,
+            dump_options: DumpOptions::default(),
         }
     }
 }
 
 impl DashboardState {
+    /// Open the device picker on the current device
+    fn open_device_picker(&mut self) {
+        let devices = match DeviceManager::new() {
+            Ok(manager) => manager.enumerate_devices(),
+            Err(e) => {
+                self.log(LogLevel::Error, format!("Cannot list input devices: {}", e));
+                return;
+            }
+        };
+        if devices.is_empty() {
+            self.log(LogLevel::Warning, "No input devices found".to_string());
+            return;
+        }
+        let current = self
+            .app
+            .as_ref()
+            .and_then(|app| app.current_device())
+            .unwrap_or_else(|| self.selected_device.clone());
+        let selected = devices
+            .iter()
+            .position(|d| d.name == current)
+            .or_else(|| devices.iter().position(|d| d.is_default))
+            .unwrap_or(0);
+        self.device_picker = Some(DevicePicker { devices, selected });
+    }
+
+    /// Keys while the device picker is open
+    fn device_picker_key(&mut self, code: KeyCode) {
+        let Some(picker) = &mut self.device_picker else {
+            return;
+        };
+        match code {
+            KeyCode::Up => picker.selected = picker.selected.saturating_sub(1),
+            KeyCode::Down => picker.selected = (picker.selected + 1).min(picker.devices.len() - 1),
+            KeyCode::Enter => {
+                let name = picker.devices[picker.selected].name.clone();
+                self.device_picker = None;
+                self.choose_device(name);
+            }
+            KeyCode::Esc | KeyCode::Char('d') | KeyCode::Char('D') => self.device_picker = None,
+            _ => {}
+        }
+    }
+
+    /// Capture from `name`: right away when running, else once started
+    fn choose_device(&mut self, name: String) {
+        let message = match &self.app {
+            Some(app) => {
+                app.set_device(&name);
+                format!("Switching input device to {}...", name)
+            }
+            None => format!("Input device set to {}", name),
+        };
+        self.log(LogLevel::Info, message);
+        self.selected_device = name;
+    }
+
     fn activation_label(mode: ActivationMode) -> &'static str {
         match mode {
             ActivationMode::Vad => "Always-on (VAD)",
@@ -384,8 +601,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Audio dump settings from CLI
     state.dump_audio = cli.dump_audio.unwrap_or(true);
-    state.dump_dir = cli.dump_dir;
-    state.dump_format = cli.dump_format;
+    state.dump_options = DumpOptions {
+        dir: cli
+            .dump_dir
+            .map(PathBuf::from)
+            .unwrap_or_else(|| DumpOptions::default().dir),
+        format: cli.dump_format,
+        max_segment_bytes: (cli.dump_max_mb > 0).then(|| cli.dump_max_mb * 1024 * 1024),
+        max_segment_duration: (cli.dump_max_minutes > 0)
+            .then(|| Duration::from_secs(cli.dump_max_minutes * 60)),
+        keep: cli.dump_keep,
+    };
 
     let res = run_app(&mut terminal, &mut state, tx, rx).await;
 
@@ -424,6 +650,15 @@ async fn run_app(
                 }
             } => {
                 if let Event::Key(key) = event {
+                    if state.device_picker.is_some() {
+                        state.device_picker_key(key.code);
+                        continue;
+                    }
+                    if state.transcripts.searching {
+                        state.transcripts.search_key(key.code);
+                        continue;
+                    }
+                    let transcripts_tab = matches!(state.current_tab, Tab::Transcripts);
                     match key.code {
                         KeyCode::Char('q') | KeyCode::Char('Q') => {
                             if state.is_running {
@@ -434,181 +669,219 @@ async fn run_app(
                             }
                             return Ok(());
                         }
-                        KeyCode::Char('s') | KeyCode::Char('S') => {
-                            if !state.is_running {
-                                state.log(LogLevel::Info, "Starting audio pipeline...".to_string());
-                                // Build runtime options
-                                let mut opts = app_runtime::AppRuntimeOptions {
-                                    device: if state.selected_device == "default" || state.selected_device.is_empty() { None } else { Some(state.selected_device.clone()) },
-                                    activation_mode: state.activation_mode,
-                                    resampler_quality: state.resampler_quality,
-                                    stt_selection: Some(coldvox_stt::plugin::PluginSelectionConfig::default()),
-                                    enable_device_monitor: false,
-                                    capture_buffer_samples: 65_536,
-                                    ..Default::default()
-                                };
+                        KeyCode::Char('s') | KeyCode::Char('S') if !state.is_running => {
+                            state.log(LogLevel::Info, "Starting audio pipeline...".to_string());
+                            // Build runtime options
+                            let mut opts = app_runtime::AppRuntimeOptions {
+                                device: if state.selected_device == "default" || state.selected_device.is_empty() { None } else { Some(state.selected_device.clone()) },
+                                activation_mode: state.activation_mode,
+                                resampler_quality: state.resampler_quality,
+                                stt_selection: Some(coldvox_stt::plugin::PluginSelectionConfig::default()),
+                                enable_device_monitor: false,
+                                capture_buffer_samples: 65_536,
+                                ..Default::default()
+                            };
 
-                                opts.injection = None;
-
-                                let ui_tx = tx.clone();
-                                // Start runtime synchronously and then wire up event forwarders
-                                match app_runtime::start(opts).await {
-                                    Ok(app) => {
-                                        #[allow(unused_mut)]
-                                        let mut app = app;
-                                        // Extract plugin manager for UI access before moving app
-                                        #[cfg(any(feature = "moonshine", feature = "parakeet"))]
-                                        {
-                                            state.plugin_manager = app.plugin_manager.clone();
+                            opts.injection = None;
+                            // Always-on recorder for the [B] key, with the
+                            // config files' settings for context
+                            opts.black_box = BlackBoxConfig::default_dir().map(|dir| BlackBoxConfig {
+                                seconds: 30,
+                                dir,
+                                settings: coldvox_app::Settings::new()
+                                    .ok()
+                                    .and_then(|s| serde_json::to_value(s).ok())
+                                    .unwrap_or_default(),
+                            });
+
+                            let ui_tx = tx.clone();
+                            // Start runtime synchronously and then wire up event forwarders
+                            match app_runtime::start(opts).await {
+                                Ok(app) => {
+                                    #[allow(unused_mut)]
+                                    let mut app = app;
+                                    // Extract plugin manager for UI access before moving app
+                                    #[cfg(any(feature = "moonshine", feature = "parakeet"))]
+                                    {
+                                        state.plugin_manager = app.plugin_manager.clone();
+                                    }
+                                    // Forward VAD events to UI
+                                    let mut vad_rx = app.subscribe_vad();
+                                    tokio::spawn(async move {
+                                        while let Ok(ev) = vad_rx.recv().await {
+                                            let _ = ui_tx.send(AppEvent::Vad(ev)).await;
                                         }
-                                        // Forward VAD events to UI
-                                        let mut vad_rx = app.subscribe_vad();
+                                    });
+
+                                    // Feed the spectrogram; frames are dropped rather
+                                    // than queued when the UI falls behind
+                                    let mut spectrum_rx = app.subscribe_audio();
+                                    let ui_tx_spectrum = tx.clone();
+                                    tokio::spawn(async move {
+                                        use tokio::sync::broadcast::error::RecvError;
+                                        let mut spectrum: Option<BandSpectrum> = None;
+                                        loop {
+                                            let frame = match spectrum_rx.recv().await {
+                                                Ok(frame) => frame,
+                                                Err(RecvError::Lagged(_)) => continue,
+                                                Err(RecvError::Closed) => break,
+                                            };
+                                            let spectrum = spectrum.get_or_insert_with(|| {
+                                                BandSpectrum::new(frame.sample_rate, SPECTRUM_BANDS)
+                                            });
+                                            if let Some(levels) = spectrum.push(&frame.samples) {
+                                                let event = AppEvent::Spectrum(levels.to_vec());
+                                                if let Err(mpsc::error::TrySendError::Closed(_)) = ui_tx_spectrum.try_send(event) {
+                                                    break;
+                                                }
+                                            }
+                                        }
+                                    });
+
+                                    // Forward capture device events to UI
+                                    let mut device_rx = app.subscribe_device_events();
+                                    let ui_tx_device = tx.clone();
+                                    tokio::spawn(async move {
+                                        use tokio::sync::broadcast::error::RecvError;
+                                        loop {
+                                            match device_rx.recv().await {
+                                                Ok(event) => {
+                                                    let _ = ui_tx_device.send(AppEvent::Device(event)).await;
+                                                }
+                                                Err(RecvError::Lagged(_)) => continue,
+                                                Err(RecvError::Closed) => break,
+                                            }
+                                        }
+                                    });
+
+                                    // Forward STT failover notices to UI
+                                    if let Some(mut failover_rx) = app.subscribe_stt_failover().await {
+                                        let ui_tx_failover = tx.clone();
                                         tokio::spawn(async move {
-                                            while let Ok(ev) = vad_rx.recv().await {
-                                                let _ = ui_tx.send(AppEvent::Vad(ev)).await;
+                                            while let Ok(notice) = failover_rx.recv().await {
+                                                let _ = ui_tx_failover.send(AppEvent::Failover(notice)).await;
                                             }
                                         });
+                                    }
 
-                                        // Forward STT events to UI (if enabled)
-                                        #[cfg(any(feature = "moonshine", feature = "parakeet"))]
-                                        if let Some(mut stt_rx) = app.stt_rx.take() {
-                                            let ui_tx2 = tx.clone();
-                                            tokio::spawn(async move {
-                                                while let Some(ev) = stt_rx.recv().await {
-                                                    let _ = ui_tx2.send(AppEvent::Transcription(ev)).await;
-                                                }
-                                            });
+                                    // Forward A/B transcription comparisons to UI
+                                    let mut comparison_rx = app.subscribe_stt_comparisons();
+                                    let ui_tx_comparison = tx.clone();
+                                    tokio::spawn(async move {
+                                        while let Ok(result) = comparison_rx.recv().await {
+                                            let _ = ui_tx_comparison.send(AppEvent::Comparison(result)).await;
                                         }
+                                    });
+
+                                    // Forward chunked-paste progress to UI
+                                    if let Some(mut paste_rx) = app.subscribe_paste_progress() {
+                                        let ui_tx_paste = tx.clone();
+                                        tokio::spawn(async move {
+                                            while let Ok(progress) = paste_rx.recv().await {
+                                                let _ = ui_tx_paste.send(AppEvent::PasteProgress(progress)).await;
+                                            }
+                                        });
+                                    }
+
+                                    // Forward transcripts held for low confidence to UI
+                                    if let Some(mut held_rx) = app.subscribe_low_confidence() {
+                                        let ui_tx_held = tx.clone();
+                                        tokio::spawn(async move {
+                                            while let Ok(held) = held_rx.recv().await {
+                                                let _ = ui_tx_held.send(AppEvent::LowConfidence(held)).await;
+                                            }
+                                        });
+                                    }
 
-                                        // Optional: dump raw audio to disk if enabled and not disabled by env
-                                        let dump_enabled = state.dump_audio;
-                                        let dump_dir = state.dump_dir.clone();
-                                        let dump_format = state.dump_format;
-                                        if dump_enabled {
-                                            let env_disable = std::env::var("COLDVOX_DISABLE_AUDIO_DUMP").unwrap_or_default().to_lowercase();
-                                            if matches!(env_disable.as_str(), "1" | "true" | "yes") {
-                                                let _ = tx.send(AppEvent::Log(LogLevel::Warning, "Audio dump disabled by COLDVOX_DISABLE_AUDIO_DUMP".to_string())).await;
-                                            } else {
-                                                let mut audio_rx = app.subscribe_audio();
-                                                let ui_tx3 = tx.clone();
-                                                tokio::spawn(async move {
-                                                    // Resolve output directory
-                                                    let base_dir = dump_dir.unwrap_or_else(|| "logs/audio_dumps".to_string());
-                                                    if let Err(e) = fs::create_dir_all(&base_dir) {
-                                                        let _ = ui_tx3.send(AppEvent::Log(LogLevel::Error, format!("Failed to create dump dir '{}': {}", base_dir, e))).await;
+                                    // Forward injection size/rate limits to UI
+                                    if let Some(mut guard_rx) = app.subscribe_guard_events() {
+                                        let ui_tx_guard = tx.clone();
+                                        tokio::spawn(async move {
+                                            while let Ok(event) = guard_rx.recv().await {
+                                                let _ = ui_tx_guard.send(AppEvent::Guard(event)).await;
+                                            }
+                                        });
+                                    }
+
+                                    // Forward injection outcomes to UI
+                                    if let Some(mut injection_rx) = app.subscribe_injection() {
+                                        let ui_tx_injection = tx.clone();
+                                        tokio::spawn(async move {
+                                            while let Ok(event) = injection_rx.recv().await {
+                                                let _ = ui_tx_injection.send(AppEvent::Injection(event)).await;
+                                            }
+                                        });
+                                    }
+
+                                    // Forward STT events to UI (if enabled)
+                                    #[cfg(any(feature = "moonshine", feature = "parakeet"))]
+                                    if let Some(mut stt_rx) = app.stt_rx.take() {
+                                        let ui_tx2 = tx.clone();
+                                        tokio::spawn(async move {
+                                            while let Some(ev) = stt_rx.recv().await {
+                                                let _ = ui_tx2.send(AppEvent::Transcription(ev)).await;
+                                            }
+                                        });
+                                    }
+
+                                    // Optional: dump audio to disk if enabled and not disabled by env
+                                    let dump_enabled = state.dump_audio;
+                                    let dump_options = state.dump_options.clone();
+                                    if dump_enabled {
+                                        let env_disable = std::env::var("COLDVOX_DISABLE_AUDIO_DUMP").unwrap_or_default().to_lowercase();
+                                        if matches!(env_disable.as_str(), "1" | "true" | "yes") {
+                                            let _ = tx.send(AppEvent::Log(LogLevel::Warning, "Audio dump disabled by COLDVOX_DISABLE_AUDIO_DUMP".to_string())).await;
+                                        } else {
+                                            let mut audio_rx = app.subscribe_audio();
+                                            let ui_tx3 = tx.clone();
+                                            tokio::spawn(async move {
+                                                let dir = dump_options.dir.clone();
+                                                let mut dump = match AudioDump::new(dump_options) {
+                                                    Ok(dump) => dump,
+                                                    Err(e) => {
+                                                        let _ = ui_tx3.send(AppEvent::Log(LogLevel::Error, format!("Failed to start audio dump in '{}': {}", dir.display(), e))).await;
                                                         return;
                                                     }
+                                                };
 
-                                                    let ts = Local::now().format("%Y%m%d_%H%M%S").to_string();
-                                                    match dump_format {
-                                                        DumpFormat::Pcm => {
-                                                            let mut path = PathBuf::from(&base_dir);
-                                                            path.push(format!("audio_{}.pcm", ts));
-                                                            let _ = ui_tx3.send(AppEvent::Log(LogLevel::Info, format!("Audio dump enabled: {}", path.display()))).await;
-
-                                                            let mut writer = match std::fs::File::create(&path).map(std::io::BufWriter::new) {
-                                                                Ok(w) => w,
-                                                                Err(e) => {
-                                                                    let _ = ui_tx3.send(AppEvent::Log(LogLevel::Error, format!("Failed to open '{}': {}", path.display(), e))).await;
-                                                                    return;
-                                                                }
-                                                            };
-
-                                                            loop {
-                                                                match audio_rx.recv().await {
-                                                                    Ok(frame) => {
-                                                                        // Write i16 samples as little-endian PCM
-                                                                        let mut buf = Vec::with_capacity(frame.samples.len() * 2);
-                                                                        for &s in frame.samples.iter() {
-                                                                            let b = s.to_le_bytes();
-                                                                            buf.push(b[0]);
-                                                                            buf.push(b[1]);
-                                                                        }
-                                                                        if let Err(e) = writer.write_all(&buf) {
-                                                                            let _ = ui_tx3.send(AppEvent::Log(LogLevel::Error, format!("PCM write error: {}", e))).await;
-                                                                            break;
-                                                                        }
-                                                                    }
-                                                                    Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
-                                                                        let _ = ui_tx3.send(AppEvent::Log(LogLevel::Debug, format!("Audio dump lagged; dropped {} frames", n))).await;
-                                                                        continue;
-                                                                    }
-                                                                    Err(_) => {
-                                                                        // Channel closed or canceled
-                                                                        break;
-                                                                    }
-                                                                }
+                                                loop {
+                                                    match audio_rx.recv().await {
+                                                        Ok(frame) => match dump.write(frame.sample_rate, &frame.samples) {
+                                                            Ok(Some(path)) => {
+                                                                let _ = ui_tx3.send(AppEvent::Log(LogLevel::Info, format!("Audio dump: {} ({} Hz)", path.display(), frame.sample_rate))).await;
                                                             }
-                                                            let _ = writer.flush();
-                                                            let _ = ui_tx3.send(AppEvent::Log(LogLevel::Info, "Audio dump stopped".to_string())).await;
-                                                        }
-                                                        DumpFormat::Wav => {
-                                                            let mut path = PathBuf::from(&base_dir);
-                                                            path.push(format!("audio_{}.wav", ts));
-                                                            // Grab the first frame to determine sample rate
-                                                            let first_frame = match audio_rx.recv().await {
-                                                                Ok(f) => f,
-                                                                Err(e) => {
-                                                                    let _ = ui_tx3.send(AppEvent::Log(LogLevel::Error, format!("Failed to start WAV dump: {}", e))).await;
-                                                                    return;
-                                                                }
-                                                            };
-                                                            let spec = WavSpec {
-                                                                channels: 1,
-                                                                sample_rate: first_frame.sample_rate,
-                                                                bits_per_sample: 16,
-                                                                sample_format: SampleFormat::Int,
-                                                            };
-                                                            let mut wav = match WavWriter::create(&path, spec) {
-                                                                Ok(w) => w,
-                                                                Err(e) => {
-                                                                    let _ = ui_tx3.send(AppEvent::Log(LogLevel::Error, format!("Failed to open '{}': {}", path.display(), e))).await;
-                                                                    return;
-                                                                }
-                                                            };
-                                                            let _ = ui_tx3.send(AppEvent::Log(LogLevel::Info, format!("Audio dump enabled: {} ({} Hz)", path.display(), first_frame.sample_rate))).await;
-                                                            // Write first frame
-                                                            for &s in first_frame.samples.iter() {
-                                                                if wav.write_sample(s).is_err() { break; }
-                                                            }
-                                                            // Remaining frames
-                                                            loop {
-                                                                match audio_rx.recv().await {
-                                                                    Ok(frame) => {
-                                                                        for &s in frame.samples.iter() {
-                                                                            if let Err(e) = wav.write_sample(s) {
-                                                                                let _ = ui_tx3.send(AppEvent::Log(LogLevel::Error, format!("WAV write error: {}", e))).await;
-                                                                                break;
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                    Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
-                                                                        let _ = ui_tx3.send(AppEvent::Log(LogLevel::Debug, format!("Audio dump lagged; dropped {} frames", n))).await;
-                                                                        continue;
-                                                                    }
-                                                                    Err(_) => break,
-                                                                }
-                                                            }
-                                                            let _ = wav.flush();
-                                                            if let Err(e) = wav.finalize() {
-                                                                let _ = ui_tx3.send(AppEvent::Log(LogLevel::Error, format!("Error finalizing WAV: {}", e))).await;
-                                                            } else {
-                                                                let _ = ui_tx3.send(AppEvent::Log(LogLevel::Info, "Audio dump stopped".to_string())).await;
+                                                            Ok(None) => {}
+                                                            Err(e) => {
+                                                                let _ = ui_tx3.send(AppEvent::Log(LogLevel::Error, format!("Audio dump write error: {}", e))).await;
+                                                                break;
                                                             }
+                                                        },
+                                                        Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                                                            let _ = ui_tx3.send(AppEvent::Log(LogLevel::Debug, format!("Audio dump lagged; dropped {} frames", n))).await;
+                                                            continue;
+                                                        }
+                                                        Err(_) => {
+                                                            // Channel closed or canceled
+                                                            break;
                                                         }
                                                     }
-                                                });
-                                            }
+                                                }
+                                                if let Err(e) = dump.finish() {
+                                                    let _ = ui_tx3.send(AppEvent::Log(LogLevel::Error, format!("Error finalizing audio dump: {}", e))).await;
+                                                } else {
+                                                    let _ = ui_tx3.send(AppEvent::Log(LogLevel::Info, "Audio dump stopped".to_string())).await;
+                                                }
+                                            });
                                         }
-
-                                        state.app = Some(app);
-                                        state.is_running = true;
-                                        state.log(LogLevel::Success, "Pipeline fully started".to_string());
-                                        state.log(LogLevel::Success, "Pipeline fully started".to_string());
-                                    }
-                                    Err(e) => {
-                                        state.log(LogLevel::Error, format!("Failed to start runtime: {}", e));
                                     }
+
+                                    state.app = Some(app);
+                                    state.is_running = true;
+                                    state.log(LogLevel::Success, "Pipeline fully started".to_string());
+                                    state.log(LogLevel::Success, "Pipeline fully started".to_string());
+                                }
+                                Err(e) => {
+                                    state.log(LogLevel::Error, format!("Failed to start runtime: {}", e));
                                 }
                             }
                         }
@@ -629,67 +902,110 @@ async fn run_app(
                         KeyCode::Char('r') | KeyCode::Char('R') => {
                             state.reset_metrics();
                         }
+                        KeyCode::Char('d') | KeyCode::Char('D') => state.open_device_picker(),
+                        KeyCode::Char(' ') => {
+                            if let Some(app) = &state.app {
+                                let msg = if app.toggle_pause() {
+                                    "Pipeline paused"
+                                } else {
+                                    "Pipeline resumed"
+                                };
+                                state.log(LogLevel::Info, msg.to_string());
+                            }
+                        }
+                        KeyCode::Char('b') | KeyCode::Char('B') => {
+                            if let Some(app) = &state.app {
+                                match app.save_black_box() {
+                                    Some(Ok(path)) => state.log(LogLevel::Success, format!("Black box saved to {}", path.display())),
+                                    Some(Err(e)) => state.log(LogLevel::Error, format!("Failed to save black box: {}", e)),
+                                    None => state.log(LogLevel::Info, "Black box recorder is off".to_string()),
+                                }
+                            }
+                        }
+                        KeyCode::Char('x') | KeyCode::Char('X') => {
+                            if let Some(app) = &state.app {
+                                app.cancel_paste();
+                                state.log(LogLevel::Info, "Cancel requested for chunked paste".to_string());
+                            }
+                        }
+                        KeyCode::Char('y') | KeyCode::Char('Y') => {
+                            if let Some(app) = &state.app {
+                                match app.accept_low_confidence().await {
+                                    Some(held) => state.log(LogLevel::Success, format!("Typing held transcript: \"{}\"", held.text)),
+                                    None => state.log(LogLevel::Info, "No low-confidence transcript is held".to_string()),
+                                }
+                            }
+                        }
                         KeyCode::Char('p') | KeyCode::Char('P') => {
                             // Toggle between tabs
                             state.current_tab = match state.current_tab {
                                 Tab::Audio => Tab::Logs,
-                                Tab::Logs => Tab::Plugins,
+                                Tab::Logs => Tab::Transcripts,
+                                Tab::Transcripts => Tab::Plugins,
                                 Tab::Plugins => Tab::Audio,
                             };
                             state.log(LogLevel::Info, format!("Switched to {:?} tab", state.current_tab));
                         }
-                        KeyCode::Char('l') | KeyCode::Char('L') => {
+                        KeyCode::Char('l') | KeyCode::Char('L') if state.is_running => {
                             // Load plugin (only when running)
-                            if state.is_running {
-                                #[cfg(any(feature = "moonshine", feature = "parakeet"))]
-                                {
-                                    if let Some(ref pm) = state.plugin_manager {
-                                        let pm_clone = pm.clone();
-                                        let tx_clone = tx.clone();
-                                        tokio::spawn(async move {
-                                            let result = pm_clone.write().await.switch_plugin("mock").await;
-                                            let _ = tx_clone.send(AppEvent::PluginSwitch("mock".to_string())).await;
-                                            if result.is_ok() {
-                                                let _ = tx_clone.send(AppEvent::PluginLoad("mock".to_string())).await;
-                                            }
-                                        });
-                                    }
+                            #[cfg(any(feature = "moonshine", feature = "parakeet"))]
+                            {
+                                if let Some(ref pm) = state.plugin_manager {
+                                    let pm_clone = pm.clone();
+                                    let tx_clone = tx.clone();
+                                    tokio::spawn(async move {
+                                        let result = pm_clone.write().await.switch_plugin("mock").await;
+                                        let _ = tx_clone.send(AppEvent::PluginSwitch("mock".to_string())).await;
+                                        if result.is_ok() {
+                                            let _ = tx_clone.send(AppEvent::PluginLoad("mock".to_string())).await;
+                                        }
+                                    });
                                 }
-                                state.log(LogLevel::Info, "Loading plugin...".to_string());
                             }
+                            state.log(LogLevel::Info, "Loading plugin...".to_string());
                         }
-                        KeyCode::Char('u') | KeyCode::Char('U') => {
+                        KeyCode::Char('u') | KeyCode::Char('U') if state.is_running => {
                             // Unload plugin (only when running)
-                            if state.is_running {
-                                #[cfg(any(feature = "moonshine", feature = "parakeet"))]
-                                {
-                                    if let Some(ref pm) = state.plugin_manager {
-                                        let pm_clone = pm.clone();
-                                        let tx_clone = tx.clone();
-                                        tokio::spawn(async move {
-                                            let _result = pm_clone.write().await.unload_plugin("mock").await;
-                                            let _ = tx_clone.send(AppEvent::PluginUnload("mock".to_string())).await;
-                                        });
-                                    }
+                            #[cfg(any(feature = "moonshine", feature = "parakeet"))]
+                            {
+                                if let Some(ref pm) = state.plugin_manager {
+                                    let pm_clone = pm.clone();
+                                    let tx_clone = tx.clone();
+                                    tokio::spawn(async move {
+                                        let _result = pm_clone.write().await.unload_plugin("mock").await;
+                                        let _ = tx_clone.send(AppEvent::PluginUnload("mock".to_string())).await;
+                                    });
                                 }
-                                state.log(LogLevel::Info, "Unloading plugin...".to_string());
                             }
+                            state.log(LogLevel::Info, "Unloading plugin...".to_string());
                         }
-                        KeyCode::Char('w') | KeyCode::Char('W') => {
+                        KeyCode::Char('w') | KeyCode::Char('W') if state.is_running && matches!(state.current_tab, Tab::Plugins) => {
                             // Switch plugin (only when running and in plugins tab)
-                            if state.is_running && matches!(state.current_tab, Tab::Plugins) {
-                                #[cfg(any(feature = "moonshine", feature = "parakeet"))]
-                                {
-                                    if let Some(ref pm) = state.plugin_manager {
-                                        let pm_clone = pm.clone();
-                                        let tx_clone = tx.clone();
-                                        tokio::spawn(async move {
-                                            let _result = pm_clone.write().await.switch_plugin("noop").await;
-                                            let _ = tx_clone.send(AppEvent::PluginSwitch("noop".to_string())).await;
-                                        });
-                                    }
+                            #[cfg(any(feature = "moonshine", feature = "parakeet"))]
+                            {
+                                if let Some(ref pm) = state.plugin_manager {
+                                    let pm_clone = pm.clone();
+                                    let tx_clone = tx.clone();
+                                    tokio::spawn(async move {
+                                        let _result = pm_clone.write().await.switch_plugin("noop").await;
+                                        let _ = tx_clone.send(AppEvent::PluginSwitch("noop".to_string())).await;
+                                    });
                                 }
-                                state.log(LogLevel::Info, "Switching plugin...".to_string());
+                            }
+                            state.log(LogLevel::Info, "Switching plugin...".to_string());
+                        }
+                        // Transcripts tab: select, search, copy
+                        KeyCode::Up if transcripts_tab => state.transcripts.move_selection(-1),
+                        KeyCode::Down if transcripts_tab => state.transcripts.move_selection(1),
+                        KeyCode::PageUp if transcripts_tab => state.transcripts.move_selection(-TRANSCRIPT_PAGE),
+                        KeyCode::PageDown if transcripts_tab => state.transcripts.move_selection(TRANSCRIPT_PAGE),
+                        KeyCode::Home if transcripts_tab => state.transcripts.selected = Some(0),
+                        KeyCode::End if transcripts_tab => state.transcripts.selected = None,
+                        KeyCode::Char('/') if transcripts_tab => state.transcripts.searching = true,
+                        KeyCode::Char('c') | KeyCode::Char('C') if transcripts_tab => {
+                            match state.transcripts.selected_text() {
+                                Some(text) => copy_to_clipboard(text, tx.clone()),
+                                None => state.log(LogLevel::Info, "No transcript selected".to_string()),
                             }
                         }
                         _ => {}
@@ -700,6 +1016,12 @@ async fn run_app(
             Some(event) = rx.recv() => {
                 match event {
                     AppEvent::Log(level, msg) => state.log(level, msg),
+                    AppEvent::Spectrum(levels) => {
+                        if state.spectrogram.len() == SPECTROGRAM_HISTORY {
+                            state.spectrogram.pop_front();
+                        }
+                        state.spectrogram.push_back(levels);
+                    }
                     AppEvent::Vad(vad_event) => {
                         state.vad_frames += 1;
                         match vad_event {
@@ -717,8 +1039,76 @@ async fn run_app(
                         }
                     }
                     AppEvent::AppReplaced(app) => {
-                        state.app = Some(app);
+                        state.app = Some(*app);
                         state.is_running = true;
+                    }
+                    AppEvent::Failover(notice) => {
+                        let level = if notice.new_plugin.is_some() { LogLevel::Warning } else { LogLevel::Error };
+                        state.log(level, format!("{} - {}", notice.summary(), notice.error));
+                        state.log(LogLevel::Info, format!("Hint: {}", notice.hint));
+                        state.failover_banner = Some((notice, Instant::now()));
+                    }
+                    AppEvent::Comparison(result) => {
+                        state.log(LogLevel::Info, result.summary());
+                    }
+                    AppEvent::Guard(event) => {
+                        let level = if event.is_error() {
+                            LogLevel::Error
+                        } else {
+                            LogLevel::Warning
+                        };
+                        state.log(level, event.to_string());
+                    }
+                    AppEvent::Injection(event) => match event {
+                        InjectionEvent::Succeeded { .. } => state.log(LogLevel::Success, event.to_string()),
+                        InjectionEvent::Failed { .. } => state.log(LogLevel::Error, event.to_string()),
+                        InjectionEvent::Queued { .. } | InjectionEvent::Attempting { .. } => {}
+                    },
+                    AppEvent::LowConfidence(held) => {
+                        let message = match &held.reason {
+                            HoldReason::LowConfidence => format!(
+                                "Low confidence ({:.0}%): \"{}\" — [Y] type it",
+                                held.confidence * 100.0,
+                                held.text
+                            ),
+                            // The text itself stays off screen
+                            HoldReason::SensitiveContent(kinds) => format!(
+                                "Held: looks like it contains {} ({} chars) — [Y] type it",
+                                coldvox_foundation::scrub::describe(kinds),
+                                held.text.chars().count()
+                            ),
+                            HoldReason::UnconfirmedCommand(cmd) => format!(
+                                "Sent '{}' early, but you said \"{}\" — [Y] type it",
+                                cmd, held.text
+                            ),
+                        };
+                        state.log(LogLevel::Warning, message);
+                    }
+                    AppEvent::Device(event) => match event {
+                        DeviceEvent::DeviceSwitched { to, .. } => {
+                            state.log(LogLevel::Success, format!("Capturing from {}", to));
+                            state.selected_device = to;
+                        }
+                        DeviceEvent::DeviceSwitchFailed { attempted, .. } => {
+                            state.log(LogLevel::Error, format!("Could not open {} or any fallback device", attempted));
+                        }
+                        DeviceEvent::DeviceAdded { name } => {
+                            state.log(LogLevel::Info, format!("Input device added: {}", name));
+                        }
+                        DeviceEvent::DeviceRemoved { name } => {
+                            state.log(LogLevel::Warning, format!("Input device removed: {}", name));
+                        }
+                        _ => {}
+                    },
+                    AppEvent::PasteProgress(progress) => {
+                        let level = match progress {
+                            PasteProgress::Failed { .. } => LogLevel::Error,
+                            PasteProgress::Cancelled { .. } => LogLevel::Warning,
+                            PasteProgress::Completed { .. } => LogLevel::Success,
+                            _ => LogLevel::Info,
+                        };
+                        state.log(level, progress.to_string());
+                        state.paste_progress = Some(progress);
                     }
                         #[cfg(any(feature = "moonshine", feature = "parakeet"))]
                         AppEvent::Transcription(tevent) => {
@@ -726,11 +1116,13 @@ async fn run_app(
                                 TranscriptionEvent::Partial { utterance_id, text, .. } => {
                                     if !text.trim().is_empty() {
                                         state.log(LogLevel::Info, format!("[STT partial:{}] {}", utterance_id, text));
+                                        state.transcripts.record(utterance_id, text, false);
                                     }
                                 }
                                 TranscriptionEvent::Final { utterance_id, text, .. } => {
                                     if !text.trim().is_empty() {
                                         state.log(LogLevel::Success, format!("[STT final:{}] {}", utterance_id, text));
+                                        state.transcripts.record(utterance_id, text.clone(), true);
                                         state.last_transcript = Some(text);
                                     }
                                 }
@@ -817,7 +1209,34 @@ async fn run_app(
     }
 }
 
+/// How long a failover banner stays on screen.
+const FAILOVER_BANNER_SECS: u64 = 30;
+
+/// Frequency bands in the spectrogram (two per text row)
+const SPECTRUM_BANDS: usize = 16;
+/// Spectrogram columns kept (one per 32 ms frame)
+const SPECTROGRAM_HISTORY: usize = 240;
+/// Band levels drawn black at or below this
+const SPECTRUM_FLOOR_DB: f32 = -90.0;
+/// Band levels drawn red at or above this
+const SPECTRUM_CEIL_DB: f32 = -20.0;
+
 fn draw_ui(f: &mut Frame, state: &DashboardState) {
+    let banner = state
+        .failover_banner
+        .as_ref()
+        .filter(|(_, at)| at.elapsed() < Duration::from_secs(FAILOVER_BANNER_SECS));
+
+    let mut area = f.area();
+    if let Some((notice, _)) = banner {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(4), Constraint::Min(0)])
+            .split(area);
+        draw_failover_banner(f, rows[0], notice);
+        area = rows[1];
+    }
+
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -825,7 +1244,7 @@ fn draw_ui(f: &mut Frame, state: &DashboardState) {
             Constraint::Min(10),
             Constraint::Length(8),
         ])
-        .split(f.area());
+        .split(area);
 
     let top_chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -835,10 +1254,23 @@ fn draw_ui(f: &mut Frame, state: &DashboardState) {
     draw_audio_levels(f, top_chunks[0], state);
     draw_pipeline_flow(f, top_chunks[1], state);
 
+    let mut middle_area = main_chunks[1];
+    if state.current_tab == Tab::Audio {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(SPECTRUM_BANDS as u16 / 2 + 2),
+                Constraint::Min(0),
+            ])
+            .split(middle_area);
+        draw_spectrogram(f, rows[0], state);
+        middle_area = rows[1];
+    }
+
     let middle_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(main_chunks[1]);
+        .split(middle_area);
 
     match state.current_tab {
         Tab::Audio => {
@@ -849,6 +1281,7 @@ fn draw_ui(f: &mut Frame, state: &DashboardState) {
             draw_logs(f, middle_chunks[0], state);
             draw_status(f, middle_chunks[1], state);
         }
+        Tab::Transcripts => draw_transcripts(f, middle_area, state),
         Tab::Plugins => {
             draw_plugins(f, middle_chunks[0], state);
             draw_plugin_status(f, middle_chunks[1], state);
@@ -856,6 +1289,69 @@ fn draw_ui(f: &mut Frame, state: &DashboardState) {
     }
 
     draw_logs(f, main_chunks[2], state);
+
+    if let Some(picker) = &state.device_picker {
+        draw_device_picker(f, area, picker, state);
+    }
+}
+
+fn draw_device_picker(f: &mut Frame, area: Rect, picker: &DevicePicker, state: &DashboardState) {
+    let height = (picker.devices.len() as u16 + 2).min(area.height);
+    let width = area.width.saturating_sub(8).min(70);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+    let block = Block::default()
+        .title("Input Device - [Up/Down] Select  [Enter] Switch  [Esc] Cancel")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let lines: Vec<Line> = picker
+        .devices
+        .iter()
+        .enumerate()
+        .map(|(i, device)| {
+            let marker = if device.name == state.selected_device {
+                "● "
+            } else {
+                "  "
+            };
+            let default = if device.is_default { " (default)" } else { "" };
+            let style = if i == picker.selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(
+                format!("{}{}{}", marker, device.name, default),
+                style,
+            ))
+        })
+        .collect();
+    f.render_widget(Clear, popup);
+    f.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+fn draw_failover_banner(f: &mut Frame, area: Rect, notice: &FailoverNotice) {
+    let color = if notice.new_plugin.is_some() {
+        Color::Yellow
+    } else {
+        Color::Red
+    };
+    let block = Block::default()
+        .title("STT Failover")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color));
+    let text = vec![
+        Line::from(Span::styled(
+            notice.summary(),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(format!("Fix: {}", notice.hint)),
+    ];
+    f.render_widget(Paragraph::new(text).block(block), area);
 }
 
 fn draw_audio_levels(f: &mut Frame, area: Rect, state: &DashboardState) {
@@ -916,6 +1412,60 @@ fn draw_audio_levels(f: &mut Frame, area: Rect, state: &DashboardState) {
     f.render_widget(sparkline, chunks[2]);
 }
 
+/// Band levels over time, newest on the right. Each cell is a half block
+/// whose foreground is the upper band and background the lower one, so a
+/// row of text shows two bands.
+fn draw_spectrogram(f: &mut Frame, area: Rect, state: &DashboardState) {
+    let block = Block::default()
+        .title(format!(
+            "Spectrogram ({} Hz - 8 kHz, low at bottom)",
+            coldvox_audio_quality::spectrum::MIN_BAND_HZ
+        ))
+        .borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let width = inner.width as usize;
+    let columns: Vec<&Vec<f32>> = state
+        .spectrogram
+        .iter()
+        .skip(state.spectrogram.len().saturating_sub(width))
+        .collect();
+    let padding = " ".repeat(width - columns.len());
+
+    let lines: Vec<Line> = (0..SPECTRUM_BANDS / 2)
+        .map(|row| {
+            let upper = SPECTRUM_BANDS - 1 - 2 * row;
+            let mut spans = vec![Span::raw(padding.clone())];
+            spans.extend(columns.iter().map(|levels| {
+                let level = |band: usize| levels.get(band).copied().unwrap_or(f32::MIN);
+                Span::styled(
+                    "▀",
+                    Style::default()
+                        .fg(heat_color(level(upper)))
+                        .bg(heat_color(level(upper - 1))),
+                )
+            }));
+            Line::from(spans)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Colour for a band level between [`SPECTRUM_FLOOR_DB`] and [`SPECTRUM_CEIL_DB`]
+fn heat_color(db: f32) -> Color {
+    const HEAT: [Color; 6] = [
+        Color::Black,
+        Color::Blue,
+        Color::Cyan,
+        Color::Green,
+        Color::Yellow,
+        Color::Red,
+    ];
+    let t = ((db - SPECTRUM_FLOOR_DB) / (SPECTRUM_CEIL_DB - SPECTRUM_FLOOR_DB)).clamp(0.0, 1.0);
+    HEAT[(t * (HEAT.len() - 1) as f32).round() as usize]
+}
+
 fn draw_pipeline_flow(f: &mut Frame, area: Rect, state: &DashboardState) {
     let block = Block::default()
         .title("Pipeline Flow")
@@ -1016,8 +1566,11 @@ fn draw_status(f: &mut Frame, area: Rect, state: &DashboardState) {
     let inner = block.inner(area);
     f.render_widget(block, area);
 
+    let paused = state.is_running && state.app.as_ref().is_some_and(|app| app.is_paused());
     let status_color = if state.is_running {
-        if state.is_speaking {
+        if paused {
+            Color::Magenta
+        } else if state.is_speaking {
             Color::Yellow
         } else {
             Color::Green
@@ -1030,7 +1583,9 @@ fn draw_status(f: &mut Frame, area: Rect, state: &DashboardState) {
     status_text.push(Line::from(vec![
         Span::raw("Pipeline: "),
         Span::styled(
-            if state.is_running {
+            if paused {
+                "PAUSED"
+            } else if state.is_running {
                 "RUNNING"
             } else {
                 "STOPPED"
@@ -1071,23 +1626,136 @@ fn draw_status(f: &mut Frame, area: Rect, state: &DashboardState) {
         status_text.push(Line::from(""));
         status_text.push(Line::from("Last Transcript (final):"));
         let txt = state.last_transcript.as_deref().unwrap_or("None");
-        let trunc = if txt.len() > 80 {
-            format!("{}…", &txt[..80])
+        let trunc = if txt.chars().count() > 80 {
+            format!(
+                "{}… (full text: Transcripts tab)",
+                txt.chars().take(80).collect::<String>()
+            )
         } else {
             txt.to_string()
         };
         status_text.push(Line::from(trunc));
     }
+    if let Some(progress) = &state.paste_progress {
+        status_text.push(Line::from(""));
+        status_text.push(Line::from(format!("Paste: {}", progress)));
+    }
     status_text.push(Line::from(""));
     status_text.push(Line::from("Controls:"));
     status_text.push(Line::from(
-        "[S] Start  [A] Toggle VAD/PTT  [R] Reset  [Q] Quit",
+        "[S] Start  [A] Toggle VAD/PTT  [D] Device  [Space] Pause  [R] Reset  [B] Save black box  [X] Cancel paste  [Y] Accept held  [Q] Quit",
     ));
 
     let paragraph = Paragraph::new(status_text);
     f.render_widget(paragraph, inner);
 }
 
+fn draw_transcripts(f: &mut Frame, area: Rect, state: &DashboardState) {
+    let log = &state.transcripts;
+    let visible = log.visible();
+    let selected = log.selected_index(visible.len());
+
+    let search = if log.searching {
+        format!(" - search: {}_", log.search)
+    } else if !log.search.is_empty() {
+        format!(" - search: {}", log.search)
+    } else {
+        String::new()
+    };
+    let block = Block::default()
+        .title(format!(
+            "Transcripts ({}/{}){}",
+            visible.len(),
+            log.entries.len(),
+            search
+        ))
+        .borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(5)])
+        .split(inner);
+
+    // Scroll so the selection stays in view
+    let height = chunks[0].height as usize;
+    let start = selected.map_or(0, |s| (s + 1).saturating_sub(height));
+    let lines: Vec<Line> = visible
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(height)
+        .map(|(i, entry)| {
+            let mut style = if entry.is_final {
+                Style::default()
+            } else {
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::ITALIC)
+            };
+            if Some(i) == selected {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "[{}] #{} ",
+                        entry.received.format("%H:%M:%S"),
+                        entry.utterance_id
+                    ),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(entry.text.clone(), style),
+            ])
+        })
+        .collect();
+    let list = if lines.is_empty() {
+        Paragraph::new(if cfg!(any(feature = "moonshine", feature = "parakeet")) {
+            "No transcripts yet"
+        } else {
+            "STT is not enabled in this build"
+        })
+    } else {
+        Paragraph::new(lines)
+    };
+    f.render_widget(list, chunks[0]);
+
+    let detail = selected
+        .map(|i| visible[i].text.clone())
+        .unwrap_or_default();
+    let detail_block = Block::default()
+        .title("[Up/Down/PgUp/PgDn/Home/End] Select  [/] Search  [C] Copy")
+        .borders(Borders::TOP);
+    f.render_widget(
+        Paragraph::new(detail)
+            .block(detail_block)
+            .wrap(Wrap { trim: false }),
+        chunks[1],
+    );
+}
+
+/// Copy `text` to the clipboard, reporting the outcome in the log
+fn copy_to_clipboard(text: String, tx: mpsc::Sender<AppEvent>) {
+    tokio::spawn(async move {
+        let clipboard = ClipboardInjector::new(InjectionConfig::default());
+        let event = match clipboard
+            .write_clipboard(text.as_bytes(), "text/plain;charset=utf-8")
+            .await
+        {
+            Ok(()) => AppEvent::Log(
+                LogLevel::Success,
+                format!(
+                    "Copied {} characters to the clipboard",
+                    text.chars().count()
+                ),
+            ),
+            Err(e) => AppEvent::Log(LogLevel::Error, format!("Copy to clipboard failed: {}", e)),
+        };
+        let _ = tx.send(event).await;
+    });
+}
+
 fn draw_logs(f: &mut Frame, area: Rect, state: &DashboardState) {
     let block = Block::default().title("Logs").borders(Borders::ALL);
 
@@ -3,6 +3,7 @@
 //! This version uses clap's builder API instead of derive macros to avoid
 //! proc-macro DLL issues with Windows App Control.
 
+use coldvox_app::audio::dump::DumpFormat;
 use coldvox_app::runtime::{self as app_runtime, ActivationMode};
 use coldvox_vad::types::VadEvent;
 use crossterm::{
@@ -41,23 +42,6 @@ impl std::str::FromStr for CliActivationMode {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
-enum DumpFormat {
-    Pcm,
-    Wav,
-}
-
-impl std::str::FromStr for DumpFormat {
-    type Err = String;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "pcm" => Ok(DumpFormat::Pcm),
-            "wav" => Ok(DumpFormat::Wav),
-            _ => Err(format!("Unknown format: {}", s)),
-        }
-    }
-}
-
 struct Cli {
     device: Option<String>,
     activation_mode: CliActivationMode,
@@ -176,7 +160,9 @@ fn print_help() {
     println!("      --dump-audio [BOOL]            Enable/disable audio dump [default: true]");
     println!("      --no-dump-audio                Disable audio dump");
     println!("      --dump-dir <DIR>               Directory to save audio dumps");
-    println!("      --dump-format <FORMAT>         Dump format: pcm or wav [default: pcm]");
+    println!(
+        "      --dump-format <FORMAT>         Dump format: pcm, wav, opus or flac [default: pcm]"
+    );
     println!("  -h, --help                         Print help");
     println!("  -V, --version                      Print version");
 }
@@ -360,54 +346,52 @@ async fn run_app(
                             }
                             return Ok(());
                         }
-                        KeyCode::Char('s') | KeyCode::Char('S') => {
-                            if !state.is_running {
-                                state.logs.push((Instant::now(), LogLevel::Info, "Starting audio pipeline...".to_string()));
-
-                                let opts = app_runtime::AppRuntimeOptions {
-                                    device: cli.device.clone(),
-                                    activation_mode: match cli.activation_mode {
-                                        CliActivationMode::Vad => ActivationMode::Vad,
-                                        CliActivationMode::Hotkey => ActivationMode::Hotkey,
-                                    },
-                                    resampler_quality: match cli.resampler_quality.to_lowercase().as_str() {
-                                        "fast" => coldvox_audio::ResamplerQuality::Fast,
-                                        "quality" => coldvox_audio::ResamplerQuality::Quality,
-                                        _ => coldvox_audio::ResamplerQuality::Balanced,
-                                    },
-                                    stt_selection: None, // Disable STT for simplicity
-                                    enable_device_monitor: false,
-                                    capture_buffer_samples: 65_536,
-                                    ..Default::default()
-                                };
-
-                                match app_runtime::start(opts).await {
-                                    Ok(app) => {
-                                        // Subscribe to VAD events
-                                        let mut vad_rx = app.subscribe_vad();
-                                        let ui_tx = tx.clone();
-                                        tokio::spawn(async move {
-                                            while let Ok(ev) = vad_rx.recv().await {
-                                                let _ = ui_tx.send(AppEvent::Vad(ev)).await;
-                                            }
-                                        });
-
-                                        // Subscribe to audio
-                                        let mut audio_rx = app.subscribe_audio();
-                                        let ui_tx2 = tx.clone();
-                                        tokio::spawn(async move {
-                                            while audio_rx.recv().await.is_ok() {
-                                                let _ = ui_tx2.send(AppEvent::Log(LogLevel::Info, "Audio frame".to_string())).await;
-                                            }
-                                        });
-
-                                        app_handle = Some(app);
-                                        state.is_running = true;
-                                        state.logs.push((Instant::now(), LogLevel::Success, "Pipeline started!".to_string()));
-                                    }
-                                    Err(e) => {
-                                        state.logs.push((Instant::now(), LogLevel::Error, format!("Failed to start: {}", e)));
-                                    }
+                        KeyCode::Char('s') | KeyCode::Char('S') if !state.is_running => {
+                            state.logs.push((Instant::now(), LogLevel::Info, "Starting audio pipeline...".to_string()));
+
+                            let opts = app_runtime::AppRuntimeOptions {
+                                device: cli.device.clone(),
+                                activation_mode: match cli.activation_mode {
+                                    CliActivationMode::Vad => ActivationMode::Vad,
+                                    CliActivationMode::Hotkey => ActivationMode::Hotkey,
+                                },
+                                resampler_quality: match cli.resampler_quality.to_lowercase().as_str() {
+                                    "fast" => coldvox_audio::ResamplerQuality::Fast,
+                                    "quality" => coldvox_audio::ResamplerQuality::Quality,
+                                    _ => coldvox_audio::ResamplerQuality::Balanced,
+                                },
+                                stt_selection: None, // Disable STT for simplicity
+                                enable_device_monitor: false,
+                                capture_buffer_samples: 65_536,
+                                ..Default::default()
+                            };
+
+                            match app_runtime::start(opts).await {
+                                Ok(app) => {
+                                    // Subscribe to VAD events
+                                    let mut vad_rx = app.subscribe_vad();
+                                    let ui_tx = tx.clone();
+                                    tokio::spawn(async move {
+                                        while let Ok(ev) = vad_rx.recv().await {
+                                            let _ = ui_tx.send(AppEvent::Vad(ev)).await;
+                                        }
+                                    });
+
+                                    // Subscribe to audio
+                                    let mut audio_rx = app.subscribe_audio();
+                                    let ui_tx2 = tx.clone();
+                                    tokio::spawn(async move {
+                                        while audio_rx.recv().await.is_ok() {
+                                            let _ = ui_tx2.send(AppEvent::Log(LogLevel::Info, "Audio frame".to_string())).await;
+                                        }
+                                    });
+
+                                    app_handle = Some(app);
+                                    state.is_running = true;
+                                    state.logs.push((Instant::now(), LogLevel::Success, "Pipeline started!".to_string()));
+                                }
+                                Err(e) => {
+                                    state.logs.push((Instant::now(), LogLevel::Error, format!("Failed to start: {}", e)));
                                 }
                             }
                         }
@@ -0,0 +1,461 @@
+//! `coldvox config validate` and `coldvox config schema`.
+//!
+//! Validation reads a settings file the way startup does (built-in defaults,
+//! the file, then `COLDVOX__*` environment overrides) and reports every
+//! problem against the line it comes from: TOML syntax errors, keys no setting
+//! goes by, values of the wrong type, and values [`Settings::validate`] would
+//! reject or reset. The schema is derived from [`Settings`] with schemars, with
+//! every key optional and the built-in defaults filled in, so editors can
+//! complete and check the TOML.
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use config::ConfigError;
+use serde_json::{Map, Value};
+
+use crate::Settings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Startup fails
+    Error,
+    /// Startup ignores or resets the value
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub severity: Severity,
+    /// 1-based line in the file, when the problem can be traced to one
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// Outcome of validating one file
+#[derive(Debug)]
+pub struct Report {
+    pub path: PathBuf,
+    source: String,
+    pub problems: Vec<Problem>,
+}
+
+impl Report {
+    pub fn has_errors(&self) -> bool {
+        self.problems.iter().any(|p| p.severity == Severity::Error)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self.path.display();
+        for problem in &self.problems {
+            let severity = match problem.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            match problem.line {
+                Some(line) => {
+                    writeln!(f, "{}:{}: {}: {}", path, line, severity, problem.message)?;
+                    if let Some(text) = self.source.lines().nth(line - 1) {
+                        writeln!(f, "{:>5} | {}", line, text)?;
+                    }
+                }
+                None => writeln!(f, "{}: {}: {}", path, severity, problem.message)?,
+            }
+        }
+        let errors = self
+            .problems
+            .iter()
+            .filter(|p| p.severity == Severity::Error)
+            .count();
+        let warnings = self.problems.len() - errors;
+        if self.problems.is_empty() {
+            writeln!(f, "{}: OK", path)
+        } else {
+            writeln!(f, "{}: {} error(s), {} warning(s)", path, errors, warnings)
+        }
+    }
+}
+
+/// Highest-precedence config file startup would merge (None when it runs on
+/// defaults only)
+pub fn default_config_path() -> Option<PathBuf> {
+    Settings::config_layers().pop().map(|layer| layer.path)
+}
+
+/// Check `path` as startup would load it
+pub fn validate_file(path: &Path) -> io::Result<Report> {
+    let source = std::fs::read_to_string(path)?;
+    let mut problems = Vec::new();
+
+    let table = match toml::from_str::<toml::Table>(&source) {
+        Ok(table) => table,
+        Err(e) => {
+            problems.push(Problem {
+                severity: Severity::Error,
+                line: e.span().map(|span| line_of(&source, span.start)),
+                message: e.message().to_string(),
+            });
+            return Ok(Report {
+                path: path.to_path_buf(),
+                source,
+                problems,
+            });
+        }
+    };
+
+    let schema = schema_with_defaults(false);
+    check_keys(&table, &schema, &schema, "", &source, &mut problems);
+
+    let deserialized = Settings::build_config(Some(path.to_path_buf()))
+        .and_then(|config| config.try_deserialize::<Settings>());
+    match deserialized {
+        Ok(mut settings) => {
+            let (errors, warnings) = capture_warnings(|| settings.check());
+            for (severity, message) in errors
+                .into_iter()
+                .map(|m| (Severity::Error, m))
+                .chain(warnings.into_iter().map(|m| (Severity::Warning, m)))
+            {
+                problems.push(Problem {
+                    severity,
+                    line: mentioned_key_line(&source, &message),
+                    message,
+                });
+            }
+        }
+        Err(e) => {
+            let line = match &e {
+                ConfigError::Type { key: Some(key), .. } => locate_key(&source, key),
+                _ => mentioned_key_line(&source, &e.to_string()),
+            };
+            problems.push(Problem {
+                severity: Severity::Error,
+                line,
+                message: e.to_string(),
+            });
+        }
+    }
+
+    problems.sort_by_key(|p| {
+        (
+            p.line.unwrap_or(usize::MAX),
+            p.severity == Severity::Warning,
+        )
+    });
+    Ok(Report {
+        path: path.to_path_buf(),
+        source,
+        problems,
+    })
+}
+
+/// JSON Schema of the settings file. Every key is optional; with `defaults`,
+/// each carries its built-in default.
+pub fn schema_with_defaults(defaults: bool) -> Value {
+    let mut schema = serde_json::to_value(schemars::schema_for!(Settings)).unwrap_or_default();
+    let default_values = if defaults {
+        Settings::defaults()
+            .and_then(|builder| builder.build())
+            .and_then(|config| config.try_deserialize::<Value>())
+            .unwrap_or_default()
+    } else {
+        Value::Null
+    };
+    let root = schema.clone();
+    prepare(&mut schema, &root, &default_values);
+    if let Some(defs) = schema.get_mut("$defs").and_then(Value::as_object_mut) {
+        for def in defs.values_mut() {
+            if let Some(obj) = def.as_object_mut() {
+                obj.remove("required");
+            }
+        }
+    }
+    schema
+}
+
+/// Drop `required` and fill in defaults below `schema`, following `$ref`s
+/// into `$defs`
+fn prepare(schema: &mut Value, root: &Value, defaults: &Value) {
+    let Some(obj) = schema.as_object_mut() else {
+        return;
+    };
+    obj.remove("required");
+    let Some(properties) = obj.get_mut("properties").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for (key, property) in properties.iter_mut() {
+        let Some(default) = defaults.get(key) else {
+            continue;
+        };
+        // Nested settings tables are inlined so each key can carry its default
+        let nested = ref_name(property)
+            .and_then(|name| root.pointer(&format!("/$defs/{}", name)))
+            .filter(|def| def.get("properties").is_some())
+            .cloned();
+        let Some(obj) = property.as_object_mut() else {
+            continue;
+        };
+        match nested {
+            Some(mut def) => {
+                prepare(&mut def, root, default);
+                obj.remove("$ref");
+                obj.insert("type".to_string(), Value::String("object".to_string()));
+                if let Some(props) = def.get("properties").cloned() {
+                    obj.insert("properties".to_string(), props);
+                }
+            }
+            None => {
+                obj.insert("default".to_string(), default.clone());
+            }
+        }
+    }
+}
+
+fn ref_name(property: &Value) -> Option<&str> {
+    property
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| r.strip_prefix("#/$defs/"))
+}
+
+/// Flag keys the schema has no property for
+fn check_keys(
+    table: &toml::Table,
+    schema: &Value,
+    root: &Value,
+    prefix: &str,
+    source: &str,
+    problems: &mut Vec<Problem>,
+) {
+    let empty = Map::new();
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        let Some(property) = properties.get(key) else {
+            let mut message = format!("unknown key `{}`", path);
+            if let Some(suggestion) = closest(key, properties.keys()) {
+                message.push_str(&format!(" (did you mean `{}`?)", suggestion));
+            }
+            problems.push(Problem {
+                severity: Severity::Warning,
+                line: locate_key(source, &path),
+                message,
+            });
+            continue;
+        };
+        // Only settings tables are checked further; maps take any key
+        if let (Some(def_name), toml::Value::Table(nested)) = (ref_name(property), value) {
+            if let Some(def) = root.pointer(&format!("/$defs/{}", def_name)) {
+                check_keys(nested, def, root, &path, source, problems);
+            }
+        }
+    }
+}
+
+/// Known key within edit distance 2 of `key`
+fn closest<'a>(key: &str, known: impl Iterator<Item = &'a String>) -> Option<&'a str> {
+    known
+        .map(|k| (edit_distance(key, k), k))
+        .filter(|(d, _)| *d <= 2)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, k)| k.as_str())
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = (prev + usize::from(ca != *cb)).min(row[j] + 1).min(cur + 1);
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+fn line_of(source: &str, offset: usize) -> usize {
+    source[..offset.min(source.len())].matches('\n').count() + 1
+}
+
+/// Line assigning `dotted` (e.g. `audio.channel`), following `[table]`
+/// headers
+pub fn locate_key(source: &str, dotted: &str) -> Option<usize> {
+    let mut table = String::new();
+    for (n, line) in source.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if let Some(header) = line.strip_prefix('[') {
+            table = header
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .trim()
+                .to_string();
+            continue;
+        }
+        let Some((key, _)) = line.split_once('=') else {
+            continue;
+        };
+        let key: String = key
+            .split('.')
+            .map(|part| part.trim().trim_matches('"'))
+            .collect::<Vec<_>>()
+            .join(".");
+        let path = if table.is_empty() {
+            key
+        } else {
+            format!("{}.{}", table, key)
+        };
+        if path == dotted {
+            return Some(n + 1);
+        }
+    }
+    None
+}
+
+/// Line of the first key named in `message`, whether written out in full
+/// (`audio.channel`) or by its last part (`max_total_latency_ms`)
+fn mentioned_key_line(source: &str, message: &str) -> Option<usize> {
+    let tokens = message
+        .split(|c: char| c.is_whitespace() || "'`\"(),:".contains(c))
+        .map(|t| t.trim_end_matches('.'))
+        .filter(|t| t.len() > 2);
+    for token in tokens {
+        if let Some(line) = locate_key(source, token) {
+            return Some(line);
+        }
+        if token.contains('_') {
+            let found = source.lines().position(|l| {
+                l.split('#')
+                    .next()
+                    .and_then(|l| l.split_once('='))
+                    .is_some_and(|(k, _)| k.trim() == token)
+            });
+            if let Some(n) = found {
+                return Some(n + 1);
+            }
+        }
+    }
+    None
+}
+
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for Captured {
+    type Writer = Captured;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+/// Run `f`, returning its result and the warnings it logged
+fn capture_warnings<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    let captured = Captured::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(captured.clone())
+        .with_max_level(tracing::Level::WARN)
+        .without_time()
+        .with_level(false)
+        .with_target(false)
+        .with_ansi(false)
+        .finish();
+    let result = tracing::subscriber::with_default(subscriber, f);
+    let output = String::from_utf8_lossy(&captured.0.lock().unwrap()).into_owned();
+    let warnings = output
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    (result, warnings)
+}
+
+/// Print the schema as pretty JSON
+pub fn write_schema(out: &mut impl io::Write) -> io::Result<()> {
+    let schema = schema_with_defaults(true);
+    serde_json::to_writer_pretty(&mut *out, &schema)?;
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report_for(toml: &str) -> Report {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, toml).unwrap();
+        validate_file(&path).unwrap()
+    }
+
+    #[test]
+    fn reports_problems_with_their_lines() {
+        let report = report_for(
+            "activation_mode = \"vad\"\n\n[audio]\nchanel = \"mix\"\nidle_suspend_minutes = \"soon\"\n",
+        );
+        assert!(report.has_errors());
+        let unknown = report
+            .problems
+            .iter()
+            .find(|p| p.message.starts_with("unknown key"))
+            .unwrap();
+        assert_eq!(unknown.line, Some(4));
+        assert!(unknown.message.contains("did you mean `channel`?"));
+        let type_error = report
+            .problems
+            .iter()
+            .find(|p| p.severity == Severity::Error)
+            .unwrap();
+        assert_eq!(type_error.line, Some(5));
+        assert!(report
+            .to_string()
+            .contains("    5 | idle_suspend_minutes = \"soon\""));
+
+        let syntax = report_for("[audio\n");
+        assert_eq!(syntax.problems.len(), 1);
+        assert_eq!(syntax.problems[0].line, Some(1));
+    }
+
+    #[test]
+    fn schema_is_optional_with_defaults() {
+        let schema = schema_with_defaults(true);
+        assert!(schema.get("required").is_none());
+        assert_eq!(
+            schema.pointer("/properties/activation_mode/default"),
+            Some(&Value::String("vad".to_string()))
+        );
+        assert_eq!(
+            schema.pointer("/properties/audio/properties/channel/default"),
+            Some(&Value::String("mix".to_string()))
+        );
+        assert_eq!(edit_distance("chanel", "channel"), 1);
+        assert_eq!(
+            locate_key("[stt]\nremote.url = 1\n", "stt.remote.url"),
+            Some(2)
+        );
+    }
+}
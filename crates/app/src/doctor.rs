@@ -0,0 +1,261 @@
+//! `coldvox doctor`: whether this machine can run the configured pipeline.
+//!
+//! Injection prerequisites come from
+//! [`prerequisites`](crate::text_injection::prerequisites); this module adds
+//! the speech-to-text side (compiled-in plugins, their availability and model
+//! path overrides) and renders both as a pass/fail list with fix hints, along
+//! with the injection backends' capability report.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use coldvox_stt::plugin::PluginInfo;
+
+use crate::text_injection::prerequisites::{CheckStatus, PrerequisiteCheck};
+use crate::text_injection::types::{BackendCapability, InjectionMode};
+use crate::SttSettings;
+
+/// Plugins that run without producing real transcripts
+const PLACEHOLDER_PLUGINS: [&str; 2] = ["mock", "noop"];
+
+/// Model path overrides read by the local plugins
+const MODEL_PATH_VARS: [(&str, &str); 2] = [
+    ("parakeet", "PARAKEET_MODEL_PATH"),
+    ("moonshine", "MOONSHINE_MODEL_PATH"),
+];
+
+/// Checks for the configured STT plugins against what this build registered
+pub fn stt_checks(stt: &SttSettings, plugins: &[PluginInfo]) -> Vec<PrerequisiteCheck> {
+    let mut checks = Vec::new();
+
+    let usable: Vec<&str> = plugins
+        .iter()
+        .filter(|p| p.is_available && !PLACEHOLDER_PLUGINS.contains(&p.id.as_str()))
+        .map(|p| p.id.as_str())
+        .collect();
+    checks.push(if usable.is_empty() {
+        PrerequisiteCheck::fail(
+            "STT plugins",
+            "no speech-to-text plugin is usable",
+            "build with a plugin feature (e.g. `--features moonshine`) and install its model, \
+             or configure [stt.remote]",
+        )
+    } else {
+        PrerequisiteCheck::pass("STT plugins", format!("usable: {}", usable.join(", ")))
+    });
+
+    if let Some(preferred) = stt.preferred.as_deref() {
+        checks.push(plugin_check("preferred plugin", preferred, plugins, true));
+    }
+    for fallback in &stt.fallbacks {
+        checks.push(plugin_check("fallback plugin", fallback, plugins, false));
+    }
+
+    for (plugin, var) in MODEL_PATH_VARS {
+        let Some(value) = std::env::var_os(var) else {
+            continue;
+        };
+        let path = Path::new(&value);
+        checks.push(if path.exists() {
+            PrerequisiteCheck::pass("model path", format!("{}={}", var, path.display()))
+        } else {
+            PrerequisiteCheck::fail(
+                "model path",
+                format!("{}={} does not exist", var, path.display()),
+                format!(
+                    "point {} at the {} model directory or unset it",
+                    var, plugin
+                ),
+            )
+        });
+    }
+
+    checks
+}
+
+fn plugin_check(
+    name: &'static str,
+    id: &str,
+    plugins: &[PluginInfo],
+    required: bool,
+) -> PrerequisiteCheck {
+    let missing = |detail: String, hint: String| {
+        if required {
+            PrerequisiteCheck::fail(name, detail, hint)
+        } else {
+            PrerequisiteCheck::warn(name, detail, hint)
+        }
+    };
+    match plugins.iter().find(|p| p.id == id) {
+        Some(p) if p.is_available => PrerequisiteCheck::pass(name, format!("{} available", id)),
+        Some(_) => missing(
+            format!("{} is compiled in but not available", id),
+            format!(
+                "install its model or runtime (see `coldvox benchmark`); for local models check \
+                 {}_MODEL_PATH",
+                id.to_uppercase().replace('-', "_")
+            ),
+        ),
+        None => missing(
+            format!("{} is not compiled in", id),
+            format!("rebuild with `--features {}`", id),
+        ),
+    }
+}
+
+/// One line per check, with its hint underneath
+pub fn render(section: &str, checks: &[PrerequisiteCheck]) -> String {
+    let mut out = format!("{}:\n", section);
+    for check in checks {
+        let status = match check.status {
+            CheckStatus::Pass => "ok",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Fail => "FAIL",
+            CheckStatus::Skip => "skip",
+        };
+        let _ = writeln!(out, "  [{:<4}] {:<22} {}", status, check.name, check.detail);
+        if let Some(hint) = &check.hint {
+            let _ = writeln!(out, "         fix: {}", hint);
+        }
+    }
+    out
+}
+
+/// Modes, then measured performance or why the backend is unavailable
+pub fn backend_summary(backend: &BackendCapability) -> String {
+    let modes: Vec<&str> = backend
+        .modes
+        .iter()
+        .map(|mode| match mode {
+            InjectionMode::Keystroke => "keystroke",
+            InjectionMode::Paste => "paste",
+        })
+        .collect();
+    let mut parts = vec![if modes.is_empty() {
+        "assist only".to_string()
+    } else {
+        modes.join("+")
+    }];
+    if let Some(reason) = &backend.unavailable_reason {
+        parts.push(reason.to_string());
+    }
+    let status = &backend.status;
+    if status.attempts > 0 {
+        let mut session = format!("session {}/{} ok", status.successes, status.attempts);
+        if let Some(avg) = backend.session_avg_ms {
+            let _ = write!(session, ", {} ms avg", avg);
+        }
+        parts.push(session);
+    }
+    if let Some(history) = &backend.history {
+        if let Some(rate) = history.success_rate() {
+            let mut recent = format!("7d {:.0}% ok", rate * 100.0);
+            match history.latency_percentile_ms(0.5) {
+                Some(u64::MAX) => recent.push_str(", p50 >5 s"),
+                Some(p50) => {
+                    let _ = write!(recent, ", p50 {} ms", p50);
+                }
+                None => {}
+            }
+            parts.push(recent);
+        }
+    }
+    parts.join(" · ")
+}
+
+/// The capability report as a table, with each backend's descriptor
+pub fn render_backends(report: &[BackendCapability]) -> String {
+    let mut out = String::from("Text injection backends:\n");
+    for backend in report {
+        let state = if backend.status.available {
+            "available"
+        } else if backend.status.cooling_down {
+            "cooling down"
+        } else {
+            "unavailable"
+        };
+        let _ = writeln!(
+            out,
+            "  {:<24} {:<12} {}",
+            backend.status.method.name(),
+            state,
+            backend_summary(backend)
+        );
+        for (key, value) in &backend.status.capabilities {
+            let _ = writeln!(out, "      {}: {}", key, value);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin(id: &str, is_available: bool) -> PluginInfo {
+        PluginInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            requires_network: false,
+            is_local: true,
+            is_available,
+            supported_languages: Vec::new(),
+            memory_usage_mb: None,
+        }
+    }
+
+    #[test]
+    fn reports_missing_and_unavailable_plugins() {
+        let stt = SttSettings {
+            preferred: Some("parakeet".to_string()),
+            fallbacks: vec!["whisper".to_string()],
+            ..Default::default()
+        };
+        let plugins = [plugin("mock", true), plugin("parakeet", false)];
+        let checks = stt_checks(&stt, &plugins);
+
+        assert_eq!(checks[0].status, CheckStatus::Fail);
+        assert_eq!(checks[1].status, CheckStatus::Fail);
+        assert!(checks[1].detail.contains("not available"));
+        assert_eq!(checks[2].status, CheckStatus::Warn);
+        assert!(checks[2]
+            .hint
+            .as_deref()
+            .unwrap()
+            .contains("--features whisper"));
+
+        let text = render("Speech to text", &checks);
+        assert!(text.contains("[FAIL] preferred plugin"));
+        assert!(text.contains("fix: rebuild with `--features whisper`"));
+    }
+
+    #[test]
+    fn summarizes_backend_capabilities() {
+        use crate::text_injection::types::{BackendStatus, InjectionMethod, UnavailableReason};
+
+        let backend = BackendCapability {
+            status: BackendStatus {
+                method: InjectionMethod::XdoToolType,
+                name: "xdotool",
+                registered: false,
+                available: false,
+                cooling_down: false,
+                attempts: 4,
+                successes: 3,
+                failures: 1,
+                last_error: None,
+                capabilities: Vec::new(),
+            },
+            unavailable_reason: Some(UnavailableReason::Disabled("injection.allow_xdotool")),
+            modes: InjectionMethod::XdoToolType.supported_modes(),
+            session_avg_ms: Some(42),
+            history: None,
+        };
+        assert_eq!(
+            backend_summary(&backend),
+            "keystroke · disabled (set injection.allow_xdotool = true) · session 3/4 ok, 42 ms avg"
+        );
+        assert!(render_backends(&[backend]).contains("XdoToolType              unavailable"));
+    }
+}
@@ -0,0 +1,357 @@
+//! `coldvox export`: one day of the transcript store as a Markdown or HTML
+//! journal.
+//!
+//! Utterances are grouped by the application that had focus when they were
+//! transcribed (sections in order of first use), listed by time, annotated
+//! with their confidence and linked to their audio clip when the session was
+//! recorded with audio.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, NaiveDate, NaiveTime};
+use serde::Deserialize;
+
+/// Section for utterances recorded without an application
+const UNKNOWN_APP: &str = "Unknown application";
+/// Confidence below this is flagged
+const LOW_CONFIDENCE: f32 = 0.6;
+
+/// Journal output format
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[default]
+    Markdown,
+    Html,
+}
+
+/// One dictated utterance
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    /// Local time the utterance started
+    pub time: NaiveTime,
+    /// Focused application, if it was recorded
+    pub app: Option<String>,
+    pub text: String,
+    /// Utterance confidence, else the mean word confidence
+    pub confidence: Option<f32>,
+    /// Audio clip of the utterance
+    pub audio: Option<PathBuf>,
+    /// Audio source ("mic", "loopback", ...)
+    pub source: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    utterances: Vec<ManifestUtterance>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestUtterance {
+    started_at: String,
+    text: String,
+    #[serde(default)]
+    confidence: Option<f32>,
+    #[serde(default)]
+    audio_path: Option<PathBuf>,
+    #[serde(default)]
+    words: Option<Vec<ManifestWord>>,
+    #[serde(default)]
+    app_id: Option<String>,
+    #[serde(default)]
+    source: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestWord {
+    confidence: f32,
+}
+
+/// Entries for `date` from a transcript store laid out as
+/// `<dir>/<date>/<session>/session.json`, ordered by time. Unreadable
+/// manifests and utterances with bad timestamps are skipped.
+pub fn load_day(dir: &Path, date: NaiveDate) -> Vec<JournalEntry> {
+    let day_dir = dir.join(date.format("%Y-%m-%d").to_string());
+    let Ok(sessions) = fs::read_dir(&day_dir) else {
+        return Vec::new();
+    };
+
+    let mut entries = Vec::new();
+    for session in sessions.flatten() {
+        let session_dir = session.path();
+        let Ok(raw) = fs::read_to_string(session_dir.join("session.json")) else {
+            continue;
+        };
+        let Ok(manifest) = serde_json::from_str::<Manifest>(&raw) else {
+            continue;
+        };
+        for u in manifest.utterances {
+            let Ok(started) = DateTime::parse_from_rfc3339(&u.started_at) else {
+                continue;
+            };
+            let confidence = u.confidence.or_else(|| {
+                let words = u.words.as_deref().filter(|w| !w.is_empty())?;
+                Some(words.iter().map(|w| w.confidence).sum::<f32>() / words.len() as f32)
+            });
+            entries.push(JournalEntry {
+                time: started.with_timezone(&Local).time(),
+                app: u.app_id.filter(|a| !a.is_empty()),
+                text: u.text,
+                confidence,
+                audio: u.audio_path.map(|p| session_dir.join(p)),
+                source: u
+                    .source
+                    .unwrap_or_else(|| coldvox_stt::SourceId::MIC.to_string()),
+            });
+        }
+    }
+    entries.sort_by_key(|e| e.time);
+    entries
+}
+
+/// Render `entries` (in time order) as a journal for `date`
+pub fn render(date: NaiveDate, entries: &[JournalEntry], format: ExportFormat) -> String {
+    let sections = group_by_app(entries);
+    match format {
+        ExportFormat::Markdown => render_markdown(date, entries.len(), &sections),
+        ExportFormat::Html => render_html(date, entries.len(), &sections),
+    }
+}
+
+/// Sections in order of each application's first utterance
+fn group_by_app(entries: &[JournalEntry]) -> Vec<(&str, Vec<&JournalEntry>)> {
+    let mut sections: Vec<(&str, Vec<&JournalEntry>)> = Vec::new();
+    for entry in entries {
+        let app = entry.app.as_deref().unwrap_or(UNKNOWN_APP);
+        match sections.iter_mut().find(|(name, _)| *name == app) {
+            Some((_, list)) => list.push(entry),
+            None => sections.push((app, vec![entry])),
+        }
+    }
+    sections
+}
+
+fn summary(utterances: usize, apps: usize) -> String {
+    format!(
+        "{} utterance{} in {} application{}.",
+        utterances,
+        if utterances == 1 { "" } else { "s" },
+        apps,
+        if apps == 1 { "" } else { "s" }
+    )
+}
+
+fn confidence_note(confidence: Option<f32>) -> Option<String> {
+    let c = confidence?;
+    let pct = (c * 100.0).round() as u32;
+    Some(if c < LOW_CONFIDENCE {
+        format!("low confidence {}%", pct)
+    } else {
+        format!("confidence {}%", pct)
+    })
+}
+
+fn render_markdown(
+    date: NaiveDate,
+    total: usize,
+    sections: &[(&str, Vec<&JournalEntry>)],
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Dictation journal — {}\n", date);
+    if sections.is_empty() {
+        let _ = writeln!(out, "No dictation recorded.");
+        return out;
+    }
+    let _ = writeln!(out, "{}", summary(total, sections.len()));
+    for (app, entries) in sections {
+        let _ = writeln!(out, "\n## {}\n", escape_markdown(app));
+        for e in entries {
+            let _ = write!(out, "- **{}**", e.time.format("%H:%M:%S"));
+            if e.source != coldvox_stt::SourceId::MIC.as_str() {
+                let _ = write!(out, " [{}]", escape_markdown(&e.source));
+            }
+            let _ = write!(out, " {}", escape_markdown(&single_line(&e.text)));
+            if let Some(note) = confidence_note(e.confidence) {
+                let _ = write!(out, " _({})_", note);
+            }
+            if let Some(audio) = &e.audio {
+                let _ = write!(out, " [audio](<{}>)", audio.display());
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn render_html(date: NaiveDate, total: usize, sections: &[(&str, Vec<&JournalEntry>)]) -> String {
+    let mut out = String::new();
+    let title = format!("Dictation journal — {}", date);
+    let _ = writeln!(out, "<!DOCTYPE html>\n<html>\n<head>");
+    let _ = writeln!(out, "<meta charset=\"utf-8\">\n<title>{}</title>", title);
+    let _ = writeln!(
+        out,
+        "<style>.confidence{{color:#666}}.low{{color:#b00}}time{{font-family:monospace}}</style>"
+    );
+    let _ = writeln!(out, "</head>\n<body>\n<h1>{}</h1>", title);
+    if sections.is_empty() {
+        let _ = writeln!(out, "<p>No dictation recorded.</p>");
+    } else {
+        let _ = writeln!(out, "<p>{}</p>", summary(total, sections.len()));
+    }
+    for (app, entries) in sections {
+        let _ = writeln!(out, "<h2>{}</h2>\n<ul>", escape_html(app));
+        for e in entries {
+            let _ = write!(out, "<li><time>{}</time>", e.time.format("%H:%M:%S"));
+            if e.source != coldvox_stt::SourceId::MIC.as_str() {
+                let _ = write!(
+                    out,
+                    " <span class=\"source\">[{}]</span>",
+                    escape_html(&e.source)
+                );
+            }
+            let _ = write!(out, " {}", escape_html(&e.text));
+            if let Some(note) = confidence_note(e.confidence) {
+                let class = if e.confidence.is_some_and(|c| c < LOW_CONFIDENCE) {
+                    "confidence low"
+                } else {
+                    "confidence"
+                };
+                let _ = write!(out, " <span class=\"{}\">({})</span>", class, note);
+            }
+            if let Some(audio) = &e.audio {
+                let _ = write!(
+                    out,
+                    " <a href=\"file://{}\">audio</a>",
+                    escape_html(&audio.display().to_string())
+                );
+            }
+            let _ = writeln!(out, "</li>");
+        }
+        let _ = writeln!(out, "</ul>");
+    }
+    let _ = writeln!(out, "</body>\n</html>");
+    out
+}
+
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(time: &str, app: Option<&str>, text: &str, confidence: Option<f32>) -> JournalEntry {
+        JournalEntry {
+            time: NaiveTime::parse_from_str(time, "%H:%M:%S").unwrap(),
+            app: app.map(str::to_string),
+            text: text.to_string(),
+            confidence,
+            audio: None,
+            source: "mic".to_string(),
+        }
+    }
+
+    #[test]
+    fn markdown_groups_by_app_in_order_of_first_use() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let mut entries = vec![
+            entry("09:00:00", Some("code"), "fix the *parser*", Some(0.92)),
+            entry("09:05:00", Some("firefox"), "search for crates", Some(0.41)),
+            entry("09:10:00", Some("code"), "add a test", None),
+            entry("09:20:00", None, "note to self", None),
+        ];
+        entries[2].audio = Some(PathBuf::from(
+            "/t/2026-10-16/091000/audio/utterance_000003.wav",
+        ));
+
+        let md = render(date, &entries, ExportFormat::Markdown);
+        assert!(md.starts_with("# Dictation journal — 2026-10-16"));
+        assert!(md.contains("4 utterances in 3 applications."));
+        let code = md.find("## code").unwrap();
+        let firefox = md.find("## firefox").unwrap();
+        let unknown = md.find("## Unknown application").unwrap();
+        assert!(code < firefox && firefox < unknown);
+        assert!(md.contains("- **09:00:00** fix the \\*parser\\* _(confidence 92%)_"));
+        assert!(md.contains("_(low confidence 41%)_"));
+        assert!(md.contains("[audio](</t/2026-10-16/091000/audio/utterance_000003.wav>)"));
+        // Both code utterances sit in the code section
+        assert!(md[code..firefox].contains("add a test"));
+    }
+
+    #[test]
+    fn html_escapes_text_and_flags_low_confidence() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let entries = vec![entry("10:00:00", Some("term"), "a < b && c", Some(0.3))];
+        let html = render(date, &entries, ExportFormat::Html);
+        assert!(html.contains("<h2>term</h2>"));
+        assert!(html.contains("a &lt; b &amp;&amp; c"));
+        assert!(html.contains("class=\"confidence low\""));
+
+        let empty = render(date, &[], ExportFormat::Html);
+        assert!(empty.contains("No dictation recorded."));
+    }
+
+    #[test]
+    fn loads_a_day_from_the_transcript_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let session = dir.path().join("2026-10-16").join("090000");
+        fs::create_dir_all(&session).unwrap();
+        fs::write(
+            session.join("session.json"),
+            r#"{"started_at":"2026-10-16T09:00:00+00:00","utterances":[
+                {"utterance_id":2,"started_at":"2026-10-16T09:01:00+00:00","ended_at":"2026-10-16T09:01:02+00:00",
+                 "text":"second","confidence":null,"audio_path":"audio/utterance_000002.wav",
+                 "words":[{"word":"second","start_ms":0,"end_ms":500,"confidence":0.5}],"app_id":"code"},
+                {"utterance_id":1,"started_at":"2026-10-16T09:00:30+00:00","ended_at":"2026-10-16T09:00:31+00:00",
+                 "text":"first","confidence":0.9,"audio_path":null,"words":null,"source":"loopback"}
+            ]}"#,
+        )
+        .unwrap();
+        let other_day = dir.path().join("2026-10-15").join("120000");
+        fs::create_dir_all(&other_day).unwrap();
+        fs::write(other_day.join("session.json"), r#"{"utterances":[]}"#).unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let entries = load_day(dir.path(), date);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].text, "first");
+        assert_eq!(entries[0].source, "loopback");
+        assert_eq!(entries[0].app, None);
+        assert_eq!(entries[1].app.as_deref(), Some("code"));
+        assert_eq!(entries[1].confidence, Some(0.5));
+        assert_eq!(
+            entries[1].audio,
+            Some(session.join("audio/utterance_000002.wav"))
+        );
+        assert!(load_day(dir.path(), date.succ_opt().unwrap()).is_empty());
+    }
+}
@@ -0,0 +1,178 @@
+//! Transcription-only mode for sessions without a display server.
+//!
+//! In a TTY or over SSH there is nothing to inject into, and starting the
+//! injection backends anyway fails on every utterance. With `--no-injection`,
+//! or when [`BackendDetector::is_headless`] finds no display, the pipeline
+//! runs without injection and final transcripts go to stdout, a file, or the
+//! local clipboard through the terminal (OSC 52) instead.
+//!
+//! [`BackendDetector::is_headless`]: crate::text_injection::backend::BackendDetector::is_headless
+
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use coldvox_stt::TranscriptionEvent;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc;
+
+use crate::text_injection::osc52_injector::osc52;
+
+/// Where finals go when nothing is injected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeadlessOutput {
+    /// One line per final
+    Stdout,
+    /// Appended to this file, one line per final
+    File(PathBuf),
+    /// Printed, and copied to the clipboard of the terminal running the
+    /// session (works over SSH in terminals that allow OSC 52)
+    Osc52,
+}
+
+impl HeadlessOutput {
+    /// From `injection.headless_output` (auto, stdout, file or osc52) and
+    /// `injection.headless_output_file`
+    pub fn from_settings(output: &str, file: &str) -> Result<Self, String> {
+        match output.to_lowercase().as_str() {
+            "auto" => Ok(Self::auto()),
+            "stdout" => Ok(Self::Stdout),
+            "osc52" => Ok(Self::Osc52),
+            "file" if file.trim().is_empty() => {
+                Err("headless_output = \"file\" needs headless_output_file".to_string())
+            }
+            "file" => Ok(Self::File(PathBuf::from(file.trim()))),
+            other => Err(format!(
+                "unknown headless_output '{}' (expected auto, stdout, file or osc52)",
+                other
+            )),
+        }
+    }
+
+    /// OSC 52 in an SSH session whose stdout is the terminal, stdout otherwise
+    pub fn auto() -> Self {
+        let over_ssh =
+            std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some();
+        if over_ssh && std::io::stdout().is_terminal() {
+            Self::Osc52
+        } else {
+            Self::Stdout
+        }
+    }
+}
+
+impl std::fmt::Display for HeadlessOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Stdout => f.write_str("stdout"),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Osc52 => f.write_str("stdout and the terminal clipboard (OSC 52)"),
+        }
+    }
+}
+
+/// Send finals to `output` until the pipeline closes the channel
+pub async fn write_finals(
+    events: mpsc::Receiver<TranscriptionEvent>,
+    output: &HeadlessOutput,
+) -> std::io::Result<()> {
+    match output {
+        HeadlessOutput::Stdout => write_lines(events, tokio::io::stdout(), None).await,
+        HeadlessOutput::Osc52 => {
+            let tmux = std::env::var_os("TMUX").is_some();
+            write_lines(events, tokio::io::stdout(), Some(tmux)).await
+        }
+        HeadlessOutput::File(path) => {
+            if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+                tokio::fs::create_dir_all(dir).await?;
+            }
+            let file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .await?;
+            write_lines(events, file, None).await
+        }
+    }
+}
+
+/// One line per non-empty final; `osc52_tmux` is Some (whether inside tmux)
+/// to also set the terminal clipboard
+async fn write_lines<W: AsyncWrite + Unpin>(
+    mut events: mpsc::Receiver<TranscriptionEvent>,
+    mut out: W,
+    osc52_tmux: Option<bool>,
+) -> std::io::Result<()> {
+    while let Some(event) = events.recv().await {
+        match event {
+            TranscriptionEvent::Final { text, .. } => {
+                let text = text.trim();
+                if text.is_empty() {
+                    continue;
+                }
+                let mut line = String::new();
+                if let Some(tmux) = osc52_tmux {
+                    line.push_str(&osc52(text, tmux));
+                }
+                line.push_str(text);
+                line.push('\n');
+                out.write_all(line.as_bytes()).await?;
+                out.flush().await?;
+            }
+            TranscriptionEvent::Error { code, message } => {
+                tracing::warn!("Transcription error {}: {}", code, message);
+            }
+            TranscriptionEvent::Partial { .. } => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coldvox_stt::SourceId;
+
+    #[test]
+    fn parses_output_settings() {
+        assert_eq!(
+            HeadlessOutput::from_settings("OSC52", ""),
+            Ok(HeadlessOutput::Osc52)
+        );
+        assert_eq!(
+            HeadlessOutput::from_settings("file", " out.txt "),
+            Ok(HeadlessOutput::File(PathBuf::from("out.txt")))
+        );
+        assert!(HeadlessOutput::from_settings("file", "").is_err());
+        assert!(HeadlessOutput::from_settings("printer", "").is_err());
+    }
+
+    #[tokio::test]
+    async fn writes_only_non_empty_finals() {
+        let (tx, rx) = mpsc::channel(8);
+        let final_event = |text: &str| TranscriptionEvent::Final {
+            utterance_id: 1,
+            text: text.to_string(),
+            words: None,
+            source: SourceId::default(),
+        };
+        tx.send(TranscriptionEvent::Partial {
+            utterance_id: 1,
+            text: "hel".to_string(),
+            t0: None,
+            t1: None,
+            source: SourceId::default(),
+        })
+        .await
+        .unwrap();
+        tx.send(final_event(" hello ")).await.unwrap();
+        tx.send(final_event("  ")).await.unwrap();
+        drop(tx);
+
+        let mut out = Vec::new();
+        write_lines(rx, &mut out, Some(false)).await.unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b]52;c;aGVsbG8=\x07hello\n"
+        );
+    }
+}
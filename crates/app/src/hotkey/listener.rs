@@ -1,56 +0,0 @@
-use coldvox_vad::types::VadEvent;
-use tokio::sync::mpsc::Sender;
-
-/// KDE KGlobalAccel hotkey listener implementation
-///
-/// This provides the actual KDE KGlobalAccel-based hotkey listener
-/// for push-to-talk functionality in ColdVox.
-pub fn spawn_hotkey_listener(event_tx: Sender<VadEvent>) -> tokio::task::JoinHandle<()> {
-    tokio::spawn(async move {
-        // Import the KDE KGlobalAccel backend
-        #[cfg(kde_globalaccel)]
-        {
-            use crate::hotkey::backend;
-
-            // Detect and initialize the best backend (KDE preferred)
-            let mut backend = backend::detect_best_backend().await;
-
-            // Initialize the backend
-            let backend_name = backend.name().to_string();
-            if let Err(e) = backend.initialize().await {
-                tracing::error!("Failed to initialize {} backend: {}", backend_name, e);
-                return;
-            }
-
-            // Register our push-to-talk shortcut
-            let ptt_shortcut = backend::Shortcut {
-                id: "coldvox_ptt".to_string(),
-                description: "ColdVox Push-to-talk".to_string(),
-                default_keys: Some("Ctrl+Alt+Space".to_string()),
-            };
-
-            if let Err(e) = backend.register_shortcut(&ptt_shortcut).await {
-                tracing::error!(
-                    "Failed to register shortcut with {} backend: {}",
-                    backend_name,
-                    e
-                );
-                return;
-            }
-
-            // Start listening for events
-            if let Err(e) = backend.start_listening(event_tx, None).await {
-                tracing::error!("{} backend listening error: {}", backend_name, e);
-            }
-        }
-
-        #[cfg(not(kde_globalaccel))]
-        {
-            tracing::warn!("KDE KGlobalAccel backend not available, using fallback implementation");
-            // Fallback implementation for non-KDE systems
-            let _ = event_tx; // keep signature stable for callers
-                              // In a real implementation, this would provide alternative hotkey handling
-            tokio::time::sleep(tokio::time::Duration::from_secs(3600)).await;
-        }
-    })
-}
@@ -1,18 +1,33 @@
+use coldvox_runtime::plugin_config::{
+    discover_plugin_selection_config_path, explicit_startup_config_path,
+};
 use coldvox_stt::plugin::PluginSelectionConfig;
 #[cfg(feature = "http-remote")]
 use coldvox_stt::plugins::http_remote::HttpRemoteConfig;
+#[cfg(feature = "ws-remote")]
+use coldvox_stt::plugins::ws_remote::WsRemoteConfig;
+use config::builder::{ConfigBuilder, DefaultState};
 use config::{Case, Config, ConfigError, Environment, File};
-use serde::Deserialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct InjectionSettings {
     pub fail_fast: bool,
     pub allow_kdotool: bool,
     pub allow_enigo: bool,
+    /// Type with xdotool on plain X11 sessions
+    pub allow_xdotool: bool,
+    /// Type through the XDG RemoteDesktop portal on Wayland
+    pub allow_portal: bool,
+    /// Inject through the primary (middle-click) selection
+    pub allow_primary_selection: bool,
+    /// Middle-click to paste after setting the primary selection
+    pub primary_selection_middle_click: bool,
     pub inject_on_unknown_focus: bool,
     pub require_focus: bool,
     pub pause_hotkey: String,
@@ -25,17 +40,95 @@ pub struct InjectionSettings {
     pub cooldown_max_ms: u64,
     pub injection_mode: String,
     pub keystroke_rate_cps: u32,
+    /// Learn a keystroke rate per app from whether typed text arrives intact
+    pub adaptive_pacing: bool,
+    /// Vary each typed injection's keystroke rate by up to this percentage
+    pub keystroke_jitter_pct: u32,
     pub max_burst_chars: u32,
     pub paste_chunk_chars: u32,
     pub chunk_delay_ms: u64,
+    /// Keyboard layout for ydotool typing, e.g. "de" or "de(nodeadkeys)"
+    /// ("" = detect)
+    pub keyboard_layout: String,
+    /// Drop final transcripts longer than this (0 = no limit)
+    pub max_utterance_chars: u32,
+    /// Drop injections beyond this many characters per minute (0 = no limit)
+    pub max_chars_per_minute: u32,
+    /// Paste longer text even in keystroke mode (0 = never)
+    pub paste_above_chars: u32,
     pub focus_cache_duration_ms: u64,
     pub enable_window_detection: bool,
     pub clipboard_restore_delay_ms: u64,
+    /// Verify the clipboard restore and retry this many times (0 = don't verify)
+    pub clipboard_restore_retries: u32,
+    /// Back up and restore every clipboard MIME type, not just text
+    pub clipboard_preserve_mime_types: bool,
+    /// Ask clipboard managers to keep seeded payloads out of history
+    pub clipboard_manager_hint: bool,
+    pub clipboard_html: bool,
+    pub clipboard_html_apps: Vec<String>,
+    pub clipboard_plain_apps: Vec<String>,
     pub discovery_timeout_ms: u64,
+    /// Check for display-server restarts this often (ms, 0 = off)
+    pub display_watch_interval_ms: u64,
     pub allowlist: Vec<String>,
     pub blocklist: Vec<String>,
     pub min_success_rate: f32,
     pub min_sample_size: u32,
+    /// Persist per-application injection stats for `coldvox stats --per-app`
+    pub persist_app_stats: bool,
+    /// Move the caret past text inserted via AT-SPI
+    pub atspi_move_caret: bool,
+    /// Replace a selection at the caret when inserting via AT-SPI
+    pub atspi_replace_selection: bool,
+    /// Formatting of AT-SPI inserts in rich text: inherit, plain or match-caret
+    pub atspi_text_attributes: String,
+    /// Send spoken commands ("enter", "undo", ...) as key chords
+    pub voice_commands: bool,
+    /// Longest utterance that may dispatch a command from partials (ms)
+    pub command_max_duration_ms: u64,
+    /// Agreeing partials required before a command is dispatched early
+    pub command_min_stable_partials: u32,
+    /// Hold dictation until flushed by hotkey or voice command
+    pub buffered_dictation: bool,
+    /// Dictation mode at startup: normal, spell, number or literal
+    pub dictation_mode: String,
+    /// Merge finals arriving within this many ms into one injection
+    pub final_merge_window_ms: u64,
+    /// Audio sources whose transcripts are injected (others are only stored)
+    pub inject_sources: Vec<String>,
+    /// While the screen is shared: ignore, keystroke-only or pause
+    pub screen_share_policy: String,
+    /// Desktop notification when dictated text could not be injected
+    pub notify_on_failure: bool,
+    /// Put text that could not be injected on the clipboard
+    pub recover_failed_text: bool,
+    /// Utterances that may wait for injection
+    pub queue_capacity: usize,
+    /// Merge utterances arriving closer together than this (ms, 0 = off)
+    pub queue_coalesce_ms: u64,
+    /// When the queue is full: block, drop-oldest or drop-newest
+    pub queue_drop_policy: String,
+    /// Hold back final transcripts whose mean word confidence is below this
+    /// (0.0-1.0, 0 = type everything)
+    pub min_confidence: f32,
+    /// How a held transcript is surfaced: notify, speak or none
+    pub low_confidence_feedback: String,
+    /// Append every injection to the audit log for `coldvox audit`
+    pub audit_log: bool,
+    /// Text kept in the audit log: full, hashed or omitted ("" follows
+    /// redact_logs: hashed when redacting, full otherwise)
+    pub audit_text: String,
+    /// Final transcripts with card numbers, email addresses or API keys:
+    /// off, warn, redact or confirm
+    pub sensitive_content: String,
+    /// Extra patterns for the sensitive content filter
+    pub sensitive_patterns: Vec<String>,
+    /// Where finals go without a display server or with `--no-injection`:
+    /// auto, stdout, file or osc52
+    pub headless_output: String,
+    /// File appended to when `headless_output = "file"`
+    pub headless_output_file: String,
 }
 
 impl Default for InjectionSettings {
@@ -44,6 +137,10 @@ impl Default for InjectionSettings {
             fail_fast: false,
             allow_kdotool: false,
             allow_enigo: false,
+            allow_xdotool: false,
+            allow_portal: false,
+            allow_primary_selection: false,
+            primary_selection_middle_click: true,
             inject_on_unknown_focus: true,
             require_focus: false,
             pause_hotkey: "".to_string(),
@@ -56,27 +153,130 @@ impl Default for InjectionSettings {
             cooldown_max_ms: 300000,
             injection_mode: "auto".to_string(),
             keystroke_rate_cps: 20,
+            adaptive_pacing: true,
+            keystroke_jitter_pct: 0,
             max_burst_chars: 50,
             paste_chunk_chars: 500,
             chunk_delay_ms: 30,
+            keyboard_layout: String::new(),
+            max_utterance_chars: 4000,
+            max_chars_per_minute: 10_000,
+            paste_above_chars: 1000,
             focus_cache_duration_ms: 200,
             enable_window_detection: true,
             clipboard_restore_delay_ms: 500,
+            clipboard_restore_retries: 3,
+            clipboard_preserve_mime_types: true,
+            clipboard_manager_hint: true,
+            clipboard_html: false,
+            clipboard_html_apps: Vec::new(),
+            clipboard_plain_apps: Vec::new(),
             discovery_timeout_ms: 1000,
+            display_watch_interval_ms: 2000,
             allowlist: Vec::new(),
             blocklist: Vec::new(),
             min_success_rate: 0.3,
             min_sample_size: 5,
+            persist_app_stats: true,
+            atspi_move_caret: true,
+            atspi_replace_selection: false,
+            atspi_text_attributes: "inherit".to_string(),
+            voice_commands: false,
+            command_max_duration_ms: 1500,
+            command_min_stable_partials: 2,
+            buffered_dictation: false,
+            dictation_mode: "normal".to_string(),
+            final_merge_window_ms: 0,
+            inject_sources: vec!["mic".to_string()],
+            screen_share_policy: "ignore".to_string(),
+            notify_on_failure: true,
+            recover_failed_text: true,
+            queue_capacity: 8,
+            queue_coalesce_ms: 0,
+            queue_drop_policy: "block".to_string(),
+            min_confidence: 0.0,
+            low_confidence_feedback: "notify".to_string(),
+            audit_log: false,
+            audit_text: String::new(),
+            sensitive_content: "off".to_string(),
+            sensitive_patterns: Vec::new(),
+            headless_output: "auto".to_string(),
+            headless_output_file: String::new(),
         }
     }
 }
 
-#[derive(Debug, Default, Deserialize)]
+impl InjectionSettings {
+    /// Runtime injection options for these settings
+    pub fn runtime_options(&self) -> crate::runtime::InjectionOptions {
+        crate::runtime::InjectionOptions {
+            enable: true,
+            allow_kdotool: self.allow_kdotool,
+            allow_enigo: self.allow_enigo,
+            allow_xdotool: self.allow_xdotool,
+            allow_portal: self.allow_portal,
+            allow_primary_selection: self.allow_primary_selection,
+            primary_selection_middle_click: self.primary_selection_middle_click,
+            inject_on_unknown_focus: self.inject_on_unknown_focus,
+            max_total_latency_ms: Some(self.max_total_latency_ms),
+            per_method_timeout_ms: Some(self.per_method_timeout_ms),
+            cooldown_initial_ms: Some(self.cooldown_initial_ms),
+            adaptive_pacing: self.adaptive_pacing,
+            keystroke_jitter_pct: self.keystroke_jitter_pct,
+            keyboard_layout: Some(self.keyboard_layout.trim().to_string())
+                .filter(|layout| !layout.is_empty()),
+            fail_fast: self.fail_fast,
+            stats_path: self
+                .persist_app_stats
+                .then(crate::text_injection::app_stats::AppStatsStore::default_path)
+                .flatten(),
+            atspi_move_caret: self.atspi_move_caret,
+            atspi_replace_selection: self.atspi_replace_selection,
+            atspi_text_attributes: self.atspi_text_attributes.parse().unwrap_or_default(),
+            voice_commands: self.voice_commands,
+            command_max_duration_ms: self.command_max_duration_ms,
+            command_min_stable_partials: self.command_min_stable_partials,
+            buffered_dictation: self.buffered_dictation,
+            dictation_mode: self.dictation_mode.parse().unwrap_or_default(),
+            final_merge_window_ms: self.final_merge_window_ms,
+            inject_sources: self.inject_sources.clone(),
+            screen_share_policy: self.screen_share_policy.parse().unwrap_or_default(),
+            notify_on_failure: self.notify_on_failure,
+            recover_failed_text: self.recover_failed_text,
+            queue_capacity: self.queue_capacity,
+            queue_coalesce_ms: self.queue_coalesce_ms,
+            queue_drop_policy: self.queue_drop_policy.parse().unwrap_or_default(),
+            clipboard_restore_retries: self.clipboard_restore_retries,
+            clipboard_preserve_mime_types: self.clipboard_preserve_mime_types,
+            clipboard_manager_hint: self.clipboard_manager_hint,
+            clipboard_html: self.clipboard_html,
+            clipboard_html_apps: self.clipboard_html_apps.clone(),
+            clipboard_plain_apps: self.clipboard_plain_apps.clone(),
+            display_watch_interval_ms: self.display_watch_interval_ms,
+            min_confidence: (self.min_confidence > 0.0).then_some(self.min_confidence),
+            low_confidence_feedback: self.low_confidence_feedback.parse().unwrap_or_default(),
+            allowlist: self.allowlist.clone(),
+            blocklist: self.blocklist.clone(),
+            audit_log_path: self
+                .audit_log
+                .then(crate::text_injection::audit::AuditLog::default_path)
+                .flatten(),
+            audit_text: self.audit_text.parse().ok(),
+            sensitive_content: self.sensitive_content.parse().unwrap_or_default(),
+            sensitive_patterns: self.sensitive_patterns.clone(),
+            max_utterance_chars: self.max_utterance_chars,
+            max_chars_per_minute: self.max_chars_per_minute,
+            paste_above_chars: self.paste_above_chars,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
 pub struct SttRemoteAuthSettings {
     pub bearer_token_env_var: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SttRemoteSettings {
     pub base_url: String,
     pub api_path: String,
@@ -109,7 +309,36 @@ impl Default for SttRemoteSettings {
     }
 }
 
-#[derive(Debug, Deserialize)]
+/// Streaming remote STT over WebSocket (the `ws-remote` plugin)
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SttRemoteWsSettings {
+    /// `ws://` or `wss://` streaming endpoint
+    pub url: String,
+    pub model_name: String,
+    pub connect_timeout_ms: u64,
+    /// Time allowed for the final transcript once an utterance ends
+    pub final_timeout_ms: u64,
+    pub headers: HashMap<String, String>,
+    pub bearer_token_env_var: Option<String>,
+    /// Send the token as this query parameter instead of an Authorization header
+    pub token_query_param: Option<String>,
+}
+
+impl Default for SttRemoteWsSettings {
+    fn default() -> Self {
+        Self {
+            url: "ws://localhost:8765/v1/stream".to_string(),
+            model_name: "whisper-small".to_string(),
+            connect_timeout_ms: 3_000,
+            final_timeout_ms: 10_000,
+            headers: HashMap::new(),
+            bearer_token_env_var: None,
+            token_query_param: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SttSettings {
     pub preferred: Option<String>,
     pub fallbacks: Vec<String>,
@@ -118,12 +347,39 @@ pub struct SttSettings {
     pub language: Option<String>,
     pub failover_threshold: u32,
     pub failover_cooldown_secs: u32,
+    /// Keep the first fallback plugin loaded for instant failover
+    pub warm_standby: bool,
+    /// Largest fallback model (MB) kept loaded on standby
+    pub standby_max_mem_mb: Option<u32>,
     pub model_ttl_secs: u32,
     pub disable_gc: bool,
     pub metrics_log_interval_secs: u32,
     pub debug_dump_events: bool,
     pub auto_extract: bool,
+    /// Local decoder threads; unset picks one less than the available cores
+    pub decode_threads: Option<usize>,
+    /// CPU cores to pin local decoding to
+    pub decode_cpu_affinity: Vec<usize>,
+    /// Niceness for local decoding
+    pub decode_niceness: Option<i32>,
+    /// Benchmark local models on first run and pick the largest meeting `target_rtf`
+    pub auto_select_model: bool,
+    /// Real-time factor (decode time / audio time) the selected model must meet
+    pub target_rtf: f64,
+    /// Also transcribe every utterance with this plugin and report both results
+    pub compare_with: Option<String>,
+    /// JSON Lines report for `compare_with` (default under the XDG state directory)
+    pub compare_report: Option<String>,
+    /// Suspected hallucinations in finals: off, flag or drop
+    pub hallucination_filter: String,
+    /// Phrases treated as hallucinations, besides the built-in ones
+    pub hallucination_phrases: Vec<String>,
+    /// Utterances quieter than this overall (dBFS) are suspect
+    pub hallucination_min_dbfs: f32,
+    /// Finals with more characters per second of audio are suspect
+    pub hallucination_max_chars_per_sec: f32,
     pub remote: SttRemoteSettings,
+    pub remote_ws: SttRemoteWsSettings,
 }
 
 impl Default for SttSettings {
@@ -136,37 +392,238 @@ impl Default for SttSettings {
             language: None,
             failover_threshold: 5,
             failover_cooldown_secs: 10,
+            warm_standby: false,
+            standby_max_mem_mb: None,
             model_ttl_secs: 300,
             disable_gc: false,
             metrics_log_interval_secs: 30,
             debug_dump_events: false,
             auto_extract: true,
+            decode_threads: None,
+            decode_cpu_affinity: Vec::new(),
+            decode_niceness: None,
+            auto_select_model: false,
+            target_rtf: 0.5,
+            compare_with: None,
+            compare_report: None,
+            hallucination_filter: "off".to_string(),
+            hallucination_phrases: Vec::new(),
+            hallucination_min_dbfs: -50.0,
+            hallucination_max_chars_per_sec: 30.0,
             remote: SttRemoteSettings::default(),
+            remote_ws: SttRemoteWsSettings::default(),
+        }
+    }
+}
+
+impl SttSettings {
+    /// CPU resources for local decoders
+    pub fn decode_resources(&self) -> coldvox_stt::DecodeResources {
+        coldvox_stt::DecodeResources {
+            threads: self.decode_threads,
+            cpu_affinity: self.decode_cpu_affinity.clone(),
+            niceness: self.decode_niceness,
+        }
+    }
+
+    /// A/B comparison settings, or None when `compare_with` is unset
+    pub fn comparison(&self) -> Option<crate::stt::compare::ComparisonConfig> {
+        let plugin_id = self
+            .compare_with
+            .clone()
+            .filter(|id| !id.trim().is_empty())?;
+        Some(crate::stt::compare::ComparisonConfig {
+            plugin_id,
+            report_path: match &self.compare_report {
+                Some(path) => Some(PathBuf::from(path)),
+                None => crate::stt::compare::ComparisonConfig::default_report_path(),
+            },
+        })
+    }
+
+    /// Hallucination filter settings, or None when it is off
+    pub fn hallucination(&self) -> Option<crate::stt::hallucination::HallucinationConfig> {
+        let action = self.hallucination_filter.parse().unwrap_or_default();
+        if action == crate::stt::hallucination::HallucinationAction::Off {
+            return None;
         }
+        Some(crate::stt::hallucination::HallucinationConfig {
+            action,
+            phrases: self.hallucination_phrases.clone(),
+            min_dbfs: self.hallucination_min_dbfs,
+            max_chars_per_sec: self.hallucination_max_chars_per_sec,
+        })
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AudioSettings {
     pub capture_buffer_samples: usize,
+    /// What to do when the capture buffer is full: "drop-newest",
+    /// "drop-oldest", "block-producer" or "grow-once". "block-producer"
+    /// sleeps in the audio callback and is not real-time safe.
+    pub capture_buffer_policy: String,
+    /// Input channel on multi-channel devices: "left", "right", "mix" or a zero-based index
+    pub channel: String,
+    /// Devices to fail over to (in order) when the active device is unplugged
+    pub fallback_devices: Vec<String>,
+    /// Suspend capture after this many minutes without speech (0 = never)
+    pub idle_suspend_minutes: u64,
+    /// While suspended, listen for sound this often (0 = never)
+    pub idle_probe_interval_secs: u64,
+    /// How long each idle probe listens
+    pub idle_probe_window_ms: u64,
+    /// Level that wakes a suspended pipeline during a probe
+    pub idle_probe_threshold_dbfs: f32,
+    /// Audio from before a VAD speech start handed to STT with the utterance
+    pub vad_pre_roll_ms: u32,
+    /// Finalize and inject VAD utterances at the next pause once they run
+    /// this long (0 = only when speech ends)
+    pub max_utterance_secs: u32,
+    /// Warn about clipping, quiet speech and off-axis talking
+    pub quality_warnings: bool,
+    /// Also show quality warnings as desktop notifications
+    pub quality_notifications: bool,
+    /// Close the capture stream while the pipeline is paused
+    pub pause_suspends_capture: bool,
+    /// Ignore the microphone while system audio is playing
+    pub suppress_on_playback: bool,
+    /// Sink output level that counts as playback
+    pub playback_threshold_dbfs: f32,
+}
+
+impl AudioSettings {
+    /// Idle-mode settings, or None when idle suspension is off
+    pub fn idle_config(&self) -> Option<crate::idle::IdleConfig> {
+        (self.idle_suspend_minutes > 0).then(|| crate::idle::IdleConfig {
+            suspend_after: std::time::Duration::from_secs(self.idle_suspend_minutes * 60),
+            probe_interval: (self.idle_probe_interval_secs > 0)
+                .then(|| std::time::Duration::from_secs(self.idle_probe_interval_secs)),
+            probe_window: std::time::Duration::from_millis(self.idle_probe_window_ms),
+            probe_threshold_dbfs: self.idle_probe_threshold_dbfs,
+        })
+    }
+
+    /// Playback suppression settings, or None when it is off
+    pub fn playback_guard(&self) -> Option<crate::audio::playback::PlaybackGuardConfig> {
+        self.suppress_on_playback
+            .then(|| crate::audio::playback::PlaybackGuardConfig {
+                threshold_dbfs: self.playback_threshold_dbfs,
+                ..Default::default()
+            })
+    }
 }
 
 impl Default for AudioSettings {
     fn default() -> Self {
         Self {
             capture_buffer_samples: 65_536,
+            capture_buffer_policy: "drop-newest".to_string(),
+            channel: "mix".to_string(),
+            fallback_devices: Vec::new(),
+            idle_suspend_minutes: 0,
+            idle_probe_interval_secs: 60,
+            idle_probe_window_ms: 1500,
+            idle_probe_threshold_dbfs: -45.0,
+            vad_pre_roll_ms: 300,
+            max_utterance_secs: 20,
+            quality_warnings: true,
+            quality_notifications: false,
+            pause_suspends_capture: false,
+            suppress_on_playback: false,
+            playback_threshold_dbfs: -45.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct HotkeySettings {
+    /// Push-to-talk feedback surface: auto, kde, gnome, notification, terminal, off
+    pub indicator: String,
+    /// Transcribe exactly the audio between press and release as one utterance
+    pub bounded_audio: bool,
+    /// Pre-roll prepended to bounded push-to-talk utterances
+    pub pre_roll_ms: u32,
+    /// Push-to-talk taps shorter than this toggle listening (0 = hold only)
+    pub hold_threshold_ms: u64,
+    /// Action → key chord, e.g. `switch_stt_plugin = "Ctrl+Alt+P"`
+    pub bindings: HashMap<String, String>,
+}
+
+impl Default for HotkeySettings {
+    fn default() -> Self {
+        Self {
+            indicator: "auto".to_string(),
+            bounded_audio: false,
+            pre_roll_ms: 300,
+            hold_threshold_ms: 0,
+            bindings: crate::hotkey::bindings::default_bindings()
+                .into_iter()
+                .map(|b| (b.action.id().to_string(), b.chord))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TtsSettings {
+    /// Synthesizer taking espeak-ng style arguments and writing WAV to stdout
+    pub command: String,
+    /// Voice name (empty = synthesizer default)
+    pub voice: String,
+    pub rate_wpm: u32,
+    /// Output device for read-back (empty = system default)
+    pub output_device: String,
+    /// Mute the microphone while speaking
+    pub mute_mic: bool,
+    /// Kill the synthesizer if it hasn't finished after this long
+    pub timeout_ms: u64,
+}
+
+impl TtsSettings {
+    pub fn tts_config(&self) -> crate::tts::TtsConfig {
+        let non_empty = |s: &str| (!s.trim().is_empty()).then(|| s.trim().to_string());
+        crate::tts::TtsConfig {
+            command: self.command.clone(),
+            voice: non_empty(&self.voice),
+            rate_wpm: self.rate_wpm,
+            output_device: non_empty(&self.output_device),
+            mute_mic: self.mute_mic,
+            timeout: std::time::Duration::from_millis(self.timeout_ms),
+        }
+    }
+}
+
+impl Default for TtsSettings {
+    fn default() -> Self {
+        Self {
+            command: "espeak-ng".to_string(),
+            voice: String::new(),
+            rate_wpm: 175,
+            output_device: String::new(),
+            mute_mic: true,
+            timeout_ms: 10_000,
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Settings {
     pub device: Option<String>,
     pub resampler_quality: String,
     pub enable_device_monitor: bool,
     pub activation_mode: String,
+    /// Append the shutdown session summary to `sessions.jsonl`
+    pub session_log: bool,
+    /// Snapshot pending dictation to `recovery.json` and offer it back after
+    /// a crash
+    pub crash_recovery: bool,
+    /// Seconds of audio the black box recorder keeps for bug reports (0 = off)
+    pub black_box_secs: u32,
     pub audio: AudioSettings,
+    pub hotkeys: HotkeySettings,
     pub injection: InjectionSettings,
+    pub tts: TtsSettings,
     pub stt: SttSettings,
 }
 
@@ -177,8 +634,13 @@ impl Default for Settings {
             resampler_quality: "".to_string(), // Empty; config builder sets "balanced" if not overridden
             enable_device_monitor: true,
             activation_mode: "".to_string(), // Empty; config builder sets "vad" if not overridden
+            session_log: false,
+            crash_recovery: true,
+            black_box_secs: 30,
             audio: AudioSettings::default(),
+            hotkeys: HotkeySettings::default(),
             injection: InjectionSettings::default(),
+            tts: TtsSettings::default(),
             stt: SttSettings::default(),
         }
     }
@@ -207,6 +669,8 @@ impl Settings {
             failover: Some(coldvox_stt::plugin::FailoverConfig {
                 failover_threshold: stt.failover_threshold,
                 failover_cooldown_secs: stt.failover_cooldown_secs,
+                warm_standby: stt.warm_standby,
+                standby_max_mem_mb: stt.standby_max_mem_mb,
             }),
             gc_policy: Some(coldvox_stt::plugin::GcPolicy {
                 model_ttl_secs: stt.model_ttl_secs,
@@ -221,20 +685,62 @@ impl Settings {
                 debug_dump_events: stt.debug_dump_events,
             }),
             auto_extract_model: stt.auto_extract,
+            decode: stt.decode_resources(),
         }
     }
 
-    fn build_config(explicit_path: Option<PathBuf>) -> Result<Config, ConfigError> {
-        let mut builder = Config::builder()
+    /// `[hotkeys.bindings]`, with `injection.pause_hotkey` as the
+    /// `toggle_pause` chord unless the bindings already set one
+    pub fn hotkey_bindings(&self) -> HashMap<String, String> {
+        let mut bindings = self.hotkeys.bindings.clone();
+        let pause = self.injection.pause_hotkey.trim();
+        if !pause.is_empty() {
+            bindings
+                .entry(crate::hotkey::HotkeyAction::TogglePause.id().to_string())
+                .or_insert_with(|| pause.to_string());
+        }
+        bindings
+    }
+
+    /// Every setting's built-in default, before any file or environment
+    fn defaults() -> Result<ConfigBuilder<DefaultState>, ConfigError> {
+        Config::builder()
             .set_default("resampler_quality", "balanced")?
             .set_default("activation_mode", "vad")?
             .set_default("enable_device_monitor", true)?
+            .set_default("session_log", false)?
+            .set_default("crash_recovery", true)?
+            .set_default("black_box_secs", 30)?
             // Audio settings defaults
             .set_default("audio.capture_buffer_samples", 65_536)?
+            .set_default("audio.capture_buffer_policy", "drop-newest")?
+            .set_default("audio.channel", "mix")?
+            .set_default("audio.fallback_devices", Vec::<String>::new())?
+            .set_default("audio.idle_suspend_minutes", 0)?
+            .set_default("audio.idle_probe_interval_secs", 60)?
+            .set_default("audio.idle_probe_window_ms", 1500)?
+            .set_default("audio.idle_probe_threshold_dbfs", -45.0)?
+            .set_default("audio.vad_pre_roll_ms", 300)?
+            .set_default("audio.max_utterance_secs", 20)?
+            .set_default("audio.quality_warnings", true)?
+            .set_default("audio.quality_notifications", false)?
+            .set_default("audio.pause_suspends_capture", false)?
+            .set_default("audio.suppress_on_playback", false)?
+            .set_default("audio.playback_threshold_dbfs", -45.0)?
+            // Hotkey settings defaults
+            .set_default("hotkeys.indicator", "auto")?
+            .set_default("hotkeys.bounded_audio", false)?
+            .set_default("hotkeys.pre_roll_ms", 300)?
+            .set_default("hotkeys.hold_threshold_ms", 0)?
+            .set_default("hotkeys.bindings.push_to_talk", "Meta+Ctrl")?
             // Injection settings defaults
             .set_default("injection.fail_fast", false)?
             .set_default("injection.allow_kdotool", false)?
             .set_default("injection.allow_enigo", false)?
+            .set_default("injection.allow_xdotool", false)?
+            .set_default("injection.allow_portal", false)?
+            .set_default("injection.allow_primary_selection", false)?
+            .set_default("injection.primary_selection_middle_click", true)?
             .set_default("injection.inject_on_unknown_focus", true)?
             .set_default("injection.require_focus", false)?
             .set_default("injection.pause_hotkey", "")?
@@ -247,17 +753,62 @@ impl Settings {
             .set_default("injection.cooldown_max_ms", 300000)?
             .set_default("injection.injection_mode", "auto")?
             .set_default("injection.keystroke_rate_cps", 20)?
+            .set_default("injection.adaptive_pacing", true)?
+            .set_default("injection.keystroke_jitter_pct", 0)?
             .set_default("injection.max_burst_chars", 50)?
             .set_default("injection.paste_chunk_chars", 500)?
             .set_default("injection.chunk_delay_ms", 30)?
+            .set_default("injection.keyboard_layout", "")?
+            .set_default("injection.max_utterance_chars", 4000)?
+            .set_default("injection.max_chars_per_minute", 10_000)?
+            .set_default("injection.paste_above_chars", 1000)?
             .set_default("injection.focus_cache_duration_ms", 200)?
             .set_default("injection.enable_window_detection", true)?
             .set_default("injection.clipboard_restore_delay_ms", 500)?
+            .set_default("injection.clipboard_restore_retries", 3)?
+            .set_default("injection.clipboard_preserve_mime_types", true)?
+            .set_default("injection.clipboard_manager_hint", true)?
+            .set_default("injection.clipboard_html", false)?
+            .set_default("injection.clipboard_html_apps", Vec::<String>::new())?
+            .set_default("injection.clipboard_plain_apps", Vec::<String>::new())?
             .set_default("injection.discovery_timeout_ms", 1000)?
+            .set_default("injection.display_watch_interval_ms", 2000)?
             .set_default("injection.allowlist", Vec::<String>::new())?
             .set_default("injection.blocklist", Vec::<String>::new())?
             .set_default("injection.min_success_rate", 0.3)?
             .set_default("injection.min_sample_size", 5)?
+            .set_default("injection.persist_app_stats", true)?
+            .set_default("injection.atspi_move_caret", true)?
+            .set_default("injection.atspi_replace_selection", false)?
+            .set_default("injection.atspi_text_attributes", "inherit")?
+            .set_default("injection.voice_commands", false)?
+            .set_default("injection.command_max_duration_ms", 1500)?
+            .set_default("injection.command_min_stable_partials", 2)?
+            .set_default("injection.buffered_dictation", false)?
+            .set_default("injection.dictation_mode", "normal")?
+            .set_default("injection.final_merge_window_ms", 0)?
+            .set_default("injection.inject_sources", vec!["mic"])?
+            .set_default("injection.screen_share_policy", "ignore")?
+            .set_default("injection.notify_on_failure", true)?
+            .set_default("injection.recover_failed_text", true)?
+            .set_default("injection.queue_capacity", 8)?
+            .set_default("injection.queue_coalesce_ms", 0)?
+            .set_default("injection.queue_drop_policy", "block")?
+            .set_default("injection.min_confidence", 0.0)?
+            .set_default("injection.low_confidence_feedback", "notify")?
+            .set_default("injection.audit_log", false)?
+            .set_default("injection.audit_text", "")?
+            .set_default("injection.sensitive_content", "off")?
+            .set_default("injection.sensitive_patterns", Vec::<String>::new())?
+            .set_default("injection.headless_output", "auto")?
+            .set_default("injection.headless_output_file", "")?
+            // Read-back settings defaults
+            .set_default("tts.command", "espeak-ng")?
+            .set_default("tts.voice", "")?
+            .set_default("tts.rate_wpm", 175)?
+            .set_default("tts.output_device", "")?
+            .set_default("tts.mute_mic", true)?
+            .set_default("tts.timeout_ms", 10_000)?
             // STT settings defaults
             .set_default("stt.preferred", Option::<String>::None)?
             .set_default("stt.fallbacks", Vec::<String>::new())?
@@ -266,11 +817,24 @@ impl Settings {
             .set_default("stt.language", Option::<String>::None)?
             .set_default("stt.failover_threshold", 5)?
             .set_default("stt.failover_cooldown_secs", 10)?
+            .set_default("stt.warm_standby", false)?
+            .set_default("stt.standby_max_mem_mb", Option::<u32>::None)?
             .set_default("stt.model_ttl_secs", 300)?
             .set_default("stt.disable_gc", false)?
             .set_default("stt.metrics_log_interval_secs", 30)?
             .set_default("stt.debug_dump_events", false)?
             .set_default("stt.auto_extract", true)?
+            .set_default("stt.decode_threads", Option::<u64>::None)?
+            .set_default("stt.decode_cpu_affinity", Vec::<u64>::new())?
+            .set_default("stt.decode_niceness", Option::<i64>::None)?
+            .set_default("stt.auto_select_model", false)?
+            .set_default("stt.target_rtf", 0.5)?
+            .set_default("stt.compare_with", Option::<String>::None)?
+            .set_default("stt.compare_report", Option::<String>::None)?
+            .set_default("stt.hallucination_filter", "off")?
+            .set_default("stt.hallucination_phrases", Vec::<String>::new())?
+            .set_default("stt.hallucination_min_dbfs", -50.0)?
+            .set_default("stt.hallucination_max_chars_per_sec", 30.0)?
             .set_default("stt.remote.base_url", "http://localhost:5092")?
             .set_default("stt.remote.api_path", "/v1/audio/transcriptions")?
             .set_default("stt.remote.health_path", "/health")?
@@ -284,7 +848,18 @@ impl Settings {
             )?
             .set_default("stt.remote.max_audio_bytes", 2_097_152)?
             .set_default("stt.remote.max_audio_seconds", 30)?
-            .set_default("stt.remote.max_payload_bytes", 2_621_440)?;
+            .set_default("stt.remote.max_payload_bytes", 2_621_440)?
+            .set_default("stt.remote_ws.url", "ws://localhost:8765/v1/stream")?
+            .set_default("stt.remote_ws.model_name", "whisper-small")?
+            .set_default("stt.remote_ws.connect_timeout_ms", 3_000)?
+            .set_default("stt.remote_ws.final_timeout_ms", 10_000)?
+            .set_default("stt.remote_ws.headers", HashMap::<String, String>::new())?
+            .set_default("stt.remote_ws.bearer_token_env_var", Option::<String>::None)?
+            .set_default("stt.remote_ws.token_query_param", Option::<String>::None)
+    }
+
+    fn build_config(explicit_path: Option<PathBuf>) -> Result<Config, ConfigError> {
+        let mut builder = Self::defaults()?;
 
         // Allow tests or callers to skip config file discovery entirely
         let skip_discovery = std::env::var("COLDVOX_SKIP_CONFIG_DISCOVERY")
@@ -292,28 +867,23 @@ impl Settings {
             .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
             .unwrap_or(false);
 
-        // Check if a config file will be loaded
-        let config_file_exists = if skip_discovery {
-            false
-        } else if let Some(path) = &explicit_path {
-            path.exists()
-        } else {
-            Self::discover_config_path().is_some()
-        };
-
-        if let Some(path) = explicit_path {
-            if path.exists() {
-                builder = builder.add_source(File::from(path));
-            } else {
+        let layers = match explicit_path {
+            Some(path) if path.exists() => vec![ConfigLayer {
+                kind: ConfigLayerKind::Explicit,
+                path,
+            }],
+            Some(path) => {
                 return Err(ConfigError::Message(format!(
                     "Config file not found at {}",
                     path.display()
                 )));
             }
-        } else if !skip_discovery {
-            if let Some(path) = Self::discover_config_path() {
-                builder = builder.add_source(File::from(path));
-            }
+            None if skip_discovery => Vec::new(),
+            None => Self::config_layers(),
+        };
+
+        for layer in &layers {
+            builder = builder.add_source(File::from(layer.path.as_path()));
         }
 
         builder = builder.add_source(
@@ -325,68 +895,54 @@ impl Settings {
 
         let config = builder.build()?;
 
-        // Log a warning if no config file was found
-        if !config_file_exists {
+        if layers.is_empty() {
             tracing::warn!("No config file found, using default values only");
+        } else {
+            let merged: Vec<String> = layers.iter().map(ConfigLayer::to_string).collect();
+            tracing::info!("Merged config layers: {}", merged.join(" -> "));
         }
 
         Ok(config)
     }
 
-    /// Resolve the startup config path.
+    /// Config files merged at startup, lowest precedence first; `COLDVOX__*`
+    /// environment variables apply on top.
     ///
-    /// `COLDVOX_CONFIG_PATH` wins first so live profiles can override the
-    /// checked-in default without changing `config/default.toml`.
-    fn discover_config_path() -> Option<PathBuf> {
-        if let Ok(custom) = env::var("COLDVOX_CONFIG_PATH") {
-            let path = PathBuf::from(custom);
-            if path.exists() {
-                tracing::info!(
-                    "Using startup config from COLDVOX_CONFIG_PATH: {}",
-                    path.display()
-                );
-                return Some(path);
-            }
-        }
-
-        if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
-            let candidate = Path::new(&manifest_dir)
-                .join("../..")
-                .join("config/default.toml");
-            if candidate.exists() {
-                return Some(candidate);
-            }
+    /// `COLDVOX_CONFIG_PATH` (or `--config`) replaces every file layer so live
+    /// profiles can run without the checked-in defaults. Otherwise the layers
+    /// are the repository's `config/default.toml` (when running from a
+    /// checkout), `/etc/coldvox/config.toml`,
+    /// `$XDG_CONFIG_HOME/coldvox/config.toml` and `./coldvox.toml`.
+    pub fn config_layers() -> Vec<ConfigLayer> {
+        if let Some(path) = explicit_startup_config_path() {
+            return vec![ConfigLayer {
+                kind: ConfigLayerKind::Explicit,
+                path,
+            }];
         }
 
-        let cwd_candidate = PathBuf::from("config/default.toml");
-        if cwd_candidate.exists() {
-            return Some(cwd_candidate);
-        }
-
-        if let Ok(cwd) = env::current_dir() {
-            for ancestor in cwd.ancestors() {
-                let candidate = ancestor.join("config/default.toml");
-                if candidate.exists() {
-                    return Some(candidate);
-                }
-            }
-        }
-
-        if let Ok(xdg_home) = env::var("XDG_CONFIG_HOME") {
-            let candidate = Path::new(&xdg_home).join("coldvox/default.toml");
-            if candidate.exists() {
-                return Some(candidate);
-            }
-        }
+        let user_dir = user_config_dir();
+        let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+        let cwd = env::current_dir().ok();
+        discover_config_layers_with(
+            Path::new(SYSTEM_CONFIG_DIR),
+            user_dir.as_deref(),
+            cwd.as_deref(),
+            manifest_dir.as_deref(),
+        )
+    }
 
-        if let Ok(home) = env::var("HOME") {
-            let candidate = Path::new(&home).join(".config/coldvox/default.toml");
-            if candidate.exists() {
-                return Some(candidate);
-            }
+    /// Config file edits made from a UI are written to: the explicit config
+    /// when one is in use, else the user layer (which may not exist yet)
+    pub fn writable_config_path() -> Option<PathBuf> {
+        if let Some(path) = explicit_startup_config_path() {
+            return Some(path);
         }
-
-        None
+        Self::config_layers()
+            .into_iter()
+            .find(|layer| layer.kind == ConfigLayerKind::User)
+            .map(|layer| layer.path)
+            .or_else(|| Some(user_config_dir()?.join("coldvox").join("config.toml")))
     }
 
     pub fn runtime_plugin_selection(&self) -> Result<PluginSelectionConfig, String> {
@@ -420,6 +976,104 @@ impl Settings {
         }
     }
 
+    #[cfg(feature = "ws-remote")]
+    pub fn runtime_ws_remote_config(&self) -> WsRemoteConfig {
+        let ws = &self.stt.remote_ws;
+        WsRemoteConfig {
+            url: ws.url.clone(),
+            model_name: ws.model_name.clone(),
+            language: self.stt.language.clone(),
+            display_name: "Remote (WebSocket)".to_string(),
+            connect_timeout_ms: ws.connect_timeout_ms,
+            final_timeout_ms: ws.final_timeout_ms,
+            sample_rate: 16_000,
+            headers: ws.headers.clone(),
+            bearer_token_env_var: ws.bearer_token_env_var.clone(),
+            token_query_param: ws.token_query_param.clone(),
+        }
+    }
+
+    /// Activation mode to start in ("hotkey", else voice activation)
+    pub fn runtime_activation_mode(&self) -> crate::runtime::ActivationMode {
+        match self.activation_mode.as_str() {
+            "hotkey" => crate::runtime::ActivationMode::Hotkey,
+            _ => crate::runtime::ActivationMode::Vad,
+        }
+    }
+
+    /// Pipeline options for these settings. Injection stays off (set
+    /// `injection` from [`InjectionSettings::runtime_options`]); the metrics
+    /// endpoint, noise profile and black box are left to the caller.
+    pub fn runtime_options(&self) -> crate::runtime::AppRuntimeOptions {
+        use coldvox_audio::ResamplerQuality;
+        use coldvox_stt::plugin::{FailoverConfig, GcPolicy, MetricsConfig};
+
+        let stt = &self.stt;
+        let stt_selection = PluginSelectionConfig {
+            preferred_plugin: stt.preferred.clone(),
+            fallback_plugins: stt.fallbacks.clone(),
+            require_local: stt.require_local,
+            max_memory_mb: stt.max_mem_mb,
+            required_language: stt.language.clone(),
+            failover: Some(FailoverConfig {
+                failover_threshold: stt.failover_threshold,
+                failover_cooldown_secs: stt.failover_cooldown_secs,
+                warm_standby: stt.warm_standby,
+                standby_max_mem_mb: stt.standby_max_mem_mb,
+            }),
+            gc_policy: Some(GcPolicy {
+                model_ttl_secs: stt.model_ttl_secs,
+                enabled: !stt.disable_gc,
+            }),
+            metrics: Some(MetricsConfig {
+                log_interval_secs: (stt.metrics_log_interval_secs != 0)
+                    .then_some(stt.metrics_log_interval_secs),
+                debug_dump_events: stt.debug_dump_events,
+            }),
+            auto_extract_model: stt.auto_extract,
+            decode: stt.decode_resources(),
+        };
+
+        crate::runtime::AppRuntimeOptions {
+            device: self.device.clone(),
+            resampler_quality: match self.resampler_quality.to_lowercase().as_str() {
+                "fast" => ResamplerQuality::Fast,
+                "quality" => ResamplerQuality::Quality,
+                _ => ResamplerQuality::Balanced,
+            },
+            activation_mode: self.runtime_activation_mode(),
+            stt_selection: Some(stt_selection),
+            stt_compare: stt.comparison(),
+            stt_hallucination: stt.hallucination(),
+            #[cfg(feature = "ws-remote")]
+            ws_remote: Some(self.runtime_ws_remote_config()),
+            enable_device_monitor: self.enable_device_monitor,
+            fallback_devices: self.audio.fallback_devices.clone(),
+            idle: self.audio.idle_config(),
+            capture_buffer_samples: self.audio.capture_buffer_samples,
+            capture_buffer_policy: self.audio.capture_buffer_policy.parse().unwrap_or_default(),
+            audio_channel: self.audio.channel.parse().unwrap_or_default(),
+            indicator: self
+                .hotkeys
+                .indicator
+                .parse()
+                .unwrap_or(crate::hotkey::indicator::IndicatorKind::Auto),
+            ptt_bounded_audio: self.hotkeys.bounded_audio,
+            ptt_pre_roll_ms: self.hotkeys.pre_roll_ms,
+            vad_pre_roll_ms: self.audio.vad_pre_roll_ms,
+            max_utterance_secs: self.audio.max_utterance_secs,
+            quality_warnings: self.audio.quality_warnings,
+            pause_suspends_capture: self.audio.pause_suspends_capture,
+            playback_guard: self.audio.playback_guard(),
+            hotkeys: crate::hotkey::HotkeyConfig {
+                bindings: crate::hotkey::bindings::parse_bindings(&self.hotkey_bindings()).0,
+                hold_threshold_ms: self.hotkeys.hold_threshold_ms,
+            },
+            tts: self.tts.tts_config(),
+            ..Default::default()
+        }
+    }
+
     /// Load settings from a specific config file path (for tests)
     pub fn from_path(config_path: impl AsRef<Path>) -> Result<Self, String> {
         let config = Self::build_config(Some(config_path.as_ref().to_path_buf()))
@@ -447,6 +1101,21 @@ impl Settings {
     }
 
     pub fn validate(&mut self) -> Result<(), String> {
+        let errors = self.check();
+        if !errors.is_empty() {
+            let error_msg = format!("Critical config validation errors: {:?}", errors);
+            return Err(error_msg);
+        }
+
+        // Log non-critical warnings if any were applied
+        tracing::info!("Configuration validation completed successfully.");
+
+        Ok(())
+    }
+
+    /// Reset invalid optional values to their defaults (logging a warning for
+    /// each) and return the errors that make the settings unusable
+    fn check(&mut self) -> Vec<String> {
         let mut errors = Vec::new();
 
         // Validate resampler_quality
@@ -469,6 +1138,89 @@ impl Settings {
             self.activation_mode = "vad".to_string();
         }
 
+        if self.black_box_secs > 600 {
+            tracing::warn!(
+                "black_box_secs {} is above the 600 second limit. Defaulting to 30.",
+                self.black_box_secs
+            );
+            self.black_box_secs = 30;
+        }
+
+        if self
+            .audio
+            .capture_buffer_policy
+            .parse::<coldvox_audio::ring_buffer::OverflowPolicy>()
+            .is_err()
+        {
+            tracing::warn!(
+                "Invalid audio.capture_buffer_policy '{}'. Defaulting to 'drop-newest'.",
+                self.audio.capture_buffer_policy
+            );
+            self.audio.capture_buffer_policy = "drop-newest".to_string();
+        }
+
+        // Validate audio channel selection
+        if self
+            .audio
+            .channel
+            .parse::<coldvox_audio::ChannelSelection>()
+            .is_err()
+        {
+            tracing::warn!(
+                "Invalid audio.channel '{}'. Defaulting to 'mix'.",
+                self.audio.channel
+            );
+            self.audio.channel = "mix".to_string();
+        }
+        if !(-90.0..=0.0).contains(&self.audio.playback_threshold_dbfs) {
+            let clamped = self.audio.playback_threshold_dbfs.clamp(-90.0, 0.0);
+            tracing::warn!(
+                "audio.playback_threshold_dbfs {} outside -90..=0. Clamping to {}.",
+                self.audio.playback_threshold_dbfs,
+                clamped
+            );
+            self.audio.playback_threshold_dbfs = clamped;
+        }
+
+        // Validate hotkey settings
+        if self
+            .hotkeys
+            .indicator
+            .parse::<crate::hotkey::indicator::IndicatorKind>()
+            .is_err()
+        {
+            tracing::warn!(
+                "Invalid hotkeys.indicator '{}'. Defaulting to 'auto'.",
+                self.hotkeys.indicator
+            );
+            self.hotkeys.indicator = "auto".to_string();
+        }
+        if self.audio.vad_pre_roll_ms > 2000 {
+            tracing::warn!(
+                "audio.vad_pre_roll_ms {} exceeds 2000. Clamping to 2000.",
+                self.audio.vad_pre_roll_ms
+            );
+            self.audio.vad_pre_roll_ms = 2000;
+        }
+        if self.audio.max_utterance_secs > 0 && self.audio.max_utterance_secs < 5 {
+            tracing::warn!(
+                "audio.max_utterance_secs {} is below 5. Clamping to 5.",
+                self.audio.max_utterance_secs
+            );
+            self.audio.max_utterance_secs = 5;
+        }
+        if self.hotkeys.pre_roll_ms > 2000 {
+            tracing::warn!(
+                "hotkeys.pre_roll_ms {} exceeds 2000. Clamping to 2000.",
+                self.hotkeys.pre_roll_ms
+            );
+            self.hotkeys.pre_roll_ms = 2000;
+        }
+        let (_, binding_errors) = crate::hotkey::bindings::parse_bindings(&self.hotkey_bindings());
+        for e in binding_errors {
+            tracing::warn!("Ignoring invalid hotkeys.bindings entry: {}", e);
+        }
+
         // Validate injection settings
         if self.injection.max_total_latency_ms == 0 {
             errors.push("Injection max_total_latency_ms must be >0".to_string());
@@ -538,6 +1290,70 @@ impl Settings {
         if self.injection.min_sample_size == 0 {
             errors.push("Injection min_sample_size must be >0".to_string());
         }
+        if !(0.0..=1.0).contains(&self.injection.min_confidence) {
+            tracing::warn!(
+                "Invalid min_confidence {}. Disabling confidence thresholding.",
+                self.injection.min_confidence
+            );
+            self.injection.min_confidence = 0.0;
+        }
+        if !["notify", "speak", "none"].contains(
+            &self
+                .injection
+                .low_confidence_feedback
+                .to_lowercase()
+                .as_str(),
+        ) {
+            tracing::warn!(
+                "Invalid low_confidence_feedback '{}'. Defaulting to 'notify'.",
+                self.injection.low_confidence_feedback
+            );
+            self.injection.low_confidence_feedback = "notify".to_string();
+        }
+        if !self.injection.audit_text.is_empty()
+            && self
+                .injection
+                .audit_text
+                .parse::<crate::text_injection::types::AuditTextMode>()
+                .is_err()
+        {
+            tracing::warn!(
+                "Invalid audit_text '{}'. Following redact_logs.",
+                self.injection.audit_text
+            );
+            self.injection.audit_text = String::new();
+        }
+        if self
+            .injection
+            .dictation_mode
+            .parse::<crate::text_injection::DictationMode>()
+            .is_err()
+        {
+            tracing::warn!(
+                "Invalid dictation_mode '{}'. Defaulting to 'normal'.",
+                self.injection.dictation_mode
+            );
+            self.injection.dictation_mode = "normal".to_string();
+        }
+        if self
+            .injection
+            .sensitive_content
+            .parse::<crate::text_injection::SensitiveContentAction>()
+            .is_err()
+        {
+            tracing::warn!(
+                "Invalid sensitive_content '{}'. Defaulting to 'off'.",
+                self.injection.sensitive_content
+            );
+            self.injection.sensitive_content = "off".to_string();
+        }
+        if let Err(e) = crate::headless::HeadlessOutput::from_settings(
+            &self.injection.headless_output,
+            &self.injection.headless_output_file,
+        ) {
+            tracing::warn!("Invalid headless output: {}. Defaulting to 'auto'.", e);
+            self.injection.headless_output = "auto".to_string();
+        }
 
         // Validate STT settings
         if self.stt.failover_threshold == 0 {
@@ -546,6 +1362,25 @@ impl Settings {
         if self.stt.failover_cooldown_secs == 0 {
             errors.push("STT failover_cooldown_secs must be >0".to_string());
         }
+        if self
+            .stt
+            .hallucination_filter
+            .parse::<crate::stt::hallucination::HallucinationAction>()
+            .is_err()
+        {
+            tracing::warn!(
+                "Invalid hallucination_filter '{}'. Defaulting to 'off'.",
+                self.stt.hallucination_filter
+            );
+            self.stt.hallucination_filter = "off".to_string();
+        }
+        if self.stt.hallucination_max_chars_per_sec <= 0.0 {
+            tracing::warn!(
+                "Invalid hallucination_max_chars_per_sec {}. Using 30.",
+                self.stt.hallucination_max_chars_per_sec
+            );
+            self.stt.hallucination_max_chars_per_sec = 30.0;
+        }
         if self.stt.model_ttl_secs == 0 {
             errors.push("STT model_ttl_secs must be >0".to_string());
         }
@@ -610,87 +1445,109 @@ impl Settings {
                 );
             }
         }
-
-        if !errors.is_empty() {
-            let error_msg = format!("Critical config validation errors: {:?}", errors);
-            return Err(error_msg);
+        let ws = &self.stt.remote_ws;
+        if !ws.url.starts_with("ws://") && !ws.url.starts_with("wss://") {
+            errors.push(format!(
+                "STT remote_ws url '{}' must start with ws:// or wss://",
+                ws.url
+            ));
+        }
+        if ws.connect_timeout_ms == 0 || ws.final_timeout_ms == 0 {
+            errors.push("STT remote_ws timeouts must be >0".to_string());
         }
 
-        // Log non-critical warnings if any were applied
-        tracing::info!("Configuration validation completed successfully.");
-
-        Ok(())
+        errors
     }
 }
 
-pub(crate) fn discover_plugin_selection_config_path() -> Option<PathBuf> {
-    if let Some(custom) = env::var_os("COLDVOX_PLUGIN_CONFIG_PATH") {
-        let path = PathBuf::from(custom);
-        if path.is_file() {
-            tracing::info!(
-                "Using plugin config from COLDVOX_PLUGIN_CONFIG_PATH: {}",
-                path.display()
-            );
-            return Some(path);
-        }
-
-        tracing::warn!(
-            "COLDVOX_PLUGIN_CONFIG_PATH is set but does not point to an existing file: {}",
-            path.display()
-        );
-        return None;
-    }
+/// System-wide config directory
+const SYSTEM_CONFIG_DIR: &str = "/etc/coldvox";
+
+/// Where a merged config file comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigLayerKind {
+    /// `config/default.toml` of a source checkout
+    Repository,
+    /// `/etc/coldvox/config.toml`
+    System,
+    /// `$XDG_CONFIG_HOME/coldvox/config.toml`
+    User,
+    /// `./coldvox.toml`
+    Project,
+    /// `--config` or `COLDVOX_CONFIG_PATH`
+    Explicit,
+}
 
-    if explicit_startup_config_path().is_some() {
-        return None;
+impl std::fmt::Display for ConfigLayerKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ConfigLayerKind::Repository => "repository",
+            ConfigLayerKind::System => "system",
+            ConfigLayerKind::User => "user",
+            ConfigLayerKind::Project => "project",
+            ConfigLayerKind::Explicit => "explicit",
+        })
     }
-
-    let manifest_dir = env::var("CARGO_MANIFEST_DIR").ok().map(PathBuf::from);
-    let cwd = env::current_dir().ok();
-    discover_plugin_selection_config_path_with(manifest_dir.as_deref(), cwd.as_deref())
 }
 
-fn discover_plugin_selection_config_path_with(
-    manifest_dir: Option<&Path>,
-    cwd: Option<&Path>,
-) -> Option<PathBuf> {
-    if let Some(manifest_dir) = manifest_dir {
-        let candidate = manifest_dir.join("../..").join("config/plugins.json");
-        if candidate.exists() {
-            return Some(candidate);
-        }
-    }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigLayer {
+    pub kind: ConfigLayerKind,
+    pub path: PathBuf,
+}
 
-    if let Some(cwd) = cwd {
-        for ancestor in cwd.ancestors() {
-            let candidate = ancestor.join("config/plugins.json");
-            if candidate.exists() && !is_legacy_app_local_plugin_config_path(&candidate) {
-                return Some(candidate);
-            }
-        }
+impl std::fmt::Display for ConfigLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.kind, self.path.display())
     }
-
-    None
 }
 
-fn explicit_startup_config_path() -> Option<PathBuf> {
-    let custom = env::var_os("COLDVOX_CONFIG_PATH")?;
-    let path = PathBuf::from(custom);
-    path.exists().then_some(path)
+/// `$XDG_CONFIG_HOME`, else `~/.config`
+fn user_config_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
 }
 
-fn is_legacy_app_local_plugin_config_path(path: &Path) -> bool {
-    let mut reversed = path.iter().rev();
-    matches!(
+fn discover_config_layers_with(
+    system_dir: &Path,
+    user_dir: Option<&Path>,
+    cwd: Option<&Path>,
+    manifest_dir: Option<&Path>,
+) -> Vec<ConfigLayer> {
+    let repository = manifest_dir
+        .map(|dir| dir.join("../..").join("config/default.toml"))
+        .filter(|candidate| candidate.is_file())
+        .or_else(|| {
+            cwd?.ancestors()
+                .map(|ancestor| ancestor.join("config/default.toml"))
+                .find(|candidate| candidate.is_file())
+        });
+    // `default.toml` is the name the user file had before layering
+    let user = user_dir.and_then(|dir| {
+        ["config.toml", "default.toml"]
+            .iter()
+            .map(|name| dir.join("coldvox").join(name))
+            .find(|candidate| candidate.is_file())
+    });
+    let candidates = [
+        (ConfigLayerKind::Repository, repository),
         (
-            reversed.next(),
-            reversed.next(),
-            reversed.next(),
-            reversed.next()
+            ConfigLayerKind::System,
+            Some(system_dir.join("config.toml")).filter(|p| p.is_file()),
         ),
-        (Some(file), Some(config), Some(app), Some(crates))
-            if file == "plugins.json" && config == "config" && app == "app" && crates == "crates"
-    )
+        (ConfigLayerKind::User, user),
+        (
+            ConfigLayerKind::Project,
+            cwd.map(|dir| dir.join("coldvox.toml"))
+                .filter(|p| p.is_file()),
+        ),
+    ];
+    candidates
+        .into_iter()
+        .filter_map(|(kind, path)| Some(ConfigLayer { kind, path: path? }))
+        .collect()
 }
 
 pub(crate) fn load_canonical_plugin_selection_config(
@@ -719,20 +1576,29 @@ pub(crate) fn load_canonical_plugin_selection_config(
     Ok(Some(config))
 }
 
-pub mod audio;
 pub mod clock;
+pub mod config_check;
+pub mod doctor;
+pub mod export;
 pub mod foundation;
-pub mod hotkey;
+pub mod headless;
+pub mod listen;
 pub mod probes;
-pub mod runtime;
+pub mod replay;
 pub mod sleep_instrumentation;
-pub mod stt;
+pub mod stats;
 pub mod telemetry;
-pub mod text_injection;
 #[cfg(feature = "tui")]
 pub mod tui;
 pub mod vad;
 
+// Pipeline orchestration lives in coldvox-runtime; keep the paths the binary
+// and its tests have always used
+pub use coldvox_runtime::{
+    audio, black_box, hotkey, idle, low_confidence, notify, pause, recovery, resume, runtime,
+    session_report, stt, text_injection, tts,
+};
+
 #[cfg(test)]
 pub mod test_utils;
 
@@ -761,6 +1627,7 @@ mod tests {
                 gc_policy: None,
                 metrics: None,
                 auto_extract_model: true,
+                decode: Default::default(),
             }),
         );
 
@@ -785,32 +1652,17 @@ mod tests {
         assert_eq!(remote.max_payload_bytes, 2_621_440);
     }
 
+    #[cfg(feature = "ws-remote")]
     #[test]
-    fn discover_plugin_selection_config_path_skips_app_local_copy() {
-        let temp = tempfile::tempdir().expect("create tempdir");
-        let repo_root = temp.path();
-        let root_config_dir = repo_root.join("config");
-        let app_config_dir = repo_root.join("crates/app/config");
-        fs::create_dir_all(&root_config_dir).expect("create root config dir");
-        fs::create_dir_all(&app_config_dir).expect("create app config dir");
-        fs::write(root_config_dir.join("plugins.json"), "{}").expect("write root plugin config");
-        fs::write(app_config_dir.join("plugins.json"), "{}")
-            .expect("write app-local plugin config");
-
-        let resolved = discover_plugin_selection_config_path_with(
-            Some(&repo_root.join("crates/app")),
-            Some(&repo_root.join("crates/app")),
-        )
-        .expect("resolve canonical plugin config path");
-        let resolved = resolved
-            .canonicalize()
-            .expect("canonicalize resolved plugin config path");
-        let expected = repo_root
-            .join("config/plugins.json")
-            .canonicalize()
-            .expect("canonicalize expected plugin config path");
+    fn runtime_ws_remote_config_carries_language_and_endpoint() {
+        let settings = Settings::from_path(PathBuf::from("../../config/default.toml"))
+            .or_else(|_| Settings::from_path(PathBuf::from("config/default.toml")))
+            .expect("load default config");
 
-        assert_eq!(resolved, expected);
+        let remote = settings.runtime_ws_remote_config();
+        assert_eq!(remote.url, "ws://localhost:8765/v1/stream");
+        assert_eq!(remote.language.as_deref(), Some("en"));
+        assert_eq!(remote.sample_rate, 16_000);
     }
 
     #[test]
@@ -854,6 +1706,52 @@ mod tests {
         // the resolved path correctly points to the repo-root config
     }
 
+    #[test]
+    fn config_layers_merge_in_precedence_order() {
+        let temp = tempfile::tempdir().expect("create tempdir");
+        let root = temp.path();
+        let system = root.join("etc");
+        let user = root.join("home/.config");
+        let project = root.join("work/project");
+        fs::create_dir_all(&system).unwrap();
+        fs::create_dir_all(user.join("coldvox")).unwrap();
+        fs::create_dir_all(&project).unwrap();
+        fs::write(system.join("config.toml"), "activation_mode = \"hotkey\"\n").unwrap();
+        fs::write(
+            user.join("coldvox/config.toml"),
+            "activation_mode = \"vad\"\nresampler_quality = \"fast\"\n",
+        )
+        .unwrap();
+        fs::write(
+            project.join("coldvox.toml"),
+            "resampler_quality = \"quality\"\n",
+        )
+        .unwrap();
+
+        let layers = discover_config_layers_with(&system, Some(&user), Some(&project), None);
+        let kinds: Vec<_> = layers.iter().map(|l| l.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ConfigLayerKind::System,
+                ConfigLayerKind::User,
+                ConfigLayerKind::Project
+            ]
+        );
+
+        let mut builder = Settings::defaults().unwrap();
+        for layer in &layers {
+            builder = builder.add_source(File::from(layer.path.as_path()));
+        }
+        let settings: Settings = builder.build().unwrap().try_deserialize().unwrap();
+        assert_eq!(settings.activation_mode, "vad");
+        assert_eq!(settings.resampler_quality, "quality");
+
+        // Missing layers are skipped
+        let none = discover_config_layers_with(&root.join("nowhere"), None, Some(&system), None);
+        assert!(none.is_empty());
+    }
+
     #[test]
     #[serial]
     fn explicit_startup_config_disables_implicit_plugin_overrides() {
@@ -892,47 +1790,4 @@ mod tests {
 
         assert!(resolved.is_none());
     }
-
-    #[test]
-    #[serial]
-    fn invalid_startup_config_keeps_implicit_plugin_overrides_available() {
-        let temp = tempfile::tempdir().expect("create tempdir");
-        let repo_root = temp.path();
-        let root_config_dir = repo_root.join("config");
-        let app_dir = repo_root.join("crates/app");
-        let root_plugins_path = root_config_dir.join("plugins.json");
-        let invalid_config_path = root_config_dir.join("missing.toml");
-        fs::create_dir_all(&root_config_dir).expect("create root config dir");
-        fs::create_dir_all(&app_dir).expect("create app dir");
-        fs::write(&root_plugins_path, "{}").expect("write root plugin config");
-
-        let original_config = env::var_os("COLDVOX_CONFIG_PATH");
-        let original_plugin = env::var_os("COLDVOX_PLUGIN_CONFIG_PATH");
-
-        env::set_var("COLDVOX_CONFIG_PATH", &invalid_config_path);
-        env::remove_var("COLDVOX_PLUGIN_CONFIG_PATH");
-
-        assert!(explicit_startup_config_path().is_none());
-
-        let resolved = discover_plugin_selection_config_path_with(Some(&app_dir), Some(&app_dir))
-            .expect("resolve canonical plugin config path")
-            .canonicalize()
-            .expect("canonicalize resolved plugin config path");
-
-        if let Some(value) = original_config {
-            env::set_var("COLDVOX_CONFIG_PATH", value);
-        } else {
-            env::remove_var("COLDVOX_CONFIG_PATH");
-        }
-        if let Some(value) = original_plugin {
-            env::set_var("COLDVOX_PLUGIN_CONFIG_PATH", value);
-        } else {
-            env::remove_var("COLDVOX_PLUGIN_CONFIG_PATH");
-        }
-
-        let expected = root_plugins_path
-            .canonicalize()
-            .expect("canonicalize expected plugin config path");
-        assert_eq!(resolved, expected);
-    }
 }
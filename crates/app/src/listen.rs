@@ -0,0 +1,169 @@
+//! Headless `coldvox listen --stdout`: capture → VAD → STT with no injection,
+//! transcripts written to stdout as newline-delimited JSON.
+//!
+//! One object per line:
+//!
+//! ```text
+//! {"type":"final","utterance_id":3,"text":"hello world","source":"mic","time":"2026-01-05T09:14:03.120+01:00","confidence":0.91}
+//! {"type":"partial","utterance_id":4,"text":"and th","source":"mic","time":"..."}
+//! {"type":"error","code":"timeout","message":"...","time":"..."}
+//! ```
+//!
+//! Partials are only written with `--partials`. `confidence` is the mean word
+//! confidence and is omitted when the plugin reports no word timings. Logs go
+//! to stderr, so stdout carries transcripts only.
+
+use chrono::{DateTime, Local, SecondsFormat};
+use coldvox_stt::TranscriptionEvent;
+use serde::Serialize;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc;
+
+/// One line of `listen --stdout` output
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TranscriptLine<'a> {
+    Partial {
+        utterance_id: u64,
+        text: &'a str,
+        source: &'a str,
+        time: String,
+    },
+    Final {
+        utterance_id: u64,
+        text: &'a str,
+        source: &'a str,
+        time: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        confidence: Option<f32>,
+    },
+    Error {
+        code: &'a str,
+        message: &'a str,
+        time: String,
+    },
+}
+
+impl<'a> TranscriptLine<'a> {
+    /// Line for `event`, stamped with `at`
+    pub fn new(event: &'a TranscriptionEvent, at: DateTime<Local>) -> Self {
+        let time = at.to_rfc3339_opts(SecondsFormat::Millis, false);
+        match event {
+            TranscriptionEvent::Partial {
+                utterance_id,
+                text,
+                source,
+                ..
+            } => TranscriptLine::Partial {
+                utterance_id: *utterance_id,
+                text,
+                source: source.as_str(),
+                time,
+            },
+            TranscriptionEvent::Final {
+                utterance_id,
+                text,
+                words,
+                source,
+            } => TranscriptLine::Final {
+                utterance_id: *utterance_id,
+                text,
+                source: source.as_str(),
+                time,
+                confidence: words
+                    .as_deref()
+                    .filter(|w| !w.is_empty())
+                    .map(|w| w.iter().map(|w| w.conf).sum::<f32>() / w.len() as f32),
+            },
+            TranscriptionEvent::Error { code, message } => TranscriptLine::Error {
+                code,
+                message,
+                time,
+            },
+        }
+    }
+}
+
+/// Write transcription events to `out` until the pipeline closes the
+/// channel. Returns the write error when the reader goes away (e.g. the
+/// other end of a pipe exited).
+pub async fn write_transcripts<W: AsyncWrite + Unpin>(
+    mut events: mpsc::Receiver<TranscriptionEvent>,
+    partials: bool,
+    mut out: W,
+) -> std::io::Result<()> {
+    while let Some(event) = events.recv().await {
+        if !partials && matches!(event, TranscriptionEvent::Partial { .. }) {
+            continue;
+        }
+        let mut line = serde_json::to_string(&TranscriptLine::new(&event, Local::now()))
+            .map_err(std::io::Error::other)?;
+        line.push('\n');
+        out.write_all(line.as_bytes()).await?;
+        // Flush per line so pipelines see each transcript as it arrives
+        out.flush().await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coldvox_stt::{SourceId, WordInfo};
+
+    fn word(text: &str, conf: f32) -> WordInfo {
+        WordInfo {
+            start: 0.0,
+            end: 0.1,
+            conf,
+            text: text.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn writes_one_json_object_per_line() {
+        let (tx, rx) = mpsc::channel(8);
+        tx.send(TranscriptionEvent::Partial {
+            utterance_id: 1,
+            text: "hel".to_string(),
+            t0: None,
+            t1: None,
+            source: SourceId::MIC,
+        })
+        .await
+        .unwrap();
+        tx.send(TranscriptionEvent::Final {
+            utterance_id: 1,
+            text: "hello \"world\"".to_string(),
+            words: Some(vec![word("hello", 0.5), word("world", 1.0)]),
+            source: SourceId::MIC,
+        })
+        .await
+        .unwrap();
+        tx.send(TranscriptionEvent::Error {
+            code: "timeout".to_string(),
+            message: "no response".to_string(),
+        })
+        .await
+        .unwrap();
+        drop(tx);
+
+        let mut out = Vec::new();
+        write_transcripts(rx, false, &mut out).await.unwrap();
+        let lines: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2, "partials are skipped by default");
+        assert_eq!(lines[0]["type"], "final");
+        assert_eq!(lines[0]["utterance_id"], 1);
+        assert_eq!(lines[0]["text"], "hello \"world\"");
+        assert_eq!(lines[0]["source"], "mic");
+        assert_eq!(lines[0]["confidence"], 0.75);
+        assert!(lines[0]["time"].is_string());
+        assert_eq!(lines[1]["type"], "error");
+        assert_eq!(lines[1]["code"], "timeout");
+    }
+}
@@ -7,25 +7,41 @@
 //   * RUST_LOG=coldvox=info,stt_debug=trace  # Fine-grained per-module control
 // - The logs/ directory is created on startup if missing; file output uses a non-blocking writer.
 // - File layer disables ANSI to keep logs clean for analysis.
+// - `--log-format json` writes one JSON object per line to both outputs; stable
+//   structured events are documented in `coldvox_foundation::events`.
+// - `--otlp-endpoint URL` (feature `otel`) additionally exports spans over OTLP/HTTP,
+//   one trace per utterance (see `coldvox_telemetry::trace`).
 use std::fs;
 use std::path::Path;
 use std::time::Duration;
 use std::time::SystemTime;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
+use coldvox_app::audio::noise::{self, NoiseProfileStore};
+use coldvox_app::headless::HeadlessOutput;
 use coldvox_app::runtime::{self as app_runtime, ActivationMode as RuntimeMode, AppRuntimeOptions};
+use coldvox_app::text_injection::backend::BackendDetector;
 use coldvox_app::Settings;
-use coldvox_audio::{DeviceManager, ResamplerQuality};
-use coldvox_foundation::{AppState, HealthMonitor, ShutdownHandler, StateManager};
+use coldvox_audio::DeviceManager;
+use coldvox_foundation::{AppState, HealthMonitor, ShutdownGuard, ShutdownHandler, StateManager};
 
 #[cfg(feature = "tui")]
 use coldvox_app::tui;
 
-fn init_logging() -> Result<tracing_appender::non_blocking::WorkerGuard, Box<dyn std::error::Error>>
-{
+/// Keeps log and span output flowing until dropped at exit
+struct LoggingGuard {
+    _file: tracing_appender::non_blocking::WorkerGuard,
+    #[cfg(feature = "otel")]
+    _otel: Option<coldvox_app::telemetry::otel::OtelGuard>,
+}
+
+fn init_logging(
+    format: LogFormat,
+    otlp_endpoint: Option<&str>,
+) -> Result<LoggingGuard, Box<dyn std::error::Error>> {
     std::fs::create_dir_all("logs")?;
     let file_appender = RollingFileAppender::new(Rotation::DAILY, "logs", "coldvox.log");
     let (non_blocking_file, guard) = tracing_appender::non_blocking(file_appender);
@@ -38,15 +54,57 @@ fn init_logging() -> Result<tracing_appender::non_blocking::WorkerGuard, Box<dyn
     let log_level = std::env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string());
     let env_filter = EnvFilter::try_new(log_level).unwrap_or_else(|_| EnvFilter::new("info"));
 
-    let stderr_layer = fmt::layer().with_writer(std::io::stderr);
-    let file_layer = fmt::layer().with_writer(non_blocking_file).with_ansi(false);
+    // Span export sits below the filter so RUST_LOG applies to it as well
+    #[cfg(feature = "otel")]
+    let (otel_layer, otel_guard, otel_error) = match otlp_endpoint {
+        Some(endpoint) => match coldvox_app::telemetry::otel::layer(endpoint) {
+            Ok((layer, guard)) => (Some(layer), Some(guard), None),
+            Err(e) => (None, None, Some(e.to_string())),
+        },
+        None => (None, None, None),
+    };
+    #[cfg(not(feature = "otel"))]
+    let otel_layer: Option<tracing_subscriber::layer::Identity> = None;
+
+    let registry = tracing_subscriber::registry()
+        .with(otel_layer)
+        .with(env_filter);
+    match format {
+        LogFormat::Text => {
+            let stderr_layer = fmt::layer().with_writer(std::io::stderr);
+            let file_layer = fmt::layer().with_writer(non_blocking_file).with_ansi(false);
+            registry.with(stderr_layer).with(file_layer).init();
+        }
+        LogFormat::Json => {
+            // Flattened so event fields sit at the top level for jq/Loki
+            let stderr_layer = fmt::layer()
+                .json()
+                .flatten_event(true)
+                .with_writer(std::io::stderr);
+            let file_layer = fmt::layer()
+                .json()
+                .flatten_event(true)
+                .with_writer(non_blocking_file);
+            registry.with(stderr_layer).with(file_layer).init();
+        }
+    }
+
+    #[cfg(feature = "otel")]
+    if let Some(e) = otel_error {
+        tracing::warn!("OTLP span export disabled: {}", e);
+    } else if let Some(endpoint) = otlp_endpoint {
+        tracing::info!("Exporting spans to {}", endpoint);
+    }
+    #[cfg(not(feature = "otel"))]
+    if otlp_endpoint.is_some() {
+        tracing::warn!("--otlp-endpoint ignored: built without the `otel` feature");
+    }
 
-    tracing_subscriber::registry()
-        .with(env_filter)
-        .with(stderr_layer)
-        .with(file_layer)
-        .init();
-    Ok(guard)
+    Ok(LoggingGuard {
+        _file: guard,
+        #[cfg(feature = "otel")]
+        _otel: otel_guard,
+    })
 }
 
 /// Prune rotated log files in `logs/` older than `retention_days` days.
@@ -99,6 +157,16 @@ fn prune_old_logs(retention_days: Option<u64>) {
     }
 }
 
+/// Log output format
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
 #[derive(Parser, Debug)]
 #[command(name = "coldvox", author, version, about = "ColdVox voice pipeline")]
 struct Cli {
@@ -113,6 +181,760 @@ struct Cli {
     /// Exit immediately if all injection methods fail
     #[arg(long = "injection-fail-fast")]
     injection_fail_fast: bool,
+
+    /// Transcribe only: send finals to injection.headless_output instead of
+    /// typing them (automatic when no display server is found)
+    #[arg(long = "no-injection")]
+    no_injection: bool,
+
+    /// Log output format for stderr and logs/coldvox.log
+    #[arg(long = "log-format", value_enum, default_value_t = LogFormat::Text, env = "COLDVOX_LOG_FORMAT")]
+    log_format: LogFormat,
+
+    /// Serve Prometheus metrics on ADDR/metrics, e.g. 127.0.0.1:9464 (feature `metrics-http`)
+    #[arg(
+        long = "metrics-addr",
+        value_name = "ADDR",
+        env = "COLDVOX_METRICS_ADDR"
+    )]
+    metrics_addr: Option<std::net::SocketAddr>,
+
+    /// Export tracing spans to this OTLP/HTTP traces URL, e.g.
+    /// http://localhost:4318/v1/traces (feature `otel`)
+    #[arg(
+        long = "otlp-endpoint",
+        value_name = "URL",
+        env = "COLDVOX_OTLP_ENDPOINT"
+    )]
+    otlp_endpoint: Option<String>,
+
+    /// Settings file to load instead of the system, user and project config
+    /// layers (/etc/coldvox/config.toml, ~/.config/coldvox/config.toml,
+    /// ./coldvox.toml); same as COLDVOX_CONFIG_PATH
+    #[arg(long = "config", value_name = "PATH", global = true)]
+    config: Option<std::path::PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print statistics recorded by previous runs
+    Stats {
+        /// Per-application table: utterances, preferred method, success rate, latency
+        #[arg(long = "per-app")]
+        per_app: bool,
+
+        /// Per-backend week-over-week change in success rate and latency
+        #[arg(long = "trend")]
+        trend: bool,
+
+        /// Injection stats file (defaults to the XDG state directory)
+        #[arg(long = "stats-file")]
+        stats_file: Option<std::path::PathBuf>,
+
+        /// Transcript store directory
+        #[arg(long = "transcripts", default_value = "transcriptions")]
+        transcripts: std::path::PathBuf,
+
+        /// Summary of the most recent session (enable saving with session_log)
+        #[arg(long = "last-session")]
+        last_session: bool,
+
+        /// Session summaries file (defaults to the XDG state directory)
+        #[arg(long = "sessions-file")]
+        sessions_file: Option<std::path::PathBuf>,
+    },
+    /// Show the injection audit log (enable with injection.audit_log)
+    Audit {
+        /// Only injections into this application id
+        #[arg(long)]
+        app: Option<String>,
+
+        /// Only injections on or after this day (YYYY-MM-DD, local time)
+        #[arg(long)]
+        since: Option<chrono::NaiveDate>,
+
+        /// Only failed injections
+        #[arg(long)]
+        failures: bool,
+
+        /// Show at most the newest N entries
+        #[arg(long, short = 'n')]
+        limit: Option<usize>,
+
+        /// Print the matching records as JSON lines
+        #[arg(long)]
+        json: bool,
+
+        /// Audit log file (defaults to the XDG state directory)
+        #[arg(long = "audit-file")]
+        audit_file: Option<std::path::PathBuf>,
+    },
+    /// Measure local model speed on this machine and recommend a model
+    Benchmark {
+        /// Re-run even if a result for this hardware is stored
+        #[arg(long)]
+        force: bool,
+
+        /// Real-time factor the recommended model must meet (defaults to stt.target_rtf)
+        #[arg(long = "target-rtf")]
+        target_rtf: Option<f64>,
+    },
+    /// Write a day's transcripts as a Markdown or HTML journal, grouped by application
+    Export {
+        /// Day to export (YYYY-MM-DD, defaults to today)
+        #[arg(long)]
+        date: Option<chrono::NaiveDate>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: coldvox_app::export::ExportFormat,
+
+        /// Transcript store directory
+        #[arg(long = "transcripts", default_value = "transcriptions")]
+        transcripts: std::path::PathBuf,
+
+        /// Write to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Re-run a recorded session's injection decisions on virtual time
+    Replay {
+        /// Session manifest (transcriptions/<id>/session.json)
+        session: std::path::PathBuf,
+
+        /// Seed for every randomized decision; same seed, same log
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Transcribe the microphone without injecting anything (capture, VAD and STT only)
+    Listen {
+        /// Print transcripts to stdout as newline-delimited JSON
+        #[arg(long)]
+        stdout: bool,
+
+        /// Include partial transcripts, not just finals
+        #[arg(long)]
+        partials: bool,
+    },
+    /// Learn the room's background noise for suppression (stay quiet while it records)
+    CalibrateNoise {
+        /// Seconds of noise to record
+        #[arg(long, default_value_t = 5)]
+        seconds: u64,
+
+        /// Name of this place, for switching between calibrations later
+        #[arg(long, default_value = "default")]
+        location: String,
+
+        /// Input device to calibrate (defaults to the configured device)
+        #[arg(long)]
+        device: Option<String>,
+    },
+    /// List calibrated noise profiles, or switch the active one
+    NoiseProfile {
+        /// Apply the profile calibrated at this location
+        #[arg(long = "use", value_name = "LOCATION")]
+        use_location: Option<String>,
+
+        /// Stop suppressing noise
+        #[arg(long, conflicts_with = "use_location")]
+        off: bool,
+
+        /// Input device the profile belongs to (defaults to the configured device)
+        #[arg(long)]
+        device: Option<String>,
+    },
+    /// Check which injection backends work here, and the injection and STT prerequisites
+    Doctor,
+    /// Compare injection backends' latency and success rate against the GTK test app
+    BenchInjection {
+        /// Text sizes in characters
+        #[arg(long, value_delimiter = ',', default_values_t = coldvox_app::text_injection::bench::DEFAULT_SIZES)]
+        sizes: Vec<usize>,
+
+        /// Runs per backend and size
+        #[arg(long, default_value_t = 5)]
+        iterations: u32,
+
+        /// Only benchmark these methods (e.g. AtspiInsert); repeatable
+        #[arg(long = "method")]
+        methods: Vec<String>,
+
+        /// gtk_test_app executable (defaults to COLDVOX_GTK_TEST_APP or the bundled build)
+        #[arg(long = "target-app")]
+        target_app: Option<std::path::PathBuf>,
+
+        /// How long injected text may take to appear
+        #[arg(long = "timeout-ms", default_value_t = 10_000)]
+        timeout_ms: u64,
+    },
+    /// Check a settings file or print its JSON Schema
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCommand {
+    /// Report syntax errors, unknown keys and invalid values, with their lines
+    Validate {
+        /// Settings file (defaults to the one startup would load)
+        path: Option<std::path::PathBuf>,
+    },
+    /// Print the JSON Schema of the settings file, with defaults
+    Schema,
+}
+
+/// Where a run without injection sends its transcripts
+enum TranscriptSink {
+    /// `listen --stdout`: JSON lines, optionally with partials
+    Json { partials: bool },
+    /// `listen` without `--stdout`: the log only
+    Log,
+    /// `--no-injection`, or no display server
+    Headless(HeadlessOutput),
+}
+
+/// `coldvox listen` and transcription-only runs: drain transcripts until
+/// Ctrl+C, or until stdout's reader goes away
+async fn run_listen(
+    mut app: app_runtime::AppHandle,
+    state_manager: &StateManager,
+    shutdown: &ShutdownGuard,
+    sink: TranscriptSink,
+    session_log: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let stt_rx = app
+        .stt_rx
+        .take()
+        .ok_or("no STT plugin is running; listen needs speech-to-text")?;
+    let writer = async move {
+        match sink {
+            TranscriptSink::Json { partials } => {
+                coldvox_app::listen::write_transcripts(stt_rx, partials, tokio::io::stdout()).await
+            }
+            TranscriptSink::Log => {
+                let mut stt_rx = stt_rx;
+                while let Some(event) = stt_rx.recv().await {
+                    if let coldvox_stt::TranscriptionEvent::Final { text, .. } = event {
+                        tracing::info!("Transcript: {}", coldvox_foundation::scrub::scrub(&text));
+                    }
+                }
+                Ok(())
+            }
+            TranscriptSink::Headless(output) => {
+                coldvox_app::headless::write_finals(stt_rx, &output).await
+            }
+        }
+    };
+    tokio::select! {
+        _ = shutdown.wait() => {
+            tracing::debug!("Shutdown signal received");
+        }
+        result = writer => match result {
+            Ok(()) => tracing::debug!("Transcription stream ended"),
+            Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => {
+                tracing::debug!("stdout closed; stopping");
+            }
+            Err(e) => tracing::error!("Failed to write transcript: {}", e),
+        },
+    }
+
+    tracing::debug!("Beginning graceful shutdown");
+    state_manager.transition(AppState::Stopping)?;
+    report_session(&app, session_log);
+    std::sync::Arc::new(app).shutdown().await;
+    state_manager.transition(AppState::Stopped)?;
+    tracing::debug!("Shutdown complete");
+    Ok(())
+}
+
+/// The calibrated noise profile selected for `device`, if any
+/// Offer the text a crashed run had not injected yet: inject it into the
+/// focused window, export it to a file, or discard it. Without a terminal
+/// to ask on it is exported.
+async fn recover_previous_run(path: &Path, settings: &Settings, can_inject: bool) {
+    use coldvox_app::recovery::{self, Snapshot};
+    use std::io::{BufRead, IsTerminal, Write};
+
+    let snapshot = match Snapshot::load(path) {
+        Ok(Some(snapshot)) if !snapshot.writer_alive() => snapshot,
+        Ok(_) => return,
+        Err(e) => {
+            tracing::warn!("Ignoring unreadable {}: {}", path.display(), e);
+            let _ = recovery::clear(path);
+            return;
+        }
+    };
+    if snapshot.is_empty() {
+        let _ = recovery::clear(path);
+        return;
+    }
+
+    let text = snapshot.text();
+    println!(
+        "The previous session ended unexpectedly at {} with dictation that was never injected:",
+        snapshot.saved_at.format("%Y-%m-%d %H:%M:%S")
+    );
+    println!();
+    println!("  {}", text);
+    println!();
+
+    let choice = if std::io::stdin().is_terminal() {
+        if can_inject {
+            print!("[i]nject into the focused window, [e]xport to a file, [d]iscard? [e] ");
+        } else {
+            print!("[e]xport to a file, [d]iscard? [e] ");
+        }
+        let _ = std::io::stdout().flush();
+        let mut line = String::new();
+        let _ = std::io::stdin().lock().read_line(&mut line);
+        line.trim().to_lowercase()
+    } else {
+        String::new()
+    };
+
+    let mut export = !choice.starts_with('d');
+    if can_inject && choice.starts_with('i') {
+        use coldvox_app::text_injection::{types::InjectionMetrics, StrategyManager};
+
+        println!("Focus the target window; injecting in 3 seconds...");
+        tokio::time::sleep(Duration::from_secs(3)).await;
+        let config = settings.injection.runtime_options().to_config();
+        let metrics = std::sync::Arc::new(InjectionMetrics::default());
+        let mut manager = StrategyManager::new(config, metrics).await;
+        match manager.inject(&text).await {
+            Ok(()) => export = false,
+            Err(e) => println!("Injection failed ({}); exporting instead.", e),
+        }
+    }
+    if export {
+        match snapshot.export(path) {
+            Ok(file) => {
+                println!("Recovered dictation saved to {}", file.display());
+                coldvox_app::notify::send(
+                    "Recovered dictation",
+                    &format!(
+                        "Text from a session that ended unexpectedly was saved to {}",
+                        file.display()
+                    ),
+                    coldvox_app::notify::Urgency::Normal,
+                )
+                .await;
+            }
+            Err(e) => {
+                // Keep the snapshot so the next start can try again
+                tracing::error!("Failed to export recovered dictation: {}", e);
+                return;
+            }
+        }
+    }
+    if let Err(e) = recovery::clear(path) {
+        tracing::warn!("Failed to remove {}: {}", path.display(), e);
+    }
+}
+
+/// Log the session summary and, with `session_log`, append it to the
+/// sessions file
+fn report_session(app: &app_runtime::AppHandle, session_log: bool) {
+    let report = app.session_report();
+    tracing::info!("Session summary:\n{}", report);
+    if !session_log {
+        return;
+    }
+    let Some(path) = coldvox_app::session_report::SessionReport::default_path() else {
+        tracing::warn!("Cannot determine the sessions file location; summary not saved");
+        return;
+    };
+    if let Err(e) = report.append(&path) {
+        tracing::warn!(
+            "Failed to save session summary to {}: {}",
+            path.display(),
+            e
+        );
+    }
+}
+
+/// Black box recorder settings, or None when it is off
+fn black_box_config(settings: &Settings) -> Option<coldvox_app::black_box::BlackBoxConfig> {
+    if settings.black_box_secs == 0 {
+        return None;
+    }
+    let dir = coldvox_app::black_box::BlackBoxConfig::default_dir()?;
+    let snapshot = match serde_json::to_value(settings) {
+        Ok(value) => value,
+        Err(e) => {
+            tracing::warn!("Black box config snapshot failed: {}", e);
+            serde_json::Value::Null
+        }
+    };
+    Some(coldvox_app::black_box::BlackBoxConfig {
+        seconds: settings.black_box_secs,
+        dir,
+        settings: snapshot,
+    })
+}
+
+fn active_noise_profile(device: Option<&str>) -> Option<coldvox_audio_quality::NoiseProfile> {
+    let path = NoiseProfileStore::default_path()?;
+    let store = NoiseProfileStore::load(&path)
+        .inspect_err(|e| tracing::warn!("Failed to load noise profiles: {}", e))
+        .ok()?;
+    let stored = store.active_profile(noise::device_key(device))?;
+    tracing::info!(location = %stored.location, "Using calibrated noise profile");
+    Some(stored.profile.clone())
+}
+
+fn noise_profiles_path() -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
+    NoiseProfileStore::default_path()
+        .ok_or_else(|| "cannot determine the noise profile location; set XDG_STATE_HOME".into())
+}
+
+/// Device the noise commands act on: the flag, else the configured device
+fn noise_device(device: Option<String>) -> String {
+    let device = device.or_else(|| Settings::new().ok().and_then(|s| s.device));
+    noise::device_key(device.as_deref()).to_string()
+}
+
+async fn run_calibrate_noise(
+    seconds: u64,
+    location: &str,
+    device: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let device = noise_device(device);
+    let path = noise_profiles_path()?;
+    let mut store = NoiseProfileStore::load(&path)?;
+
+    eprintln!(
+        "Recording {}s of background noise from {}; please stay quiet...",
+        seconds, device
+    );
+    let capture_device = (device != noise::DEFAULT_DEVICE).then(|| device.clone());
+    let profile = noise::calibrate(capture_device, Duration::from_secs(seconds)).await?;
+    println!(
+        "Noise floor {:.1} dBFS (peak {:.1} dBFS) over {:.1}s",
+        profile.rms_dbfs, profile.peak_dbfs, profile.duration_secs
+    );
+    store.insert(&device, location, profile);
+    store.save(&path)?;
+    println!("Saved as '{}' for {} and made it active", location, device);
+    Ok(())
+}
+
+fn run_noise_profile(
+    use_location: Option<String>,
+    off: bool,
+    device: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let device = noise_device(device);
+    let path = noise_profiles_path()?;
+    let mut store = NoiseProfileStore::load(&path)?;
+
+    if off {
+        store.deactivate(&device);
+        store.save(&path)?;
+        println!("Noise suppression off for {}", device);
+        return Ok(());
+    }
+    if let Some(location) = use_location {
+        if !store.activate(&device, &location) {
+            return Err(format!(
+                "no noise profile for {} at '{}'; run `coldvox calibrate-noise --location {}`",
+                device, location, location
+            )
+            .into());
+        }
+        store.save(&path)?;
+        println!("Using the '{}' noise profile for {}", location, device);
+        return Ok(());
+    }
+
+    if store.profiles.is_empty() {
+        println!("No noise profiles. Run `coldvox calibrate-noise` in a quiet moment.");
+        return Ok(());
+    }
+    for p in &store.profiles {
+        let active = store.active.get(&p.device) == Some(&p.location);
+        println!(
+            "{} {} / {}: noise floor {:.1} dBFS, recorded {}",
+            if active { "*" } else { " " },
+            p.device,
+            p.location,
+            p.profile.rms_dbfs,
+            p.recorded_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+        );
+    }
+    Ok(())
+}
+
+/// `coldvox audit`: print matching audit records, oldest first
+fn run_audit(
+    app: Option<String>,
+    since: Option<chrono::NaiveDate>,
+    failures_only: bool,
+    limit: Option<usize>,
+    json: bool,
+    audit_file: Option<std::path::PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use coldvox_app::text_injection::audit::{self, AuditLog, AuditQuery};
+
+    let path = audit_file
+        .or_else(AuditLog::default_path)
+        .ok_or("cannot determine the audit log location; pass --audit-file")?;
+    let since = since
+        .and_then(|day| day.and_hms_opt(0, 0, 0))
+        .and_then(|midnight| midnight.and_local_timezone(chrono::Local).earliest())
+        .map(|start| start.with_timezone(&chrono::Utc));
+    let query = AuditQuery {
+        app,
+        since,
+        failures_only,
+        limit,
+    };
+    let records = query.apply(audit::read_records(&path)?);
+    if records.is_empty() && !json {
+        println!("No matching injections in {}", path.display());
+    }
+    for record in &records {
+        if json {
+            println!("{}", serde_json::to_string(record)?);
+        } else {
+            println!("{}", audit::format_record(record));
+        }
+    }
+    Ok(())
+}
+
+fn run_config(action: ConfigCommand) -> Result<(), Box<dyn std::error::Error>> {
+    use coldvox_app::config_check;
+
+    match action {
+        ConfigCommand::Validate { path } => {
+            let path = path.or_else(config_check::default_config_path).ok_or(
+                "no config file found (see `coldvox --help` for the locations searched); pass a path",
+            )?;
+            let report = config_check::validate_file(&path)
+                .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+            print!("{}", report);
+            if report.has_errors() {
+                std::process::exit(1);
+            }
+        }
+        ConfigCommand::Schema => config_check::write_schema(&mut std::io::stdout().lock())?,
+    }
+    Ok(())
+}
+
+fn run_last_session(
+    sessions_file: Option<std::path::PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use coldvox_app::session_report::SessionReport;
+
+    let path = sessions_file
+        .or_else(SessionReport::default_path)
+        .ok_or("cannot determine the sessions file location; pass --sessions-file")?;
+    match SessionReport::load_last(&path)? {
+        Some(report) => print!("{}", report),
+        None => println!(
+            "No session summaries recorded yet ({}). Set session_log = true to save them.",
+            path.display()
+        ),
+    }
+    Ok(())
+}
+
+fn run_stats(
+    per_app: bool,
+    trend: bool,
+    stats_file: Option<std::path::PathBuf>,
+    transcripts: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use coldvox_app::text_injection::app_stats::AppStatsStore;
+
+    let path = stats_file
+        .or_else(AppStatsStore::default_path)
+        .ok_or("cannot determine the stats file location; pass --stats-file")?;
+    let store = AppStatsStore::load(&path)?;
+
+    if per_app {
+        print!("{}", coldvox_app::stats::render_per_app(&store));
+    }
+    if trend {
+        if per_app {
+            println!();
+        }
+        let today = chrono::Local::now().date_naive();
+        print!("{}", coldvox_app::stats::render_trend(&store, today));
+    }
+    if !per_app && !trend {
+        let apps = store.apps();
+        let utterances: u64 = apps.values().map(|a| a.utterances).sum();
+        let successes: u64 = apps.values().map(|a| a.successes).sum();
+        println!(
+            "Injections: {} across {} application(s), {} succeeded ({})",
+            utterances,
+            apps.len(),
+            successes,
+            path.display()
+        );
+        println!(
+            "Use --per-app for a per-application breakdown, --trend for week-over-week changes."
+        );
+    }
+    if let Some(totals) = coldvox_app::stats::transcript_totals(transcripts) {
+        println!(
+            "\nTranscript store: {} utterance(s) in {} session(s) ({})",
+            totals.utterances,
+            totals.sessions,
+            transcripts.display()
+        );
+    }
+    Ok(())
+}
+
+async fn run_benchmark(
+    force: bool,
+    target_rtf: Option<f64>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use coldvox_app::stt::model_benchmark::{ensure_benchmark, BenchmarkResult};
+
+    let target_rtf =
+        target_rtf.unwrap_or_else(|| Settings::new().map_or(0.5, |s| s.stt.target_rtf));
+    let path =
+        BenchmarkResult::default_path().ok_or("cannot determine the benchmark file location")?;
+    if force {
+        let _ = fs::remove_file(&path);
+    }
+    let result = ensure_benchmark(&path, target_rtf).await?;
+
+    println!(
+        "Hardware: {} ({} cores, {} MB)",
+        result.hardware.cpu_model, result.hardware.cores, result.hardware.total_mem_mb
+    );
+    for (model, rtf) in &result.rtf {
+        println!("  {:<6} RTF {:.3}", model, rtf);
+    }
+    match &result.recommended {
+        Some(model) => println!(
+            "Recommended model for RTF <= {}: {} (MOONSHINE_MODEL={})",
+            result.target_rtf, model, model
+        ),
+        None => println!(
+            "No local model meets RTF <= {}; consider a remote backend",
+            result.target_rtf
+        ),
+    }
+    println!("Stored in {}", path.display());
+    Ok(())
+}
+
+async fn run_doctor() -> Result<(), Box<dyn std::error::Error>> {
+    use coldvox_app::doctor;
+    use coldvox_app::text_injection::prerequisites::CheckStatus;
+    use coldvox_app::text_injection::{types::InjectionMetrics, StrategyManager};
+
+    let settings = Settings::new().unwrap_or_default();
+    let config = settings.injection.runtime_options().to_config();
+    let metrics = std::sync::Arc::new(InjectionMetrics::default());
+    let manager = StrategyManager::new(config, metrics).await;
+
+    print!(
+        "{}",
+        doctor::render_backends(&manager.capability_report().await)
+    );
+    println!(
+        "Backends are registered when compiled in, enabled in [injection] and detected at startup."
+    );
+    println!();
+
+    let injection_checks =
+        coldvox_app::text_injection::prerequisites::check_injection_prerequisites().await;
+    print!(
+        "{}",
+        doctor::render("Injection prerequisites", &injection_checks)
+    );
+    println!();
+    let plugins = coldvox_app::stt::plugin_manager::SttPluginManager::new().list_plugins_sync();
+    let stt_checks = doctor::stt_checks(&settings.stt, &plugins);
+    print!("{}", doctor::render("Speech to text", &stt_checks));
+
+    let failures = injection_checks
+        .iter()
+        .chain(&stt_checks)
+        .filter(|c| c.status == CheckStatus::Fail)
+        .count();
+    if failures > 0 {
+        println!();
+        println!(
+            "{} check(s) failed; backends that depend on them will be skipped.",
+            failures
+        );
+    }
+    Ok(())
+}
+
+async fn run_bench_injection(
+    sizes: Vec<usize>,
+    iterations: u32,
+    methods: Vec<String>,
+    target_app: Option<std::path::PathBuf>,
+    timeout_ms: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use coldvox_app::text_injection::bench::{self, BenchOptions};
+    use coldvox_app::text_injection::InjectionMethod;
+
+    let target_app = target_app.or_else(bench::gtk_test_app_path).ok_or(
+        "GTK test app not found; build it with `cargo build -p coldvox-text-injection \
+         --features real-injection-tests` or pass --target-app",
+    )?;
+    let settings = Settings::new().unwrap_or_default();
+    let config = settings.injection.runtime_options().to_config();
+    let opts = BenchOptions {
+        sizes,
+        iterations,
+        timeout: Duration::from_millis(timeout_ms),
+        methods: methods
+            .iter()
+            .map(|m| InjectionMethod::from_name(m))
+            .collect(),
+        ..BenchOptions::new(target_app)
+    };
+
+    let rows = bench::run(config, &opts).await?;
+    print!("{}", bench::render_table(&rows));
+    Ok(())
+}
+
+/// Apply the benchmark's model choice unless the user picked one explicitly
+async fn auto_select_model(target_rtf: f64) {
+    use coldvox_app::stt::model_benchmark::{ensure_benchmark, BenchmarkResult};
+
+    if std::env::var_os("MOONSHINE_MODEL").is_some() {
+        tracing::debug!("MOONSHINE_MODEL is set; skipping automatic model selection");
+        return;
+    }
+    let Some(path) = BenchmarkResult::default_path() else {
+        return;
+    };
+    match ensure_benchmark(&path, target_rtf).await {
+        Ok(result) => match result.recommended {
+            Some(model) => {
+                tracing::info!("Auto-selected Moonshine model '{}' from benchmark", model);
+                std::env::set_var("MOONSHINE_MODEL", model);
+            }
+            None => tracing::warn!(
+                "No local model meets the RTF target {}; keeping the default model",
+                target_rtf
+            ),
+        },
+        Err(e) => tracing::warn!("Automatic model selection skipped: {}", e),
+    }
 }
 
 #[tokio::main]
@@ -123,7 +945,101 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         "PIPEWIRE_PROPS",
         "{ application.name=ColdVox media.role=capture }",
     );
-    let _log_guard = init_logging()?;
+    let cli = Cli::parse();
+    if let Some(path) = &cli.config {
+        if !path.is_file() {
+            return Err(format!("config file not found: {}", path.display()).into());
+        }
+        // Settings and plugin config discovery both honour the variable
+        std::env::set_var("COLDVOX_CONFIG_PATH", path);
+    }
+    // Set when nothing is injected and transcripts go to a sink instead
+    let mut listen = None;
+    match cli.command {
+        Some(Command::Stats {
+            per_app,
+            trend,
+            stats_file,
+            transcripts,
+            last_session,
+            sessions_file,
+        }) => {
+            if last_session {
+                return run_last_session(sessions_file);
+            }
+            return run_stats(per_app, trend, stats_file, &transcripts);
+        }
+        Some(Command::Audit {
+            app,
+            since,
+            failures,
+            limit,
+            json,
+            audit_file,
+        }) => return run_audit(app, since, failures, limit, json, audit_file),
+        Some(Command::Benchmark { force, target_rtf }) => {
+            return run_benchmark(force, target_rtf).await;
+        }
+        Some(Command::Export {
+            date,
+            format,
+            transcripts,
+            output,
+        }) => {
+            let date = date.unwrap_or_else(|| chrono::Local::now().date_naive());
+            let entries = coldvox_app::export::load_day(&transcripts, date);
+            let journal = coldvox_app::export::render(date, &entries, format);
+            match output {
+                Some(path) => std::fs::write(&path, journal)?,
+                None => print!("{}", journal),
+            }
+            return Ok(());
+        }
+        Some(Command::Replay { session, seed }) => {
+            let recorded = coldvox_app::replay::load_session(&session)?;
+            let config = Settings::new()
+                .unwrap_or_default()
+                .injection
+                .runtime_options()
+                .to_config();
+            print!(
+                "{}",
+                coldvox_app::replay::replay_session(&recorded, seed, &config)?
+            );
+            return Ok(());
+        }
+        Some(Command::Listen { stdout, partials }) => {
+            listen = Some(if stdout {
+                TranscriptSink::Json { partials }
+            } else {
+                TranscriptSink::Log
+            })
+        }
+        Some(Command::CalibrateNoise {
+            seconds,
+            location,
+            device,
+        }) => return run_calibrate_noise(seconds, &location, device).await,
+        Some(Command::NoiseProfile {
+            use_location,
+            off,
+            device,
+        }) => return run_noise_profile(use_location, off, device),
+        Some(Command::Doctor) => return run_doctor().await,
+        Some(Command::BenchInjection {
+            sizes,
+            iterations,
+            methods,
+            target_app,
+            timeout_ms,
+        }) => {
+            return run_bench_injection(sizes, iterations, methods, target_app, timeout_ms).await;
+        }
+        Some(Command::Config { action }) => return run_config(action),
+        None => {}
+    }
+
+    let _log_guard = init_logging(cli.log_format, cli.otlp_endpoint.as_deref())?;
     // Prune old rotated logs. Set COLDVOX_LOG_RETENTION_DAYS=0 to disable pruning.
     let retention_days = std::env::var("COLDVOX_LOG_RETENTION_DAYS")
         .ok()
@@ -131,7 +1047,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     prune_old_logs(retention_days);
     tracing::info!("Starting ColdVox application");
 
-    let cli = Cli::parse();
     let mut settings = Settings::new().unwrap_or_else(|e| {
         tracing::error!("Failed to load settings: {}", e);
         Settings::default()
@@ -154,6 +1069,38 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
+    // Without a display server every injection attempt would fail; run
+    // transcription-only instead (the TUI shows transcripts itself)
+    let headless = BackendDetector::new(Default::default()).is_headless();
+    let no_injection = listen.is_some() || cli.no_injection || headless;
+    if no_injection && listen.is_none() {
+        let output = HeadlessOutput::from_settings(
+            &settings.injection.headless_output,
+            &settings.injection.headless_output_file,
+        )
+        .unwrap_or_else(|_| HeadlessOutput::auto());
+        if headless {
+            tracing::info!("No display server found; running transcription-only");
+        }
+        if !(cfg!(feature = "tui") && cli.tui) {
+            tracing::info!("Injection disabled; transcripts go to {}", output);
+            listen = Some(TranscriptSink::Headless(output));
+        }
+    }
+
+    // `coldvox listen` keeps stdout for transcripts; the next interactive run
+    // picks the snapshot up
+    let recovery_path =
+        coldvox_app::recovery::Snapshot::default_path().filter(|_| settings.crash_recovery);
+    let interactive = matches!(listen, None | Some(TranscriptSink::Headless(_)));
+    if let Some(path) = recovery_path.as_deref().filter(|_| interactive) {
+        recover_previous_run(path, &settings, !no_injection).await;
+    }
+
+    if settings.stt.auto_select_model {
+        auto_select_model(settings.stt.target_rtf).await;
+    }
+
     // Unified runtime start
     let state_manager = StateManager::new();
     let _health_monitor = HealthMonitor::new(Duration::from_secs(10)).start();
@@ -162,77 +1109,63 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     state_manager.transition(AppState::Running)?;
     tracing::info!("Application state: Running");
 
-    // Build STT configuration from settings
-    let stt_selection = {
-        use coldvox_stt::plugin::{FailoverConfig, GcPolicy, MetricsConfig, PluginSelectionConfig};
-
-        let failover = FailoverConfig {
-            failover_threshold: settings.stt.failover_threshold,
-            failover_cooldown_secs: settings.stt.failover_cooldown_secs,
-        };
-
-        let gc_policy = GcPolicy {
-            model_ttl_secs: settings.stt.model_ttl_secs,
-            enabled: !settings.stt.disable_gc,
-        };
-
-        let metrics = MetricsConfig {
-            log_interval_secs: if settings.stt.metrics_log_interval_secs == 0 {
-                None
-            } else {
-                Some(settings.stt.metrics_log_interval_secs)
-            },
-            debug_dump_events: settings.stt.debug_dump_events,
-        };
-
-        Some(PluginSelectionConfig {
-            preferred_plugin: settings.stt.preferred,
-            fallback_plugins: settings.stt.fallbacks,
-            require_local: settings.stt.require_local,
-            max_memory_mb: settings.stt.max_mem_mb,
-            required_language: settings.stt.language,
-            failover: Some(failover),
-            gc_policy: Some(gc_policy),
-            metrics: Some(metrics),
-            auto_extract_model: settings.stt.auto_extract,
-        })
-    };
-
-    let device = settings.device.clone();
-    let resampler_quality = match settings.resampler_quality.to_lowercase().as_str() {
-        "fast" => ResamplerQuality::Fast,
-        "quality" => ResamplerQuality::Quality,
-        _ => ResamplerQuality::Balanced,
-    };
-    let activation_mode = match settings.activation_mode.as_str() {
-        "vad" => RuntimeMode::Vad,
-        "hotkey" => RuntimeMode::Hotkey,
-        _ => RuntimeMode::Vad,
-    };
-
     let mut opts = AppRuntimeOptions {
-        device,
-        resampler_quality,
-        activation_mode,
-        stt_selection,
-        enable_device_monitor: settings.enable_device_monitor,
-        capture_buffer_samples: settings.audio.capture_buffer_samples,
-        ..Default::default()
+        metrics_addr: cli.metrics_addr,
+        noise_profile: active_noise_profile(settings.device.as_deref()),
+        black_box: black_box_config(&settings),
+        ..settings.runtime_options()
     };
 
-    opts.injection = Some(coldvox_app::runtime::InjectionOptions {
-        enable: true,
-        allow_kdotool: settings.injection.allow_kdotool,
-        allow_enigo: settings.injection.allow_enigo,
-        inject_on_unknown_focus: settings.injection.inject_on_unknown_focus,
-        max_total_latency_ms: Some(settings.injection.max_total_latency_ms),
-        per_method_timeout_ms: Some(settings.injection.per_method_timeout_ms),
-        cooldown_initial_ms: Some(settings.injection.cooldown_initial_ms),
-        fail_fast: settings.injection.fail_fast,
-    });
+    // Listen mode runs without desktop integration, so no hotkeys
+    if listen.is_some() {
+        opts.activation_mode = RuntimeMode::Vad;
+        opts.indicator = coldvox_app::hotkey::indicator::IndicatorKind::Off;
+    }
+    if !no_injection {
+        opts.injection = Some(settings.injection.runtime_options());
+        opts.recovery_path = recovery_path;
+    }
     let app = app_runtime::start(opts)
         .await
         .map_err(|e| e as Box<dyn std::error::Error>)?;
+    // Surface STT failovers to the user, not just the logs
+    if let Some(mut failover_rx) = app.subscribe_stt_failover().await {
+        tokio::spawn(async move {
+            use tokio::sync::broadcast::error::RecvError;
+            loop {
+                match failover_rx.recv().await {
+                    Ok(notice) => {
+                        tracing::warn!(
+                            failed_plugin = %notice.failed_plugin,
+                            new_plugin = ?notice.new_plugin,
+                            error_class = notice.error_class.label(),
+                            "{}; hint: {}",
+                            notice.summary(),
+                            notice.hint
+                        );
+                        let urgency = if notice.new_plugin.is_some() {
+                            coldvox_app::notify::Urgency::Normal
+                        } else {
+                            coldvox_app::notify::Urgency::Critical
+                        };
+                        let body = format!("{}\n\nSuggested fix: {}", notice.error, notice.hint);
+                        coldvox_app::notify::send(&notice.summary(), &body, urgency).await;
+                    }
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    if settings.audio.quality_warnings && settings.audio.quality_notifications {
+        coldvox_app::audio::quality::spawn_quality_notifications(app.subscribe_quality());
+    }
+
+    if let Some(sink) = listen {
+        return run_listen(app, &state_manager, &shutdown, sink, settings.session_log).await;
+    }
+
     // make sharable for spawn + shutdown
     let app = std::sync::Arc::new(app);
 
@@ -314,6 +1247,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Shutdown
     tracing::debug!("Beginning graceful shutdown");
     state_manager.transition(AppState::Stopping)?;
+    report_session(&app, settings.session_log);
     // Shutdown directly on the Arc<AppHandle>
     app.shutdown().await;
     state_manager.transition(AppState::Stopped)?;
@@ -13,7 +13,7 @@ impl TextInjectionProbe {
     pub async fn run(_ctx: &TestContext) -> Result<LiveTestResult, TestError> {
         let config = InjectionConfig::default();
 
-        let injection_metrics = Arc::new(std::sync::Mutex::new(InjectionMetrics::default()));
+        let injection_metrics = Arc::new(InjectionMetrics::default());
 
         // Create strategy manager
         let mut manager = StrategyManager::new(config, injection_metrics.clone()).await;
@@ -24,7 +24,7 @@ impl TextInjectionProbe {
         let duration = start_time.elapsed().as_millis() as u64;
 
         // Collect metrics
-        let injection_metrics_guard = injection_metrics.lock().unwrap();
+        let injection_metrics_guard = injection_metrics.snapshot();
         let mut metrics_map = HashMap::new();
         metrics_map.insert("success".to_string(), json!(result.is_ok()));
         metrics_map.insert("duration_ms".to_string(), json!(duration));
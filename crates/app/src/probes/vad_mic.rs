@@ -95,6 +95,7 @@ impl VadMicCheck {
             frame_size_samples: 512,
             sample_rate_hz: 16_000,
             resampler_quality: ResamplerQuality::Balanced,
+            ..Default::default()
         };
 
         let frame_reader = FrameReader::new(
@@ -115,6 +116,8 @@ impl VadMicCheck {
                 threshold: 0.2,
                 ..Default::default()
             },
+            energy: Default::default(),
+            vote: Default::default(),
             frame_size_samples: 512,
             sample_rate_hz: 16000, // Silero requires 16kHz - resampler will handle conversion
         };
@@ -0,0 +1,208 @@
+//! `coldvox replay`: re-run a recorded session through the injection decision
+//! logic and print a decision log.
+//!
+//! Input is a transcript store session manifest (`transcriptions/<id>/session.json`).
+//! Finals are fed to an [`InjectionSession`] on virtual time at their recorded
+//! end offsets, ticking at the injection processor's check interval. Each
+//! injection also logs the keystroke rate the pacer picks for it under the
+//! given injection config. Timing comes only from the file, the pacer's
+//! jitter (when `keystroke_jitter_pct` is set) from an RNG seeded with
+//! `--seed`, and utterance IDs from a source of the run's own starting at 1,
+//! so two runs over the same file and seed print byte-identical logs. No
+//! process-wide state is touched.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::DateTime;
+use coldvox_foundation::clock::{Clock, TestClock};
+use coldvox_foundation::rng::seeded_rng;
+use coldvox_stt::UtteranceIds;
+use serde::Deserialize;
+
+use crate::text_injection::pacing::KeystrokePacer;
+use crate::text_injection::types::{InjectionConfig, InjectionMetrics};
+use crate::text_injection::{InjectionSession, SessionConfig, SessionState};
+
+/// Matches the injection processor's silence check interval
+const TICK: Duration = Duration::from_millis(100);
+/// Give up draining the session this long after the last final
+const DRAIN_LIMIT: Duration = Duration::from_secs(60);
+/// Pacing key for the replayed target
+const REPLAY_APP: &str = "replay";
+
+/// The parts of a session manifest replay needs
+#[derive(Debug, Deserialize)]
+pub struct RecordedSession {
+    pub started_at: String,
+    #[serde(default)]
+    pub utterances: Vec<RecordedUtterance>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordedUtterance {
+    pub ended_at: String,
+    pub text: String,
+}
+
+/// Load a session manifest
+pub fn load_session(path: &Path) -> Result<RecordedSession, String> {
+    let raw = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&raw).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+/// Replay `session`, pacing typed injections as `config` would, and return
+/// the decision log
+pub fn replay_session(
+    session: &RecordedSession,
+    seed: u64,
+    config: &InjectionConfig,
+) -> Result<String, String> {
+    let start = DateTime::parse_from_rfc3339(&session.started_at)
+        .map_err(|e| format!("Bad session start time '{}': {}", session.started_at, e))?;
+    let mut finals = session
+        .utterances
+        .iter()
+        .map(|u| {
+            let end = DateTime::parse_from_rfc3339(&u.ended_at)
+                .map_err(|e| format!("Bad utterance end time '{}': {}", u.ended_at, e))?;
+            let offset = (end - start).to_std().unwrap_or_default();
+            Ok((offset, u.text.as_str()))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    // Stable: utterances ending together keep their recorded order
+    finals.sort_by_key(|(offset, _)| *offset);
+
+    let clock = Arc::new(TestClock::new());
+    let base = clock.now();
+    let metrics = Arc::new(InjectionMetrics::default());
+    let mut injection =
+        InjectionSession::new_with_clock(SessionConfig::default(), metrics, clock.clone());
+    let pacer = KeystrokePacer::with_rng(config, seeded_rng(seed));
+    let ids = UtteranceIds::new(1);
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "replay seed={} utterances={} tick={}ms",
+        seed,
+        finals.len(),
+        TICK.as_millis()
+    );
+
+    let last_offset = finals.last().map(|(offset, _)| *offset).unwrap_or_default();
+    let mut pending = finals.into_iter().peekable();
+    let mut state = injection.state();
+    loop {
+        let elapsed = clock.now() - base;
+        let at = elapsed.as_millis();
+
+        let mut log_transition = |out: &mut String, injection: &InjectionSession| {
+            if injection.state() != state {
+                let _ = writeln!(out, "{at:>8}ms state {} -> {}", state, injection.state());
+                state = injection.state();
+            }
+        };
+
+        while let Some((_, text)) = pending.next_if(|(offset, _)| *offset <= elapsed) {
+            let id = ids.next_id();
+            let _ = writeln!(
+                out,
+                "{at:>8}ms final #{id} ({} chars)",
+                text.chars().count()
+            );
+            injection.add_transcription(text.to_string());
+            log_transition(&mut out, &injection);
+        }
+
+        let inject = injection.should_inject();
+        log_transition(&mut out, &injection);
+        if inject {
+            let text = injection.take_buffer();
+            let _ = writeln!(
+                out,
+                "{at:>8}ms inject {:?} at {}cps",
+                text,
+                pacer.next_rate_for(REPLAY_APP)
+            );
+            state = injection.state();
+        }
+
+        if pending.peek().is_none() && state == SessionState::Idle {
+            break;
+        }
+        if elapsed > last_offset + DRAIN_LIMIT {
+            let _ = writeln!(out, "{at:>8}ms gave up with session {}", state);
+            break;
+        }
+        clock.advance(TICK);
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> RecordedSession {
+        serde_json::from_str(
+            r#"{
+                "session_id": "s1",
+                "started_at": "2026-01-01T10:00:00+00:00",
+                "utterances": [
+                    {"ended_at": "2026-01-01T10:00:01.250+00:00", "text": "hello there"},
+                    {"ended_at": "2026-01-01T10:00:03+00:00", "text": "second one."}
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn replay_is_reproducible() {
+        let config = InjectionConfig::default();
+        let first = replay_session(&session(), 7, &config).unwrap();
+        let second = replay_session(&session(), 7, &config).unwrap();
+        assert_eq!(first, second);
+        assert!(first.starts_with("replay seed=7 utterances=2"));
+        assert!(first.contains("    1300ms final #1 (11 chars)"));
+        assert!(first.contains("inject \"second one.\""));
+    }
+
+    #[test]
+    fn seed_drives_pacing_jitter() {
+        let config = InjectionConfig {
+            keystroke_jitter_pct: 25,
+            ..Default::default()
+        };
+        let session = RecordedSession {
+            started_at: "2026-01-01T10:00:00+00:00".to_string(),
+            utterances: (1..=8)
+                .map(|i| RecordedUtterance {
+                    ended_at: format!("2026-01-01T10:00:{:02}+00:00", i * 5),
+                    text: format!("sentence {i}."),
+                })
+                .collect(),
+        };
+        let rates = |seed, config: &InjectionConfig| {
+            replay_session(&session, seed, config)
+                .unwrap()
+                .lines()
+                .filter(|line| line.contains(" inject "))
+                .map(|line| line.rsplit(' ').next().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        let first = rates(7, &config);
+        assert_eq!(first.len(), 8);
+        assert_eq!(first, rates(7, &config));
+        assert_ne!(first, rates(8, &config));
+
+        // Jitter is off by default: every injection types at the same rate
+        let steady = rates(7, &InjectionConfig::default());
+        assert!(steady.iter().all(|rate| *rate == steady[0]));
+    }
+}
@@ -0,0 +1,294 @@
+//! `coldvox stats`: reports built from state persisted by previous runs.
+//!
+//! Per-application numbers and daily per-backend aggregates come from the
+//! injection stats store written by the text-injection strategy manager; the
+//! transcript store (session manifests under `transcriptions/`) supplies
+//! overall utterance totals.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use chrono::{Duration, NaiveDate};
+
+use crate::text_injection::app_stats::{AppStatsStore, DailyMethodStats, LATENCY_BUCKETS_MS};
+
+/// Both weeks need this many attempts before a change is called out
+const TREND_MIN_ATTEMPTS: u64 = 5;
+/// Success-rate drop (percentage points) flagged as a degradation
+const TREND_SUCCESS_DROP_PP: f64 = 10.0;
+/// p95 growth flagged as a degradation: at least 1.5x and at least this many ms
+const TREND_P95_GROWTH_MS: u64 = 50;
+
+/// Render the per-application table, busiest apps first.
+pub fn render_per_app(store: &AppStatsStore) -> String {
+    let mut out = String::new();
+    if store.apps().is_empty() {
+        let _ = writeln!(
+            out,
+            "No per-application injection stats recorded yet ({}).",
+            store.path().display()
+        );
+        return out;
+    }
+
+    let mut rows: Vec<_> = store.apps().iter().collect();
+    rows.sort_by(|(a_id, a), (b_id, b)| b.utterances.cmp(&a.utterances).then(a_id.cmp(b_id)));
+
+    let app_width = rows
+        .iter()
+        .map(|(id, _)| id.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp("Application".len(), 40);
+
+    let _ = writeln!(
+        out,
+        "{:<app_width$}  {:>10}  {:<24}  {:>8}  {:>11}",
+        "Application", "Utterances", "Preferred method", "Success", "Avg latency"
+    );
+    let _ = writeln!(out, "{}", "-".repeat(app_width + 63));
+    for (app_id, stats) in rows {
+        let app: String = if app_id.chars().count() > app_width {
+            let truncated: String = app_id.chars().take(app_width - 1).collect();
+            format!("{}…", truncated)
+        } else {
+            app_id.clone()
+        };
+        let method = stats
+            .preferred_method()
+            .map(|m| m.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let latency = stats
+            .avg_latency_ms()
+            .map(|ms| format!("{:.0} ms", ms))
+            .unwrap_or_else(|| "-".to_string());
+        let _ = writeln!(
+            out,
+            "{:<app_width$}  {:>10}  {:<24}  {:>7.1}%  {:>11}",
+            app,
+            stats.utterances,
+            method,
+            stats.success_rate() * 100.0,
+            latency
+        );
+    }
+    out
+}
+
+fn format_latency(ms: Option<u64>) -> String {
+    match ms {
+        None => "-".to_string(),
+        Some(u64::MAX) => format!(">{}", LATENCY_BUCKETS_MS[LATENCY_BUCKETS_MS.len() - 1]),
+        Some(ms) => format!("<={}", ms),
+    }
+}
+
+/// Week-over-week per-backend table: the 7 days ending `today` against the 7
+/// days before. Latencies are histogram bucket bounds in ms.
+pub fn render_trend(store: &AppStatsStore, today: NaiveDate) -> String {
+    let mut out = String::new();
+    let this_week = store.window(today, 7);
+    let last_week = store.window(today - Duration::days(7), 7);
+    let methods: BTreeSet<_> = this_week.keys().chain(last_week.keys()).collect();
+    if methods.is_empty() {
+        let _ = writeln!(
+            out,
+            "No daily injection stats in the last two weeks ({}).",
+            store.path().display()
+        );
+        return out;
+    }
+
+    let _ = writeln!(
+        out,
+        "Week ending {} vs the week before (latency in ms)",
+        today
+    );
+    let _ = writeln!(
+        out,
+        "{:<24}  {:>8}  {:>8}  {:>9}  {:>7}  {:>7}  {:>7}  Note",
+        "Backend", "Attempts", "Success", "Δ Success", "p50", "p95", "Last p95"
+    );
+    let _ = writeln!(out, "{}", "-".repeat(92));
+    let empty = DailyMethodStats::default();
+    for method in methods {
+        let now = this_week.get(method).unwrap_or(&empty);
+        let before = last_week.get(method).unwrap_or(&empty);
+        let success = now
+            .success_rate()
+            .map(|r| format!("{:.1}%", r * 100.0))
+            .unwrap_or_else(|| "-".to_string());
+        let delta = match (now.success_rate(), before.success_rate()) {
+            (Some(a), Some(b)) => format!("{:+.1}pp", (a - b) * 100.0),
+            _ => "-".to_string(),
+        };
+        let note = trend_note(now, before);
+        let _ = writeln!(
+            out,
+            "{:<24}  {:>8}  {:>8}  {:>9}  {:>7}  {:>7}  {:>7}  {}",
+            method.to_string(),
+            now.attempts(),
+            success,
+            delta,
+            format_latency(now.latency_percentile_ms(0.5)),
+            format_latency(now.latency_percentile_ms(0.95)),
+            format_latency(before.latency_percentile_ms(0.95)),
+            note
+        );
+    }
+    out
+}
+
+fn trend_note(now: &DailyMethodStats, before: &DailyMethodStats) -> &'static str {
+    if before.attempts() == 0 {
+        return if now.attempts() > 0 { "new" } else { "" };
+    }
+    if now.attempts() == 0 {
+        return "unused";
+    }
+    if now.attempts() < TREND_MIN_ATTEMPTS || before.attempts() < TREND_MIN_ATTEMPTS {
+        return "";
+    }
+    let success_drop = before.success_rate().unwrap_or(0.0) - now.success_rate().unwrap_or(0.0);
+    let slower = match (
+        now.latency_percentile_ms(0.95),
+        before.latency_percentile_ms(0.95),
+    ) {
+        (Some(a), Some(b)) => {
+            a > b.saturating_add(TREND_P95_GROWTH_MS) && a as f64 >= b as f64 * 1.5
+        }
+        _ => false,
+    };
+    if success_drop * 100.0 >= TREND_SUCCESS_DROP_PP {
+        "DEGRADED: success"
+    } else if slower {
+        "DEGRADED: latency"
+    } else {
+        ""
+    }
+}
+
+/// Totals from the transcript store.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TranscriptTotals {
+    pub sessions: usize,
+    pub utterances: usize,
+}
+
+/// Count sessions and utterances in a transcript store laid out as
+/// `<dir>/<date>/<session>/session.json`. Unreadable manifests are skipped.
+pub fn transcript_totals(dir: &Path) -> Option<TranscriptTotals> {
+    let mut totals = TranscriptTotals::default();
+    for date_dir in fs::read_dir(dir).ok()?.flatten() {
+        let Ok(sessions) = fs::read_dir(date_dir.path()) else {
+            continue;
+        };
+        for session in sessions.flatten() {
+            let manifest = session.path().join("session.json");
+            let Ok(content) = fs::read_to_string(&manifest) else {
+                continue;
+            };
+            let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) else {
+                continue;
+            };
+            totals.sessions += 1;
+            totals.utterances += json["utterances"].as_array().map_or(0, |u| u.len());
+        }
+    }
+    Some(totals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text_injection::InjectionMethod;
+
+    #[test]
+    fn per_app_table_sorts_by_utterances() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = AppStatsStore::load(dir.path().join("stats.json")).unwrap();
+        store.record("firefox", &[(InjectionMethod::AtspiInsert, true)], 40, 0);
+        for _ in 0..3 {
+            store.record(
+                "code",
+                &[
+                    (InjectionMethod::AtspiInsert, false),
+                    (InjectionMethod::ClipboardPasteFallback, true),
+                ],
+                90,
+                0,
+            );
+        }
+
+        let table = render_per_app(&store);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("Application"));
+        assert!(lines[2].starts_with("code"));
+        assert!(lines[2].contains("ClipboardPasteFallback"));
+        assert!(lines[2].contains("100.0%"));
+        assert!(lines[2].contains("90 ms"));
+        assert!(lines[3].starts_with("firefox"));
+    }
+
+    #[test]
+    fn trend_flags_week_over_week_degradation() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = AppStatsStore::load(dir.path().join("stats.json")).unwrap();
+        let today = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let last_week = today - Duration::days(9);
+        for _ in 0..10 {
+            store.record_attempts(last_week, &[(InjectionMethod::AtspiInsert, true, 30)]);
+            store.record_attempts(
+                last_week,
+                &[(InjectionMethod::ClipboardPasteFallback, true, 100)],
+            );
+        }
+        for i in 0..10 {
+            store.record_attempts(today, &[(InjectionMethod::AtspiInsert, i % 2 == 0, 30)]);
+            store.record_attempts(
+                today,
+                &[(InjectionMethod::ClipboardPasteFallback, true, 90)],
+            );
+        }
+        store.record_attempts(today, &[(InjectionMethod::EnigoText, true, 10)]);
+
+        let table = render_trend(&store, today);
+        let row = |name: &str| {
+            table
+                .lines()
+                .find(|l| l.starts_with(name))
+                .unwrap()
+                .to_string()
+        };
+        assert!(row("AtspiInsert").contains("-50.0pp"));
+        assert!(row("AtspiInsert").ends_with("DEGRADED: success"));
+        assert!(!row("ClipboardPasteFallback").contains("DEGRADED"));
+        assert!(row("EnigoText").ends_with("new"));
+
+        let empty = AppStatsStore::load(dir.path().join("none.json")).unwrap();
+        assert!(render_trend(&empty, today).starts_with("No daily injection stats"));
+    }
+
+    #[test]
+    fn counts_transcript_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let session = dir.path().join("2025-01-01").join("101500");
+        fs::create_dir_all(&session).unwrap();
+        fs::write(
+            session.join("session.json"),
+            r#"{"session_id":"101500","utterances":[{},{}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            transcript_totals(dir.path()),
+            Some(TranscriptTotals {
+                sessions: 1,
+                utterances: 2
+            })
+        );
+        assert_eq!(transcript_totals(&dir.path().join("missing")), None);
+    }
+}
@@ -4,3 +4,6 @@
 //! by re-exporting types from the coldvox-telemetry crate.
 
 pub use coldvox_telemetry::*;
+
+#[cfg(feature = "otel")]
+pub mod otel;
@@ -0,0 +1,44 @@
+//! OpenTelemetry export of tracing spans (feature `otel`).
+//!
+//! `--otlp-endpoint http://localhost:4318/v1/traces` sends every span over
+//! OTLP/HTTP, e.g. to Jaeger. The per-utterance spans from
+//! [`UtteranceTraces`](coldvox_telemetry::UtteranceTraces) then show up as
+//! one trace per utterance: `utterance` → `speech`, `stt.finalize`
+//! (`stt.process_batch`, `stt.plugin_finalize`) and `inject`.
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use tracing_subscriber::{Layer, Registry};
+
+/// Flushes spans still buffered when dropped
+pub struct OtelGuard {
+    provider: SdkTracerProvider,
+}
+
+impl Drop for OtelGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.provider.shutdown() {
+            tracing::warn!("Failed to flush OpenTelemetry spans: {}", e);
+        }
+    }
+}
+
+/// Tracing layer exporting spans to the OTLP/HTTP traces endpoint `endpoint`
+pub fn layer(
+    endpoint: &str,
+) -> Result<(Box<dyn Layer<Registry> + Send + Sync>, OtelGuard), Box<dyn std::error::Error>> {
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name("coldvox").build())
+        .build();
+    let layer = tracing_opentelemetry::layer()
+        .with_tracer(provider.tracer("coldvox"))
+        .boxed();
+    Ok((layer, OtelGuard { provider }))
+}
@@ -19,13 +19,23 @@ use ratatui::{
 use std::collections::VecDeque;
 use std::io;
 use std::sync::atomic::Ordering;
-use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 // Reuse global tracing subscriber initialized in `main.rs`.
 
 use crate::runtime::ActivationMode;
+use coldvox_audio_quality::QualityWarning;
+#[cfg(any(feature = "moonshine", feature = "parakeet"))]
+use coldvox_stt::TranscriptionEvent;
 use coldvox_vad::types::VadEvent;
+use coldvox_vad_silero::SensitivityPreset;
+
+/// Threshold change per +/- key press
+const VAD_THRESHOLD_STEP: f32 = 0.05;
+/// How long an audio quality warning stays on the Audio tab
+const QUALITY_WARNING_DISPLAY: Duration = Duration::from_secs(5);
+/// How often the Plugins tab re-probes the injection backends
+const BACKEND_REFRESH: Duration = Duration::from_secs(3);
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum Tab {
@@ -38,12 +48,17 @@ enum Tab {
 enum AppEvent {
     Log(LogLevel, String),
     Vad(VadEvent),
+    Quality(QualityWarning),
+    #[cfg(any(feature = "moonshine", feature = "parakeet"))]
+    Transcription(TranscriptionEvent),
     /// Internal control signal: runtime replaced (after restart)
     AppReplaced(std::sync::Arc<crate::runtime::AppHandle>),
     PluginLoad(String),
     PluginUnload(String),
     PluginSwitch(String),
     PluginStatusUpdate,
+    /// Fresh injection backend capability report
+    Backends(Vec<crate::text_injection::BackendCapability>),
 }
 
 struct PipelineMetricsSnapshot {
@@ -54,6 +69,8 @@ struct PipelineMetricsSnapshot {
     chunker_fps: u64,
     vad_fps: u64,
     capture_buffer_fill: usize,
+    capture_overflows: u64,
+    capture_dropped_samples: u64,
     chunker_buffer_fill: usize,
     vad_buffer_fill: usize,
     stage_capture: bool,
@@ -62,6 +79,13 @@ struct PipelineMetricsSnapshot {
     stage_output: bool,
     capture_frames: u64,
     chunker_frames: u64,
+    capture_suspended: bool,
+    pipeline_paused: bool,
+    last_resume_latency_ms: u64,
+    quality_clipping_warnings: u64,
+    quality_too_quiet_warnings: u64,
+    quality_off_axis_warnings: u64,
+    end_to_end_latency: coldvox_telemetry::LatencyPercentiles,
 }
 
 struct DashboardState {
@@ -70,6 +94,8 @@ struct DashboardState {
     is_speaking: bool,
     speech_segments: u64,
     last_vad_event: Option<String>,
+    /// Latest audio quality warning and when it arrived
+    last_quality_warning: Option<(QualityWarning, Instant)>,
     is_running: bool,
     selected_device: String,
     start_time: Instant,
@@ -80,6 +106,23 @@ struct DashboardState {
     metrics: PipelineMetricsSnapshot,
     has_metrics_snapshot: bool,
     current_tab: Tab,
+    /// Injection backends as last reported, for the Plugins tab
+    backends: Vec<crate::text_injection::BackendCapability>,
+    backends_requested: Option<Instant>,
+    /// Plugin shown as active on the Plugins tab
+    plugin_current: Option<String>,
+    plugin_active_count: usize,
+    #[cfg(any(feature = "moonshine", feature = "parakeet"))]
+    last_transcript: Option<String>,
+    #[cfg(any(feature = "moonshine", feature = "parakeet"))]
+    plugin_manager:
+        Option<std::sync::Arc<tokio::sync::RwLock<crate::stt::plugin_manager::SttPluginManager>>>,
+    #[cfg(any(feature = "moonshine", feature = "parakeet"))]
+    plugin_transcription_requests: u64,
+    #[cfg(any(feature = "moonshine", feature = "parakeet"))]
+    plugin_success: u64,
+    #[cfg(any(feature = "moonshine", feature = "parakeet"))]
+    plugin_failures: u64,
 }
 
 #[derive(Clone)]
@@ -121,6 +164,7 @@ impl Default for DashboardState {
             is_speaking: false,
             speech_segments: 0,
             last_vad_event: None,
+            last_quality_warning: None,
             is_running: false,
             selected_device: "default".to_string(),
             start_time: Instant::now(),
@@ -136,6 +180,8 @@ impl Default for DashboardState {
                 chunker_fps: 0,
                 vad_fps: 0,
                 capture_buffer_fill: 0,
+                capture_overflows: 0,
+                capture_dropped_samples: 0,
                 chunker_buffer_fill: 0,
                 vad_buffer_fill: 0,
                 stage_capture: false,
@@ -144,16 +190,23 @@ impl Default for DashboardState {
                 stage_output: false,
                 capture_frames: 0,
                 chunker_frames: 0,
+                capture_suspended: false,
+                pipeline_paused: false,
+                last_resume_latency_ms: 0,
+                quality_clipping_warnings: 0,
+                quality_too_quiet_warnings: 0,
+                quality_off_axis_warnings: 0,
+                end_to_end_latency: Default::default(),
             },
             has_metrics_snapshot: false,
             current_tab: Tab::Audio,
+            backends: Vec::new(),
+            backends_requested: None,
             #[cfg(any(feature = "moonshine", feature = "parakeet"))]
             last_transcript: None,
             #[cfg(any(feature = "moonshine", feature = "parakeet"))]
             plugin_manager: None,
-            #[cfg(any(feature = "moonshine", feature = "parakeet"))]
             plugin_current: None,
-            #[cfg(any(feature = "moonshine", feature = "parakeet"))]
             plugin_active_count: 0,
             #[cfg(any(feature = "moonshine", feature = "parakeet"))]
             plugin_transcription_requests: 0,
@@ -199,24 +252,55 @@ impl DashboardState {
         self.log(LogLevel::Info, "Metrics reset".to_string());
     }
 
+    fn nudge_vad_threshold(&mut self, delta: f32) {
+        let Some(app) = self.app.clone() else {
+            return;
+        };
+        app.set_vad_threshold(app.vad_sensitivity().threshold + delta);
+        let threshold = app.vad_sensitivity().threshold;
+        self.log(
+            LogLevel::Info,
+            format!("VAD threshold set to {:.2}", threshold),
+        );
+    }
+
+    fn cycle_vad_preset(&mut self) {
+        let Some(app) = self.app.clone() else {
+            return;
+        };
+        let preset = SensitivityPreset::matching(&app.vad_sensitivity())
+            .map_or(SensitivityPreset::Balanced, SensitivityPreset::next);
+        app.set_vad_preset(preset);
+        self.log(
+            LogLevel::Info,
+            format!("VAD sensitivity preset: {}", preset),
+        );
+    }
+
     fn toggle_activation_mode(&mut self) {
         self.activation_mode = match self.activation_mode {
             ActivationMode::Vad => ActivationMode::Hotkey,
-            ActivationMode::Hotkey => ActivationMode::Vad,
+            ActivationMode::Hotkey => ActivationMode::AlwaysOnPushToTranscribe,
+            ActivationMode::AlwaysOnPushToTranscribe => ActivationMode::Vad,
         };
         self.log(
             LogLevel::Info,
             format!(
                 "Switched activation mode to {}",
-                match self.activation_mode {
-                    ActivationMode::Vad => "VAD",
-                    ActivationMode::Hotkey => "Push-to-talk",
-                }
+                activation_label(self.activation_mode)
             ),
         );
     }
 }
 
+fn activation_label(mode: ActivationMode) -> &'static str {
+    match mode {
+        ActivationMode::Vad => "VAD",
+        ActivationMode::Hotkey => "Push-to-talk",
+        ActivationMode::AlwaysOnPushToTranscribe => "Always-on (push-to-transcribe)",
+    }
+}
+
 /// Run the TUI dashboard with the given app handle
 pub async fn run_tui(
     app: std::sync::Arc<crate::runtime::AppHandle>,
@@ -234,8 +318,10 @@ pub async fn run_tui(
 
     let (tx, rx) = mpsc::channel(100);
 
-    let mut state = DashboardState::default();
-    state.app = Some(app.clone());
+    let mut state = DashboardState {
+        app: Some(app.clone()),
+        ..Default::default()
+    };
 
     // Set up plugin manager reference if available
     #[cfg(any(feature = "moonshine", feature = "parakeet"))]
@@ -245,7 +331,49 @@ pub async fn run_tui(
         }
     }
 
+    // Forward audio quality warnings to the Audio tab
+    let mut quality_rx = app.subscribe_quality();
+    let quality_tx = tx.clone();
+    let quality_forward = tokio::spawn(async move {
+        use tokio::sync::broadcast::error::RecvError;
+        loop {
+            match quality_rx.recv().await {
+                Ok(warning) => {
+                    if quality_tx.send(AppEvent::Quality(warning)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+
+    // Forward transcripts to the Status tab and logs
+    #[cfg(any(feature = "moonshine", feature = "parakeet"))]
+    let transcript_forward = {
+        let mut stt_rx = app.subscribe_stt();
+        let stt_tx = tx.clone();
+        tokio::spawn(async move {
+            use tokio::sync::broadcast::error::RecvError;
+            loop {
+                match stt_rx.recv().await {
+                    Ok(event) => {
+                        if stt_tx.send(AppEvent::Transcription(event)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    };
+
     let res = run_app(&mut terminal, &mut state, tx, rx).await;
+    quality_forward.abort();
+    #[cfg(any(feature = "moonshine", feature = "parakeet"))]
+    transcript_forward.abort();
 
     disable_raw_mode()?;
     execute!(
@@ -291,13 +419,11 @@ async fn run_app(
                             }
                             return Ok(());
                         }
-                        KeyCode::Char('s') | KeyCode::Char('S') => {
-                            if !state.is_running {
-                                state.log(LogLevel::Info, "Starting audio pipeline...".to_string());
-                                // Pipeline is already started, just mark as running
-                                state.is_running = true;
-                                state.log(LogLevel::Success, "Pipeline already running".to_string());
-                            }
+                        KeyCode::Char('s') | KeyCode::Char('S') if !state.is_running => {
+                            state.log(LogLevel::Info, "Starting audio pipeline...".to_string());
+                            // Pipeline is already started, just mark as running
+                            state.is_running = true;
+                            state.log(LogLevel::Success, "Pipeline already running".to_string());
                         }
                         KeyCode::Char('a') | KeyCode::Char('A') => {
                             state.toggle_activation_mode();
@@ -315,6 +441,28 @@ async fn run_app(
                         KeyCode::Char('r') | KeyCode::Char('R') => {
                             state.reset_metrics();
                         }
+                        KeyCode::Char('i') | KeyCode::Char('I') => {
+                            if let Some(app) = &state.app {
+                                let report = app.session_report().to_string();
+                                for line in report.lines() {
+                                    state.log(LogLevel::Info, line.to_string());
+                                }
+                            }
+                        }
+                        KeyCode::Char(' ') => {
+                            if let Some(app) = &state.app {
+                                let msg = if app.toggle_pause() {
+                                    "Pipeline paused"
+                                } else {
+                                    "Pipeline resumed"
+                                };
+                                state.log(LogLevel::Info, msg.to_string());
+                            }
+                        }
+                        // '+' catches quieter speech, '-' ignores more noise
+                        KeyCode::Char('+') | KeyCode::Char('=') => state.nudge_vad_threshold(-VAD_THRESHOLD_STEP),
+                        KeyCode::Char('-') | KeyCode::Char('_') => state.nudge_vad_threshold(VAD_THRESHOLD_STEP),
+                        KeyCode::Char('v') | KeyCode::Char('V') => state.cycle_vad_preset(),
                         KeyCode::Char('p') | KeyCode::Char('P') => {
                             state.current_tab = match state.current_tab {
                                 Tab::Audio => Tab::Logs,
@@ -378,13 +526,17 @@ async fn run_app(
                             }
                         }
                     }
+                    AppEvent::Quality(warning) => {
+                        state.log(LogLevel::Warning, warning.message());
+                        state.last_quality_warning = Some((warning, Instant::now()));
+                    }
                     AppEvent::AppReplaced(app) => {
                         state.app = Some(app);
                         state.is_running = true;
                     }
                         #[cfg(any(feature = "moonshine", feature = "parakeet"))]
                         AppEvent::Transcription(tevent) => {
-                            tracing::debug!(target: "coldvox::tui", transcription_event = ?tevent, "Received TranscriptionEvent");
+                            tracing::debug!(target: "coldvox::tui", transcription_event = %coldvox_foundation::scrub::scrub(&format!("{:?}", tevent)), "Received TranscriptionEvent");
                             match tevent.clone() {
                                 TranscriptionEvent::Partial { utterance_id, text, .. } => {
                                     if !text.trim().is_empty() {
@@ -429,10 +581,23 @@ async fn run_app(
                     AppEvent::PluginStatusUpdate => {
                         state.log(LogLevel::Debug, "Plugin status updated".to_string());
                     }
+                    AppEvent::Backends(report) => state.backends = report,
                 }
             }
 
             _ = ui_update_interval.tick() => {
+                // Probing backends can take seconds (AT-SPI, portal); keep it off the UI loop
+                if state.current_tab == Tab::Plugins
+                    && state.backends_requested.is_none_or(|at| at.elapsed() >= BACKEND_REFRESH)
+                {
+                    if let Some(app) = state.app.clone() {
+                        state.backends_requested = Some(Instant::now());
+                        let tx = tx.clone();
+                        tokio::spawn(async move {
+                            let _ = tx.send(AppEvent::Backends(app.capability_report().await)).await;
+                        });
+                    }
+                }
                 if state.is_running {
                     if let Some(app) = &state.app {
                         let m = &app.metrics;
@@ -444,6 +609,8 @@ async fn run_app(
                             chunker_fps: m.chunker_fps.load(Ordering::Relaxed),
                             vad_fps: m.vad_fps.load(Ordering::Relaxed),
                             capture_buffer_fill: m.capture_buffer_fill.load(Ordering::Relaxed),
+                            capture_overflows: m.capture_overflows.load(Ordering::Relaxed),
+                            capture_dropped_samples: m.capture_dropped_samples.load(Ordering::Relaxed),
                             chunker_buffer_fill: m.chunker_buffer_fill.load(Ordering::Relaxed),
                             vad_buffer_fill: m.vad_buffer_fill.load(Ordering::Relaxed),
                             stage_capture: m.stage_capture.load(Ordering::Relaxed),
@@ -452,6 +619,13 @@ async fn run_app(
                             stage_output: m.stage_output.load(Ordering::Relaxed),
                             capture_frames: m.capture_frames.load(Ordering::Relaxed),
                             chunker_frames: m.chunker_frames.load(Ordering::Relaxed),
+                            capture_suspended: m.capture_suspended.load(Ordering::Relaxed),
+                            pipeline_paused: m.pipeline_paused.load(Ordering::Relaxed),
+                            last_resume_latency_ms: m.last_resume_latency_ms.load(Ordering::Relaxed),
+                            quality_clipping_warnings: m.quality_clipping_warnings.load(Ordering::Relaxed),
+                            quality_too_quiet_warnings: m.quality_too_quiet_warnings.load(Ordering::Relaxed),
+                            quality_off_axis_warnings: m.quality_off_axis_warnings.load(Ordering::Relaxed),
+                            end_to_end_latency: m.latency.snapshot().end_to_end,
                         };
                         state.has_metrics_snapshot = true;
                         state.update_level_history();
@@ -495,7 +669,12 @@ fn draw_ui(f: &mut Frame, state: &DashboardState) {
             draw_status(f, middle_chunks[1], state);
         }
         Tab::Plugins => {
-            draw_plugins(f, middle_chunks[0], state);
+            let left = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .split(middle_chunks[0]);
+            draw_plugins(f, left[0], state);
+            draw_backends(f, left[1], state);
             draw_plugin_status(f, middle_chunks[1], state);
         }
     }
@@ -629,7 +808,7 @@ fn draw_metrics(f: &mut Frame, area: Rect, state: &DashboardState) {
     f.render_widget(block, area);
 
     let elapsed = state.start_time.elapsed().as_secs();
-    let metrics_text = vec![
+    let mut metrics_text = vec![
         Line::from(format!("Runtime: {}s", elapsed)),
         Line::from(""),
         Line::from(format!(
@@ -647,22 +826,75 @@ fn draw_metrics(f: &mut Frame, area: Rect, state: &DashboardState) {
         Line::from(""),
         Line::from("Buffer Fill:"),
         Line::from(format!("  Capture: {}%", state.metrics.capture_buffer_fill)),
+        capture_drops_line(&state.metrics),
         Line::from(format!("  Chunker: {}%", state.metrics.chunker_buffer_fill)),
         Line::from(format!("  VAD: {}%", state.metrics.vad_buffer_fill)),
+        Line::from(""),
+        Line::from("Audio Quality:"),
     ];
+    metrics_text.extend(quality_lines(state));
 
     let paragraph = Paragraph::new(metrics_text);
     f.render_widget(paragraph, inner);
 }
 
+/// Capture buffer overruns; red once any audio was lost
+fn capture_drops_line(metrics: &PipelineMetricsSnapshot) -> Line<'static> {
+    let text = format!(
+        "  Dropped: {} samples ({} overruns)",
+        metrics.capture_dropped_samples, metrics.capture_overflows
+    );
+    if metrics.capture_dropped_samples > 0 {
+        Line::from(Span::styled(text, Style::default().fg(Color::Red)))
+    } else {
+        Line::from(text)
+    }
+}
+
+/// Recent quality warning with its fix, or OK, plus counts per kind
+fn quality_lines(state: &DashboardState) -> Vec<Line<'static>> {
+    let mut lines = match &state.last_quality_warning {
+        Some((warning, at)) if at.elapsed() < QUALITY_WARNING_DISPLAY => {
+            let color = match warning {
+                QualityWarning::Clipping { .. } => Color::Red,
+                QualityWarning::TooQuiet { .. } | QualityWarning::OffAxis { .. } => Color::Yellow,
+            };
+            vec![
+                Line::from(Span::styled(
+                    format!("  {}", warning.message()),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                )),
+                Line::from(format!("  Fix: {}", warning.suggested_action())),
+            ]
+        }
+        _ => vec![Line::from(Span::styled(
+            "  OK",
+            Style::default().fg(Color::Green),
+        ))],
+    };
+    lines.push(Line::from(format!(
+        "  Clipping: {} | Too quiet: {} | Off-axis: {}",
+        state.metrics.quality_clipping_warnings,
+        state.metrics.quality_too_quiet_warnings,
+        state.metrics.quality_off_axis_warnings
+    )));
+    lines
+}
+
 fn draw_status(f: &mut Frame, area: Rect, state: &DashboardState) {
     let block = Block::default().title("Status & VAD").borders(Borders::ALL);
 
     let inner = block.inner(area);
     f.render_widget(block, area);
 
+    let idle = state.is_running && state.metrics.capture_suspended;
+    let paused = state.is_running && state.metrics.pipeline_paused;
     let status_color = if state.is_running {
-        if state.is_speaking {
+        if paused {
+            Color::Magenta
+        } else if idle {
+            Color::Blue
+        } else if state.is_speaking {
             Color::Yellow
         } else {
             Color::Green
@@ -675,7 +907,11 @@ fn draw_status(f: &mut Frame, area: Rect, state: &DashboardState) {
     status_text.push(Line::from(vec![
         Span::raw("Pipeline: "),
         Span::styled(
-            if state.is_running {
+            if paused {
+                "PAUSED"
+            } else if idle {
+                "IDLE (capture suspended)"
+            } else if state.is_running {
                 "RUNNING"
             } else {
                 "STOPPED"
@@ -686,13 +922,26 @@ fn draw_status(f: &mut Frame, area: Rect, state: &DashboardState) {
         ),
     ]));
     status_text.push(Line::from(format!("Device: {}", state.selected_device)));
+    if state.metrics.last_resume_latency_ms > 0 {
+        status_text.push(Line::from(format!(
+            "Last idle resume: {} ms",
+            state.metrics.last_resume_latency_ms
+        )));
+    }
     status_text.push(Line::from(format!(
         "Activation: {}",
-        match state.activation_mode {
-            ActivationMode::Vad => "VAD",
-            ActivationMode::Hotkey => "Push-to-talk",
-        }
+        activation_label(state.activation_mode)
     )));
+    if let Some(app) = &state.app {
+        let vad = app.vad_sensitivity();
+        status_text.push(Line::from(format!(
+            "VAD sensitivity: {} (threshold {:.2}, speech {} ms, silence {} ms)",
+            SensitivityPreset::matching(&vad).map_or("custom", |p| p.as_str()),
+            vad.threshold,
+            vad.min_speech_duration_ms,
+            vad.min_silence_duration_ms
+        )));
+    }
     status_text.push(Line::from(""));
     status_text.push(Line::from(vec![
         Span::raw("Speaking: "),
@@ -709,6 +958,13 @@ fn draw_status(f: &mut Frame, area: Rect, state: &DashboardState) {
         "Speech Segments: {}",
         state.speech_segments
     )));
+    let latency = &state.metrics.end_to_end_latency;
+    if let (Some(p50), Some(p95)) = (latency.p50_ms, latency.p95_ms) {
+        status_text.push(Line::from(format!(
+            "Speech end to text: p50 {} ms, p95 {} ms",
+            p50, p95
+        )));
+    }
     status_text.push(Line::from(""));
     status_text.push(Line::from("Last VAD Event:"));
     status_text.push(Line::from(
@@ -729,7 +985,10 @@ fn draw_status(f: &mut Frame, area: Rect, state: &DashboardState) {
     status_text.push(Line::from(""));
     status_text.push(Line::from("Controls:"));
     status_text.push(Line::from(
-        "[S] Start  [A] Toggle VAD/PTT  [R] Reset  [Q] Quit",
+        "[S] Start  [A] Toggle VAD/PTT  [Space] Pause  [R] Reset  [Q] Quit",
+    ));
+    status_text.push(Line::from(
+        "[+/-] VAD sensitivity  [V] Next VAD preset  [I] Session summary",
     ));
 
     let paragraph = Paragraph::new(status_text);
@@ -811,6 +1070,46 @@ fn draw_plugins(f: &mut Frame, area: Rect, _state: &DashboardState) {
     f.render_widget(paragraph, inner);
 }
 
+fn draw_backends(f: &mut Frame, area: Rect, state: &DashboardState) {
+    let block = Block::default()
+        .title("Injection Backends")
+        .borders(Borders::ALL);
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let lines: Vec<Line> = if state.backends.is_empty() {
+        vec![Line::from(if state.app.is_some() {
+            "Probing backends (none when injection is disabled)"
+        } else {
+            "Start the pipeline to list backends"
+        })]
+    } else {
+        state
+            .backends
+            .iter()
+            .map(|backend| {
+                let color = if backend.status.available {
+                    Color::Green
+                } else if backend.status.registered {
+                    Color::Yellow
+                } else {
+                    Color::DarkGray
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:<22} ", backend.status.method.name()),
+                        Style::default().fg(color),
+                    ),
+                    Span::raw(crate::doctor::backend_summary(backend)),
+                ])
+            })
+            .collect()
+    };
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
 fn draw_plugin_status(f: &mut Frame, area: Rect, state: &DashboardState) {
     let block = Block::default()
         .title("Plugin Status")
@@ -819,12 +1118,9 @@ fn draw_plugin_status(f: &mut Frame, area: Rect, state: &DashboardState) {
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    let mut status_lines: Vec<Line> = Vec::new();
-
-    #[cfg(any(feature = "moonshine", feature = "parakeet"))]
-    {
-        let current = state.plugin_current.as_deref().unwrap_or("None");
-        status_lines.push(Line::from(vec![
+    let current = state.plugin_current.as_deref().unwrap_or("None");
+    let mut status_lines: Vec<Line> = vec![
+        Line::from(vec![
             Span::raw("Current: "),
             Span::styled(
                 current,
@@ -832,10 +1128,12 @@ fn draw_plugin_status(f: &mut Frame, area: Rect, state: &DashboardState) {
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD),
             ),
-        ]));
-
-        status_lines.push(Line::from(format!("Active: {}", state.plugin_active_count)));
+        ]),
+        Line::from(format!("Active: {}", state.plugin_active_count)),
+    ];
 
+    #[cfg(any(feature = "moonshine", feature = "parakeet"))]
+    {
         status_lines.push(Line::from(format!(
             "Requests: {}",
             state.plugin_transcription_requests
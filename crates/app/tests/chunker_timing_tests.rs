@@ -21,6 +21,7 @@ async fn chunker_timestamps_are_32ms_apart_at_16k() {
         frame_size_samples: 512,
         sample_rate_hz: 16_000,
         resampler_quality: ResamplerQuality::Balanced,
+        ..Default::default()
     };
     let (tx, _) = broadcast::channel::<VadFrame>(64);
     let mut rx = tx.subscribe();
@@ -1,4 +1,5 @@
 pub mod logging;
+pub mod synthetic_speech;
 pub mod test_utils;
 pub mod timeout;
 pub mod wer;
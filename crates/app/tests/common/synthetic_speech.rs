@@ -0,0 +1,327 @@
+#![allow(dead_code)] // Not every test binary uses every condition
+
+//! Deterministic synthetic speech for VAD and STT tests.
+//!
+//! Audio is built from "syllables": a glottal pulse train (jittered pitch with
+//! declination over the utterance) shaped by three formant resonators whose
+//! targets change per vowel, preceded by short fricative noise bursts for the
+//! consonants. It is not intelligible, but it has the spectral and temporal
+//! structure (harmonics, formants, ~4 Hz syllable rate, pauses) that VADs key
+//! on. Everything is driven by a seeded PRNG, so the same seed always yields
+//! the same samples.
+//!
+//! ```ignore
+//! let audio = SyntheticSpeech::new(7)
+//!     .silence_ms(500)
+//!     .speech_ms(1500)
+//!     .silence_ms(800)
+//!     .render(Condition::Noisy { snr_db: 10.0 });
+//! assert_eq!(audio.speech_regions.len(), 1);
+//! ```
+
+use std::f64::consts::PI;
+use std::ops::Range;
+
+pub const SAMPLE_RATE: u32 = 16_000;
+
+/// Recording condition applied after synthesis
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Condition {
+    /// Speech at a comfortable level (about -20 dBFS RMS) over digital silence
+    Clean,
+    /// White noise over the whole clip at the given speech-to-noise ratio
+    Noisy { snr_db: f64 },
+    /// Clean speech attenuated by `gain_db` (negative = quieter)
+    Quiet { gain_db: f64 },
+    /// Speech driven `drive_db` above full scale and hard-clipped
+    Clipped { drive_db: f64 },
+}
+
+/// Rendered clip with ground truth
+#[derive(Debug, Clone)]
+pub struct SyntheticAudio {
+    pub samples: Vec<i16>,
+    /// Sample ranges that contain speech
+    pub speech_regions: Vec<Range<usize>>,
+}
+
+impl SyntheticAudio {
+    pub fn duration_ms(&self) -> u64 {
+        self.samples.len() as u64 * 1000 / u64::from(SAMPLE_RATE)
+    }
+
+    /// Speech regions in milliseconds
+    pub fn speech_regions_ms(&self) -> Vec<Range<u64>> {
+        let to_ms = |s: usize| s as u64 * 1000 / u64::from(SAMPLE_RATE);
+        self.speech_regions
+            .iter()
+            .map(|r| to_ms(r.start)..to_ms(r.end))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Segment {
+    Silence(usize),
+    Speech(usize),
+}
+
+/// Builder for a clip of alternating silence and speech
+#[derive(Debug, Clone)]
+pub struct SyntheticSpeech {
+    seed: u64,
+    segments: Vec<Segment>,
+}
+
+impl SyntheticSpeech {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            segments: Vec::new(),
+        }
+    }
+
+    pub fn silence_ms(mut self, ms: u32) -> Self {
+        self.segments.push(Segment::Silence(ms_to_samples(ms)));
+        self
+    }
+
+    pub fn speech_ms(mut self, ms: u32) -> Self {
+        self.segments.push(Segment::Speech(ms_to_samples(ms)));
+        self
+    }
+
+    /// Synthesize the clip and apply `condition`
+    pub fn render(&self, condition: Condition) -> SyntheticAudio {
+        let mut rng = Rng::new(self.seed);
+        let mut signal = Vec::new();
+        let mut speech_regions = Vec::new();
+        for segment in &self.segments {
+            match *segment {
+                Segment::Silence(n) => signal.resize(signal.len() + n, 0.0),
+                Segment::Speech(n) => {
+                    let start = signal.len();
+                    signal.extend(speech(n, &mut rng));
+                    speech_regions.push(start..signal.len());
+                }
+            }
+        }
+
+        // Normalise speech to -20 dBFS RMS so conditions are relative to a known level
+        let speech_rms = rms_over(&signal, &speech_regions);
+        if speech_rms > 0.0 {
+            let gain = db_to_amplitude(-20.0) / speech_rms;
+            signal.iter_mut().for_each(|s| *s *= gain);
+        }
+
+        match condition {
+            Condition::Clean => {}
+            Condition::Noisy { snr_db } => {
+                let noise_rms = db_to_amplitude(-20.0 - snr_db);
+                // Uniform noise in [-a, a] has RMS a / sqrt(3)
+                let amplitude = noise_rms * 3f64.sqrt();
+                for s in &mut signal {
+                    *s += amplitude * rng.signed();
+                }
+            }
+            Condition::Quiet { gain_db } => {
+                let gain = db_to_amplitude(gain_db);
+                signal.iter_mut().for_each(|s| *s *= gain);
+            }
+            Condition::Clipped { drive_db } => {
+                // Drive the speech peak `drive_db` past full scale
+                let peak = signal.iter().fold(0.0f64, |m, s| m.max(s.abs()));
+                if peak > 0.0 {
+                    let gain = db_to_amplitude(drive_db) / peak;
+                    signal.iter_mut().for_each(|s| *s *= gain);
+                }
+            }
+        }
+
+        SyntheticAudio {
+            samples: signal.iter().map(|&s| to_i16(s)).collect(),
+            speech_regions,
+        }
+    }
+}
+
+/// Noise-only clip (no speech) at `level_dbfs` RMS
+pub fn noise(ms: u32, level_dbfs: f64, seed: u64) -> Vec<i16> {
+    let mut rng = Rng::new(seed);
+    let amplitude = db_to_amplitude(level_dbfs) * 3f64.sqrt();
+    (0..ms_to_samples(ms))
+        .map(|_| to_i16(amplitude * rng.signed()))
+        .collect()
+}
+
+/// RMS level of `samples` in dBFS (-inf for digital silence)
+pub fn rms_dbfs(samples: &[i16]) -> f64 {
+    if samples.is_empty() {
+        return f64::NEG_INFINITY;
+    }
+    let sum: f64 = samples
+        .iter()
+        .map(|&s| {
+            let x = f64::from(s) / 32768.0;
+            x * x
+        })
+        .sum();
+    20.0 * (sum / samples.len() as f64).sqrt().log10()
+}
+
+/// Vowel formant targets (F1, F2, F3) in Hz
+const VOWELS: [[f64; 3]; 5] = [
+    [730.0, 1090.0, 2440.0], // a
+    [530.0, 1840.0, 2480.0], // e
+    [270.0, 2290.0, 3010.0], // i
+    [570.0, 840.0, 2410.0],  // o
+    [300.0, 870.0, 2240.0],  // u
+];
+const FORMANT_BANDWIDTHS: [f64; 3] = [90.0, 110.0, 170.0];
+const FORMANT_GAINS: [f64; 3] = [1.0, 0.5, 0.25];
+
+/// `n` samples of speech-like signal (unnormalised)
+fn speech(n: usize, rng: &mut Rng) -> Vec<f64> {
+    let rate = f64::from(SAMPLE_RATE);
+    let mut out = Vec::with_capacity(n);
+    let base_f0 = 110.0 + 90.0 * rng.unit(); // 110-200 Hz speaker
+    let mut resonators = [Resonator::default(); 3];
+    let mut phase = 0.0f64;
+
+    while out.len() < n {
+        // Syllable: optional fricative onset, then a voiced nucleus
+        let vowel = VOWELS[rng.below(VOWELS.len())];
+        let fricative = ms_to_samples(20 + rng.below(50) as u32) * usize::from(rng.unit() < 0.6);
+        let voiced = ms_to_samples(120 + rng.below(130) as u32);
+        let gap = if rng.unit() < 0.2 {
+            ms_to_samples(40 + rng.below(80) as u32) // short pause between words
+        } else {
+            0
+        };
+
+        let mut prev = 0.0;
+        for i in 0..fricative {
+            let env = (PI * i as f64 / fricative as f64).sin();
+            // First difference of white noise tilts it toward high frequencies
+            let white = rng.signed();
+            out.push(0.15 * env * (white - 0.7 * prev));
+            prev = white;
+        }
+
+        for (k, r) in resonators.iter_mut().enumerate() {
+            r.tune(
+                vowel[k] * (0.95 + 0.1 * rng.unit()),
+                FORMANT_BANDWIDTHS[k],
+                rate,
+            );
+        }
+        for i in 0..voiced {
+            let progress = out.len() as f64 / n as f64;
+            // Declination plus a little vibrato and jitter
+            let f0 = base_f0
+                * (1.1 - 0.2 * progress)
+                * (1.0 + 0.02 * (2.0 * PI * 5.0 * i as f64 / rate).sin())
+                * (1.0 + 0.01 * rng.signed());
+            phase += f0 / rate;
+            let excitation = if phase >= 1.0 {
+                phase -= 1.0;
+                1.0
+            } else {
+                0.0
+            };
+            let voiced_sample: f64 = resonators
+                .iter_mut()
+                .zip(FORMANT_GAINS)
+                .map(|(r, g)| g * r.process(excitation))
+                .sum();
+            // Raised-cosine syllable envelope with a small breath component
+            let env = 0.5 - 0.5 * (2.0 * PI * i as f64 / voiced as f64).cos();
+            out.push(env * (voiced_sample + 0.01 * rng.signed()));
+        }
+        out.resize(out.len() + gap, 0.0);
+    }
+    out.truncate(n);
+    out
+}
+
+/// Two-pole resonator (one formant)
+#[derive(Debug, Clone, Copy, Default)]
+struct Resonator {
+    a1: f64,
+    a2: f64,
+    gain: f64,
+    y1: f64,
+    y2: f64,
+}
+
+impl Resonator {
+    fn tune(&mut self, freq: f64, bandwidth: f64, rate: f64) {
+        let r = (-PI * bandwidth / rate).exp();
+        self.a1 = 2.0 * r * (2.0 * PI * freq / rate).cos();
+        self.a2 = -r * r;
+        self.gain = 1.0 - r;
+    }
+
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.gain * x + self.a1 * self.y1 + self.a2 * self.y2;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
+
+/// xorshift64*: tiny, deterministic across platforms and crate versions
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // Avoid the all-zero state
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Uniform in [0, 1)
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in [-1, 1)
+    fn signed(&mut self) -> f64 {
+        2.0 * self.unit() - 1.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.unit() * n as f64) as usize
+    }
+}
+
+fn ms_to_samples(ms: u32) -> usize {
+    (u64::from(ms) * u64::from(SAMPLE_RATE) / 1000) as usize
+}
+
+fn db_to_amplitude(db: f64) -> f64 {
+    10f64.powf(db / 20.0)
+}
+
+fn rms_over(signal: &[f64], regions: &[Range<usize>]) -> f64 {
+    let (sum, count) = regions
+        .iter()
+        .flat_map(|r| &signal[r.clone()])
+        .fold((0.0, 0usize), |(s, c), x| (s + x * x, c + 1));
+    if count == 0 {
+        0.0
+    } else {
+        (sum / count as f64).sqrt()
+    }
+}
+
+fn to_i16(x: f64) -> i16 {
+    (x * 32768.0).round().clamp(-32768.0, 32767.0) as i16
+}
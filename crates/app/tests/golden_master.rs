@@ -205,7 +205,11 @@ mod tests {
                 min_speech_duration_ms: 100, // Reduced from default 250ms
                 min_silence_duration_ms: 300, // Increased from default 100ms for cleaner end detection
                 window_size_samples: 512,
+                // The golden outputs were recorded with speech ending at `threshold`
+                offset_hysteresis: 0.0,
             },
+            energy: Default::default(),
+            vote: Default::default(),
             frame_size_samples: 512,
             sample_rate_hz: 16000,
         };
@@ -227,6 +231,7 @@ mod tests {
             test_device_config: Some(DeviceConfig {
                 sample_rate: wav_loader.sample_rate(),
                 channels: wav_loader.channels(),
+                channel: Default::default(),
             }),
             test_injection_sink: Some(mock_sink.clone()),
             transcription_config: Some(transcription_config),
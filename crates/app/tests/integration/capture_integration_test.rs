@@ -163,6 +163,7 @@ mod tests {
                 let frame = AudioFrame {
                     samples: std::sync::Arc::from(vec![i as i16; FRAME_SIZE_SAMPLES]),
                     timestamp: std::time::Instant::now(),
+                    captured_at: std::time::Instant::now(),
                     sample_rate: 16000,
                 };
 
@@ -215,6 +216,7 @@ mod tests {
             let frame = AudioFrame {
                 samples: std::sync::Arc::from(vec![i as i16; FRAME_SIZE_SAMPLES]),
                 timestamp: std::time::Instant::now(),
+                captured_at: std::time::Instant::now(),
                 sample_rate: 16000,
             };
 
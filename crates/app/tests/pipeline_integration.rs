@@ -1,6 +1,6 @@
 // NOTE: This test is temporarily disabled due to missing WAV loading utilities.
 // The functionality is now covered by the comprehensive end-to-end test in
-// crates/app/src/stt/tests/end_to_end_wav.rs which tests the full pipeline
+// crates/coldvox-runtime/src/stt/tests/end_to_end_wav.rs which tests the full pipeline
 // including AudioChunker, VAD, STT, and text injection.
 //
 // Original test kept for reference but commented out to fix compilation.
@@ -44,6 +44,7 @@ async fn test_chunker_emits_frames_for_known_audio() {
         frame_size_samples: 512,
         sample_rate_hz: sample_rate,
         resampler_quality: coldvox_app::audio::chunker::ResamplerQuality::Balanced,
+        ..Default::default()
     };
     let (audio_tx, _) = broadcast::channel::<VadFrame>(64);
     let chunker = AudioChunker::new(reader, audio_tx.clone(), cfg).with_metrics(metrics.clone());
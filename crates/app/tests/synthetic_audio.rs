@@ -0,0 +1,111 @@
+//! Checks for the synthetic speech fixtures, and VAD behaviour across
+//! recording conditions using them.
+
+mod common;
+use common::synthetic_speech::{noise, rms_dbfs, Condition, SyntheticSpeech};
+
+fn utterance(seed: u64) -> SyntheticSpeech {
+    SyntheticSpeech::new(seed)
+        .silence_ms(600)
+        .speech_ms(1800)
+        .silence_ms(900)
+}
+
+#[test]
+fn same_seed_renders_identical_audio() {
+    let a = utterance(42).render(Condition::Noisy { snr_db: 15.0 });
+    let b = utterance(42).render(Condition::Noisy { snr_db: 15.0 });
+    let c = utterance(43).render(Condition::Noisy { snr_db: 15.0 });
+    assert_eq!(a.samples, b.samples);
+    assert_ne!(a.samples, c.samples);
+    assert_eq!(a.speech_regions_ms(), vec![600..2400]);
+    assert_eq!(a.duration_ms(), 3300);
+}
+
+#[test]
+fn conditions_hit_their_levels() {
+    let clean = utterance(1).render(Condition::Clean);
+    let speech = &clean.samples[clean.speech_regions[0].clone()];
+    assert!(
+        (rms_dbfs(speech) + 20.0).abs() < 0.5,
+        "{}",
+        rms_dbfs(speech)
+    );
+    assert!(clean.samples[..clean.speech_regions[0].start]
+        .iter()
+        .all(|&s| s == 0));
+
+    let noisy = utterance(1).render(Condition::Noisy { snr_db: 10.0 });
+    let lead_in = &noisy.samples[..noisy.speech_regions[0].start];
+    assert!(
+        (rms_dbfs(lead_in) + 30.0).abs() < 1.0,
+        "{}",
+        rms_dbfs(lead_in)
+    );
+
+    let quiet = utterance(1).render(Condition::Quiet { gain_db: -25.0 });
+    let speech = &quiet.samples[quiet.speech_regions[0].clone()];
+    assert!(
+        (rms_dbfs(speech) + 45.0).abs() < 0.5,
+        "{}",
+        rms_dbfs(speech)
+    );
+
+    let clipped = utterance(1).render(Condition::Clipped { drive_db: 12.0 });
+    let saturated = clipped
+        .samples
+        .iter()
+        .filter(|&&s| s == i16::MAX || s == i16::MIN)
+        .count();
+    assert!(saturated > clipped.samples.len() / 100, "{}", saturated);
+}
+
+#[cfg(feature = "silero")]
+mod vad {
+    use super::*;
+    use coldvox_app::audio::vad_adapter::VadAdapter;
+    use coldvox_vad::{UnifiedVadConfig, VadEvent};
+
+    /// Timestamps (ms) of every speech start the VAD reports for `samples`
+    fn speech_starts(samples: &[i16]) -> Vec<u64> {
+        let config = UnifiedVadConfig::default();
+        let frame = config.frame_size_samples;
+        let mut vad = VadAdapter::new(config).expect("Silero VAD should load");
+        samples
+            .chunks_exact(frame)
+            .filter_map(|chunk| vad.process(chunk).expect("VAD processing failed"))
+            .filter_map(|ev| match ev {
+                VadEvent::SpeechStart { timestamp_ms, .. } => Some(timestamp_ms),
+                VadEvent::SpeechEnd { .. } => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn detects_speech_in_clean_noisy_and_clipped_audio() {
+        for condition in [
+            Condition::Clean,
+            Condition::Noisy { snr_db: 10.0 },
+            Condition::Clipped { drive_db: 6.0 },
+        ] {
+            let audio = utterance(7).render(condition);
+            let region = &audio.speech_regions_ms()[0];
+            let starts = speech_starts(&audio.samples);
+            assert!(
+                starts
+                    .iter()
+                    .any(|&t| t + 100 >= region.start && t < region.end),
+                "{:?}: expected a speech start within {:?}, got {:?}",
+                condition,
+                region,
+                starts
+            );
+        }
+    }
+
+    #[test]
+    fn ignores_background_noise() {
+        let starts = speech_starts(&noise(3000, -50.0, 3));
+        assert!(starts.is_empty(), "false speech starts at {:?}", starts);
+    }
+}
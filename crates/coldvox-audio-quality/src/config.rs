@@ -2,6 +2,8 @@
 
 use std::time::Duration;
 
+use crate::noise::{NoiseProfile, SPEECH_MARGIN_DB};
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -128,6 +130,19 @@ impl QualityConfig {
 
         config
     }
+
+    /// Adapt the level thresholds to a calibrated room: speech must clear
+    /// the measured noise floor by [`SPEECH_MARGIN_DB`] before it stops
+    /// counting as too quiet. The threshold is never lowered, and never
+    /// raised past the optimal range.
+    pub fn with_noise_profile(mut self, profile: &NoiseProfile) -> Self {
+        let noise_floor = profile.rms_dbfs + SPEECH_MARGIN_DB;
+        self.too_quiet_threshold_dbfs = self
+            .too_quiet_threshold_dbfs
+            .max(noise_floor)
+            .min(self.optimal_min_dbfs);
+        self
+    }
 }
 
 /// Builder for QualityConfig.
@@ -232,6 +247,28 @@ mod tests {
         assert_eq!(config.off_axis_threshold, 0.35);
     }
 
+    #[test]
+    fn test_noise_profile_raises_too_quiet_threshold() {
+        let profile = |rms_dbfs| NoiseProfile {
+            sample_rate: 16000,
+            spectrum_db: Vec::new(),
+            rms_dbfs,
+            peak_dbfs: rms_dbfs + 10.0,
+            duration_secs: 3.0,
+        };
+
+        // Quiet room: the default threshold already clears the noise
+        let config = QualityConfig::default().with_noise_profile(&profile(-70.0));
+        assert_eq!(config.too_quiet_threshold_dbfs, -40.0);
+
+        let config = QualityConfig::default().with_noise_profile(&profile(-45.0));
+        assert_eq!(config.too_quiet_threshold_dbfs, -35.0);
+
+        // Very loud room: capped at the optimal range
+        let config = QualityConfig::default().with_noise_profile(&profile(-20.0));
+        assert_eq!(config.too_quiet_threshold_dbfs, -25.0);
+    }
+
     #[test]
     fn test_omnidirectional_preset() {
         let config = QualityConfig::omnidirectional();
@@ -8,6 +8,8 @@
 //! - dBFS conversion
 //! - Spectral analysis for off-axis detection
 //! - Quality classification (Good/Warning)
+//! - Ambient noise profiles and spectral noise suppression
+//! - Log-spaced band levels for spectrum displays
 //!
 //! # Example
 //!
@@ -36,13 +38,17 @@
 
 pub mod config;
 pub mod level;
+pub mod noise;
 pub mod spectral;
+pub mod spectrum;
 pub mod types;
 
 // Re-export main types
 pub use config::QualityConfig;
 pub use level::LevelMonitor;
+pub use noise::{NoiseProfile, NoiseProfiler, NoiseSuppressor};
 pub use spectral::SpectralAnalyzer;
+pub use spectrum::BandSpectrum;
 pub use types::{QualityStatus, QualityWarning};
 
 use std::time::Instant;
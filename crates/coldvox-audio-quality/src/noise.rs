@@ -0,0 +1,384 @@
+//! Ambient noise profiles and spectral noise suppression.
+//!
+//! A [`NoiseProfile`] is learned from a few seconds of room noise (nobody
+//! speaking) with [`NoiseProfiler`]. It records the mean noise power per FFT
+//! bin plus the overall noise level, and is then used to:
+//!
+//! - subtract the noise spectrum from the live signal ([`NoiseSuppressor`])
+//! - raise the quality monitor's "too quiet" threshold above the measured
+//!   noise floor ([`QualityConfig::with_noise_profile`](crate::QualityConfig::with_noise_profile))
+//!
+//! Both the profiler and the suppressor analyse 512-sample frames with a
+//! square-root Hann window at 50% overlap, so profile and live spectra are
+//! directly comparable.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Analysis frame length in samples
+pub const FFT_SIZE: usize = 512;
+/// Frame advance (50% overlap)
+const HOP: usize = FFT_SIZE / 2;
+/// Bins in a one-sided spectrum
+const BINS: usize = FFT_SIZE / 2 + 1;
+/// Frames needed before a profile is trusted (~0.5 s at 16 kHz)
+const MIN_PROFILE_FRAMES: usize = 30;
+/// Noise estimate is scaled by this before subtraction, trading a little
+/// speech for less residual noise
+const OVER_SUBTRACTION: f32 = 2.0;
+/// Lowest per-bin gain (-20 dB); zeroing bins outright leaves "musical" noise
+const GAIN_FLOOR: f32 = 0.1;
+/// Speech has to clear the noise floor by this much to count as loud enough
+pub const SPEECH_MARGIN_DB: f32 = 10.0;
+
+/// Spectral fingerprint of the ambient noise at one place with one microphone
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NoiseProfile {
+    /// Sample rate the profile was learned at
+    pub sample_rate: u32,
+    /// Mean noise power per FFT bin in dB (`FFT_SIZE / 2 + 1` bins)
+    pub spectrum_db: Vec<f32>,
+    /// RMS level of the noise in dBFS
+    pub rms_dbfs: f32,
+    /// Loudest noise sample in dBFS
+    pub peak_dbfs: f32,
+    /// Seconds of audio the profile was learned from
+    pub duration_secs: f32,
+}
+
+/// Accumulates room noise into a [`NoiseProfile`]
+pub struct NoiseProfiler {
+    sample_rate: u32,
+    analysis: Analysis,
+    pending: Vec<f32>,
+    power_sum: Vec<f64>,
+    frames: usize,
+    sum_sq: f64,
+    samples: u64,
+    peak: u16,
+}
+
+impl NoiseProfiler {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            analysis: Analysis::new(),
+            pending: Vec::with_capacity(FFT_SIZE * 2),
+            power_sum: vec![0.0; BINS],
+            frames: 0,
+            sum_sq: 0.0,
+            samples: 0,
+            peak: 0,
+        }
+    }
+
+    /// Feed noise samples (mono i16 PCM at the profiler's sample rate)
+    pub fn push(&mut self, samples: &[i16]) {
+        for &s in samples {
+            self.sum_sq += f64::from(s) * f64::from(s);
+            self.peak = self.peak.max(s.unsigned_abs());
+            self.pending.push(f32::from(s) / 32768.0);
+        }
+        self.samples += samples.len() as u64;
+
+        while self.pending.len() >= FFT_SIZE {
+            let power = self.analysis.power(&self.pending[..FFT_SIZE]);
+            for (sum, p) in self.power_sum.iter_mut().zip(power) {
+                *sum += f64::from(*p);
+            }
+            self.frames += 1;
+            self.pending.drain(..HOP);
+        }
+    }
+
+    /// Seconds of audio fed so far
+    pub fn duration_secs(&self) -> f32 {
+        self.samples as f32 / self.sample_rate.max(1) as f32
+    }
+
+    /// The learned profile, or None if too little audio was fed
+    pub fn finish(self) -> Option<NoiseProfile> {
+        if self.frames < MIN_PROFILE_FRAMES {
+            return None;
+        }
+        let rms = (self.sum_sq / self.samples as f64).sqrt() / 32768.0;
+        Some(NoiseProfile {
+            sample_rate: self.sample_rate,
+            spectrum_db: self
+                .power_sum
+                .iter()
+                .map(|&sum| power_to_db((sum / self.frames as f64) as f32))
+                .collect(),
+            rms_dbfs: amplitude_to_dbfs(rms as f32),
+            peak_dbfs: amplitude_to_dbfs(f32::from(self.peak) / 32768.0),
+            duration_secs: self.duration_secs(),
+        })
+    }
+}
+
+/// Streaming spectral subtraction against a learned noise profile.
+///
+/// Output lags input by [`NoiseSuppressor::LATENCY_SAMPLES`]; every call
+/// returns as many samples as it was given.
+pub struct NoiseSuppressor {
+    noise_power: Vec<f32>,
+    analysis: Analysis,
+    inverse: Arc<dyn Fft<f32>>,
+    history: Vec<f32>,
+    pending: Vec<f32>,
+    tail: Vec<f32>,
+    output: VecDeque<i16>,
+}
+
+impl NoiseSuppressor {
+    /// Delay added by the overlap-add
+    pub const LATENCY_SAMPLES: usize = HOP;
+
+    pub fn new(profile: &NoiseProfile) -> Self {
+        let mut noise_power: Vec<f32> = profile
+            .spectrum_db
+            .iter()
+            .map(|&db| db_to_power(db))
+            .collect();
+        noise_power.resize(BINS, 0.0);
+        Self {
+            noise_power,
+            analysis: Analysis::new(),
+            inverse: FftPlanner::new().plan_fft_inverse(FFT_SIZE),
+            history: vec![0.0; FFT_SIZE],
+            pending: Vec::with_capacity(FFT_SIZE),
+            tail: vec![0.0; HOP],
+            output: VecDeque::with_capacity(FFT_SIZE * 2),
+        }
+    }
+
+    /// Suppress noise in `samples` (mono i16 PCM at the profile's rate)
+    pub fn process(&mut self, samples: &[i16]) -> Vec<i16> {
+        self.pending
+            .extend(samples.iter().map(|&s| f32::from(s) / 32768.0));
+        let mut hops = 0;
+        while self.pending.len() - hops * HOP >= HOP {
+            self.history.copy_within(HOP.., 0);
+            self.history[HOP..].copy_from_slice(&self.pending[hops * HOP..(hops + 1) * HOP]);
+            self.process_frame();
+            hops += 1;
+        }
+        self.pending.drain(..hops * HOP);
+
+        // Input that doesn't fill a hop yet is made up for with leading
+        // silence so frame sizes stay constant downstream
+        let ready = self.output.len().min(samples.len());
+        let mut out = vec![0i16; samples.len() - ready];
+        out.extend(self.output.drain(..ready));
+        out
+    }
+
+    /// Drop buffered audio, e.g. after a gap in the stream
+    pub fn reset(&mut self) {
+        self.history.fill(0.0);
+        self.tail.fill(0.0);
+        self.pending.clear();
+        self.output.clear();
+    }
+
+    fn process_frame(&mut self) {
+        let buffer = self.analysis.spectrum(&self.history);
+        for k in 0..BINS {
+            let power = buffer[k].norm_sqr() / (FFT_SIZE * FFT_SIZE) as f32;
+            let gain = if power > 0.0 {
+                (1.0 - OVER_SUBTRACTION * self.noise_power[k] / power)
+                    .max(GAIN_FLOOR * GAIN_FLOOR)
+                    .sqrt()
+            } else {
+                GAIN_FLOOR
+            };
+            buffer[k] *= gain;
+            if k > 0 && k < FFT_SIZE / 2 {
+                buffer[FFT_SIZE - k] *= gain;
+            }
+        }
+        self.inverse.process(buffer);
+
+        let Analysis { buffer, window, .. } = &self.analysis;
+        let scale = 1.0 / FFT_SIZE as f32;
+        for i in 0..FFT_SIZE {
+            let y = buffer[i].re * scale * window[i];
+            if i < HOP {
+                let s = (self.tail[i] + y).clamp(-1.0, 1.0);
+                self.output.push_back((s * 32767.0).round() as i16);
+            } else {
+                self.tail[i - HOP] = y;
+            }
+        }
+    }
+}
+
+/// Windowed forward FFT shared by the profiler and the suppressor
+struct Analysis {
+    forward: Arc<dyn Fft<f32>>,
+    /// Square-root periodic Hann: applied on analysis and synthesis, the two
+    /// multiply to a Hann window, which sums to one at 50% overlap
+    window: Vec<f32>,
+    buffer: Vec<Complex<f32>>,
+    power: Vec<f32>,
+}
+
+impl Analysis {
+    fn new() -> Self {
+        let window = (0..FFT_SIZE)
+            .map(|i| {
+                let phase = 2.0 * std::f32::consts::PI * i as f32 / FFT_SIZE as f32;
+                (0.5 - 0.5 * phase.cos()).sqrt()
+            })
+            .collect();
+        Self {
+            forward: FftPlanner::new().plan_fft_forward(FFT_SIZE),
+            window,
+            buffer: vec![Complex::default(); FFT_SIZE],
+            power: vec![0.0; BINS],
+        }
+    }
+
+    /// Spectrum of one frame (left in the scratch buffer)
+    fn spectrum(&mut self, frame: &[f32]) -> &mut [Complex<f32>] {
+        for ((b, &x), &w) in self.buffer.iter_mut().zip(frame).zip(&self.window) {
+            *b = Complex::new(x * w, 0.0);
+        }
+        self.forward.process(&mut self.buffer);
+        &mut self.buffer
+    }
+
+    /// Power per bin of one frame
+    fn power(&mut self, frame: &[f32]) -> &[f32] {
+        let scale = (FFT_SIZE * FFT_SIZE) as f32;
+        self.spectrum(frame);
+        for (p, b) in self.power.iter_mut().zip(&self.buffer) {
+            *p = b.norm_sqr() / scale;
+        }
+        &self.power
+    }
+}
+
+fn power_to_db(power: f32) -> f32 {
+    10.0 * power.max(1e-12).log10()
+}
+
+fn db_to_power(db: f32) -> f32 {
+    10f32.powf(db / 10.0)
+}
+
+fn amplitude_to_dbfs(amplitude: f32) -> f32 {
+    20.0 * amplitude.max(1e-6).log10()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic white noise at roughly `amplitude` peak
+    fn white_noise(len: usize, amplitude: f32, seed: u32) -> Vec<i16> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                let unit = (state >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0;
+                (unit * amplitude) as i16
+            })
+            .collect()
+    }
+
+    fn tone(len: usize, hz: f32, amplitude: f32) -> Vec<i16> {
+        (0..len)
+            .map(|i| {
+                let t = i as f32 / 16_000.0;
+                ((2.0 * std::f32::consts::PI * hz * t).sin() * amplitude) as i16
+            })
+            .collect()
+    }
+
+    fn rms(samples: &[i16]) -> f32 {
+        let sum: f64 = samples.iter().map(|&s| f64::from(s) * f64::from(s)).sum();
+        (sum / samples.len() as f64).sqrt() as f32
+    }
+
+    fn learn(noise: &[i16]) -> NoiseProfile {
+        let mut profiler = NoiseProfiler::new(16_000);
+        for frame in noise.chunks(512) {
+            profiler.push(frame);
+        }
+        profiler.finish().expect("enough audio for a profile")
+    }
+
+    #[test]
+    fn profile_needs_enough_audio_and_measures_the_level() {
+        let mut short = NoiseProfiler::new(16_000);
+        short.push(&white_noise(2_000, 1_000.0, 1));
+        assert!(short.finish().is_none());
+
+        let profile = learn(&white_noise(48_000, 1_000.0, 1));
+        assert_eq!(profile.spectrum_db.len(), FFT_SIZE / 2 + 1);
+        assert!((profile.duration_secs - 3.0).abs() < 1e-3);
+        // Uniform noise at ±1000 has an RMS of 1000/√3 ≈ -35.1 dBFS
+        assert!(
+            (profile.rms_dbfs + 35.1).abs() < 0.5,
+            "rms {}",
+            profile.rms_dbfs
+        );
+        assert!(profile.peak_dbfs <= -30.0);
+    }
+
+    #[test]
+    fn unity_gain_reconstructs_the_input_with_a_hop_of_delay() {
+        let silent = NoiseProfile {
+            sample_rate: 16_000,
+            spectrum_db: vec![-240.0; FFT_SIZE / 2 + 1],
+            rms_dbfs: -120.0,
+            peak_dbfs: -120.0,
+            duration_secs: 1.0,
+        };
+        let mut suppressor = NoiseSuppressor::new(&silent);
+        let input = tone(4_096, 440.0, 8_000.0);
+        let output: Vec<i16> = input
+            .chunks(512)
+            .flat_map(|frame| suppressor.process(frame))
+            .collect();
+        assert_eq!(output.len(), input.len());
+        let delay = NoiseSuppressor::LATENCY_SAMPLES;
+        for (i, (&out, &inp)) in output[delay..].iter().zip(&input).enumerate() {
+            assert!((i32::from(out) - i32::from(inp)).abs() <= 2, "sample {}", i);
+        }
+    }
+
+    #[test]
+    fn attenuates_profiled_noise_but_keeps_speech_band_tone() {
+        let profile = learn(&white_noise(32_000, 1_000.0, 7));
+        let mut suppressor = NoiseSuppressor::new(&profile);
+
+        let noise = white_noise(16_000, 1_000.0, 99);
+        let cleaned: Vec<i16> = noise
+            .chunks(512)
+            .flat_map(|frame| suppressor.process(frame))
+            .collect();
+        // Roughly -9 dB: bins that briefly rise above twice the profile survive
+        assert!(rms(&cleaned) < rms(&noise) * 0.5);
+
+        suppressor.reset();
+        let speech: Vec<i16> = tone(16_000, 300.0, 8_000.0)
+            .iter()
+            .zip(white_noise(16_000, 1_000.0, 5))
+            .map(|(&t, n)| t.saturating_add(n))
+            .collect();
+        let kept: Vec<i16> = speech
+            .chunks(512)
+            .flat_map(|frame| suppressor.process(frame))
+            .collect();
+        assert!(rms(&kept) > rms(&speech) * 0.9);
+    }
+}
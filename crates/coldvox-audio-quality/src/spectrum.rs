@@ -0,0 +1,159 @@
+//! Band levels for spectrum displays.
+//!
+//! [`BandSpectrum`] turns a sample stream into levels for a handful of
+//! log-spaced frequency bands, one set per 512-sample frame (32 ms at
+//! 16 kHz). Log spacing matches how speech looks: its energy sits in a few
+//! hundred Hz to a few kHz, while hum and fan noise pile up at the bottom
+//! and hiss spreads evenly across the top.
+
+use std::sync::Arc;
+
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+
+/// Analysis frame length in samples
+pub const FFT_SIZE: usize = 512;
+/// Lower edge of the lowest band
+pub const MIN_BAND_HZ: f32 = 80.0;
+/// Bins in a one-sided spectrum
+const BINS: usize = FFT_SIZE / 2 + 1;
+/// Level reported for a band with no energy at all
+const FLOOR_DBFS: f32 = -120.0;
+/// Energy a Hann-windowed sine spreads over its main lobe, relative to the
+/// peak bin (1 + 2 × 0.25), so a full-scale sine reads 0 dBFS
+const HANN_SINE_ENERGY: f32 = 1.5;
+
+/// Per-band levels in dBFS over consecutive frames
+pub struct BandSpectrum {
+    forward: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+    buffer: Vec<Complex<f32>>,
+    pending: Vec<f32>,
+    /// Bin range of each band, lowest band first
+    bands: Vec<std::ops::Range<usize>>,
+    levels: Vec<f32>,
+}
+
+impl BandSpectrum {
+    /// Analyzer for `band_count` log-spaced bands from [`MIN_BAND_HZ`] to
+    /// the Nyquist frequency of `sample_rate`. Bands narrower than an FFT
+    /// bin are widened to one bin.
+    pub fn new(sample_rate: u32, band_count: usize) -> Self {
+        let bin_hz = sample_rate as f32 / FFT_SIZE as f32;
+        let max_hz = sample_rate as f32 / 2.0;
+        let ratio = (max_hz / MIN_BAND_HZ).powf(1.0 / band_count.max(1) as f32);
+        let mut edges: Vec<usize> = (0..=band_count)
+            .map(|i| (MIN_BAND_HZ * ratio.powi(i as i32) / bin_hz).round() as usize)
+            .collect();
+        for i in 1..edges.len() {
+            edges[i] = edges[i].max(edges[i - 1] + 1).min(BINS);
+        }
+        let bands = edges.windows(2).map(|e| e[0]..e[1].max(e[0])).collect();
+
+        let window = (0..FFT_SIZE)
+            .map(|i| {
+                let phase = 2.0 * std::f32::consts::PI * i as f32 / FFT_SIZE as f32;
+                0.5 - 0.5 * phase.cos()
+            })
+            .collect();
+        Self {
+            forward: FftPlanner::new().plan_fft_forward(FFT_SIZE),
+            window,
+            buffer: vec![Complex::default(); FFT_SIZE],
+            pending: Vec::with_capacity(FFT_SIZE),
+            bands,
+            levels: vec![FLOOR_DBFS; band_count],
+        }
+    }
+
+    /// Number of bands, lowest first in [`levels`](Self::levels)
+    pub fn band_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Levels of the most recent frame in dBFS, lowest band first
+    pub fn levels(&self) -> &[f32] {
+        &self.levels
+    }
+
+    /// Feed samples. Returns the new levels when at least one full frame
+    /// was analysed (the last one, if `samples` spanned several).
+    pub fn push(&mut self, samples: &[i16]) -> Option<&[f32]> {
+        let mut updated = false;
+        for &s in samples {
+            self.pending.push(s as f32 / 32768.0);
+            if self.pending.len() == FFT_SIZE {
+                self.analyse();
+                self.pending.clear();
+                updated = true;
+            }
+        }
+        updated.then_some(&self.levels[..])
+    }
+
+    fn analyse(&mut self) {
+        for ((b, &x), &w) in self.buffer.iter_mut().zip(&self.pending).zip(&self.window) {
+            *b = Complex::new(x * w, 0.0);
+        }
+        self.forward.process(&mut self.buffer);
+
+        // A full-scale sine peaks at N/4 after the Hann window
+        let reference = (FFT_SIZE as f32 / 4.0).powi(2) * HANN_SINE_ENERGY;
+        for (level, band) in self.levels.iter_mut().zip(&self.bands) {
+            let energy: f32 = self.buffer[band.clone()].iter().map(|b| b.norm_sqr()).sum();
+            *level = if energy > 0.0 {
+                (10.0 * (energy / reference).log10()).max(FLOOR_DBFS)
+            } else {
+                FLOOR_DBFS
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tone_lands_in_its_band_at_its_level() {
+        let mut spectrum = BandSpectrum::new(16_000, 16);
+        let tone: Vec<i16> = (0..FFT_SIZE * 2)
+            .map(|i| {
+                let t = i as f32 / 16_000.0;
+                ((2.0 * std::f32::consts::PI * 1000.0 * t).sin() * 16_384.0) as i16
+            })
+            .collect();
+
+        // Nothing until a full frame has arrived
+        assert!(spectrum.push(&tone[..100]).is_none());
+        let levels = spectrum.push(&tone[100..]).unwrap().to_vec();
+        assert_eq!(levels.len(), 16);
+
+        let loudest = (0..levels.len())
+            .max_by(|&a, &b| levels[a].total_cmp(&levels[b]))
+            .unwrap();
+        let range = &spectrum.bands[loudest];
+        let bin_hz = 16_000.0 / FFT_SIZE as f32;
+        assert!(
+            (range.start as f32 * bin_hz..=range.end as f32 * bin_hz).contains(&1000.0),
+            "1 kHz tone peaked in band {:?}",
+            range
+        );
+        // Half scale is -6 dBFS
+        assert!((levels[loudest] + 6.0).abs() < 1.5, "{}", levels[loudest]);
+
+        let silence = vec![0i16; FFT_SIZE];
+        let levels = spectrum.push(&silence).unwrap();
+        assert!(levels.iter().all(|&l| l == FLOOR_DBFS));
+    }
+
+    #[test]
+    fn bands_are_contiguous_and_non_empty() {
+        let spectrum = BandSpectrum::new(16_000, 24);
+        for pair in spectrum.bands.windows(2) {
+            assert!(!pair[0].is_empty());
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+        assert!(spectrum.bands.last().unwrap().end <= BINS);
+    }
+}
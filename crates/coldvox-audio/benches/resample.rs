@@ -0,0 +1,78 @@
+use coldvox_audio::{convert, ResamplerQuality, StreamResampler};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::hint::black_box;
+
+/// 10 ms callbacks of interleaved 48 kHz audio with a 440 Hz tone
+fn capture_block(channels: usize) -> Vec<i16> {
+    (0..480)
+        .flat_map(|i| {
+            let t = i as f32 / 48_000.0;
+            let s = ((2.0 * std::f32::consts::PI * 440.0 * t).sin() * 16_000.0) as i16;
+            std::iter::repeat_n(s, channels)
+        })
+        .collect()
+}
+
+fn bench_resample_48k_to_16k(c: &mut Criterion) {
+    let mut group = c.benchmark_group("resample_48k_to_16k");
+    let input = capture_block(1);
+    group.throughput(Throughput::Elements(input.len() as u64));
+
+    for quality in [
+        ResamplerQuality::Fast,
+        ResamplerQuality::Balanced,
+        ResamplerQuality::Quality,
+    ] {
+        let mut resampler = StreamResampler::new_with_quality(48_000, 16_000, quality);
+        let mut out = Vec::new();
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{:?}", quality)),
+            &input,
+            |b, input| {
+                b.iter(|| {
+                    out.clear();
+                    resampler.process_into(black_box(input), &mut out);
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn bench_conversion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("conversion");
+    let stereo = capture_block(2);
+    group.throughput(Throughput::Elements(stereo.len() as u64));
+
+    let mut mono = Vec::new();
+    group.bench_function("downmix_stereo", |b| {
+        b.iter(|| {
+            mono.clear();
+            convert::downmix(black_box(&stereo), 2, &mut mono);
+        })
+    });
+
+    let mut floats = Vec::new();
+    convert::i16_to_f32(&stereo, &mut floats);
+    let mut scratch = Vec::new();
+    group.bench_function("i16_to_f32", |b| {
+        b.iter(|| {
+            scratch.clear();
+            convert::i16_to_f32(black_box(&stereo), &mut scratch);
+        })
+    });
+
+    let mut ints = Vec::new();
+    group.bench_function("f32_to_i16", |b| {
+        b.iter(|| {
+            ints.clear();
+            convert::f32_to_i16(black_box(&floats), &mut ints);
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_resample_48k_to_16k, bench_conversion);
+criterion_main!(benches);
@@ -7,13 +7,16 @@ use std::sync::Arc;
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
+use super::chunker::ChannelSelection;
 use super::detector::SilenceDetector;
 use super::device::DeviceManager;
+use super::format_watch::FormatWatch;
 use super::monitor::DeviceMonitor;
 // Test hook output
 
 use super::ring_buffer::AudioProducer;
 use super::watchdog::WatchdogTimer;
+use coldvox_foundation::error::ColdVoxError;
 use coldvox_foundation::{AudioConfig, AudioError, DeviceEvent};
 
 // This remains the primary data structure for audio data.
@@ -26,9 +29,46 @@ pub struct AudioCapture {
     stats: Arc<CaptureStats>,
     running: Arc<AtomicBool>,
     restart_needed: Arc<AtomicBool>,
+    /// Set by the data callback when the device format changed under the stream
+    format_changed: Arc<AtomicBool>,
+    /// While set, the device keeps running but silence is captured instead
+    muted: Arc<AtomicBool>,
     config_tx: Option<tokio::sync::broadcast::Sender<DeviceConfig>>,
     device_event_tx: Option<tokio::sync::broadcast::Sender<DeviceEvent>>,
     current_device_name: Option<String>,
+    /// Requested input channel(s), resolved against each opened device
+    channel: ChannelSelection,
+}
+
+/// Zeros written in place of audio while muted
+static SILENCE: [i16; 4096] = [0; 4096];
+
+/// Delay between failover attempts when no input device can be opened.
+const FAILOVER_RETRY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Ordered device attempts after the active device was lost: configured
+/// fallbacks, then the configured device, then system candidates, then the
+/// host default (`None`). The lost device is skipped.
+fn failover_candidates(
+    configured: Option<&str>,
+    fallbacks: &[String],
+    lost: Option<&str>,
+    system: Vec<String>,
+) -> Vec<Option<String>> {
+    let mut names: Vec<String> = Vec::new();
+    let ordered = fallbacks
+        .iter()
+        .map(String::as_str)
+        .chain(configured)
+        .chain(system.iter().map(String::as_str));
+    for name in ordered {
+        if Some(name) != lost && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    let mut attempts: Vec<Option<String>> = names.into_iter().map(Some).collect();
+    attempts.push(None);
+    attempts
 }
 
 // Device configuration info
@@ -36,6 +76,8 @@ pub struct AudioCapture {
 pub struct DeviceConfig {
     pub sample_rate: u32,
     pub channels: u16,
+    /// Channel(s) of this device feeding the mono pipeline
+    pub channel: ChannelSelection,
 }
 
 // A handle to the dedicated audio thread.
@@ -43,6 +85,14 @@ pub struct AudioCaptureThread {
     pub handle: JoinHandle<()>,
     pub shutdown: Arc<AtomicBool>,
     pub device_monitor_handle: Option<JoinHandle<()>>,
+    /// Requested suspension of the input stream (see [`Self::suspend`])
+    pub suspended: Arc<AtomicBool>,
+    /// Microphone mute (see [`Self::set_muted`])
+    pub muted: Arc<AtomicBool>,
+    /// Pending request to reopen the device (see [`Self::revalidate_device`])
+    pub revalidate: Arc<AtomicBool>,
+    /// Pending request to move to another device (see [`Self::switch_device`])
+    pub switch_to: Arc<Mutex<Option<String>>>,
 }
 
 impl AudioCaptureThread {
@@ -59,6 +109,38 @@ impl AudioCaptureThread {
             tokio::sync::broadcast::Receiver<DeviceEvent>,
         ),
         AudioError,
+    > {
+        Self::spawn_with_fallbacks(
+            config,
+            audio_producer,
+            device_name,
+            Vec::new(),
+            ChannelSelection::Mix,
+            enable_device_monitor,
+        )
+    }
+
+    /// Like [`spawn`](Self::spawn), but with an ordered list of fallback devices
+    /// to re-open when the active device disappears. When none of them are
+    /// available the system candidates (and finally the host default) are used.
+    /// If the configured device comes back, capture switches back to it.
+    /// `channel` picks the input channel(s) on every device opened; the
+    /// resolved choice is published in each [`DeviceConfig`].
+    pub fn spawn_with_fallbacks(
+        config: AudioConfig,
+        audio_producer: Arc<Mutex<AudioProducer>>,
+        device_name: Option<String>,
+        fallback_devices: Vec<String>,
+        channel: ChannelSelection,
+        enable_device_monitor: bool,
+    ) -> Result<
+        (
+            Self,
+            DeviceConfig,
+            tokio::sync::broadcast::Receiver<DeviceConfig>,
+            tokio::sync::broadcast::Receiver<DeviceEvent>,
+        ),
+        AudioError,
     > {
         // Start in running state so the device monitor thread stays alive
         // until we explicitly stop via `stop()`. Previously this was false,
@@ -66,6 +148,14 @@ impl AudioCaptureThread {
         // detect a closed channel and terminate early.
         let running = Arc::new(AtomicBool::new(true));
         let shutdown = running.clone();
+        let suspended = Arc::new(AtomicBool::new(false));
+        let suspend_requested = suspended.clone();
+        let muted = Arc::new(AtomicBool::new(false));
+        let mute_requested = muted.clone();
+        let revalidate = Arc::new(AtomicBool::new(false));
+        let revalidate_requested = revalidate.clone();
+        let switch_to = Arc::new(Mutex::new(None::<String>));
+        let switch_requested = switch_to.clone();
         let device_config = Arc::new(RwLock::new(None::<DeviceConfig>));
         let device_config_clone = device_config.clone();
 
@@ -77,10 +167,19 @@ impl AudioCaptureThread {
         let (device_event_tx, device_event_rx) = tokio::sync::broadcast::channel(32);
         let device_event_tx_clone = device_event_tx.clone();
 
+        // Name of the device capture is currently running on, shared with the monitor
+        // so it can tell when the *active* device disappears.
+        let active_device = Arc::new(RwLock::new(None::<String>));
+
         // Start device monitor with 2-second interval to reduce false positives from CPAL enumeration glitches
         let monitor_running = running.clone();
         let (monitor_rx_opt, monitor_handle) = if enable_device_monitor {
-            let (device_monitor, monitor_rx) = DeviceMonitor::new(Duration::from_secs(2))?;
+            let (mut device_monitor, monitor_rx) = DeviceMonitor::new(Duration::from_secs(2))?;
+            device_monitor.set_active_device_handle(active_device.clone());
+            let mut preferred = Vec::new();
+            preferred.extend(device_name.clone());
+            preferred.extend(fallback_devices.iter().cloned());
+            device_monitor.set_preferred_devices(preferred);
             let handle = device_monitor.start_monitoring(monitor_running);
             (Some(monitor_rx), Some(handle))
         } else {
@@ -92,9 +191,16 @@ impl AudioCaptureThread {
             .name("audio-capture".to_string())
             .spawn(move || {
                 let mut monitor_rx = monitor_rx_opt;
-                let mut capture = match AudioCapture::new(config, audio_producer, running.clone()) {
+                // The device failover returns to; a requested switch replaces it
+                let mut device_name = device_name;
+                // The stream has its own running flag: stopping a stream for a restart
+                // must not end this thread or the device monitor.
+                let stream_running = Arc::new(AtomicBool::new(false));
+                let mut capture = match AudioCapture::new(config, audio_producer, stream_running) {
                     Ok(c) => c.with_config_channel(config_tx_clone)
-                              .with_device_event_channel(device_event_tx_clone),
+                              .with_device_event_channel(device_event_tx_clone)
+                              .with_mute_flag(mute_requested)
+                              .with_channel(channel),
                     Err(e) => {
                         tracing::error!("Failed to create AudioCapture: {}", e);
                         return;
@@ -116,6 +222,7 @@ impl AudioCaptureThread {
                         Ok(cfg) => {
                             tracing::info!("Audio stream started on device: {:?}", attempt);
                             capture.current_device_name = attempt.clone();
+                            *active_device.write() = attempt.clone().or_else(|| capture.device_manager.default_input_device_name());
 
                             // Preflight: wait up to 3s for at least one frame
                             let start = Instant::now();
@@ -151,9 +258,13 @@ impl AudioCaptureThread {
                 *device_config_clone.write() = Some(dev_cfg);
 
                 // Monitor for watchdog, error-triggered restarts, and device events
+                let mut retry_at: Option<Instant> = None;
+                let mut stream_suspended = false;
                 while running.load(Ordering::Relaxed) {
                     let mut needs_restart = false;
                     let mut restart_reason = "unknown";
+                    let mut restart_target: Option<String> = None;
+                    let mut lost_device: Option<String> = None;
 
                     // Check for device monitor events
                     if let Some(rx) = monitor_rx.as_mut() {
@@ -164,20 +275,28 @@ impl AudioCaptureThread {
 
                                 match event {
                                     DeviceEvent::CurrentDeviceDisconnected { name } => {
-                                        if capture.current_device_name.as_ref() == Some(&name) {
-                                            tracing::warn!("Current device {} disconnected, attempting recovery", name);
-                                            needs_restart = true;
-                                            restart_reason = "device disconnected";
-                                        }
+                                        tracing::warn!("Current device {} disconnected, failing over", name);
+                                        capture.stats.disconnections.fetch_add(1, Ordering::Relaxed);
+                                        needs_restart = true;
+                                        restart_reason = "device disconnected";
+                                        lost_device = Some(name);
                                     }
                                     DeviceEvent::DeviceAdded { name } => {
                                         tracing::info!("New device available: {}", name);
-                                        // Could implement automatic switching to preferred devices here
+                                        // Return to the configured device once it is plugged back in
+                                        if device_name.as_ref() == Some(&name)
+                                            && capture.current_device_name.as_ref() != Some(&name)
+                                        {
+                                            needs_restart = true;
+                                            restart_reason = "configured device reconnected";
+                                            restart_target = Some(name);
+                                        }
                                     }
                                     DeviceEvent::DeviceSwitchRequested { target } => {
                                         tracing::info!("Manual device switch requested to: {}", target);
                                         needs_restart = true;
                                         restart_reason = "manual device switch requested";
+                                        restart_target = Some(target);
                                     }
                                     _ => {}
                                 }
@@ -195,6 +314,58 @@ impl AudioCaptureThread {
                         }
                     }
 
+                    // Held while suspended; resuming reopens the old device first
+                    let mut switch_target: Option<String> = None;
+                    let requested = if stream_suspended { None } else { switch_requested.lock().take() };
+                    if let Some(target) = requested {
+                        tracing::info!("Device switch requested to: {}", target);
+                        let _ = capture.device_event_tx.as_ref().map(|tx| {
+                            tx.send(DeviceEvent::DeviceSwitchRequested { target: target.clone() })
+                        });
+                        needs_restart = true;
+                        restart_reason = "device switch requested";
+                        restart_target = Some(target.clone());
+                        switch_target = Some(target);
+                    }
+
+                    if revalidate_requested.swap(false, Ordering::Relaxed) {
+                        needs_restart = true;
+                        restart_reason = "system resumed";
+                        restart_target = restart_target.or_else(|| capture.current_device_name.clone());
+                    }
+
+                    // Close or reopen the stream when suspension is toggled
+                    let want_suspended = suspend_requested.load(Ordering::Relaxed);
+                    if want_suspended != stream_suspended {
+                        stream_suspended = want_suspended;
+                        if stream_suspended {
+                            capture.stop();
+                            capture.restart_needed.store(false, Ordering::SeqCst);
+                            retry_at = None;
+                            tracing::info!("Audio capture suspended");
+                        } else {
+                            let device = capture.current_device_name.clone();
+                            match capture.start(device.as_deref()) {
+                                Ok(cfg) => {
+                                    *device_config_clone.write() = Some(cfg);
+                                    tracing::info!("Audio capture resumed on device: {:?}", device);
+                                }
+                                Err(e) => {
+                                    // Device went away while suspended; fail over
+                                    tracing::warn!("Resume failed on {:?}: {}", device, e);
+                                    retry_at = Some(Instant::now());
+                                }
+                            }
+                        }
+                    }
+                    if stream_suspended {
+                        if needs_restart {
+                            tracing::debug!("Capture suspended; ignoring restart ({})", restart_reason);
+                        }
+                        thread::sleep(Duration::from_millis(100));
+                        continue;
+                    }
+
                     // Check existing restart conditions
                     if capture.watchdog.is_triggered() {
                         needs_restart = true;
@@ -206,26 +377,49 @@ impl AudioCaptureThread {
                         restart_reason = "stream error";
                     }
 
+                    // Renegotiate on the same device first
+                    if capture.format_changed.load(Ordering::SeqCst) {
+                        needs_restart = true;
+                        restart_reason = "device format changed";
+                        restart_target = restart_target.or_else(|| capture.current_device_name.clone());
+                    }
+
+                    // A previous failover found no usable device; retry with backoff
+                    if retry_at.is_some_and(|at| Instant::now() >= at) {
+                        needs_restart = true;
+                        restart_reason = "retrying failover";
+                    }
+
                     if needs_restart {
                         tracing::warn!("Capture restart triggered ({})", restart_reason);
                         let old_device = capture.current_device_name.clone();
                         capture.stop();
                         capture.restart_needed.store(false, Ordering::SeqCst);
 
-                        // Attempt re-open starting from current priority list
-                        let mut restarted = false;
                         let mut attempts: Vec<Option<String>> = Vec::new();
-                        let candidates = capture.device_manager.candidate_device_names();
-                        for name in candidates { attempts.push(Some(name)); }
-                        attempts.push(None);
+                        if let Some(target) = restart_target {
+                            attempts.push(Some(target));
+                        }
+                        attempts.extend(failover_candidates(
+                            device_name.as_deref(),
+                            &fallback_devices,
+                            lost_device.as_deref(),
+                            capture.device_manager.candidate_device_names(),
+                        ));
 
+                        let mut restarted = false;
                         for attempt in attempts {
                             match capture.start(attempt.as_deref()) {
                                 Ok(cfg) => {
                                     tracing::info!("Capture restarted on device: {:?}", attempt);
+                                    if switch_target.is_some() && attempt == switch_target {
+                                        device_name = switch_target.take();
+                                    }
                                     let new_device = attempt.clone();
                                     capture.current_device_name = new_device.clone();
+                                    *active_device.write() = new_device.clone().or_else(|| capture.device_manager.default_input_device_name());
                                     *device_config_clone.write() = Some(cfg);
+                                    capture.stats.reconnections.fetch_add(1, Ordering::Relaxed);
 
                                     // Emit device switch event
                                     let switch_event = DeviceEvent::DeviceSwitched {
@@ -242,11 +436,17 @@ impl AudioCaptureThread {
                                 }
                             }
                         }
-                        if !restarted {
-                            tracing::error!("Failed to restart capture on any candidate device");
+                        if restarted {
+                            retry_at = None;
+                        } else {
+                            tracing::error!(
+                                "Failed to restart capture on any candidate device; retrying in {:?}",
+                                FAILOVER_RETRY_INTERVAL
+                            );
+                            retry_at = Some(Instant::now() + FAILOVER_RETRY_INTERVAL);
                             let switch_event = DeviceEvent::DeviceSwitchFailed {
                                 attempted: old_device.unwrap_or_else(|| "unknown".to_string()),
-                                fallback: None,
+                                fallback: fallback_devices.first().cloned(),
                             };
                             let _ = capture.device_event_tx.as_ref().map(|tx| tx.send(switch_event));
                         }
@@ -279,6 +479,10 @@ impl AudioCaptureThread {
                 handle,
                 shutdown,
                 device_monitor_handle: monitor_handle,
+                suspended,
+                muted,
+                revalidate,
+                switch_to,
             },
             cfg,
             config_rx,
@@ -286,6 +490,52 @@ impl AudioCaptureThread {
         ))
     }
 
+    /// Close the input stream without ending the capture thread, so the
+    /// device (and the CPU) can idle. The device monitor keeps running.
+    pub fn suspend(&self) {
+        self.suspended.store(true, Ordering::Relaxed);
+    }
+
+    /// Reopen the input stream on the device it was last running on,
+    /// failing over as usual if that device is gone.
+    pub fn resume(&self) {
+        self.suspended.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        self.suspended.load(Ordering::Relaxed)
+    }
+
+    /// Reopen the input stream, on the same device if it is still there and
+    /// failing over otherwise. Used after a system resume, when the device
+    /// may have changed or stopped delivering audio while asleep. Ignored
+    /// while capture is suspended; resuming reopens the device anyway.
+    pub fn revalidate_device(&self) {
+        self.revalidate.store(true, Ordering::Relaxed);
+    }
+
+    /// Reopen the input stream on `device` without restarting the thread.
+    /// On success it replaces the configured device, so failover returns
+    /// to it; if it can't be opened capture fails over as usual. Emits
+    /// `DeviceSwitchRequested` and then `DeviceSwitched` or
+    /// `DeviceSwitchFailed`. While capture is suspended the switch waits
+    /// for [`resume`](Self::resume).
+    pub fn switch_device(&self, device: String) {
+        *self.switch_to.lock() = Some(device);
+    }
+
+    /// Capture silence instead of the microphone, e.g. while ColdVox itself
+    /// is playing audio that would otherwise be transcribed. Unlike
+    /// [`suspend`](Self::suspend) the stream stays open, so unmuting is
+    /// instant and VAD sees the silence end any open utterance.
+    pub fn set_muted(&self, muted: bool) {
+        self.muted.store(muted, Ordering::Relaxed);
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.muted.load(Ordering::Relaxed)
+    }
+
     pub fn stop(self) {
         self.shutdown.store(false, Ordering::Relaxed);
         let _ = self.handle.join();
@@ -329,9 +579,12 @@ impl AudioCapture {
             stats: Arc::new(CaptureStats::default()),
             running,
             restart_needed: Arc::new(AtomicBool::new(false)),
+            format_changed: Arc::new(AtomicBool::new(false)),
+            muted: Arc::new(AtomicBool::new(false)),
             config_tx: None,
             device_event_tx: None,
             current_device_name: None,
+            channel: ChannelSelection::Mix,
         };
 
         // Check audio setup for PipeWire compatibility (baseline timing)
@@ -375,8 +628,20 @@ impl AudioCapture {
         self
     }
 
+    pub fn with_mute_flag(mut self, muted: Arc<AtomicBool>) -> Self {
+        self.muted = muted;
+        self
+    }
+
+    /// Feed the pipeline from `channel` of each device opened
+    pub fn with_channel(mut self, channel: ChannelSelection) -> Self {
+        self.channel = channel;
+        self
+    }
+
     fn start(&mut self, device_name: Option<&str>) -> Result<DeviceConfig, AudioError> {
         self.running.store(true, Ordering::SeqCst);
+        self.format_changed.store(false, Ordering::SeqCst);
 
         let device = self.device_manager.open_device(device_name)?;
         if let Ok(desc) = device.description() {
@@ -388,9 +653,18 @@ impl AudioCapture {
         }
         let (config, sample_format) = self.negotiate_config(&device)?;
 
+        let channel = self.channel.resolve(config.channels);
+        if channel != self.channel {
+            tracing::warn!(
+                "Configured audio channel {} not available on {}-channel device; downmixing instead",
+                self.channel,
+                config.channels
+            );
+        }
         let device_config = DeviceConfig {
             sample_rate: config.sample_rate,
             channels: config.channels,
+            channel,
         };
 
         // Broadcast the device config change if we have a channel
@@ -418,6 +692,9 @@ impl AudioCapture {
         let detector = Arc::new(RwLock::new(self.silence_detector.clone()));
         let running = Arc::clone(&self.running);
         let restart_needed = Arc::clone(&self.restart_needed);
+        let format_changed = Arc::clone(&self.format_changed);
+        let format_watch = Mutex::new(FormatWatch::new(config.sample_rate, config.channels));
+        let muted = Arc::clone(&self.muted);
 
         let err_fn = move |err: cpal::StreamError| {
             tracing::error!("Audio stream error: {}", err);
@@ -431,6 +708,18 @@ impl AudioCapture {
                 return;
             }
             watchdog.feed();
+            if let Some(drift) = format_watch.lock().observe(i16_data.len(), Instant::now()) {
+                // Drop audio until the stream is rebuilt rather than pass on garbage
+                if !format_changed.swap(true, Ordering::SeqCst) {
+                    tracing::warn!("Input format changed mid-stream ({}); renegotiating", drift);
+                }
+                stats.frames_dropped.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+            if format_changed.load(Ordering::Relaxed) {
+                stats.frames_dropped.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
             let mut det = detector.write();
             if det.is_silence(i16_data) {
                 stats.silent_frames.fetch_add(1, Ordering::Relaxed);
@@ -438,8 +727,20 @@ impl AudioCapture {
                 stats.active_frames.fetch_add(1, Ordering::Relaxed);
             }
 
+            // Muted: keep the timing, drop the content
+            let write = |producer: &mut AudioProducer| -> Result<usize, ColdVoxError> {
+                if !muted.load(Ordering::Relaxed) {
+                    return producer.write(i16_data);
+                }
+                let mut written = 0;
+                for chunk in i16_data.chunks(SILENCE.len()) {
+                    written += producer.write(&SILENCE[..chunk.len()])?;
+                }
+                Ok(written)
+            };
+
             // Use the shared producer
-            if let Ok(written) = audio_producer.lock().write(i16_data) {
+            if let Ok(written) = write(&mut audio_producer.lock()) {
                 if written == i16_data.len() {
                     stats.frames_captured.fetch_add(1, Ordering::Relaxed);
                 } else {
@@ -473,17 +774,8 @@ impl AudioCapture {
                     CONVERT_BUFFER.with(|buf| {
                         let mut converted = buf.borrow_mut();
                         converted.clear();
-                        // Ensure sufficient capacity (resize only on first call per thread)
-                        let needed = data.len();
-                        let cap = converted.capacity();
-                        if cap < needed {
-                            converted.reserve(needed - cap);
-                        }
-                        for &s in data {
-                            let clamped = s.clamp(-1.0, 1.0);
-                            let v = (clamped * 32767.0).round() as i16;
-                            converted.push(v);
-                        }
+                        // Vectorized; grows the buffer only on first call per thread
+                        crate::convert::f32_to_i16(data, &mut converted);
                         handle_i16(&converted);
                     });
                 },
@@ -650,3 +942,39 @@ mod convert_tests {
         assert!(out[0] <= -32767 && out[3] >= 32766);
     }
 }
+
+#[cfg(test)]
+mod failover_tests {
+    use super::failover_candidates;
+
+    #[test]
+    fn fallbacks_come_first_and_lost_device_is_skipped() {
+        let attempts = failover_candidates(
+            Some("usb-mic"),
+            &["headset".to_string(), "usb-mic".to_string()],
+            Some("usb-mic"),
+            vec!["pipewire".to_string(), "headset".to_string()],
+        );
+        assert_eq!(
+            attempts,
+            vec![
+                Some("headset".to_string()),
+                Some("pipewire".to_string()),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn configured_device_is_retried_when_not_lost() {
+        let attempts = failover_candidates(Some("usb-mic"), &[], None, vec!["default".to_string()]);
+        assert_eq!(
+            attempts,
+            vec![
+                Some("usb-mic".to_string()),
+                Some("default".to_string()),
+                None
+            ]
+        );
+    }
+}
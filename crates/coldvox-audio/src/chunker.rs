@@ -6,6 +6,8 @@ use tokio::task::JoinHandle;
 use tokio::time::{self, Duration};
 
 use super::capture::DeviceConfig;
+use super::convert;
+use super::frame_pool::FramePool;
 use super::frame_reader::FrameReader;
 use super::resampler::StreamResampler;
 use crate::SharedAudioFrame;
@@ -26,10 +28,67 @@ pub enum ResamplerQuality {
     Quality,  // Higher quality, higher CPU usage
 }
 
+/// Which input channel(s) feed the mono pipeline on multi-channel devices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChannelSelection {
+    /// Average all channels (default).
+    #[default]
+    Mix,
+    /// Use a single channel by zero-based index (0 = left, 1 = right).
+    Index(u16),
+}
+
+impl ChannelSelection {
+    pub const LEFT: ChannelSelection = ChannelSelection::Index(0);
+    pub const RIGHT: ChannelSelection = ChannelSelection::Index(1);
+
+    /// This selection on a `channels`-channel device: a channel the device
+    /// does not have falls back to the downmix.
+    pub fn resolve(self, channels: u16) -> ChannelSelection {
+        match self {
+            ChannelSelection::Index(idx) if channels > 1 && idx >= channels => {
+                ChannelSelection::Mix
+            }
+            other => other,
+        }
+    }
+}
+
+impl std::str::FromStr for ChannelSelection {
+    type Err = String;
+
+    /// Parses `"left"`, `"right"`, `"mix"` or a zero-based channel index.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "mix" | "downmix" => Ok(ChannelSelection::Mix),
+            "left" => Ok(ChannelSelection::LEFT),
+            "right" => Ok(ChannelSelection::RIGHT),
+            other => other
+                .parse::<u16>()
+                .map(ChannelSelection::Index)
+                .map_err(|_| format!("invalid channel selection '{}'", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for ChannelSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChannelSelection::Mix => write!(f, "mix"),
+            ChannelSelection::Index(0) => write!(f, "left"),
+            ChannelSelection::Index(1) => write!(f, "right"),
+            ChannelSelection::Index(i) => write!(f, "{}", i),
+        }
+    }
+}
+
 pub struct ChunkerConfig {
     pub frame_size_samples: usize,
     pub sample_rate_hz: u32,
     pub resampler_quality: ResamplerQuality,
+    /// Channel selection/downmix applied before resampling; replaced by the
+    /// capture's choice in each [`DeviceConfig`] received
+    pub channel: ChannelSelection,
 }
 
 impl Default for ChunkerConfig {
@@ -38,6 +97,7 @@ impl Default for ChunkerConfig {
             frame_size_samples: 512,
             sample_rate_hz: 16_000,
             resampler_quality: ResamplerQuality::Balanced,
+            channel: ChannelSelection::Mix,
         }
     }
 }
@@ -49,6 +109,7 @@ pub struct AudioChunker {
     running: Arc<AtomicBool>,
     metrics: Option<Arc<PipelineMetrics>>,
     device_cfg_rx: Option<broadcast::Receiver<DeviceConfig>>,
+    paused: Option<Arc<AtomicBool>>,
 }
 
 impl AudioChunker {
@@ -64,6 +125,7 @@ impl AudioChunker {
             running: Arc::new(AtomicBool::new(false)),
             metrics: None,
             device_cfg_rx: None,
+            paused: None,
         }
     }
 
@@ -77,6 +139,12 @@ impl AudioChunker {
         self
     }
 
+    /// Drop captured audio instead of emitting it while `paused` is set
+    pub fn with_pause(mut self, paused: Arc<AtomicBool>) -> Self {
+        self.paused = Some(paused);
+        self
+    }
+
     pub fn spawn(self) -> JoinHandle<()> {
         let mut worker = ChunkerWorker::new(
             self.frame_reader,
@@ -85,6 +153,7 @@ impl AudioChunker {
             self.metrics,
             self.device_cfg_rx,
         );
+        worker.paused = self.paused;
         self.running.store(true, Ordering::SeqCst);
         let running = self.running.clone();
 
@@ -99,6 +168,12 @@ struct ChunkerWorker {
     output_tx: broadcast::Sender<SharedAudioFrame>,
     cfg: ChunkerConfig,
     buffer: VecDeque<i16>,
+    /// Scratch for channel selection/downmix and resampler output, reused
+    /// across frames
+    mono: Vec<i16>,
+    resampled: Vec<i16>,
+    /// Buffers for emitted frames
+    pool: FramePool,
     samples_emitted: u64,
     metrics: Option<Arc<PipelineMetrics>>,
     capture_fps_tracker: FpsTracker,
@@ -109,6 +184,9 @@ struct ChunkerWorker {
     current_input_channels: Option<u16>,
     device_cfg_rx: Option<broadcast::Receiver<DeviceConfig>>,
     start_time: std::time::Instant,
+    /// Mark the next emitted frame as following a gap
+    discontinuity: bool,
+    paused: Option<Arc<AtomicBool>>,
 }
 
 impl ChunkerWorker {
@@ -125,6 +203,9 @@ impl ChunkerWorker {
             output_tx,
             cfg,
             buffer: VecDeque::with_capacity(cap),
+            mono: Vec::new(),
+            resampled: Vec::new(),
+            pool: FramePool::default(),
             samples_emitted: 0,
             metrics,
             capture_fps_tracker: FpsTracker::new(),
@@ -134,21 +215,47 @@ impl ChunkerWorker {
             current_input_channels: None,
             device_cfg_rx,
             start_time: std::time::Instant::now(),
+            discontinuity: false,
+            paused: None,
         }
     }
 
     async fn run(&mut self, running: Arc<AtomicBool>) {
         tracing::info!("Audio chunker started");
 
+        // Reused for every read so the hot path does not allocate
+        let mut frame = super::capture::AudioFrame {
+            samples: Vec::new(),
+            timestamp: std::time::Instant::now(),
+            sample_rate: 0,
+            channels: 0,
+        };
+
         while running.load(Ordering::SeqCst) {
             // Apply device config updates if any
+            let mut restarted = false;
             if let Some(rx) = &mut self.device_cfg_rx {
                 while let Ok(cfg) = rx.try_recv() {
                     self.frame_reader
                         .update_device_config(cfg.sample_rate, cfg.channels);
+                    self.cfg.channel = cfg.channel;
+                    restarted = true;
                 }
             }
-            if let Some(frame) = self.frame_reader.read_frame(4096) {
+            // The first config just describes the stream we started on
+            if restarted && self.current_input_rate.is_some() {
+                self.handle_stream_restart();
+            }
+            if self
+                .paused
+                .as_ref()
+                .is_some_and(|p| p.load(Ordering::Relaxed))
+            {
+                self.drop_paused_audio();
+                time::sleep(Duration::from_millis(25)).await;
+                continue;
+            }
+            if self.frame_reader.read_frame_into(4096, &mut frame) {
                 if let Some(m) = &self.metrics {
                     m.increment_capture_frames();
                     if let Some(fps) = self.capture_fps_tracker.tick() {
@@ -166,8 +273,7 @@ impl ChunkerWorker {
                 }
 
                 // Process the frame (resampling and channel conversion)
-                let processed_samples = self.process_frame(&frame);
-                self.buffer.extend(processed_samples);
+                self.process_frame(&frame);
                 self.flush_ready_frames().await;
             } else {
                 // Sleep for 25ms when no data available. At 16kHz with 512-sample chunks,
@@ -181,12 +287,58 @@ impl ChunkerWorker {
         tracing::info!("Audio chunker stopped");
     }
 
+    /// Paused: throw away what was captured so resuming starts from live
+    /// audio, and flag the gap on the first frame after it.
+    fn drop_paused_audio(&mut self) {
+        self.frame_reader.discard_pending();
+        self.buffer.clear();
+        if !self.discontinuity {
+            self.discontinuity = true;
+            if let Some(resampler) = &self.resampler {
+                resampler.lock().reset();
+            }
+        }
+    }
+
+    /// The capture stream was rebuilt (failover, resume, or a format change):
+    /// whatever is still queued may be in the old format, and resampler
+    /// history belongs to the old stream. Drop both and flag the gap.
+    fn handle_stream_restart(&mut self) {
+        let stale = self.frame_reader.discard_pending();
+        let partial = self.buffer.len();
+        self.buffer.clear();
+        self.resampler = None;
+        self.current_input_rate = None;
+        self.current_input_channels = None;
+        self.discontinuity = true;
+        tracing::info!(
+            "Capture stream restarted; dropped {} queued and {} partial samples",
+            stale,
+            partial
+        );
+    }
+
+    /// Capture time of the newest emitted sample: everything still waiting
+    /// (here and in the ring buffer) was captured after it.
+    fn last_sample_captured_at(&self) -> std::time::Instant {
+        let mut backlog_secs = self.buffer.len() as f64 / f64::from(self.cfg.sample_rate_hz);
+        if let (Some(rate), Some(channels)) = (self.current_input_rate, self.current_input_channels)
+        {
+            let per_sec = f64::from(rate) * f64::from(channels.max(1));
+            backlog_secs += self.frame_reader.available_samples() as f64 / per_sec;
+        }
+        let now = std::time::Instant::now();
+        now.checked_sub(Duration::from_secs_f64(backlog_secs))
+            .unwrap_or(now)
+    }
+
     async fn flush_ready_frames(&mut self) {
         let fs = self.cfg.frame_size_samples;
         while self.buffer.len() >= fs {
-            let mut out = Vec::with_capacity(fs);
-            for _ in 0..fs {
-                out.push(self.buffer.pop_front().unwrap());
+            let mut samples = self.pool.take(fs);
+            let out = Arc::get_mut(&mut samples).expect("pooled buffers are unshared");
+            for (dst, src) in out.iter_mut().zip(self.buffer.drain(..fs)) {
+                *dst = src;
             }
 
             // Calculate timestamp based on samples emitted
@@ -194,12 +346,12 @@ impl ChunkerWorker {
                 (self.samples_emitted as u128 * 1000 / self.cfg.sample_rate_hz as u128) as u64;
             let timestamp = self.start_time + std::time::Duration::from_millis(timestamp_ms);
 
-            // Convert Vec<i16> to Arc<[i16]> without extra copying
-            let samples_arc: Arc<[i16]> = Arc::from(out);
             let vf = SharedAudioFrame {
-                samples: samples_arc,
+                samples: samples.clone(),
                 sample_rate: self.cfg.sample_rate_hz,
                 timestamp,
+                captured_at: self.last_sample_captured_at(),
+                discontinuity: std::mem::take(&mut self.discontinuity),
             };
 
             // A send on a broadcast channel can fail if there are no receivers.
@@ -213,6 +365,7 @@ impl ChunkerWorker {
                 }
             }
 
+            self.pool.recycle(samples);
             self.samples_emitted += fs as u64;
 
             if let Some(m) = &self.metrics {
@@ -226,6 +379,23 @@ impl ChunkerWorker {
     }
 
     fn reconfigure_for_device(&mut self, frame: &super::capture::AudioFrame) {
+        if let ChannelSelection::Index(idx) = self.cfg.channel {
+            if frame.channels > 1 && idx >= frame.channels {
+                tracing::warn!(
+                    "Configured audio channel {} not available on {}-channel device; downmixing instead",
+                    idx,
+                    frame.channels
+                );
+            } else if frame.channels > 1 {
+                tracing::info!(
+                    "Using input channel {} of {} ({})",
+                    idx,
+                    frame.channels,
+                    self.cfg.channel
+                );
+            }
+        }
+
         let needs_resampling = frame.sample_rate != self.cfg.sample_rate_hz;
 
         if needs_resampling {
@@ -255,28 +425,32 @@ impl ChunkerWorker {
         self.current_input_channels = Some(frame.channels);
     }
 
-    fn process_frame(&mut self, frame: &super::capture::AudioFrame) -> Vec<i16> {
-        // First, handle channel conversion if needed
-        let mono_samples = if frame.channels == 1 {
-            frame.samples.clone()
+    /// Select or mix down to mono, resample, and queue the result
+    fn process_frame(&mut self, frame: &super::capture::AudioFrame) {
+        let channels = frame.channels as usize;
+        let mono: &[i16] = if channels <= 1 {
+            &frame.samples
         } else {
-            // Convert multi-channel to mono by averaging
-            let channels = frame.channels as usize;
-            frame
-                .samples
-                .chunks_exact(channels)
-                .map(|chunk| {
-                    let sum: i32 = chunk.iter().map(|&s| s as i32).sum();
-                    (sum / channels as i32) as i16
-                })
-                .collect()
+            self.mono.clear();
+            match self.cfg.channel {
+                // Pick a single channel out of each interleaved sample group
+                ChannelSelection::Index(idx) if (idx as usize) < channels => {
+                    convert::pick_channel(&frame.samples, channels, idx as usize, &mut self.mono)
+                }
+                // Convert multi-channel to mono by averaging (also the fallback
+                // when the selected channel does not exist on this device)
+                _ => convert::downmix(&frame.samples, channels, &mut self.mono),
+            }
+            &self.mono
         };
 
         // Then, apply resampling if needed
         if let Some(resampler) = &self.resampler {
-            resampler.lock().process(&mono_samples)
+            self.resampled.clear();
+            resampler.lock().process_into(mono, &mut self.resampled);
+            self.buffer.extend(&self.resampled);
         } else {
-            mono_samples
+            self.buffer.extend(mono);
         }
     }
 }
@@ -298,6 +472,7 @@ mod tests {
             frame_size_samples: 512,
             sample_rate_hz: 16_000,
             resampler_quality: ResamplerQuality::Balanced,
+            ..Default::default()
         };
         let mut worker = ChunkerWorker::new(reader, tx, cfg, None, None);
 
@@ -332,6 +507,7 @@ mod tests {
             frame_size_samples: 512,
             sample_rate_hz: 16_000,
             resampler_quality: ResamplerQuality::Balanced,
+            ..Default::default()
         };
         let mut worker = ChunkerWorker::new(reader, tx, cfg, None, None);
 
@@ -343,8 +519,116 @@ mod tests {
             channels: 2,
         };
         worker.reconfigure_for_device(&frame);
-        let out = worker.process_frame(&frame);
+        worker.process_frame(&frame);
         // Each pair averaged -> zeros
-        assert_eq!(out, vec![0, 0, 0, 0]);
+        assert_eq!(worker.buffer, vec![0, 0, 0, 0]);
+    }
+
+    fn worker_with_channel(channel: ChannelSelection) -> ChunkerWorker {
+        let rb = AudioRingBuffer::new(1024);
+        let (_prod, cons) = rb.split();
+        let reader = FrameReader::new(cons, 16_000, 4, 1024, None);
+        let (tx, _rx) = broadcast::channel::<SharedAudioFrame>(8);
+        let cfg = ChunkerConfig {
+            channel,
+            ..Default::default()
+        };
+        ChunkerWorker::new(reader, tx, cfg, None, None)
+    }
+
+    fn quad_frame() -> CapFrame {
+        CapFrame {
+            samples: vec![1, 2, 3, 4, 10, 20, 30, 40],
+            timestamp: Instant::now(),
+            sample_rate: 16_000,
+            channels: 4,
+        }
+    }
+
+    #[test]
+    fn channel_selection_picks_single_channel() {
+        let frame = quad_frame();
+
+        let mut right = worker_with_channel(ChannelSelection::RIGHT);
+        right.reconfigure_for_device(&frame);
+        right.process_frame(&frame);
+        assert_eq!(right.buffer, vec![2, 20]);
+
+        let mut third = worker_with_channel(ChannelSelection::Index(3));
+        third.reconfigure_for_device(&frame);
+        third.process_frame(&frame);
+        assert_eq!(third.buffer, vec![4, 40]);
+    }
+
+    #[test]
+    fn out_of_range_channel_falls_back_to_mix() {
+        let frame = quad_frame();
+        let mut worker = worker_with_channel(ChannelSelection::Index(7));
+        worker.reconfigure_for_device(&frame);
+        worker.process_frame(&frame);
+        assert_eq!(worker.buffer, vec![2, 25]);
+
+        // Capture resolves the selection the same way before publishing it
+        assert_eq!(ChannelSelection::Index(7).resolve(4), ChannelSelection::Mix);
+        assert_eq!(ChannelSelection::RIGHT.resolve(2), ChannelSelection::RIGHT);
+        assert_eq!(ChannelSelection::RIGHT.resolve(1), ChannelSelection::RIGHT);
+    }
+
+    #[tokio::test]
+    async fn stream_restart_drops_stale_audio_and_flags_gap() {
+        let rb = AudioRingBuffer::new(1024);
+        let (mut prod, cons) = rb.split();
+        let reader = FrameReader::new(cons, 16_000, 1, 1024, None);
+        let (tx, mut rx) = broadcast::channel::<SharedAudioFrame>(8);
+        let mut worker = ChunkerWorker::new(reader, tx, ChunkerConfig::default(), None, None);
+
+        let frame = CapFrame {
+            samples: vec![0i16; 160],
+            timestamp: Instant::now(),
+            sample_rate: 16_000,
+            channels: 1,
+        };
+        worker.reconfigure_for_device(&frame);
+        worker.buffer.extend([1i16; 100]);
+        prod.write(&[7i16; 300]).unwrap();
+
+        worker.handle_stream_restart();
+        assert_eq!(worker.frame_reader.available_samples(), 0);
+        assert!(worker.buffer.is_empty());
+        assert_eq!(worker.current_input_rate, None);
+
+        worker.buffer.extend([2i16; 1024]);
+        worker.flush_ready_frames().await;
+        assert!(rx.recv().await.unwrap().discontinuity);
+        assert!(!rx.recv().await.unwrap().discontinuity);
+    }
+
+    #[tokio::test]
+    async fn paused_audio_is_dropped_and_resume_flags_gap() {
+        let rb = AudioRingBuffer::new(1024);
+        let (mut prod, cons) = rb.split();
+        let reader = FrameReader::new(cons, 16_000, 1, 1024, None);
+        let (tx, mut rx) = broadcast::channel::<SharedAudioFrame>(8);
+        let mut worker = ChunkerWorker::new(reader, tx, ChunkerConfig::default(), None, None);
+
+        worker.buffer.extend([1i16; 100]);
+        prod.write(&[7i16; 300]).unwrap();
+        worker.drop_paused_audio();
+        assert_eq!(worker.frame_reader.available_samples(), 0);
+        assert!(worker.buffer.is_empty());
+
+        worker.buffer.extend([2i16; 1024]);
+        worker.flush_ready_frames().await;
+        assert!(rx.recv().await.unwrap().discontinuity);
+        assert!(!rx.recv().await.unwrap().discontinuity);
+    }
+
+    #[test]
+    fn channel_selection_parses_config_values() {
+        assert_eq!("mix".parse(), Ok(ChannelSelection::Mix));
+        assert_eq!("Left".parse(), Ok(ChannelSelection::LEFT));
+        assert_eq!("right".parse(), Ok(ChannelSelection::RIGHT));
+        assert_eq!("5".parse(), Ok(ChannelSelection::Index(5)));
+        assert!("center".parse::<ChannelSelection>().is_err());
     }
 }
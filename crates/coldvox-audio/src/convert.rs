@@ -0,0 +1,123 @@
+//! Sample conversion and channel mixing for the capture hot path.
+//!
+//! Each loop works on blocks of [`LANES`] samples with a branch-free body, so
+//! on stable Rust LLVM emits SIMD for them (SSE2/AVX2 on x86-64, NEON on
+//! aarch64, which covers Raspberry Pi–class boards); a scalar tail handles
+//! the remainder. All functions append to `out` and only grow it, so callers
+//! can reuse one buffer across frames.
+
+/// Samples per block
+pub const LANES: usize = 8;
+
+const I16_TO_F32: f32 = 1.0 / 32768.0;
+
+/// Apply `f` to every sample of `input`, appending the results to `out`
+#[inline(always)]
+fn map_into<I: Copy, O: Copy + Default>(input: &[I], out: &mut Vec<O>, f: impl Fn(I) -> O) {
+    let start = out.len();
+    out.resize(start + input.len(), O::default());
+    let mut dst = out[start..].chunks_exact_mut(LANES);
+    let mut src = input.chunks_exact(LANES);
+    for (d, s) in (&mut dst).zip(&mut src) {
+        for (d, &s) in d.iter_mut().zip(s) {
+            *d = f(s);
+        }
+    }
+    for (d, &s) in dst.into_remainder().iter_mut().zip(src.remainder()) {
+        *d = f(s);
+    }
+}
+
+/// i16 samples scaled to [-1, 1)
+pub fn i16_to_f32(input: &[i16], out: &mut Vec<f32>) {
+    map_into(input, out, |s| f32::from(s) * I16_TO_F32);
+}
+
+/// f32 samples clamped to [-1, 1] and scaled to i16, rounding half away
+/// from zero
+pub fn f32_to_i16(input: &[f32], out: &mut Vec<i16>) {
+    // `round()` is a libm call on baseline x86-64 and would keep the loop
+    // scalar; adding ±0.5 and truncating is the same for this range
+    map_into(input, out, |s| {
+        let v = s.clamp(-1.0, 1.0) * 32767.0;
+        (v + 0.5f32.copysign(v)) as i16
+    });
+}
+
+/// Mono average of each interleaved frame of `channels` samples
+pub fn downmix(input: &[i16], channels: usize, out: &mut Vec<i16>) {
+    match channels {
+        0 | 1 => out.extend_from_slice(input),
+        2 => {
+            let start = out.len();
+            out.resize(start + input.len() / 2, 0);
+            for (d, pair) in out[start..].iter_mut().zip(input.chunks_exact(2)) {
+                *d = ((i32::from(pair[0]) + i32::from(pair[1])) / 2) as i16;
+            }
+        }
+        _ => {
+            let start = out.len();
+            out.resize(start + input.len() / channels, 0);
+            for (d, frame) in out[start..].iter_mut().zip(input.chunks_exact(channels)) {
+                let sum: i32 = frame.iter().map(|&s| i32::from(s)).sum();
+                *d = (sum / channels as i32) as i16;
+            }
+        }
+    }
+}
+
+/// Channel `index` of each interleaved frame of `channels` samples
+pub fn pick_channel(input: &[i16], channels: usize, index: usize, out: &mut Vec<i16>) {
+    let start = out.len();
+    out.resize(start + input.len() / channels, 0);
+    for (d, frame) in out[start..].iter_mut().zip(input.chunks_exact(channels)) {
+        *d = frame[index];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_like_the_scalar_formulas() {
+        // Longer than one block, with a tail
+        let input: Vec<i16> = (-10..11).map(|i| i * 3000).collect();
+        let mut floats = Vec::new();
+        i16_to_f32(&input, &mut floats);
+        for (&s, &f) in input.iter().zip(&floats) {
+            assert_eq!(f, s as f32 / 32768.0);
+        }
+
+        let samples = [
+            -2.0, -1.0, -0.5, -0.00002, 0.0, 0.00002, 0.25, 0.5, 1.0, 3.0,
+        ];
+        let mut ints = vec![7];
+        f32_to_i16(&samples, &mut ints);
+        let expected: Vec<i16> = std::iter::once(7)
+            .chain(
+                samples
+                    .iter()
+                    .map(|s| (s.clamp(-1.0, 1.0) * 32767.0).round() as i16),
+            )
+            .collect();
+        assert_eq!(ints, expected);
+    }
+
+    #[test]
+    fn mixes_and_picks_channels() {
+        let stereo: Vec<i16> = (0..20).collect();
+        let mut mono = Vec::new();
+        downmix(&stereo, 2, &mut mono);
+        assert_eq!(mono, (0..10).map(|i| (4 * i + 1) / 2).collect::<Vec<i16>>());
+
+        let quad = [1, 2, 3, 4, 10, 20, 30, 40];
+        let mut mono = Vec::new();
+        downmix(&quad, 4, &mut mono);
+        assert_eq!(mono, [2, 25]);
+
+        let mut right = Vec::new();
+        pick_channel(&quad, 4, 1, &mut right);
+        assert_eq!(right, [2, 20]);
+    }
+}
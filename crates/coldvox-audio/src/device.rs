@@ -12,7 +12,7 @@ const DEVICE_CACHE_TTL: Duration = Duration::from_secs(5);
 
 /// Helper to suppress stderr on Unix, no-op on other platforms
 #[cfg(unix)]
-fn with_stderr_suppressed<F, R>(f: F) -> R
+pub(crate) fn with_stderr_suppressed<F, R>(f: F) -> R
 where
     F: FnOnce() -> R,
 {
@@ -20,7 +20,7 @@ where
 }
 
 #[cfg(not(unix))]
-fn with_stderr_suppressed<F, R>(f: F) -> R
+pub(crate) fn with_stderr_suppressed<F, R>(f: F) -> R
 where
     F: FnOnce() -> R,
 {
@@ -0,0 +1,174 @@
+//! Mid-stream format change detection.
+//!
+//! After suspend/resume some devices come back at a different rate or channel
+//! count while cpal keeps delivering buffers under the config negotiated when
+//! the stream was built. Nothing reports the change, and the chunker would
+//! resample the new audio with the old ratio. The capture callback therefore
+//! watches how fast samples actually arrive: when the rate stays well away
+//! from `sample_rate * channels` for consecutive windows, or a buffer no
+//! longer splits into whole channel frames, the stream is rebuilt and the
+//! format negotiated again.
+
+use std::time::{Duration, Instant};
+
+/// Span over which the delivered sample rate is measured
+const WINDOW: Duration = Duration::from_secs(2);
+/// Relative deviation from the negotiated rate tolerated per window
+const TOLERANCE: f64 = 0.2;
+/// Out-of-tolerance windows in a row before a change is reported
+/// (startup bursts and scheduler hiccups only skew a single window)
+const CONFIRM_WINDOWS: u32 = 2;
+/// A gap this long between callbacks is a stall, not a format change
+const STALL: Duration = Duration::from_millis(500);
+
+/// Evidence that the device format no longer matches the stream config
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormatDrift {
+    /// Interleaved samples per second drifted from the negotiated rate
+    Rate { expected: u32, observed: u32 },
+    /// A buffer did not hold a whole number of frames for the channel count
+    Misaligned { samples: usize, channels: u16 },
+}
+
+impl std::fmt::Display for FormatDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatDrift::Rate { expected, observed } => write!(
+                f,
+                "delivering {} samples/s, expected {}",
+                observed, expected
+            ),
+            FormatDrift::Misaligned { samples, channels } => write!(
+                f,
+                "buffer of {} samples is not a multiple of {} channels",
+                samples, channels
+            ),
+        }
+    }
+}
+
+/// Tracks the delivered sample rate of one stream
+#[derive(Debug)]
+pub struct FormatWatch {
+    expected_per_sec: f64,
+    channels: u16,
+    window_start: Option<Instant>,
+    last_callback: Option<Instant>,
+    window_samples: u64,
+    bad_windows: u32,
+}
+
+impl FormatWatch {
+    /// Watch a stream negotiated at `sample_rate` Hz with `channels` channels
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        Self {
+            expected_per_sec: f64::from(sample_rate) * f64::from(channels.max(1)),
+            channels: channels.max(1),
+            window_start: None,
+            last_callback: None,
+            window_samples: 0,
+            bad_windows: 0,
+        }
+    }
+
+    /// Account for one callback buffer of `samples` interleaved samples
+    pub fn observe(&mut self, samples: usize, now: Instant) -> Option<FormatDrift> {
+        if !samples.is_multiple_of(usize::from(self.channels)) {
+            return Some(FormatDrift::Misaligned {
+                samples,
+                channels: self.channels,
+            });
+        }
+
+        let stalled = self
+            .last_callback
+            .is_some_and(|last| now.saturating_duration_since(last) >= STALL);
+        self.last_callback = Some(now);
+        let start = match self.window_start {
+            Some(start) if !stalled => start,
+            _ => {
+                // Measure from the first buffer after start or a stall
+                self.window_start = Some(now);
+                self.window_samples = 0;
+                return None;
+            }
+        };
+
+        self.window_samples += samples as u64;
+        let elapsed = now.saturating_duration_since(start);
+        if elapsed < WINDOW {
+            return None;
+        }
+
+        let observed = self.window_samples as f64 / elapsed.as_secs_f64();
+        self.window_start = Some(now);
+        self.window_samples = 0;
+        if (observed - self.expected_per_sec).abs() <= self.expected_per_sec * TOLERANCE {
+            self.bad_windows = 0;
+            return None;
+        }
+        self.bad_windows += 1;
+        (self.bad_windows >= CONFIRM_WINDOWS).then(|| FormatDrift::Rate {
+            expected: self.expected_per_sec.round() as u32,
+            observed: observed.round() as u32,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed `secs` seconds of 10ms buffers at `per_sec` samples/s
+    fn feed(
+        watch: &mut FormatWatch,
+        start: Instant,
+        from_ms: u64,
+        secs: u64,
+        per_sec: u64,
+    ) -> Option<FormatDrift> {
+        let mut drift = None;
+        for i in 0..secs * 100 {
+            let now = start + Duration::from_millis(from_ms + i * 10);
+            drift = drift.or(watch.observe((per_sec / 100) as usize, now));
+        }
+        drift
+    }
+
+    #[test]
+    fn steady_stream_and_stalls_are_not_drift() {
+        let start = Instant::now();
+        let mut watch = FormatWatch::new(48_000, 2);
+        assert_eq!(feed(&mut watch, start, 0, 10, 96_000), None);
+        // A suspend-length gap restarts the measurement instead of reading
+        // as a slow rate
+        assert_eq!(feed(&mut watch, start, 15_000, 10, 96_000), None);
+    }
+
+    #[test]
+    fn reports_rate_change_after_consecutive_windows() {
+        let start = Instant::now();
+        let mut watch = FormatWatch::new(48_000, 2);
+        assert_eq!(feed(&mut watch, start, 0, 4, 96_000), None);
+        // Device came back in mono; buffers still split into stereo frames
+        assert_eq!(
+            feed(&mut watch, start, 4_000, 6, 48_000),
+            Some(FormatDrift::Rate {
+                expected: 96_000,
+                observed: 48_000
+            })
+        );
+    }
+
+    #[test]
+    fn reports_misaligned_buffers() {
+        let mut watch = FormatWatch::new(48_000, 2);
+        assert_eq!(
+            watch.observe(481, Instant::now()),
+            Some(FormatDrift::Misaligned {
+                samples: 481,
+                channels: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// Recycles the `Arc<[i16]>` buffers behind emitted audio frames.
+///
+/// An emitted frame is shared with every receiver and stays in the broadcast
+/// channel until it is overwritten, so its buffer can only be reused once all
+/// those copies are gone. The pool keeps the last `capacity` buffers in emit
+/// order and hands out the oldest when nothing else holds it; otherwise it
+/// allocates. Keep `capacity` above the channel capacity for reuse to happen.
+pub struct FramePool {
+    frames: VecDeque<Arc<[i16]>>,
+    capacity: usize,
+    allocated: u64,
+    reused: u64,
+}
+
+impl FramePool {
+    /// Enough for the runtime's 200-frame audio channel plus frames in flight
+    pub const DEFAULT_CAPACITY: usize = 256;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            frames: VecDeque::with_capacity(capacity),
+            capacity,
+            allocated: 0,
+            reused: 0,
+        }
+    }
+
+    /// A buffer of `len` samples that nothing else references, with
+    /// unspecified contents
+    pub fn take(&mut self, len: usize) -> Arc<[i16]> {
+        if let Some(oldest) = self.frames.front_mut() {
+            if oldest.len() == len && Arc::get_mut(oldest).is_some() {
+                self.reused += 1;
+                return self.frames.pop_front().unwrap();
+            }
+        }
+        self.allocated += 1;
+        vec![0i16; len].into()
+    }
+
+    /// Keep an emitted frame's buffer for reuse once its receivers are done
+    pub fn recycle(&mut self, frame: Arc<[i16]>) {
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// Buffers allocated so far
+    pub fn allocated(&self) -> u64 {
+        self.allocated
+    }
+
+    /// Buffers handed out again instead of allocated
+    pub fn reused(&self) -> u64 {
+        self.reused
+    }
+}
+
+impl Default for FramePool {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_buffers_once_released() {
+        let mut pool = FramePool::new(2);
+        let first = pool.take(4);
+        pool.recycle(first.clone());
+        // Still held by a receiver
+        let second = pool.take(4);
+        assert_eq!(pool.allocated(), 2);
+        pool.recycle(second);
+
+        drop(first);
+        let mut again = pool.take(4);
+        assert_eq!(pool.reused(), 1);
+        assert!(Arc::get_mut(&mut again).is_some());
+
+        // Wrong size is never handed out
+        assert_eq!(pool.take(8).len(), 8);
+        assert_eq!(pool.allocated(), 3);
+    }
+}
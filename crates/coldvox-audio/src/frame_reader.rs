@@ -1,3 +1,4 @@
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -39,24 +40,43 @@ impl FrameReader {
 
     /// Read next audio frame, reconstructing timestamp from sample count
     pub fn read_frame(&mut self, max_samples: usize) -> Option<AudioFrame> {
+        let mut frame = AudioFrame {
+            samples: Vec::new(),
+            timestamp: self.start_time,
+            sample_rate: self.device_sample_rate,
+            channels: self.device_channels,
+        };
+        self.read_frame_into(max_samples, &mut frame)
+            .then_some(frame)
+    }
+
+    /// Like [`read_frame`](Self::read_frame), but reuses `frame` and its
+    /// sample buffer. Returns false, leaving `frame` untouched, when no
+    /// samples are available.
+    pub fn read_frame_into(&mut self, max_samples: usize, frame: &mut AudioFrame) -> bool {
         if let Some(metrics) = &self.metrics {
             let available = self.consumer.slots();
-            let fill_percent = if self.capacity > 0 {
-                (available * 100) / self.capacity
-            } else {
-                0
-            };
+            // A grow-once buffer can outgrow the capacity it started with
+            let stats = self.consumer.stats();
+            let capacity = self.capacity.max(stats.capacity());
+            let fill_percent = (available * 100).checked_div(capacity).unwrap_or(0);
             metrics.update_buffer_fill(BufferType::Capture, fill_percent);
+            metrics
+                .capture_overflows
+                .store(stats.overflows(), Ordering::Relaxed);
+            metrics
+                .capture_dropped_samples
+                .store(stats.dropped_samples(), Ordering::Relaxed);
         }
 
-        let mut buffer = vec![0i16; max_samples];
-        let samples_read = self.consumer.read(&mut buffer);
-
-        if samples_read == 0 {
+        if self.consumer.slots() == 0 {
             tracing::trace!("FrameReader: No samples available to read");
-            return None;
+            return false;
         }
 
+        let buffer = &mut frame.samples;
+        buffer.resize(max_samples, 0);
+        let samples_read = self.consumer.read(buffer);
         buffer.truncate(samples_read);
 
         // Calculate timestamp based on samples read
@@ -74,12 +94,10 @@ impl FrameReader {
             timestamp
         );
 
-        Some(AudioFrame {
-            samples: buffer,
-            timestamp,
-            sample_rate: self.device_sample_rate,
-            channels: self.device_channels,
-        })
+        frame.timestamp = timestamp;
+        frame.sample_rate = self.device_sample_rate;
+        frame.channels = self.device_channels;
+        true
     }
 
     /// Check how many samples are available to read
@@ -87,6 +105,20 @@ impl FrameReader {
         self.consumer.slots()
     }
 
+    /// Drop everything waiting in the ring buffer (audio from a stream that
+    /// has since been replaced). Returns the number of samples dropped.
+    pub fn discard_pending(&mut self) -> usize {
+        let mut scratch = [0i16; 4096];
+        let mut dropped = 0;
+        loop {
+            let n = self.consumer.read(&mut scratch);
+            if n == 0 {
+                return dropped;
+            }
+            dropped += n;
+        }
+    }
+
     /// Update device configuration when it changes
     pub fn update_device_config(&mut self, sample_rate: u32, channels: u16) {
         if self.device_sample_rate != sample_rate || self.device_channels != channels {
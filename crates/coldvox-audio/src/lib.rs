@@ -1,9 +1,13 @@
 pub mod capture;
 pub mod chunker;
+pub mod convert;
 pub mod detector;
 pub mod device;
+pub mod format_watch;
+pub mod frame_pool;
 pub mod frame_reader;
 pub mod monitor;
+pub mod playback;
 pub mod resampler;
 pub mod ring_buffer;
 #[cfg(unix)]
@@ -12,10 +16,13 @@ pub mod watchdog;
 
 // Public API
 pub use capture::{AudioCaptureThread, DeviceConfig};
-pub use chunker::{AudioChunker, AudioFrame, ChunkerConfig, ResamplerQuality};
+pub use chunker::{AudioChunker, AudioFrame, ChannelSelection, ChunkerConfig, ResamplerQuality};
 pub use device::{DeviceInfo, DeviceManager};
+pub use format_watch::{FormatDrift, FormatWatch};
+pub use frame_pool::FramePool;
 pub use frame_reader::FrameReader;
 pub use monitor::DeviceMonitor;
+pub use playback::{AudioPlayer, Clip, Playback};
 pub use resampler::StreamResampler;
 pub use ring_buffer::AudioRingBuffer;
 pub use watchdog::WatchdogTimer;
@@ -26,11 +33,16 @@ use std::time::Instant;
 /// Zero-copy shared audio frame used for broadcasting to multiple consumers.
 ///
 /// - samples: i16 PCM at the configured sample rate (typically 16kHz mono)
-/// - timestamp: monotonic Instant approximating capture time
+/// - timestamp: monotonic Instant derived from the sample count (evenly spaced)
+/// - captured_at: when the frame's last sample was captured, used for latency
 /// - sample_rate: sample rate in Hz for the samples buffer
+/// - discontinuity: audio before this frame was dropped (stream restarted or
+///   the device format changed); consumers should reset stream state
 #[derive(Debug, Clone)]
 pub struct SharedAudioFrame {
     pub samples: Arc<[i16]>,
     pub timestamp: Instant,
+    pub captured_at: Instant,
     pub sample_rate: u32,
+    pub discontinuity: bool,
 }
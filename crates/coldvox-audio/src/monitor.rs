@@ -1,3 +1,4 @@
+use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -19,6 +20,8 @@ pub struct DeviceMonitor {
     preferred_devices: Vec<String>,
     // Track how many consecutive scans a device has been missing
     missing_count: HashMap<String, u32>,
+    // Device the capture thread is currently using, updated from outside the monitor thread
+    active_device: Option<Arc<RwLock<Option<String>>>>,
 }
 
 impl DeviceMonitor {
@@ -36,6 +39,7 @@ impl DeviceMonitor {
             current_device: None,
             preferred_devices: Vec::new(),
             missing_count: HashMap::new(),
+            active_device: None,
         };
 
         Ok((monitor, event_rx))
@@ -50,6 +54,14 @@ impl DeviceMonitor {
         );
     }
 
+    /// Track the active device through a handle shared with the capture thread.
+    ///
+    /// Each scan syncs `current_device` from the handle, so removal of the
+    /// device capture is actually running on emits `CurrentDeviceDisconnected`.
+    pub fn set_active_device_handle(&mut self, handle: Arc<RwLock<Option<String>>>) {
+        self.active_device = Some(handle);
+    }
+
     /// Start monitoring in a background thread
     pub fn start_monitoring(mut self, running: Arc<AtomicBool>) -> JoinHandle<()> {
         thread::Builder::new()
@@ -95,6 +107,13 @@ impl DeviceMonitor {
     }
 
     fn scan_and_update_devices(&mut self) -> Result<(), AudioError> {
+        if let Some(handle) = &self.active_device {
+            let active = handle.read().clone();
+            if active != self.current_device {
+                self.set_current_device(active);
+            }
+        }
+
         let current_devices = self.device_manager.enumerate_devices();
         let now = Instant::now();
         let mut new_device_map = HashMap::new();
@@ -126,6 +145,7 @@ impl DeviceMonitor {
         // Check for removed devices (with debouncing to prevent false positives)
         const REMOVAL_THRESHOLD: u32 = 3; // Device must be missing for 3 consecutive scans
 
+        let mut carried_over = Vec::new();
         for (old_name, old_status) in &self.last_devices {
             if !new_device_map.contains_key(old_name) {
                 // Increment missing count
@@ -148,7 +168,7 @@ impl DeviceMonitor {
                     });
 
                     // If current device was removed, signal disconnection
-                    if old_status.is_current {
+                    if self.current_device.as_deref() == Some(old_name.as_str()) {
                         warn!("Current device disconnected: {}", old_name);
                         let _ = self.event_tx.send(DeviceEvent::CurrentDeviceDisconnected {
                             name: old_name.clone(),
@@ -157,6 +177,12 @@ impl DeviceMonitor {
 
                     // Clear from missing count after emitting removal
                     self.missing_count.remove(old_name);
+                } else {
+                    // Keep tracking the device until the threshold is reached;
+                    // otherwise it would be forgotten after a single missed scan.
+                    let mut status = old_status.clone();
+                    status.is_available = false;
+                    carried_over.push(status);
                 }
             } else {
                 // Device reappeared, reset missing count
@@ -164,6 +190,10 @@ impl DeviceMonitor {
             }
         }
 
+        for status in carried_over {
+            new_device_map.insert(status.name.clone(), status);
+        }
+
         self.last_devices = new_device_map;
         Ok(())
     }
@@ -0,0 +1,408 @@
+//! Audio output for spoken feedback.
+//!
+//! Clips (typically WAV bytes from a speech synthesizer) are queued and
+//! played one after another on an output device. `stop()` cuts the current
+//! clip short and drops everything queued, so new feedback can interrupt
+//! stale feedback. Each queued clip hands back a [`Playback`] that resolves
+//! once the clip has finished or was interrupted.
+//!
+//! Clips are converted to mono at the device rate when queued; the output
+//! callback only copies samples.
+
+use std::collections::VecDeque;
+use std::sync::mpsc as std_mpsc;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use coldvox_foundation::AudioError;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{SampleFormat, Stream, StreamConfig};
+use parking_lot::Mutex;
+use tokio::sync::oneshot;
+
+use crate::device::with_stderr_suppressed;
+
+/// Decoded PCM audio
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clip {
+    /// Interleaved i16 samples
+    pub samples: Vec<i16>,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+impl Clip {
+    /// Decode a 16-bit PCM WAV file
+    pub fn from_wav(bytes: &[u8]) -> Result<Self, AudioError> {
+        let bad = |what: &str| AudioError::FormatNotSupported {
+            format: format!("WAV: {}", what),
+        };
+        if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+            return Err(bad("missing RIFF/WAVE header"));
+        }
+
+        let mut format = None;
+        let mut pos = 12;
+        while pos + 8 <= bytes.len() {
+            let id = &bytes[pos..pos + 4];
+            let declared = u32::from_le_bytes(bytes[pos + 4..pos + 8].try_into().unwrap());
+            let body = &bytes[pos + 8..];
+            // Streaming writers (espeak --stdout among them) leave the data
+            // size unset; take whatever follows
+            let len = (declared as usize).min(body.len());
+            let body = &body[..len];
+            match id {
+                b"fmt " => {
+                    if body.len() < 16 {
+                        return Err(bad("short fmt chunk"));
+                    }
+                    let tag = u16::from_le_bytes([body[0], body[1]]);
+                    let channels = u16::from_le_bytes([body[2], body[3]]);
+                    let sample_rate = u32::from_le_bytes(body[4..8].try_into().unwrap());
+                    let bits = u16::from_le_bytes([body[14], body[15]]);
+                    // 1 = PCM, 0xfffe = extensible (PCM subformat assumed)
+                    if !matches!(tag, 1 | 0xfffe) || bits != 16 {
+                        return Err(bad(&format!("format {} at {} bits", tag, bits)));
+                    }
+                    if channels == 0 || sample_rate == 0 {
+                        return Err(bad("zero channels or sample rate"));
+                    }
+                    format = Some((sample_rate, channels));
+                }
+                b"data" => {
+                    let (sample_rate, channels) = format.ok_or_else(|| bad("data before fmt"))?;
+                    let samples = body
+                        .chunks_exact(2)
+                        .map(|b| i16::from_le_bytes([b[0], b[1]]))
+                        .collect();
+                    return Ok(Self {
+                        samples,
+                        sample_rate,
+                        channels,
+                    });
+                }
+                _ => {}
+            }
+            // Chunks are padded to an even length
+            pos += 8 + len + (len & 1);
+        }
+        Err(bad("no data chunk"))
+    }
+
+    /// Mono f32 samples resampled to `rate` (linear interpolation; speech
+    /// feedback doesn't need better)
+    fn to_mono_at(&self, rate: u32) -> Vec<f32> {
+        let channels = usize::from(self.channels.max(1));
+        let mono: Vec<f32> = self
+            .samples
+            .chunks_exact(channels)
+            .map(|frame| {
+                frame.iter().map(|&s| f32::from(s)).sum::<f32>() / (channels as f32 * 32768.0)
+            })
+            .collect();
+        if self.sample_rate == rate || mono.len() < 2 {
+            return mono;
+        }
+        let step = f64::from(self.sample_rate) / f64::from(rate);
+        let out_len = ((mono.len() as f64) / step).floor() as usize;
+        (0..out_len)
+            .map(|i| {
+                let src = i as f64 * step;
+                let idx = src as usize;
+                let frac = (src - idx as f64) as f32;
+                let a = mono[idx];
+                let b = mono.get(idx + 1).copied().unwrap_or(a);
+                a + (b - a) * frac
+            })
+            .collect()
+    }
+}
+
+/// Handle to a queued clip
+#[derive(Debug)]
+pub struct Playback {
+    done: oneshot::Receiver<bool>,
+}
+
+impl Playback {
+    /// Wait until the clip is over. True if it played to the end, false if
+    /// it was interrupted or the player went away.
+    pub async fn finished(self) -> bool {
+        self.done.await.unwrap_or(false)
+    }
+}
+
+struct Queued {
+    samples: Vec<f32>,
+    pos: usize,
+    done: Option<oneshot::Sender<bool>>,
+}
+
+impl Queued {
+    fn finish(&mut self, completed: bool) {
+        if let Some(done) = self.done.take() {
+            let _ = done.send(completed);
+        }
+    }
+}
+
+/// Clips waiting for the output callback
+#[derive(Default)]
+struct Queue {
+    clips: VecDeque<Queued>,
+}
+
+impl Queue {
+    /// Fill an interleaved buffer of `channels` channels, silence once empty
+    fn fill(&mut self, out: &mut [f32], channels: usize) {
+        for frame in out.chunks_mut(channels.max(1)) {
+            let sample = loop {
+                let Some(clip) = self.clips.front_mut() else {
+                    break 0.0;
+                };
+                if let Some(&s) = clip.samples.get(clip.pos) {
+                    clip.pos += 1;
+                    break s;
+                }
+                clip.finish(true);
+                self.clips.pop_front();
+            };
+            frame.fill(sample);
+        }
+    }
+
+    fn clear(&mut self) {
+        for mut clip in self.clips.drain(..) {
+            clip.finish(false);
+        }
+    }
+}
+
+/// Plays queued clips on an output device
+pub struct AudioPlayer {
+    queue: Arc<Mutex<Queue>>,
+    sample_rate: u32,
+    shutdown_tx: Option<std_mpsc::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl AudioPlayer {
+    /// Open the named output device (exact name, else the system default)
+    /// and start its stream. The stream lives on its own thread because cpal
+    /// streams can't move between threads.
+    pub fn open(device_name: Option<&str>) -> Result<Self, AudioError> {
+        let queue = Arc::new(Mutex::new(Queue::default()));
+        let (ready_tx, ready_rx) = std_mpsc::channel();
+        let (shutdown_tx, shutdown_rx) = std_mpsc::channel::<()>();
+        let device_name = device_name.map(str::to_string);
+        let stream_queue = Arc::clone(&queue);
+
+        let handle = thread::Builder::new()
+            .name("audio-playback".to_string())
+            .spawn(move || {
+                let stream = match build_output(device_name.as_deref(), stream_queue) {
+                    Ok((stream, rate)) => {
+                        let _ = ready_tx.send(Ok(rate));
+                        stream
+                    }
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(e));
+                        return;
+                    }
+                };
+                // Keep the stream alive until the player is dropped
+                let _ = shutdown_rx.recv();
+                drop(stream);
+            })
+            .map_err(|e| AudioError::Fatal(format!("failed to spawn playback thread: {}", e)))?;
+
+        let sample_rate = ready_rx
+            .recv()
+            .map_err(|_| AudioError::Fatal("playback thread exited".to_string()))??;
+        Ok(Self {
+            queue,
+            sample_rate,
+            shutdown_tx: Some(shutdown_tx),
+            handle: Some(handle),
+        })
+    }
+
+    /// Queue `clip` behind anything already playing
+    pub fn enqueue(&self, clip: &Clip) -> Playback {
+        let (done_tx, done_rx) = oneshot::channel();
+        let samples = clip.to_mono_at(self.sample_rate);
+        self.queue.lock().clips.push_back(Queued {
+            samples,
+            pos: 0,
+            done: Some(done_tx),
+        });
+        Playback { done: done_rx }
+    }
+
+    /// Stop the current clip and drop everything queued
+    pub fn stop(&self) {
+        self.queue.lock().clear();
+    }
+
+    /// Whether a clip is playing or queued
+    pub fn is_playing(&self) -> bool {
+        !self.queue.lock().clips.is_empty()
+    }
+}
+
+impl Drop for AudioPlayer {
+    fn drop(&mut self) {
+        self.stop();
+        drop(self.shutdown_tx.take());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn build_output(
+    device_name: Option<&str>,
+    queue: Arc<Mutex<Queue>>,
+) -> Result<(Stream, u32), AudioError> {
+    let host = with_stderr_suppressed(cpal::default_host);
+    let named = device_name.and_then(|name| {
+        host.output_devices().ok()?.find(|d| {
+            d.description()
+                .map(|desc| desc.to_string() == name)
+                .unwrap_or(false)
+        })
+    });
+    if let (Some(name), None) = (device_name, named.as_ref()) {
+        tracing::warn!("Output device '{}' not found; using the default", name);
+    }
+    let device = named
+        .or_else(|| host.default_output_device())
+        .ok_or(AudioError::DeviceNotFound { name: None })?;
+
+    let default_config =
+        device
+            .default_output_config()
+            .map_err(|e| AudioError::FormatNotSupported {
+                format: format!("no default output config: {}", e),
+            })?;
+    let sample_format = default_config.sample_format();
+    let config = StreamConfig {
+        channels: default_config.channels(),
+        sample_rate: default_config.sample_rate(),
+        buffer_size: cpal::BufferSize::Default,
+    };
+    let channels = usize::from(config.channels);
+    let err_fn = |err: cpal::StreamError| {
+        tracing::error!("Audio playback stream error: {}", err);
+    };
+
+    let stream = match sample_format {
+        SampleFormat::F32 => device.build_output_stream(
+            &config,
+            move |data: &mut [f32], _: &_| queue.lock().fill(data, channels),
+            err_fn,
+            None,
+        )?,
+        SampleFormat::I16 => {
+            let mut scratch = Vec::new();
+            device.build_output_stream(
+                &config,
+                move |data: &mut [i16], _: &_| {
+                    scratch.resize(data.len(), 0.0);
+                    queue.lock().fill(&mut scratch, channels);
+                    for (out, &s) in data.iter_mut().zip(&scratch) {
+                        *out = (s.clamp(-1.0, 1.0) * 32767.0).round() as i16;
+                    }
+                },
+                err_fn,
+                None,
+            )?
+        }
+        other => {
+            return Err(AudioError::FormatNotSupported {
+                format: format!("{:?}", other),
+            });
+        }
+    };
+    stream.play()?;
+    tracing::info!(
+        sample_rate = config.sample_rate,
+        channels = config.channels,
+        "Audio playback started"
+    );
+    Ok((stream, config.sample_rate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wav(sample_rate: u32, channels: u16, samples: &[i16]) -> Vec<u8> {
+        let data: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let mut out = Vec::new();
+        out.extend_from_slice(b"RIFF");
+        out.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+        out.extend_from_slice(b"WAVEfmt ");
+        out.extend_from_slice(&16u32.to_le_bytes());
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&channels.to_le_bytes());
+        out.extend_from_slice(&sample_rate.to_le_bytes());
+        out.extend_from_slice(&(sample_rate * u32::from(channels) * 2).to_le_bytes());
+        out.extend_from_slice(&(channels * 2).to_le_bytes());
+        out.extend_from_slice(&16u16.to_le_bytes());
+        out.extend_from_slice(b"data");
+        // Unset size, as streaming writers leave it
+        out.extend_from_slice(&u32::MAX.to_le_bytes());
+        out.extend_from_slice(&data);
+        out
+    }
+
+    #[test]
+    fn decodes_pcm_wav_and_converts_to_device_rate() {
+        let clip = Clip::from_wav(&wav(8_000, 2, &[100, 300, -200, -400])).unwrap();
+        assert_eq!(clip.sample_rate, 8_000);
+        assert_eq!(clip.channels, 2);
+        assert_eq!(clip.samples, vec![100, 300, -200, -400]);
+
+        let mono = clip.to_mono_at(8_000);
+        assert_eq!(mono, vec![200.0 / 32768.0, -300.0 / 32768.0]);
+        let clip = Clip {
+            samples: vec![0; 160],
+            sample_rate: 8_000,
+            channels: 1,
+        };
+        assert_eq!(clip.to_mono_at(48_000).len(), 960);
+
+        assert!(Clip::from_wav(b"RIFF\0\0\0\0WAVE").is_err());
+        assert!(Clip::from_wav(b"not a wav").is_err());
+    }
+
+    #[tokio::test]
+    async fn queue_plays_in_order_and_stop_interrupts() {
+        let mut queue = Queue::default();
+        let mut add = |samples: Vec<f32>| {
+            let (tx, rx) = oneshot::channel();
+            queue.clips.push_back(Queued {
+                samples,
+                pos: 0,
+                done: Some(tx),
+            });
+            Playback { done: rx }
+        };
+        let first = add(vec![0.5; 2]);
+        let second = add(vec![-0.5; 4]);
+        let third = add(vec![0.25; 4]);
+
+        let mut out = [1.0; 8];
+        queue.fill(&mut out, 2);
+        // First clip fills two stereo frames, then the second starts
+        assert_eq!(out, [0.5, 0.5, 0.5, 0.5, -0.5, -0.5, -0.5, -0.5]);
+        assert!(first.finished().await);
+
+        queue.clear();
+        assert!(!second.finished().await);
+        assert!(!third.finished().await);
+        queue.fill(&mut out, 2);
+        assert_eq!(out, [0.0; 8]);
+    }
+}
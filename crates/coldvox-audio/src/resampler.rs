@@ -1,11 +1,11 @@
-use audioadapter_buffers::owned::SequentialOwned;
-use rubato::audioadapter::Adapter;
+use audioadapter_buffers::direct::SequentialSlice;
 use rubato::{
     Async, FixedAsync, PolynomialDegree, Resampler, SincInterpolationParameters,
     SincInterpolationType, WindowFunction,
 };
 
 use super::chunker::ResamplerQuality;
+use super::convert;
 
 /// Streaming resampler for mono i16 audio using Rubato's high-quality resampling.
 ///
@@ -19,7 +19,7 @@ pub struct StreamResampler {
     resampler: Async<f32>,
     /// Input buffer for accumulating samples
     input_buffer: Vec<f32>,
-    /// Output buffer for accumulating resampled samples
+    /// Scratch output for one Rubato chunk
     output_buffer: Vec<f32>,
     /// Chunk size required by Rubato
     chunk_size: usize,
@@ -103,72 +103,68 @@ impl StreamResampler {
     /// Process an arbitrary chunk of mono i16 samples.
     /// Returns a freshly allocated Vec with resampled i16 at out_rate.
     pub fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        let mut out = Vec::new();
+        self.process_into(input, &mut out);
+        out
+    }
+
+    /// Like [`process`](Self::process), but appends to `out`. Once the
+    /// internal buffers have grown to size, this allocates nothing.
+    pub fn process_into(&mut self, input: &[i16], out: &mut Vec<i16>) {
         if self.in_rate == self.out_rate {
-            // Fast path: just clone input
+            // Fast path: just copy input
             tracing::trace!(
                 "Resampler: Passthrough {} samples (no rate change)",
                 input.len()
             );
-            return input.to_vec();
+            out.extend_from_slice(input);
+            return;
         }
 
-        // Convert i16 to f32 and append to input buffer
-        for &sample in input {
-            self.input_buffer.push(sample as f32 / 32768.0);
-        }
+        let before = out.len();
+        convert::i16_to_f32(input, &mut self.input_buffer);
 
-        // Process complete chunks
-        while self.input_buffer.len() >= self.chunk_size {
-            // Prepare input for Rubato using SequentialOwned adapter
-            // SequentialOwned stores all samples for one channel consecutively
-            let chunk: Vec<f32> = self.input_buffer.drain(..self.chunk_size).collect();
-            let input_adapter = SequentialOwned::new_from(chunk, 1, self.chunk_size)
+        // Process complete chunks straight from the input buffer
+        let mut consumed = 0;
+        while self.input_buffer.len() - consumed >= self.chunk_size {
+            let frames_out = self.resampler.output_frames_next();
+            if self.output_buffer.len() < frames_out {
+                self.output_buffer.resize(frames_out, 0.0);
+            }
+            let chunk = &self.input_buffer[consumed..consumed + self.chunk_size];
+            let input_adapter = SequentialSlice::new(chunk, 1, self.chunk_size)
                 .expect("Failed to create input adapter");
+            let out_len = self.output_buffer.len();
+            let mut output_adapter = SequentialSlice::new_mut(&mut self.output_buffer, 1, out_len)
+                .expect("Failed to create output adapter");
 
-            // Process the chunk - use process method which allocates output
-            match self.resampler.process(
-                &input_adapter,
-                0,    // input_offset
-                None, // active_channels_mask
-            ) {
-                Ok(output_frames) => {
-                    // output_frames is SequentialOwned - copy data from channel 0
-                    let out_frames = output_frames.frames();
-                    let mut temp_buffer = vec![0.0f32; out_frames];
-                    let copied = output_frames.copy_from_channel_to_slice(0, 0, &mut temp_buffer);
-                    self.output_buffer.extend_from_slice(&temp_buffer[..copied]);
+            match self
+                .resampler
+                .process_into_buffer(&input_adapter, &mut output_adapter, None)
+            {
+                Ok((_, written)) => {
+                    convert::f32_to_i16(&self.output_buffer[..written], out);
                 }
                 Err(e) => {
                     tracing::error!("Resampler error: {}", e);
-                    // Return empty on error to maintain stream continuity
-                    return Vec::new();
+                    // Drop the input rather than retry it on every call
+                    self.input_buffer.clear();
+                    return;
                 }
             }
+            consumed += self.chunk_size;
         }
+        self.input_buffer.drain(..consumed);
 
-        // Convert accumulated f32 samples back to i16
-        let mut result = Vec::with_capacity(self.output_buffer.len());
-        for &sample in &self.output_buffer {
-            // Clamp to [-1.0, 1.0] and convert to i16
-            let clamped = sample.clamp(-1.0, 1.0);
-            let i16_sample = (clamped * 32767.0).round() as i16;
-            result.push(i16_sample);
-        }
-
-        // Clear the output buffer for next time
-        self.output_buffer.clear();
-
-        if !result.is_empty() {
+        if out.len() > before {
             tracing::trace!(
                 "Resampler: Processed {} input samples -> {} output samples ({}Hz -> {}Hz)",
                 input.len(),
-                result.len(),
+                out.len() - before,
                 self.in_rate,
                 self.out_rate
             );
         }
-
-        result
     }
 
     /// Reset internal state, clearing buffers and resetting the resampler.
@@ -1,29 +1,176 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use rtrb::{Consumer, Producer, RingBuffer};
 use tracing::{trace, warn};
 
 use coldvox_foundation::error::{AudioError, ColdVoxError};
 
+/// Longest a `BlockProducer` write waits for the consumer to make room
+const BLOCK_TIMEOUT: Duration = Duration::from_millis(20);
+
+/// What a write does when the buffer is full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Reject the incoming samples (the new audio is lost)
+    #[default]
+    DropNewest,
+    /// Discard the oldest unread samples to make room, keeping latency bounded.
+    /// Allocates twice the capacity up front so new samples always land; the
+    /// consumer skips the oldest ones on its next read.
+    DropOldest,
+    /// Wait up to 20 ms for the consumer to catch up, then drop the newest.
+    /// Sleeps on the writing thread, so it is not real-time safe: do not use
+    /// it from an audio callback (cpal capture), only from producers on
+    /// ordinary threads such as file or network sources.
+    BlockProducer,
+    /// Double the capacity on the first overflow, then drop the newest. The
+    /// larger buffer is allocated up front; the consumer moves to it once it
+    /// has drained the first one.
+    GrowOnce,
+}
+
+impl std::str::FromStr for OverflowPolicy {
+    type Err = String;
+
+    /// Parses `"drop-newest"`, `"drop-oldest"`, `"block-producer"` or `"grow-once"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('_', "-").as_str() {
+            "" | "drop-newest" => Ok(OverflowPolicy::DropNewest),
+            "drop-oldest" => Ok(OverflowPolicy::DropOldest),
+            "block-producer" | "block" => Ok(OverflowPolicy::BlockProducer),
+            "grow-once" | "grow" => Ok(OverflowPolicy::GrowOnce),
+            _ => Err(format!("invalid capture buffer overflow policy '{}'", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for OverflowPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            OverflowPolicy::DropNewest => "drop-newest",
+            OverflowPolicy::DropOldest => "drop-oldest",
+            OverflowPolicy::BlockProducer => "block-producer",
+            OverflowPolicy::GrowOnce => "grow-once",
+        })
+    }
+}
+
+/// Overflow accounting shared by both halves of a ring buffer
+#[derive(Debug, Default)]
+pub struct RingBufferStats {
+    overflows: AtomicU64,
+    dropped_samples: AtomicU64,
+    capacity: AtomicUsize,
+}
+
+impl RingBufferStats {
+    /// Writes that did not fit when they arrived
+    pub fn overflows(&self) -> u64 {
+        self.overflows.load(Ordering::Relaxed)
+    }
+
+    /// Samples lost to overflows, old or new depending on the policy
+    pub fn dropped_samples(&self) -> u64 {
+        self.dropped_samples.load(Ordering::Relaxed)
+    }
+
+    /// Current capacity in samples (changes once under `GrowOnce`)
+    pub fn capacity(&self) -> usize {
+        self.capacity.load(Ordering::Relaxed)
+    }
+
+    fn record_overflow(&self, dropped: usize) {
+        let n = self.overflows.fetch_add(1, Ordering::Relaxed) + 1;
+        let total = self
+            .dropped_samples
+            .fetch_add(dropped as u64, Ordering::Relaxed)
+            + dropped as u64;
+        // The audio callback overflows in bursts; don't log every one
+        if n == 1 || n.is_power_of_two() {
+            warn!(
+                "Ring buffer overflow #{}: {} samples dropped ({} in total)",
+                n, dropped, total
+            );
+        } else {
+            trace!("Ring buffer overflow #{}: {} samples dropped", n, dropped);
+        }
+    }
+}
+
+/// Overflow handling the producer asks the consumer to carry out, so neither
+/// half ever waits on the other
+#[derive(Debug, Default)]
+struct Control {
+    /// Oldest samples the consumer must discard before its next read
+    /// (`DropOldest`)
+    skip: AtomicUsize,
+    /// The producer has moved to the preallocated larger buffer (`GrowOnce`)
+    grown: AtomicBool,
+}
+
 /// Audio ring buffer using rtrb (real-time safe)
 pub struct AudioRingBuffer {
-    producer: Producer<i16>,
-    consumer: Consumer<i16>,
+    capacity: usize,
+    policy: OverflowPolicy,
+    stats: Arc<RingBufferStats>,
 }
 
 impl AudioRingBuffer {
     /// Create a new ring buffer with the specified capacity
     pub fn new(capacity: usize) -> Self {
-        let (producer, consumer) = RingBuffer::new(capacity);
-        Self { producer, consumer }
+        Self::with_policy(capacity, OverflowPolicy::default())
+    }
+
+    /// Create a ring buffer that handles a full buffer according to `policy`
+    pub fn with_policy(capacity: usize, policy: OverflowPolicy) -> Self {
+        let stats = Arc::new(RingBufferStats::default());
+        stats.capacity.store(capacity, Ordering::Relaxed);
+        Self {
+            capacity,
+            policy,
+            stats,
+        }
+    }
+
+    /// Overflow counters, shared with both halves after `split`
+    pub fn stats(&self) -> Arc<RingBufferStats> {
+        self.stats.clone()
     }
 
-    /// Split into producer and consumer for separate threads
+    /// Split into producer and consumer for separate threads. Everything
+    /// either half may need later is allocated here.
     pub fn split(self) -> (AudioProducer, AudioConsumer) {
+        // Headroom for samples the consumer has been told to skip but has
+        // not skipped yet
+        let allocated = match self.policy {
+            OverflowPolicy::DropOldest => self.capacity * 2,
+            _ => self.capacity,
+        };
+        let (producer, consumer) = RingBuffer::new(allocated);
+        let (next_producer, next_consumer) = match self.policy {
+            OverflowPolicy::GrowOnce => {
+                let (producer, consumer) = RingBuffer::new(self.capacity * 2);
+                (Some(producer), Some(consumer))
+            }
+            _ => (None, None),
+        };
+        let control = Arc::new(Control::default());
         (
             AudioProducer {
-                producer: self.producer,
+                producer,
+                next: next_producer,
+                capacity: self.capacity,
+                policy: self.policy,
+                control: control.clone(),
+                stats: self.stats.clone(),
             },
             AudioConsumer {
-                consumer: self.consumer,
+                consumer,
+                next: next_consumer,
+                control,
+                stats: self.stats,
             },
         )
     }
@@ -32,25 +179,130 @@ impl AudioRingBuffer {
 /// Producer half of the ring buffer (for audio callback thread)
 pub struct AudioProducer {
     producer: Producer<i16>,
+    /// Larger buffer to move to on the first overflow (`GrowOnce`)
+    next: Option<Producer<i16>>,
+    /// Samples the consumer may have queued up
+    capacity: usize,
+    policy: OverflowPolicy,
+    control: Arc<Control>,
+    stats: Arc<RingBufferStats>,
 }
 
 impl AudioProducer {
-    /// Write samples from audio callback (non-blocking)
+    /// Write samples from audio callback (non-blocking except under
+    /// `BlockProducer`). Returns how many of `samples` were stored.
     pub fn write(&mut self, samples: &[i16]) -> Result<usize, ColdVoxError> {
-        let mut chunk = match self.producer.write_chunk(samples.len()) {
-            Ok(chunk) => chunk,
-            Err(_) => {
-                warn!(
-                    "Ring buffer overflow: tried to write {} samples, buffer full",
-                    samples.len()
-                );
-                return Err(AudioError::BufferOverflow {
-                    count: samples.len(),
+        if self.policy == OverflowPolicy::DropOldest {
+            return self.write_dropping_oldest(samples);
+        }
+        if self.producer.slots() < samples.len() {
+            return self.write_full(samples);
+        }
+        self.push(samples);
+        Ok(samples.len())
+    }
+
+    /// Check available space
+    pub fn slots(&self) -> usize {
+        match self.policy {
+            OverflowPolicy::DropOldest => self.capacity.saturating_sub(self.queued()),
+            _ => self.producer.slots(),
+        }
+    }
+
+    /// Overflow policy this buffer was created with
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Overflow counters
+    pub fn stats(&self) -> Arc<RingBufferStats> {
+        self.stats.clone()
+    }
+
+    /// Unread samples the consumer has not been told to skip
+    fn queued(&self) -> usize {
+        let occupied = self.producer.buffer().capacity() - self.producer.slots();
+        occupied.saturating_sub(self.control.skip.load(Ordering::Acquire))
+    }
+
+    /// Always store the newest samples and have the consumer skip as many of
+    /// the oldest as it takes to keep `capacity` samples queued. Only when the
+    /// consumer has not kept up with earlier skips either does the headroom
+    /// run out and the newest samples get dropped.
+    fn write_dropping_oldest(&mut self, samples: &[i16]) -> Result<usize, ColdVoxError> {
+        // Only the newest `capacity` samples can be kept at all
+        let truncated = samples.len().saturating_sub(self.capacity);
+        let samples = &samples[truncated..];
+        if self.producer.slots() < samples.len() {
+            self.stats.record_overflow(truncated + samples.len());
+            return Err(AudioError::BufferOverflow {
+                count: truncated + samples.len(),
+            }
+            .into());
+        }
+        let excess = (self.queued() + samples.len()).saturating_sub(self.capacity);
+        self.push(samples);
+        if excess > 0 {
+            self.control.skip.fetch_add(excess, Ordering::AcqRel);
+        }
+        if truncated + excess > 0 {
+            self.stats.record_overflow(truncated + excess);
+        }
+        Ok(samples.len())
+    }
+
+    fn write_full(&mut self, samples: &[i16]) -> Result<usize, ColdVoxError> {
+        match self.policy {
+            OverflowPolicy::DropNewest | OverflowPolicy::DropOldest => {}
+            OverflowPolicy::BlockProducer => {
+                let deadline = Instant::now() + BLOCK_TIMEOUT;
+                while self.producer.slots() < samples.len() && Instant::now() < deadline {
+                    std::thread::sleep(Duration::from_micros(500));
+                }
+                if self.producer.slots() >= samples.len() {
+                    self.push(samples);
+                    return Ok(samples.len());
                 }
-                .into());
             }
-        };
+            OverflowPolicy::GrowOnce => {
+                if let Some(next) = self.next.take() {
+                    self.grow(next);
+                    if self.producer.slots() >= samples.len() {
+                        self.push(samples);
+                        return Ok(samples.len());
+                    }
+                }
+            }
+        }
+        self.stats.record_overflow(samples.len());
+        Err(AudioError::BufferOverflow {
+            count: samples.len(),
+        }
+        .into())
+    }
 
+    /// Move to the preallocated buffer twice the size. The consumer finishes
+    /// reading the old one before it follows, so sample order is kept.
+    fn grow(&mut self, next: Producer<i16>) {
+        let old_capacity = self.capacity;
+        self.capacity = next.buffer().capacity();
+        self.producer = next;
+        self.control.grown.store(true, Ordering::Release);
+        self.stats.capacity.store(self.capacity, Ordering::Relaxed);
+        warn!(
+            "Ring buffer full; grew from {} to {} samples",
+            old_capacity, self.capacity
+        );
+    }
+
+    fn push(&mut self, samples: &[i16]) {
+        if samples.is_empty() {
+            return;
+        }
+        let Ok(mut chunk) = self.producer.write_chunk(samples.len()) else {
+            return;
+        };
         // Write may wrap; fill both slices
         let (first, second) = chunk.as_mut_slices();
         let split = first.len();
@@ -62,18 +314,16 @@ impl AudioProducer {
         }
         chunk.commit_all();
         trace!("Ring buffer: wrote {} samples", samples.len());
-        Ok(samples.len())
-    }
-
-    /// Check available space
-    pub fn slots(&self) -> usize {
-        self.producer.slots()
     }
 }
 
 /// Consumer half of the ring buffer (for processing thread)
 pub struct AudioConsumer {
     consumer: Consumer<i16>,
+    /// Larger buffer the producer moves to on its first overflow (`GrowOnce`)
+    next: Option<Consumer<i16>>,
+    control: Arc<Control>,
+    stats: Arc<RingBufferStats>,
 }
 
 impl AudioConsumer {
@@ -83,34 +333,70 @@ impl AudioConsumer {
             "Ring buffer: attempting to read up to {} samples",
             buffer.len()
         );
-        let chunk = match self.consumer.read_chunk(buffer.len()) {
-            Ok(chunk) => chunk,
-            Err(rtrb::chunks::ChunkError::TooFewSlots(available)) => {
-                if available == 0 {
-                    return 0;
+        self.skip_dropped();
+        let mut len = read_into(&mut self.consumer, buffer);
+        if len < buffer.len() && self.next.is_some() && self.control.grown.load(Ordering::Acquire) {
+            // The producer no longer writes here; anything it wrote before
+            // moving is visible now
+            len += read_into(&mut self.consumer, &mut buffer[len..]);
+            if self.consumer.is_empty() {
+                if let Some(next) = self.next.take() {
+                    self.consumer = next;
                 }
-                self.consumer.read_chunk(available).unwrap()
+                len += read_into(&mut self.consumer, &mut buffer[len..]);
             }
-        };
-
-        let len = chunk.len();
-        let (first, second) = chunk.as_slices();
-        let split = first.len();
-        if !first.is_empty() {
-            buffer[..split].copy_from_slice(first);
-        }
-        if !second.is_empty() {
-            buffer[split..split + second.len()].copy_from_slice(second);
         }
-        chunk.commit_all();
         trace!("Ring buffer: read {} samples", len);
         len
     }
 
     /// Check available samples to read
     pub fn slots(&self) -> usize {
-        self.consumer.slots()
+        let moved = self.control.grown.load(Ordering::Acquire);
+        let next = self
+            .next
+            .as_ref()
+            .filter(|_| moved)
+            .map_or(0, |c| c.slots());
+        (self.consumer.slots() + next).saturating_sub(self.control.skip.load(Ordering::Acquire))
+    }
+
+    /// Overflow counters
+    pub fn stats(&self) -> Arc<RingBufferStats> {
+        self.stats.clone()
+    }
+
+    /// Discard the oldest samples the producer asked to drop (`DropOldest`)
+    fn skip_dropped(&mut self) {
+        let requested = self.control.skip.load(Ordering::Acquire);
+        if requested == 0 {
+            return;
+        }
+        // The producer may ask for a few more than are left when this side
+        // read concurrently; those are simply gone already
+        let skip = requested.min(self.consumer.slots());
+        if let Ok(chunk) = self.consumer.read_chunk(skip) {
+            chunk.commit_all();
+        }
+        self.control.skip.fetch_sub(requested, Ordering::AcqRel);
+    }
+}
+
+/// Copy as many samples as are available and fit into `buffer`
+fn read_into(consumer: &mut Consumer<i16>, buffer: &mut [i16]) -> usize {
+    let len = consumer.slots().min(buffer.len());
+    if len == 0 {
+        return 0;
     }
+    let Ok(chunk) = consumer.read_chunk(len) else {
+        return 0;
+    };
+    let (first, second) = chunk.as_slices();
+    let split = first.len();
+    buffer[..split].copy_from_slice(first);
+    buffer[split..len].copy_from_slice(second);
+    chunk.commit_all();
+    len
 }
 
 #[cfg(test)]
@@ -135,6 +421,7 @@ mod tests {
     #[test]
     fn test_overflow() {
         let rb = AudioRingBuffer::new(16);
+        let stats = rb.stats();
         let (mut producer, mut _consumer) = rb.split();
 
         let samples = vec![1i16; 20];
@@ -145,5 +432,87 @@ mod tests {
 
         let samples = vec![2i16; 1];
         assert!(producer.write(&samples).is_err());
+        assert_eq!(stats.overflows(), 2);
+        assert_eq!(stats.dropped_samples(), 21);
+    }
+
+    #[test]
+    fn drop_oldest_keeps_the_newest_samples() {
+        let rb = AudioRingBuffer::with_policy(8, OverflowPolicy::DropOldest);
+        let (mut producer, mut consumer) = rb.split();
+        producer.write(&[1, 2, 3, 4, 5, 6]).unwrap();
+        assert_eq!(producer.write(&[7, 8, 9, 10]).unwrap(), 4);
+
+        let mut buffer = [0i16; 16];
+        let read = consumer.read(&mut buffer);
+        assert_eq!(&buffer[..read], &[3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(consumer.stats().overflows(), 1);
+        assert_eq!(consumer.stats().dropped_samples(), 2);
+    }
+
+    #[test]
+    fn drop_oldest_drops_newest_once_the_headroom_is_spent() {
+        let rb = AudioRingBuffer::with_policy(4, OverflowPolicy::DropOldest);
+        let (mut producer, mut consumer) = rb.split();
+        // The consumer never reads: each write past capacity asks it to skip
+        assert_eq!(producer.write(&[1, 2, 3, 4]).unwrap(), 4);
+        assert_eq!(producer.write(&[5, 6, 7, 8]).unwrap(), 4);
+        assert_eq!(producer.slots(), 0);
+        assert_eq!(consumer.slots(), 4);
+        assert!(producer.write(&[9]).is_err());
+
+        let mut buffer = [0i16; 16];
+        let read = consumer.read(&mut buffer);
+        assert_eq!(&buffer[..read], &[5, 6, 7, 8]);
+        assert_eq!(consumer.stats().dropped_samples(), 5);
+
+        // Longer than the buffer: only the newest samples are kept
+        assert_eq!(producer.write(&[1, 2, 3, 4, 5, 6]).unwrap(), 4);
+        let read = consumer.read(&mut buffer);
+        assert_eq!(&buffer[..read], &[3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn grow_once_keeps_pending_audio() {
+        let rb = AudioRingBuffer::with_policy(4, OverflowPolicy::GrowOnce);
+        let (mut producer, mut consumer) = rb.split();
+        producer.write(&[1, 2, 3]).unwrap();
+        assert_eq!(producer.write(&[4, 5, 6]).unwrap(), 3);
+        assert_eq!(consumer.stats().capacity(), 8);
+        assert_eq!(consumer.stats().dropped_samples(), 0);
+
+        let mut buffer = [0i16; 16];
+        let read = consumer.read(&mut buffer);
+        assert_eq!(&buffer[..read], &[1, 2, 3, 4, 5, 6]);
+
+        // Only once
+        producer.write(&[0; 8]).unwrap();
+        assert!(producer.write(&[0; 1]).is_err());
+        assert_eq!(consumer.stats().capacity(), 8);
+    }
+
+    #[test]
+    fn block_producer_gives_up_after_the_timeout() {
+        let rb = AudioRingBuffer::with_policy(4, OverflowPolicy::BlockProducer);
+        let (mut producer, _consumer) = rb.split();
+        producer.write(&[1, 2, 3, 4]).unwrap();
+        let start = Instant::now();
+        assert!(producer.write(&[5]).is_err());
+        assert!(start.elapsed() >= BLOCK_TIMEOUT);
+        assert_eq!(producer.stats().dropped_samples(), 1);
+    }
+
+    #[test]
+    fn parses_policies() {
+        for policy in [
+            OverflowPolicy::DropNewest,
+            OverflowPolicy::DropOldest,
+            OverflowPolicy::BlockProducer,
+            OverflowPolicy::GrowOnce,
+        ] {
+            assert_eq!(policy.to_string().parse::<OverflowPolicy>(), Ok(policy));
+        }
+        assert_eq!("grow_once".parse(), Ok(OverflowPolicy::GrowOnce));
+        assert!("fifo".parse::<OverflowPolicy>().is_err());
     }
 }
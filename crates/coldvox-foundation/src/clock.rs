@@ -3,7 +3,7 @@
 //! This module provides a Clock trait that can be implemented for both real-time
 //! and virtual-time execution, enabling deterministic testing of time-dependent code.
 
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 /// Clock trait for time abstraction
 pub trait Clock: Send + Sync {
@@ -106,3 +106,131 @@ pub fn test_clock() -> SharedClock {
 pub fn test_clock_with_start(start_time: Instant) -> SharedClock {
     std::sync::Arc::new(TestClock::new_with_start_time(start_time))
 }
+
+/// A disagreement between the monotonic and the wall clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockJump {
+    /// Time passed that the poll loop didn't see: the machine was most
+    /// likely suspended. `instant_paused` is set when [`Instant`] stood still
+    /// meanwhile (Linux), so `Instant`-based deadlines are now `slept` late.
+    Resumed {
+        slept: Duration,
+        instant_paused: bool,
+    },
+    /// The wall clock was set back
+    WallClockBack { by: Duration },
+}
+
+/// Detects suspend/resume and wall clock changes by comparing how far
+/// [`Instant`] and [`SystemTime`] advanced between regular polls
+#[derive(Debug)]
+pub struct ClockJumpDetector {
+    interval: Duration,
+    threshold: Duration,
+    last: Option<(Instant, SystemTime)>,
+}
+
+impl ClockJumpDetector {
+    /// `interval` is how often [`poll`](Self::poll) is called; jumps shorter
+    /// than `threshold` are ignored
+    pub fn new(interval: Duration, threshold: Duration) -> Self {
+        Self {
+            interval,
+            threshold,
+            last: None,
+        }
+    }
+
+    pub fn poll(&mut self) -> Option<ClockJump> {
+        self.observe(Instant::now(), SystemTime::now())
+    }
+
+    pub fn observe(&mut self, now: Instant, wall: SystemTime) -> Option<ClockJump> {
+        let (last_now, last_wall) = self.last.replace((now, wall))?;
+        let mono_elapsed = now.saturating_duration_since(last_now);
+        let wall_elapsed = match wall.duration_since(last_wall) {
+            Ok(elapsed) => elapsed,
+            Err(e) => {
+                let by = e.duration() + mono_elapsed;
+                return (by >= self.threshold).then_some(ClockJump::WallClockBack { by });
+            }
+        };
+
+        // Linux: Instant stops during suspend while the wall clock runs on
+        let unseen = wall_elapsed.saturating_sub(mono_elapsed);
+        // Elsewhere both run on, but the poll came late by the nap
+        let late = mono_elapsed.saturating_sub(self.interval);
+        if unseen.max(late) >= self.threshold {
+            return Some(ClockJump::Resumed {
+                slept: unseen.max(late),
+                instant_paused: unseen >= late,
+            });
+        }
+        let behind = mono_elapsed.saturating_sub(wall_elapsed);
+        (behind >= self.threshold).then_some(ClockJump::WallClockBack { by: behind })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_secs(2);
+
+    fn detector() -> (ClockJumpDetector, Instant, SystemTime) {
+        let mut d = ClockJumpDetector::new(INTERVAL, Duration::from_secs(5));
+        let (now, wall) = (Instant::now(), SystemTime::now());
+        assert_eq!(d.observe(now, wall), None);
+        (d, now, wall)
+    }
+
+    #[test]
+    fn steady_clocks_are_quiet() {
+        let (mut d, now, wall) = detector();
+        assert_eq!(d.observe(now + INTERVAL, wall + INTERVAL), None);
+        // Scheduling jitter and small NTP slews stay below the threshold
+        let jitter = Duration::from_millis(2300);
+        assert_eq!(
+            d.observe(now + INTERVAL * 2, wall + INTERVAL * 2 + jitter),
+            None
+        );
+    }
+
+    #[test]
+    fn suspend_with_paused_instant_is_a_resume() {
+        let (mut d, now, wall) = detector();
+        let nap = Duration::from_secs(3600);
+        assert_eq!(
+            d.observe(now + INTERVAL, wall + INTERVAL + nap),
+            Some(ClockJump::Resumed {
+                slept: nap,
+                instant_paused: true
+            })
+        );
+    }
+
+    #[test]
+    fn late_poll_with_running_instant_is_a_resume() {
+        let (mut d, now, wall) = detector();
+        let nap = Duration::from_secs(600);
+        assert_eq!(
+            d.observe(now + INTERVAL + nap, wall + INTERVAL + nap),
+            Some(ClockJump::Resumed {
+                slept: nap,
+                instant_paused: false
+            })
+        );
+    }
+
+    #[test]
+    fn wall_clock_set_back_is_reported() {
+        let (mut d, now, wall) = detector();
+        let back = Duration::from_secs(60);
+        assert_eq!(
+            d.observe(now + INTERVAL, wall - back),
+            Some(ClockJump::WallClockBack {
+                by: back + INTERVAL
+            })
+        );
+    }
+}
@@ -174,12 +174,27 @@ pub enum InjectionError {
     #[error("Clipboard error: {0}")]
     Clipboard(String),
 
+    #[error("Clipboard busy: {0}")]
+    ClipboardBusy(String),
+
+    #[error("Target not ready: {0}")]
+    TargetNotReady(String),
+
     #[error("Process error: {0}")]
     Process(String),
 
     #[error("Permission denied: {0}")]
     PermissionDenied(String),
 
+    #[error("Focus state unknown and injection disabled")]
+    FocusUnknown,
+
+    #[error("Locked target window could not be activated")]
+    LockedTargetUnavailable,
+
+    #[error("Injection refused: {0}")]
+    Refused(Refusal),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -187,6 +202,19 @@ pub enum InjectionError {
     Other(String),
 }
 
+/// Why injection was declined on purpose rather than attempted
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Refusal {
+    #[error("injection is paused")]
+    Paused,
+
+    #[error("injection is paused while the screen is shared")]
+    ScreenShared,
+
+    #[error("application {0} is not allowed for injection")]
+    Blocked(String),
+}
+
 /// Device status events for monitoring audio device changes
 #[derive(Debug, Clone, PartialEq)]
 pub enum DeviceEvent {
@@ -243,6 +271,56 @@ impl ColdVoxError {
     }
 }
 
+/// How an injection failure should be handled, independent of its message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FailureClass {
+    /// Likely to work if tried again shortly (timeout, clipboard busy, window
+    /// still activating)
+    Transient,
+    /// The method can't work here until the environment changes (tool not
+    /// installed, permission missing)
+    Unavailable,
+    /// The target rejected the method (no editable element); other methods
+    /// may still work
+    Target,
+    /// The method ran and failed
+    Failed,
+    /// Injection as a whole was stopped; not the method's fault
+    Aborted,
+}
+
+impl InjectionError {
+    pub fn class(&self) -> FailureClass {
+        match self {
+            InjectionError::Timeout(_)
+            | InjectionError::ClipboardBusy(_)
+            | InjectionError::TargetNotReady(_) => FailureClass::Transient,
+            InjectionError::MethodNotAvailable(_)
+            | InjectionError::MethodUnavailable(_)
+            | InjectionError::PermissionDenied(_) => FailureClass::Unavailable,
+            InjectionError::NoEditableFocus
+            | InjectionError::FocusUnknown
+            | InjectionError::LockedTargetUnavailable => FailureClass::Target,
+            InjectionError::BudgetExhausted
+            | InjectionError::AllMethodsFailed(_)
+            | InjectionError::Refused(_) => FailureClass::Aborted,
+            InjectionError::Io(e) => match e.kind() {
+                std::io::ErrorKind::TimedOut
+                | std::io::ErrorKind::WouldBlock
+                | std::io::ErrorKind::Interrupted => FailureClass::Transient,
+                std::io::ErrorKind::NotFound | std::io::ErrorKind::PermissionDenied => {
+                    FailureClass::Unavailable
+                }
+                _ => FailureClass::Failed,
+            },
+            InjectionError::MethodFailed(_)
+            | InjectionError::Clipboard(_)
+            | InjectionError::Process(_)
+            | InjectionError::Other(_) => FailureClass::Failed,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct AudioConfig {
     pub silence_threshold: i16,
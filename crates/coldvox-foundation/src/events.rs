@@ -0,0 +1,152 @@
+//! # Structured Events for Log Pipelines
+//!
+//! A small, stable set of events for external consumers (Loki, jq scripts)
+//! that should not have to parse human-oriented log messages. Every event is
+//! emitted at INFO under the [`TARGET`] target with an `event` field naming it
+//! and a `schema` field carrying [`SCHEMA_VERSION`]. With `--log-format json`
+//! each one is a single JSON object per line:
+//!
+//! ```text
+//! {"timestamp":"…","level":"INFO","message":"injection.result","event":"injection.result","schema":1,"method":"AtspiInsert","app":"org.kde.kate","success":true,"duration_ms":14,"target":"coldvox::event"}
+//! ```
+//!
+//! | `event`               | fields                                                      |
+//! |-----------------------|-------------------------------------------------------------|
+//! | `injection.attempt`   | `method`, `app`, `chars`, `attempt`                         |
+//! | `injection.result`    | `method`, `app`, `success`, `duration_ms`, `error` (failed) |
+//! | `transcription`       | `utterance_id`, `source`, `chars`, `words`                  |
+//! | `transcription.error` | `code`, `error`                                             |
+//! | `vad.speech_start`    | `timestamp_ms`, `energy_db`                                 |
+//! | `vad.speech_end`      | `timestamp_ms`, `duration_ms`, `energy_db`                  |
+//! | `system.resume`       | `slept_ms`                                                  |
+//!
+//! Within a schema version fields are only ever added; renaming or removing
+//! one bumps [`SCHEMA_VERSION`]. Dictated text is never part of an event.
+//! Filter them with `RUST_LOG=coldvox::event=info` or `jq 'select(.target == "coldvox::event")'`.
+
+use tracing::info;
+
+/// Target all structured events are emitted under
+pub const TARGET: &str = "coldvox::event";
+
+/// Version of the event field set
+pub const SCHEMA_VERSION: u32 = 1;
+
+pub const INJECTION_ATTEMPT: &str = "injection.attempt";
+pub const INJECTION_RESULT: &str = "injection.result";
+pub const TRANSCRIPTION: &str = "transcription";
+pub const TRANSCRIPTION_ERROR: &str = "transcription.error";
+pub const VAD_SPEECH_START: &str = "vad.speech_start";
+pub const VAD_SPEECH_END: &str = "vad.speech_end";
+pub const SYSTEM_RESUME: &str = "system.resume";
+
+/// An injection method is about to be tried (`attempt` is 1-based)
+pub fn injection_attempt(method: &str, app: &str, chars: usize, attempt: usize) {
+    info!(
+        target: TARGET,
+        event = INJECTION_ATTEMPT,
+        schema = SCHEMA_VERSION,
+        method,
+        app,
+        chars,
+        attempt,
+        "{}",
+        INJECTION_ATTEMPT
+    );
+}
+
+/// Outcome of one injection method; `error` is set when it failed
+pub fn injection_result(method: &str, app: &str, duration_ms: u64, error: Option<&str>) {
+    match error {
+        None => info!(
+            target: TARGET,
+            event = INJECTION_RESULT,
+            schema = SCHEMA_VERSION,
+            method,
+            app,
+            success = true,
+            duration_ms,
+            "{}",
+            INJECTION_RESULT
+        ),
+        Some(error) => info!(
+            target: TARGET,
+            event = INJECTION_RESULT,
+            schema = SCHEMA_VERSION,
+            method,
+            app,
+            success = false,
+            duration_ms,
+            error,
+            "{}",
+            INJECTION_RESULT
+        ),
+    }
+}
+
+/// A final transcript; `words` is 0 when the backend reports no word timings
+pub fn transcription(utterance_id: u64, source: &str, chars: usize, words: usize) {
+    info!(
+        target: TARGET,
+        event = TRANSCRIPTION,
+        schema = SCHEMA_VERSION,
+        utterance_id,
+        source,
+        chars,
+        words,
+        "{}",
+        TRANSCRIPTION
+    );
+}
+
+/// The STT backend reported an error
+pub fn transcription_error(code: &str, error: &str) {
+    info!(
+        target: TARGET,
+        event = TRANSCRIPTION_ERROR,
+        schema = SCHEMA_VERSION,
+        code,
+        error,
+        "{}",
+        TRANSCRIPTION_ERROR
+    );
+}
+
+/// Speech started (from the VAD or an activation hotkey)
+pub fn vad_speech_start(timestamp_ms: u64, energy_db: f32) {
+    info!(
+        target: TARGET,
+        event = VAD_SPEECH_START,
+        schema = SCHEMA_VERSION,
+        timestamp_ms,
+        energy_db,
+        "{}",
+        VAD_SPEECH_START
+    );
+}
+
+/// Speech ended after `duration_ms`
+pub fn vad_speech_end(timestamp_ms: u64, duration_ms: u64, energy_db: f32) {
+    info!(
+        target: TARGET,
+        event = VAD_SPEECH_END,
+        schema = SCHEMA_VERSION,
+        timestamp_ms,
+        duration_ms,
+        energy_db,
+        "{}",
+        VAD_SPEECH_END
+    );
+}
+
+/// The machine woke up after roughly `slept_ms` of suspend
+pub fn system_resume(slept_ms: u64) {
+    info!(
+        target: TARGET,
+        event = SYSTEM_RESUME,
+        schema = SCHEMA_VERSION,
+        slept_ms,
+        "{}",
+        SYSTEM_RESUME
+    );
+}
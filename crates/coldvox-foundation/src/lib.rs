@@ -1,7 +1,11 @@
 pub mod clock;
 pub mod env;
 pub mod error;
+pub mod events;
 pub mod health;
+pub mod notify;
+pub mod rng;
+pub mod scrub;
 pub mod shutdown;
 pub mod state;
 pub mod test_env;
@@ -0,0 +1,125 @@
+//! Desktop notifications via the freedesktop `org.freedesktop.Notifications`
+//! service. Used for events the user has to act on (e.g. STT failover,
+//! undelivered dictation) while ColdVox runs headless, and for the
+//! replace-in-place push-to-talk indicator. Without the `notifications`
+//! feature, or on platforms without a session bus, this only logs.
+
+/// Notification urgency as defined by the freedesktop spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Urgency {
+    Low = 0,
+    Normal = 1,
+    Critical = 2,
+}
+
+impl Urgency {
+    /// Icon used when the caller doesn't pick one
+    pub fn icon_name(self) -> &'static str {
+        match self {
+            Urgency::Critical => "dialog-error",
+            _ => "dialog-warning",
+        }
+    }
+}
+
+/// A notification to show or update
+#[derive(Debug, Clone)]
+pub struct Notification<'a> {
+    pub summary: &'a str,
+    pub body: &'a str,
+    pub icon: &'a str,
+    pub urgency: Urgency,
+    /// Keep it out of the notification history
+    pub transient: bool,
+    /// Id of an earlier notification to replace, or 0 for a new one
+    pub replaces_id: u32,
+}
+
+impl<'a> Notification<'a> {
+    pub fn new(summary: &'a str, body: &'a str, urgency: Urgency) -> Self {
+        Self {
+            summary,
+            body,
+            icon: urgency.icon_name(),
+            urgency,
+            transient: false,
+            replaces_id: 0,
+        }
+    }
+}
+
+/// Show a desktop notification. Failures are logged at debug level and
+/// otherwise ignored; notifications are best-effort.
+pub async fn send(summary: &str, body: &str, urgency: Urgency) {
+    #[cfg(all(target_os = "linux", feature = "notifications"))]
+    {
+        let notification = Notification::new(summary, body, urgency);
+        let shown = match zbus::Connection::session().await {
+            Ok(conn) => show(&conn, &notification).await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = shown {
+            tracing::debug!("Desktop notification failed: {}", e);
+        }
+    }
+
+    #[cfg(not(all(target_os = "linux", feature = "notifications")))]
+    tracing::debug!(
+        "Desktop notifications unavailable: {} ({:?}): {}",
+        summary,
+        urgency,
+        body
+    );
+}
+
+/// Show (or replace) a notification on `conn`; returns its id.
+#[cfg(all(target_os = "linux", feature = "notifications"))]
+pub async fn show(conn: &zbus::Connection, notification: &Notification<'_>) -> zbus::Result<u32> {
+    use std::collections::HashMap;
+    use zbus::zvariant::Value;
+
+    let mut hints: HashMap<&str, Value<'_>> = HashMap::new();
+    hints.insert("urgency", Value::from(notification.urgency as u8));
+    if notification.transient {
+        hints.insert("transient", Value::from(true));
+    }
+    let actions: Vec<&str> = Vec::new();
+
+    proxy(conn)
+        .await?
+        .call(
+            "Notify",
+            &(
+                "ColdVox",
+                notification.replaces_id,
+                notification.icon,
+                notification.summary,
+                notification.body,
+                actions,
+                hints,
+                -1i32,
+            ),
+        )
+        .await
+}
+
+/// Close a notification shown by [`show`].
+#[cfg(all(target_os = "linux", feature = "notifications"))]
+pub async fn close(conn: &zbus::Connection, id: u32) -> zbus::Result<()> {
+    proxy(conn)
+        .await?
+        .call_method("CloseNotification", &(id,))
+        .await?;
+    Ok(())
+}
+
+#[cfg(all(target_os = "linux", feature = "notifications"))]
+async fn proxy(conn: &zbus::Connection) -> zbus::Result<zbus::Proxy<'static>> {
+    zbus::Proxy::new(
+        conn,
+        "org.freedesktop.Notifications",
+        "/org/freedesktop/Notifications",
+        "org.freedesktop.Notifications",
+    )
+    .await
+}
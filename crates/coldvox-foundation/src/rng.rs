@@ -0,0 +1,121 @@
+//! # Seedable Randomness for Replay Determinism
+//!
+//! Components that need randomness (backoff jitter, pacing jitter) draw from a
+//! [`SharedRng`] instead of ambient sources, so a fixed seed reproduces the
+//! same decisions. The process-wide source is seeded from `COLDVOX_SEED` when
+//! set, or via [`set_global_seed`], and from entropy otherwise.
+
+use std::ops::Range;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Small deterministic PRNG (SplitMix64). Not for cryptographic use.
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    /// Create a generator that always yields the same sequence for `seed`
+    pub fn from_seed(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Create a generator seeded from the system clock and process id
+    pub fn from_entropy() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Self::from_seed(nanos ^ (u64::from(std::process::id()) << 32))
+    }
+
+    /// Re-seed in place
+    pub fn reseed(&mut self, seed: u64) {
+        self.state = seed;
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `range` (returns `range.start` for an empty range)
+    pub fn range_u64(&mut self, range: Range<u64>) -> u64 {
+        let span = range.end.saturating_sub(range.start);
+        if span == 0 {
+            return range.start;
+        }
+        range.start + self.next_u64() % span
+    }
+
+    /// Uniform value in `[0, 1)`
+    pub fn f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Thread-safe RNG that can be shared across components
+pub type SharedRng = Arc<Mutex<SeededRng>>;
+
+/// Create a shared RNG with a fixed seed
+pub fn seeded_rng(seed: u64) -> SharedRng {
+    Arc::new(Mutex::new(SeededRng::from_seed(seed)))
+}
+
+/// Create a shared RNG seeded from entropy
+pub fn entropy_rng() -> SharedRng {
+    Arc::new(Mutex::new(SeededRng::from_entropy()))
+}
+
+static GLOBAL_RNG: OnceLock<SharedRng> = OnceLock::new();
+
+/// Process-wide RNG, seeded from `COLDVOX_SEED` on first use when it is set
+pub fn global_rng() -> SharedRng {
+    GLOBAL_RNG
+        .get_or_init(|| {
+            match std::env::var("COLDVOX_SEED")
+                .ok()
+                .and_then(|s| s.trim().parse::<u64>().ok())
+            {
+                Some(seed) => seeded_rng(seed),
+                None => entropy_rng(),
+            }
+        })
+        .clone()
+}
+
+/// Re-seed the process-wide RNG (e.g. at the start of a replay run)
+pub fn set_global_seed(seed: u64) {
+    global_rng().lock().unwrap().reseed(seed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = SeededRng::from_seed(42);
+        let mut b = SeededRng::from_seed(42);
+        let xs: Vec<u64> = (0..8).map(|_| a.next_u64()).collect();
+        let ys: Vec<u64> = (0..8).map(|_| b.next_u64()).collect();
+        assert_eq!(xs, ys);
+        assert_ne!(SeededRng::from_seed(43).next_u64(), xs[0]);
+    }
+
+    #[test]
+    fn range_and_unit_interval_bounds() {
+        let mut rng = SeededRng::from_seed(7);
+        for _ in 0..1000 {
+            let v = rng.range_u64(10..20);
+            assert!((10..20).contains(&v));
+            let f = rng.f64();
+            assert!((0.0..1.0).contains(&f));
+        }
+        assert_eq!(rng.range_u64(5..5), 5);
+    }
+}
@@ -18,6 +18,8 @@ pub struct OverlaySnapshot {
     pub expanded: bool,
     pub status: OverlayStatus,
     pub paused: bool,
+    /// VAD reports speech right now
+    pub speech_active: bool,
     pub partial_transcript: String,
     pub final_transcript: String,
     pub status_detail: String,
@@ -30,6 +32,7 @@ impl Default for OverlaySnapshot {
             expanded: false,
             status: OverlayStatus::Idle,
             paused: false,
+            speech_active: false,
             partial_transcript: String::new(),
             final_transcript: String::new(),
             status_detail: "Overlay shell ready. Expand to inspect the seam.".to_string(),
@@ -44,3 +47,63 @@ pub struct OverlayEvent {
     pub reason: String,
     pub snapshot: OverlaySnapshot,
 }
+
+pub const SETTINGS_EVENT_NAME: &str = "coldvox://settings";
+
+/// Label of the settings window; the frontend renders the settings panel
+/// when loaded with `?view=settings`
+pub const SETTINGS_WINDOW_LABEL: &str = "settings";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ActivationChoice {
+    Vad,
+    Hotkey,
+}
+
+/// Injection options the settings window edits. They take effect the next
+/// time the pipeline starts.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct InjectionForm {
+    pub voice_commands: bool,
+    pub adaptive_pacing: bool,
+    pub inject_on_unknown_focus: bool,
+    pub notify_on_failure: bool,
+}
+
+/// The editable part of `Settings`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsForm {
+    /// Input device name; None for the host default
+    pub device: Option<String>,
+    pub activation_mode: ActivationChoice,
+    /// Preferred STT plugin id; None to let the runtime choose
+    pub stt_plugin: Option<String>,
+    pub injection: InjectionForm,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsSnapshot {
+    pub form: SettingsForm,
+    /// Input devices to choose from
+    pub devices: Vec<String>,
+    /// STT plugin ids to choose from
+    pub stt_plugins: Vec<String>,
+    pub pipeline_running: bool,
+    /// Saved changes the running pipeline only picks up after a restart
+    pub restart_required: bool,
+    /// Config file changes are written to
+    pub config_path: Option<String>,
+    pub status_detail: String,
+    pub error_message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsEvent {
+    pub reason: String,
+    pub snapshot: SettingsSnapshot,
+}
@@ -1,3 +1,423 @@
+//! Tauri host for the ColdVox overlay and its settings window.
+//!
+//! The overlay follows the running pipeline: VAD speech start/end drives the
+//! listening indicator, STT partials and finals fill the transcript lanes.
+//! The settings window edits [`Settings`], saves them to the user's config
+//! file and applies what it can to the running pipeline (input device,
+//! activation mode, STT plugin); the rest takes effect on the next start.
+
+mod contract;
+mod state;
+mod window;
+
+use std::sync::Arc;
+
+use coldvox_app::Settings;
+use coldvox_runtime::stt::plugin_manager::SttPluginManager;
+use coldvox_runtime::TranscriptionEvent;
+use parking_lot::Mutex;
+use tauri::async_runtime::JoinHandle;
+use tauri::{Emitter, Manager, State, WebviewUrl, WebviewWindowBuilder};
+use tokio::sync::broadcast::error::RecvError;
+
+use contract::{
+    OverlayEvent, OverlaySnapshot, SettingsEvent, SettingsForm, SettingsSnapshot,
+    OVERLAY_EVENT_NAME, SETTINGS_EVENT_NAME, SETTINGS_WINDOW_LABEL,
+};
+use state::{OverlayModel, SettingsModel};
+
+const MAIN_WINDOW_LABEL: &str = "main";
+
+/// A started pipeline and the tasks forwarding its events to the overlay
+struct Pipeline {
+    handle: Arc<coldvox_runtime::AppHandle>,
+    forwarders: Vec<JoinHandle<()>>,
+}
+
+struct Shell {
+    overlay: Mutex<OverlayModel>,
+    settings: Mutex<SettingsModel>,
+    pipeline: tokio::sync::Mutex<Option<Pipeline>>,
+}
+
+impl Shell {
+    fn new(settings: Settings) -> Self {
+        Self {
+            overlay: Mutex::new(OverlayModel::default()),
+            settings: Mutex::new(SettingsModel::new(
+                settings,
+                Settings::writable_config_path(),
+            )),
+            pipeline: tokio::sync::Mutex::new(None),
+        }
+    }
+}
+
+/// Apply `change` to the overlay model and broadcast the result
+fn update_overlay(
+    app: &tauri::AppHandle,
+    reason: &str,
+    change: impl FnOnce(&mut OverlayModel) -> OverlaySnapshot,
+) -> OverlaySnapshot {
+    let shell = app.state::<Shell>();
+    let (was_expanded, snapshot) = {
+        let mut overlay = shell.overlay.lock();
+        let was_expanded = overlay.snapshot().expanded;
+        (was_expanded, change(&mut overlay))
+    };
+    if snapshot.expanded != was_expanded {
+        if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
+            if let Err(e) = window::sync_window(&window, &snapshot) {
+                tracing::warn!("Failed to resize the overlay: {}", e);
+            }
+        }
+    }
+    let event = OverlayEvent {
+        reason: reason.to_string(),
+        snapshot: snapshot.clone(),
+    };
+    if let Err(e) = app.emit(OVERLAY_EVENT_NAME, event) {
+        tracing::debug!("Overlay event not delivered: {}", e);
+    }
+    snapshot
+}
+
+fn emit_settings(app: &tauri::AppHandle, reason: &str, snapshot: &SettingsSnapshot) {
+    let event = SettingsEvent {
+        reason: reason.to_string(),
+        snapshot: snapshot.clone(),
+    };
+    if let Err(e) = app.emit(SETTINGS_EVENT_NAME, event) {
+        tracing::debug!("Settings event not delivered: {}", e);
+    }
+}
+
+/// Forward VAD, STT and pause events from the pipeline to the overlay
+fn spawn_forwarders(
+    app: &tauri::AppHandle,
+    handle: &coldvox_runtime::AppHandle,
+) -> Vec<JoinHandle<()>> {
+    let mut vad_rx = handle.subscribe_vad();
+    let vad_app = app.clone();
+    let vad = tauri::async_runtime::spawn(async move {
+        loop {
+            match vad_rx.recv().await {
+                Ok(event) => {
+                    update_overlay(&vad_app, "vad", |m| m.apply_vad_event(&event));
+                }
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+
+    let mut stt_rx = handle.subscribe_stt();
+    let stt_app = app.clone();
+    let stt = tauri::async_runtime::spawn(async move {
+        loop {
+            match stt_rx.recv().await {
+                Ok(TranscriptionEvent::Partial { text, .. }) => {
+                    update_overlay(&stt_app, "partial", |m| {
+                        m.apply_partial_transcript(&text, None)
+                    });
+                }
+                Ok(TranscriptionEvent::Final { text, .. }) => {
+                    update_overlay(&stt_app, "final", |m| m.apply_final_transcript(&text, None));
+                }
+                Ok(TranscriptionEvent::Error { message, .. }) => {
+                    update_overlay(&stt_app, "stt-error", |m| {
+                        m.reject_command(&message, "Transcription failed.")
+                    });
+                }
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+
+    // Pause can also be toggled by hotkey
+    let mut pause_rx = handle.subscribe_pause();
+    let pause_app = app.clone();
+    let pause = tauri::async_runtime::spawn(async move {
+        while pause_rx.changed().await.is_ok() {
+            let paused = *pause_rx.borrow_and_update();
+            update_overlay(&pause_app, "pause", |m| m.set_paused(paused));
+        }
+    });
+
+    vec![vad, stt, pause]
+}
+
+/// Refresh the device and STT plugin choices offered by the settings window
+async fn refresh_choices(shell: &Shell) {
+    let devices = match coldvox_audio::DeviceManager::new() {
+        Ok(manager) => manager
+            .enumerate_devices()
+            .into_iter()
+            .map(|device| device.name)
+            .collect(),
+        Err(e) => {
+            tracing::warn!("Cannot list input devices: {}", e);
+            Vec::new()
+        }
+    };
+    let plugin_manager = shell
+        .pipeline
+        .lock()
+        .await
+        .as_ref()
+        .and_then(|pipeline| pipeline.handle.plugin_manager.clone());
+    let plugins = match plugin_manager {
+        Some(manager) => manager.read().await.list_plugins_sync(),
+        None => SttPluginManager::new().list_plugins_sync(),
+    };
+    shell.settings.lock().set_choices(
+        devices,
+        plugins.into_iter().map(|plugin| plugin.id).collect(),
+    );
+}
+
+#[tauri::command]
+fn get_overlay_snapshot(shell: State<'_, Shell>) -> OverlaySnapshot {
+    shell.overlay.lock().snapshot()
+}
+
+#[tauri::command]
+fn set_overlay_expanded(app: tauri::AppHandle, expanded: bool) -> OverlaySnapshot {
+    update_overlay(&app, "expanded", |m| m.set_expanded(expanded))
+}
+
+#[tauri::command]
+async fn start_pipeline(
+    app: tauri::AppHandle,
+    shell: State<'_, Shell>,
+) -> Result<OverlaySnapshot, String> {
+    let mut pipeline = shell.pipeline.lock().await;
+    if pipeline.is_some() {
+        return Ok(shell.overlay.lock().snapshot());
+    }
+
+    let options = {
+        let settings = shell.settings.lock();
+        coldvox_runtime::AppRuntimeOptions {
+            injection: Some(settings.settings().injection.runtime_options()),
+            ..settings.settings().runtime_options()
+        }
+    };
+    let handle = match coldvox_runtime::runtime::start(options).await {
+        Ok(handle) => Arc::new(handle),
+        Err(e) => {
+            let message = format!("Pipeline failed to start: {}", e);
+            return Ok(update_overlay(&app, "start-failed", |m| {
+                m.apply_pipeline_error(&message)
+            }));
+        }
+    };
+    let forwarders = spawn_forwarders(&app, &handle);
+    *pipeline = Some(Pipeline { handle, forwarders });
+    drop(pipeline);
+
+    let settings = {
+        let mut settings = shell.settings.lock();
+        settings.set_pipeline_running(true);
+        settings.snapshot()
+    };
+    emit_settings(&app, "pipeline-started", &settings);
+    Ok(update_overlay(&app, "started", OverlayModel::start_capture))
+}
+
+#[tauri::command]
+async fn toggle_pause_state(
+    app: tauri::AppHandle,
+    shell: State<'_, Shell>,
+) -> Result<OverlaySnapshot, String> {
+    let paused = shell
+        .pipeline
+        .lock()
+        .await
+        .as_ref()
+        .map(|pipeline| pipeline.handle.toggle_pause());
+    Ok(update_overlay(&app, "pause", |m| match paused {
+        Some(paused) => m.set_paused(paused),
+        // Rejected: nothing is running
+        None => m.set_paused(!m.is_paused()),
+    }))
+}
+
+#[tauri::command]
+async fn stop_pipeline(
+    app: tauri::AppHandle,
+    shell: State<'_, Shell>,
+) -> Result<OverlaySnapshot, String> {
+    let pipeline = shell.pipeline.lock().await.take();
+    if let Some(pipeline) = pipeline {
+        for forwarder in pipeline.forwarders {
+            forwarder.abort();
+        }
+        pipeline.handle.shutdown().await;
+
+        let settings = {
+            let mut settings = shell.settings.lock();
+            settings.set_pipeline_running(false);
+            settings.snapshot()
+        };
+        emit_settings(&app, "pipeline-stopped", &settings);
+    }
+    Ok(update_overlay(&app, "stopped", OverlayModel::stop))
+}
+
+#[tauri::command]
+fn clear_overlay_transcript(app: tauri::AppHandle) -> OverlaySnapshot {
+    update_overlay(&app, "cleared", OverlayModel::clear)
+}
+
+// Async: creating a window from a synchronous command deadlocks on Windows
+#[tauri::command]
+async fn open_settings(
+    app: tauri::AppHandle,
+    shell: State<'_, Shell>,
+) -> Result<OverlaySnapshot, String> {
+    match app.get_webview_window(SETTINGS_WINDOW_LABEL) {
+        Some(window) => window.set_focus().map_err(|e| e.to_string())?,
+        None => {
+            WebviewWindowBuilder::new(
+                &app,
+                SETTINGS_WINDOW_LABEL,
+                WebviewUrl::App("index.html?view=settings".into()),
+            )
+            .title("ColdVox Settings")
+            .inner_size(480.0, 560.0)
+            .build()
+            .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(shell.overlay.lock().snapshot())
+}
+
+#[tauri::command]
+fn update_partial_transcript(app: tauri::AppHandle, text: String) -> OverlaySnapshot {
+    update_overlay(&app, "partial", |m| m.apply_partial_transcript(&text, None))
+}
+
+#[tauri::command]
+fn update_final_transcript(app: tauri::AppHandle, text: String) -> OverlaySnapshot {
+    update_overlay(&app, "final", |m| m.apply_final_transcript(&text, None))
+}
+
+#[tauri::command]
+fn set_overlay_processing(app: tauri::AppHandle) -> OverlaySnapshot {
+    update_overlay(&app, "processing", |m| m.apply_processing_state(None))
+}
+
+#[tauri::command]
+fn set_overlay_listening(app: tauri::AppHandle) -> OverlaySnapshot {
+    update_overlay(&app, "listening", |m| m.apply_listening_state(None))
+}
+
+#[tauri::command]
+fn stop_overlay_capture(app: tauri::AppHandle) -> OverlaySnapshot {
+    update_overlay(&app, "stopped", OverlayModel::stop_capture)
+}
+
+#[tauri::command]
+async fn get_settings(shell: State<'_, Shell>) -> Result<SettingsSnapshot, String> {
+    refresh_choices(&shell).await;
+    Ok(shell.settings.lock().snapshot())
+}
+
+#[tauri::command]
+async fn apply_settings(
+    app: tauri::AppHandle,
+    shell: State<'_, Shell>,
+    form: SettingsForm,
+) -> Result<SettingsSnapshot, String> {
+    let applied = shell.settings.lock().apply(form);
+    let live = match applied {
+        Ok(live) => live,
+        Err(e) => {
+            let snapshot = {
+                let mut settings = shell.settings.lock();
+                settings.record_error(e);
+                settings.snapshot()
+            };
+            emit_settings(&app, "rejected", &snapshot);
+            return Ok(snapshot);
+        }
+    };
+
+    let mut errors = Vec::new();
+    if let Some(pipeline) = shell.pipeline.lock().await.as_ref() {
+        let handle = &pipeline.handle;
+        if let Some(device) = &live.device {
+            handle.set_device(device);
+        }
+        if let Some(mode) = live.activation_mode {
+            if let Err(e) = handle.set_activation_mode(mode).await {
+                errors.push(format!("Activation mode not switched: {}", e));
+            }
+        }
+        if let (Some(plugin), Some(manager)) = (&live.stt_plugin, &handle.plugin_manager) {
+            if let Err(e) = manager.write().await.switch_plugin(plugin).await {
+                errors.push(format!("STT plugin not switched: {}", e));
+            }
+        }
+    }
+
+    let snapshot = {
+        let mut settings = shell.settings.lock();
+        match settings.save() {
+            Ok(path) => settings.record_saved(&path),
+            Err(e) => errors.push(e),
+        }
+        if !errors.is_empty() {
+            settings.record_error(errors.join("\n"));
+        }
+        settings.snapshot()
+    };
+    emit_settings(&app, "applied", &snapshot);
+    Ok(snapshot)
+}
+
 pub fn run() {
-    println!("ColdVox GUI stub smoke: Windows GUI is out of scope for this wave.");
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
+
+    let settings = Settings::new().unwrap_or_else(|e| {
+        tracing::warn!("Using default settings: {}", e);
+        Settings::default()
+    });
+
+    tauri::Builder::default()
+        .manage(Shell::new(settings))
+        .invoke_handler(tauri::generate_handler![
+            get_overlay_snapshot,
+            set_overlay_expanded,
+            start_pipeline,
+            toggle_pause_state,
+            stop_pipeline,
+            clear_overlay_transcript,
+            open_settings,
+            update_partial_transcript,
+            update_final_transcript,
+            set_overlay_processing,
+            set_overlay_listening,
+            stop_overlay_capture,
+            get_settings,
+            apply_settings,
+        ])
+        .build(tauri::generate_context!())
+        .expect("error while building the ColdVox overlay")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                let shell = app.state::<Shell>();
+                let pipeline = tauri::async_runtime::block_on(shell.pipeline.lock()).take();
+                if let Some(pipeline) = pipeline {
+                    tauri::async_runtime::block_on(pipeline.handle.shutdown());
+                }
+            }
+        });
 }
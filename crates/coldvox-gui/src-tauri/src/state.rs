@@ -1,9 +1,17 @@
-use crate::contract::{OverlaySnapshot, OverlayStatus};
-use crate::demo::DemoStep;
+use std::path::{Path, PathBuf};
+
+use coldvox_app::Settings;
+use coldvox_runtime::{ActivationMode, VadEvent};
+
+use crate::contract::{
+    ActivationChoice, InjectionForm, OverlaySnapshot, OverlayStatus, SettingsForm, SettingsSnapshot,
+};
 
 #[derive(Debug, Default)]
 pub struct OverlayModel {
     snapshot: OverlaySnapshot,
+    /// A pipeline is running
+    running: bool,
 }
 
 impl OverlayModel {
@@ -11,12 +19,6 @@ impl OverlayModel {
         self.snapshot.clone()
     }
 
-    pub fn set_status(&mut self, status: OverlayStatus, detail: String) -> OverlaySnapshot {
-        self.snapshot.status = status;
-        self.snapshot.status_detail = detail;
-        self.snapshot()
-    }
-
     pub fn is_paused(&self) -> bool {
         self.snapshot.paused
     }
@@ -32,87 +34,85 @@ impl OverlayModel {
         self.snapshot()
     }
 
-    pub fn update_partial(&mut self, text: String) -> OverlaySnapshot {
-        self.snapshot.status = OverlayStatus::Listening;
-        self.snapshot.partial_transcript = text;
-        self.snapshot.status_detail = "Live transcription...".to_string();
-        self.snapshot()
-    }
-
-    pub fn update_final(&mut self, text: String) -> OverlaySnapshot {
-        self.snapshot.status = OverlayStatus::Ready;
-        self.snapshot.partial_transcript.clear();
-        self.snapshot.final_transcript = text;
-        self.snapshot.status_detail = "Transcription complete.".to_string();
-        self.snapshot()
-    }
-
-    pub fn reset_to_idle(&mut self, detail: String) -> OverlaySnapshot {
-        self.snapshot.status = OverlayStatus::Idle;
-        self.snapshot.status_detail = detail;
-        self.snapshot.partial_transcript.clear();
+    /// The pipeline started; wait for speech
+    pub fn start_capture(&mut self) -> OverlaySnapshot {
+        self.running = true;
+        self.snapshot.speech_active = false;
         self.snapshot.error_message = None;
-        self.snapshot()
+        self.apply_listening_state(Some("Pipeline running. Waiting for speech."))
     }
 
-    pub fn toggle_pause(&mut self) -> OverlaySnapshot {
-        if self.snapshot.status != OverlayStatus::Listening {
+    /// Mirror the pipeline's paused state
+    pub fn set_paused(&mut self, paused: bool) -> OverlaySnapshot {
+        if !self.running {
             return self.reject_command(
-                "Pause/resume is only available during the listening demo phase.",
-                "Pause is a placeholder seam until real capture wiring lands.",
+                "Pause/resume needs a running pipeline.",
+                "Start the pipeline, then pause it from here.",
             );
         }
 
-        self.snapshot.paused = !self.snapshot.paused;
-        self.snapshot.status_detail = if self.snapshot.paused {
-            "Demo paused. Resume when you want the partial stream to continue.".to_string()
+        self.snapshot.paused = paused;
+        self.snapshot.speech_active = false;
+        self.snapshot.status_detail = if paused {
+            "Paused. Nothing is transcribed until you resume.".to_string()
         } else {
-            "Listening for provisional words from the demo driver.".to_string()
+            "Listening for speech.".to_string()
         };
         self.snapshot.error_message = None;
         self.snapshot()
     }
 
     pub fn stop(&mut self) -> OverlaySnapshot {
-        if self.snapshot.status != OverlayStatus::Listening
-            && self.snapshot.status != OverlayStatus::Processing
-        {
+        if !self.running {
             return self.reject_command(
                 "Nothing is active to stop.",
-                "Stop only applies while the demo is simulating live capture.",
+                "Stop only applies while the pipeline is running.",
             );
         }
 
-        self.demo_token += 1;
-        self.snapshot.status = OverlayStatus::Idle;
-        self.snapshot.paused = false;
-        self.snapshot.partial_transcript.clear();
-        self.snapshot.status_detail =
-            "Capture stopped. Expand again or rerun the demo when ready.".to_string();
-        self.snapshot.error_message = None;
-        self.snapshot()
+        self.stop_capture()
     }
 
     pub fn clear(&mut self) -> OverlaySnapshot {
-        self.demo_token += 1;
         let expanded = self.snapshot.expanded;
+        let paused = self.snapshot.paused;
+        let speech_active = self.snapshot.speech_active;
         self.snapshot = OverlaySnapshot {
             expanded,
-            status_detail: "Transcript cleared. Demo seam remains ready.".to_string(),
+            paused,
+            speech_active,
+            status: if self.running {
+                OverlayStatus::Listening
+            } else {
+                OverlayStatus::Idle
+            },
+            status_detail: "Transcript cleared.".to_string(),
             ..OverlaySnapshot::default()
         };
         self.snapshot()
     }
 
-    pub fn open_settings_placeholder(&mut self) -> OverlaySnapshot {
-        self.snapshot.expanded = true;
-        self.snapshot.status_detail =
-            "Settings stay out of scope in this tranche, but the command seam is in place."
-                .to_string();
-        if self.snapshot.status != OverlayStatus::Error {
-            self.snapshot.error_message = None;
+    /// Drive the listening indicator from VAD: speech start shows the
+    /// overlay as listening, speech end as processing until the final
+    /// transcript arrives
+    pub fn apply_vad_event(&mut self, event: &VadEvent) -> OverlaySnapshot {
+        match event {
+            VadEvent::SpeechStart { .. } => {
+                self.snapshot.speech_active = true;
+                self.apply_listening_state(Some("Speech detected."))
+            }
+            VadEvent::SpeechEnd { .. } => {
+                self.snapshot.speech_active = false;
+                self.apply_processing_state(None)
+            }
         }
-        self.snapshot()
+    }
+
+    /// The pipeline failed or stopped on its own
+    pub fn apply_pipeline_error(&mut self, message: &str) -> OverlaySnapshot {
+        self.running = false;
+        self.snapshot.speech_active = false;
+        self.reject_command(message, "The pipeline is not running.")
     }
 
     /// Apply a live partial transcript update from the STT pipeline.
@@ -150,9 +150,7 @@ impl OverlayModel {
         if let Some(detail) = status_detail {
             self.snapshot.status_detail = detail.to_string();
         } else {
-            self.snapshot.status_detail =
-                "Final transcript staged. Real injection wiring lands in a later tranche."
-                    .to_string();
+            self.snapshot.status_detail = "Final transcript committed.".to_string();
         }
         self.snapshot.error_message = None;
         self.snapshot.expanded = true;
@@ -189,21 +187,19 @@ impl OverlayModel {
     }
 
     /// Stop capture and return to Idle, clearing all transcript state.
-    /// Unlike `stop()` which increments the demo token, this is used by the real pipeline.
     pub fn stop_capture(&mut self) -> OverlaySnapshot {
-        self.demo_token += 1;
+        self.running = false;
         self.snapshot.status = OverlayStatus::Idle;
         self.snapshot.paused = false;
+        self.snapshot.speech_active = false;
         self.snapshot.partial_transcript.clear();
         self.snapshot.final_transcript.clear();
-        self.snapshot.status_detail =
-            "Capture stopped. The seam is ready for the next session.".to_string();
+        self.snapshot.status_detail = "Capture stopped.".to_string();
         self.snapshot.error_message = None;
         self.snapshot()
     }
 
-    fn reject_command(&mut self, message: &str, detail: &str) -> OverlaySnapshot {
-        self.demo_token += 1;
+    pub fn reject_command(&mut self, message: &str, detail: &str) -> OverlaySnapshot {
         self.snapshot.expanded = true;
         self.snapshot.status = OverlayStatus::Error;
         self.snapshot.paused = false;
@@ -213,10 +209,244 @@ impl OverlayModel {
     }
 }
 
+/// Settings changes the running pipeline can apply without a restart
+#[derive(Debug, Default, PartialEq)]
+pub struct LiveChanges {
+    pub device: Option<String>,
+    pub activation_mode: Option<ActivationMode>,
+    pub stt_plugin: Option<String>,
+}
+
+/// The settings window's view of [`Settings`]
+#[derive(Debug)]
+pub struct SettingsModel {
+    settings: Settings,
+    config_path: Option<PathBuf>,
+    devices: Vec<String>,
+    stt_plugins: Vec<String>,
+    pipeline_running: bool,
+    restart_required: bool,
+    status_detail: String,
+    error_message: Option<String>,
+}
+
+impl SettingsModel {
+    pub fn new(settings: Settings, config_path: Option<PathBuf>) -> Self {
+        Self {
+            settings,
+            config_path,
+            devices: Vec::new(),
+            stt_plugins: Vec::new(),
+            pipeline_running: false,
+            restart_required: false,
+            status_detail: "Changes are saved to your config file.".to_string(),
+            error_message: None,
+        }
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    pub fn form(&self) -> SettingsForm {
+        let injection = &self.settings.injection;
+        SettingsForm {
+            device: self.settings.device.clone(),
+            activation_mode: match self.settings.runtime_activation_mode() {
+                ActivationMode::Hotkey => ActivationChoice::Hotkey,
+                _ => ActivationChoice::Vad,
+            },
+            stt_plugin: self.settings.stt.preferred.clone(),
+            injection: InjectionForm {
+                voice_commands: injection.voice_commands,
+                adaptive_pacing: injection.adaptive_pacing,
+                inject_on_unknown_focus: injection.inject_on_unknown_focus,
+                notify_on_failure: injection.notify_on_failure,
+            },
+        }
+    }
+
+    pub fn snapshot(&self) -> SettingsSnapshot {
+        SettingsSnapshot {
+            form: self.form(),
+            devices: self.devices.clone(),
+            stt_plugins: self.stt_plugins.clone(),
+            pipeline_running: self.pipeline_running,
+            restart_required: self.restart_required,
+            config_path: self
+                .config_path
+                .as_ref()
+                .map(|path| path.display().to_string()),
+            status_detail: self.status_detail.clone(),
+            error_message: self.error_message.clone(),
+        }
+    }
+
+    pub fn set_choices(&mut self, devices: Vec<String>, stt_plugins: Vec<String>) {
+        self.devices = devices;
+        self.stt_plugins = stt_plugins;
+    }
+
+    pub fn set_pipeline_running(&mut self, running: bool) {
+        self.pipeline_running = running;
+        // A fresh start reads every setting
+        self.restart_required = false;
+    }
+
+    /// Take over `form`; returns what a running pipeline can switch to
+    /// live. Everything else is flagged as needing a restart.
+    pub fn apply(&mut self, form: SettingsForm) -> Result<LiveChanges, String> {
+        if let Some(plugin) = &form.stt_plugin {
+            if !self.stt_plugins.is_empty() && !self.stt_plugins.contains(plugin) {
+                return Err(format!("Unknown STT plugin '{}'", plugin));
+            }
+        }
+
+        let previous = self.form();
+        let mut live = LiveChanges::default();
+        let mut restart = previous.injection != form.injection;
+
+        if form.device != previous.device {
+            // There is no live switch back to the host default
+            match &form.device {
+                Some(name) => live.device = Some(name.clone()),
+                None => restart = true,
+            }
+        }
+        if form.activation_mode != previous.activation_mode {
+            live.activation_mode = Some(match form.activation_mode {
+                ActivationChoice::Vad => ActivationMode::Vad,
+                ActivationChoice::Hotkey => ActivationMode::Hotkey,
+            });
+        }
+        if form.stt_plugin != previous.stt_plugin {
+            match &form.stt_plugin {
+                Some(id) => live.stt_plugin = Some(id.clone()),
+                None => restart = true,
+            }
+        }
+
+        self.settings.device = form.device;
+        self.settings.activation_mode = match form.activation_mode {
+            ActivationChoice::Vad => "vad",
+            ActivationChoice::Hotkey => "hotkey",
+        }
+        .to_string();
+        self.settings.stt.preferred = form.stt_plugin;
+        let injection = &mut self.settings.injection;
+        injection.voice_commands = form.injection.voice_commands;
+        injection.adaptive_pacing = form.injection.adaptive_pacing;
+        injection.inject_on_unknown_focus = form.injection.inject_on_unknown_focus;
+        injection.notify_on_failure = form.injection.notify_on_failure;
+
+        self.restart_required |= self.pipeline_running && restart;
+        Ok(live)
+    }
+
+    /// Write the edited keys into the config file, keeping everything else
+    /// in it
+    pub fn save(&self) -> Result<PathBuf, String> {
+        let path = self
+            .config_path
+            .clone()
+            .ok_or("No config location found; set XDG_CONFIG_HOME")?;
+        let mut table = match std::fs::read_to_string(&path) {
+            Ok(text) => text
+                .parse::<toml::Table>()
+                .map_err(|e| format!("Cannot parse {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => toml::Table::new(),
+            Err(e) => return Err(format!("Cannot read {}: {}", path.display(), e)),
+        };
+        self.write_overrides(&mut table);
+        write_config(&path, &table)?;
+        Ok(path)
+    }
+
+    /// Set the keys the settings window edits; unset ones are removed so
+    /// lower config layers apply again
+    pub fn write_overrides(&self, table: &mut toml::Table) {
+        use toml::Value;
+
+        let settings = &self.settings;
+        let injection = &settings.injection;
+        set_key(table, &["device"], settings.device.clone().map(Value::from));
+        set_key(
+            table,
+            &["activation_mode"],
+            Some(Value::from(settings.activation_mode.as_str())),
+        );
+        set_key(
+            table,
+            &["stt", "preferred"],
+            settings.stt.preferred.clone().map(Value::from),
+        );
+        for (key, value) in [
+            ("voice_commands", injection.voice_commands),
+            ("adaptive_pacing", injection.adaptive_pacing),
+            ("inject_on_unknown_focus", injection.inject_on_unknown_focus),
+            ("notify_on_failure", injection.notify_on_failure),
+        ] {
+            set_key(table, &["injection", key], Some(Value::from(value)));
+        }
+    }
+
+    pub fn record_saved(&mut self, path: &Path) {
+        self.status_detail = if self.restart_required {
+            format!(
+                "Saved to {}. Restart the pipeline to apply every change.",
+                path.display()
+            )
+        } else {
+            format!("Saved to {}.", path.display())
+        };
+        self.error_message = None;
+    }
+
+    pub fn record_error(&mut self, message: String) {
+        self.status_detail = "Settings were not applied.".to_string();
+        self.error_message = Some(message);
+    }
+}
+
+fn set_key(table: &mut toml::Table, path: &[&str], value: Option<toml::Value>) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let mut table = table;
+    for key in parents {
+        let entry = table
+            .entry(key.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if !entry.is_table() {
+            *entry = toml::Value::Table(toml::Table::new());
+        }
+        let Some(child) = entry.as_table_mut() else {
+            return;
+        };
+        table = child;
+    }
+    match value {
+        Some(value) => {
+            table.insert(last.to_string(), value);
+        }
+        None => {
+            table.remove(*last);
+        }
+    }
+}
+
+fn write_config(path: &Path, table: &toml::Table) -> Result<(), String> {
+    let text = toml::to_string(table).map_err(|e| e.to_string())?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+    }
+    std::fs::write(path, text).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::demo::demo_script;
 
     #[test]
     fn starts_idle_and_collapsed() {
@@ -241,34 +471,15 @@ mod tests {
     }
 
     #[test]
-    fn demo_script_reaches_ready_state_with_final_text() {
-        let mut model = OverlayModel::default();
-        let (_token, start_snapshot) = model.start_demo();
-
-        assert_eq!(start_snapshot.status, OverlayStatus::Listening);
-
-        let mut last_snapshot = start_snapshot;
-        for step in demo_script() {
-            last_snapshot = model.apply_demo_step(&step);
-        }
-
-        assert_eq!(last_snapshot.status, OverlayStatus::Ready);
-        assert!(last_snapshot.partial_transcript.is_empty());
-        assert!(last_snapshot
-            .final_transcript
-            .contains("Streaming partials"));
-    }
-
-    #[test]
-    fn pause_round_trip_keeps_demo_in_listening_state() {
+    fn pause_round_trip_keeps_pipeline_in_listening_state() {
         let mut model = OverlayModel::default();
-        model.start_demo();
+        model.start_capture();
 
-        let paused = model.toggle_pause();
+        let paused = model.set_paused(true);
         assert_eq!(paused.status, OverlayStatus::Listening);
         assert!(paused.paused);
 
-        let resumed = model.toggle_pause();
+        let resumed = model.set_paused(false);
         assert_eq!(resumed.status, OverlayStatus::Listening);
         assert!(!resumed.paused);
     }
@@ -314,39 +525,151 @@ mod tests {
     }
 
     #[test]
-    fn stop_capture_bumps_demo_token_and_stops_demo_driver() {
+    fn pause_without_a_pipeline_is_rejected() {
         let mut model = OverlayModel::default();
-        let (token, _snap) = model.start_demo();
-        assert_eq!(model.current_demo_token(), token);
+        let snapshot = model.set_paused(true);
 
-        // stop_capture must bump the token so the demo driver exits its loop
-        model.stop_capture();
-        assert_eq!(model.current_demo_token(), token + 1);
+        assert_eq!(snapshot.status, OverlayStatus::Error);
+        assert!(!snapshot.paused);
     }
 
     #[test]
     fn pipeline_transitions_reset_paused_flag() {
         let mut model = OverlayModel::default();
 
-        // Simulate paused state from demo
-        model.start_demo();
-        model.toggle_pause(); // now paused
+        model.start_capture();
+        model.set_paused(true);
         assert!(model.snapshot.paused);
 
         // Any pipeline transition must clear paused so real capture is not stuck
         let snap1 = model.apply_partial_transcript("hello", None);
         assert!(!snap1.paused);
 
-        model.toggle_pause(); // paused again
+        model.set_paused(true);
         let snap2 = model.apply_final_transcript("hello world", None);
         assert!(!snap2.paused);
 
-        model.toggle_pause(); // paused again
+        model.set_paused(true);
         let snap3 = model.apply_processing_state(None);
         assert!(!snap3.paused);
 
-        model.toggle_pause(); // paused again
+        model.set_paused(true);
         let snap4 = model.apply_listening_state(None);
         assert!(!snap4.paused);
     }
+
+    #[test]
+    fn vad_events_drive_the_listening_indicator() {
+        let mut model = OverlayModel::default();
+        model.start_capture();
+        assert!(!model.snapshot().speech_active);
+
+        let speaking = model.apply_vad_event(&VadEvent::SpeechStart {
+            timestamp_ms: 0,
+            energy_db: -20.0,
+        });
+        assert!(speaking.speech_active);
+        assert_eq!(speaking.status, OverlayStatus::Listening);
+
+        let ended = model.apply_vad_event(&VadEvent::SpeechEnd {
+            timestamp_ms: 900,
+            duration_ms: 900,
+            energy_db: -20.0,
+        });
+        assert!(!ended.speech_active);
+        assert_eq!(ended.status, OverlayStatus::Processing);
+
+        model.apply_vad_event(&VadEvent::SpeechStart {
+            timestamp_ms: 1000,
+            energy_db: -20.0,
+        });
+        assert!(!model.stop().speech_active);
+    }
+
+    fn settings_model() -> SettingsModel {
+        let mut model = SettingsModel::new(Settings::default(), None);
+        model.set_choices(
+            vec!["USB Mic".to_string()],
+            vec!["mock".to_string(), "parakeet".to_string()],
+        );
+        model
+    }
+
+    #[test]
+    fn live_settings_switch_without_restart() {
+        let mut model = settings_model();
+        model.set_pipeline_running(true);
+
+        let mut form = model.form();
+        form.device = Some("USB Mic".to_string());
+        form.activation_mode = ActivationChoice::Hotkey;
+        form.stt_plugin = Some("parakeet".to_string());
+        let live = model.apply(form.clone()).unwrap();
+
+        assert_eq!(live.device.as_deref(), Some("USB Mic"));
+        assert_eq!(live.activation_mode, Some(ActivationMode::Hotkey));
+        assert_eq!(live.stt_plugin.as_deref(), Some("parakeet"));
+        assert!(!model.snapshot().restart_required);
+        assert_eq!(model.form(), form);
+        assert_eq!(model.settings().activation_mode, "hotkey");
+    }
+
+    #[test]
+    fn injection_changes_need_a_restart() {
+        let mut model = settings_model();
+        model.set_pipeline_running(true);
+
+        let mut form = model.form();
+        form.injection.voice_commands = !form.injection.voice_commands;
+        assert_eq!(model.apply(form).unwrap(), LiveChanges::default());
+        assert!(model.snapshot().restart_required);
+
+        model.set_pipeline_running(true);
+        assert!(!model.snapshot().restart_required);
+    }
+
+    #[test]
+    fn unknown_plugin_is_rejected() {
+        let mut model = settings_model();
+        let mut form = model.form();
+        form.stt_plugin = Some("nope".to_string());
+
+        assert!(model.apply(form).is_err());
+        assert_eq!(model.settings().stt.preferred, None);
+    }
+
+    #[test]
+    fn overrides_keep_unrelated_keys() {
+        let mut model = settings_model();
+        let mut form = model.form();
+        form.device = Some("USB Mic".to_string());
+        model.apply(form.clone()).unwrap();
+
+        let mut table: toml::Table = "resampler_quality = \"fast\"\n[stt]\nlanguage = \"de\"\n"
+            .parse()
+            .unwrap();
+        model.write_overrides(&mut table);
+        assert_eq!(table["resampler_quality"].as_str(), Some("fast"));
+        assert_eq!(table["stt"]["language"].as_str(), Some("de"));
+        assert_eq!(table["device"].as_str(), Some("USB Mic"));
+
+        form.device = None;
+        model.apply(form).unwrap();
+        model.write_overrides(&mut table);
+        assert!(!table.contains_key("device"));
+    }
+
+    #[test]
+    fn save_creates_the_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("coldvox").join("config.toml");
+        let mut model = SettingsModel::new(Settings::default(), Some(path.clone()));
+        let mut form = model.form();
+        form.activation_mode = ActivationChoice::Hotkey;
+        model.apply(form).unwrap();
+
+        assert_eq!(model.save().unwrap(), path);
+        let saved: toml::Table = std::fs::read_to_string(&path).unwrap().parse().unwrap();
+        assert_eq!(saved["activation_mode"].as_str(), Some("hotkey"));
+    }
 }
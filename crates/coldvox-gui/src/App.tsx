@@ -1,7 +1,9 @@
 import { OverlayShell } from "./components/OverlayShell";
+import { SettingsPanel } from "./components/SettingsPanel";
 import { useOverlayShell } from "./hooks/useOverlayShell";
+import { useSettingsPanel } from "./hooks/useSettingsPanel";
 
-function App() {
+function OverlayApp() {
   const {
     snapshot,
     clearTranscript,
@@ -17,7 +19,7 @@ function App() {
       <OverlayShell
         snapshot={snapshot}
         onSetExpanded={setExpanded}
-        onStartDemo={startPipeline}
+        onStart={startPipeline}
         onTogglePause={togglePause}
         onStop={stopPipeline}
         onClear={clearTranscript}
@@ -27,4 +29,24 @@ function App() {
   );
 }
 
+function SettingsApp() {
+  const { snapshot, bridgeError, apply } = useSettingsPanel();
+
+  if (!snapshot) {
+    return (
+      <main className="settings-panel">
+        <p>{bridgeError ?? "Loading settings..."}</p>
+      </main>
+    );
+  }
+
+  return <SettingsPanel snapshot={snapshot} onApply={apply} />;
+}
+
+// The host opens the settings window on `index.html?view=settings`
+function App() {
+  const view = new URLSearchParams(window.location.search).get("view");
+  return view === "settings" ? <SettingsApp /> : <OverlayApp />;
+}
+
 export default App;
@@ -20,7 +20,7 @@ describe("OverlayShell", () => {
           finalTranscript: "final words stay dominant",
         })}
         onSetExpanded={vi.fn().mockResolvedValue(undefined)}
-        onStartDemo={vi.fn().mockResolvedValue(undefined)}
+        onStart={vi.fn().mockResolvedValue(undefined)}
         onTogglePause={vi.fn().mockResolvedValue(undefined)}
         onStop={vi.fn().mockResolvedValue(undefined)}
         onClear={vi.fn().mockResolvedValue(undefined)}
@@ -47,7 +47,7 @@ describe("OverlayShell", () => {
       <OverlayShell
         snapshot={createSnapshot({ status: "processing" })}
         onSetExpanded={vi.fn().mockResolvedValue(undefined)}
-        onStartDemo={vi.fn().mockResolvedValue(undefined)}
+        onStart={vi.fn().mockResolvedValue(undefined)}
         onTogglePause={onTogglePause}
         onStop={onStop}
         onClear={onClear}
@@ -5,7 +5,7 @@ import { StatusPill } from "./StatusPill";
 interface OverlayShellProps {
   snapshot: OverlaySnapshot;
   onSetExpanded: (expanded: boolean) => Promise<void>;
-  onStartDemo: () => Promise<void>;
+  onStart: () => Promise<void>;
   onTogglePause: () => Promise<void>;
   onStop: () => Promise<void>;
   onClear: () => Promise<void>;
@@ -16,14 +16,14 @@ const STATE_NOTES = {
   idle: "Collapsed idle presence with an explicit expansion path.",
   listening: "Streaming partial text remains visible while speech is active.",
   processing: "Partial words stay nearby while the shell stages a final result.",
-  ready: "Final text is visually promoted, but nothing is injected yet.",
+  ready: "Final text is committed and handed to injection.",
   error: "Command rejection or shell issues surface without hiding prior text.",
 } as const;
 
 export function OverlayShell({
   snapshot,
   onSetExpanded,
-  onStartDemo,
+  onStart,
   onTogglePause,
   onStop,
   onClear,
@@ -50,7 +50,7 @@ export function OverlayShell({
   }, [snapshot.finalTranscript, snapshot.partialTranscript, snapshot.statusDetail]);
 
   const pauseLabel = snapshot.paused ? "Resume" : "Pause";
-  const showRunDemo =
+  const showStart =
     snapshot.status === "idle" ||
     snapshot.status === "ready" ||
     snapshot.status === "error";
@@ -71,7 +71,7 @@ export function OverlayShell({
           <span className="collapsed-shell__detail">{collapsedSummary}</span>
         </div>
         <div className="collapsed-shell__status">
-          <StatusPill status={snapshot.status} />
+          <StatusPill status={snapshot.status} speechActive={snapshot.speechActive} />
           <span className="collapsed-shell__action">Expand</span>
         </div>
       </button>
@@ -88,7 +88,7 @@ export function OverlayShell({
         </div>
 
         <div className="header-column header-column--status">
-          <StatusPill status={snapshot.status} />
+          <StatusPill status={snapshot.status} speechActive={snapshot.speechActive} />
           <span className="status-caption">{STATE_NOTES[snapshot.status]}</span>
         </div>
 
@@ -124,10 +124,10 @@ export function OverlayShell({
           </div>
 
           <div className="signal-rail__section">
-            <span className="signal-rail__label">Scope</span>
+            <span className="signal-rail__label">Pipeline</span>
             <p>
-              Demo only. No STT runtime, hotkeys, injection, or packaging work in
-              this tranche.
+              Start runs the ColdVox pipeline with your saved settings. Voice
+              activity lights the indicator while you speak.
             </p>
           </div>
         </aside>
@@ -150,7 +150,7 @@ export function OverlayShell({
               <span className="transcript-block__label">Final text</span>
               <p data-testid="final-transcript">
                 {snapshot.finalTranscript ||
-                  "Final transcript will land here once the demo promotes it out of the partial stream."}
+                  "Final transcript will land here once an utterance is committed."}
               </p>
             </section>
 
@@ -167,15 +167,15 @@ export function OverlayShell({
 
       <footer className="overlay-footer">
         <div className="overlay-footer__group overlay-footer__group--primary">
-          {showRunDemo ? (
+          {showStart ? (
             <button
               type="button"
               className="control-button control-button--primary"
               onClick={() => {
-                void onStartDemo();
+                void onStart();
               }}
             >
-              Run demo
+              Start
             </button>
           ) : null}
           <button
@@ -0,0 +1,66 @@
+import { fireEvent, render, screen } from "@testing-library/react";
+import { SettingsPanel } from "./SettingsPanel";
+import { type SettingsSnapshot } from "../contracts/settings";
+
+function createSnapshot(overrides: Partial<SettingsSnapshot> = {}): SettingsSnapshot {
+  return {
+    form: {
+      device: null,
+      activationMode: "vad",
+      sttPlugin: null,
+      injection: {
+        voiceCommands: true,
+        adaptivePacing: false,
+        injectOnUnknownFocus: true,
+        notifyOnFailure: true,
+      },
+    },
+    devices: ["USB Mic"],
+    sttPlugins: ["mock", "parakeet"],
+    pipelineRunning: true,
+    restartRequired: false,
+    configPath: "/home/user/.config/coldvox/config.toml",
+    statusDetail: "Changes are saved to your config file.",
+    errorMessage: null,
+    ...overrides,
+  };
+}
+
+describe("SettingsPanel", () => {
+  it("submits the edited form", () => {
+    const onApply = vi.fn().mockResolvedValue(undefined);
+    render(<SettingsPanel snapshot={createSnapshot()} onApply={onApply} />);
+
+    fireEvent.change(screen.getByLabelText("Input device"), {
+      target: { value: "USB Mic" },
+    });
+    fireEvent.change(screen.getByLabelText("Activation mode"), {
+      target: { value: "hotkey" },
+    });
+    fireEvent.change(screen.getByLabelText("STT plugin"), {
+      target: { value: "parakeet" },
+    });
+    fireEvent.click(screen.getByRole("button", { name: "Save" }));
+
+    expect(onApply).toHaveBeenCalledWith(
+      expect.objectContaining({
+        device: "USB Mic",
+        activationMode: "hotkey",
+        sttPlugin: "parakeet",
+      }),
+    );
+  });
+
+  it("asks for a restart when saved changes are not live", () => {
+    render(
+      <SettingsPanel
+        snapshot={createSnapshot({ restartRequired: true })}
+        onApply={vi.fn().mockResolvedValue(undefined)}
+      />,
+    );
+
+    expect(screen.getByTestId("settings-detail")).toHaveTextContent(
+      "Restart the pipeline",
+    );
+  });
+});
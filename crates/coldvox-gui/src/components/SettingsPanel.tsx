@@ -0,0 +1,135 @@
+import { useEffect, useState } from "react";
+import {
+  type ActivationChoice,
+  type InjectionForm,
+  type SettingsForm,
+  type SettingsSnapshot,
+} from "../contracts/settings";
+
+interface SettingsPanelProps {
+  snapshot: SettingsSnapshot;
+  onApply: (form: SettingsForm) => Promise<void>;
+}
+
+const INJECTION_OPTIONS: { key: keyof InjectionForm; label: string }[] = [
+  { key: "voiceCommands", label: "Voice commands (\"new line\", \"scratch that\")" },
+  { key: "adaptivePacing", label: "Learn a typing speed per application" },
+  { key: "injectOnUnknownFocus", label: "Inject when the focused field is unknown" },
+  { key: "notifyOnFailure", label: "Notify when dictation cannot be delivered" },
+];
+
+export function SettingsPanel({ snapshot, onApply }: SettingsPanelProps) {
+  const [form, setForm] = useState<SettingsForm>(snapshot.form);
+
+  // Follow changes saved from elsewhere
+  useEffect(() => {
+    setForm(snapshot.form);
+  }, [snapshot.form]);
+
+  const devices =
+    form.device && !snapshot.devices.includes(form.device)
+      ? [form.device, ...snapshot.devices]
+      : snapshot.devices;
+  const plugins =
+    form.sttPlugin && !snapshot.sttPlugins.includes(form.sttPlugin)
+      ? [form.sttPlugin, ...snapshot.sttPlugins]
+      : snapshot.sttPlugins;
+
+  return (
+    <form
+      className="settings-panel"
+      aria-label="ColdVox settings"
+      onSubmit={(event) => {
+        event.preventDefault();
+        void onApply(form);
+      }}
+    >
+      <fieldset>
+        <legend>Input device</legend>
+        <select
+          aria-label="Input device"
+          value={form.device ?? ""}
+          onChange={(event) => {
+            setForm({ ...form, device: event.target.value || null });
+          }}
+        >
+          <option value="">System default</option>
+          {devices.map((device) => (
+            <option key={device} value={device}>
+              {device}
+            </option>
+          ))}
+        </select>
+      </fieldset>
+
+      <fieldset>
+        <legend>Activation</legend>
+        <select
+          aria-label="Activation mode"
+          value={form.activationMode}
+          onChange={(event) => {
+            setForm({ ...form, activationMode: event.target.value as ActivationChoice });
+          }}
+        >
+          <option value="vad">Voice activation</option>
+          <option value="hotkey">Push-to-talk hotkey</option>
+        </select>
+      </fieldset>
+
+      <fieldset>
+        <legend>Speech-to-text</legend>
+        <select
+          aria-label="STT plugin"
+          value={form.sttPlugin ?? ""}
+          onChange={(event) => {
+            setForm({ ...form, sttPlugin: event.target.value || null });
+          }}
+        >
+          <option value="">Automatic</option>
+          {plugins.map((plugin) => (
+            <option key={plugin} value={plugin}>
+              {plugin}
+            </option>
+          ))}
+        </select>
+      </fieldset>
+
+      <fieldset>
+        <legend>Text injection</legend>
+        {INJECTION_OPTIONS.map(({ key, label }) => (
+          <label key={key} className="settings-panel__checkbox">
+            <input
+              type="checkbox"
+              checked={form.injection[key]}
+              onChange={(event) => {
+                setForm({
+                  ...form,
+                  injection: { ...form.injection, [key]: event.target.checked },
+                });
+              }}
+            />
+            {label}
+          </label>
+        ))}
+        <p className="settings-panel__note">
+          Injection changes apply the next time the pipeline starts.
+        </p>
+      </fieldset>
+
+      {snapshot.errorMessage ? (
+        <div className="error-badge" role="alert">
+          {snapshot.errorMessage}
+        </div>
+      ) : null}
+      <p className="settings-panel__note" data-testid="settings-detail">
+        {snapshot.restartRequired
+          ? "Restart the pipeline to apply every saved change."
+          : snapshot.statusDetail}
+      </p>
+
+      <button type="submit" className="control-button control-button--primary">
+        Save
+      </button>
+    </form>
+  );
+}
@@ -8,11 +8,19 @@ const LABELS: Record<OverlayStatus, string> = {
   error: "Error",
 };
 
-export function StatusPill({ status }: { status: OverlayStatus }) {
+export function StatusPill({
+  status,
+  speechActive = false,
+}: {
+  status: OverlayStatus;
+  speechActive?: boolean;
+}) {
+  const speaking = speechActive ? " status-pill--speaking" : "";
+
   return (
-    <div className={`status-pill status-pill--${status}`}>
+    <div className={`status-pill status-pill--${status}${speaking}`}>
       <span className="status-pill__dot" aria-hidden="true" />
-      <span>{LABELS[status]}</span>
+      <span>{speechActive ? "Hearing speech" : LABELS[status]}</span>
     </div>
   );
 }
@@ -11,6 +11,8 @@ export interface OverlaySnapshot {
   expanded: boolean;
   status: OverlayStatus;
   paused: boolean;
+  /** VAD reports speech right now */
+  speechActive: boolean;
   partialTranscript: string;
   finalTranscript: string;
   statusDetail: string;
@@ -26,6 +28,7 @@ export const DEFAULT_SNAPSHOT: OverlaySnapshot = {
   expanded: false,
   status: "idle",
   paused: false,
+  speechActive: false,
   partialTranscript: "",
   finalTranscript: "",
   statusDetail: "Overlay shell ready. Expand to inspect the seam.",
@@ -0,0 +1,33 @@
+export const SETTINGS_EVENT_NAME = "coldvox://settings";
+
+export type ActivationChoice = "vad" | "hotkey";
+
+export interface InjectionForm {
+  voiceCommands: boolean;
+  adaptivePacing: boolean;
+  injectOnUnknownFocus: boolean;
+  notifyOnFailure: boolean;
+}
+
+export interface SettingsForm {
+  device: string | null;
+  activationMode: ActivationChoice;
+  sttPlugin: string | null;
+  injection: InjectionForm;
+}
+
+export interface SettingsSnapshot {
+  form: SettingsForm;
+  devices: string[];
+  sttPlugins: string[];
+  pipelineRunning: boolean;
+  restartRequired: boolean;
+  configPath: string | null;
+  statusDetail: string;
+  errorMessage: string | null;
+}
+
+export interface SettingsEvent {
+  reason: string;
+  snapshot: SettingsSnapshot;
+}
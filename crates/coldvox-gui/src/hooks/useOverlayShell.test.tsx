@@ -9,6 +9,7 @@ const bridgeMocks = vi.hoisted(() => {
     expanded: false,
     status: "idle",
     paused: false,
+    speechActive: false,
     partialTranscript: "",
     finalTranscript: "",
     statusDetail: "Host shell connected.",
@@ -34,9 +35,9 @@ const bridgeMocks = vi.hoisted(() => {
     }),
     stopDemoDriver: vi.fn<() => Promise<OverlaySnapshot>>().mockResolvedValue(idleSnapshot),
     clearOverlayTranscript: vi.fn<() => Promise<OverlaySnapshot>>().mockResolvedValue(idleSnapshot),
-    openSettingsPlaceholder: vi
+    openSettings: vi
       .fn<() => Promise<OverlaySnapshot>>()
-      .mockResolvedValue({ ...idleSnapshot, expanded: true, statusDetail: "Settings later." }),
+      .mockResolvedValue({ ...idleSnapshot, expanded: true }),
     subscribeToOverlayEvents: vi.fn<(callback: (event: OverlayEvent) => void) => Promise<UnlistenFn>>()
       .mockImplementation(async (callback) => {
         listener = callback;
@@ -81,7 +82,7 @@ vi.mock("../lib/overlayBridge", () => ({
   togglePauseState: bridgeMocks.togglePauseState,
   stopDemoDriver: bridgeMocks.stopDemoDriver,
   clearOverlayTranscript: bridgeMocks.clearOverlayTranscript,
-  openSettingsPlaceholder: bridgeMocks.openSettingsPlaceholder,
+  openSettings: bridgeMocks.openSettings,
   subscribeToOverlayEvents: bridgeMocks.subscribeToOverlayEvents,
   updatePartialTranscript: bridgeMocks.updatePartialTranscript,
   updateFinalTranscript: bridgeMocks.updateFinalTranscript,
@@ -6,7 +6,7 @@ import {
 import {
   clearOverlayTranscript,
   getOverlaySnapshot,
-  openSettingsPlaceholder,
+  openSettings,
   setOverlayExpanded,
   startPipeline,
   stopPipeline,
@@ -136,7 +136,7 @@ export function useOverlayShell() {
     togglePause: () => runCommand(togglePauseState),
     stopPipeline: () => runCommand(stopPipeline),
     clearTranscript: () => runCommand(clearOverlayTranscript),
-    openSettings: () => runCommand(openSettingsPlaceholder),
+    openSettings: () => runCommand(openSettings),
     // Pipeline wiring — for real STT integration.
     // queuePartialTranscript debounces rapid partials; flushPartial sends immediately.
     queuePartialTranscript,
@@ -0,0 +1,64 @@
+import { useCallback, useEffect, useState } from "react";
+import { type SettingsForm, type SettingsSnapshot } from "../contracts/settings";
+import {
+  applySettings,
+  getSettings,
+  subscribeToSettingsEvents,
+} from "../lib/settingsBridge";
+
+function messageFromError(error: unknown): string {
+  if (error instanceof Error) {
+    return error.message;
+  }
+
+  if (typeof error === "string") {
+    return error;
+  }
+
+  return "Unknown bridge failure.";
+}
+
+export function useSettingsPanel() {
+  const [snapshot, setSnapshot] = useState<SettingsSnapshot | null>(null);
+  const [bridgeError, setBridgeError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let active = true;
+
+    void getSettings()
+      .then((initialSnapshot) => {
+        if (active) {
+          setSnapshot(initialSnapshot);
+        }
+      })
+      .catch((error: unknown) => {
+        if (active) {
+          setBridgeError(messageFromError(error));
+        }
+      });
+
+    const unlistenPromise = subscribeToSettingsEvents((event) => {
+      if (active) {
+        setSnapshot(event.snapshot);
+      }
+    });
+
+    return () => {
+      active = false;
+      void unlistenPromise.then((unlisten) => {
+        unlisten();
+      });
+    };
+  }, []);
+
+  const apply = useCallback(async (form: SettingsForm) => {
+    try {
+      setSnapshot(await applySettings(form));
+      setBridgeError(null);
+    } catch (error: unknown) {
+      setBridgeError(messageFromError(error));
+    }
+  }, []);
+
+  return { snapshot, bridgeError, apply };
+}
@@ -32,8 +32,8 @@ export function clearOverlayTranscript(): Promise<OverlaySnapshot> {
   return invoke<OverlaySnapshot>("clear_overlay_transcript");
 }
 
-export function openSettingsPlaceholder(): Promise<OverlaySnapshot> {
-  return invoke<OverlaySnapshot>("open_settings_placeholder");
+export function openSettings(): Promise<OverlaySnapshot> {
+  return invoke<OverlaySnapshot>("open_settings");
 }
 
 export function subscribeToOverlayEvents(
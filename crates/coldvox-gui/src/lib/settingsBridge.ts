@@ -0,0 +1,25 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import {
+  SETTINGS_EVENT_NAME,
+  type SettingsEvent,
+  type SettingsForm,
+  type SettingsSnapshot,
+} from "../contracts/settings";
+
+export function getSettings(): Promise<SettingsSnapshot> {
+  return invoke<SettingsSnapshot>("get_settings");
+}
+
+/// Save the form and apply what the running pipeline can switch live.
+export function applySettings(form: SettingsForm): Promise<SettingsSnapshot> {
+  return invoke<SettingsSnapshot>("apply_settings", { form });
+}
+
+export function subscribeToSettingsEvents(
+  onEvent: (event: SettingsEvent) => void,
+): Promise<UnlistenFn> {
+  return listen<SettingsEvent>(SETTINGS_EVENT_NAME, (event) => {
+    onEvent(event.payload);
+  });
+}
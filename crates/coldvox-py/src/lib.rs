@@ -0,0 +1,348 @@
+//! Python bindings for the ColdVox STT pipeline.
+//!
+//! Wraps the daemon's [`SttPluginManager`] so notebooks and scripts select,
+//! load and fail over between plugins exactly as `coldvox` does:
+//!
+//! ```python
+//! import coldvox
+//!
+//! print(coldvox.list_plugins())
+//! t = coldvox.Transcriber(plugin="moonshine")
+//! for chunk in chunks:            # 16 kHz mono int16, bytes or list of ints
+//!     for event in t.feed(chunk):
+//!         print(event.kind, event.text)
+//! print(t.finalize())
+//!
+//! text = t.transcribe(pcm)        # one whole utterance
+//! ```
+//!
+//! Build with `maturin develop --release` (add `--features moonshine` etc. for
+//! the plugins you need).
+
+use coldvox_runtime::stt::plugin_manager::SttPluginManager;
+use coldvox_stt::plugin::PluginSelectionConfig;
+use coldvox_stt::{TranscriptionConfig, TranscriptionEvent};
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+
+/// Sample rate every plugin expects
+pub const SAMPLE_RATE_HZ: u32 = 16_000;
+
+fn runtime_error(e: impl std::fmt::Display) -> PyErr {
+    PyRuntimeError::new_err(e.to_string())
+}
+
+/// Little-endian int16 PCM from `bytes`, or a sequence of ints
+fn samples_from(audio: &Bound<'_, PyAny>) -> PyResult<Vec<i16>> {
+    if let Ok(bytes) = audio.cast::<PyBytes>() {
+        return pcm_from_bytes(bytes.as_bytes());
+    }
+    audio.extract::<Vec<i16>>()
+}
+
+fn pcm_from_bytes(bytes: &[u8]) -> PyResult<Vec<i16>> {
+    if !bytes.len().is_multiple_of(2) {
+        return Err(PyValueError::new_err(
+            "PCM bytes must hold whole 16-bit samples",
+        ));
+    }
+    Ok(bytes
+        .chunks_exact(2)
+        .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+        .collect())
+}
+
+/// One word of a final, with times in seconds from the utterance start
+#[pyclass(module = "coldvox", get_all, frozen, skip_from_py_object)]
+#[derive(Debug, Clone)]
+pub struct Word {
+    pub text: String,
+    pub start: f32,
+    pub end: f32,
+    pub confidence: f32,
+}
+
+/// A partial, final or error from the plugin
+#[pyclass(module = "coldvox", get_all, frozen, skip_from_py_object)]
+#[derive(Debug, Clone)]
+pub struct Event {
+    /// "partial", "final" or "error"
+    pub kind: &'static str,
+    pub utterance_id: Option<u64>,
+    /// Transcript, or the message of an error
+    pub text: String,
+    pub t0: Option<f32>,
+    pub t1: Option<f32>,
+    pub words: Vec<Word>,
+    /// Error code (errors only)
+    pub code: Option<String>,
+    pub source: Option<String>,
+}
+
+#[pymethods]
+impl Event {
+    fn __repr__(&self) -> String {
+        format!("Event(kind={:?}, text={:?})", self.kind, self.text)
+    }
+}
+
+impl From<TranscriptionEvent> for Event {
+    fn from(event: TranscriptionEvent) -> Self {
+        match event {
+            TranscriptionEvent::Partial {
+                utterance_id,
+                text,
+                t0,
+                t1,
+                source,
+            } => Event {
+                kind: "partial",
+                utterance_id: Some(utterance_id),
+                text,
+                t0,
+                t1,
+                words: Vec::new(),
+                code: None,
+                source: Some(source.to_string()),
+            },
+            TranscriptionEvent::Final {
+                utterance_id,
+                text,
+                words,
+                source,
+            } => Event {
+                kind: "final",
+                utterance_id: Some(utterance_id),
+                text,
+                t0: None,
+                t1: None,
+                words: words
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|w| Word {
+                        text: w.text,
+                        start: w.start,
+                        end: w.end,
+                        confidence: w.conf,
+                    })
+                    .collect(),
+                code: None,
+                source: Some(source.to_string()),
+            },
+            TranscriptionEvent::Error { code, message } => Event {
+                kind: "error",
+                utterance_id: None,
+                text: message,
+                t0: None,
+                t1: None,
+                words: Vec::new(),
+                code: Some(code),
+                source: None,
+            },
+        }
+    }
+}
+
+/// Streaming transcriber backed by the ColdVox plugin manager
+#[pyclass(module = "coldvox")]
+pub struct Transcriber {
+    runtime: tokio::runtime::Runtime,
+    manager: SttPluginManager,
+}
+
+impl Transcriber {
+    fn block_on<T: Send>(
+        &mut self,
+        py: Python<'_>,
+        f: impl for<'a> FnOnce(
+                &'a mut SttPluginManager,
+            ) -> std::pin::Pin<
+                Box<dyn std::future::Future<Output = Result<T, String>> + Send + 'a>,
+            > + Send,
+    ) -> PyResult<T> {
+        let Self { runtime, manager } = self;
+        py.detach(|| runtime.block_on(f(manager)))
+            .map_err(runtime_error)
+    }
+}
+
+#[pymethods]
+impl Transcriber {
+    /// `plugin` picks a plugin id (best available when None); `fallbacks`
+    /// are tried in order when it fails. `model_path` overrides the plugin's
+    /// default model.
+    #[new]
+    #[pyo3(signature = (plugin=None, fallbacks=Vec::new(), model_path=None, partials=true, words=false))]
+    fn new(
+        py: Python<'_>,
+        plugin: Option<String>,
+        fallbacks: Vec<String>,
+        model_path: Option<String>,
+        partials: bool,
+        words: bool,
+    ) -> PyResult<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .map_err(runtime_error)?;
+        let selection = PluginSelectionConfig {
+            preferred_plugin: plugin,
+            fallback_plugins: fallbacks,
+            ..Default::default()
+        };
+        let mut config = TranscriptionConfig {
+            enabled: true,
+            streaming: true,
+            partial_results: partials,
+            include_words: words,
+            ..Default::default()
+        };
+        if let Some(path) = model_path {
+            config.model_path = path;
+        }
+        let manager = py.detach(|| {
+            runtime.block_on(async {
+                let mut manager = SttPluginManager::new();
+                manager
+                    .set_selection_config(selection)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                manager.initialize().await.map_err(|e| e.to_string())?;
+                manager.apply_transcription_config(config).await?;
+                Ok::<_, String>(manager)
+            })
+        });
+        Ok(Self {
+            manager: manager.map_err(runtime_error)?,
+            runtime,
+        })
+    }
+
+    /// Id of the plugin in use (changes after a failover)
+    #[getter]
+    fn plugin(&self) -> Option<String> {
+        self.runtime.block_on(self.manager.current_plugin())
+    }
+
+    /// Feed 16 kHz mono int16 audio; returns the events it produced
+    fn feed(&mut self, py: Python<'_>, audio: &Bound<'_, PyAny>) -> PyResult<Vec<Event>> {
+        let samples = samples_from(audio)?;
+        let event = self.block_on(py, move |m| {
+            Box::pin(async move { m.process_audio(&samples).await })
+        })?;
+        Ok(event.into_iter().map(Event::from).collect())
+    }
+
+    /// End the utterance; returns the final, if any
+    fn finalize(&mut self, py: Python<'_>) -> PyResult<Vec<Event>> {
+        let event = self.block_on(py, |m| Box::pin(m.finalize()))?;
+        Ok(event.into_iter().map(Event::from).collect())
+    }
+
+    /// Drop the current utterance and start over
+    fn reset(&mut self, py: Python<'_>) -> PyResult<()> {
+        self.block_on(py, |m| Box::pin(m.reset()))
+    }
+
+    /// Transcribe one whole utterance; returns the final text ("" when the
+    /// plugin heard nothing)
+    fn transcribe(&mut self, py: Python<'_>, audio: &Bound<'_, PyAny>) -> PyResult<String> {
+        let samples = samples_from(audio)?;
+        let events = self.block_on(py, move |m| {
+            Box::pin(async move {
+                m.reset().await?;
+                let mut events = Vec::new();
+                // Same chunking as the live pipeline (512-sample frames)
+                for chunk in samples.chunks(512) {
+                    events.extend(m.process_audio(chunk).await?);
+                }
+                events.extend(m.finalize().await?);
+                Ok(events)
+            })
+        })?;
+        let mut text = None;
+        for event in events {
+            match event {
+                TranscriptionEvent::Final { text: t, .. } => text = Some(t),
+                TranscriptionEvent::Error { code, message } => {
+                    return Err(runtime_error(format!("{}: {}", code, message)))
+                }
+                TranscriptionEvent::Partial { .. } => {}
+            }
+        }
+        Ok(text.unwrap_or_default())
+    }
+}
+
+/// Registered plugins as dicts (id, name, description, is_local,
+/// requires_network, is_available)
+#[pyfunction]
+fn list_plugins(py: Python<'_>) -> PyResult<Vec<Bound<'_, PyDict>>> {
+    let plugins = SttPluginManager::new().list_plugins_sync();
+    plugins
+        .into_iter()
+        .map(|info| {
+            let dict = PyDict::new(py);
+            dict.set_item("id", info.id)?;
+            dict.set_item("name", info.name)?;
+            dict.set_item("description", info.description)?;
+            dict.set_item("is_local", info.is_local)?;
+            dict.set_item("requires_network", info.requires_network)?;
+            dict.set_item("is_available", info.is_available)?;
+            Ok(dict)
+        })
+        .collect()
+}
+
+#[pymodule]
+fn coldvox(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("SAMPLE_RATE_HZ", SAMPLE_RATE_HZ)?;
+    m.add_class::<Transcriber>()?;
+    m.add_class::<Event>()?;
+    m.add_class::<Word>()?;
+    m.add_function(wrap_pyfunction!(list_plugins, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coldvox_stt::{SourceId, WordInfo};
+
+    #[test]
+    fn pcm_bytes_are_little_endian_pairs() {
+        assert_eq!(
+            pcm_from_bytes(&[0x01, 0x00, 0xff, 0xff]).unwrap(),
+            vec![1, -1]
+        );
+        assert!(pcm_from_bytes(&[0x01]).is_err());
+    }
+
+    #[test]
+    fn events_keep_text_words_and_source() {
+        let event = Event::from(TranscriptionEvent::Final {
+            utterance_id: 3,
+            text: "hello".to_string(),
+            words: Some(vec![WordInfo {
+                start: 0.1,
+                end: 0.4,
+                conf: 0.9,
+                text: "hello".to_string(),
+            }]),
+            source: SourceId::default(),
+        });
+        assert_eq!(event.kind, "final");
+        assert_eq!(event.utterance_id, Some(3));
+        assert_eq!(event.words.len(), 1);
+        assert_eq!(event.source.as_deref(), Some("mic"));
+
+        let error = Event::from(TranscriptionEvent::Error {
+            code: "E1".to_string(),
+            message: "boom".to_string(),
+        });
+        assert_eq!(error.kind, "error");
+        assert_eq!(error.code.as_deref(), Some("E1"));
+    }
+}
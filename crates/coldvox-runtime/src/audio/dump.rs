@@ -0,0 +1,635 @@
+//! Debug dumps of the pipeline's audio.
+//!
+//! [`AudioDump`] writes the mono i16 stream to `audio_<timestamp>_<n>.<ext>`
+//! segments, starting a new one when the current segment reaches the size or
+//! duration limit and deleting the oldest segments beyond `keep`. Raw PCM and
+//! WAV are always available; Ogg Opus and FLAC need the `dump-opus` and
+//! `dump-flac` features.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::Local;
+use hound::{SampleFormat, WavSpec, WavWriter};
+
+#[derive(Debug, thiserror::Error)]
+pub enum DumpError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("WAV error: {0}")]
+    Wav(#[from] hound::Error),
+    #[error("{0} dumps need coldvox-app built with the `{1}` feature")]
+    Unsupported(DumpFormat, &'static str),
+    #[error("{0} encoder error: {1}")]
+    Encoder(DumpFormat, String),
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DumpFormat {
+    /// Headerless little-endian i16
+    #[default]
+    Pcm,
+    Wav,
+    /// Ogg Opus, 24 kbit/s speech
+    Opus,
+    /// Lossless FLAC
+    Flac,
+}
+
+impl DumpFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            DumpFormat::Pcm => "pcm",
+            DumpFormat::Wav => "wav",
+            DumpFormat::Opus => "opus",
+            DumpFormat::Flac => "flac",
+        }
+    }
+
+    /// Whether this build can write the format
+    pub fn is_available(self) -> bool {
+        match self {
+            DumpFormat::Pcm | DumpFormat::Wav => true,
+            DumpFormat::Opus => cfg!(feature = "dump-opus"),
+            DumpFormat::Flac => cfg!(feature = "dump-flac"),
+        }
+    }
+}
+
+impl fmt::Display for DumpFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+impl FromStr for DumpFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pcm" | "raw" => Ok(DumpFormat::Pcm),
+            "wav" => Ok(DumpFormat::Wav),
+            "opus" | "ogg" => Ok(DumpFormat::Opus),
+            "flac" => Ok(DumpFormat::Flac),
+            _ => Err(format!(
+                "Unknown format: {} (expected pcm, wav, opus or flac)",
+                s
+            )),
+        }
+    }
+}
+
+/// Where and how a dump is written
+#[derive(Debug, Clone)]
+pub struct DumpOptions {
+    pub dir: PathBuf,
+    pub format: DumpFormat,
+    /// Start a new segment once the current one reaches this many bytes
+    /// (uncompressed size for FLAC, which is encoded when a segment closes)
+    pub max_segment_bytes: Option<u64>,
+    /// Start a new segment once the current one holds this much audio
+    pub max_segment_duration: Option<Duration>,
+    /// Segments of this dump to keep on disk; 0 keeps all
+    pub keep: usize,
+}
+
+impl Default for DumpOptions {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from("logs/audio_dumps"),
+            format: DumpFormat::default(),
+            max_segment_bytes: Some(64 * 1024 * 1024),
+            max_segment_duration: Some(Duration::from_secs(10 * 60)),
+            keep: 0,
+        }
+    }
+}
+
+/// A segmented audio dump; segments are opened on the first write after a
+/// rotation, so a dump that never receives audio leaves no files
+pub struct AudioDump {
+    options: DumpOptions,
+    stem: String,
+    next_index: u32,
+    current: Option<Segment>,
+    closed: VecDeque<PathBuf>,
+}
+
+struct Segment {
+    path: PathBuf,
+    writer: SegmentWriter,
+    sample_rate: u32,
+    samples: u64,
+}
+
+impl AudioDump {
+    pub fn new(options: DumpOptions) -> Result<Self, DumpError> {
+        match options.format {
+            DumpFormat::Opus if !options.format.is_available() => {
+                return Err(DumpError::Unsupported(options.format, "dump-opus"))
+            }
+            DumpFormat::Flac if !options.format.is_available() => {
+                return Err(DumpError::Unsupported(options.format, "dump-flac"))
+            }
+            _ => {}
+        }
+        fs::create_dir_all(&options.dir)?;
+        Ok(Self {
+            stem: format!("audio_{}", Local::now().format("%Y%m%d_%H%M%S")),
+            options,
+            next_index: 0,
+            current: None,
+            closed: VecDeque::new(),
+        })
+    }
+
+    pub fn options(&self) -> &DumpOptions {
+        &self.options
+    }
+
+    /// Append `samples` at `sample_rate`; returns the path of the segment
+    /// opened for them, if a new one was
+    pub fn write(
+        &mut self,
+        sample_rate: u32,
+        samples: &[i16],
+    ) -> Result<Option<PathBuf>, DumpError> {
+        let rotate = self
+            .current
+            .as_ref()
+            .is_some_and(|s| s.sample_rate != sample_rate || self.segment_full(s));
+        if rotate {
+            self.close_segment()?;
+        }
+
+        let mut opened = None;
+        if self.current.is_none() {
+            self.prune();
+            let path = self.options.dir.join(format!(
+                "{}_{:03}.{}",
+                self.stem,
+                self.next_index,
+                self.options.format.extension()
+            ));
+            self.next_index += 1;
+            let writer = SegmentWriter::create(self.options.format, &path, sample_rate)?;
+            opened = Some(path.clone());
+            self.current = Some(Segment {
+                path,
+                writer,
+                sample_rate,
+                samples: 0,
+            });
+        }
+
+        let segment = self.current.as_mut().unwrap();
+        segment.writer.write(samples)?;
+        segment.samples += samples.len() as u64;
+        Ok(opened)
+    }
+
+    /// Close the open segment
+    pub fn finish(mut self) -> Result<(), DumpError> {
+        self.close_segment()
+    }
+
+    fn segment_full(&self, segment: &Segment) -> bool {
+        let too_big = self
+            .options
+            .max_segment_bytes
+            .is_some_and(|max| segment.writer.bytes_written() >= max);
+        let too_long = self.options.max_segment_duration.is_some_and(|max| {
+            segment.samples >= (max.as_secs_f64() * f64::from(segment.sample_rate)) as u64
+        });
+        too_big || too_long
+    }
+
+    fn close_segment(&mut self) -> Result<(), DumpError> {
+        let Some(segment) = self.current.take() else {
+            return Ok(());
+        };
+        segment.writer.finish()?;
+        self.closed.push_back(segment.path);
+        Ok(())
+    }
+
+    /// Delete the oldest closed segments so that a new one fits in `keep`
+    fn prune(&mut self) {
+        if self.options.keep == 0 {
+            return;
+        }
+        while self.closed.len() >= self.options.keep {
+            let Some(oldest) = self.closed.pop_front() else {
+                break;
+            };
+            if let Err(e) = fs::remove_file(&oldest) {
+                tracing::warn!("Failed to remove old dump {}: {}", oldest.display(), e);
+            }
+        }
+    }
+}
+
+enum SegmentWriter {
+    Pcm(BufWriter<File>, u64),
+    Wav(WavWriter<BufWriter<File>>, u64),
+    #[cfg(feature = "dump-opus")]
+    Opus(opus_ogg::OggOpusWriter),
+    #[cfg(feature = "dump-flac")]
+    Flac(flac::FlacWriter),
+}
+
+impl SegmentWriter {
+    fn create(format: DumpFormat, path: &Path, sample_rate: u32) -> Result<Self, DumpError> {
+        Ok(match format {
+            DumpFormat::Pcm => SegmentWriter::Pcm(BufWriter::new(File::create(path)?), 0),
+            DumpFormat::Wav => {
+                let spec = WavSpec {
+                    channels: 1,
+                    sample_rate,
+                    bits_per_sample: 16,
+                    sample_format: SampleFormat::Int,
+                };
+                SegmentWriter::Wav(WavWriter::create(path, spec)?, 44)
+            }
+            #[cfg(feature = "dump-opus")]
+            DumpFormat::Opus => {
+                SegmentWriter::Opus(opus_ogg::OggOpusWriter::create(path, sample_rate)?)
+            }
+            #[cfg(feature = "dump-flac")]
+            DumpFormat::Flac => SegmentWriter::Flac(flac::FlacWriter::new(path, sample_rate)),
+            #[allow(unreachable_patterns)]
+            _ => {
+                let feature = if format == DumpFormat::Opus {
+                    "dump-opus"
+                } else {
+                    "dump-flac"
+                };
+                return Err(DumpError::Unsupported(format, feature));
+            }
+        })
+    }
+
+    fn write(&mut self, samples: &[i16]) -> Result<(), DumpError> {
+        match self {
+            SegmentWriter::Pcm(w, bytes) => {
+                for &s in samples {
+                    w.write_all(&s.to_le_bytes())?;
+                }
+                *bytes += samples.len() as u64 * 2;
+            }
+            SegmentWriter::Wav(w, bytes) => {
+                let mut i16_writer = w.get_i16_writer(samples.len() as u32);
+                for &s in samples {
+                    i16_writer.write_sample(s);
+                }
+                i16_writer.flush()?;
+                *bytes += samples.len() as u64 * 2;
+            }
+            #[cfg(feature = "dump-opus")]
+            SegmentWriter::Opus(w) => w.write(samples)?,
+            #[cfg(feature = "dump-flac")]
+            SegmentWriter::Flac(w) => w.write(samples),
+        }
+        Ok(())
+    }
+
+    fn bytes_written(&self) -> u64 {
+        match self {
+            SegmentWriter::Pcm(_, bytes) | SegmentWriter::Wav(_, bytes) => *bytes,
+            #[cfg(feature = "dump-opus")]
+            SegmentWriter::Opus(w) => w.bytes_written(),
+            #[cfg(feature = "dump-flac")]
+            SegmentWriter::Flac(w) => w.bytes_buffered(),
+        }
+    }
+
+    fn finish(self) -> Result<(), DumpError> {
+        match self {
+            SegmentWriter::Pcm(mut w, _) => w.flush()?,
+            SegmentWriter::Wav(w, _) => w.finalize()?,
+            #[cfg(feature = "dump-opus")]
+            SegmentWriter::Opus(w) => w.finish()?,
+            #[cfg(feature = "dump-flac")]
+            SegmentWriter::Flac(w) => w.finish()?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "dump-opus")]
+mod opus_ogg {
+    //! Ogg Opus per RFC 7845: an OpusHead page, an OpusTags page, then one
+    //! 20 ms packet per frame with granule positions counted at 48 kHz.
+
+    use super::{DumpError, DumpFormat};
+    use ogg::writing::{PacketWriteEndInfo, PacketWriter};
+    use std::fs::File;
+    use std::io::{self, BufWriter, Write};
+    use std::path::Path;
+
+    const BITRATE: i32 = 24_000;
+    /// Largest packet the encoder is allowed to produce
+    const MAX_PACKET: usize = 4000;
+
+    fn encoder_error(e: opus::Error) -> DumpError {
+        DumpError::Encoder(DumpFormat::Opus, e.to_string())
+    }
+
+    /// Counts the bytes passed through to the file
+    struct Counting<W> {
+        inner: W,
+        bytes: u64,
+    }
+
+    impl<W: Write> Write for Counting<W> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let n = self.inner.write(buf)?;
+            self.bytes += n as u64;
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.inner.flush()
+        }
+    }
+
+    pub struct OggOpusWriter {
+        encoder: opus::Encoder,
+        ogg: PacketWriter<'static, Counting<BufWriter<File>>>,
+        serial: u32,
+        /// Samples per 20 ms frame at the input rate
+        frame_len: usize,
+        /// 48 kHz samples per input sample
+        scale: u64,
+        pre_skip: u64,
+        /// Input samples the encoder still has to see
+        pending: Vec<i16>,
+        samples_in: u64,
+        packets: u64,
+        packet: Vec<u8>,
+    }
+
+    impl OggOpusWriter {
+        pub fn create(path: &Path, sample_rate: u32) -> Result<Self, DumpError> {
+            if ![8_000, 12_000, 16_000, 24_000, 48_000].contains(&sample_rate) {
+                return Err(DumpError::Encoder(
+                    DumpFormat::Opus,
+                    format!("unsupported sample rate {} Hz", sample_rate),
+                ));
+            }
+            let mut encoder =
+                opus::Encoder::new(sample_rate, opus::Channels::Mono, opus::Application::Voip)
+                    .map_err(encoder_error)?;
+            encoder
+                .set_bitrate(opus::Bitrate::Bits(BITRATE))
+                .map_err(encoder_error)?;
+            let scale = u64::from(48_000 / sample_rate);
+            let pre_skip = encoder.get_lookahead().map_err(encoder_error)? as u64 * scale;
+
+            let file = BufWriter::new(File::create(path)?);
+            let mut ogg = PacketWriter::new(Counting {
+                inner: file,
+                bytes: 0,
+            });
+            let serial = rand_serial();
+
+            let mut head = Vec::with_capacity(19);
+            head.extend_from_slice(b"OpusHead");
+            head.push(1); // version
+            head.push(1); // channels
+            head.extend_from_slice(&(pre_skip as u16).to_le_bytes());
+            head.extend_from_slice(&sample_rate.to_le_bytes());
+            head.extend_from_slice(&0i16.to_le_bytes()); // output gain
+            head.push(0); // mapping family
+            ogg.write_packet(head, serial, PacketWriteEndInfo::EndPage, 0)?;
+
+            let vendor = concat!("coldvox ", env!("CARGO_PKG_VERSION"));
+            let mut tags = Vec::new();
+            tags.extend_from_slice(b"OpusTags");
+            tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+            tags.extend_from_slice(vendor.as_bytes());
+            tags.extend_from_slice(&0u32.to_le_bytes()); // no comments
+            ogg.write_packet(tags, serial, PacketWriteEndInfo::EndPage, 0)?;
+
+            Ok(Self {
+                encoder,
+                ogg,
+                serial,
+                frame_len: sample_rate as usize / 50,
+                scale,
+                pre_skip,
+                pending: Vec::new(),
+                samples_in: 0,
+                packets: 0,
+                packet: vec![0; MAX_PACKET],
+            })
+        }
+
+        pub fn write(&mut self, samples: &[i16]) -> Result<(), DumpError> {
+            self.pending.extend_from_slice(samples);
+            self.samples_in += samples.len() as u64;
+            self.encode_full_frames(false)
+        }
+
+        pub fn bytes_written(&self) -> u64 {
+            self.ogg.inner().bytes
+        }
+
+        pub fn finish(mut self) -> Result<(), DumpError> {
+            // Flush the encoder's lookahead, then pad to a whole frame
+            let lookahead = (self.pre_skip / self.scale) as usize;
+            self.pending.extend(std::iter::repeat_n(0, lookahead));
+            let padded = self.pending.len().div_ceil(self.frame_len) * self.frame_len;
+            self.pending.resize(padded.max(self.frame_len), 0);
+            self.encode_full_frames(true)?;
+            let mut out = self.ogg.into_inner();
+            out.flush()?;
+            Ok(())
+        }
+
+        /// Encode every whole frame in `pending`, ending the stream with the
+        /// last one if `end_stream`
+        fn encode_full_frames(&mut self, end_stream: bool) -> Result<(), DumpError> {
+            let frames = self.pending.len() / self.frame_len;
+            for i in 0..frames {
+                let frame = &self.pending[i * self.frame_len..(i + 1) * self.frame_len];
+                let len = self
+                    .encoder
+                    .encode(frame, &mut self.packet)
+                    .map_err(encoder_error)?;
+                self.packets += 1;
+                let decoded = self.packets * self.frame_len as u64 * self.scale;
+                let (info, granule) = if end_stream && i + 1 == frames {
+                    // End trimming: drop the padding after the real audio
+                    let end = (self.pre_skip + self.samples_in * self.scale).min(decoded);
+                    (PacketWriteEndInfo::EndStream, end)
+                } else {
+                    (PacketWriteEndInfo::NormalPacket, decoded)
+                };
+                self.ogg
+                    .write_packet(self.packet[..len].to_vec(), self.serial, info, granule)?;
+            }
+            self.pending.drain(..frames * self.frame_len);
+            Ok(())
+        }
+    }
+
+    /// Stream serial numbers only need to differ between chained streams;
+    /// the clock is enough for that
+    fn rand_serial() -> u32 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos() ^ d.as_secs() as u32)
+            .unwrap_or(0x436f_6c64)
+    }
+}
+
+#[cfg(feature = "dump-flac")]
+mod flac {
+    //! FLAC has no streaming encoder here: a segment is buffered and encoded
+    //! when it closes, which the rotation limits keep bounded.
+
+    use super::{DumpError, DumpFormat};
+    use flacenc::component::BitRepr;
+    use flacenc::error::Verify;
+    use std::path::{Path, PathBuf};
+
+    pub struct FlacWriter {
+        path: PathBuf,
+        sample_rate: u32,
+        samples: Vec<i32>,
+    }
+
+    impl FlacWriter {
+        pub fn new(path: &Path, sample_rate: u32) -> Self {
+            Self {
+                path: path.to_path_buf(),
+                sample_rate,
+                samples: Vec::new(),
+            }
+        }
+
+        pub fn write(&mut self, samples: &[i16]) {
+            self.samples.extend(samples.iter().map(|&s| i32::from(s)));
+        }
+
+        /// Size the segment would have as 16-bit PCM
+        pub fn bytes_buffered(&self) -> u64 {
+            self.samples.len() as u64 * 2
+        }
+
+        pub fn finish(self) -> Result<(), DumpError> {
+            let encoder_error = |e: String| DumpError::Encoder(DumpFormat::Flac, e);
+            let config = flacenc::config::Encoder::default()
+                .into_verified()
+                .map_err(|(_, e)| encoder_error(format!("{:?}", e)))?;
+            let source = flacenc::source::MemSource::from_samples(
+                &self.samples,
+                1,
+                16,
+                self.sample_rate as usize,
+            );
+            let stream = flacenc::encode_with_fixed_block_size(&config, source, config.block_size)
+                .map_err(|e| encoder_error(format!("{:?}", e)))?;
+            let mut sink = flacenc::bitsink::ByteSink::new();
+            stream
+                .write(&mut sink)
+                .map_err(|e| encoder_error(format!("{:?}", e)))?;
+            std::fs::write(&self.path, sink.as_slice())?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(dir: &Path, format: DumpFormat) -> DumpOptions {
+        DumpOptions {
+            dir: dir.to_path_buf(),
+            format,
+            max_segment_bytes: None,
+            max_segment_duration: None,
+            keep: 0,
+        }
+    }
+
+    fn segments(dir: &Path) -> Vec<PathBuf> {
+        let mut paths: Vec<_> = fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("WAV".parse::<DumpFormat>().unwrap(), DumpFormat::Wav);
+        assert_eq!("opus".parse::<DumpFormat>().unwrap(), DumpFormat::Opus);
+        assert_eq!("flac".parse::<DumpFormat>().unwrap(), DumpFormat::Flac);
+        assert!("mp3".parse::<DumpFormat>().is_err());
+    }
+
+    #[test]
+    fn rotates_by_size_and_keeps_the_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut opts = options(dir.path(), DumpFormat::Pcm);
+        opts.max_segment_bytes = Some(1280);
+        opts.keep = 2;
+        let mut dump = AudioDump::new(opts).unwrap();
+
+        let frame = [1i16; 320];
+        let mut opened = 0;
+        for _ in 0..5 {
+            opened += dump.write(16_000, &frame).unwrap().is_some() as usize;
+        }
+        dump.finish().unwrap();
+
+        // Two frames per segment: 1, 2 | 3, 4 | 5
+        assert_eq!(opened, 3);
+        let paths = segments(dir.path());
+        assert_eq!(paths.len(), 2);
+        assert!(paths[0].to_string_lossy().ends_with("_001.pcm"));
+        assert_eq!(fs::metadata(&paths[0]).unwrap().len(), 1280);
+        assert!(paths[1].to_string_lossy().ends_with("_002.pcm"));
+        assert_eq!(fs::metadata(&paths[1]).unwrap().len(), 640);
+    }
+
+    #[test]
+    fn rotates_wav_by_duration() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut opts = options(dir.path(), DumpFormat::Wav);
+        opts.max_segment_duration = Some(Duration::from_millis(50));
+        let mut dump = AudioDump::new(opts).unwrap();
+        for _ in 0..6 {
+            dump.write(16_000, &[0i16; 320]).unwrap();
+        }
+        dump.finish().unwrap();
+
+        let paths = segments(dir.path());
+        assert_eq!(paths.len(), 2);
+        let reader = hound::WavReader::open(&paths[0]).unwrap();
+        assert_eq!(reader.spec().sample_rate, 16_000);
+        assert_eq!(reader.len(), 960);
+    }
+
+    #[test]
+    fn unavailable_formats_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        for format in [DumpFormat::Opus, DumpFormat::Flac] {
+            if !format.is_available() {
+                assert!(matches!(
+                    AudioDump::new(options(dir.path(), format)),
+                    Err(DumpError::Unsupported(..))
+                ));
+            }
+        }
+    }
+}
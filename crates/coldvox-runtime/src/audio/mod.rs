@@ -1,3 +1,7 @@
+pub mod dump;
+pub mod noise;
+pub mod playback;
+pub mod quality;
 pub mod vad_adapter;
 pub mod vad_processor;
 pub mod wav_file_loader;
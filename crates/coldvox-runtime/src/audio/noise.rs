@@ -0,0 +1,285 @@
+//! Noise calibration and the noise-suppression stage.
+//!
+//! `coldvox calibrate-noise` records a few seconds of room noise through the
+//! normal capture path (device → resampler → 16 kHz mono) and stores the
+//! learned [`NoiseProfile`] under a device and location name, so switching
+//! between e.g. "office" and "kitchen" doesn't need a new calibration. The
+//! active profile for the capture device is loaded at startup and runs as a
+//! spectral-subtraction stage between the chunker and VAD/STT.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use coldvox_audio::{
+    AudioCaptureThread, AudioChunker, AudioRingBuffer, ChunkerConfig, FrameReader,
+    ResamplerQuality, SharedAudioFrame,
+};
+use coldvox_audio_quality::{NoiseProfile, NoiseProfiler, NoiseSuppressor};
+use coldvox_foundation::AudioConfig;
+use coldvox_vad::{FRAME_SIZE_SAMPLES, SAMPLE_RATE_HZ};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// Key for profiles recorded on the system default input
+pub const DEFAULT_DEVICE: &str = "default";
+
+/// A calibrated profile for one device at one location
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredNoiseProfile {
+    pub device: String,
+    pub location: String,
+    pub recorded_at: DateTime<Utc>,
+    pub profile: NoiseProfile,
+}
+
+/// Noise profiles per device and location, plus the active location per device
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NoiseProfileStore {
+    #[serde(default)]
+    pub profiles: Vec<StoredNoiseProfile>,
+    /// Device → location whose profile is applied
+    #[serde(default)]
+    pub active: BTreeMap<String, String>,
+}
+
+impl NoiseProfileStore {
+    /// Default location: `$XDG_STATE_HOME/coldvox/noise_profiles.json`,
+    /// falling back to `~/.local/state/coldvox/noise_profiles.json`.
+    pub fn default_path() -> Option<PathBuf> {
+        let state_dir = std::env::var_os("XDG_STATE_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local").join("state"))
+            })?;
+        Some(state_dir.join("coldvox").join("noise_profiles.json"))
+    }
+
+    /// Load the store. A missing file yields an empty store.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+
+    /// Store `profile` for `device` at `location`, replacing an earlier
+    /// calibration there, and make it the device's active profile
+    pub fn insert(&mut self, device: &str, location: &str, profile: NoiseProfile) {
+        self.profiles
+            .retain(|p| !(p.device == device && p.location == location));
+        self.profiles.push(StoredNoiseProfile {
+            device: device.to_string(),
+            location: location.to_string(),
+            recorded_at: Utc::now(),
+            profile,
+        });
+        self.active.insert(device.to_string(), location.to_string());
+    }
+
+    /// Switch `device` to the profile recorded at `location`. Returns false
+    /// if there is none.
+    pub fn activate(&mut self, device: &str, location: &str) -> bool {
+        let known = self
+            .profiles
+            .iter()
+            .any(|p| p.device == device && p.location == location);
+        if known {
+            self.active.insert(device.to_string(), location.to_string());
+        }
+        known
+    }
+
+    /// Stop suppressing noise on `device`
+    pub fn deactivate(&mut self, device: &str) {
+        self.active.remove(device);
+    }
+
+    /// The profile to apply for `device`, if one is active
+    pub fn active_profile(&self, device: &str) -> Option<&StoredNoiseProfile> {
+        let location = self.active.get(device)?;
+        self.profiles
+            .iter()
+            .find(|p| p.device == device && &p.location == location)
+    }
+}
+
+/// Store key for a configured capture device
+pub fn device_key(device: Option<&str>) -> &str {
+    device.filter(|d| !d.is_empty()).unwrap_or(DEFAULT_DEVICE)
+}
+
+/// Record `duration` of room noise from `device` and learn its profile
+pub async fn calibrate(
+    device: Option<String>,
+    duration: Duration,
+) -> Result<NoiseProfile, Box<dyn std::error::Error>> {
+    let config = AudioConfig::default();
+    let rb = AudioRingBuffer::new(config.capture_buffer_samples);
+    let (audio_producer, audio_consumer) = rb.split();
+    let audio_producer = Arc::new(parking_lot::Mutex::new(audio_producer));
+    let (capture, device_cfg, config_rx, _device_event_rx) =
+        AudioCaptureThread::spawn(config, audio_producer, device, false)?;
+
+    let reader = FrameReader::new(
+        audio_consumer,
+        device_cfg.sample_rate,
+        device_cfg.channels,
+        config.capture_buffer_samples,
+        None,
+    );
+    let (audio_tx, mut audio_rx) = broadcast::channel::<SharedAudioFrame>(200);
+    let chunker = AudioChunker::new(
+        reader,
+        audio_tx,
+        ChunkerConfig {
+            frame_size_samples: FRAME_SIZE_SAMPLES,
+            sample_rate_hz: SAMPLE_RATE_HZ,
+            resampler_quality: ResamplerQuality::Balanced,
+            channel: Default::default(),
+        },
+    )
+    .with_device_config(config_rx)
+    .spawn();
+
+    let mut profiler = NoiseProfiler::new(SAMPLE_RATE_HZ);
+    let deadline = tokio::time::sleep(duration);
+    tokio::pin!(deadline);
+    loop {
+        tokio::select! {
+            _ = &mut deadline => break,
+            frame = audio_rx.recv() => match frame {
+                Ok(frame) => profiler.push(&frame.samples),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        }
+    }
+    chunker.abort();
+    capture.stop();
+
+    let recorded = profiler.duration_secs();
+    profiler.finish().ok_or_else(|| {
+        format!(
+            "only {:.1}s of audio arrived from the microphone; not enough for a noise profile",
+            recorded
+        )
+        .into()
+    })
+}
+
+/// Noise suppression between the chunker and its consumers: frames from
+/// `raw_rx` are cleaned with `profile` and republished on `audio_tx`. Ends
+/// when the chunker goes away.
+pub fn spawn_noise_suppression(
+    profile: NoiseProfile,
+    mut raw_rx: broadcast::Receiver<SharedAudioFrame>,
+    audio_tx: broadcast::Sender<SharedAudioFrame>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut suppressor = NoiseSuppressor::new(&profile);
+        let mut warned_rate = false;
+        loop {
+            let frame = match raw_rx.recv().await {
+                Ok(frame) => frame,
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    tracing::warn!("Noise suppression lagged by {} frames", n);
+                    suppressor.reset();
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            if frame.sample_rate != profile.sample_rate {
+                // A profile from another rate would subtract the wrong bins
+                if !warned_rate {
+                    tracing::warn!(
+                        "Noise profile is for {} Hz but audio is {} Hz; not suppressing",
+                        profile.sample_rate,
+                        frame.sample_rate
+                    );
+                    warned_rate = true;
+                }
+                let _ = audio_tx.send(frame);
+                continue;
+            }
+            if frame.discontinuity {
+                suppressor.reset();
+            }
+            let cleaned = suppressor.process(&frame.samples);
+            let _ = audio_tx.send(SharedAudioFrame {
+                samples: cleaned.into(),
+                ..frame
+            });
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(rms_dbfs: f32) -> NoiseProfile {
+        NoiseProfile {
+            sample_rate: SAMPLE_RATE_HZ,
+            spectrum_db: vec![-90.0; 257],
+            rms_dbfs,
+            peak_dbfs: rms_dbfs + 12.0,
+            duration_secs: 5.0,
+        }
+    }
+
+    #[test]
+    fn profiles_are_kept_per_device_and_location() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("coldvox").join("noise_profiles.json");
+        let mut store = NoiseProfileStore::load(&path).unwrap();
+        assert!(store.active_profile(DEFAULT_DEVICE).is_none());
+
+        store.insert(DEFAULT_DEVICE, "office", profile(-60.0));
+        store.insert(DEFAULT_DEVICE, "kitchen", profile(-45.0));
+        store.insert("USB Mic", "office", profile(-70.0));
+        // Recalibrating replaces the old profile
+        store.insert(DEFAULT_DEVICE, "office", profile(-58.0));
+        assert_eq!(store.profiles.len(), 3);
+        store.save(&path).unwrap();
+
+        let mut store = NoiseProfileStore::load(&path).unwrap();
+        let active = store.active_profile(DEFAULT_DEVICE).unwrap();
+        assert_eq!(active.location, "office");
+        assert_eq!(active.profile.rms_dbfs, -58.0);
+
+        assert!(store.activate(DEFAULT_DEVICE, "kitchen"));
+        assert_eq!(
+            store
+                .active_profile(DEFAULT_DEVICE)
+                .unwrap()
+                .profile
+                .rms_dbfs,
+            -45.0
+        );
+        assert!(!store.activate(DEFAULT_DEVICE, "garage"));
+        assert_eq!(store.active_profile("USB Mic").unwrap().location, "office");
+
+        store.deactivate(DEFAULT_DEVICE);
+        assert!(store.active_profile(DEFAULT_DEVICE).is_none());
+        assert_eq!(device_key(None), DEFAULT_DEVICE);
+        assert_eq!(device_key(Some("USB Mic")), "USB Mic");
+    }
+}
@@ -0,0 +1,238 @@
+//! Barge-in prevention: ignore the microphone while the system plays audio.
+//!
+//! With `audio.suppress_on_playback` the runtime records the monitor of the
+//! default sink through `parec` (PulseAudio, or PipeWire's pulse server) and
+//! treats playback at or above `playback_threshold_dbfs` as active until it
+//! has stayed quiet for [`PlaybackGuardConfig::hold`]. While it is active a
+//! stage between the chunker and VAD/STT passes silence on instead of the
+//! microphone, so a video or call playing through the speakers is not
+//! dictated, and VAD sees any open utterance end. ColdVox's own read-back
+//! already mutes capture while it speaks.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use coldvox_audio::SharedAudioFrame;
+use coldvox_telemetry::PipelineMetrics;
+use tokio::io::AsyncReadExt;
+use tokio::process::Command;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::idle::frame_dbfs;
+
+/// Rate the sink monitor is recorded at; only the level matters
+const MONITOR_RATE_HZ: u32 = 8_000;
+/// Level measurement window (20 ms)
+const MONITOR_CHUNK_SAMPLES: usize = 160;
+/// Wait before recording the monitor again after `parec` exits
+const RESTART_DELAY: Duration = Duration::from_secs(5);
+
+/// Playback detection tuning
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaybackGuardConfig {
+    /// Sink output level that counts as playback
+    pub threshold_dbfs: f32,
+    /// Quiet time after playback before the microphone is listened to again
+    pub hold: Duration,
+    /// Source recorded by `parec`
+    pub monitor_source: String,
+}
+
+impl Default for PlaybackGuardConfig {
+    fn default() -> Self {
+        Self {
+            threshold_dbfs: -45.0,
+            hold: Duration::from_millis(600),
+            monitor_source: "@DEFAULT_MONITOR@".to_string(),
+        }
+    }
+}
+
+/// Decides from sink monitor levels whether playback is active
+#[derive(Debug)]
+struct PlaybackDetector {
+    threshold_dbfs: f32,
+    hold: Duration,
+    last_loud: Option<Instant>,
+    active: bool,
+}
+
+impl PlaybackDetector {
+    fn new(config: &PlaybackGuardConfig) -> Self {
+        Self {
+            threshold_dbfs: config.threshold_dbfs,
+            hold: config.hold,
+            last_loud: None,
+            active: false,
+        }
+    }
+
+    /// Feed a chunk of monitor audio (None when the monitor delivered
+    /// nothing, e.g. a suspended sink). Returns the new state on a change.
+    fn observe(&mut self, samples: Option<&[i16]>, now: Instant) -> Option<bool> {
+        if samples.is_some_and(|s| frame_dbfs(s) >= self.threshold_dbfs) {
+            self.last_loud = Some(now);
+        }
+        let active = self
+            .last_loud
+            .is_some_and(|at| now.duration_since(at) < self.hold);
+        (active != self.active).then(|| {
+            self.active = active;
+            active
+        })
+    }
+}
+
+/// Record the sink monitor and keep `active` set while playback is heard.
+/// Ends if `parec` is not installed.
+pub fn spawn_playback_monitor(
+    config: PlaybackGuardConfig,
+    active: Arc<AtomicBool>,
+    metrics: Arc<PipelineMetrics>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut detector = PlaybackDetector::new(&config);
+        let mut bytes = vec![0u8; MONITOR_CHUNK_SAMPLES * 2];
+        let mut samples = vec![0i16; MONITOR_CHUNK_SAMPLES];
+        loop {
+            let child = Command::new("parec")
+                .arg(format!("--device={}", config.monitor_source))
+                .args(["--raw", "--format=s16le", "--channels=1"])
+                .arg(format!("--rate={}", MONITOR_RATE_HZ))
+                .arg("--latency-msec=20")
+                .stdout(std::process::Stdio::piped())
+                .stderr(std::process::Stdio::null())
+                .kill_on_drop(true)
+                .spawn();
+            let mut child = match child {
+                Ok(child) => child,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    tracing::warn!(
+                        "audio.suppress_on_playback needs parec (pulseaudio-utils); not suppressing"
+                    );
+                    return;
+                }
+                Err(e) => {
+                    tracing::warn!("Could not record the sink monitor: {}", e);
+                    tokio::time::sleep(RESTART_DELAY).await;
+                    continue;
+                }
+            };
+            let Some(mut stdout) = child.stdout.take() else {
+                return;
+            };
+            tracing::info!(
+                "Suppressing dictation while playback on {} reaches {:.0} dBFS",
+                config.monitor_source,
+                config.threshold_dbfs
+            );
+
+            let mut filled = 0;
+            loop {
+                // `read` is cancel-safe, so a timeout never splits a sample
+                let read =
+                    tokio::time::timeout(config.hold, stdout.read(&mut bytes[filled..])).await;
+                let chunk = match read {
+                    Ok(Ok(0)) | Ok(Err(_)) => break,
+                    Ok(Ok(n)) => {
+                        filled += n;
+                        if filled < bytes.len() {
+                            continue;
+                        }
+                        filled = 0;
+                        for (s, b) in samples.iter_mut().zip(bytes.chunks_exact(2)) {
+                            *s = i16::from_le_bytes([b[0], b[1]]);
+                        }
+                        Some(&samples[..])
+                    }
+                    // A suspended sink's monitor stops delivering rather than
+                    // sending silence
+                    Err(_) => None,
+                };
+                if let Some(now_active) = detector.observe(chunk, Instant::now()) {
+                    set_active(&active, &metrics, now_active);
+                }
+            }
+
+            tracing::debug!("Sink monitor recording ended; restarting");
+            set_active(&active, &metrics, false);
+            detector = PlaybackDetector::new(&config);
+            let _ = child.wait().await;
+            tokio::time::sleep(RESTART_DELAY).await;
+        }
+    })
+}
+
+fn set_active(active: &AtomicBool, metrics: &PipelineMetrics, now_active: bool) {
+    if active.swap(now_active, Ordering::Relaxed) == now_active {
+        return;
+    }
+    metrics
+        .playback_suppressed
+        .store(now_active, Ordering::Relaxed);
+    if now_active {
+        metrics
+            .playback_suppressions
+            .fetch_add(1, Ordering::Relaxed);
+        tracing::debug!("System audio playing; ignoring the microphone");
+    } else {
+        tracing::debug!("System audio stopped; listening again");
+    }
+}
+
+/// Forward frames from `raw_rx` to `audio_tx`, silenced while `active` is set
+pub fn spawn_playback_gate(
+    active: Arc<AtomicBool>,
+    mut raw_rx: broadcast::Receiver<SharedAudioFrame>,
+    audio_tx: broadcast::Sender<SharedAudioFrame>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let frame = match raw_rx.recv().await {
+                Ok(frame) => frame,
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            let frame = if active.load(Ordering::Relaxed) {
+                SharedAudioFrame {
+                    samples: vec![0i16; frame.samples.len()].into(),
+                    ..frame
+                }
+            } else {
+                frame
+            };
+            let _ = audio_tx.send(frame);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn playback_stays_active_for_the_hold_time() {
+        let config = PlaybackGuardConfig {
+            hold: Duration::from_millis(500),
+            ..Default::default()
+        };
+        let mut detector = PlaybackDetector::new(&config);
+        let start = Instant::now();
+        let loud = vec![8_000i16; MONITOR_CHUNK_SAMPLES];
+        let quiet = vec![10i16; MONITOR_CHUNK_SAMPLES];
+
+        assert_eq!(detector.observe(Some(&quiet), start), None);
+        assert_eq!(detector.observe(Some(&loud), start), Some(true));
+
+        // Gaps between words don't let the microphone back in
+        let gap = start + Duration::from_millis(300);
+        assert_eq!(detector.observe(Some(&quiet), gap), None);
+
+        // Nothing delivered counts as quiet
+        let after = start + Duration::from_millis(600);
+        assert_eq!(detector.observe(None, after), Some(false));
+    }
+}
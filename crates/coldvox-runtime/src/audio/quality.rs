@@ -0,0 +1,196 @@
+//! Audio quality warnings.
+//!
+//! Runs [`AudioQualityMonitor`] over the chunker output and publishes a
+//! [`QualityWarning`] when the input clips, speech is too quiet or the
+//! speaker is off-axis. Clipping counts whenever it happens; level and
+//! spectral checks only mean something while someone talks, so those are
+//! judged during speech, once the monitor's RMS window holds nothing but
+//! speech. Warnings are rate-limited by the monitor's cooldown; desktop
+//! notifications get a much longer one per kind.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use coldvox_audio::SharedAudioFrame;
+use coldvox_audio_quality::{AudioQualityMonitor, QualityConfig, QualityStatus, QualityWarning};
+use coldvox_telemetry::PipelineMetrics;
+use coldvox_vad::VadEvent;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// Minimum time between desktop notifications of the same kind
+pub const NOTIFY_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Monitor plus the speech state it needs to tell a quiet talker from a
+/// quiet room
+struct QualityGate {
+    monitor: AudioQualityMonitor,
+    speaking: bool,
+    /// Speech samples seen since the last speech start
+    speech_samples: usize,
+    /// Samples in the monitor's RMS window
+    window_samples: usize,
+}
+
+impl QualityGate {
+    fn new(config: QualityConfig) -> Self {
+        let window_samples = (config.sample_rate as u64 * config.rms_window_ms / 1000) as usize;
+        Self {
+            monitor: AudioQualityMonitor::new(config),
+            speaking: false,
+            speech_samples: 0,
+            window_samples,
+        }
+    }
+
+    fn vad_event(&mut self, event: &VadEvent) {
+        self.speaking = matches!(event, VadEvent::SpeechStart { .. });
+        self.speech_samples = 0;
+    }
+
+    /// Analyze a frame; the warning to publish, if any
+    fn check(&mut self, samples: &[i16]) -> Option<QualityWarning> {
+        if self.speaking {
+            self.speech_samples += samples.len();
+        }
+        let QualityStatus::Warning(warning) = self.monitor.analyze(samples) else {
+            return None;
+        };
+        let relevant = match warning {
+            QualityWarning::Clipping { .. } => true,
+            _ => self.speaking && self.speech_samples >= self.window_samples,
+        };
+        (relevant && self.monitor.should_send_warning()).then_some(warning)
+    }
+}
+
+/// Watch the audio on `audio_rx` (with speech boundaries from `vad_rx`) and
+/// publish quality warnings on `warning_tx`. Ends when the audio goes away.
+pub fn spawn_quality_monitor(
+    config: QualityConfig,
+    mut audio_rx: broadcast::Receiver<SharedAudioFrame>,
+    mut vad_rx: broadcast::Receiver<VadEvent>,
+    warning_tx: broadcast::Sender<QualityWarning>,
+    metrics: Arc<PipelineMetrics>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut gate = QualityGate::new(config);
+        let mut vad_open = true;
+        loop {
+            tokio::select! {
+                frame = audio_rx.recv() => {
+                    let frame = match frame {
+                        Ok(frame) => frame,
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    };
+                    if let Some(warning) = gate.check(&frame.samples) {
+                        tracing::warn!(
+                            warning_type = warning.warning_type(),
+                            "{}",
+                            warning.message()
+                        );
+                        metrics.record_quality_warning(warning.warning_type());
+                        let _ = warning_tx.send(warning);
+                    }
+                }
+                event = vad_rx.recv(), if vad_open => match event {
+                    Ok(event) => gate.vad_event(&event),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    // Without speech boundaries only clipping is reported
+                    Err(broadcast::error::RecvError::Closed) => vad_open = false,
+                },
+            }
+        }
+    })
+}
+
+/// Show quality warnings from `warning_rx` as desktop notifications with a
+/// remediation hint, at most one per kind every [`NOTIFY_COOLDOWN`]
+pub fn spawn_quality_notifications(
+    mut warning_rx: broadcast::Receiver<QualityWarning>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut last_sent: HashMap<String, Instant> = HashMap::new();
+        loop {
+            let warning = match warning_rx.recv().await {
+                Ok(warning) => warning,
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            let kind = warning.warning_type().to_string();
+            if last_sent
+                .get(&kind)
+                .is_some_and(|at| at.elapsed() < NOTIFY_COOLDOWN)
+            {
+                continue;
+            }
+            last_sent.insert(kind, Instant::now());
+            let (summary, urgency) = match warning {
+                QualityWarning::Clipping { .. } => {
+                    ("Microphone is clipping", crate::notify::Urgency::Normal)
+                }
+                QualityWarning::TooQuiet { .. } => {
+                    ("Speech is too quiet", crate::notify::Urgency::Low)
+                }
+                QualityWarning::OffAxis { .. } => {
+                    ("Speaking off-axis", crate::notify::Urgency::Low)
+                }
+            };
+            let body = format!(
+                "{}\n\nSuggested fix: {}",
+                warning.message(),
+                warning.suggested_action()
+            );
+            crate::notify::send(summary, &body, urgency).await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn speech_start() -> VadEvent {
+        VadEvent::SpeechStart {
+            timestamp_ms: 0,
+            energy_db: -30.0,
+        }
+    }
+
+    #[test]
+    fn quiet_audio_only_warns_during_settled_speech() {
+        let mut gate = QualityGate::new(QualityConfig {
+            warning_cooldown: Duration::ZERO,
+            enable_off_axis_detection: false,
+            ..Default::default()
+        });
+        let silence = vec![0i16; 512];
+
+        // A quiet room between utterances is fine
+        assert_eq!(gate.check(&silence), None);
+
+        // Right after speech starts the window still holds the lead-in
+        gate.vad_event(&speech_start());
+        assert_eq!(gate.check(&silence), None);
+
+        // 500 ms of speech later a quiet talker is reported
+        let warnings: Vec<_> = (0..20).filter_map(|_| gate.check(&silence)).collect();
+        assert!(matches!(
+            warnings.last(),
+            Some(QualityWarning::TooQuiet { .. })
+        ));
+
+        // Clipping is reported even outside speech
+        gate.vad_event(&VadEvent::SpeechEnd {
+            timestamp_ms: 1000,
+            duration_ms: 1000,
+            energy_db: -30.0,
+        });
+        assert!(matches!(
+            gate.check(&[i16::MAX; 512]),
+            Some(QualityWarning::Clipping { .. })
+        ));
+    }
+}
@@ -0,0 +1,121 @@
+use coldvox_audio::StreamResampler;
+use coldvox_vad::{EnergyVad, UnifiedVadConfig, VadEngine, VadEvent, VadMode, VadState};
+#[cfg(feature = "silero")]
+use coldvox_vad_silero::SileroEngine;
+
+pub struct VadAdapter {
+    engine: Box<dyn VadEngine>,
+    config: UnifiedVadConfig,
+    resampler: Option<StreamResampler>,
+}
+
+impl VadAdapter {
+    pub fn new(config: UnifiedVadConfig) -> Result<Self, String> {
+        let engine: Box<dyn VadEngine> = match config.mode {
+            VadMode::Silero => silero_or_energy(&config),
+            VadMode::Energy => Box::new(EnergyVad::new(config.energy.clone())),
+            VadMode::Vote => voting(&config)?,
+        };
+
+        let resampler = if engine.required_sample_rate() != config.sample_rate_hz {
+            Some(StreamResampler::new(
+                config.sample_rate_hz,
+                engine.required_sample_rate(),
+            ))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            engine,
+            config,
+            resampler,
+        })
+    }
+
+    pub fn process(&mut self, frame: &[i16]) -> Result<Option<VadEvent>, String> {
+        if let Some(resampler) = &mut self.resampler {
+            let processed_frame = resampler.process(frame);
+            // Only process if we got a complete frame
+            if !processed_frame.is_empty() {
+                self.engine.process(&processed_frame)
+            } else {
+                // Not enough samples yet, waiting for more
+                Ok(None)
+            }
+        } else {
+            self.engine.process(frame)
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.engine.reset();
+        if let Some(resampler) = &mut self.resampler {
+            resampler.reset();
+        }
+    }
+
+    /// Apply new Silero detection parameters to the running engine
+    pub fn retune(&mut self, silero: coldvox_vad::config::SileroConfig) -> bool {
+        self.config.silero = silero;
+        self.engine.retune(&self.config)
+    }
+
+    /// Re-derive detection thresholds from the next `duration_ms` of ambient
+    /// noise; false if the engine doesn't calibrate
+    pub fn calibrate(&mut self, duration_ms: u32) -> bool {
+        self.engine.calibrate(duration_ms)
+    }
+
+    pub fn current_state(&self) -> VadState {
+        self.engine.current_state()
+    }
+
+    pub fn config(&self) -> &UnifiedVadConfig {
+        &self.config
+    }
+}
+
+/// Silero, or the energy VAD when the model can't be loaded
+#[cfg(feature = "silero")]
+fn silero_or_energy(config: &UnifiedVadConfig) -> Box<dyn VadEngine> {
+    match SileroEngine::new(config.silero.clone()) {
+        Ok(engine) => Box::new(engine),
+        Err(e) => {
+            tracing::warn!("{}; falling back to the energy VAD", e);
+            Box::new(EnergyVad::new(config.energy.clone()))
+        }
+    }
+}
+
+#[cfg(not(feature = "silero"))]
+fn silero_or_energy(config: &UnifiedVadConfig) -> Box<dyn VadEngine> {
+    tracing::warn!("Built without the 'silero' feature; falling back to the energy VAD");
+    Box::new(EnergyVad::new(config.energy.clone()))
+}
+
+/// Silero and the energy VAD voting; the energy VAD alone without Silero
+fn voting(config: &UnifiedVadConfig) -> Result<Box<dyn VadEngine>, String> {
+    let energy: Box<dyn VadEngine> = Box::new(EnergyVad::new(config.energy.clone()));
+    #[cfg(feature = "silero")]
+    {
+        match SileroEngine::new(config.silero.clone()) {
+            Ok(silero) => {
+                return Ok(Box::new(coldvox_vad::VotingVad::new(
+                    Box::new(silero),
+                    energy,
+                    config.vote,
+                    &config.energy,
+                )?));
+            }
+            Err(e) => tracing::warn!("{}; voting VAD runs on the energy VAD alone", e),
+        }
+    }
+    #[cfg(not(feature = "silero"))]
+    {
+        tracing::warn!(
+            "Built without the 'silero' feature; voting VAD runs on the energy VAD alone"
+        );
+    }
+    Ok(energy)
+}
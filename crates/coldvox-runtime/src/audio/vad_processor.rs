@@ -0,0 +1,317 @@
+use std::sync::Arc;
+
+use coldvox_audio::SharedAudioFrame;
+use coldvox_telemetry::{FpsTracker, PipelineMetrics};
+use coldvox_vad::config::SileroConfig;
+use coldvox_vad::{UnifiedVadConfig, VadEvent, VadState};
+use std::time::Instant;
+use tokio::sync::mpsc::Sender;
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, trace};
+
+use super::vad_adapter::VadAdapter;
+
+pub struct VadProcessor {
+    adapter: VadAdapter,
+    audio_rx: broadcast::Receiver<SharedAudioFrame>,
+    event_tx: Sender<VadEvent>,
+    metrics: Option<Arc<PipelineMetrics>>,
+    fps_tracker: FpsTracker,
+    frames_processed: u64,
+    events_generated: u64,
+    /// Requests to end the current utterance now (see `AppHandle::finalize_now`)
+    finalize_rx: Option<broadcast::Receiver<()>>,
+    /// Live sensitivity changes (see `AppHandle::set_vad_preset`)
+    tuning_rx: Option<watch::Receiver<SileroConfig>>,
+    /// Noise calibration requests in ms (see `AppHandle::calibrate_vad`)
+    calibrate_rx: Option<broadcast::Receiver<u32>>,
+    /// Timestamp and wall-clock time of the SpeechStart we forwarded
+    speech_started: Option<(u64, Instant)>,
+}
+
+impl VadProcessor {
+    pub fn new(
+        config: UnifiedVadConfig,
+        audio_rx: broadcast::Receiver<SharedAudioFrame>,
+        event_tx: Sender<VadEvent>,
+        metrics: Option<Arc<PipelineMetrics>>,
+    ) -> Result<Self, String> {
+        let adapter = VadAdapter::new(config)?;
+
+        Ok(Self {
+            adapter,
+            audio_rx,
+            event_tx,
+            metrics,
+            fps_tracker: FpsTracker::new(),
+            frames_processed: 0,
+            events_generated: 0,
+            finalize_rx: None,
+            tuning_rx: None,
+            calibrate_rx: None,
+            speech_started: None,
+        })
+    }
+
+    /// End the current utterance whenever a message arrives on `rx`
+    pub fn with_finalize_signal(mut self, rx: broadcast::Receiver<()>) -> Self {
+        self.finalize_rx = Some(rx);
+        self
+    }
+
+    /// Apply each Silero configuration published on `rx` while running
+    pub fn with_tuning(mut self, mut rx: watch::Receiver<SileroConfig>) -> Self {
+        // The processor was built from the current value
+        rx.mark_unchanged();
+        self.tuning_rx = Some(rx);
+        self
+    }
+
+    /// Calibrate the engine to the ambient noise on each request from `rx`
+    pub fn with_calibration_requests(mut self, rx: broadcast::Receiver<u32>) -> Self {
+        self.calibrate_rx = Some(rx);
+        self
+    }
+
+    pub async fn run(mut self) {
+        info!("VAD processor task started");
+
+        // This loop will automatically exit when the sender side of the broadcast channel is dropped.
+        loop {
+            tokio::select! {
+                frame = self.audio_rx.recv() => match frame {
+                    Ok(frame) => self.process_frame(frame).await,
+                    Err(_) => break,
+                },
+                request = next_finalize(&mut self.finalize_rx) => match request {
+                    Ok(()) | Err(broadcast::error::RecvError::Lagged(_)) => {
+                        self.force_speech_end().await
+                    }
+                    Err(broadcast::error::RecvError::Closed) => self.finalize_rx = None,
+                },
+                tuning = next_tuning(&mut self.tuning_rx) => match tuning {
+                    Some(config) => self.retune(config),
+                    None => self.tuning_rx = None,
+                },
+                request = next_calibration(&mut self.calibrate_rx) => match request {
+                    Ok(duration_ms) => self.calibrate(duration_ms),
+                    Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => self.calibrate_rx = None,
+                },
+            }
+        }
+
+        info!(
+            "VAD processor task shutting down. Frames processed: {}, Events generated: {}",
+            self.frames_processed, self.events_generated
+        );
+    }
+
+    /// End the utterance in progress without waiting for the silence timeout.
+    /// The detector is reset so that speech continuing afterwards opens a new
+    /// utterance instead of trailing an end event later.
+    async fn force_speech_end(&mut self) {
+        if self.adapter.current_state() != VadState::Speech {
+            debug!("VAD: Finalize requested outside speech; nothing to end");
+            return;
+        }
+        self.adapter.reset();
+        let (started_ms, started_at) = self.speech_started.take().unwrap_or((0, Instant::now()));
+        let duration_ms = started_at.elapsed().as_millis() as u64;
+        let event = VadEvent::SpeechEnd {
+            timestamp_ms: started_ms + duration_ms,
+            duration_ms,
+            energy_db: 0.0,
+        };
+        info!("VAD: Speech end forced after {}ms", duration_ms);
+        self.events_generated += 1;
+        if let Err(e) = self.event_tx.send(event).await {
+            error!("Failed to send VAD event: {}", e);
+        }
+    }
+
+    fn retune(&mut self, config: SileroConfig) {
+        info!(
+            "VAD: Retuned to threshold {:.2}, min speech {}ms, min silence {}ms",
+            config.threshold, config.min_speech_duration_ms, config.min_silence_duration_ms
+        );
+        if !self.adapter.retune(config) {
+            tracing::warn!("VAD engine can't be retuned while running; change applies on restart");
+        }
+    }
+
+    fn calibrate(&mut self, duration_ms: u32) {
+        if self.adapter.calibrate(duration_ms) {
+            info!(
+                "VAD: Calibrating to ambient noise over the next {}ms; keep quiet",
+                duration_ms
+            );
+        } else {
+            info!("VAD: Engine adapts on its own; calibration request ignored");
+        }
+    }
+
+    async fn process_frame(&mut self, frame: SharedAudioFrame) {
+        trace!(
+            "VAD: Processing frame {:?} with {} samples",
+            frame.timestamp,
+            frame.samples.len()
+        );
+
+        if let Some(metrics) = &self.metrics {
+            if let Some(fps) = self.fps_tracker.tick() {
+                metrics.update_vad_fps(fps);
+            }
+        }
+
+        // Audio was dropped before this frame; detector history no longer
+        // lines up with the stream, so close any utterance and start fresh
+        if frame.discontinuity {
+            info!("VAD: Audio discontinuity; resetting detector");
+            self.force_speech_end().await;
+            self.adapter.reset();
+        }
+
+        // Process i16 samples directly (zero-copy from SharedAudioFrame)
+        match self.adapter.process(&frame.samples) {
+            Ok(Some(event)) => {
+                self.events_generated += 1;
+
+                // Log the specific VAD event
+                match &event {
+                    VadEvent::SpeechStart {
+                        timestamp_ms,
+                        energy_db,
+                    } => {
+                        debug!(
+                            "VAD: Speech started at {}ms (energy: {:.2} dB)",
+                            timestamp_ms, energy_db
+                        );
+                        self.speech_started = Some((*timestamp_ms, Instant::now()));
+                    }
+                    VadEvent::SpeechEnd {
+                        timestamp_ms,
+                        duration_ms,
+                        energy_db,
+                    } => {
+                        debug!(
+                            "VAD: Speech ended at {}ms (duration: {}ms, energy: {:.2} dB)",
+                            timestamp_ms, duration_ms, energy_db
+                        );
+                        self.speech_started = None;
+                    }
+                }
+
+                debug!(
+                    "VAD event: {:?} @ {}ms",
+                    event,
+                    match &event {
+                        VadEvent::SpeechStart { timestamp_ms, .. } => *timestamp_ms,
+                        VadEvent::SpeechEnd { timestamp_ms, .. } => *timestamp_ms,
+                    }
+                );
+
+                if let Err(e) = self.event_tx.send(event).await {
+                    error!("Failed to send VAD event: {}", e);
+                }
+            }
+            Ok(None) => {
+                // No event generated
+            }
+            Err(e) => {
+                error!("VAD processing error: {}", e);
+            }
+        }
+
+        self.frames_processed += 1;
+
+        if self.frames_processed.is_multiple_of(100) {
+            tracing::debug!(
+                "VAD: Received {} frames, processing active",
+                self.frames_processed
+            );
+        }
+
+        if self.frames_processed.is_multiple_of(1000) {
+            debug!(
+                "VAD processor: {} frames processed, {} events generated, current state: {:?}",
+                self.frames_processed,
+                self.events_generated,
+                self.adapter.current_state()
+            );
+        }
+    }
+
+    pub fn spawn(
+        config: UnifiedVadConfig,
+        audio_rx: broadcast::Receiver<SharedAudioFrame>,
+        event_tx: Sender<VadEvent>,
+        metrics: Option<Arc<PipelineMetrics>>,
+    ) -> Result<JoinHandle<()>, String> {
+        Self::spawn_with_finalize(config, audio_rx, event_tx, metrics, None, None, None)
+    }
+
+    /// Like [`Self::spawn`], ending the current utterance on each message
+    /// from `finalize_rx`, retuning on each change of `tuning_rx` and
+    /// calibrating on each request from `calibrate_rx`
+    pub fn spawn_with_finalize(
+        config: UnifiedVadConfig,
+        audio_rx: broadcast::Receiver<SharedAudioFrame>,
+        event_tx: Sender<VadEvent>,
+        metrics: Option<Arc<PipelineMetrics>>,
+        finalize_rx: Option<broadcast::Receiver<()>>,
+        tuning_rx: Option<watch::Receiver<SileroConfig>>,
+        calibrate_rx: Option<broadcast::Receiver<u32>>,
+    ) -> Result<JoinHandle<()>, String> {
+        tracing::info!("VAD processor task spawning for mode: {:?}", config.mode);
+        let mut processor = VadProcessor::new(config, audio_rx, event_tx, metrics)?;
+        if let Some(rx) = finalize_rx {
+            processor = processor.with_finalize_signal(rx);
+        }
+        if let Some(rx) = tuning_rx {
+            processor = processor.with_tuning(rx);
+        }
+        if let Some(rx) = calibrate_rx {
+            processor = processor.with_calibration_requests(rx);
+        }
+
+        let handle = tokio::spawn(async move {
+            processor.run().await;
+        });
+
+        Ok(handle)
+    }
+}
+
+/// Next finalize request; never resolves without a finalize channel
+async fn next_finalize(
+    rx: &mut Option<broadcast::Receiver<()>>,
+) -> Result<(), broadcast::error::RecvError> {
+    match rx {
+        Some(rx) => rx.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Next tuning change, or None once the sender is gone; never resolves
+/// without a tuning channel
+async fn next_tuning(rx: &mut Option<watch::Receiver<SileroConfig>>) -> Option<SileroConfig> {
+    match rx {
+        Some(rx) => match rx.changed().await {
+            Ok(()) => Some(rx.borrow_and_update().clone()),
+            Err(_) => None,
+        },
+        None => std::future::pending().await,
+    }
+}
+
+/// Next calibration request; never resolves without a calibration channel
+async fn next_calibration(
+    rx: &mut Option<broadcast::Receiver<u32>>,
+) -> Result<u32, broadcast::error::RecvError> {
+    match rx {
+        Some(rx) => rx.recv().await,
+        None => std::future::pending().await,
+    }
+}
@@ -0,0 +1,396 @@
+//! Always-on flight recorder for bug reports.
+//!
+//! The [`BlackBox`] keeps the last few seconds of pipeline audio and the most
+//! recent pipeline events (VAD, transcripts, device changes, quality
+//! warnings, hotkey actions) in memory. The `save_black_box` hotkey, `SIGUSR1`
+//! or the dashboard's `b` key write them out as a bundle under
+//! `$XDG_STATE_HOME/coldvox/black-box/<timestamp>/`:
+//!
+//! - `audio.wav`: the buffered audio
+//! - `events.json`: build and timing info plus the buffered events, with
+//!   secrets and personal data in transcripts masked
+//! - `config.json`: the effective settings with secrets stripped
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Local};
+use coldvox_audio::SharedAudioFrame;
+use coldvox_audio_quality::QualityWarning;
+use coldvox_foundation::DeviceEvent;
+use coldvox_stt::TranscriptionEvent;
+use coldvox_vad::VadEvent;
+use parking_lot::Mutex;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// Events kept besides the audio
+pub const MAX_EVENTS: usize = 1000;
+
+const REDACTED: &str = "<redacted>";
+
+/// What to record and where bundles go
+#[derive(Debug, Clone)]
+pub struct BlackBoxConfig {
+    /// Seconds of audio kept
+    pub seconds: u32,
+    /// Directory bundles are written under
+    pub dir: PathBuf,
+    /// Effective settings; secrets are stripped before anything is kept
+    pub settings: Value,
+}
+
+impl BlackBoxConfig {
+    /// `$XDG_STATE_HOME/coldvox/black-box`, falling back to
+    /// `~/.local/state/coldvox/black-box`
+    pub fn default_dir() -> Option<PathBuf> {
+        let state_dir = std::env::var_os("XDG_STATE_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local").join("state"))
+            })?;
+        Some(state_dir.join("coldvox").join("black-box"))
+    }
+}
+
+/// A pipeline event as kept in the recorder
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordedEvent {
+    pub at: DateTime<Local>,
+    /// Which part of the pipeline reported it, e.g. `vad` or `stt`
+    pub source: &'static str,
+    pub event: String,
+}
+
+#[derive(Serialize)]
+struct EventsFile<'a> {
+    saved_at: DateTime<Local>,
+    version: &'static str,
+    pid: u32,
+    os: &'static str,
+    /// Wall-clock time of the first sample in `audio.wav`
+    audio_started_at: Option<DateTime<Local>>,
+    audio_seconds: f64,
+    sample_rate: u32,
+    events: &'a [RecordedEvent],
+}
+
+struct AudioRing {
+    samples: VecDeque<i16>,
+    sample_rate: u32,
+    /// When the newest sample arrived
+    last_at: Option<DateTime<Local>>,
+}
+
+/// In-memory ring of recent audio and events
+pub struct BlackBox {
+    seconds: u32,
+    dir: PathBuf,
+    settings: Value,
+    audio: Mutex<AudioRing>,
+    events: Mutex<VecDeque<RecordedEvent>>,
+}
+
+impl BlackBox {
+    pub fn new(config: BlackBoxConfig) -> Self {
+        let mut settings = config.settings;
+        strip_secrets(&mut settings);
+        Self {
+            seconds: config.seconds,
+            dir: config.dir,
+            settings,
+            audio: Mutex::new(AudioRing {
+                samples: VecDeque::new(),
+                sample_rate: 0,
+                last_at: None,
+            }),
+            events: Mutex::new(VecDeque::with_capacity(MAX_EVENTS)),
+        }
+    }
+
+    pub fn record_audio(&self, sample_rate: u32, samples: &[i16]) {
+        let mut ring = self.audio.lock();
+        if ring.sample_rate != sample_rate {
+            ring.samples.clear();
+            ring.sample_rate = sample_rate;
+        }
+        let capacity = self.seconds as usize * sample_rate as usize;
+        let keep = samples.len().min(capacity);
+        let overflow = (ring.samples.len() + keep).saturating_sub(capacity);
+        ring.samples.drain(..overflow);
+        ring.samples.extend(&samples[samples.len() - keep..]);
+        ring.last_at = Some(Local::now());
+    }
+
+    pub fn record(&self, source: &'static str, event: impl std::fmt::Debug) {
+        let mut events = self.events.lock();
+        if events.len() == MAX_EVENTS {
+            events.pop_front();
+        }
+        events.push_back(RecordedEvent {
+            at: Local::now(),
+            source,
+            event: coldvox_foundation::scrub::scrub(&format!("{:?}", event)),
+        });
+    }
+
+    /// Write the buffered audio, events and settings to a new bundle
+    /// directory and return it
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let saved_at = Local::now();
+        let bundle = unique_dir(&self.dir.join(saved_at.format("%Y%m%d-%H%M%S").to_string()))?;
+
+        let (samples, sample_rate, last_at) = {
+            let ring = self.audio.lock();
+            let samples: Vec<i16> = ring.samples.iter().copied().collect();
+            (samples, ring.sample_rate, ring.last_at)
+        };
+        let audio_seconds = if sample_rate == 0 {
+            0.0
+        } else {
+            samples.len() as f64 / f64::from(sample_rate)
+        };
+        if sample_rate > 0 {
+            let spec = hound::WavSpec {
+                channels: 1,
+                sample_rate,
+                bits_per_sample: 16,
+                sample_format: hound::SampleFormat::Int,
+            };
+            let mut wav = hound::WavWriter::create(bundle.join("audio.wav"), spec)
+                .map_err(io::Error::other)?;
+            for &s in &samples {
+                wav.write_sample(s).map_err(io::Error::other)?;
+            }
+            wav.finalize().map_err(io::Error::other)?;
+        }
+
+        let events: Vec<RecordedEvent> = self.events.lock().iter().cloned().collect();
+        let audio_started_at = last_at
+            .map(|at| at - chrono::Duration::microseconds((audio_seconds * 1_000_000.0) as i64));
+        write_json(
+            &bundle.join("events.json"),
+            &EventsFile {
+                saved_at,
+                version: env!("CARGO_PKG_VERSION"),
+                pid: std::process::id(),
+                os: std::env::consts::OS,
+                audio_started_at,
+                audio_seconds,
+                sample_rate,
+                events: &events,
+            },
+        )?;
+        write_json(&bundle.join("config.json"), &self.settings)?;
+        Ok(bundle)
+    }
+}
+
+/// Create `path`, or `path-2`, `path-3`… if it exists
+fn unique_dir(path: &Path) -> io::Result<PathBuf> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut candidate = path.to_path_buf();
+    let mut n = 1;
+    loop {
+        match fs::create_dir(&candidate) {
+            Ok(()) => return Ok(candidate),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                n += 1;
+                candidate = PathBuf::from(format!("{}-{}", path.display(), n));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn write_json(path: &Path, value: &impl Serialize) -> io::Result<()> {
+    let json = serde_json::to_vec_pretty(value)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(path, json)
+}
+
+/// Blank out anything in a settings tree that could be a credential: header
+/// values, keys named like secrets, and user info and query strings in URLs
+pub fn strip_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                let key = key.to_ascii_lowercase();
+                if key == "headers" {
+                    if let Value::Object(headers) = v {
+                        for header in headers.values_mut() {
+                            *header = Value::String(REDACTED.to_string());
+                        }
+                    }
+                } else if is_secret_key(&key) && !v.is_null() {
+                    *v = Value::String(REDACTED.to_string());
+                } else {
+                    strip_secrets(v);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_secrets),
+        Value::String(s) => {
+            if let Some(stripped) = strip_url_credentials(s) {
+                *s = stripped;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_secret_key(key: &str) -> bool {
+    // Names of environment variables and parameters that hold secrets are
+    // not secrets themselves
+    if key.ends_with("_env_var") || key.ends_with("_param") {
+        return false;
+    }
+    ["password", "secret", "token", "api_key", "apikey", "credential"]
+        .iter()
+        .any(|s| key.contains(s))
+        // User-written patterns describe what the user considers sensitive
+        || key == "sensitive_patterns"
+}
+
+/// `s` without `user:pass@` and `?query` if it is a URL that has them
+fn strip_url_credentials(s: &str) -> Option<String> {
+    let (scheme, rest) = s.split_once("://")?;
+    let authority_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (authority, path) = rest.split_at(authority_end);
+    let host = authority.rsplit_once('@').map(|(_, host)| host);
+    let query = path.find('?');
+    if host.is_none() && query.is_none() {
+        return None;
+    }
+    let mut out = format!("{}://{}", scheme, host.unwrap_or(authority));
+    out.push_str(&path[..query.unwrap_or(path.len())]);
+    Some(out)
+}
+
+/// Feed the recorder from the pipeline's broadcasts until they close
+pub fn spawn_recorder(
+    black_box: Arc<BlackBox>,
+    mut audio: broadcast::Receiver<SharedAudioFrame>,
+    mut vad: broadcast::Receiver<VadEvent>,
+    mut stt: broadcast::Receiver<TranscriptionEvent>,
+    mut devices: broadcast::Receiver<DeviceEvent>,
+    mut quality: broadcast::Receiver<QualityWarning>,
+) -> JoinHandle<()> {
+    use broadcast::error::RecvError;
+
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                frame = audio.recv() => match frame {
+                    Ok(frame) => black_box.record_audio(frame.sample_rate, &frame.samples),
+                    Err(RecvError::Lagged(n)) => black_box.record("black_box", format_args!("missed {} audio frames", n)),
+                    Err(RecvError::Closed) => break,
+                },
+                Ok(event) = vad.recv() => black_box.record("vad", event),
+                Ok(event) = stt.recv() => black_box.record("stt", event),
+                Ok(event) = devices.recv() => black_box.record("device", event),
+                Ok(warning) = quality.recv() => black_box.record("quality", warning),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn black_box(dir: &Path, seconds: u32) -> BlackBox {
+        BlackBox::new(BlackBoxConfig {
+            seconds,
+            dir: dir.to_path_buf(),
+            settings: json!({ "stt": { "remote": { "headers": { "Authorization": "Bearer abc" } } } }),
+        })
+    }
+
+    #[test]
+    fn keeps_only_the_last_seconds_and_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let bb = black_box(dir.path(), 1);
+        bb.record_audio(100, &[1; 80]);
+        bb.record_audio(100, &[2; 80]);
+        {
+            let ring = bb.audio.lock();
+            assert_eq!(ring.samples.len(), 100);
+            assert_eq!(ring.samples.iter().filter(|&&s| s == 1).count(), 20);
+        }
+        // A new rate starts over
+        bb.record_audio(50, &[3; 10]);
+        assert_eq!(bb.audio.lock().samples.len(), 10);
+
+        for i in 0..MAX_EVENTS + 5 {
+            bb.record("test", i);
+        }
+        let events = bb.events.lock();
+        assert_eq!(events.len(), MAX_EVENTS);
+        assert_eq!(events.front().unwrap().event, "5");
+    }
+
+    #[test]
+    fn saves_a_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        let bb = black_box(dir.path(), 2);
+        bb.record_audio(16_000, &[7; 1600]);
+        bb.record("vad", "SpeechStart");
+
+        let first = bb.save().unwrap();
+        let second = bb.save().unwrap();
+        assert_ne!(first, second);
+
+        let wav = hound::WavReader::open(first.join("audio.wav")).unwrap();
+        assert_eq!(wav.len(), 1600);
+        let events: Value =
+            serde_json::from_slice(&fs::read(first.join("events.json")).unwrap()).unwrap();
+        assert_eq!(events["events"][0]["source"], "vad");
+        assert_eq!(events["audio_seconds"], 0.1);
+        let config = fs::read_to_string(first.join("config.json")).unwrap();
+        assert!(!config.contains("abc"));
+    }
+
+    #[test]
+    fn strips_secrets() {
+        let mut settings = json!({
+            "stt": {
+                "remote": {
+                    "base_url": "https://user:pw@example.com:8443/api?key=123",
+                    "headers": { "X-Api-Key": "k" },
+                    "auth": { "bearer_token_env_var": "COLDVOX_TOKEN" },
+                },
+                "remote_ws": { "url": "ws://localhost:8765/v1/stream", "token_query_param": "token" },
+                "api_token": "t",
+            },
+            "injection": { "sensitive_patterns": ["\\d{16}"], "allowlist": ["kate"] },
+        });
+        strip_secrets(&mut settings);
+        assert_eq!(
+            settings["stt"]["remote"]["base_url"],
+            "https://example.com:8443/api"
+        );
+        assert_eq!(settings["stt"]["remote"]["headers"]["X-Api-Key"], REDACTED);
+        assert_eq!(
+            settings["stt"]["remote"]["auth"]["bearer_token_env_var"],
+            "COLDVOX_TOKEN"
+        );
+        assert_eq!(
+            settings["stt"]["remote_ws"]["url"],
+            "ws://localhost:8765/v1/stream"
+        );
+        assert_eq!(settings["stt"]["remote_ws"]["token_query_param"], "token");
+        assert_eq!(settings["stt"]["api_token"], REDACTED);
+        assert_eq!(settings["injection"]["sensitive_patterns"], REDACTED);
+        assert_eq!(settings["injection"]["allowlist"][0], "kate");
+    }
+}
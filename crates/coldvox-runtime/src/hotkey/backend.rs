@@ -1,5 +1,5 @@
+use super::bindings::HotkeyEvent;
 use async_trait::async_trait;
-use coldvox_vad::types::VadEvent;
 use tokio::sync::mpsc::Sender;
 
 /// Represents a global hotkey/shortcut
@@ -33,10 +33,10 @@ pub trait HotkeyBackend: Send + Sync {
         shortcut: &Shortcut,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
 
-    /// Start listening for hotkey events
+    /// Start listening for hotkey events on all registered shortcuts
     async fn start_listening(
         self: Box<Self>,
-        event_tx: Sender<VadEvent>,
+        event_tx: Sender<HotkeyEvent>,
         status_tx: Option<Sender<BackendStatus>>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
 
@@ -83,7 +83,7 @@ impl HotkeyBackend for DummyBackend {
 
     async fn start_listening(
         self: Box<Self>,
-        _event_tx: Sender<VadEvent>,
+        _event_tx: Sender<HotkeyEvent>,
         status_tx: Option<Sender<BackendStatus>>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         if let Some(tx) = status_tx {
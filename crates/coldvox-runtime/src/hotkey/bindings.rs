@@ -0,0 +1,647 @@
+//! Configurable hotkey bindings.
+//!
+//! `[hotkeys.bindings]` maps action names to key chords, e.g.
+//! `push_to_talk = "Meta+Ctrl"` or `switch_stt_plugin = "Ctrl+Alt+P"`. The
+//! listener registers one global shortcut per binding with the active backend
+//! and [`HotkeyDispatcher`] turns shortcut presses into VAD events (for the
+//! activation actions) or [`HotkeyAction`] commands for the runtime.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use coldvox_vad::types::VadEvent;
+
+use super::backend::Shortcut;
+use crate::runtime::ActivationMode;
+
+/// Something a hotkey can do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HotkeyAction {
+    /// Press to start a dictation session, press again to end it.
+    ToggleListening,
+    /// Dictate while held.
+    PushToTalk,
+    /// Inject the most recent final transcript again.
+    InjectLastTranscript,
+    /// Erase the most recently injected transcript.
+    Undo,
+    /// Cycle to the next available STT plugin.
+    SwitchSttPlugin,
+    /// End the current utterance now instead of waiting for silence.
+    FinalizeNow,
+    /// Inject the held draft (buffered dictation).
+    FlushDictation,
+    /// Speak the held draft, or the last final transcript.
+    ReadBack,
+    /// Stop feeding audio to VAD/STT, press again to resume.
+    TogglePause,
+    /// Type the transcript held back for low confidence.
+    AcceptLowConfidence,
+    /// Lock injection to the active window, press again to unlock.
+    ToggleTargetLock,
+    /// Write the black box recording to disk for a bug report.
+    SaveBlackBox,
+    /// Switch to the next dictation mode (normal, spell, number, literal).
+    CycleDictationMode,
+}
+
+impl HotkeyAction {
+    pub const ALL: [HotkeyAction; 13] = [
+        HotkeyAction::ToggleListening,
+        HotkeyAction::PushToTalk,
+        HotkeyAction::InjectLastTranscript,
+        HotkeyAction::Undo,
+        HotkeyAction::SwitchSttPlugin,
+        HotkeyAction::FinalizeNow,
+        HotkeyAction::FlushDictation,
+        HotkeyAction::ReadBack,
+        HotkeyAction::TogglePause,
+        HotkeyAction::AcceptLowConfidence,
+        HotkeyAction::ToggleTargetLock,
+        HotkeyAction::SaveBlackBox,
+        HotkeyAction::CycleDictationMode,
+    ];
+
+    /// Config key, also used as the backend shortcut id.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::ToggleListening => "toggle_listening",
+            Self::PushToTalk => "push_to_talk",
+            Self::InjectLastTranscript => "inject_last_transcript",
+            Self::Undo => "undo",
+            Self::SwitchSttPlugin => "switch_stt_plugin",
+            Self::FinalizeNow => "finalize_now",
+            Self::FlushDictation => "flush_dictation",
+            Self::ReadBack => "read_back",
+            Self::TogglePause => "toggle_pause",
+            Self::AcceptLowConfidence => "accept_low_confidence",
+            Self::ToggleTargetLock => "toggle_target_lock",
+            Self::SaveBlackBox => "save_black_box",
+            Self::CycleDictationMode => "cycle_dictation_mode",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::ToggleListening => "ColdVox Toggle Listening",
+            Self::PushToTalk => "ColdVox Push-to-Talk",
+            Self::InjectLastTranscript => "ColdVox Inject Last Transcript",
+            Self::Undo => "ColdVox Undo Last Injection",
+            Self::SwitchSttPlugin => "ColdVox Switch STT Plugin",
+            Self::FinalizeNow => "ColdVox Finalize Utterance Now",
+            Self::FlushDictation => "ColdVox Flush Dictation Draft",
+            Self::ReadBack => "ColdVox Read Back Transcript",
+            Self::TogglePause => "ColdVox Pause/Resume Listening",
+            Self::AcceptLowConfidence => "ColdVox Type Held Low-Confidence Transcript",
+            Self::ToggleTargetLock => "ColdVox Lock/Unlock Injection Target Window",
+            Self::SaveBlackBox => "ColdVox Save Black Box Recording",
+            Self::CycleDictationMode => "ColdVox Cycle Dictation Mode",
+        }
+    }
+
+    /// Whether this action starts/stops dictation (as opposed to a one-shot command).
+    pub fn is_activation(&self) -> bool {
+        matches!(self, Self::ToggleListening | Self::PushToTalk)
+    }
+}
+
+impl fmt::Display for HotkeyAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+impl FromStr for HotkeyAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_ascii_lowercase().replace('-', "_");
+        Self::ALL
+            .into_iter()
+            .find(|a| a.id() == normalized)
+            .ok_or_else(|| {
+                format!(
+                    "unknown hotkey action '{}' (expected one of: {})",
+                    s,
+                    Self::ALL.map(|a| a.id()).join(", ")
+                )
+            })
+    }
+}
+
+/// A chord bound to an action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HotkeyBinding {
+    pub action: HotkeyAction,
+    /// Chord as written in the config, e.g. "Ctrl+Alt+Space".
+    pub chord: String,
+}
+
+impl HotkeyBinding {
+    pub fn new(action: HotkeyAction, chord: impl Into<String>) -> Self {
+        Self {
+            action,
+            chord: chord.into(),
+        }
+    }
+
+    pub fn shortcut(&self) -> Shortcut {
+        Shortcut {
+            id: self.action.id().to_string(),
+            description: self.action.description().to_string(),
+            default_keys: Some(self.chord.clone()),
+        }
+    }
+}
+
+/// Bindings used when the config does not override them.
+pub fn default_bindings() -> Vec<HotkeyBinding> {
+    vec![HotkeyBinding::new(HotkeyAction::PushToTalk, "Meta+Ctrl")]
+}
+
+/// Everything the hotkey listener needs from the config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HotkeyConfig {
+    pub bindings: Vec<HotkeyBinding>,
+    /// Push-to-talk presses shorter than this toggle listening instead of
+    /// ending the session on release (0 = pure push-to-talk)
+    pub hold_threshold_ms: u64,
+}
+
+impl Default for HotkeyConfig {
+    fn default() -> Self {
+        Self {
+            bindings: default_bindings(),
+            hold_threshold_ms: 0,
+        }
+    }
+}
+
+/// Parse `[hotkeys.bindings]` (action → chord). Empty chords unbind the
+/// action. Invalid entries are returned as errors alongside the valid bindings
+/// so callers can warn and carry on.
+pub fn parse_bindings(table: &HashMap<String, String>) -> (Vec<HotkeyBinding>, Vec<String>) {
+    let mut bindings = Vec::new();
+    let mut errors = Vec::new();
+    for (action, chord) in table {
+        let action = match action.parse::<HotkeyAction>() {
+            Ok(a) => a,
+            Err(e) => {
+                errors.push(e);
+                continue;
+            }
+        };
+        if chord.trim().is_empty() {
+            continue;
+        }
+        if let Err(e) = parse_chord(chord) {
+            errors.push(format!("{}: {}", action, e));
+            continue;
+        }
+        bindings.push(HotkeyBinding::new(action, chord.trim()));
+    }
+    // HashMap order is random; keep registration order stable
+    bindings.sort_by_key(|b| HotkeyAction::ALL.iter().position(|a| *a == b.action));
+    (bindings, errors)
+}
+
+// Qt modifier and key codes, as expected by KGlobalAccel's setShortcut.
+const QT_SHIFT: i32 = 0x0200_0000;
+const QT_CTRL: i32 = 0x0400_0000;
+const QT_ALT: i32 = 0x0800_0000;
+const QT_META: i32 = 0x1000_0000;
+
+/// Parse a chord like "Ctrl+Alt+Space" into a Qt key sequence code.
+/// Modifier-only chords ("Meta+Ctrl") yield just the modifier bits.
+pub fn parse_chord(chord: &str) -> Result<i32, String> {
+    let mut modifiers = 0;
+    let mut key: Option<i32> = None;
+    for part in chord.split('+').map(str::trim) {
+        let lower = part.to_ascii_lowercase();
+        let modifier = match lower.as_str() {
+            "ctrl" | "control" => Some(QT_CTRL),
+            "alt" => Some(QT_ALT),
+            "shift" => Some(QT_SHIFT),
+            "meta" | "super" | "win" => Some(QT_META),
+            _ => None,
+        };
+        if let Some(m) = modifier {
+            modifiers |= m;
+            continue;
+        }
+        if key.is_some() {
+            return Err(format!(
+                "chord '{}' has more than one non-modifier key",
+                chord
+            ));
+        }
+        key = Some(qt_key(&lower).ok_or_else(|| format!("unknown key '{}' in '{}'", part, chord))?);
+    }
+    if modifiers == 0 && key.is_none() {
+        return Err(format!("empty chord '{}'", chord));
+    }
+    Ok(modifiers | key.unwrap_or(0))
+}
+
+fn qt_key(name: &str) -> Option<i32> {
+    let code = match name {
+        "space" => 0x20,
+        "esc" | "escape" => 0x0100_0000,
+        "tab" => 0x0100_0001,
+        "backspace" => 0x0100_0003,
+        "return" | "enter" => 0x0100_0004,
+        "insert" | "ins" => 0x0100_0006,
+        "delete" | "del" => 0x0100_0007,
+        "pause" => 0x0100_0008,
+        "print" => 0x0100_0009,
+        "home" => 0x0100_0010,
+        "end" => 0x0100_0011,
+        "left" => 0x0100_0012,
+        "up" => 0x0100_0013,
+        "right" => 0x0100_0014,
+        "down" => 0x0100_0015,
+        "pageup" | "pgup" => 0x0100_0016,
+        "pagedown" | "pgdown" => 0x0100_0017,
+        "scrolllock" => 0x0100_0026,
+        "menu" => 0x0100_0055,
+        _ => {
+            let mut chars = name.chars();
+            return match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii_alphanumeric() => Some(c.to_ascii_uppercase() as i32),
+                (Some('f'), Some(_)) => match name[1..].parse::<i32>() {
+                    Ok(n @ 1..=35) => Some(0x0100_0030 + n - 1),
+                    _ => None,
+                },
+                _ => None,
+            };
+        }
+    };
+    Some(code)
+}
+
+/// Shortcut transition reported by a hotkey backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HotkeyEvent {
+    Pressed(String),
+    Released(String),
+}
+
+/// What the listener should do in response to a [`HotkeyEvent`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Dispatch {
+    Vad(VadEvent),
+    Action(HotkeyAction),
+}
+
+/// Maps backend shortcut transitions onto VAD events and commands.
+///
+/// Push-to-talk and toggle-listening only drive the pipeline in the hotkey
+/// activation modes; in VAD mode the microphone is already gated by speech
+/// detection, so they are ignored. Command actions fire on press in any mode.
+///
+/// With a non-zero hold threshold the push-to-talk key does double duty:
+/// holding it at least that long dictates until release, while a shorter tap
+/// leaves listening on until the next tap. The session starts on press either
+/// way, so the first word is never lost waiting to tell the two apart.
+#[derive(Debug, Default)]
+pub struct HotkeyDispatcher {
+    /// Timestamp of the SpeechStart we emitted for the open session, if any
+    session_started_ms: Option<u64>,
+    /// Whether the open session was started by toggle (vs. held push-to-talk)
+    toggled: bool,
+    /// When the push-to-talk key went down for the open session
+    ptt_pressed_ms: Option<u64>,
+    /// The push-to-talk press that ended a toggled session; ignore its release
+    swallow_release: bool,
+    hold_threshold_ms: u64,
+}
+
+impl HotkeyDispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable tap-to-toggle on the push-to-talk key (0 disables it).
+    pub fn with_hold_threshold(mut self, hold_threshold_ms: u64) -> Self {
+        self.hold_threshold_ms = hold_threshold_ms;
+        self
+    }
+
+    pub fn handle(
+        &mut self,
+        event: &HotkeyEvent,
+        mode: ActivationMode,
+        now_ms: u64,
+    ) -> Option<Dispatch> {
+        let (id, pressed) = match event {
+            HotkeyEvent::Pressed(id) => (id, true),
+            HotkeyEvent::Released(id) => (id, false),
+        };
+        let action = match id.parse::<HotkeyAction>() {
+            Ok(a) => a,
+            Err(_) => {
+                tracing::debug!("Ignoring unknown shortcut id '{}'", id);
+                return None;
+            }
+        };
+
+        if !action.is_activation() {
+            return pressed.then_some(Dispatch::Action(action));
+        }
+        if mode == ActivationMode::Vad {
+            tracing::debug!("Ignoring {} in VAD activation mode", action);
+            return None;
+        }
+
+        match (action, pressed) {
+            (HotkeyAction::PushToTalk, true) => {
+                if self.session_started_ms.is_none() {
+                    self.toggled = false;
+                    self.ptt_pressed_ms = Some(now_ms);
+                    Some(self.start(now_ms))
+                } else if self.toggled && self.hold_threshold_ms > 0 {
+                    // Tap while toggled on: stop listening
+                    self.swallow_release = true;
+                    self.end(now_ms)
+                } else {
+                    None // key repeat while held
+                }
+            }
+            (HotkeyAction::PushToTalk, false) => {
+                if std::mem::take(&mut self.swallow_release) {
+                    return None;
+                }
+                let pressed_at = self.ptt_pressed_ms.take()?;
+                if now_ms.saturating_sub(pressed_at) < self.hold_threshold_ms {
+                    tracing::debug!("Push-to-talk tapped; listening until the next tap");
+                    self.toggled = true;
+                    None
+                } else {
+                    self.end(now_ms)
+                }
+            }
+            (HotkeyAction::ToggleListening, true) => {
+                if self.session_started_ms.is_some() {
+                    self.end(now_ms)
+                } else {
+                    self.toggled = true;
+                    Some(self.start(now_ms))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Split the open hotkey session: end it now so STT finalizes what was
+    /// said so far, and start a new one since the key or toggle still holds
+    /// listening on. Returns nothing when no session is open.
+    pub fn finalize_now(&mut self, now_ms: u64) -> Option<[VadEvent; 2]> {
+        let started = self.session_started_ms?;
+        self.session_started_ms = Some(now_ms);
+        Some([
+            VadEvent::SpeechEnd {
+                timestamp_ms: now_ms,
+                duration_ms: now_ms.saturating_sub(started),
+                energy_db: 0.0,
+            },
+            VadEvent::SpeechStart {
+                timestamp_ms: now_ms,
+                energy_db: 0.0,
+            },
+        ])
+    }
+
+    fn start(&mut self, now_ms: u64) -> Dispatch {
+        self.session_started_ms = Some(now_ms);
+        Dispatch::Vad(VadEvent::SpeechStart {
+            timestamp_ms: now_ms,
+            energy_db: 0.0,
+        })
+    }
+
+    fn end(&mut self, now_ms: u64) -> Option<Dispatch> {
+        let started = self.session_started_ms.take()?;
+        self.toggled = false;
+        self.ptt_pressed_ms = None;
+        Some(Dispatch::Vad(VadEvent::SpeechEnd {
+            timestamp_ms: now_ms,
+            duration_ms: now_ms.saturating_sub(started),
+            energy_db: 0.0,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pressed(a: HotkeyAction) -> HotkeyEvent {
+        HotkeyEvent::Pressed(a.id().to_string())
+    }
+
+    fn released(a: HotkeyAction) -> HotkeyEvent {
+        HotkeyEvent::Released(a.id().to_string())
+    }
+
+    #[test]
+    fn parses_chords() {
+        assert_eq!(parse_chord("Ctrl+Alt+Space"), Ok(QT_CTRL | QT_ALT | 0x20));
+        assert_eq!(parse_chord("Meta+Ctrl"), Ok(QT_META | QT_CTRL));
+        assert_eq!(parse_chord("shift+f9"), Ok(QT_SHIFT | 0x0100_0038));
+        assert_eq!(parse_chord("Super+z"), Ok(QT_META | 'Z' as i32));
+        assert!(parse_chord("Ctrl+A+B").is_err());
+        assert!(parse_chord("Ctrl+Banana").is_err());
+        assert!(parse_chord("").is_err());
+    }
+
+    #[test]
+    fn parses_binding_table() {
+        let table = HashMap::from([
+            ("switch_stt_plugin".to_string(), "Ctrl+Alt+P".to_string()),
+            ("push-to-talk".to_string(), "Meta+Ctrl".to_string()),
+            ("undo".to_string(), "".to_string()),
+            ("explode".to_string(), "Ctrl+E".to_string()),
+            ("toggle_listening".to_string(), "Ctrl+Nope".to_string()),
+        ]);
+        let (bindings, errors) = parse_bindings(&table);
+        assert_eq!(
+            bindings,
+            vec![
+                HotkeyBinding::new(HotkeyAction::PushToTalk, "Meta+Ctrl"),
+                HotkeyBinding::new(HotkeyAction::SwitchSttPlugin, "Ctrl+Alt+P"),
+            ]
+        );
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn push_to_talk_spans_press_and_release() {
+        let mut d = HotkeyDispatcher::new();
+        let start = d.handle(
+            &pressed(HotkeyAction::PushToTalk),
+            ActivationMode::Hotkey,
+            100,
+        );
+        assert!(matches!(
+            start,
+            Some(Dispatch::Vad(VadEvent::SpeechStart {
+                timestamp_ms: 100,
+                ..
+            }))
+        ));
+        // Key repeat while held is ignored
+        assert_eq!(
+            d.handle(
+                &pressed(HotkeyAction::PushToTalk),
+                ActivationMode::Hotkey,
+                150
+            ),
+            None
+        );
+        let end = d.handle(
+            &released(HotkeyAction::PushToTalk),
+            ActivationMode::Hotkey,
+            400,
+        );
+        assert!(matches!(
+            end,
+            Some(Dispatch::Vad(VadEvent::SpeechEnd {
+                duration_ms: 300,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn toggle_listening_ignores_release() {
+        let mut d = HotkeyDispatcher::new();
+        let mode = ActivationMode::AlwaysOnPushToTranscribe;
+        assert!(d
+            .handle(&pressed(HotkeyAction::ToggleListening), mode, 0)
+            .is_some());
+        assert_eq!(
+            d.handle(&released(HotkeyAction::ToggleListening), mode, 10),
+            None
+        );
+        // Releasing push-to-talk does not end a toggled session
+        assert_eq!(
+            d.handle(&released(HotkeyAction::PushToTalk), mode, 20),
+            None
+        );
+        assert!(matches!(
+            d.handle(&pressed(HotkeyAction::ToggleListening), mode, 500),
+            Some(Dispatch::Vad(VadEvent::SpeechEnd {
+                duration_ms: 500,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn tap_toggles_and_hold_talks_on_the_same_key() {
+        let mut d = HotkeyDispatcher::new().with_hold_threshold(300);
+        let mode = ActivationMode::Hotkey;
+        let ptt = HotkeyAction::PushToTalk;
+
+        // Hold: start on press, end on release
+        assert!(d.handle(&pressed(ptt), mode, 0).is_some());
+        assert!(matches!(
+            d.handle(&released(ptt), mode, 800),
+            Some(Dispatch::Vad(VadEvent::SpeechEnd {
+                duration_ms: 800,
+                ..
+            }))
+        ));
+
+        // Tap: start on press, keep listening after release
+        assert!(matches!(
+            d.handle(&pressed(ptt), mode, 1000),
+            Some(Dispatch::Vad(VadEvent::SpeechStart { .. }))
+        ));
+        assert_eq!(d.handle(&released(ptt), mode, 1100), None);
+
+        // Next tap ends the session on press; its release is swallowed
+        assert!(matches!(
+            d.handle(&pressed(ptt), mode, 5000),
+            Some(Dispatch::Vad(VadEvent::SpeechEnd {
+                duration_ms: 4000,
+                ..
+            }))
+        ));
+        assert_eq!(d.handle(&released(ptt), mode, 5050), None);
+
+        // And the key works normally again afterwards
+        assert!(d.handle(&pressed(ptt), mode, 6000).is_some());
+    }
+
+    #[test]
+    fn zero_threshold_is_pure_push_to_talk() {
+        let mut d = HotkeyDispatcher::new();
+        let mode = ActivationMode::Hotkey;
+        assert!(d
+            .handle(&pressed(HotkeyAction::PushToTalk), mode, 0)
+            .is_some());
+        assert!(d
+            .handle(&released(HotkeyAction::PushToTalk), mode, 50)
+            .is_some());
+    }
+
+    #[test]
+    fn commands_fire_in_any_mode_and_activation_is_ignored_in_vad_mode() {
+        let mut d = HotkeyDispatcher::new();
+        assert_eq!(
+            d.handle(
+                &pressed(HotkeyAction::SwitchSttPlugin),
+                ActivationMode::Vad,
+                0
+            ),
+            Some(Dispatch::Action(HotkeyAction::SwitchSttPlugin))
+        );
+        assert_eq!(
+            d.handle(
+                &released(HotkeyAction::SwitchSttPlugin),
+                ActivationMode::Vad,
+                5
+            ),
+            None
+        );
+        assert_eq!(
+            d.handle(&pressed(HotkeyAction::PushToTalk), ActivationMode::Vad, 10),
+            None
+        );
+    }
+
+    #[test]
+    fn finalize_now_splits_open_session() {
+        let mut d = HotkeyDispatcher::new();
+        let mode = ActivationMode::Hotkey;
+        assert_eq!(d.finalize_now(10), None);
+
+        d.handle(&pressed(HotkeyAction::ToggleListening), mode, 100);
+        let [end, start] = d.finalize_now(400).expect("session is open");
+        assert!(matches!(
+            end,
+            VadEvent::SpeechEnd {
+                duration_ms: 300,
+                ..
+            }
+        ));
+        assert!(matches!(
+            start,
+            VadEvent::SpeechStart {
+                timestamp_ms: 400,
+                ..
+            }
+        ));
+
+        // Still listening: the next toggle ends the new session
+        assert!(matches!(
+            d.handle(&pressed(HotkeyAction::ToggleListening), mode, 900),
+            Some(Dispatch::Vad(VadEvent::SpeechEnd {
+                duration_ms: 500,
+                ..
+            }))
+        ));
+    }
+}
@@ -0,0 +1,770 @@
+//! Push-to-talk feedback indicators.
+//!
+//! While the PTT key is held the user gets a "Listening…" hint that fills in
+//! with the partial transcript and the app the text will be injected into.
+//! After release a short-lived result state follows ("Transcribing…", then
+//! the recognized text or an error) and fades out. Compositor-native
+//! surfaces are preferred:
+//!
+//! 1. KDE Plasma OSD (`org.kde.osdService.showText`)
+//! 2. GNOME Shell OSD (`org.gnome.Shell.ShowOSD`)
+//! 3. Our own `zwlr_layer_shell_v1` overlay on wlroots-style compositors
+//!    (Sway, Hyprland, river), with the `layer-shell` feature
+//! 4. Freedesktop notifications (replace-in-place)
+//! 5. The terminal bar drawn by [`RecordingIndicator`]
+
+use async_trait::async_trait;
+use coldvox_stt::TranscriptionEvent;
+use coldvox_vad::types::VadEvent;
+use crossterm::{
+    cursor::MoveTo,
+    style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
+    terminal, QueueableCommand,
+};
+use std::io::{stdout, Write};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+use tokio::task::JoinHandle;
+
+use crate::runtime::ActivationMode;
+
+/// How long the result state stays visible after the final transcript arrives.
+const RESULT_LINGER: Duration = Duration::from_millis(1500);
+
+/// OSD popups auto-hide after ~2s, so "Listening…" is re-shown while the key is held.
+const LISTENING_REFRESH: Duration = Duration::from_millis(1500);
+
+/// Maximum characters of transcript shown in the result state.
+const RESULT_PREVIEW_CHARS: usize = 48;
+
+/// Maximum characters of partial transcript shown while listening.
+const PARTIAL_PREVIEW_CHARS: usize = 40;
+
+/// Simple terminal indicator shown while recording is active.
+/// Draws a small bar centered horizontally about one-third from the bottom
+/// of the terminal. Designed to be unobtrusive and easily ignored.
+pub struct RecordingIndicator {
+    displayed: Option<String>,
+}
+
+impl RecordingIndicator {
+    pub fn new() -> Self {
+        Self { displayed: None }
+    }
+
+    /// Show the indicator if it is not already visible.
+    pub fn show(&mut self) {
+        self.show_label(" Recording ");
+    }
+
+    /// Show the indicator with a custom label, replacing any previous label.
+    pub fn show_label(&mut self, label: &str) {
+        if self.displayed.as_deref() == Some(label) {
+            return;
+        }
+        self.hide();
+        if let Ok((cols, rows)) = terminal::size() {
+            let x = cols.saturating_sub(label.chars().count() as u16) / 2;
+            let y = rows.saturating_mul(2) / 3; // one-third from bottom
+            let mut out = stdout();
+            let _ = out
+                .queue(MoveTo(x, y))
+                .and_then(|o| o.queue(SetForegroundColor(Color::White)))
+                .and_then(|o| o.queue(SetBackgroundColor(Color::DarkGrey)))
+                .and_then(|o| o.queue(Print(label)))
+                .and_then(|o| o.queue(ResetColor))
+                .and_then(|o| o.flush());
+        }
+        self.displayed = Some(label.to_string());
+    }
+
+    /// Hide the indicator if visible.
+    pub fn hide(&mut self) {
+        let Some(label) = self.displayed.take() else {
+            return;
+        };
+        if let Ok((cols, rows)) = terminal::size() {
+            let width = label.chars().count();
+            let x = cols.saturating_sub(width as u16) / 2;
+            let y = rows.saturating_mul(2) / 3;
+            let mut out = stdout();
+            let blank = " ".repeat(width);
+            let _ = out
+                .queue(MoveTo(x, y))
+                .and_then(|o| o.queue(Print(blank)))
+                .and_then(|o| o.flush());
+        }
+    }
+}
+
+impl Default for RecordingIndicator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What the indicator is currently communicating to the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndicatorState {
+    /// Nothing to show; the surface should be hidden.
+    Idle,
+    /// PTT key is held and audio is being captured.
+    Listening,
+    /// Still listening, with the transcript so far and the injection target.
+    Partial {
+        text: String,
+        target: Option<String>,
+    },
+    /// Key released, waiting for the final transcript.
+    Processing,
+    /// Final transcript received.
+    Result { text: String },
+    /// Transcription failed.
+    Error { message: String },
+}
+
+impl IndicatorState {
+    /// Short human-readable label for the state.
+    pub fn label(&self) -> String {
+        match self {
+            IndicatorState::Idle => String::new(),
+            IndicatorState::Listening => "Listening…".to_string(),
+            IndicatorState::Partial { text, target } => {
+                let text = text.trim();
+                // The newest words matter most, so long partials keep their tail
+                let preview = if text.is_empty() {
+                    "Listening…".to_string()
+                } else if text.chars().count() > PARTIAL_PREVIEW_CHARS {
+                    let skip = text.chars().count() - PARTIAL_PREVIEW_CHARS;
+                    format!("…{}", text.chars().skip(skip).collect::<String>())
+                } else {
+                    text.to_string()
+                };
+                match target {
+                    Some(app) => format!("{} → {}", preview, app),
+                    None => preview,
+                }
+            }
+            IndicatorState::Processing => "Transcribing…".to_string(),
+            IndicatorState::Result { text } => {
+                let text = text.trim();
+                if text.is_empty() {
+                    "No speech recognized".to_string()
+                } else if text.chars().count() > RESULT_PREVIEW_CHARS {
+                    let preview: String = text.chars().take(RESULT_PREVIEW_CHARS).collect();
+                    format!("✓ {}…", preview)
+                } else {
+                    format!("✓ {}", text)
+                }
+            }
+            IndicatorState::Error { message } => format!("✗ {}", message),
+        }
+    }
+
+    /// Freedesktop icon name matching the state.
+    pub fn icon_name(&self) -> &'static str {
+        match self {
+            IndicatorState::Idle => "",
+            IndicatorState::Listening | IndicatorState::Partial { .. } => "audio-input-microphone",
+            IndicatorState::Processing => "content-loading-symbolic",
+            IndicatorState::Result { .. } => "emblem-ok-symbolic",
+            IndicatorState::Error { .. } => "dialog-error",
+        }
+    }
+}
+
+/// Which indicator surface to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndicatorKind {
+    /// Pick the best available surface for the running desktop.
+    Auto,
+    KdeOsd,
+    GnomeOsd,
+    Notification,
+    /// Overlay bar on layer-shell compositors (needs the `layer-shell` feature).
+    LayerShell,
+    Terminal,
+    /// No indicator at all.
+    Off,
+}
+
+impl FromStr for IndicatorKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" | "" => Ok(IndicatorKind::Auto),
+            "kde" | "kde-osd" => Ok(IndicatorKind::KdeOsd),
+            "gnome" | "gnome-osd" => Ok(IndicatorKind::GnomeOsd),
+            "notification" | "notify" => Ok(IndicatorKind::Notification),
+            "layer-shell" | "layershell" | "overlay" => Ok(IndicatorKind::LayerShell),
+            "terminal" => Ok(IndicatorKind::Terminal),
+            "off" | "none" | "disabled" => Ok(IndicatorKind::Off),
+            other => Err(format!("unknown indicator '{}'", other)),
+        }
+    }
+}
+
+/// A surface capable of rendering [`IndicatorState`]s.
+#[async_trait]
+pub trait IndicatorSink: Send {
+    /// Render the given state. `Idle` hides the surface where possible.
+    async fn show(
+        &mut self,
+        state: &IndicatorState,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Name for logging.
+    fn name(&self) -> &'static str;
+}
+
+/// Terminal bar fallback.
+#[derive(Default)]
+pub struct TerminalIndicator {
+    inner: RecordingIndicator,
+}
+
+#[async_trait]
+impl IndicatorSink for TerminalIndicator {
+    async fn show(
+        &mut self,
+        state: &IndicatorState,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        match state {
+            IndicatorState::Idle => self.inner.hide(),
+            other => self.inner.show_label(&format!(" {} ", other.label())),
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "terminal"
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod dbus {
+    use super::{IndicatorSink, IndicatorState};
+    use async_trait::async_trait;
+    use coldvox_foundation::notify;
+    use std::collections::HashMap;
+    use zbus::zvariant::Value;
+    use zbus::{Connection, Proxy};
+
+    type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+    /// Returns true if `name` currently has an owner on the session bus.
+    pub(super) async fn name_has_owner(conn: &Connection, name: &str) -> bool {
+        let Ok(proxy) = Proxy::new(
+            conn,
+            "org.freedesktop.DBus",
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus",
+        )
+        .await
+        else {
+            return false;
+        };
+        proxy
+            .call::<_, _, bool>("NameHasOwner", &(name,))
+            .await
+            .unwrap_or(false)
+    }
+
+    /// KDE Plasma on-screen display.
+    pub struct KdeOsdIndicator {
+        conn: Connection,
+    }
+
+    impl KdeOsdIndicator {
+        pub fn new(conn: Connection) -> Self {
+            Self { conn }
+        }
+    }
+
+    #[async_trait]
+    impl IndicatorSink for KdeOsdIndicator {
+        async fn show(&mut self, state: &IndicatorState) -> Result<(), BoxError> {
+            // The Plasma OSD hides itself; there is nothing to do for Idle.
+            if *state == IndicatorState::Idle {
+                return Ok(());
+            }
+            let proxy = Proxy::new(
+                &self.conn,
+                "org.kde.plasmashell",
+                "/org/kde/osdService",
+                "org.kde.osdService",
+            )
+            .await?;
+            proxy
+                .call_method("showText", &(state.icon_name(), state.label()))
+                .await?;
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "kde-osd"
+        }
+    }
+
+    /// GNOME Shell on-screen display.
+    pub struct GnomeOsdIndicator {
+        conn: Connection,
+    }
+
+    impl GnomeOsdIndicator {
+        pub fn new(conn: Connection) -> Self {
+            Self { conn }
+        }
+    }
+
+    #[async_trait]
+    impl IndicatorSink for GnomeOsdIndicator {
+        async fn show(&mut self, state: &IndicatorState) -> Result<(), BoxError> {
+            if *state == IndicatorState::Idle {
+                return Ok(());
+            }
+            let proxy = Proxy::new(
+                &self.conn,
+                "org.gnome.Shell",
+                "/org/gnome/Shell",
+                "org.gnome.Shell",
+            )
+            .await?;
+            let label = state.label();
+            let mut params: HashMap<&str, Value<'_>> = HashMap::new();
+            params.insert("icon", Value::from(state.icon_name()));
+            params.insert("label", Value::from(label.as_str()));
+            proxy.call_method("ShowOSD", &(params,)).await?;
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "gnome-osd"
+        }
+    }
+
+    /// Freedesktop notification that is replaced in place as the state changes.
+    pub struct NotificationIndicator {
+        conn: Connection,
+        notification_id: u32,
+    }
+
+    impl NotificationIndicator {
+        pub fn new(conn: Connection) -> Self {
+            Self {
+                conn,
+                notification_id: 0,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl IndicatorSink for NotificationIndicator {
+        async fn show(&mut self, state: &IndicatorState) -> Result<(), BoxError> {
+            if *state == IndicatorState::Idle {
+                if self.notification_id != 0 {
+                    let _ = notify::close(&self.conn, self.notification_id).await;
+                    self.notification_id = 0;
+                }
+                return Ok(());
+            }
+
+            let label = state.label();
+            let notification = notify::Notification {
+                icon: state.icon_name(),
+                transient: true,
+                replaces_id: self.notification_id,
+                ..notify::Notification::new("ColdVox", &label, notify::Urgency::Low)
+            };
+            self.notification_id = notify::show(&self.conn, &notification).await?;
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "notification"
+        }
+    }
+}
+
+/// Build the indicator sink for `kind`, probing the desktop for `Auto`.
+pub async fn detect_indicator(kind: IndicatorKind) -> Option<Box<dyn IndicatorSink>> {
+    match kind {
+        IndicatorKind::Off => None,
+        IndicatorKind::Terminal => Some(Box::new(TerminalIndicator::default())),
+        #[cfg(target_os = "linux")]
+        _ => Some(detect_desktop_indicator(kind).await),
+        #[cfg(not(target_os = "linux"))]
+        _ => Some(Box::new(TerminalIndicator::default())),
+    }
+}
+
+#[cfg(target_os = "linux")]
+async fn detect_desktop_indicator(kind: IndicatorKind) -> Box<dyn IndicatorSink> {
+    let conn = match zbus::Connection::session().await {
+        Ok(conn) => Some(conn),
+        Err(e) => {
+            tracing::debug!("No session bus for PTT indicator: {}", e);
+            None
+        }
+    };
+    let owned = |name: &'static str| {
+        let conn = conn.clone();
+        async move {
+            match &conn {
+                Some(conn) => dbus::name_has_owner(conn, name).await,
+                None => false,
+            }
+        }
+    };
+    let chosen = match kind {
+        IndicatorKind::Auto => {
+            if owned("org.kde.plasmashell").await {
+                IndicatorKind::KdeOsd
+            } else if owned("org.gnome.Shell").await {
+                IndicatorKind::GnomeOsd
+            } else if let Some(overlay) = layer_shell_indicator().await {
+                return overlay;
+            } else if owned("org.freedesktop.Notifications").await {
+                IndicatorKind::Notification
+            } else {
+                IndicatorKind::Terminal
+            }
+        }
+        IndicatorKind::LayerShell => match layer_shell_indicator().await {
+            Some(overlay) => return overlay,
+            None => IndicatorKind::Terminal,
+        },
+        other => other,
+    };
+    match (chosen, conn) {
+        (IndicatorKind::KdeOsd, Some(conn)) => Box::new(dbus::KdeOsdIndicator::new(conn)),
+        (IndicatorKind::GnomeOsd, Some(conn)) => Box::new(dbus::GnomeOsdIndicator::new(conn)),
+        (IndicatorKind::Notification, Some(conn)) => {
+            Box::new(dbus::NotificationIndicator::new(conn))
+        }
+        _ => Box::new(TerminalIndicator::default()),
+    }
+}
+
+/// The layer-shell overlay, when the compositor supports it
+#[cfg(target_os = "linux")]
+async fn layer_shell_indicator() -> Option<Box<dyn IndicatorSink>> {
+    #[cfg(feature = "layer-shell")]
+    match super::layer_shell::LayerShellIndicator::connect().await {
+        Ok(overlay) => return Some(Box::new(overlay)),
+        Err(e) => tracing::debug!("No layer-shell overlay for PTT indicator: {}", e),
+    }
+    None
+}
+
+/// Tracks PTT feedback state from VAD/hotkey and transcription events.
+#[derive(Debug, Default)]
+pub struct IndicatorTracker {
+    state: Option<IndicatorState>,
+    /// Partial transcript of the current press.
+    partial: String,
+    /// App that had focus when the current press started.
+    target: Option<String>,
+}
+
+impl IndicatorTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current state (Idle when nothing has happened yet).
+    pub fn state(&self) -> IndicatorState {
+        self.state.clone().unwrap_or(IndicatorState::Idle)
+    }
+
+    /// True while the key is held.
+    pub fn is_listening(&self) -> bool {
+        matches!(
+            self.state(),
+            IndicatorState::Listening | IndicatorState::Partial { .. }
+        )
+    }
+
+    /// Apply a VAD/hotkey event; returns the new state if it changed.
+    pub fn on_vad(&mut self, event: &VadEvent) -> Option<IndicatorState> {
+        let next = match event {
+            VadEvent::SpeechStart { .. } => {
+                self.partial.clear();
+                self.target = None;
+                IndicatorState::Listening
+            }
+            VadEvent::SpeechEnd { .. } => IndicatorState::Processing,
+        };
+        self.transition(next)
+    }
+
+    /// Record the injection target for the current press.
+    pub fn set_target(&mut self, target: Option<String>) -> Option<IndicatorState> {
+        self.target = target.filter(|t| !t.is_empty());
+        self.listening_state()
+    }
+
+    /// Apply a transcription event; partials only update the listening state.
+    pub fn on_transcription(&mut self, event: &TranscriptionEvent) -> Option<IndicatorState> {
+        let next = match event {
+            TranscriptionEvent::Partial { text, .. } => {
+                self.partial = text.clone();
+                return self.listening_state();
+            }
+            TranscriptionEvent::Final { text, .. } => {
+                // A final that lands while the key is held again belongs to the
+                // previous press; keep showing "Listening…".
+                if self.is_listening() {
+                    return None;
+                }
+                IndicatorState::Result { text: text.clone() }
+            }
+            TranscriptionEvent::Error { message, .. } => IndicatorState::Error {
+                message: message.clone(),
+            },
+        };
+        self.transition(next)
+    }
+
+    /// Clear a lingering result/error state.
+    pub fn expire(&mut self) -> Option<IndicatorState> {
+        match self.state() {
+            IndicatorState::Result { .. } | IndicatorState::Error { .. } => {
+                self.transition(IndicatorState::Idle)
+            }
+            _ => None,
+        }
+    }
+
+    /// Refresh the listening state with the latest partial and target.
+    fn listening_state(&mut self) -> Option<IndicatorState> {
+        if !self.is_listening() {
+            return None;
+        }
+        if self.partial.trim().is_empty() && self.target.is_none() {
+            return self.transition(IndicatorState::Listening);
+        }
+        self.transition(IndicatorState::Partial {
+            text: self.partial.clone(),
+            target: self.target.clone(),
+        })
+    }
+
+    fn transition(&mut self, next: IndicatorState) -> Option<IndicatorState> {
+        if self.state.as_ref() == Some(&next) {
+            return None;
+        }
+        self.state = Some(next.clone());
+        Some(next)
+    }
+}
+
+/// Spawn the PTT indicator task.
+///
+/// Feedback is only rendered while the runtime is in a hotkey-driven
+/// activation mode; in VAD mode events are tracked but not shown.
+pub fn spawn_indicator(
+    kind: IndicatorKind,
+    mut vad_rx: broadcast::Receiver<VadEvent>,
+    mut stt_rx: broadcast::Receiver<TranscriptionEvent>,
+    mode: Arc<RwLock<ActivationMode>>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let Some(mut sink) = detect_indicator(kind).await else {
+            return;
+        };
+        tracing::info!("PTT indicator using {} surface", sink.name());
+
+        let mut tracker = IndicatorTracker::new();
+        let mut refresh = tokio::time::interval(LISTENING_REFRESH);
+        refresh.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let linger = tokio::time::sleep(Duration::from_secs(0));
+        tokio::pin!(linger);
+        let mut lingering = false;
+        let (target_tx, mut target_rx) = tokio::sync::mpsc::channel::<Option<String>>(4);
+
+        loop {
+            let changed = tokio::select! {
+                ev = vad_rx.recv() => match ev {
+                    Ok(ev) => {
+                        if matches!(ev, VadEvent::SpeechStart { .. }) {
+                            // Window lookups shell out, so keep them off this loop
+                            let target_tx = target_tx.clone();
+                            tokio::spawn(async move {
+                                let _ = target_tx.send(focused_app().await).await;
+                            });
+                        }
+                        tracker.on_vad(&ev)
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => None,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                ev = stt_rx.recv() => match ev {
+                    Ok(ev) => tracker.on_transcription(&ev),
+                    Err(broadcast::error::RecvError::Lagged(_)) => None,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                Some(target) = target_rx.recv() => tracker.set_target(target),
+                _ = &mut linger, if lingering => {
+                    lingering = false;
+                    tracker.expire()
+                }
+                _ = refresh.tick() => tracker.is_listening().then(|| tracker.state()),
+            };
+
+            let Some(state) = changed else {
+                continue;
+            };
+
+            if matches!(
+                state,
+                IndicatorState::Result { .. } | IndicatorState::Error { .. }
+            ) {
+                linger
+                    .as_mut()
+                    .reset(tokio::time::Instant::now() + RESULT_LINGER);
+                lingering = true;
+            }
+
+            if *mode.read().await == ActivationMode::Vad {
+                continue;
+            }
+
+            if let Err(e) = sink.show(&state).await {
+                tracing::debug!("PTT indicator {} failed: {}", sink.name(), e);
+            }
+        }
+
+        let _ = sink.show(&IndicatorState::Idle).await;
+    })
+}
+
+/// Class of the focused window, which is where the transcript will be injected.
+async fn focused_app() -> Option<String> {
+    tokio::task::spawn_blocking(crate::text_injection::window_manager::get_active_window_class)
+        .await
+        .ok()?
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn final_event(text: &str) -> TranscriptionEvent {
+        TranscriptionEvent::Final {
+            utterance_id: 1,
+            text: text.to_string(),
+            words: None,
+            source: coldvox_stt::SourceId::default(),
+        }
+    }
+
+    #[test]
+    fn tracker_follows_ptt_lifecycle() {
+        let mut tracker = IndicatorTracker::new();
+        assert_eq!(
+            tracker.on_vad(&VadEvent::SpeechStart {
+                timestamp_ms: 0,
+                energy_db: 0.0
+            }),
+            Some(IndicatorState::Listening)
+        );
+        assert_eq!(
+            tracker.on_vad(&VadEvent::SpeechEnd {
+                timestamp_ms: 500,
+                duration_ms: 500,
+                energy_db: 0.0
+            }),
+            Some(IndicatorState::Processing)
+        );
+        assert_eq!(
+            tracker.on_transcription(&final_event("hello world")),
+            Some(IndicatorState::Result {
+                text: "hello world".to_string()
+            })
+        );
+        assert_eq!(tracker.expire(), Some(IndicatorState::Idle));
+        assert_eq!(tracker.expire(), None);
+    }
+
+    #[test]
+    fn final_during_new_press_keeps_listening() {
+        let mut tracker = IndicatorTracker::new();
+        tracker.on_vad(&VadEvent::SpeechStart {
+            timestamp_ms: 0,
+            energy_db: 0.0,
+        });
+        assert_eq!(tracker.on_transcription(&final_event("late")), None);
+        assert_eq!(tracker.state(), IndicatorState::Listening);
+    }
+
+    #[test]
+    fn partials_and_target_fill_in_listening_state() {
+        let mut tracker = IndicatorTracker::new();
+        tracker.on_vad(&VadEvent::SpeechStart {
+            timestamp_ms: 0,
+            energy_db: 0.0,
+        });
+        let state = tracker.set_target(Some("firefox".to_string())).unwrap();
+        assert_eq!(state.label(), "Listening… → firefox");
+
+        let partial = TranscriptionEvent::Partial {
+            utterance_id: 1,
+            text: "hello wor".to_string(),
+            t0: None,
+            t1: None,
+            source: coldvox_stt::SourceId::default(),
+        };
+        let state = tracker.on_transcription(&partial).unwrap();
+        assert_eq!(state.label(), "hello wor → firefox");
+        assert!(tracker.is_listening());
+
+        // Partials after release don't bring the listening state back
+        tracker.on_vad(&VadEvent::SpeechEnd {
+            timestamp_ms: 500,
+            duration_ms: 500,
+            energy_db: 0.0,
+        });
+        assert_eq!(tracker.on_transcription(&partial), None);
+        assert_eq!(tracker.state(), IndicatorState::Processing);
+
+        // A new press starts from scratch
+        assert_eq!(
+            tracker.on_vad(&VadEvent::SpeechStart {
+                timestamp_ms: 1000,
+                energy_db: 0.0
+            }),
+            Some(IndicatorState::Listening)
+        );
+    }
+
+    #[test]
+    fn result_label_is_truncated() {
+        let long = "a".repeat(RESULT_PREVIEW_CHARS + 10);
+        let label = IndicatorState::Result { text: long }.label();
+        assert!(label.ends_with('…'));
+        assert_eq!(label.chars().count(), RESULT_PREVIEW_CHARS + 3);
+        assert_eq!(
+            IndicatorState::Result {
+                text: "  ".to_string()
+            }
+            .label(),
+            "No speech recognized"
+        );
+    }
+
+    #[test]
+    fn indicator_kind_parses_config_values() {
+        assert_eq!("auto".parse(), Ok(IndicatorKind::Auto));
+        assert_eq!("KDE".parse(), Ok(IndicatorKind::KdeOsd));
+        assert_eq!("gnome-osd".parse(), Ok(IndicatorKind::GnomeOsd));
+        assert_eq!("notification".parse(), Ok(IndicatorKind::Notification));
+        assert_eq!("layer-shell".parse(), Ok(IndicatorKind::LayerShell));
+        assert_eq!("off".parse(), Ok(IndicatorKind::Off));
+        assert!("blinkenlights".parse::<IndicatorKind>().is_err());
+    }
+}
@@ -1,17 +1,20 @@
 use super::backend::{BackendStatus, HotkeyBackend, Shortcut};
+use super::bindings::{parse_chord, HotkeyEvent};
 use async_trait::async_trait;
-use coldvox_vad::types::VadEvent;
 use futures::StreamExt;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc::Sender;
 use zbus::{Connection, Proxy};
 
 /// KDE KGlobalAccel backend for native Plasma integration
+///
+/// Each registered [`Shortcut`] becomes one KGlobalAccel action under the
+/// component, so users can rebind them in System Settings → Shortcuts.
 pub struct KGlobalAccelBackend {
     connection: Option<Arc<Connection>>,
     component_name: String,
-    action_name: String,
     shortcuts: Vec<Shortcut>,
 }
 
@@ -23,14 +26,13 @@ impl Default for KGlobalAccelBackend {
 
 impl KGlobalAccelBackend {
     pub fn new() -> Self {
-        Self::with_names("coldvox".to_string(), "push_to_talk".to_string())
+        Self::with_component("coldvox".to_string())
     }
 
-    pub fn with_names(component: String, action: String) -> Self {
+    pub fn with_component(component: String) -> Self {
         Self {
             connection: None,
             component_name: component,
-            action_name: action,
             shortcuts: Vec::new(),
         }
     }
@@ -72,14 +74,13 @@ impl HotkeyBackend for KGlobalAccelBackend {
         // Get connection
         let conn = self.connection.as_ref().ok_or("Not initialized")?;
 
-        // Try to programmatically register the shortcut
-        // Default: Left Ctrl + Super (Meta)
-        let default_shortcut = "Meta+Ctrl";
+        let keys = shortcut.default_keys.as_deref().unwrap_or_default();
 
         tracing::info!(
-            "Registering global shortcut for component: '{}', action: '{}'",
+            "Registering global shortcut for component: '{}', action: '{}' ({})",
             self.component_name,
-            self.action_name
+            shortcut.id,
+            keys
         );
 
         // Create proxy to main KGlobalAccel service
@@ -91,16 +92,20 @@ impl HotkeyBackend for KGlobalAccelBackend {
         )
         .await?;
 
-        // First, register the component and action using doRegister
-        // doRegister signature: as (array of strings)
-        let action_id = vec![
+        // Action specifier: componentUnique, actionUnique, componentFriendly, actionFriendly
+        let action_spec = vec![
             self.component_name.clone(),
-            self.action_name.clone(),
-            "ColdVox Push-to-Talk".to_string(),
+            shortcut.id.clone(),
             "ColdVox".to_string(),
+            shortcut.description.clone(),
         ];
 
-        match proxy.call_method("doRegister", &(action_id.clone(),)).await {
+        // First, register the component and action using doRegister
+        // doRegister signature: as (array of strings)
+        match proxy
+            .call_method("doRegister", &(action_spec.clone(),))
+            .await
+        {
             Ok(_) => {
                 tracing::debug!("Component/action registered with KGlobalAccel");
             }
@@ -109,44 +114,37 @@ impl HotkeyBackend for KGlobalAccelBackend {
             }
         }
 
+        let key_code = match parse_chord(keys) {
+            Ok(code) => code,
+            Err(e) => {
+                tracing::warn!(
+                    "Not setting a default key for '{}': {}. Bind it in KDE System Settings → Shortcuts",
+                    shortcut.id,
+                    e
+                );
+                return Ok(());
+            }
+        };
+
         // Now try to set default shortcut
         // setShortcut signature: asaiu -> ai
         // Parameters: action_list, key_codes, flags
-
-        // Qt key codes for Meta+Ctrl
-        // Meta modifier = 0x08000000
-        // Ctrl modifier = 0x04000000
-        // Combined: Meta+Ctrl (without Space)
-        let key_code = vec![
-            0x08000000_i32 | 0x04000000_i32, // Meta+Ctrl
-        ];
-
-        // Build the action specifier
-        let action_spec = vec![
-            self.component_name.clone(),
-            self.action_name.clone(),
-            "Push-to-Talk".to_string(),
-            "ColdVox".to_string(),
-        ];
-
         match proxy
             .call_method(
                 "setShortcut",
                 &(
-                    action_spec, // as: action specifier
-                    key_code,    // ai: key codes
-                    0x3u32,      // u: flags (3 = present | active | default)
+                    action_spec,    // as: action specifier
+                    vec![key_code], // ai: Qt key codes
+                    0x3u32,         // u: flags (3 = present | active | default)
                 ),
             )
             .await
         {
             Ok(_) => {
                 tracing::info!(
-                    "Successfully registered shortcut: {} (Left Super + Left Ctrl)",
-                    default_shortcut
-                );
-                tracing::info!(
-                    "The shortcut should now be active. Press Meta+Ctrl to activate push-to-talk."
+                    "Successfully registered shortcut: {} → {}",
+                    keys,
+                    shortcut.id
                 );
             }
             Err(e) => {
@@ -157,8 +155,8 @@ impl HotkeyBackend for KGlobalAccelBackend {
                 tracing::info!(
                     "Component: '{}', Action: '{}', Suggested: {}",
                     self.component_name,
-                    self.action_name,
-                    default_shortcut
+                    shortcut.id,
+                    keys
                 );
             }
         }
@@ -168,11 +166,10 @@ impl HotkeyBackend for KGlobalAccelBackend {
 
     async fn start_listening(
         self: Box<Self>,
-        event_tx: Sender<VadEvent>,
+        event_tx: Sender<HotkeyEvent>,
         status_tx: Option<Sender<BackendStatus>>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let component_name = self.component_name.clone();
-        let action_name = self.action_name.clone();
 
         // Reconnection loop with backoff
         let mut backoff = Duration::from_millis(250);
@@ -185,7 +182,6 @@ impl HotkeyBackend for KGlobalAccelBackend {
                 event_tx.clone(),
                 status_tx.clone(),
                 &component_name,
-                &action_name,
                 &mut no_event_warning_shown,
             )
             .await
@@ -209,7 +205,12 @@ impl HotkeyBackend for KGlobalAccelBackend {
 
                     // Exponential backoff with jitter
                     backoff = std::cmp::min(backoff * 2, max_backoff);
-                    let jitter = Duration::from_millis(fastrand::u64(0..100));
+                    let jitter = Duration::from_millis(
+                        coldvox_foundation::rng::global_rng()
+                            .lock()
+                            .unwrap()
+                            .range_u64(0..100),
+                    );
                     backoff += jitter;
                 }
             }
@@ -250,14 +251,13 @@ impl HotkeyBackend for KGlobalAccelBackend {
 impl KGlobalAccelBackend {
     async fn listen_with_reconnect(
         backend: &Self,
-        event_tx: Sender<VadEvent>,
+        event_tx: Sender<HotkeyEvent>,
         status_tx: Option<Sender<BackendStatus>>,
         component_name: &str,
-        action_name: &str,
         no_event_warning_shown: &mut bool,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let proxy = backend.get_component_proxy().await?;
-        let start = Instant::now();
+        let action_ids: HashSet<&str> = backend.shortcuts.iter().map(|s| s.id.as_str()).collect();
 
         // Subscribe to both Pressed and Released signals
         let mut pressed_stream = proxy.receive_signal("globalShortcutPressed").await?;
@@ -268,18 +268,17 @@ impl KGlobalAccelBackend {
         }
 
         tracing::info!(
-            "KGlobalAccel listener ready for component: '{}', action: '{}'",
+            "KGlobalAccel listener ready for component: '{}', actions: {:?}",
             component_name,
-            action_name
+            action_ids
         );
 
         // Track when we last saw an event
         let mut last_event_time = Instant::now();
         let warning_timeout = Duration::from_secs(10);
 
-        // Track pressed state for debouncing
-        let mut is_pressed = false;
-        let mut press_timestamp_ms = 0u64;
+        // Track pressed state per action for debouncing key repeat
+        let mut pressed: HashSet<String> = HashSet::new();
 
         loop {
             // Check if we should warn about no events
@@ -288,7 +287,7 @@ impl KGlobalAccelBackend {
                     "No hotkey events received for {} seconds. Please ensure a shortcut is bound in KDE System Settings.",
                     warning_timeout.as_secs()
                 );
-                tracing::info!("Component: '{}', Action: '{}'", component_name, action_name);
+                tracing::info!("Component: '{}', Actions: {:?}", component_name, action_ids);
                 *no_event_warning_shown = true;
             }
 
@@ -297,15 +296,10 @@ impl KGlobalAccelBackend {
                     if let Ok((component, action, _timestamp)) =
                         msg.body().deserialize::<(String, String, i64)>()
                     {
-                        if component == component_name && action == action_name {
+                        if component == component_name && action_ids.contains(action.as_str()) {
                             last_event_time = Instant::now();
-                            let ts_ms = start.elapsed().as_millis() as u64;
-
-                            // Debounce: only send if not already pressed
-                            if !is_pressed {
-                                is_pressed = true;
-                                press_timestamp_ms = ts_ms;  // Store press timestamp
 
+                            if pressed.insert(action.clone()) {
                                 tracing::debug!("Hotkey pressed: {} / {}", component, action);
 
                                 if let Some(tx) = &status_tx {
@@ -314,12 +308,7 @@ impl KGlobalAccelBackend {
                                         .await;
                                 }
 
-                                let _ = event_tx
-                                    .send(VadEvent::SpeechStart {
-                                        timestamp_ms: ts_ms,
-                                        energy_db: 0.0,
-                                    })
-                                    .await;
+                                let _ = event_tx.send(HotkeyEvent::Pressed(action)).await;
                             }
                         }
                     }
@@ -333,13 +322,10 @@ impl KGlobalAccelBackend {
                             component, action
                         );
 
-                        if component == component_name && action == action_name {
+                        if component == component_name && action_ids.contains(action.as_str()) {
                             last_event_time = Instant::now();
-                            let ts_ms = start.elapsed().as_millis() as u64;
-
-                            if is_pressed {
-                                is_pressed = false;
 
+                            if pressed.remove(&action) {
                                 tracing::debug!("Hotkey released: {} / {}", component, action);
 
                                 if let Some(tx) = &status_tx {
@@ -348,13 +334,7 @@ impl KGlobalAccelBackend {
                                         .await;
                                 }
 
-                                let _ = event_tx
-                                    .send(VadEvent::SpeechEnd {
-                                        timestamp_ms: ts_ms,
-                                        duration_ms: ts_ms - press_timestamp_ms,  // Calculate actual duration
-                                        energy_db: 0.0,
-                                    })
-                                    .await;
+                                let _ = event_tx.send(HotkeyEvent::Released(action)).await;
                             }
                         }
                     }
@@ -0,0 +1,537 @@
+//! PTT indicator drawn as a wlr layer-shell overlay.
+//!
+//! Compositors implementing `zwlr_layer_shell_v1` (Sway, Hyprland, river,
+//! Wayfire, niri) have no OSD service to call, so the indicator draws its
+//! own bar: a rounded pill near the bottom edge, in the overlay layer and
+//! transparent to input. The label uses a built-in 5×7 bitmap font, so only
+//! ASCII and the indicator's own symbols (…, →, ✓, ✗) are drawn; anything
+//! else shows as '?'.
+//!
+//! Wayland calls block, so the surface lives on its own thread and
+//! [`LayerShellIndicator`] only hands it the latest state.
+
+use std::io::Write;
+use std::os::fd::AsFd;
+use std::sync::mpsc;
+
+use async_trait::async_trait;
+use wayland_client::globals::{registry_queue_init, GlobalListContents};
+use wayland_client::protocol::{
+    wl_buffer::WlBuffer, wl_compositor::WlCompositor, wl_region::WlRegion, wl_registry::WlRegistry,
+    wl_shm, wl_shm::WlShm, wl_shm_pool::WlShmPool, wl_surface::WlSurface,
+};
+use wayland_client::{delegate_noop, Connection, Dispatch, EventQueue, QueueHandle};
+use wayland_protocols_wlr::layer_shell::v1::client::{
+    zwlr_layer_shell_v1::{self, ZwlrLayerShellV1},
+    zwlr_layer_surface_v1::{self, ZwlrLayerSurfaceV1},
+};
+
+use super::indicator::{IndicatorSink, IndicatorState};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Pixels per font dot
+const SCALE: u32 = 2;
+const GLYPH_WIDTH: u32 = 5;
+const GLYPH_HEIGHT: u32 = 7;
+/// Horizontal advance per character, in font dots
+const ADVANCE: u32 = GLYPH_WIDTH + 1;
+const PADDING_X: u32 = 14;
+const PADDING_Y: u32 = 9;
+const CORNER_RADIUS: u32 = 10;
+/// Distance from the bottom edge of the output
+const BOTTOM_MARGIN: i32 = 64;
+/// Longer labels are cut with '…' so the bar stays on screen
+const MAX_LABEL_CHARS: usize = 72;
+const TEXT_COLOR: u32 = 0xFFFF_FFFF;
+
+/// Overlay bar on a layer-shell compositor.
+pub struct LayerShellIndicator {
+    states: mpsc::Sender<IndicatorState>,
+}
+
+impl LayerShellIndicator {
+    /// Connect to the compositor; fails when there is no Wayland session or
+    /// the compositor has no layer-shell.
+    pub async fn connect() -> Result<Self, BoxError> {
+        let (ready_tx, ready_rx) = tokio::sync::oneshot::channel();
+        let (states, states_rx) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("coldvox-overlay".to_string())
+            .spawn(move || match Overlay::connect() {
+                Ok(overlay) => {
+                    let _ = ready_tx.send(Ok(()));
+                    overlay.run(states_rx);
+                }
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                }
+            })?;
+        ready_rx
+            .await
+            .map_err(|_| BoxError::from("layer-shell overlay thread exited"))??;
+        Ok(Self { states })
+    }
+}
+
+#[async_trait]
+impl IndicatorSink for LayerShellIndicator {
+    async fn show(&mut self, state: &IndicatorState) -> Result<(), BoxError> {
+        self.states
+            .send(state.clone())
+            .map_err(|_| BoxError::from("layer-shell overlay is gone"))
+    }
+
+    fn name(&self) -> &'static str {
+        "layer-shell"
+    }
+}
+
+/// Wayland event state
+#[derive(Default)]
+struct Events {
+    configured: bool,
+    closed: bool,
+}
+
+/// The mapped bar
+struct Shown {
+    surface: WlSurface,
+    layer: ZwlrLayerSurfaceV1,
+    buffer: Option<WlBuffer>,
+    size: (u32, u32),
+}
+
+impl Shown {
+    fn destroy(self) {
+        self.layer.destroy();
+        self.surface.destroy();
+        if let Some(buffer) = self.buffer {
+            buffer.destroy();
+        }
+    }
+}
+
+/// Owns the Wayland connection; runs on the overlay thread
+struct Overlay {
+    conn: Connection,
+    queue: EventQueue<Events>,
+    events: Events,
+    compositor: WlCompositor,
+    shm: WlShm,
+    layer_shell: ZwlrLayerShellV1,
+    shown: Option<Shown>,
+    drawn: IndicatorState,
+}
+
+impl Overlay {
+    fn connect() -> Result<Self, BoxError> {
+        let conn = Connection::connect_to_env()?;
+        let (globals, queue) = registry_queue_init::<Events>(&conn)?;
+        let qh = queue.handle();
+        // damage_buffer needs wl_surface version 4
+        let compositor = globals.bind(&qh, 4..=6, ())?;
+        let shm = globals.bind(&qh, 1..=1, ())?;
+        let layer_shell = globals.bind(&qh, 1..=4, ())?;
+        Ok(Self {
+            conn,
+            queue,
+            events: Events::default(),
+            compositor,
+            shm,
+            layer_shell,
+            shown: None,
+            drawn: IndicatorState::Idle,
+        })
+    }
+
+    /// Draw states until the indicator is dropped
+    fn run(mut self, states: mpsc::Receiver<IndicatorState>) {
+        while let Ok(state) = states.recv() {
+            // Only the newest state matters after a burst of partials
+            let state = states.try_iter().last().unwrap_or(state);
+            if state == self.drawn && !self.events.closed {
+                continue;
+            }
+            if let Err(e) = self.render(&state) {
+                tracing::warn!("Layer-shell indicator stopped: {}", e);
+                break;
+            }
+            self.drawn = state;
+        }
+        self.hide();
+        let _ = self.conn.flush();
+    }
+
+    fn render(&mut self, state: &IndicatorState) -> Result<(), BoxError> {
+        if self.events.closed {
+            // The compositor dropped the surface (e.g. output unplugged)
+            self.hide();
+        }
+        let Some(background) = background(state) else {
+            self.hide();
+            self.queue.roundtrip(&mut self.events)?;
+            return Ok(());
+        };
+        let label = fit_label(&state.label());
+        let size = bar_size(label.chars().count());
+        let qh = self.queue.handle();
+        if self.shown.is_none() {
+            self.shown = Some(self.create(size, &qh)?);
+        }
+        let shown = self.shown.as_mut().expect("surface created above");
+        if shown.size != size {
+            shown.layer.set_size(size.0, size.1);
+            shown.size = size;
+        }
+
+        let pixels = draw(size, background, &label);
+        let fd = rustix::fs::memfd_create("coldvox-indicator", rustix::fs::MemfdFlags::CLOEXEC)?;
+        let mut file = std::fs::File::from(fd);
+        file.write_all(&pixels)?;
+        let pool = self
+            .shm
+            .create_pool(file.as_fd(), pixels.len() as i32, &qh, ());
+        let buffer = pool.create_buffer(
+            0,
+            size.0 as i32,
+            size.1 as i32,
+            (size.0 * 4) as i32,
+            wl_shm::Format::Argb8888,
+            &qh,
+            (),
+        );
+        pool.destroy();
+        shown.surface.attach(Some(&buffer), 0, 0);
+        shown
+            .surface
+            .damage_buffer(0, 0, size.0 as i32, size.1 as i32);
+        shown.surface.commit();
+        if let Some(previous) = shown.buffer.replace(buffer) {
+            previous.destroy();
+        }
+        self.queue.roundtrip(&mut self.events)?;
+        Ok(())
+    }
+
+    /// Create the layer surface and wait for the compositor's first configure
+    fn create(&mut self, size: (u32, u32), qh: &QueueHandle<Events>) -> Result<Shown, BoxError> {
+        let surface = self.compositor.create_surface(qh, ());
+        // An empty input region lets clicks through to the window below
+        let region = self.compositor.create_region(qh, ());
+        surface.set_input_region(Some(&region));
+        region.destroy();
+        let layer = self.layer_shell.get_layer_surface(
+            &surface,
+            None,
+            zwlr_layer_shell_v1::Layer::Overlay,
+            "coldvox-indicator".to_string(),
+            qh,
+            (),
+        );
+        layer.set_anchor(zwlr_layer_surface_v1::Anchor::Bottom);
+        layer.set_margin(0, 0, BOTTOM_MARGIN, 0);
+        layer.set_size(size.0, size.1);
+        surface.commit();
+
+        self.events = Events::default();
+        while !self.events.configured {
+            self.queue.blocking_dispatch(&mut self.events)?;
+            if self.events.closed {
+                layer.destroy();
+                surface.destroy();
+                return Err("compositor closed the indicator surface".into());
+            }
+        }
+        Ok(Shown {
+            surface,
+            layer,
+            buffer: None,
+            size,
+        })
+    }
+
+    fn hide(&mut self) {
+        if let Some(shown) = self.shown.take() {
+            shown.destroy();
+        }
+        self.events = Events::default();
+    }
+}
+
+impl Dispatch<WlRegistry, GlobalListContents> for Events {
+    fn event(
+        _: &mut Self,
+        _: &WlRegistry,
+        _: <WlRegistry as wayland_client::Proxy>::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwlrLayerSurfaceV1, ()> for Events {
+    fn event(
+        events: &mut Self,
+        layer: &ZwlrLayerSurfaceV1,
+        event: zwlr_layer_surface_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        match event {
+            zwlr_layer_surface_v1::Event::Configure { serial, .. } => {
+                layer.ack_configure(serial);
+                events.configured = true;
+            }
+            zwlr_layer_surface_v1::Event::Closed => events.closed = true,
+            _ => {}
+        }
+    }
+}
+
+delegate_noop!(Events: ignore WlCompositor);
+delegate_noop!(Events: ignore WlSurface);
+delegate_noop!(Events: ignore WlShm);
+delegate_noop!(Events: ignore WlBuffer);
+delegate_noop!(Events: WlShmPool);
+delegate_noop!(Events: WlRegion);
+delegate_noop!(Events: ZwlrLayerShellV1);
+
+/// Bar colour (ARGB) for a state; None hides the bar
+fn background(state: &IndicatorState) -> Option<u32> {
+    match state {
+        IndicatorState::Idle => None,
+        IndicatorState::Listening | IndicatorState::Partial { .. } => Some(0xE6C6_2828),
+        IndicatorState::Processing => Some(0xE637_474F),
+        IndicatorState::Result { .. } => Some(0xE62E_7D32),
+        IndicatorState::Error { .. } => Some(0xE6BF_360C),
+    }
+}
+
+/// Cut overlong labels, keeping the start
+fn fit_label(label: &str) -> String {
+    if label.chars().count() <= MAX_LABEL_CHARS {
+        return label.to_string();
+    }
+    let mut cut: String = label.chars().take(MAX_LABEL_CHARS - 1).collect();
+    cut.push('…');
+    cut
+}
+
+/// Bar size in pixels for a label of `chars` characters
+fn bar_size(chars: usize) -> (u32, u32) {
+    let text = (chars.max(1) as u32 * ADVANCE - 1) * SCALE;
+    (text + 2 * PADDING_X, GLYPH_HEIGHT * SCALE + 2 * PADDING_Y)
+}
+
+/// Render the bar as premultiplied ARGB8888 (little-endian)
+fn draw(size: (u32, u32), background: u32, label: &str) -> Vec<u8> {
+    let (width, height) = size;
+    let mut pixels = vec![0u8; (width * height * 4) as usize];
+    let mut put = |x: u32, y: u32, argb: u32| {
+        let at = ((y * width + x) * 4) as usize;
+        pixels[at..at + 4].copy_from_slice(&premultiply(argb).to_le_bytes());
+    };
+    for y in 0..height {
+        for x in 0..width {
+            if inside_rounded(x, y, width, height, CORNER_RADIUS) {
+                put(x, y, background);
+            }
+        }
+    }
+    for (i, c) in label.chars().enumerate() {
+        let left = PADDING_X + i as u32 * ADVANCE * SCALE;
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for col in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - col)) == 0 {
+                    continue;
+                }
+                for dy in 0..SCALE {
+                    for dx in 0..SCALE {
+                        put(
+                            left + col * SCALE + dx,
+                            PADDING_Y + row as u32 * SCALE + dy,
+                            TEXT_COLOR,
+                        );
+                    }
+                }
+            }
+        }
+    }
+    pixels
+}
+
+fn premultiply(argb: u32) -> u32 {
+    let a = argb >> 24;
+    let channel = |shift: u32| ((argb >> shift) & 0xFF) * a / 255;
+    (a << 24) | (channel(16) << 16) | (channel(8) << 8) | channel(0)
+}
+
+fn inside_rounded(x: u32, y: u32, width: u32, height: u32, radius: u32) -> bool {
+    let radius = radius.min(width / 2).min(height / 2) as i64;
+    let (x, y) = (x as i64, y as i64);
+    let cx = x.clamp(radius, width as i64 - 1 - radius);
+    let cy = y.clamp(radius, height as i64 - 1 - radius);
+    (x - cx).pow(2) + (y - cy).pow(2) <= radius.pow(2)
+}
+
+/// 5×7 glyph rows, top to bottom; bit 4 is the leftmost column
+fn glyph(c: char) -> [u8; 7] {
+    match c {
+        ' '..='~' => FONT[(c as u8 - b' ') as usize],
+        '…' => [0, 0, 0, 0, 0, 0, 0b10101],
+        '→' => [0, 0b00100, 0b00010, 0b11111, 0b00010, 0b00100, 0],
+        '✓' => [0, 0b00001, 0b00010, 0b10100, 0b01000, 0, 0],
+        '✗' => [0, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0],
+        _ => FONT[(b'?' - b' ') as usize],
+    }
+}
+
+/// Printable ASCII, from ' ' to '~'
+#[rustfmt::skip]
+const FONT: [[u8; 7]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04], // !
+    [0x0A, 0x0A, 0x0A, 0x00, 0x00, 0x00, 0x00], // "
+    [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A], // #
+    [0x04, 0x0F, 0x14, 0x0E, 0x05, 0x1E, 0x04], // $
+    [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03], // %
+    [0x0C, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0D], // &
+    [0x0C, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00], // '
+    [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02], // (
+    [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08], // )
+    [0x00, 0x04, 0x15, 0x0E, 0x15, 0x04, 0x00], // *
+    [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00], // +
+    [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08], // ,
+    [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00], // -
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C], // .
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00], // /
+    [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E], // 0
+    [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E], // 1
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F], // 2
+    [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E], // 3
+    [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02], // 4
+    [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E], // 5
+    [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E], // 6
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08], // 7
+    [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E], // 8
+    [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C], // 9
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00], // :
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x04, 0x08], // ;
+    [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02], // <
+    [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00], // =
+    [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08], // >
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04], // ?
+    [0x0E, 0x11, 0x01, 0x0D, 0x15, 0x15, 0x0E], // @
+    [0x0E, 0x11, 0x11, 0x11, 0x1F, 0x11, 0x11], // A
+    [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E], // B
+    [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E], // C
+    [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C], // D
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F], // E
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10], // F
+    [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F], // G
+    [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11], // H
+    [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E], // I
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C], // J
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11], // K
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F], // L
+    [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11], // M
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11], // N
+    [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // O
+    [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10], // P
+    [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D], // Q
+    [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11], // R
+    [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E], // S
+    [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // T
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // U
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04], // V
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A], // W
+    [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11], // X
+    [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04], // Y
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F], // Z
+    [0x0E, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0E], // [
+    [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00], // \
+    [0x0E, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0E], // ]
+    [0x04, 0x0A, 0x11, 0x00, 0x00, 0x00, 0x00], // ^
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F], // _
+    [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00], // `
+    [0x00, 0x00, 0x0E, 0x01, 0x0F, 0x11, 0x0F], // a
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1E], // b
+    [0x00, 0x00, 0x0E, 0x10, 0x10, 0x11, 0x0E], // c
+    [0x01, 0x01, 0x0D, 0x13, 0x11, 0x11, 0x0F], // d
+    [0x00, 0x00, 0x0E, 0x11, 0x1F, 0x10, 0x0E], // e
+    [0x06, 0x09, 0x08, 0x1C, 0x08, 0x08, 0x08], // f
+    [0x00, 0x0F, 0x11, 0x11, 0x0F, 0x01, 0x0E], // g
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11], // h
+    [0x04, 0x00, 0x0C, 0x04, 0x04, 0x04, 0x0E], // i
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x12, 0x0C], // j
+    [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12], // k
+    [0x0C, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E], // l
+    [0x00, 0x00, 0x1A, 0x15, 0x15, 0x11, 0x11], // m
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11], // n
+    [0x00, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E], // o
+    [0x00, 0x00, 0x1E, 0x11, 0x1E, 0x10, 0x10], // p
+    [0x00, 0x00, 0x0D, 0x13, 0x0F, 0x01, 0x01], // q
+    [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10], // r
+    [0x00, 0x00, 0x0E, 0x10, 0x0E, 0x01, 0x1E], // s
+    [0x08, 0x08, 0x1C, 0x08, 0x08, 0x09, 0x06], // t
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0D], // u
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0A, 0x04], // v
+    [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0A], // w
+    [0x00, 0x00, 0x11, 0x0A, 0x04, 0x0A, 0x11], // x
+    [0x00, 0x00, 0x11, 0x11, 0x0F, 0x01, 0x0E], // y
+    [0x00, 0x00, 0x1F, 0x02, 0x04, 0x08, 0x1F], // z
+    [0x02, 0x04, 0x04, 0x08, 0x04, 0x04, 0x02], // {
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // |
+    [0x08, 0x04, 0x04, 0x02, 0x04, 0x04, 0x08], // }
+    [0x00, 0x00, 0x08, 0x15, 0x02, 0x00, 0x00], // ~
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indicator_labels_have_glyphs() {
+        let unknown = glyph('?');
+        for state in [
+            IndicatorState::Listening,
+            IndicatorState::Processing,
+            IndicatorState::Partial {
+                text: "hello".to_string(),
+                target: Some("firefox".to_string()),
+            },
+            IndicatorState::Result {
+                text: "Hello, world!".to_string(),
+            },
+            IndicatorState::Error {
+                message: "timeout".to_string(),
+            },
+        ] {
+            for c in state.label().chars() {
+                assert!(c == '?' || glyph(c) != unknown, "no glyph for {:?}", c);
+            }
+        }
+        assert_eq!(glyph('ß'), unknown);
+    }
+
+    #[test]
+    fn bar_fits_the_label() {
+        let label = fit_label(&"x".repeat(200));
+        assert_eq!(label.chars().count(), MAX_LABEL_CHARS);
+        assert!(label.ends_with('…'));
+
+        let (width, height) = bar_size(11);
+        assert_eq!(width, (11 * ADVANCE - 1) * SCALE + 2 * PADDING_X);
+        assert_eq!(height, GLYPH_HEIGHT * SCALE + 2 * PADDING_Y);
+
+        let pixels = draw((width, height), 0xFF00_0000, "Listening…");
+        assert_eq!(pixels.len(), (width * height * 4) as usize);
+        // Rounded corners stay transparent; the middle is the background
+        assert_eq!(&pixels[..4], &[0, 0, 0, 0]);
+        let middle = ((height / 2 * width + 4) * 4) as usize;
+        assert_eq!(&pixels[middle..middle + 4], &[0, 0, 0, 0xFF]);
+    }
+}
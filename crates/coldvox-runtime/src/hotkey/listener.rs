@@ -0,0 +1,116 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use coldvox_vad::types::VadEvent;
+use tokio::sync::mpsc::{self, Sender};
+use tokio::sync::{broadcast, RwLock};
+
+use super::bindings::{Dispatch, HotkeyAction, HotkeyConfig, HotkeyDispatcher, HotkeyEvent};
+use crate::runtime::ActivationMode;
+
+/// Hotkey listener for the configured bindings
+///
+/// Registers every binding with the best available backend (KDE KGlobalAccel
+/// preferred) and dispatches shortcut presses: push-to-talk and
+/// toggle-listening become `VadEvent` start/end pairs on `event_tx` while a
+/// hotkey activation mode is active; other actions are forwarded on
+/// `action_tx`. With `hold_threshold_ms` set, a short tap of the push-to-talk
+/// key toggles listening and a longer hold acts as push-to-talk. Messages on
+/// `finalize_rx` split the open session so STT finalizes it right away.
+pub fn spawn_hotkey_listener(
+    config: HotkeyConfig,
+    mode: Arc<RwLock<ActivationMode>>,
+    event_tx: Sender<VadEvent>,
+    action_tx: Sender<HotkeyAction>,
+    mut finalize_rx: broadcast::Receiver<()>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let HotkeyConfig {
+            bindings,
+            hold_threshold_ms,
+        } = config;
+        let (hotkey_tx, mut hotkey_rx) = mpsc::channel::<HotkeyEvent>(32);
+
+        // Import the KDE KGlobalAccel backend
+        #[cfg(kde_globalaccel)]
+        let backend_task = {
+            use crate::hotkey::backend;
+
+            // Detect and initialize the best backend (KDE preferred)
+            let mut backend = backend::detect_best_backend().await;
+
+            // Initialize the backend
+            let backend_name = backend.name().to_string();
+            if let Err(e) = backend.initialize().await {
+                tracing::error!("Failed to initialize {} backend: {}", backend_name, e);
+                return;
+            }
+
+            for binding in &bindings {
+                if let Err(e) = backend.register_shortcut(&binding.shortcut()).await {
+                    tracing::error!(
+                        "Failed to register {} shortcut with {} backend: {}",
+                        binding.action,
+                        backend_name,
+                        e
+                    );
+                }
+            }
+
+            // Start listening for events
+            tokio::spawn(async move {
+                if let Err(e) = backend.start_listening(hotkey_tx, None).await {
+                    tracing::error!("{} backend listening error: {}", backend_name, e);
+                }
+            })
+        };
+
+        #[cfg(not(kde_globalaccel))]
+        {
+            tracing::warn!(
+                "KDE KGlobalAccel backend not available; {} hotkey binding(s) inactive",
+                bindings.len()
+            );
+            // Fallback implementation for non-KDE systems
+            // In a real implementation, this would provide alternative hotkey handling
+            drop(hotkey_tx);
+        }
+
+        let start = Instant::now();
+        let mut dispatcher = HotkeyDispatcher::new().with_hold_threshold(hold_threshold_ms);
+        loop {
+            tokio::select! {
+                event = hotkey_rx.recv() => {
+                    let Some(event) = event else { break };
+                    let current_mode = *mode.read().await;
+                    let now_ms = start.elapsed().as_millis() as u64;
+                    match dispatcher.handle(&event, current_mode, now_ms) {
+                        Some(Dispatch::Vad(ev)) => {
+                            let _ = event_tx.send(ev).await;
+                        }
+                        Some(Dispatch::Action(action)) => {
+                            tracing::debug!("Hotkey action: {}", action);
+                            let _ = action_tx.send(action).await;
+                        }
+                        None => {}
+                    }
+                }
+                Ok(()) = finalize_rx.recv() => {
+                    let now_ms = start.elapsed().as_millis() as u64;
+                    for ev in dispatcher.finalize_now(now_ms).into_iter().flatten() {
+                        let _ = event_tx.send(ev).await;
+                    }
+                }
+            }
+        }
+
+        #[cfg(kde_globalaccel)]
+        backend_task.abort();
+
+        #[cfg(not(kde_globalaccel))]
+        {
+            let _ = (event_tx, action_tx); // keep signature stable for callers
+            tokio::time::sleep(tokio::time::Duration::from_secs(3600)).await;
+        }
+    })
+}
@@ -0,0 +1,29 @@
+pub mod backend;
+pub mod bindings;
+pub mod indicator;
+#[cfg(kde_globalaccel)]
+pub mod kglobalaccel;
+#[cfg(all(target_os = "linux", feature = "layer-shell"))]
+pub mod layer_shell;
+pub mod listener;
+
+use std::sync::Arc;
+
+use coldvox_vad::types::VadEvent;
+use tokio::sync::mpsc::Sender;
+use tokio::sync::{broadcast, RwLock};
+
+pub use bindings::{HotkeyAction, HotkeyBinding, HotkeyConfig};
+
+use crate::runtime::ActivationMode;
+
+/// Spawn a hotkey listener for the configured bindings using the best available backend
+pub fn spawn_hotkey_listener(
+    config: HotkeyConfig,
+    mode: Arc<RwLock<ActivationMode>>,
+    event_tx: Sender<VadEvent>,
+    action_tx: Sender<HotkeyAction>,
+    finalize_rx: broadcast::Receiver<()>,
+) -> tokio::task::JoinHandle<()> {
+    listener::spawn_hotkey_listener(config, mode, event_tx, action_tx, finalize_rx)
+}
@@ -0,0 +1,286 @@
+//! Energy-saver idle mode.
+//!
+//! After `suspend_after` without speech the capture stream is closed, so the
+//! audio device stops delivering buffers and the chunker, VAD and STT tasks sit
+//! blocked on empty channels. Capture is reopened when:
+//!
+//! - an activation hotkey is pressed (it emits a speech start),
+//! - another hotkey action runs or [`AppHandle::wake`](crate::runtime::AppHandle::wake)
+//!   is called,
+//! - a periodic probe briefly reopens the stream and hears sound at or above
+//!   `probe_threshold_dbfs`.
+//!
+//! The time from wake request to the first captured frame is recorded in
+//! [`PipelineMetrics::last_resume_latency_ms`].
+
+use std::future::pending;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use coldvox_audio::SharedAudioFrame;
+use coldvox_telemetry::PipelineMetrics;
+use coldvox_vad::VadEvent;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio::time::{self, Instant, Interval};
+use tracing::{debug, info, warn};
+
+/// Longest wait for audio after reopening the stream
+const RESUME_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Idle-mode tuning
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdleConfig {
+    /// Time without speech before capture is suspended
+    pub suspend_after: Duration,
+    /// How often a suspended pipeline listens for sound (None = never)
+    pub probe_interval: Option<Duration>,
+    /// How long each probe listens, including the time to reopen the stream
+    pub probe_window: Duration,
+    /// Frame level that counts as sound worth waking for
+    pub probe_threshold_dbfs: f32,
+}
+
+impl Default for IdleConfig {
+    fn default() -> Self {
+        Self {
+            suspend_after: Duration::from_secs(30 * 60),
+            probe_interval: Some(Duration::from_secs(60)),
+            probe_window: Duration::from_millis(1500),
+            probe_threshold_dbfs: -45.0,
+        }
+    }
+}
+
+/// What ended an idle period
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeReason {
+    /// Speech start from a hotkey (or the VAD during a probe)
+    Activation,
+    /// Hotkey command or explicit wake request
+    Request,
+    /// Probe heard sound above the threshold
+    Probe,
+}
+
+/// RMS level of a frame in dBFS (-inf for digital silence)
+pub fn frame_dbfs(samples: &[i16]) -> f32 {
+    if samples.is_empty() {
+        return f32::NEG_INFINITY;
+    }
+    let sum: f64 = samples.iter().map(|&s| f64::from(s) * f64::from(s)).sum();
+    let rms = (sum / samples.len() as f64).sqrt();
+    (20.0 * (rms / f64::from(i16::MAX)).log10()) as f32
+}
+
+/// Suspend capture after prolonged silence and resume it on demand.
+///
+/// `capture_suspended` is the capture thread's suspension flag
+/// ([`AudioCaptureThread::suspended`](coldvox_audio::AudioCaptureThread::suspended)).
+pub fn spawn_idle_monitor(
+    config: IdleConfig,
+    capture_suspended: Arc<AtomicBool>,
+    audio_tx: broadcast::Sender<SharedAudioFrame>,
+    mut vad_rx: broadcast::Receiver<VadEvent>,
+    wake: Arc<Notify>,
+    metrics: Arc<PipelineMetrics>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut last_activity = Instant::now();
+        loop {
+            tokio::select! {
+                ev = vad_rx.recv() => match ev {
+                    Ok(_) => last_activity = Instant::now(),
+                    Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => break,
+                },
+                _ = wake.notified() => last_activity = Instant::now(),
+                _ = time::sleep_until(last_activity + config.suspend_after) => {
+                    info!(
+                        "No speech for {}s; suspending audio capture",
+                        config.suspend_after.as_secs()
+                    );
+                    capture_suspended.store(true, Ordering::Relaxed);
+                    metrics.record_idle_suspend();
+
+                    let Some(reason) =
+                        wait_for_wake(&config, &capture_suspended, &audio_tx, &mut vad_rx, &wake)
+                            .await
+                    else {
+                        break;
+                    };
+                    let latency = resume(&capture_suspended, &audio_tx).await;
+                    metrics.record_idle_resume(latency.as_millis() as u64);
+                    info!(
+                        "Resumed audio capture from idle ({:?}) in {} ms",
+                        reason,
+                        latency.as_millis()
+                    );
+                    last_activity = Instant::now();
+                }
+            }
+        }
+    })
+}
+
+async fn next_probe(probe: &mut Option<Interval>) {
+    match probe {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => pending().await,
+    }
+}
+
+/// Wait while suspended until something asks for audio. `None` when the
+/// pipeline is shutting down.
+async fn wait_for_wake(
+    config: &IdleConfig,
+    capture_suspended: &AtomicBool,
+    audio_tx: &broadcast::Sender<SharedAudioFrame>,
+    vad_rx: &mut broadcast::Receiver<VadEvent>,
+    wake: &Notify,
+) -> Option<WakeReason> {
+    let mut probe = config
+        .probe_interval
+        .map(|every| time::interval_at(Instant::now() + every, every));
+    loop {
+        tokio::select! {
+            ev = vad_rx.recv() => match ev {
+                Ok(VadEvent::SpeechStart { .. }) => return Some(WakeReason::Activation),
+                Ok(_) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return None,
+            },
+            _ = wake.notified() => return Some(WakeReason::Request),
+            _ = next_probe(&mut probe) => {
+                if probe_for_sound(config, capture_suspended, audio_tx).await {
+                    return Some(WakeReason::Probe);
+                }
+            }
+        }
+    }
+}
+
+/// Reopen capture for one probe window. Returns true (leaving capture open)
+/// when a frame reaches the threshold.
+async fn probe_for_sound(
+    config: &IdleConfig,
+    capture_suspended: &AtomicBool,
+    audio_tx: &broadcast::Sender<SharedAudioFrame>,
+) -> bool {
+    let mut audio_rx = audio_tx.subscribe();
+    capture_suspended.store(false, Ordering::Relaxed);
+    let window = time::sleep(config.probe_window);
+    tokio::pin!(window);
+
+    let mut loudest = f32::NEG_INFINITY;
+    loop {
+        tokio::select! {
+            frame = audio_rx.recv() => match frame {
+                Ok(frame) => {
+                    loudest = loudest.max(frame_dbfs(&frame.samples));
+                    if loudest >= config.probe_threshold_dbfs {
+                        debug!("Idle probe heard {:.1} dBFS; waking", loudest);
+                        return true;
+                    }
+                }
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            },
+            _ = &mut window => break,
+        }
+    }
+    capture_suspended.store(true, Ordering::Relaxed);
+    debug!(
+        "Idle probe peaked at {:.1} dBFS; staying suspended",
+        loudest
+    );
+    false
+}
+
+/// Reopen capture and wait for the first frame
+async fn resume(
+    capture_suspended: &AtomicBool,
+    audio_tx: &broadcast::Sender<SharedAudioFrame>,
+) -> Duration {
+    let start = Instant::now();
+    let mut audio_rx = audio_tx.subscribe();
+    capture_suspended.store(false, Ordering::Relaxed);
+    let first_frame = time::timeout(RESUME_TIMEOUT, async {
+        loop {
+            match audio_rx.recv().await {
+                Ok(_) | Err(RecvError::Closed) => break,
+                Err(RecvError::Lagged(_)) => {}
+            }
+        }
+    })
+    .await;
+    if first_frame.is_err() {
+        warn!("No audio within {:?} of resuming capture", RESUME_TIMEOUT);
+    }
+    start.elapsed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(amplitude: i16) -> SharedAudioFrame {
+        SharedAudioFrame {
+            samples: vec![amplitude; 512].into(),
+            timestamp: std::time::Instant::now(),
+            captured_at: std::time::Instant::now(),
+            discontinuity: false,
+            sample_rate: 16_000,
+        }
+    }
+
+    #[test]
+    fn dbfs_of_full_scale_and_silence() {
+        assert!(frame_dbfs(&[i16::MAX; 64]).abs() < 0.01);
+        assert!((frame_dbfs(&[3277; 64]) + 20.0).abs() < 0.1);
+        assert_eq!(frame_dbfs(&[0; 64]), f32::NEG_INFINITY);
+    }
+
+    #[tokio::test]
+    async fn suspends_after_silence_and_wakes_on_hotkey() {
+        let config = IdleConfig {
+            suspend_after: Duration::from_millis(50),
+            probe_interval: None,
+            ..Default::default()
+        };
+        let suspended = Arc::new(AtomicBool::new(false));
+        let (audio_tx, _) = broadcast::channel(16);
+        let (vad_tx, vad_rx) = broadcast::channel(16);
+        let metrics = Arc::new(PipelineMetrics::default());
+        let handle = spawn_idle_monitor(
+            config,
+            suspended.clone(),
+            audio_tx.clone(),
+            vad_rx,
+            Arc::new(Notify::new()),
+            metrics.clone(),
+        );
+
+        time::sleep(Duration::from_millis(150)).await;
+        assert!(suspended.load(Ordering::Relaxed));
+        assert!(metrics.capture_suspended.load(Ordering::Relaxed));
+
+        vad_tx
+            .send(VadEvent::SpeechStart {
+                timestamp_ms: 0,
+                energy_db: 0.0,
+            })
+            .unwrap();
+        time::sleep(Duration::from_millis(20)).await;
+        assert!(!suspended.load(Ordering::Relaxed));
+        audio_tx.send(frame(1000)).unwrap();
+        time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(metrics.idle_resume_count.load(Ordering::Relaxed), 1);
+        assert!(!metrics.capture_suspended.load(Ordering::Relaxed));
+
+        handle.abort();
+    }
+}
@@ -0,0 +1,185 @@
+//! Embeddable ColdVox pipeline.
+//!
+//! Assembles audio capture → VAD → STT → text injection for Rust applications
+//! that want dictation without the `coldvox` binary, its settings file or its
+//! CLI:
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! use coldvox_runtime::{ActivationMode, InjectionOptions, PipelineBuilder};
+//!
+//! let pipeline = PipelineBuilder::new()
+//!     .with_device("USB Microphone")
+//!     .with_activation_mode(ActivationMode::Hotkey)
+//!     .with_injection(InjectionOptions {
+//!         enable: true,
+//!         ..Default::default()
+//!     })
+//!     .start()
+//!     .await?;
+//!
+//! let mut transcripts = pipeline.subscribe_stt();
+//! while let Ok(event) = transcripts.recv().await {
+//!     println!("{:?}", event);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Anything the builder does not cover is still reachable through
+//! [`PipelineBuilder::with_options`], which edits the full
+//! [`AppRuntimeOptions`].
+//!
+//! The `coldvox` binary drives the same [`runtime`] and re-exports these
+//! modules; only its settings file, CLI and TUI live in `coldvox-app`.
+
+pub mod audio;
+pub mod black_box;
+pub mod hotkey;
+pub mod idle;
+pub mod low_confidence;
+pub mod pause;
+pub mod plugin_config;
+pub mod recovery;
+pub mod resume;
+pub mod runtime;
+pub mod session_report;
+pub mod stt;
+pub mod text_injection;
+pub mod tts;
+
+pub use coldvox_foundation::notify;
+
+pub use coldvox_audio::{ChannelSelection, ResamplerQuality};
+pub use coldvox_stt::plugin::PluginSelectionConfig;
+pub use coldvox_stt::{TranscriptionConfig, TranscriptionEvent};
+pub use coldvox_vad::{UnifiedVadConfig, VadEvent};
+pub use hotkey::HotkeyConfig;
+pub use runtime::{ActivationMode, AppHandle, AppRuntimeOptions, InjectionOptions};
+
+/// Handle to a running pipeline
+pub type Pipeline = AppHandle;
+
+/// Error returned when the pipeline fails to start
+pub type StartError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Builder for a ColdVox pipeline. Starts from [`AppRuntimeOptions::default`]:
+/// default input device, VAD activation, no text injection.
+#[derive(Debug, Clone, Default)]
+pub struct PipelineBuilder {
+    options: AppRuntimeOptions,
+}
+
+impl PipelineBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from options assembled elsewhere (e.g. from ColdVox settings)
+    pub fn from_options(options: AppRuntimeOptions) -> Self {
+        Self { options }
+    }
+
+    /// Capture from the device with this name instead of the host default
+    pub fn with_device(mut self, name: impl Into<String>) -> Self {
+        self.options.device = Some(name.into());
+        self
+    }
+
+    /// Devices to fail over to, in order, when the active one disappears.
+    /// Turns on device monitoring.
+    pub fn with_fallback_devices(mut self, devices: Vec<String>) -> Self {
+        self.options.fallback_devices = devices;
+        self.options.enable_device_monitor = true;
+        self
+    }
+
+    pub fn with_resampler_quality(mut self, quality: ResamplerQuality) -> Self {
+        self.options.resampler_quality = quality;
+        self
+    }
+
+    pub fn with_audio_channel(mut self, channel: ChannelSelection) -> Self {
+        self.options.audio_channel = channel;
+        self
+    }
+
+    pub fn with_activation_mode(mut self, mode: ActivationMode) -> Self {
+        self.options.activation_mode = mode;
+        self
+    }
+
+    pub fn with_vad(mut self, config: UnifiedVadConfig) -> Self {
+        self.options.vad_config = Some(config);
+        self
+    }
+
+    /// STT plugin preference and fallbacks
+    pub fn with_stt(mut self, selection: PluginSelectionConfig) -> Self {
+        self.options.stt_selection = Some(selection);
+        self
+    }
+
+    pub fn with_transcription(mut self, config: TranscriptionConfig) -> Self {
+        self.options.transcription_config = Some(config);
+        self
+    }
+
+    /// Type finals into the focused application (enable with
+    /// `InjectionOptions::enable`)
+    pub fn with_injection(mut self, options: InjectionOptions) -> Self {
+        self.options.injection = Some(options);
+        self
+    }
+
+    pub fn with_hotkeys(mut self, hotkeys: HotkeyConfig) -> Self {
+        self.options.hotkeys = hotkeys;
+        self
+    }
+
+    /// Adjust any other runtime option
+    pub fn with_options(mut self, edit: impl FnOnce(&mut AppRuntimeOptions)) -> Self {
+        edit(&mut self.options);
+        self
+    }
+
+    pub fn options(&self) -> &AppRuntimeOptions {
+        &self.options
+    }
+
+    /// Open the device, load the STT plugin and start every pipeline task.
+    /// Stop it with [`AppHandle::shutdown`] on an `Arc` of the handle.
+    pub async fn start(self) -> Result<Pipeline, StartError> {
+        runtime::start(self.options).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_fills_runtime_options() {
+        let builder = PipelineBuilder::new()
+            .with_device("USB Microphone")
+            .with_fallback_devices(vec!["Laptop Mic".to_string()])
+            .with_activation_mode(ActivationMode::Hotkey)
+            .with_injection(InjectionOptions {
+                enable: true,
+                ..Default::default()
+            })
+            .with_options(|o| o.max_utterance_secs = 30);
+
+        let options = builder.options();
+        assert_eq!(options.device.as_deref(), Some("USB Microphone"));
+        assert!(options.enable_device_monitor);
+        assert_eq!(options.activation_mode, ActivationMode::Hotkey);
+        assert!(options.injection.as_ref().is_some_and(|i| i.enable));
+        assert_eq!(options.max_utterance_secs, 30);
+        // Untouched options keep the runtime defaults
+        assert_eq!(
+            options.capture_buffer_samples,
+            AppRuntimeOptions::default().capture_buffer_samples
+        );
+    }
+}
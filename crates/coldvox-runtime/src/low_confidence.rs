@@ -0,0 +1,123 @@
+//! Feedback for transcripts held back for low confidence.
+//!
+//! With `injection.min_confidence` set, a final transcript whose mean word
+//! confidence is below it is not typed. The injection processor holds the
+//! latest one until the user accepts it (the `accept_low_confidence` hotkey,
+//! the TUI or [`AppHandle::accept_low_confidence`](crate::runtime::AppHandle::accept_low_confidence))
+//! or the next one replaces it. This task tells the user that something was
+//! held: a desktop notification, which like injection failure notices never
+//! contains the dictated text, or a spoken "didn't catch that". Transcripts
+//! held by `sensitive_content = "confirm"` go through the same handle and are
+//! announced by notification, naming only what the filter found.
+
+use std::sync::Arc;
+
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+use crate::text_injection::{HoldReason, LowConfidenceHandle};
+use crate::tts::ReadBack;
+
+/// How a held transcript is surfaced
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LowConfidenceFeedback {
+    /// Desktop notification
+    #[default]
+    Notify,
+    /// Spoken prompt through read-back
+    Speak,
+    /// Only logged (and shown in the TUI)
+    None,
+}
+
+impl std::str::FromStr for LowConfidenceFeedback {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "notify" | "" => Ok(Self::Notify),
+            "speak" => Ok(Self::Speak),
+            "none" | "off" => Ok(Self::None),
+            other => Err(format!("unknown low-confidence feedback '{}'", other)),
+        }
+    }
+}
+
+/// Tell the user about each held transcript
+pub fn spawn_low_confidence_feedback(
+    handle: &LowConfidenceHandle,
+    feedback: LowConfidenceFeedback,
+    read_back: Arc<ReadBack>,
+) -> JoinHandle<()> {
+    let mut held_rx = handle.subscribe();
+    tokio::spawn(async move {
+        loop {
+            let held = match held_rx.recv().await {
+                Ok(held) => held,
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            let notice = match &held.reason {
+                HoldReason::LowConfidence => None,
+                HoldReason::SensitiveContent(kinds) => Some(format!(
+                    "It looks like it contains: {}. Use the accept hotkey to type it.",
+                    coldvox_foundation::scrub::describe(kinds)
+                )),
+                HoldReason::UnconfirmedCommand(cmd) => Some(format!(
+                    "'{}' was sent before you finished; the rest was not typed. Use the accept hotkey to type it.",
+                    cmd
+                )),
+            };
+            if let Some(notice) = notice {
+                // Always a notification: reading the prompt aloud would be
+                // odd, and nothing at all would silently drop the text
+                if feedback != LowConfidenceFeedback::None {
+                    crate::notify::send(
+                        "ColdVox held a transcript",
+                        &notice,
+                        crate::notify::Urgency::Normal,
+                    )
+                    .await;
+                }
+                continue;
+            }
+            match feedback {
+                LowConfidenceFeedback::Notify => {
+                    crate::notify::send(
+                        "ColdVox didn't catch that",
+                        &format!(
+                            "Transcript held back ({:.0}% confidence). Use the accept hotkey to type it.",
+                            held.confidence * 100.0
+                        ),
+                        crate::notify::Urgency::Normal,
+                    )
+                    .await;
+                }
+                LowConfidenceFeedback::Speak => {
+                    if let Err(e) = read_back.speak("Sorry, I didn't catch that.").await {
+                        tracing::debug!("Low-confidence prompt: {}", e);
+                    }
+                }
+                LowConfidenceFeedback::None => {}
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feedback_parses_from_settings() {
+        assert_eq!(
+            "Speak".parse::<LowConfidenceFeedback>(),
+            Ok(LowConfidenceFeedback::Speak)
+        );
+        assert_eq!(
+            "".parse::<LowConfidenceFeedback>(),
+            Ok(LowConfidenceFeedback::Notify)
+        );
+        assert!("beep".parse::<LowConfidenceFeedback>().is_err());
+    }
+}
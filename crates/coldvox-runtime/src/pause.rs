@@ -0,0 +1,139 @@
+//! User-requested pause of the whole pipeline.
+//!
+//! Pausing keeps every task running and every model loaded, but the chunker
+//! drops captured audio instead of publishing it, so VAD and STT receive
+//! nothing until the pipeline is resumed. The utterance in progress is
+//! finalized first so nothing already spoken is lost. Optionally the capture
+//! stream is closed as well, the same way idle mode does it, which also
+//! turns off the microphone indicator of the desktop.
+//!
+//! Pausing is driven by the `toggle_pause` hotkey (or `injection.pause_hotkey`),
+//! the TUI and [`AppHandle::pause`](crate::runtime::AppHandle::pause).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use coldvox_telemetry::PipelineMetrics;
+use tokio::sync::{broadcast, watch};
+use tracing::info;
+
+/// Shared pause switch for the running pipeline
+#[derive(Clone)]
+pub struct PauseControl {
+    /// Watched by the chunker
+    paused: Arc<AtomicBool>,
+    /// Capture suspension flag, when pausing also closes the stream
+    capture_suspended: Option<Arc<AtomicBool>>,
+    finalize_tx: broadcast::Sender<()>,
+    state_tx: watch::Sender<bool>,
+    metrics: Arc<PipelineMetrics>,
+}
+
+impl PauseControl {
+    pub fn new(finalize_tx: broadcast::Sender<()>, metrics: Arc<PipelineMetrics>) -> Self {
+        Self {
+            paused: Arc::new(AtomicBool::new(false)),
+            capture_suspended: None,
+            finalize_tx,
+            state_tx: watch::channel(false).0,
+            metrics,
+        }
+    }
+
+    /// Also suspend capture while paused, using the capture thread's
+    /// suspension flag
+    pub fn with_capture_suspend(mut self, capture_suspended: Arc<AtomicBool>) -> Self {
+        self.capture_suspended = Some(capture_suspended);
+        self
+    }
+
+    /// Flag for [`AudioChunker::with_pause`](coldvox_audio::AudioChunker::with_pause)
+    pub fn flag(&self) -> Arc<AtomicBool> {
+        self.paused.clone()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Stop feeding audio to VAD and STT. Returns false if already paused.
+    pub fn pause(&self) -> bool {
+        if self.paused.swap(true, Ordering::Relaxed) {
+            return false;
+        }
+        // End the open utterance with what was heard so far
+        let _ = self.finalize_tx.send(());
+        if let Some(suspended) = &self.capture_suspended {
+            suspended.store(true, Ordering::Relaxed);
+        }
+        self.publish(true);
+        info!("Pipeline paused");
+        true
+    }
+
+    /// Feed audio again. Returns false if not paused.
+    pub fn resume(&self) -> bool {
+        if !self.paused.swap(false, Ordering::Relaxed) {
+            return false;
+        }
+        if let Some(suspended) = &self.capture_suspended {
+            suspended.store(false, Ordering::Relaxed);
+        }
+        self.publish(false);
+        info!("Pipeline resumed");
+        true
+    }
+
+    /// Pause if running, resume if paused. Returns the new state.
+    pub fn toggle(&self) -> bool {
+        if self.is_paused() {
+            self.resume();
+            false
+        } else {
+            self.pause();
+            true
+        }
+    }
+
+    /// Watch the paused state
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.state_tx.subscribe()
+    }
+
+    fn publish(&self, paused: bool) {
+        self.metrics
+            .pipeline_paused
+            .store(paused, Ordering::Relaxed);
+        self.state_tx.send_replace(paused);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pause_finalizes_once_and_resume_restores_capture() {
+        let (finalize_tx, mut finalize_rx) = broadcast::channel(4);
+        let metrics = Arc::new(PipelineMetrics::default());
+        let suspended = Arc::new(AtomicBool::new(false));
+        let control =
+            PauseControl::new(finalize_tx, metrics.clone()).with_capture_suspend(suspended.clone());
+        let state = control.subscribe();
+
+        assert!(control.pause());
+        assert!(!control.pause());
+        assert!(control.flag().load(Ordering::Relaxed));
+        assert!(suspended.load(Ordering::Relaxed));
+        assert!(metrics.pipeline_paused.load(Ordering::Relaxed));
+        assert!(*state.borrow());
+        assert!(finalize_rx.try_recv().is_ok());
+        assert!(finalize_rx.try_recv().is_err());
+
+        assert!(!control.toggle());
+        assert!(!control.is_paused());
+        assert!(!suspended.load(Ordering::Relaxed));
+        assert!(!*state.borrow());
+        assert!(!control.resume());
+    }
+}
@@ -0,0 +1,157 @@
+//! Where the STT plugin selection config (`config/plugins.json`) is found.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Find `config/plugins.json`: `COLDVOX_PLUGIN_CONFIG_PATH` when set,
+/// nothing when an explicit startup config (`COLDVOX_CONFIG_PATH`) is in use,
+/// otherwise the repository root or the nearest ancestor of the working
+/// directory that has one
+pub fn discover_plugin_selection_config_path() -> Option<PathBuf> {
+    if let Some(custom) = env::var_os("COLDVOX_PLUGIN_CONFIG_PATH") {
+        let path = PathBuf::from(custom);
+        if path.is_file() {
+            tracing::info!(
+                "Using plugin config from COLDVOX_PLUGIN_CONFIG_PATH: {}",
+                path.display()
+            );
+            return Some(path);
+        }
+
+        tracing::warn!(
+            "COLDVOX_PLUGIN_CONFIG_PATH is set but does not point to an existing file: {}",
+            path.display()
+        );
+        return None;
+    }
+
+    if explicit_startup_config_path().is_some() {
+        return None;
+    }
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").ok().map(PathBuf::from);
+    let cwd = env::current_dir().ok();
+    discover_plugin_selection_config_path_with(manifest_dir.as_deref(), cwd.as_deref())
+}
+
+fn discover_plugin_selection_config_path_with(
+    manifest_dir: Option<&Path>,
+    cwd: Option<&Path>,
+) -> Option<PathBuf> {
+    if let Some(manifest_dir) = manifest_dir {
+        let candidate = manifest_dir.join("../..").join("config/plugins.json");
+        if candidate.exists() {
+            return Some(candidate);
+        }
+    }
+
+    if let Some(cwd) = cwd {
+        for ancestor in cwd.ancestors() {
+            let candidate = ancestor.join("config/plugins.json");
+            if candidate.exists() && !is_legacy_app_local_plugin_config_path(&candidate) {
+                return Some(candidate);
+            }
+        }
+    }
+
+    None
+}
+
+/// The startup config named by `COLDVOX_CONFIG_PATH`, when it exists
+pub fn explicit_startup_config_path() -> Option<PathBuf> {
+    let custom = env::var_os("COLDVOX_CONFIG_PATH")?;
+    let path = PathBuf::from(custom);
+    path.exists().then_some(path)
+}
+
+fn is_legacy_app_local_plugin_config_path(path: &Path) -> bool {
+    let mut reversed = path.iter().rev();
+    matches!(
+        (
+            reversed.next(),
+            reversed.next(),
+            reversed.next(),
+            reversed.next()
+        ),
+        (Some(file), Some(config), Some(app), Some(crates))
+            if file == "plugins.json" && config == "config" && app == "app" && crates == "crates"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::fs;
+
+    #[test]
+    fn discover_plugin_selection_config_path_skips_app_local_copy() {
+        let temp = tempfile::tempdir().expect("create tempdir");
+        let repo_root = temp.path();
+        let root_config_dir = repo_root.join("config");
+        let app_config_dir = repo_root.join("crates/app/config");
+        fs::create_dir_all(&root_config_dir).expect("create root config dir");
+        fs::create_dir_all(&app_config_dir).expect("create app config dir");
+        fs::write(root_config_dir.join("plugins.json"), "{}").expect("write root plugin config");
+        fs::write(app_config_dir.join("plugins.json"), "{}")
+            .expect("write app-local plugin config");
+
+        let resolved = discover_plugin_selection_config_path_with(
+            Some(&repo_root.join("crates/app")),
+            Some(&repo_root.join("crates/app")),
+        )
+        .expect("resolve canonical plugin config path");
+        let resolved = resolved
+            .canonicalize()
+            .expect("canonicalize resolved plugin config path");
+        let expected = repo_root
+            .join("config/plugins.json")
+            .canonicalize()
+            .expect("canonicalize expected plugin config path");
+
+        assert_eq!(resolved, expected);
+    }
+
+    #[test]
+    #[serial]
+    fn invalid_startup_config_keeps_implicit_plugin_overrides_available() {
+        let temp = tempfile::tempdir().expect("create tempdir");
+        let repo_root = temp.path();
+        let root_config_dir = repo_root.join("config");
+        let app_dir = repo_root.join("crates/app");
+        let root_plugins_path = root_config_dir.join("plugins.json");
+        let invalid_config_path = root_config_dir.join("missing.toml");
+        fs::create_dir_all(&root_config_dir).expect("create root config dir");
+        fs::create_dir_all(&app_dir).expect("create app dir");
+        fs::write(&root_plugins_path, "{}").expect("write root plugin config");
+
+        let original_config = env::var_os("COLDVOX_CONFIG_PATH");
+        let original_plugin = env::var_os("COLDVOX_PLUGIN_CONFIG_PATH");
+
+        env::set_var("COLDVOX_CONFIG_PATH", &invalid_config_path);
+        env::remove_var("COLDVOX_PLUGIN_CONFIG_PATH");
+
+        assert!(explicit_startup_config_path().is_none());
+
+        let resolved = discover_plugin_selection_config_path_with(Some(&app_dir), Some(&app_dir))
+            .expect("resolve canonical plugin config path")
+            .canonicalize()
+            .expect("canonicalize resolved plugin config path");
+
+        if let Some(value) = original_config {
+            env::set_var("COLDVOX_CONFIG_PATH", value);
+        } else {
+            env::remove_var("COLDVOX_CONFIG_PATH");
+        }
+        if let Some(value) = original_plugin {
+            env::set_var("COLDVOX_PLUGIN_CONFIG_PATH", value);
+        } else {
+            env::remove_var("COLDVOX_PLUGIN_CONFIG_PATH");
+        }
+
+        let expected = root_plugins_path
+            .canonicalize()
+            .expect("canonicalize expected plugin config path");
+        assert_eq!(resolved, expected);
+    }
+}
@@ -0,0 +1,323 @@
+//! Crash recovery for dictation in flight.
+//!
+//! While injection runs, [`RecoveryTracker`] follows the transcript stream:
+//! the partial of the utterance being spoken, and finals the injection worker
+//! has not settled yet. Every few seconds, when that changed, it is written to
+//! `recovery.json` in the state directory along with the session counters. A
+//! clean shutdown removes the file, so finding one at startup means the
+//! previous run crashed or was killed, and `coldvox` offers to inject or
+//! export the text it held.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use coldvox_stt::TranscriptionEvent;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+use crate::session_report::{SessionCounters, SessionStats};
+
+/// How often a changed state is written out
+pub const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A final the injection worker had not finished with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingTranscript {
+    pub utterance_id: u64,
+    pub text: String,
+    pub at: DateTime<Local>,
+}
+
+/// What `recovery.json` holds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub saved_at: DateTime<Local>,
+    /// Process that wrote the snapshot
+    pub pid: u32,
+    /// Latest partial of the utterance being spoken
+    pub partial: Option<String>,
+    pub pending: Vec<PendingTranscript>,
+    pub session: SessionCounters,
+}
+
+impl Snapshot {
+    /// `$XDG_STATE_HOME/coldvox/recovery.json`, falling back to
+    /// `~/.local/state/coldvox/recovery.json`
+    pub fn default_path() -> Option<PathBuf> {
+        let state_dir = std::env::var_os("XDG_STATE_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local").join("state"))
+            })?;
+        Some(state_dir.join("coldvox").join("recovery.json"))
+    }
+
+    /// Pending finals in order, then the unfinished partial
+    pub fn text(&self) -> String {
+        self.pending
+            .iter()
+            .map(|p| p.text.trim())
+            .chain(self.partial.as_deref().map(str::trim))
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text().is_empty()
+    }
+
+    /// Whether the process that wrote this is still running, i.e. another
+    /// instance owns the file
+    pub fn writer_alive(&self) -> bool {
+        self.pid == std::process::id()
+            || (cfg!(target_os = "linux") && Path::new(&format!("/proc/{}", self.pid)).exists())
+    }
+
+    /// Replace `path` atomically, readable only by the user
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_vec_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let tmp = path.with_extension("json.tmp");
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&tmp)?;
+        file.write_all(&json)?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)
+    }
+
+    /// The snapshot at `path`, or None if there is none
+    pub fn load(path: &Path) -> io::Result<Option<Self>> {
+        match fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write the recoverable text next to `path` as
+    /// `recovered-<timestamp>.txt`; returns the file written
+    pub fn export(&self, path: &Path) -> io::Result<PathBuf> {
+        let dir = path.parent().unwrap_or(Path::new("."));
+        fs::create_dir_all(dir)?;
+        let out = dir.join(format!(
+            "recovered-{}.txt",
+            self.saved_at.format("%Y%m%d-%H%M%S")
+        ));
+        fs::write(&out, format!("{}\n", self.text()))?;
+        Ok(out)
+    }
+}
+
+/// Remove the snapshot at `path`, if any
+pub fn clear(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[derive(Default)]
+struct TrackerState {
+    partial: Option<String>,
+    pending: VecDeque<PendingTranscript>,
+    /// Highest utterance id the injection worker has settled
+    settled_through: Option<u64>,
+    dirty: bool,
+}
+
+/// Text transcribed but not yet injected
+#[derive(Default)]
+pub struct RecoveryTracker {
+    state: Mutex<TrackerState>,
+}
+
+impl RecoveryTracker {
+    pub fn observe(&self, event: &TranscriptionEvent) {
+        let mut state = self.state.lock().unwrap();
+        match event {
+            TranscriptionEvent::Partial { text, .. } => {
+                let text = text.trim();
+                if !text.is_empty() && state.partial.as_deref() != Some(text) {
+                    state.partial = Some(text.to_string());
+                    state.dirty = true;
+                }
+            }
+            TranscriptionEvent::Final {
+                utterance_id, text, ..
+            } => {
+                state.partial = None;
+                state.dirty = true;
+                // The worker can settle an utterance before the broadcast
+                // copy of its final arrives here
+                let settled = state.settled_through.is_some_and(|s| *utterance_id <= s);
+                if !settled && !text.trim().is_empty() {
+                    state.pending.push_back(PendingTranscript {
+                        utterance_id: *utterance_id,
+                        text: text.clone(),
+                        at: Local::now(),
+                    });
+                }
+            }
+            TranscriptionEvent::Error { .. } => {}
+        }
+    }
+
+    /// The injection worker is done with `utterance_ids`; it works in order,
+    /// so anything up to the highest of them is done too
+    pub fn settle(&self, utterance_ids: &[u64]) {
+        let Some(&highest) = utterance_ids.iter().max() else {
+            return;
+        };
+        let mut state = self.state.lock().unwrap();
+        let through = state.settled_through.map_or(highest, |s| s.max(highest));
+        state.settled_through = Some(through);
+        let before = state.pending.len();
+        state.pending.retain(|p| p.utterance_id > through);
+        state.dirty |= state.pending.len() != before;
+    }
+
+    /// A snapshot if anything changed since the last one
+    pub fn take_snapshot(&self, session: SessionCounters) -> Option<Snapshot> {
+        let mut state = self.state.lock().unwrap();
+        if !std::mem::take(&mut state.dirty) {
+            return None;
+        }
+        Some(Snapshot {
+            saved_at: Local::now(),
+            pid: std::process::id(),
+            partial: state.partial.clone(),
+            pending: state.pending.iter().cloned().collect(),
+            session,
+        })
+    }
+}
+
+/// Follow `transcripts` and `settled`, and keep `path` up to date with what
+/// would be lost if the process died now; the file is removed while there
+/// is nothing to recover
+pub fn spawn_snapshot_writer(
+    path: PathBuf,
+    mut transcripts: broadcast::Receiver<TranscriptionEvent>,
+    mut settled: broadcast::Receiver<Vec<u64>>,
+    stats: Arc<SessionStats>,
+) -> JoinHandle<()> {
+    let tracker = RecoveryTracker::default();
+    tokio::spawn(async move {
+        let mut tick = tokio::time::interval(SNAPSHOT_INTERVAL);
+        tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                event = transcripts.recv() => match event {
+                    Ok(event) => tracker.observe(&event),
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        tracing::warn!("Crash recovery missed {} transcription events", n);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                ids = settled.recv() => match ids {
+                    Ok(ids) => tracker.settle(&ids),
+                    Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                _ = tick.tick() => {
+                    let Some(snapshot) = tracker.take_snapshot(stats.counters()) else {
+                        continue;
+                    };
+                    let result = if snapshot.is_empty() {
+                        clear(&path)
+                    } else {
+                        snapshot.save(&path)
+                    };
+                    if let Err(e) = result {
+                        tracing::warn!("Failed to write {}: {}", path.display(), e);
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coldvox_stt::SourceId;
+
+    fn partial(id: u64, text: &str) -> TranscriptionEvent {
+        TranscriptionEvent::Partial {
+            utterance_id: id,
+            text: text.to_string(),
+            t0: None,
+            t1: None,
+            source: SourceId::default(),
+        }
+    }
+
+    fn final_event(id: u64, text: &str) -> TranscriptionEvent {
+        TranscriptionEvent::Final {
+            utterance_id: id,
+            text: text.to_string(),
+            words: None,
+            source: SourceId::default(),
+        }
+    }
+
+    #[test]
+    fn tracks_text_until_settled() {
+        let tracker = RecoveryTracker::default();
+        tracker.observe(&final_event(1, "first"));
+        tracker.observe(&final_event(2, "second"));
+        tracker.observe(&partial(3, "thi"));
+        let snapshot = tracker.take_snapshot(SessionCounters::default()).unwrap();
+        assert_eq!(snapshot.text(), "first second thi");
+        assert!(tracker.take_snapshot(SessionCounters::default()).is_none());
+
+        tracker.settle(&[1, 2]);
+        let snapshot = tracker.take_snapshot(SessionCounters::default()).unwrap();
+        assert_eq!(snapshot.text(), "thi");
+
+        // A final the worker already settled is not pending
+        tracker.settle(&[3]);
+        tracker.observe(&final_event(3, "third"));
+        assert!(tracker
+            .take_snapshot(SessionCounters::default())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn saves_loads_and_exports() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join("recovery.json");
+        assert_eq!(Snapshot::load(&path).unwrap(), None);
+
+        let tracker = RecoveryTracker::default();
+        tracker.observe(&final_event(1, "hello world"));
+        let snapshot = tracker.take_snapshot(SessionCounters::default()).unwrap();
+        snapshot.save(&path).unwrap();
+        assert_eq!(Snapshot::load(&path).unwrap(), Some(snapshot.clone()));
+        assert!(snapshot.writer_alive());
+
+        let exported = snapshot.export(&path).unwrap();
+        assert_eq!(fs::read_to_string(exported).unwrap(), "hello world\n");
+
+        clear(&path).unwrap();
+        clear(&path).unwrap();
+        assert_eq!(Snapshot::load(&path).unwrap(), None);
+    }
+}
@@ -0,0 +1,95 @@
+//! Suspend/resume handling.
+//!
+//! Cooldowns, latency budgets, model TTLs and metrics windows are measured
+//! with `Instant`, which stands still during suspend on Linux and runs on
+//! elsewhere. Either way they are off after a laptop wakes up: a backend
+//! that failed before the lid closed is still cooling down, an idle model
+//! stays loaded for hours longer than its TTL, an utterance spanning the
+//! nap reports its length as latency. Audio devices may also have changed
+//! while asleep.
+//!
+//! The watch compares `Instant` with the wall clock every few seconds. When
+//! they disagree by more than [`JUMP_THRESHOLD`] it warns, resets that
+//! state, re-validates the capture device and emits a `system.resume` event.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use coldvox_foundation::{events, ClockJump, ClockJumpDetector};
+use coldvox_telemetry::PipelineMetrics;
+use tokio::sync::{broadcast, RwLock};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::stt::plugin_manager::SttPluginManager;
+use crate::text_injection::BackendCatalog;
+
+/// How often the clocks are compared
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Smallest discrepancy treated as a suspend or clock change
+pub const JUMP_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// Time-based state to fix up after a resume
+pub struct ResumeTargets {
+    /// Capture's device re-validation flag
+    /// ([`AudioCaptureThread::revalidate`](coldvox_audio::AudioCaptureThread::revalidate))
+    pub capture_revalidate: Arc<AtomicBool>,
+    pub backend_catalog: Option<BackendCatalog>,
+    pub plugin_manager: Option<Arc<RwLock<SttPluginManager>>>,
+    pub metrics: Arc<PipelineMetrics>,
+}
+
+impl ResumeTargets {
+    async fn apply(&self, slept: Duration, instant_paused: bool) {
+        self.capture_revalidate.store(true, Ordering::Relaxed);
+        if let Some(catalog) = &self.backend_catalog {
+            catalog.reset_timers();
+        }
+        // Where Instant ran on through the suspend, TTLs already saw it
+        if instant_paused {
+            if let Some(pm) = &self.plugin_manager {
+                pm.read().await.age_activity(slept).await;
+            }
+        }
+        self.metrics.latency.forget_pending();
+    }
+}
+
+/// Watch for clock jumps until aborted, publishing each one on `jumps`
+pub fn spawn_resume_watch(
+    targets: ResumeTargets,
+    jumps: broadcast::Sender<ClockJump>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut detector = ClockJumpDetector::new(POLL_INTERVAL, JUMP_THRESHOLD);
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let Some(jump) = detector.poll() else {
+                continue;
+            };
+            match jump {
+                ClockJump::Resumed {
+                    slept,
+                    instant_paused,
+                } => {
+                    tracing::warn!(
+                        "System resumed after about {}s; resetting timers and re-checking the audio device",
+                        slept.as_secs()
+                    );
+                    events::system_resume(slept.as_millis() as u64);
+                    targets.apply(slept, instant_paused).await;
+                }
+                ClockJump::WallClockBack { by } => {
+                    tracing::warn!(
+                        "Wall clock went back by {}s; timestamps in logs and stats may be out of order",
+                        by.as_secs()
+                    );
+                }
+            }
+            let _ = jumps.send(jump);
+        }
+    })
+}
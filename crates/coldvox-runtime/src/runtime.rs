@@ -0,0 +1,2185 @@
+use coldvox_audio::ring_buffer::{AudioProducer, OverflowPolicy};
+use coldvox_audio::SharedAudioFrame;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use tokio::signal;
+use tokio::sync::{broadcast, mpsc, watch, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info};
+
+use coldvox_audio::{
+    AudioCaptureThread, AudioChunker, AudioRingBuffer, ChannelSelection, ChunkerConfig,
+    FrameReader, ResamplerQuality,
+};
+use coldvox_audio_quality::{QualityConfig, QualityWarning};
+use coldvox_foundation::{events, AudioConfig, DeviceEvent};
+use coldvox_stt::TranscriptionEvent;
+use coldvox_telemetry::PipelineMetrics;
+use coldvox_vad::config::SileroConfig;
+use coldvox_vad::{UnifiedVadConfig, VadEvent, VadMode, FRAME_SIZE_SAMPLES, SAMPLE_RATE_HZ};
+use coldvox_vad_silero::{clamp_threshold, SensitivityPreset};
+
+use crate::hotkey::indicator::{spawn_indicator, IndicatorKind};
+use crate::hotkey::{spawn_hotkey_listener, HotkeyAction, HotkeyConfig};
+use crate::pause::PauseControl;
+use crate::stt::plugin_manager::SttPluginManager;
+
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+use crate::stt::processor::PluginSttProcessor;
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+use crate::stt::session::{SessionEvent, SessionSource, Settings};
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+use coldvox_stt::TranscriptionConfig;
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+use std::time::Instant;
+
+/// Activation strategy for push-to-talk vs voice activation
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum ActivationMode {
+    Vad,
+    Hotkey,
+    AlwaysOnPushToTranscribe,
+}
+
+impl From<ActivationMode> for crate::stt::session::ActivationMode {
+    fn from(val: ActivationMode) -> Self {
+        match val {
+            ActivationMode::Vad => crate::stt::session::ActivationMode::Vad,
+            ActivationMode::Hotkey => crate::stt::session::ActivationMode::Hotkey,
+            ActivationMode::AlwaysOnPushToTranscribe => {
+                crate::stt::session::ActivationMode::AlwaysOnPushToTranscribe
+            }
+        }
+    }
+}
+
+/// Text-injection options (only when the feature is enabled)
+
+#[derive(Clone, Debug)]
+pub struct InjectionOptions {
+    pub enable: bool,
+    pub allow_kdotool: bool,
+    pub allow_enigo: bool,
+    /// Type with xdotool on plain X11 sessions
+    pub allow_xdotool: bool,
+    /// Type through the XDG RemoteDesktop portal on Wayland
+    pub allow_portal: bool,
+    /// Inject through the primary (middle-click) selection
+    pub allow_primary_selection: bool,
+    /// Middle-click to paste after setting the primary selection
+    pub primary_selection_middle_click: bool,
+    pub inject_on_unknown_focus: bool,
+    pub max_total_latency_ms: Option<u64>,
+    pub per_method_timeout_ms: Option<u64>,
+    pub cooldown_initial_ms: Option<u64>,
+    /// Learn a keystroke rate per app for typing backends
+    pub adaptive_pacing: bool,
+    /// Vary each typed injection's keystroke rate by up to this percentage
+    pub keystroke_jitter_pct: u32,
+    /// Keyboard layout for uinput typing (None = detect)
+    pub keyboard_layout: Option<String>,
+    /// If true, exit immediately if all injection methods fail.
+    pub fail_fast: bool,
+    /// Where to persist per-application injection stats (None = don't persist)
+    pub stats_path: Option<std::path::PathBuf>,
+    /// Move the caret past text inserted via AT-SPI
+    pub atspi_move_caret: bool,
+    /// Replace a selection at the caret when inserting via AT-SPI
+    pub atspi_replace_selection: bool,
+    /// Formatting control for AT-SPI inserts into rich text targets
+    pub atspi_text_attributes: crate::text_injection::AtspiTextAttributes,
+    /// Send spoken commands ("enter", "undo", ...) as key chords
+    pub voice_commands: bool,
+    /// Longest utterance that may dispatch a command from partials (ms)
+    pub command_max_duration_ms: u64,
+    /// Agreeing partials required before a command is dispatched early
+    pub command_min_stable_partials: u32,
+    /// Hold dictation until flushed by hotkey or voice command
+    pub buffered_dictation: bool,
+    /// Dictation mode at startup: normal, spell, number or literal
+    pub dictation_mode: crate::text_injection::DictationMode,
+    /// Merge finals arriving within this many ms into one injection
+    pub final_merge_window_ms: u64,
+    /// Audio sources whose transcripts are injected (others are only stored)
+    pub inject_sources: Vec<String>,
+    /// What to do while the screen is shared
+    pub screen_share_policy: crate::text_injection::ScreenSharePolicy,
+    /// Desktop notification when dictated text could not be injected
+    pub notify_on_failure: bool,
+    /// Put text that could not be injected on the clipboard
+    pub recover_failed_text: bool,
+    /// Utterances that may wait for injection
+    pub queue_capacity: usize,
+    /// Merge utterances arriving closer together than this (ms, 0 = off)
+    pub queue_coalesce_ms: u64,
+    /// What to do when the injection queue is full
+    pub queue_drop_policy: crate::text_injection::QueueDropPolicy,
+    /// Verify the clipboard restore and retry this many times (0 = don't verify)
+    pub clipboard_restore_retries: u32,
+    /// Back up and restore every clipboard MIME type, not just text
+    pub clipboard_preserve_mime_types: bool,
+    /// Ask clipboard managers to keep seeded payloads out of history
+    pub clipboard_manager_hint: bool,
+    /// Offer HTML next to plain text when pasting lists and paragraphs
+    pub clipboard_html: bool,
+    /// Targets offered HTML even with `clipboard_html` off
+    pub clipboard_html_apps: Vec<String>,
+    /// Targets that only get plain text
+    pub clipboard_plain_apps: Vec<String>,
+    /// Check for display-server restarts this often (ms, 0 = off)
+    pub display_watch_interval_ms: u64,
+    /// Hold back finals whose mean word confidence is below this
+    pub min_confidence: Option<f32>,
+    /// How a held transcript is surfaced
+    pub low_confidence_feedback: crate::low_confidence::LowConfidenceFeedback,
+    /// Only inject into matching targets (app id, `title:` or `url:` patterns)
+    pub allowlist: Vec<String>,
+    /// Never inject into matching targets
+    pub blocklist: Vec<String>,
+    /// Where to append the injection audit log (None = no audit log)
+    pub audit_log_path: Option<std::path::PathBuf>,
+    /// Text kept in the audit log (None = follow redact_logs)
+    pub audit_text: Option<crate::text_injection::types::AuditTextMode>,
+    /// What to do with transcripts the content filter flags
+    pub sensitive_content: crate::text_injection::SensitiveContentAction,
+    /// Extra patterns for the content filter
+    pub sensitive_patterns: Vec<String>,
+    /// Drop final transcripts longer than this (0: no limit)
+    pub max_utterance_chars: u32,
+    /// Drop injections beyond this many characters per minute (0: no limit)
+    pub max_chars_per_minute: u32,
+    /// Paste longer text even in keystroke mode (0: never)
+    pub paste_above_chars: u32,
+}
+
+impl Default for InjectionOptions {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            allow_kdotool: false,
+            allow_enigo: false,
+            allow_xdotool: false,
+            allow_portal: false,
+            allow_primary_selection: false,
+            primary_selection_middle_click: true,
+            inject_on_unknown_focus: false,
+            max_total_latency_ms: None,
+            per_method_timeout_ms: None,
+            cooldown_initial_ms: None,
+            adaptive_pacing: true,
+            keystroke_jitter_pct: 0,
+            keyboard_layout: None,
+            fail_fast: false,
+            stats_path: None,
+            atspi_move_caret: true,
+            atspi_replace_selection: false,
+            atspi_text_attributes: Default::default(),
+            voice_commands: false,
+            command_max_duration_ms: 1500,
+            command_min_stable_partials: 2,
+            buffered_dictation: false,
+            dictation_mode: Default::default(),
+            final_merge_window_ms: 0,
+            inject_sources: vec!["mic".to_string()],
+            screen_share_policy: Default::default(),
+            notify_on_failure: true,
+            recover_failed_text: true,
+            queue_capacity: 8,
+            queue_coalesce_ms: 0,
+            queue_drop_policy: Default::default(),
+            clipboard_restore_retries: 3,
+            clipboard_preserve_mime_types: true,
+            clipboard_manager_hint: true,
+            clipboard_html: false,
+            clipboard_html_apps: Vec::new(),
+            clipboard_plain_apps: Vec::new(),
+            display_watch_interval_ms: 2000,
+            min_confidence: None,
+            low_confidence_feedback: Default::default(),
+            allowlist: Vec::new(),
+            blocklist: Vec::new(),
+            audit_log_path: None,
+            audit_text: None,
+            sensitive_content: Default::default(),
+            sensitive_patterns: Vec::new(),
+            max_utterance_chars: 4000,
+            max_chars_per_minute: 10_000,
+            paste_above_chars: 1000,
+        }
+    }
+}
+
+impl InjectionOptions {
+    /// Injection crate config for these options
+    pub fn to_config(&self) -> crate::text_injection::InjectionConfig {
+        let mut config = crate::text_injection::InjectionConfig {
+            allow_kdotool: self.allow_kdotool,
+            allow_enigo: self.allow_enigo,
+            allow_xdotool: self.allow_xdotool,
+            allow_portal: self.allow_portal,
+            allow_primary_selection: self.allow_primary_selection,
+            primary_selection_middle_click: self.primary_selection_middle_click,
+            inject_on_unknown_focus: self.inject_on_unknown_focus,
+            adaptive_pacing: self.adaptive_pacing,
+            keystroke_jitter_pct: self.keystroke_jitter_pct,
+            keyboard_layout: self.keyboard_layout.clone(),
+            stats_path: self.stats_path.clone(),
+            atspi_move_caret: self.atspi_move_caret,
+            atspi_replace_selection: self.atspi_replace_selection,
+            atspi_text_attributes: self.atspi_text_attributes,
+            voice_commands: self.voice_commands,
+            command_max_duration_ms: self.command_max_duration_ms,
+            command_min_stable_partials: self.command_min_stable_partials,
+            buffered_dictation: self.buffered_dictation,
+            dictation_mode: self.dictation_mode,
+            final_merge_window_ms: self.final_merge_window_ms,
+            inject_sources: self.inject_sources.clone(),
+            screen_share_policy: self.screen_share_policy,
+            notify_on_failure: self.notify_on_failure,
+            recover_failed_text: self.recover_failed_text,
+            queue_capacity: self.queue_capacity,
+            queue_coalesce_ms: self.queue_coalesce_ms,
+            queue_drop_policy: self.queue_drop_policy,
+            clipboard_restore_retries: self.clipboard_restore_retries,
+            clipboard_preserve_mime_types: self.clipboard_preserve_mime_types,
+            clipboard_manager_hint: self.clipboard_manager_hint,
+            clipboard_html: self.clipboard_html,
+            clipboard_html_apps: self.clipboard_html_apps.clone(),
+            clipboard_plain_apps: self.clipboard_plain_apps.clone(),
+            display_watch_interval_ms: self.display_watch_interval_ms,
+            min_confidence: self.min_confidence,
+            allowlist: self.allowlist.clone(),
+            blocklist: self.blocklist.clone(),
+            audit_log_path: self.audit_log_path.clone(),
+            audit_text: self.audit_text,
+            sensitive_content: self.sensitive_content,
+            sensitive_patterns: self.sensitive_patterns.clone(),
+            max_utterance_chars: self.max_utterance_chars,
+            max_chars_per_minute: self.max_chars_per_minute,
+            paste_above_chars: self.paste_above_chars,
+            // clipboard restore is always enabled by the text-injection crate
+            ..Default::default()
+        };
+        if let Some(v) = self.max_total_latency_ms {
+            config.max_total_latency_ms = v;
+        }
+        if let Some(v) = self.per_method_timeout_ms {
+            config.per_method_timeout_ms = v;
+        }
+        if let Some(v) = self.cooldown_initial_ms {
+            config.cooldown_initial_ms = v;
+        }
+        // NOTE: fail_fast is currently not a field on InjectionConfig
+        // This mapping may need to be re-added once the field is available
+        // config.fail_fast = self.fail_fast
+        //     || std::env::var("COLDVOX_FAIL_FAST")
+        //         .map(|v| v == "1" || v.to_lowercase() == "true")
+        //         .unwrap_or(false);
+        config
+    }
+}
+
+/// Options for starting the ColdVox runtime
+#[derive(Clone)]
+pub struct AppRuntimeOptions {
+    pub device: Option<String>,
+    pub resampler_quality: ResamplerQuality,
+    pub activation_mode: ActivationMode,
+    /// Optional VAD configuration override (uses defaults if None)
+    pub vad_config: Option<coldvox_vad::config::UnifiedVadConfig>,
+    /// STT plugin selection configuration
+    pub stt_selection: Option<coldvox_stt::plugin::PluginSelectionConfig>,
+    /// Second STT plugin to transcribe every utterance with, for comparison
+    pub stt_compare: Option<crate::stt::compare::ComparisonConfig>,
+    /// Drop or flag finals that look hallucinated (None = off)
+    pub stt_hallucination: Option<crate::stt::hallucination::HallucinationConfig>,
+    /// Endpoint for the `ws-remote` plugin (built-in default when None)
+    #[cfg(feature = "ws-remote")]
+    pub ws_remote: Option<coldvox_stt::plugins::ws_remote::WsRemoteConfig>,
+
+    pub injection: Option<InjectionOptions>,
+    /// Snapshot un-injected text here for crash recovery (None = off)
+    pub recovery_path: Option<std::path::PathBuf>,
+    /// Keep recent audio and events in memory for bug reports (None = off)
+    pub black_box: Option<crate::black_box::BlackBoxConfig>,
+    /// Whether to poll for device hotplug events (ALSA/CPAL enumeration)
+    pub enable_device_monitor: bool,
+    /// Devices to fail over to (in order) when the active device disappears
+    pub fallback_devices: Vec<String>,
+    /// Suspend capture after prolonged silence (None = always capture)
+    pub idle: Option<crate::idle::IdleConfig>,
+    /// Capture ring buffer capacity in samples
+    pub capture_buffer_samples: usize,
+    /// What a write into a full capture buffer does
+    pub capture_buffer_policy: coldvox_audio::ring_buffer::OverflowPolicy,
+    /// Input channel selection/downmix for multi-channel devices
+    pub audio_channel: ChannelSelection,
+    /// Calibrated room noise to subtract before VAD/STT (None = no suppression)
+    pub noise_profile: Option<coldvox_audio_quality::NoiseProfile>,
+    pub test_device_config: Option<coldvox_audio::DeviceConfig>,
+    pub test_capture_to_dummy: bool,
+    pub test_injection_sink: Option<Arc<dyn crate::text_injection::TextInjector>>,
+    pub transcription_config: Option<coldvox_stt::TranscriptionConfig>,
+    /// Push-to-talk feedback surface (OSD/notification/terminal)
+    pub indicator: IndicatorKind,
+    /// In hotkey mode, transcribe exactly the press..release audio as one utterance
+    pub ptt_bounded_audio: bool,
+    /// Pre-roll prepended to bounded push-to-talk utterances
+    pub ptt_pre_roll_ms: u32,
+    /// Audio from before a VAD speech start prepended to the utterance
+    pub vad_pre_roll_ms: u32,
+    /// Split VAD utterances at a pause once they run this long (0 = off)
+    pub max_utterance_secs: u32,
+    /// Watch the input for clipping, quiet speech and off-axis talking
+    pub quality_warnings: bool,
+    /// Close the capture stream while the pipeline is paused
+    pub pause_suspends_capture: bool,
+    /// Ignore the microphone while system audio plays (None = off)
+    pub playback_guard: Option<crate::audio::playback::PlaybackGuardConfig>,
+    /// Global shortcuts, the actions they trigger and tap/hold behaviour
+    pub hotkeys: HotkeyConfig,
+    /// Speech synthesizer for reading dictation back
+    pub tts: crate::tts::TtsConfig,
+    /// Serve Prometheus metrics on this address (needs feature `metrics-http`)
+    pub metrics_addr: Option<std::net::SocketAddr>,
+}
+
+impl std::fmt::Debug for AppRuntimeOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppRuntimeOptions")
+            .field("device", &self.device)
+            .field("resampler_quality", &self.resampler_quality)
+            .field("activation_mode", &self.activation_mode)
+            .field("stt_selection", &self.stt_selection)
+            .field("stt_compare", &self.stt_compare)
+            .field("stt_hallucination", &self.stt_hallucination)
+            .field("injection", &self.injection)
+            .field("recovery_path", &self.recovery_path)
+            .field(
+                "black_box",
+                &self.black_box.as_ref().map(|b| (&b.dir, b.seconds)),
+            )
+            .field("enable_device_monitor", &self.enable_device_monitor)
+            .field("fallback_devices", &self.fallback_devices)
+            .field("idle", &self.idle)
+            .field("capture_buffer_samples", &self.capture_buffer_samples)
+            .field("capture_buffer_policy", &self.capture_buffer_policy)
+            .field("audio_channel", &self.audio_channel)
+            .field(
+                "noise_profile",
+                &self.noise_profile.as_ref().map(|p| p.rms_dbfs),
+            )
+            .field("test_device_config", &self.test_device_config)
+            .field("test_capture_to_dummy", &self.test_capture_to_dummy)
+            .field(
+                "test_injection_sink",
+                &self.test_injection_sink.as_ref().map(|_| "Some(...)"),
+            )
+            .field("transcription_config", &self.transcription_config)
+            .field("indicator", &self.indicator)
+            .field("ptt_bounded_audio", &self.ptt_bounded_audio)
+            .field("ptt_pre_roll_ms", &self.ptt_pre_roll_ms)
+            .field("vad_pre_roll_ms", &self.vad_pre_roll_ms)
+            .field("max_utterance_secs", &self.max_utterance_secs)
+            .field("quality_warnings", &self.quality_warnings)
+            .field("pause_suspends_capture", &self.pause_suspends_capture)
+            .field("playback_guard", &self.playback_guard)
+            .field("hotkeys", &self.hotkeys)
+            .field("tts", &self.tts)
+            .field("metrics_addr", &self.metrics_addr)
+            .finish()
+    }
+}
+
+impl Default for AppRuntimeOptions {
+    fn default() -> Self {
+        Self {
+            device: None,
+            resampler_quality: ResamplerQuality::Balanced,
+            activation_mode: ActivationMode::Vad,
+            vad_config: None, // Use VAD defaults
+            stt_selection: None,
+            stt_compare: None,
+            stt_hallucination: None,
+            #[cfg(feature = "ws-remote")]
+            ws_remote: None,
+
+            injection: None,
+            recovery_path: None,
+            black_box: None,
+            enable_device_monitor: false,
+            fallback_devices: Vec::new(),
+            idle: None,
+            capture_buffer_samples: 65_536,
+            capture_buffer_policy: Default::default(),
+            audio_channel: ChannelSelection::Mix,
+            noise_profile: None,
+            test_device_config: None,
+            test_capture_to_dummy: false,
+            test_injection_sink: None,
+            transcription_config: None,
+            indicator: IndicatorKind::Off,
+            ptt_bounded_audio: false,
+            ptt_pre_roll_ms: 300,
+            vad_pre_roll_ms: 300,
+            max_utterance_secs: 20,
+            quality_warnings: true,
+            pause_suspends_capture: false,
+            playback_guard: None,
+            hotkeys: HotkeyConfig::default(),
+            tts: crate::tts::TtsConfig::default(),
+            metrics_addr: None,
+        }
+    }
+}
+
+/// Handle to the running application pipeline
+pub struct AppHandle {
+    pub metrics: Arc<PipelineMetrics>,
+    vad_tx: broadcast::Sender<VadEvent>,
+    raw_vad_tx: mpsc::Sender<VadEvent>,
+    audio_tx: broadcast::Sender<SharedAudioFrame>,
+    current_mode: std::sync::Arc<RwLock<ActivationMode>>,
+    pub stt_rx: Option<mpsc::Receiver<TranscriptionEvent>>,
+    stt_bcast_tx: broadcast::Sender<TranscriptionEvent>,
+    comparison_tx: broadcast::Sender<crate::stt::compare::ComparisonResult>,
+    pub plugin_manager: Option<Arc<tokio::sync::RwLock<SttPluginManager>>>,
+
+    audio_capture: AudioCaptureThread,
+    pub audio_producer: Arc<Mutex<AudioProducer>>,
+    chunker_handle: JoinHandle<()>,
+    /// VAD processor in VAD mode; hotkey modes are driven by `hotkey_handle`
+    trigger_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+    hotkeys: HotkeyConfig,
+    hotkey_action_tx: mpsc::Sender<HotkeyAction>,
+    finalize_tx: broadcast::Sender<()>,
+    /// VAD configuration the runtime started with
+    vad_config: UnifiedVadConfig,
+    /// Current Silero sensitivity, watched by the VAD processor
+    vad_tuning: watch::Sender<SileroConfig>,
+    vad_calibrate_tx: broadcast::Sender<u32>,
+    hotkey_handle: Mutex<Option<JoinHandle<()>>>,
+    hotkey_action_handle: JoinHandle<()>,
+    vad_fanout_handle: JoinHandle<()>,
+    stt_handle: Option<JoinHandle<()>>,
+    stt_forward_handle: Option<JoinHandle<()>>,
+
+    injection_handle: Option<JoinHandle<()>>,
+    /// Keeps the crash recovery snapshot current (None when it is off)
+    recovery: Option<(JoinHandle<()>, std::path::PathBuf)>,
+    /// Recent audio and events for bug reports (None when it is off)
+    black_box: Option<Arc<crate::black_box::BlackBox>>,
+    /// Feed the black box and save it on SIGUSR1
+    black_box_handles: Vec<JoinHandle<()>>,
+    /// Suspends capture after prolonged silence (None when idle mode is off)
+    idle_handle: Option<JoinHandle<()>>,
+    /// Fixes up timers and the capture device after a system resume
+    resume_handle: JoinHandle<()>,
+    clock_jump_tx: broadcast::Sender<coldvox_foundation::ClockJump>,
+    /// Audio quality monitor (None when quality warnings are off)
+    quality_handle: Option<JoinHandle<()>>,
+    quality_tx: broadcast::Sender<QualityWarning>,
+    /// User-requested pause of VAD/STT input
+    pause: PauseControl,
+    /// Sink monitor for playback suppression (None when it is off)
+    playback_handle: Option<JoinHandle<()>>,
+    idle_wake: Arc<tokio::sync::Notify>,
+    paste_control: Option<crate::text_injection::PasteControl>,
+    /// Window injection is locked to (None when injection is disabled)
+    target_lock: Option<crate::text_injection::TargetLock>,
+    /// Flushes the buffered dictation draft (None when injection is disabled)
+    flush_signal: Option<Arc<tokio::sync::Notify>>,
+    /// Injection backend status (None when injection is disabled)
+    backend_catalog: Option<crate::text_injection::BackendCatalog>,
+    /// Transcript held back for low confidence (None when injection is disabled)
+    low_confidence: Option<crate::text_injection::LowConfidenceHandle>,
+    /// Notifies the user of held transcripts (None unless `min_confidence` is set)
+    low_confidence_handle: Option<JoinHandle<()>>,
+    /// Size and rate limits of injection (None when injection is disabled)
+    guard_events: Option<broadcast::Sender<crate::text_injection::GuardEvent>>,
+    /// Queued text, methods tried and outcomes (None when injection is disabled)
+    injection_events: Option<broadcast::Sender<crate::text_injection::InjectionEvent>>,
+    /// Dictation time, utterances and words this session
+    session_stats: Arc<crate::session_report::SessionStats>,
+    /// Per-method and per-app injection counters (None when injection is disabled)
+    injection_metrics: Option<Arc<crate::text_injection::types::InjectionMetrics>>,
+    indicator_handle: Option<JoinHandle<()>>,
+    /// Prometheus `/metrics` server (None unless `metrics_addr` is set)
+    metrics_server_handle: Option<JoinHandle<()>>,
+    device_event_handle: JoinHandle<()>,
+    device_event_tx: broadcast::Sender<DeviceEvent>,
+    /// Device capture runs on (None: host default, not yet reported)
+    active_device: Arc<Mutex<Option<String>>>,
+}
+
+impl AppHandle {
+    /// Subscribe to VAD events (multiple subscribers supported)
+    pub fn subscribe_vad(&self) -> broadcast::Receiver<VadEvent> {
+        self.vad_tx.subscribe()
+    }
+
+    /// Subscribe to raw audio frames (16kHz mono i16 samples via SharedAudioFrame)
+    pub fn subscribe_audio(&self) -> broadcast::Receiver<SharedAudioFrame> {
+        self.audio_tx.subscribe()
+    }
+
+    /// Subscribe to transcription events (multiple subscribers supported)
+    pub fn subscribe_stt(&self) -> broadcast::Receiver<TranscriptionEvent> {
+        self.stt_bcast_tx.subscribe()
+    }
+
+    /// Subscribe to A/B transcription comparisons (only sent when
+    /// `stt_compare` is configured)
+    pub fn subscribe_stt_comparisons(
+        &self,
+    ) -> broadcast::Receiver<crate::stt::compare::ComparisonResult> {
+        self.comparison_tx.subscribe()
+    }
+
+    /// Subscribe to STT plugin failover notices (None when STT is disabled)
+    pub async fn subscribe_stt_failover(
+        &self,
+    ) -> Option<broadcast::Receiver<crate::stt::failover::FailoverNotice>> {
+        let pm = self.plugin_manager.as_ref()?;
+        Some(pm.read().await.subscribe_failover())
+    }
+
+    /// Subscribe to chunked-paste progress (None when injection is disabled)
+    pub fn subscribe_paste_progress(
+        &self,
+    ) -> Option<broadcast::Receiver<crate::text_injection::PasteProgress>> {
+        self.paste_control.as_ref().map(|c| c.subscribe())
+    }
+
+    /// Stop a chunked paste in progress before its next chunk
+    pub fn cancel_paste(&self) {
+        if let Some(control) = &self.paste_control {
+            control.cancel();
+        }
+    }
+
+    /// Lock injection to the active window: until unlocked, each transcript
+    /// brings it back to the front before typing, and is not typed if that
+    /// fails. None when injection is disabled or the window can't be captured.
+    pub async fn lock_injection_target(&self) -> Option<crate::text_injection::WindowRef> {
+        let lock = self.target_lock.as_ref()?;
+        match lock.lock_active_window().await {
+            Ok(window) => Some(window),
+            Err(e) => {
+                tracing::warn!("Could not lock injection target: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Let injection follow the focus again. Returns the window it was locked to.
+    pub fn unlock_injection_target(&self) -> Option<crate::text_injection::WindowRef> {
+        self.target_lock.as_ref()?.release()
+    }
+
+    /// Window injection is locked to, if any
+    pub fn injection_target(&self) -> Option<crate::text_injection::WindowRef> {
+        self.target_lock.as_ref()?.current()
+    }
+
+    /// Subscribe to target lock changes (None when injection is disabled)
+    pub fn subscribe_target_lock(
+        &self,
+    ) -> Option<broadcast::Receiver<Option<crate::text_injection::WindowRef>>> {
+        self.target_lock.as_ref().map(|l| l.subscribe())
+    }
+
+    /// End the current utterance now instead of waiting out the silence
+    /// timeout: VAD mode forces a speech end, hotkey modes split the open
+    /// session. STT finalizes and the text is injected right away. No effect
+    /// outside an utterance.
+    pub fn finalize_now(&self) {
+        if self.finalize_tx.send(()).is_err() {
+            debug!("Finalize requested but no activation source is listening");
+        }
+    }
+
+    /// Current VAD sensitivity
+    pub fn vad_sensitivity(&self) -> SileroConfig {
+        self.vad_tuning.borrow().clone()
+    }
+
+    /// Switch VAD sensitivity to a named preset; applies from the next frame
+    pub fn set_vad_preset(&self, preset: SensitivityPreset) {
+        self.vad_tuning
+            .send_modify(|config| *config = preset.apply(config));
+    }
+
+    /// Speech probability threshold (clamped to
+    /// [`THRESHOLD_RANGE`](coldvox_vad_silero::THRESHOLD_RANGE)); lower
+    /// catches quieter speech
+    pub fn set_vad_threshold(&self, threshold: f32) {
+        self.vad_tuning
+            .send_modify(|config| config.threshold = clamp_threshold(threshold));
+    }
+
+    /// Speech needed before an utterance starts
+    pub fn set_vad_min_speech_ms(&self, ms: u32) {
+        self.vad_tuning
+            .send_modify(|config| config.min_speech_duration_ms = ms);
+    }
+
+    /// Silence needed before an utterance ends
+    pub fn set_vad_min_silence_ms(&self, ms: u32) {
+        self.vad_tuning
+            .send_modify(|config| config.min_silence_duration_ms = ms);
+    }
+
+    /// Re-derive the energy VAD's thresholds from the next `duration` of
+    /// room noise (Silero adapts on its own and ignores this). Nothing is
+    /// detected meanwhile, so keep quiet.
+    pub fn calibrate_vad(&self, duration: std::time::Duration) {
+        let ms = duration.as_millis().min(u32::MAX as u128) as u32;
+        if self.vad_calibrate_tx.send(ms).is_err() {
+            debug!("VAD calibration requested but VAD isn't running");
+        }
+    }
+
+    /// Inject the held draft when buffered dictation is on
+    pub fn flush_dictation(&self) {
+        match &self.flush_signal {
+            Some(flush) => flush.notify_one(),
+            None => debug!("Flush requested but text injection is disabled"),
+        }
+    }
+
+    /// Speak the held dictation draft, or the last final transcript when
+    /// nothing is held. The microphone is muted while it plays.
+    pub fn read_back(&self) {
+        if self
+            .hotkey_action_tx
+            .try_send(HotkeyAction::ReadBack)
+            .is_err()
+        {
+            debug!("Read-back requested but the command handler is busy or gone");
+        }
+    }
+
+    /// Subscribe to transcripts held back for low confidence (None when
+    /// injection is disabled)
+    pub fn subscribe_low_confidence(
+        &self,
+    ) -> Option<broadcast::Receiver<crate::text_injection::HeldTranscript>> {
+        self.low_confidence.as_ref().map(|h| h.subscribe())
+    }
+
+    /// Subscribe to injection size and rate limits as they trigger (None when
+    /// injection is disabled)
+    pub fn subscribe_guard_events(
+        &self,
+    ) -> Option<broadcast::Receiver<crate::text_injection::GuardEvent>> {
+        self.guard_events.as_ref().map(|tx| tx.subscribe())
+    }
+
+    /// Subscribe to injection as it happens: text queued, each method tried,
+    /// and whether it was injected (None when injection is disabled)
+    pub fn subscribe_injection(
+        &self,
+    ) -> Option<broadcast::Receiver<crate::text_injection::InjectionEvent>> {
+        self.injection_events.as_ref().map(|tx| tx.subscribe())
+    }
+
+    /// Summary of the session so far: dictation time, utterances, injections
+    /// per application and method, latency and STT failovers
+    pub fn session_report(&self) -> crate::session_report::SessionReport {
+        let injection = self.injection_metrics.as_ref().map(|m| m.snapshot());
+        crate::session_report::SessionReport::collect(
+            &self.session_stats,
+            &self.metrics,
+            injection.as_ref(),
+        )
+    }
+
+    /// Type the transcript held back for low confidence. Returns it, or None
+    /// when nothing is held.
+    pub async fn accept_low_confidence(&self) -> Option<crate::text_injection::HeldTranscript> {
+        match &self.low_confidence {
+            Some(handle) => handle.accept().await,
+            None => None,
+        }
+    }
+
+    /// Name, availability, last error and capabilities of each injection
+    /// backend (empty when injection is disabled)
+    pub async fn list_backends(&self) -> Vec<crate::text_injection::BackendStatus> {
+        match &self.backend_catalog {
+            Some(catalog) => catalog.list_backends().await,
+            None => Vec::new(),
+        }
+    }
+
+    /// Each injection backend's unavailability reason, supported modes and
+    /// measured success and latency (empty when injection is disabled)
+    pub async fn capability_report(&self) -> Vec<crate::text_injection::BackendCapability> {
+        match &self.backend_catalog {
+            Some(catalog) => catalog.capability_report().await,
+            None => Vec::new(),
+        }
+    }
+
+    /// Move capture to input device `name` without restarting the pipeline;
+    /// it also becomes the device failover returns to. If it can't be
+    /// opened capture fails over as usual. Watch
+    /// [`subscribe_device_events`](Self::subscribe_device_events) for the
+    /// outcome.
+    pub fn set_device(&self, name: &str) {
+        self.audio_capture.switch_device(name.to_string());
+    }
+
+    /// Input device capture is running on (None for the host default until
+    /// a switch names it)
+    pub fn current_device(&self) -> Option<String> {
+        self.active_device.lock().clone()
+    }
+
+    /// Subscribe to device hotplug, failover and switch events
+    pub fn subscribe_device_events(&self) -> broadcast::Receiver<DeviceEvent> {
+        self.device_event_tx.subscribe()
+    }
+
+    /// Subscribe to detected system resumes and wall clock changes
+    pub fn subscribe_clock_jumps(&self) -> broadcast::Receiver<coldvox_foundation::ClockJump> {
+        self.clock_jump_tx.subscribe()
+    }
+
+    /// Subscribe to audio quality warnings (clipping, quiet speech,
+    /// off-axis), rate-limited at the source
+    pub fn subscribe_quality(&self) -> broadcast::Receiver<QualityWarning> {
+        self.quality_tx.subscribe()
+    }
+
+    /// Stop feeding audio to VAD and STT, keeping models loaded. The
+    /// utterance in progress is finalized first; with
+    /// `pause_suspends_capture` the capture stream is closed too.
+    pub fn pause(&self) {
+        self.pause.pause();
+    }
+
+    /// Feed audio to VAD and STT again after [`pause`](Self::pause)
+    pub fn resume(&self) {
+        self.pause.resume();
+    }
+
+    /// Pause if running, resume if paused; returns whether it is now paused
+    pub fn toggle_pause(&self) -> bool {
+        self.pause.toggle()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.pause.is_paused()
+    }
+
+    /// Watch the paused state
+    pub fn subscribe_pause(&self) -> watch::Receiver<bool> {
+        self.pause.subscribe()
+    }
+
+    /// Write the black box recording to a bug report bundle; None when the
+    /// recorder is off
+    pub fn save_black_box(&self) -> Option<std::io::Result<std::path::PathBuf>> {
+        self.black_box.as_ref().map(|b| b.save())
+    }
+
+    /// Resume capture if the idle monitor suspended it
+    pub fn wake(&self) {
+        self.idle_wake.notify_one();
+    }
+
+    /// Whether capture is suspended by the energy-saver idle mode
+    pub fn is_idle(&self) -> bool {
+        self.audio_capture.is_suspended()
+    }
+
+    /// Gracefully stop the pipeline and wait for shutdown
+    pub async fn shutdown(self: Arc<Self>) {
+        debug!("Shutting down ColdVox runtime...");
+        // Caller and runtime logs both emit at debug to reduce noisy shutdown info-level logs.
+
+        // Try to unwrap the Arc to get ownership
+        let this = match Arc::try_unwrap(self) {
+            Ok(handle) => handle,
+            Err(_) => {
+                error!("Cannot shutdown: AppHandle still has multiple references");
+                return;
+            }
+        };
+        // Ensure tqdm is enabled to avoid buggy 'disabled_tqdm' stub in some Python envs
+        std::env::set_var("TQDM_DISABLE", "0");
+
+        // Stop audio capture first to quiesce the source
+        this.audio_capture.stop();
+
+        // Abort async tasks
+        this.chunker_handle.abort();
+        if let Some(h) = this.trigger_handle.lock().as_ref() {
+            h.abort();
+        }
+        if let Some(h) = this.hotkey_handle.lock().take() {
+            h.abort();
+        }
+        this.hotkey_action_handle.abort();
+        this.vad_fanout_handle.abort();
+        if let Some(h) = &this.stt_handle {
+            h.abort();
+        }
+        if let Some(h) = &this.stt_forward_handle {
+            h.abort();
+        }
+
+        if let Some(h) = &this.injection_handle {
+            h.abort();
+        }
+        if let Some((h, path)) = &this.recovery {
+            h.abort();
+            // A clean exit leaves nothing to recover
+            if let Err(e) = crate::recovery::clear(path) {
+                tracing::warn!("Failed to remove {}: {}", path.display(), e);
+            }
+        }
+        for h in &this.black_box_handles {
+            h.abort();
+        }
+        if let Some(h) = &this.indicator_handle {
+            h.abort();
+        }
+        if let Some(h) = &this.idle_handle {
+            h.abort();
+        }
+        if let Some(h) = &this.quality_handle {
+            h.abort();
+        }
+        if let Some(h) = &this.playback_handle {
+            h.abort();
+        }
+        if let Some(h) = &this.low_confidence_handle {
+            h.abort();
+        }
+        this.resume_handle.abort();
+        if let Some(h) = &this.metrics_server_handle {
+            h.abort();
+        }
+        this.device_event_handle.abort();
+
+        // Stop plugin manager tasks
+        if let Some(pm) = &this.plugin_manager {
+            // Unload all plugins before stopping tasks
+            let _ = pm.read().await.unload_all_plugins().await;
+            let _ = pm.read().await.stop_gc_task().await;
+            let _ = pm.read().await.stop_metrics_task().await;
+        }
+
+        // Await tasks to ensure clean termination
+        let _ = this.chunker_handle.await;
+        let trigger_handle = Arc::try_unwrap(this.trigger_handle)
+            .expect("trigger_handle should have no other references")
+            .into_inner();
+        if let Some(h) = trigger_handle {
+            let _ = h.await;
+        }
+        let _ = this.vad_fanout_handle.await;
+        if let Some(h) = this.stt_handle {
+            let _ = h.await;
+        }
+
+        if let Some(h) = this.injection_handle {
+            let _ = h.await;
+        }
+
+        debug!("ColdVox runtime shutdown complete");
+    }
+
+    /// Wait for shutdown signal (SIGINT, SIGTERM)
+    pub async fn wait_for_shutdown_signal() {
+        info!("Waiting for shutdown signal (Ctrl+C or SIGTERM)...");
+        match signal::ctrl_c().await {
+            Ok(()) => {
+                info!("Received SIGINT (Ctrl+C), initiating graceful shutdown");
+            }
+            Err(err) => {
+                error!("Failed to listen for SIGINT: {}", err);
+            }
+        }
+    }
+
+    /// Switch activation mode at runtime without full restart
+    pub async fn set_activation_mode(
+        &self,
+        mode: ActivationMode,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut old = self.current_mode.write().await;
+        if *old == mode {
+            return Ok(());
+        }
+
+        info!("Switching activation mode from {:?} to {:?}", *old, mode);
+
+        // Unload STT plugins before switching modes to ensure clean state
+        if let Some(ref pm) = self.plugin_manager {
+            info!("Unloading STT plugins before activation mode switch");
+            let _ = pm.read().await.unload_all_plugins().await;
+        }
+
+        if let Some(h) = self.trigger_handle.lock().take() {
+            h.abort();
+        }
+        // Spawn new trigger
+        let new_handle = match mode {
+            ActivationMode::Vad => {
+                // Pick up live sensitivity changes made while in another mode
+                let vad_cfg = UnifiedVadConfig {
+                    silero: self.vad_tuning.borrow().clone(),
+                    ..self.vad_config.clone()
+                };
+                let vad_audio_rx = self.audio_tx.subscribe();
+                Some(
+                    crate::audio::vad_processor::VadProcessor::spawn_with_finalize(
+                        vad_cfg,
+                        vad_audio_rx,
+                        self.raw_vad_tx.clone(),
+                        Some(self.metrics.clone()),
+                        Some(self.finalize_tx.subscribe()),
+                        Some(self.vad_tuning.subscribe()),
+                        Some(self.vad_calibrate_tx.subscribe()),
+                    )?,
+                )
+            }
+            ActivationMode::Hotkey | ActivationMode::AlwaysOnPushToTranscribe => {
+                let mut hotkey_guard = self.hotkey_handle.lock();
+                if hotkey_guard.is_none() {
+                    *hotkey_guard = Some(spawn_hotkey_listener(
+                        self.hotkeys.clone(),
+                        self.current_mode.clone(),
+                        self.raw_vad_tx.clone(),
+                        self.hotkey_action_tx.clone(),
+                        self.finalize_tx.subscribe(),
+                    ));
+                }
+                None
+            }
+        };
+        *self.trigger_handle.lock() = new_handle;
+        *old = mode;
+
+        info!("Successfully switched to {:?} activation mode", mode);
+        Ok(())
+    }
+}
+
+/// Start the ColdVox pipeline with the given options
+pub async fn start(
+    opts: AppRuntimeOptions,
+) -> Result<AppHandle, Box<dyn std::error::Error + Send + Sync>> {
+    // Metrics shared across components
+    let metrics = Arc::new(PipelineMetrics::default());
+
+    info!("Starting ColdVox runtime with unified STT architecture");
+
+    // 1) Audio capture
+    let audio_config = AudioConfig {
+        silence_threshold: 100,
+        capture_buffer_samples: opts.capture_buffer_samples,
+    };
+    if opts.capture_buffer_policy == OverflowPolicy::BlockProducer {
+        tracing::warn!(
+            "Capture buffer policy block-producer sleeps in the audio callback \
+             and may cause xruns; prefer drop-oldest or grow-once"
+        );
+    }
+    let ring_buffer = AudioRingBuffer::with_policy(
+        audio_config.capture_buffer_samples,
+        opts.capture_buffer_policy,
+    );
+    let (audio_producer, audio_consumer) = ring_buffer.split();
+    let audio_producer = Arc::new(Mutex::new(audio_producer));
+
+    let (audio_capture, device_cfg, device_config_rx, device_event_rx) = if opts
+        .test_capture_to_dummy
+    {
+        // In test "dummy" mode, avoid opening any real audio device to prevent ALSA spam.
+        // Construct a no-op capture thread and synthesize device config + channels.
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::thread;
+        let shutdown = std::sync::Arc::new(AtomicBool::new(true));
+        let shutdown_clone = shutdown.clone();
+        let handle = thread::Builder::new()
+            .name("audio-capture-dummy".to_string())
+            .spawn(move || {
+                while shutdown_clone.load(Ordering::Relaxed) {
+                    thread::sleep(std::time::Duration::from_millis(50));
+                }
+            })
+            .map_err(Box::<dyn std::error::Error + Send + Sync>::from)
+            .unwrap();
+
+        // Use provided test device config if available; else fall back to a sane default.
+        let initial_dc = if let Some(dc) = opts.test_device_config.clone() {
+            dc
+        } else {
+            coldvox_audio::DeviceConfig {
+                sample_rate: SAMPLE_RATE_HZ,
+                channels: 1,
+                channel: opts.audio_channel,
+            }
+        };
+
+        // Create broadcast channels and emit initial device config
+        let (cfg_tx, cfg_rx) = tokio::sync::broadcast::channel::<coldvox_audio::DeviceConfig>(16);
+        let (dev_evt_tx, dev_evt_rx) =
+            tokio::sync::broadcast::channel::<coldvox_foundation::DeviceEvent>(32);
+        let _ = dev_evt_tx; // not used in tests here
+
+        // **Crucially, send the initial config immediately.**
+        if let Err(e) = cfg_tx.send(initial_dc.clone()) {
+            tracing::warn!("Failed to send initial dummy device config: {}", e);
+        }
+
+        // Build a dummy AudioCaptureThread
+        let dummy_capture = AudioCaptureThread {
+            handle,
+            shutdown,
+            device_monitor_handle: None,
+            suspended: Arc::new(AtomicBool::new(false)),
+            muted: Arc::new(AtomicBool::new(false)),
+            revalidate: Arc::new(AtomicBool::new(false)),
+            switch_to: Arc::new(Mutex::new(None)),
+        };
+
+        (dummy_capture, initial_dc, cfg_rx, dev_evt_rx)
+    } else {
+        AudioCaptureThread::spawn_with_fallbacks(
+            audio_config,
+            audio_producer.clone(),
+            opts.device.clone(),
+            opts.fallback_devices.clone(),
+            opts.audio_channel,
+            opts.enable_device_monitor,
+        )?
+    };
+
+    // Record capture device failovers in telemetry, track the active device
+    // and republish the events for subscribers
+    let (device_event_tx, _) = broadcast::channel::<DeviceEvent>(32);
+    let active_device = Arc::new(Mutex::new(opts.device.clone()));
+    let device_event_handle = {
+        let metrics = metrics.clone();
+        let active_device = active_device.clone();
+        let device_event_tx = device_event_tx.clone();
+        let mut rx = device_event_rx;
+        tokio::spawn(async move {
+            // Target of a switch requested through `AppHandle::set_device`
+            let mut requested: Option<String> = None;
+            loop {
+                let event = match rx.recv().await {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                match &event {
+                    DeviceEvent::DeviceSwitchRequested { target } => {
+                        requested = Some(target.clone());
+                    }
+                    DeviceEvent::DeviceSwitched { from, to } => {
+                        *active_device.lock() = Some(to.clone());
+                        if requested.take().as_ref() == Some(to) {
+                            info!(
+                                "Audio capture switched from {:?} to {} as requested",
+                                from, to
+                            );
+                        } else {
+                            info!("Audio capture switched from {:?} to {}", from, to);
+                            metrics.record_device_failover(true);
+                        }
+                    }
+                    DeviceEvent::DeviceSwitchFailed { attempted, .. } => {
+                        requested = None;
+                        error!(
+                            "Audio capture lost device {} and no fallback could be opened",
+                            attempted
+                        );
+                        metrics.record_device_failover(false);
+                    }
+                    _ => {}
+                }
+                let _ = device_event_tx.send(event);
+            }
+        })
+    };
+
+    // 2) Chunker (with resampler)
+    let frame_reader = FrameReader::new(
+        audio_consumer,
+        device_cfg.sample_rate,
+        device_cfg.channels,
+        audio_config.capture_buffer_samples,
+        Some(metrics.clone()),
+    );
+    let chunker_cfg = ChunkerConfig {
+        frame_size_samples: FRAME_SIZE_SAMPLES,
+        sample_rate_hz: SAMPLE_RATE_HZ,
+        resampler_quality: opts.resampler_quality,
+        channel: opts.audio_channel,
+    };
+    let (audio_tx, _) = broadcast::channel::<SharedAudioFrame>(200);
+    // In tests, allow overriding the device config to match the injected WAV
+    #[cfg(test)]
+    let device_config_rx_for_chunker = if let Some(dc) = opts.test_device_config.clone() {
+        let (tx, rx) = broadcast::channel::<coldvox_audio::DeviceConfig>(8);
+        let _ = tx.send(dc);
+        rx
+    } else {
+        device_config_rx.resubscribe()
+    };
+
+    #[cfg(not(test))]
+    let device_config_rx_for_chunker = device_config_rx.resubscribe();
+
+    // With playback suppression the last stage before `audio_tx` silences
+    // the microphone while system audio plays
+    let (stage_tx, playback_handle) = match opts.playback_guard.clone() {
+        Some(guard) => {
+            let playing = Arc::new(std::sync::atomic::AtomicBool::new(false));
+            let handle = crate::audio::playback::spawn_playback_monitor(
+                guard,
+                playing.clone(),
+                metrics.clone(),
+            );
+            let (gate_tx, gate_rx) = broadcast::channel::<SharedAudioFrame>(200);
+            crate::audio::playback::spawn_playback_gate(playing, gate_rx, audio_tx.clone());
+            (gate_tx, Some(handle))
+        }
+        None => (audio_tx.clone(), None),
+    };
+    // With a noise profile the chunker feeds the suppression stage, which
+    // publishes the cleaned frames on `stage_tx`
+    let chunker_tx = match opts.noise_profile.clone() {
+        Some(profile) => {
+            info!(
+                noise_floor_dbfs = profile.rms_dbfs,
+                "Noise suppression enabled"
+            );
+            let (raw_tx, raw_rx) = broadcast::channel::<SharedAudioFrame>(200);
+            crate::audio::noise::spawn_noise_suppression(profile, raw_rx, stage_tx);
+            raw_tx
+        }
+        None => stage_tx,
+    };
+    // Quality is judged on the input as captured, before noise suppression
+    let quality_audio_rx = opts.quality_warnings.then(|| chunker_tx.subscribe());
+    // "Finalize now" requests, served by whichever activation source is live
+    let (finalize_tx, _) = broadcast::channel::<()>(4);
+    let mut pause = PauseControl::new(finalize_tx.clone(), metrics.clone());
+    if opts.pause_suspends_capture {
+        pause = pause.with_capture_suspend(audio_capture.suspended.clone());
+    }
+    let chunker = AudioChunker::new(frame_reader, chunker_tx, chunker_cfg)
+        .with_metrics(metrics.clone())
+        .with_device_config(device_config_rx_for_chunker)
+        .with_pause(pause.flag());
+    let chunker_handle = chunker.spawn();
+
+    // 3) Activation source (VAD or Hotkey) feeding a raw VAD mpsc channel
+    let (raw_vad_tx, raw_vad_rx) = mpsc::channel::<VadEvent>(200);
+    let current_mode = Arc::new(RwLock::new(opts.activation_mode));
+
+    // VAD (Voice Activity Detection) Configuration
+    //
+    // The VAD is configured to detect speech segments from the audio stream.
+    // Key parameters for the Silero VAD engine are set here.
+    //
+    // Of particular note is `min_silence_duration_ms`. This value was
+    // intentionally increased from a default of 100ms to 500ms.
+    //
+    // Rationale for 500ms silence duration (see issue #61):
+    // - **Problem:** Shorter silence durations (e.g., 100-200ms) can cause the
+    //   VAD to split a single logical utterance into multiple speech events
+    //   during natural pauses in speech.
+    // - **Impact:** This fragmentation leads to disjointed transcriptions and
+    //   can prevent the STT engine from understanding the full context of a
+    //   sentence. It also increases overhead from starting and stopping the
+    //   STT process multiple times.
+    // - **Solution:** A longer duration of 500ms acts as a buffer, "stitching"
+    //   together speech segments that are separated by short pauses. This
+    //   results in more coherent, sentence-like chunks being sent to the STT
+    //   engine, significantly improving transcription quality.
+    // - **Trade-off:** The primary trade-off is a slight increase in latency,
+    //   as the system waits longer to confirm the end of an utterance. For
+    //   dictation, this is an acceptable trade-off for the gain in accuracy.
+    let vad_config = opts.vad_config.clone().unwrap_or(UnifiedVadConfig {
+        mode: VadMode::Silero,
+        frame_size_samples: FRAME_SIZE_SAMPLES,
+        sample_rate_hz: SAMPLE_RATE_HZ,
+        silero: SileroConfig {
+            threshold: 0.1,
+            min_speech_duration_ms: 100,
+            min_silence_duration_ms: 500,
+            window_size_samples: FRAME_SIZE_SAMPLES,
+            ..Default::default()
+        },
+        energy: Default::default(),
+        vote: Default::default(),
+    });
+    // Live sensitivity changes from `AppHandle::set_vad_*`, applied by the VAD
+    // processor and kept across activation mode switches
+    let (vad_tuning, _) = watch::channel(vad_config.silero.clone());
+    let (vad_calibrate_tx, _) = broadcast::channel::<u32>(4);
+    let trigger_handle = match opts.activation_mode {
+        ActivationMode::Vad => {
+            let vad_audio_rx = audio_tx.subscribe();
+            let vad_handle = crate::audio::vad_processor::VadProcessor::spawn_with_finalize(
+                vad_config.clone(),
+                vad_audio_rx,
+                raw_vad_tx.clone(),
+                Some(metrics.clone()),
+                Some(finalize_tx.subscribe()),
+                Some(vad_tuning.subscribe()),
+                Some(vad_calibrate_tx.subscribe()),
+            )
+            .map_err(|e| {
+                tracing::error!("Failed to spawn VAD processor: {}", e);
+                e
+            })?;
+            Some(vad_handle)
+        }
+        ActivationMode::Hotkey | ActivationMode::AlwaysOnPushToTranscribe => None,
+    };
+
+    // Global shortcuts. Activation bindings only act in the hotkey modes, so in
+    // VAD mode the listener is only needed for command bindings.
+    let (hotkey_action_tx, hotkey_action_rx) = mpsc::channel::<HotkeyAction>(16);
+    let hotkey_handle = (opts.activation_mode != ActivationMode::Vad
+        || opts
+            .hotkeys
+            .bindings
+            .iter()
+            .any(|b| !b.action.is_activation()))
+    .then(|| {
+        spawn_hotkey_listener(
+            opts.hotkeys.clone(),
+            current_mode.clone(),
+            raw_vad_tx.clone(),
+            hotkey_action_tx.clone(),
+            finalize_tx.subscribe(),
+        )
+    });
+
+    // Log successful VAD processor spawn
+    if let ActivationMode::Vad = opts.activation_mode {
+        tracing::info!("VAD processor spawned successfully");
+    }
+
+    // 4) Fan-out raw VAD mpsc -> broadcast for UI, and to STT when enabled
+    let (vad_bcast_tx, _) = broadcast::channel::<VadEvent>(256);
+
+    // 5) STT Plugin Manager
+    let plugin_manager: Option<Arc<tokio::sync::RwLock<SttPluginManager>>> =
+        if opts.stt_selection.is_some() {
+            let metrics_clone = metrics.clone();
+            let mut manager = SttPluginManager::new().with_metrics_sink(metrics_clone);
+            #[cfg(feature = "ws-remote")]
+            if let Some(config) = opts.ws_remote.clone() {
+                manager.configure_ws_remote_factory(config).await;
+            }
+            if let Some(config) = opts.stt_selection.clone() {
+                manager.set_selection_config(config).await?;
+            }
+            // Initialize the plugin manager; enforce fail-fast semantics when no STT plugin is available
+            match manager.initialize().await {
+                Ok(plugin_id) => {
+                    info!(
+                        "STT plugin manager initialized successfully with plugin: {}",
+                        plugin_id
+                    );
+                    Some(Arc::new(tokio::sync::RwLock::new(manager)))
+                }
+                Err(e) => {
+                    // Fail startup when STT cannot be initialized (no-op/disabled STT not allowed)
+                    error!("Failed to initialize STT plugin manager: {}", e);
+                    return Err(Box::new(e));
+                }
+            }
+        } else {
+            None
+        };
+
+    // Create transcription event channels
+    let (stt_tx, stt_rx) = mpsc::channel::<TranscriptionEvent>(100);
+    let (stt_bcast_tx, _) = broadcast::channel::<TranscriptionEvent>(100);
+    let (comparison_tx, _) = broadcast::channel::<crate::stt::compare::ComparisonResult>(16);
+    #[cfg(not(any(feature = "moonshine", feature = "parakeet", feature = "http-remote")))]
+    let _ = &stt_tx; // suppress unused warning when no active STT backend
+
+    // Text injection channel
+
+    let (_text_injection_tx, text_injection_rx) = mpsc::channel::<TranscriptionEvent>(100);
+
+    let session_stats = Arc::new(crate::session_report::SessionStats::default());
+
+    // 6) STT Processor and Fanout - Unified Path
+    #[allow(unused_mut)]
+    let mut stt_forward_handle: Option<JoinHandle<()>> = None;
+    let (stt_handle, vad_fanout_handle) = if let Some(_pm) = plugin_manager.clone() {
+        // This is the single, unified path for STT processing.
+        #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+        let (session_tx, session_rx) = mpsc::channel::<SessionEvent>(100);
+
+        #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+        let stt_audio_rx = audio_tx.subscribe();
+
+        #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+        let (stt_pipeline_tx, stt_pipeline_rx) = mpsc::channel::<TranscriptionEvent>(100);
+
+        #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+        let stt_config = opts
+            .transcription_config
+            .clone()
+            .unwrap_or_else(|| TranscriptionConfig {
+                enabled: true,
+                streaming: true,
+                ..Default::default()
+            });
+
+        #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+        let mut processor_settings = Settings::default();
+        #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+        {
+            processor_settings.activation_mode = opts.activation_mode.into();
+            processor_settings.vad_pre_roll_ms = opts.vad_pre_roll_ms;
+            processor_settings.max_utterance_ms = opts.max_utterance_secs.saturating_mul(1000);
+            if opts.ptt_bounded_audio && opts.activation_mode == ActivationMode::Hotkey {
+                info!(
+                    "Push-to-talk bounded audio enabled ({}ms pre-roll)",
+                    opts.ptt_pre_roll_ms
+                );
+                processor_settings.hotkey_behavior =
+                    crate::stt::session::HotkeyBehavior::BatchOnRelease;
+                processor_settings.pre_roll_ms = opts.ptt_pre_roll_ms;
+            }
+        }
+
+        #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+        let comparison = match opts.stt_compare.clone() {
+            Some(config) => {
+                let plugin_id = config.plugin_id.clone();
+                let created = _pm.read().await.create_plugin(&plugin_id).await;
+                let comparison = match created {
+                    Ok(plugin) => {
+                        crate::stt::compare::SttComparison::new(
+                            config,
+                            plugin,
+                            stt_config.clone(),
+                            comparison_tx.clone(),
+                        )
+                        .await
+                    }
+                    Err(e) => Err(e),
+                };
+                match comparison {
+                    Ok(comparison) => Some(Arc::new(comparison)),
+                    Err(e) => {
+                        tracing::warn!("STT comparison with '{}' disabled: {}", plugin_id, e);
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+
+        #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+        let processor = PluginSttProcessor::new(
+            stt_audio_rx,
+            session_rx,
+            stt_pipeline_tx.clone(),
+            _pm,
+            stt_config,
+            processor_settings,
+        )
+        .with_latency(metrics.latency.clone())
+        .with_traces(metrics.traces.clone());
+        #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+        let processor = match comparison {
+            Some(comparison) => processor.with_comparison(comparison),
+            None => processor,
+        };
+        #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+        let processor = match opts.stt_hallucination.clone() {
+            Some(config) => {
+                info!("Hallucination filter enabled ({:?})", config.action);
+                processor.with_hallucination_filter(Arc::new(
+                    crate::stt::hallucination::HallucinationFilter::new(config),
+                ))
+            }
+            None => processor,
+        };
+
+        let vad_bcast_tx_clone = vad_bcast_tx.clone();
+        let fanout_stats = session_stats.clone();
+        #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+        let activation_mode = opts.activation_mode;
+
+        // This task is the new "translator" from VAD/Hotkey events to generic SessionEvents.
+        let vad_fanout_handle = tokio::spawn(async move {
+            let mut rx = raw_vad_rx;
+            while let Some(ev) = rx.recv().await {
+                emit_vad_event(&ev);
+                if let VadEvent::SpeechEnd { duration_ms, .. } = ev {
+                    fanout_stats.record_speech(duration_ms);
+                }
+                // Forward the raw VAD event for UI purposes
+                let _ = vad_bcast_tx_clone.send(ev);
+
+                // Translate to SessionEvent for the STT processor
+                #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+                {
+                    let session_event = match ev {
+                        VadEvent::SpeechStart { .. } => {
+                            let source = match activation_mode {
+                                ActivationMode::Vad => SessionSource::Vad,
+                                ActivationMode::Hotkey
+                                | ActivationMode::AlwaysOnPushToTranscribe => SessionSource::Hotkey,
+                            };
+                            Some(SessionEvent::Start(source, Instant::now()))
+                        }
+                        VadEvent::SpeechEnd { .. } => {
+                            let source = match activation_mode {
+                                ActivationMode::Vad => SessionSource::Vad,
+                                ActivationMode::Hotkey
+                                | ActivationMode::AlwaysOnPushToTranscribe => SessionSource::Hotkey,
+                            };
+                            Some(SessionEvent::End(source, Instant::now()))
+                        }
+                    };
+
+                    if let Some(event) = session_event {
+                        if session_tx.send(event).await.is_err() {
+                            // STT processor channel closed, probably shutting down.
+                            // Continue forwarding VAD events for UI rather than exiting.
+                            continue;
+                        }
+                    }
+                }
+            }
+        });
+
+        #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+        let stt_handle = Some(tokio::spawn(async move {
+            processor.run().await;
+        }));
+        #[cfg(not(any(feature = "moonshine", feature = "parakeet", feature = "http-remote")))]
+        let stt_handle: Option<JoinHandle<()>> = None;
+
+        #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+        {
+            let mut pipeline_rx = stt_pipeline_rx;
+            let stt_tx_forward = stt_tx.clone();
+            let stt_bcast_forward = stt_bcast_tx.clone();
+
+            let mut text_injection_tx_forwarder = _text_injection_tx.clone();
+
+            let mut injection_active = true;
+
+            // "Read that back" is handled here rather than by the injection
+            // processor so it is never typed or shown as a transcript
+            let read_back_commands = opts.injection.as_ref().is_some_and(|i| i.voice_commands);
+            let read_back_tx = hotkey_action_tx.clone();
+            let forward_stats = session_stats.clone();
+
+            // Test-only: If a mock sink is provided, spawn a task to drain events to it.
+            // Note: We don't use #[cfg(test)] here because integration tests in tests/
+            // need this code, and they compile the library without cfg(test).
+            if let Some(mock_sink) = opts.test_injection_sink.clone() {
+                let (mock_tx, mut mock_rx) = mpsc::channel::<TranscriptionEvent>(100);
+                let _mock_handle = tokio::spawn(async move {
+                    while let Some(event) = mock_rx.recv().await {
+                        if let TranscriptionEvent::Final { text, .. } = event {
+                            let _ = mock_sink.inject_text(&text, None).await;
+                        }
+                    }
+                });
+                // Overwrite the forwarder to send to our mock channel instead
+                text_injection_tx_forwarder = mock_tx;
+            }
+
+            stt_forward_handle = Some(tokio::spawn(async move {
+                while let Some(event) = pipeline_rx.recv().await {
+                    emit_transcription_event(&event);
+                    if let TranscriptionEvent::Final { text, .. } = &event {
+                        if read_back_commands && crate::tts::is_read_back_command(text) {
+                            let _ = read_back_tx.try_send(HotkeyAction::ReadBack);
+                            continue;
+                        }
+                        forward_stats.record_final(text);
+                    }
+                    let mut injection_closed_this_event = false;
+
+                    {
+                        if injection_active
+                            && text_injection_tx_forwarder
+                                .send(event.clone())
+                                .await
+                                .is_err()
+                        {
+                            tracing::debug!(
+                                "Text injection channel closed; continuing without injection"
+                            );
+                            injection_closed_this_event = true;
+                            injection_active = false;
+                        }
+                    }
+
+                    let _ = stt_bcast_forward.send(event.clone());
+
+                    if stt_tx_forward.send(event).await.is_err() {
+                        tracing::debug!("STT receiver dropped; continuing without UI consumer");
+
+                        {
+                            if !injection_active {
+                                break;
+                            }
+                        }
+                        continue;
+                    }
+
+                    if injection_closed_this_event {
+                        tracing::debug!("Text injection receiver unavailable; UI forward only");
+                    }
+                }
+            }));
+        }
+
+        (stt_handle, vad_fanout_handle)
+    } else {
+        // No STT, just fanout VAD events for UI
+        let vad_bcast_tx_clone = vad_bcast_tx.clone();
+        let fanout_stats = session_stats.clone();
+        let vad_fanout_handle = tokio::spawn(async move {
+            let mut rx = raw_vad_rx;
+            while let Some(ev) = rx.recv().await {
+                emit_vad_event(&ev);
+                if let VadEvent::SpeechEnd { duration_ms, .. } = ev {
+                    fanout_stats.record_speech(duration_ms);
+                }
+                let _ = vad_bcast_tx_clone.send(ev);
+            }
+        });
+
+        let stt_handle: Option<JoinHandle<()>> = None;
+
+        (stt_handle, vad_fanout_handle)
+    };
+
+    // Optional text-injection
+
+    let (
+        injection_handle,
+        paste_control,
+        flush_signal,
+        undo_signal,
+        processor_metrics,
+        backend_catalog,
+        draft,
+        low_confidence,
+        target_lock,
+        dictation_mode,
+        guard_events,
+        injection_events,
+        injection_metrics,
+        injection_settled,
+    ) = {
+        let inj_opts = opts.injection.clone();
+        if let Some(inj) = inj_opts {
+            if inj.enable {
+                let config = inj.to_config();
+
+                let (shutdown_tx, shutdown_rx) = mpsc::channel::<()>(1);
+                let processor = crate::text_injection::AsyncInjectionProcessor::new(
+                    config,
+                    text_injection_rx,
+                    shutdown_rx,
+                    Some(metrics.clone()),
+                )
+                .await;
+                let paste_control = processor.paste_control();
+                let flush_signal = processor.flush_signal();
+                let undo_signal = processor.undo_signal();
+                let processor_metrics = processor.metrics_handle();
+                let backend_catalog = processor.backend_catalog();
+                let draft = processor.draft_handle();
+                let low_confidence = processor.low_confidence_handle().await;
+                let target_lock = processor.target_lock();
+                let dictation_mode = processor.dictation_mode_handle();
+                let guard_events = processor.guard_events();
+                let injection_events = processor.injection_events();
+                let injection_metrics = processor.injection_metrics();
+                let injection_settled = processor.subscribe_settled();
+
+                let handle = tokio::spawn(async move {
+                    if let Err(e) = processor.run().await {
+                        tracing::error!("Injection processor error: {}", e);
+                    }
+                    drop(shutdown_tx);
+                });
+                (
+                    Some(handle),
+                    Some(paste_control),
+                    Some(flush_signal),
+                    Some(undo_signal),
+                    Some(processor_metrics),
+                    Some(backend_catalog),
+                    Some(draft),
+                    Some(low_confidence),
+                    Some(target_lock),
+                    Some(dictation_mode),
+                    Some(guard_events),
+                    Some(injection_events),
+                    Some(injection_metrics),
+                    Some(injection_settled),
+                )
+            } else {
+                (
+                    None, None, None, None, None, None, None, None, None, None, None, None, None,
+                    None,
+                )
+            }
+        } else {
+            (
+                None, None, None, None, None, None, None, None, None, None, None, None, None, None,
+            )
+        }
+    };
+
+    // Crash recovery snapshots of text not yet injected
+    let recovery = opts
+        .recovery_path
+        .clone()
+        .zip(injection_settled)
+        .map(|(path, settled)| {
+            let handle = crate::recovery::spawn_snapshot_writer(
+                path.clone(),
+                stt_bcast_tx.subscribe(),
+                settled,
+                session_stats.clone(),
+            );
+            (handle, path)
+        });
+
+    // Prometheus endpoint
+    let metrics_server_handle = match opts.metrics_addr {
+        Some(addr) => spawn_metrics_endpoint(addr, metrics.clone(), processor_metrics).await,
+        None => None,
+    };
+
+    // Any hotkey action also wakes capture from idle
+    let idle_wake = Arc::new(tokio::sync::Notify::new());
+
+    // Spoken read-back, muting capture while it plays
+    let read_back = Arc::new(crate::tts::ReadBack::new(
+        opts.tts.clone(),
+        audio_capture.muted.clone(),
+    ));
+
+    // Feedback for transcripts held back for low confidence
+    let low_confidence_handle = opts
+        .injection
+        .as_ref()
+        .filter(|i| i.min_confidence.is_some())
+        .zip(low_confidence.as_ref())
+        .map(|(inj, handle)| {
+            crate::low_confidence::spawn_low_confidence_feedback(
+                handle,
+                inj.low_confidence_feedback,
+                read_back.clone(),
+            )
+        });
+
+    // Hotkey commands (inject last transcript, undo, switch STT plugin, read back)
+    // Black box recorder for bug reports
+    let black_box = opts
+        .black_box
+        .clone()
+        .map(|config| Arc::new(crate::black_box::BlackBox::new(config)));
+
+    let hotkey_action_handle = spawn_hotkey_action_handler(
+        hotkey_action_rx,
+        stt_bcast_tx.subscribe(),
+        _text_injection_tx.clone(),
+        opts.injection.as_ref().is_some_and(|i| i.enable),
+        plugin_manager.clone(),
+        opts.transcription_config.clone(),
+        finalize_tx.clone(),
+        flush_signal.clone(),
+        undo_signal,
+        draft,
+        low_confidence.clone(),
+        target_lock.clone(),
+        dictation_mode,
+        read_back,
+        idle_wake.clone(),
+        pause.clone(),
+        black_box.clone(),
+    );
+
+    // Energy-saver idle mode
+    let idle_handle = opts.idle.clone().map(|idle| {
+        crate::idle::spawn_idle_monitor(
+            idle,
+            audio_capture.suspended.clone(),
+            audio_tx.clone(),
+            vad_bcast_tx.subscribe(),
+            idle_wake.clone(),
+            metrics.clone(),
+        )
+    });
+
+    // Audio quality warnings
+    let (quality_tx, _) = broadcast::channel::<QualityWarning>(16);
+    let quality_handle = quality_audio_rx.map(|audio_rx| {
+        let mut config = QualityConfig::from_env();
+        config.sample_rate = SAMPLE_RATE_HZ;
+        if let Some(profile) = &opts.noise_profile {
+            config = config.with_noise_profile(profile);
+        }
+        crate::audio::quality::spawn_quality_monitor(
+            config,
+            audio_rx,
+            vad_bcast_tx.subscribe(),
+            quality_tx.clone(),
+            metrics.clone(),
+        )
+    });
+
+    // Suspend/resume: reset timers and re-check the capture device
+    let (clock_jump_tx, _) = broadcast::channel(8);
+    let resume_handle = crate::resume::spawn_resume_watch(
+        crate::resume::ResumeTargets {
+            capture_revalidate: audio_capture.revalidate.clone(),
+            backend_catalog: backend_catalog.clone(),
+            plugin_manager: plugin_manager.clone(),
+            metrics: metrics.clone(),
+        },
+        clock_jump_tx.clone(),
+    );
+
+    let black_box_handles = match &black_box {
+        Some(black_box) => {
+            let mut handles = vec![crate::black_box::spawn_recorder(
+                black_box.clone(),
+                audio_tx.subscribe(),
+                vad_bcast_tx.subscribe(),
+                stt_bcast_tx.subscribe(),
+                device_event_tx.subscribe(),
+                quality_tx.subscribe(),
+            )];
+            #[cfg(unix)]
+            handles.push(spawn_black_box_signal(black_box.clone()));
+            handles
+        }
+        None => Vec::new(),
+    };
+
+    // Push-to-talk feedback indicator
+    let indicator_handle = (opts.indicator != IndicatorKind::Off).then(|| {
+        spawn_indicator(
+            opts.indicator,
+            vad_bcast_tx.subscribe(),
+            stt_bcast_tx.subscribe(),
+            current_mode.clone(),
+        )
+    });
+
+    // Log pipeline component initialization status
+    tracing::info!(
+        "Audio pipeline components initialized: capture={}, chunker={}, vad={}, stt={}",
+        true, // audio_capture is always initialized
+        true, // chunker_handle is always initialized
+        matches!(opts.activation_mode, ActivationMode::Vad),
+        opts.stt_selection.is_some()
+    );
+
+    Ok(AppHandle {
+        metrics,
+        vad_tx: vad_bcast_tx,
+        raw_vad_tx,
+        audio_tx,
+        current_mode,
+        stt_rx: Some(stt_rx),
+        stt_bcast_tx,
+        comparison_tx,
+        plugin_manager,
+        audio_capture,
+        audio_producer,
+        chunker_handle,
+        trigger_handle: Arc::new(Mutex::new(trigger_handle)),
+        hotkeys: opts.hotkeys,
+        hotkey_action_tx,
+        finalize_tx,
+        vad_config,
+        vad_tuning,
+        vad_calibrate_tx,
+        hotkey_handle: Mutex::new(hotkey_handle),
+        hotkey_action_handle,
+        vad_fanout_handle,
+        stt_handle,
+        stt_forward_handle,
+        injection_handle,
+        recovery,
+        black_box,
+        black_box_handles,
+        idle_handle,
+        resume_handle,
+        clock_jump_tx,
+        quality_handle,
+        quality_tx,
+        pause,
+        playback_handle,
+        idle_wake,
+        paste_control,
+        target_lock,
+        flush_signal,
+        backend_catalog,
+        low_confidence,
+        low_confidence_handle,
+        guard_events,
+        injection_events,
+        session_stats,
+        injection_metrics,
+        indicator_handle,
+        metrics_server_handle,
+        device_event_handle,
+        device_event_tx,
+        active_device,
+    })
+}
+
+/// Serve pipeline, STT and injection metrics on `/metrics`
+#[cfg(feature = "metrics-http")]
+async fn spawn_metrics_endpoint(
+    addr: std::net::SocketAddr,
+    metrics: Arc<PipelineMetrics>,
+    processor_metrics: Option<Arc<std::sync::Mutex<crate::text_injection::ProcessorMetrics>>>,
+) -> Option<JoinHandle<()>> {
+    use coldvox_telemetry::{MetricsSource, PrometheusText};
+
+    let mut sources: Vec<Arc<dyn MetricsSource>> = vec![metrics];
+    if let Some(pm) = processor_metrics {
+        sources.push(Arc::new(move |out: &mut PrometheusText| {
+            if let Ok(m) = pm.lock() {
+                m.write_metrics(out);
+            }
+        }));
+    }
+    match coldvox_telemetry::metrics_http::spawn_metrics_server(addr, sources).await {
+        Ok((_, handle)) => Some(handle),
+        Err(e) => {
+            tracing::warn!("Could not start metrics endpoint on {}: {}", addr, e);
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "metrics-http"))]
+async fn spawn_metrics_endpoint(
+    addr: std::net::SocketAddr,
+    _metrics: Arc<PipelineMetrics>,
+    _processor_metrics: Option<Arc<std::sync::Mutex<crate::text_injection::ProcessorMetrics>>>,
+) -> Option<JoinHandle<()>> {
+    tracing::warn!(
+        "Metrics endpoint {} requested but this build lacks the metrics-http feature",
+        addr
+    );
+    None
+}
+
+/// Structured `vad.*` event for log pipelines
+fn emit_vad_event(ev: &VadEvent) {
+    match *ev {
+        VadEvent::SpeechStart {
+            timestamp_ms,
+            energy_db,
+        } => events::vad_speech_start(timestamp_ms, energy_db),
+        VadEvent::SpeechEnd {
+            timestamp_ms,
+            duration_ms,
+            energy_db,
+        } => events::vad_speech_end(timestamp_ms, duration_ms, energy_db),
+    }
+}
+
+/// Structured `transcription*` event for log pipelines (finals and errors)
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+fn emit_transcription_event(event: &TranscriptionEvent) {
+    match event {
+        TranscriptionEvent::Final {
+            utterance_id,
+            text,
+            words,
+            source,
+        } => events::transcription(
+            *utterance_id,
+            source.as_str(),
+            text.chars().count(),
+            words.as_ref().map_or(0, Vec::len),
+        ),
+        TranscriptionEvent::Error { code, message } => events::transcription_error(code, message),
+        TranscriptionEvent::Partial { .. } => {}
+    }
+}
+
+/// Carry out hotkey command actions against the running pipeline.
+#[allow(clippy::too_many_arguments)]
+fn spawn_hotkey_action_handler(
+    mut action_rx: mpsc::Receiver<HotkeyAction>,
+    mut stt_rx: broadcast::Receiver<TranscriptionEvent>,
+    text_injection_tx: mpsc::Sender<TranscriptionEvent>,
+    injection_enabled: bool,
+    plugin_manager: Option<Arc<tokio::sync::RwLock<SttPluginManager>>>,
+    transcription_config: Option<coldvox_stt::TranscriptionConfig>,
+    finalize_tx: broadcast::Sender<()>,
+    flush_signal: Option<Arc<tokio::sync::Notify>>,
+    undo_signal: Option<Arc<tokio::sync::Notify>>,
+    draft: Option<crate::text_injection::DraftHandle>,
+    low_confidence: Option<crate::text_injection::LowConfidenceHandle>,
+    target_lock: Option<crate::text_injection::TargetLock>,
+    dictation_mode: Option<crate::text_injection::DictationModeHandle>,
+    read_back: Arc<crate::tts::ReadBack>,
+    idle_wake: Arc<tokio::sync::Notify>,
+    pause: PauseControl,
+    black_box: Option<Arc<crate::black_box::BlackBox>>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut last_final: Option<TranscriptionEvent> = None;
+        // Read-back in progress; a new request replaces it
+        let mut speaking: Option<JoinHandle<()>> = None;
+        loop {
+            tokio::select! {
+                ev = stt_rx.recv() => match ev {
+                    Ok(ev @ TranscriptionEvent::Final { .. }) => last_final = Some(ev),
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => {
+                        // STT disabled; keep serving plugin/injection-independent actions
+                        stt_rx = broadcast::channel(1).1;
+                    }
+                },
+                Some(action) = action_rx.recv() => {
+                    // Commands need audio (or at least a live pipeline) again
+                    idle_wake.notify_one();
+                    if let Some(black_box) = &black_box {
+                        black_box.record("hotkey", action);
+                    }
+                    match action {
+                    HotkeyAction::InjectLastTranscript => {
+                        let Some(ev) = last_final.clone() else {
+                            info!("No transcript to inject yet");
+                            continue;
+                        };
+                        if !injection_enabled || text_injection_tx.send(ev).await.is_err() {
+                            info!("Text injection is disabled; ignoring inject-last-transcript");
+                            continue;
+                        }
+                    }
+                    // The injection worker erases what it typed last
+                    HotkeyAction::Undo => match &undo_signal {
+                        Some(undo) => undo.notify_one(),
+                        None => info!("Text injection is disabled; ignoring undo"),
+                    },
+                    HotkeyAction::SwitchSttPlugin => {
+                        let Some(pm) = &plugin_manager else {
+                            info!("STT is disabled; ignoring switch-stt-plugin");
+                            continue;
+                        };
+                        switch_to_next_plugin(pm, transcription_config.clone()).await;
+                    }
+                    HotkeyAction::FinalizeNow => {
+                        let _ = finalize_tx.send(());
+                    }
+                    HotkeyAction::FlushDictation => match &flush_signal {
+                        Some(flush) => flush.notify_one(),
+                        None => info!("Text injection is disabled; ignoring flush-dictation"),
+                    },
+                    HotkeyAction::ReadBack => {
+                        let held = match &draft {
+                            Some(draft) => draft.text().await,
+                            None => String::new(),
+                        };
+                        let text = if held.trim().is_empty() {
+                            match &last_final {
+                                Some(TranscriptionEvent::Final { text, .. }) => text.clone(),
+                                _ => {
+                                    info!("Nothing to read back yet");
+                                    continue;
+                                }
+                            }
+                        } else {
+                            held
+                        };
+                        if let Some(previous) = speaking.take() {
+                            previous.abort();
+                            read_back.stop();
+                        }
+                        let read_back = read_back.clone();
+                        speaking = Some(tokio::spawn(async move {
+                            match read_back.speak(&text).await {
+                                Ok(()) => {}
+                                Err(e @ crate::tts::TtsError::Cancelled(_)) => {
+                                    tracing::debug!("Read-back: {}", e)
+                                }
+                                Err(e) => tracing::warn!("Read-back failed: {}", e),
+                            }
+                        }));
+                    }
+                    HotkeyAction::TogglePause => {
+                        pause.toggle();
+                    }
+                    HotkeyAction::AcceptLowConfidence => {
+                        let accepted = match &low_confidence {
+                            Some(handle) => handle.accept().await,
+                            None => None,
+                        };
+                        if accepted.is_none() {
+                            info!("No low-confidence transcript is held");
+                        }
+                    }
+                    HotkeyAction::ToggleTargetLock => {
+                        let Some(lock) = &target_lock else {
+                            info!("Text injection is disabled; ignoring toggle-target-lock");
+                            continue;
+                        };
+                        let (summary, body) = match lock.toggle().await {
+                            Ok(Some(window)) => (
+                                "Dictation locked",
+                                format!("Text goes to {} until unlocked", window.class),
+                            ),
+                            Ok(None) => (
+                                "Dictation unlocked",
+                                "Text follows the focused window again".to_string(),
+                            ),
+                            Err(e) => {
+                                tracing::warn!("Could not lock injection target: {}", e);
+                                (
+                                    "Dictation not locked",
+                                    "Could not tell which window is active".to_string(),
+                                )
+                            }
+                        };
+                        crate::notify::send(summary, &body, crate::notify::Urgency::Low).await;
+                    }
+                    HotkeyAction::CycleDictationMode => {
+                        let Some(handle) = &dictation_mode else {
+                            info!("Text injection is disabled; ignoring cycle-dictation-mode");
+                            continue;
+                        };
+                        let mode = handle.cycle().await;
+                        crate::notify::send(
+                            "Dictation mode",
+                            &format!("Now in {} mode", mode),
+                            crate::notify::Urgency::Low,
+                        )
+                        .await;
+                    }
+                    HotkeyAction::SaveBlackBox => match &black_box {
+                        Some(black_box) => {
+                            tokio::spawn(save_black_box(black_box.clone()));
+                        }
+                        None => info!("The black box recorder is off; ignoring save-black-box"),
+                    },
+                    HotkeyAction::PushToTalk | HotkeyAction::ToggleListening => {}
+                    }
+                }
+                else => break,
+            }
+        }
+    })
+}
+
+/// Write the black box bundle off the async threads and tell the user where
+async fn save_black_box(black_box: Arc<crate::black_box::BlackBox>) {
+    match tokio::task::spawn_blocking(move || black_box.save()).await {
+        Ok(Ok(path)) => {
+            info!("Black box saved to {}", path.display());
+            crate::notify::send(
+                "Black box saved",
+                &path.display().to_string(),
+                crate::notify::Urgency::Low,
+            )
+            .await;
+        }
+        Ok(Err(e)) => tracing::warn!("Failed to save the black box: {}", e),
+        Err(e) => tracing::warn!("Black box save task failed: {}", e),
+    }
+}
+
+/// Save the black box on SIGUSR1 (`pkill -USR1 coldvox`)
+#[cfg(unix)]
+fn spawn_black_box_signal(black_box: Arc<crate::black_box::BlackBox>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut usr1 = match signal::unix::signal(signal::unix::SignalKind::user_defined1()) {
+            Ok(s) => s,
+            Err(e) => {
+                tracing::warn!(
+                    "Cannot listen for SIGUSR1; black box only saves by hotkey: {}",
+                    e
+                );
+                return;
+            }
+        };
+        while usr1.recv().await.is_some() {
+            save_black_box(black_box.clone()).await;
+        }
+    })
+}
+
+/// Cycle to the next available STT plugin after the current one.
+async fn switch_to_next_plugin(
+    pm: &tokio::sync::RwLock<SttPluginManager>,
+    transcription_config: Option<coldvox_stt::TranscriptionConfig>,
+) {
+    let mut pm = pm.write().await;
+    let available: Vec<String> = pm
+        .list_plugins_sync()
+        .into_iter()
+        .filter(|p| p.is_available)
+        .map(|p| p.id)
+        .collect();
+    let current = pm.current_plugin().await;
+    let next = match current
+        .as_ref()
+        .and_then(|c| available.iter().position(|id| id == c))
+    {
+        Some(i) => available.get((i + 1) % available.len()),
+        None => available.first(),
+    };
+    let Some(next) = next.filter(|n| Some(*n) != current.as_ref()).cloned() else {
+        info!("No other STT plugin available to switch to");
+        return;
+    };
+
+    let config = transcription_config.unwrap_or_else(|| coldvox_stt::TranscriptionConfig {
+        enabled: true,
+        streaming: true,
+        ..Default::default()
+    });
+    let result = match pm.switch_plugin(&next).await {
+        Ok(()) => pm.apply_transcription_config(config).await,
+        Err(e) => Err(e.to_string()),
+    };
+    match result {
+        Ok(()) => {
+            info!("Switched STT plugin to {}", next);
+            crate::notify::send(
+                &format!("STT plugin: {}", next),
+                "Switched by hotkey",
+                crate::notify::Urgency::Low,
+            )
+            .await;
+        }
+        Err(e) => error!("Failed to switch STT plugin to {}: {}", next, e),
+    }
+}
+
+// Integration tests for the STT pipeline live in tests/.
+// Whisper-specific unit tests were removed with the dead whisper feature stubs.
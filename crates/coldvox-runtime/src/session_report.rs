@@ -0,0 +1,339 @@
+//! Summary of one dictation session, shown on shutdown.
+//!
+//! [`SessionStats`] is fed by the runtime while it runs (speech segments from
+//! the VAD fanout, finals from the STT forwarder). [`SessionReport::collect`]
+//! combines it with the pipeline and injection metrics into a report that is
+//! logged on shutdown, shown by the TUI on demand and, with `session_log`
+//! enabled, appended to `sessions.jsonl` for `coldvox stats --last-session`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::text_injection::types::InjectionMetricsSnapshot;
+use coldvox_telemetry::PipelineMetrics;
+
+/// Live counters for the running session
+#[derive(Debug)]
+pub struct SessionStats {
+    started: Instant,
+    started_at: DateTime<Local>,
+    speech_ms: AtomicU64,
+    utterances: AtomicU64,
+    words: AtomicU64,
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            started_at: Local::now(),
+            speech_ms: AtomicU64::new(0),
+            utterances: AtomicU64::new(0),
+            words: AtomicU64::new(0),
+        }
+    }
+}
+
+impl SessionStats {
+    /// One speech segment of `duration_ms` ended
+    pub fn record_speech(&self, duration_ms: u64) {
+        self.speech_ms.fetch_add(duration_ms, Ordering::Relaxed);
+    }
+
+    /// One final transcript came out of STT
+    pub fn record_final(&self, text: &str) {
+        let words = text.split_whitespace().count() as u64;
+        if words == 0 {
+            return;
+        }
+        self.utterances.fetch_add(1, Ordering::Relaxed);
+        self.words.fetch_add(words, Ordering::Relaxed);
+    }
+}
+
+/// Point-in-time copy of [`SessionStats`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionCounters {
+    pub started_at: Option<DateTime<Local>>,
+    pub speech_ms: u64,
+    pub utterances: u64,
+    pub words: u64,
+}
+
+impl SessionStats {
+    pub fn counters(&self) -> SessionCounters {
+        SessionCounters {
+            started_at: Some(self.started_at),
+            speech_ms: self.speech_ms.load(Ordering::Relaxed),
+            utterances: self.utterances.load(Ordering::Relaxed),
+            words: self.words.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Success rate of one injection method
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MethodSummary {
+    pub attempts: u64,
+    pub successes: u64,
+}
+
+/// Requests sent to one application
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AppSummary {
+    pub requests: u64,
+    pub successes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionReport {
+    pub started_at: DateTime<Local>,
+    pub ended_at: DateTime<Local>,
+    pub uptime_secs: u64,
+    /// Time spent speaking, summed over speech segments
+    pub dictation_ms: u64,
+    pub utterances: u64,
+    pub words: u64,
+    /// Keyed by application id
+    pub apps: BTreeMap<String, AppSummary>,
+    /// Keyed by method name
+    pub methods: BTreeMap<String, MethodSummary>,
+    /// Mean speech-end to injection latency (None before the first injection)
+    pub avg_latency_ms: Option<u64>,
+    pub stt_failovers: u64,
+}
+
+impl SessionReport {
+    /// Report as of now. `injection` is None when injection is disabled.
+    pub fn collect(
+        stats: &SessionStats,
+        pipeline: &PipelineMetrics,
+        injection: Option<&InjectionMetricsSnapshot>,
+    ) -> Self {
+        let load = |v: &AtomicU64| v.load(Ordering::Relaxed);
+        let latency = pipeline.latency.snapshot().end_to_end;
+        let (apps, methods) = match injection {
+            Some(snapshot) => (
+                snapshot
+                    .app_injections
+                    .iter()
+                    .map(|(app, counts)| {
+                        (
+                            app.clone(),
+                            AppSummary {
+                                requests: counts.requests,
+                                successes: counts.successes,
+                            },
+                        )
+                    })
+                    .collect(),
+                snapshot
+                    .method_metrics
+                    .iter()
+                    .map(|(method, m)| {
+                        (
+                            method.to_string(),
+                            MethodSummary {
+                                attempts: m.attempts,
+                                successes: m.successes,
+                            },
+                        )
+                    })
+                    .collect(),
+            ),
+            None => (BTreeMap::new(), BTreeMap::new()),
+        };
+        Self {
+            started_at: stats.started_at,
+            ended_at: Local::now(),
+            uptime_secs: stats.started.elapsed().as_secs(),
+            dictation_ms: load(&stats.speech_ms),
+            utterances: load(&stats.utterances),
+            words: load(&stats.words),
+            apps,
+            methods,
+            avg_latency_ms: (latency.total_count > 0)
+                .then(|| latency.total_ms / latency.total_count),
+            stt_failovers: load(&pipeline.stt_failover_count),
+        }
+    }
+
+    /// Default location: `$XDG_STATE_HOME/coldvox/sessions.jsonl`,
+    /// falling back to `~/.local/state/coldvox/sessions.jsonl`.
+    pub fn default_path() -> Option<PathBuf> {
+        let state_dir = std::env::var_os("XDG_STATE_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local").join("state"))
+            })?;
+        Some(state_dir.join("coldvox").join("sessions.jsonl"))
+    }
+
+    /// Append this report to `path` as one JSON line
+    pub fn append(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut line = serde_json::to_string(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(line.as_bytes())
+    }
+
+    /// Most recent report in `path`. Unreadable lines are skipped; a missing
+    /// file yields None.
+    pub fn load_last(path: &Path) -> io::Result<Option<Self>> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(content
+                .lines()
+                .rev()
+                .find_map(|line| serde_json::from_str(line).ok())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+fn format_duration(secs: u64) -> String {
+    match secs {
+        s if s >= 3600 => format!("{}h {:02}m", s / 3600, s % 3600 / 60),
+        s if s >= 60 => format!("{}m {:02}s", s / 60, s % 60),
+        s => format!("{}s", s),
+    }
+}
+
+fn percent(successes: u64, attempts: u64) -> f64 {
+    if attempts == 0 {
+        0.0
+    } else {
+        successes as f64 * 100.0 / attempts as f64
+    }
+}
+
+impl fmt::Display for SessionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Session {} – {} ({})",
+            self.started_at.format("%Y-%m-%d %H:%M"),
+            self.ended_at.format("%H:%M"),
+            format_duration(self.uptime_secs)
+        )?;
+        writeln!(
+            f,
+            "  Dictation time: {}",
+            format_duration(self.dictation_ms / 1000)
+        )?;
+        writeln!(
+            f,
+            "  Utterances:     {} ({} words)",
+            self.utterances, self.words
+        )?;
+        match self.avg_latency_ms {
+            Some(ms) => writeln!(f, "  Avg latency:    {} ms", ms)?,
+            None => writeln!(f, "  Avg latency:    -")?,
+        }
+        writeln!(f, "  STT failovers:  {}", self.stt_failovers)?;
+
+        if self.apps.is_empty() {
+            writeln!(f, "  No text injected")?;
+            return Ok(());
+        }
+        writeln!(f, "  Injections by application:")?;
+        let mut apps: Vec<_> = self.apps.iter().collect();
+        apps.sort_by(|(a_id, a), (b_id, b)| b.requests.cmp(&a.requests).then(a_id.cmp(b_id)));
+        for (app, counts) in apps {
+            writeln!(
+                f,
+                "    {:<28} {:>5}  ({:.0}% ok)",
+                app,
+                counts.requests,
+                percent(counts.successes, counts.requests)
+            )?;
+        }
+        writeln!(f, "  Methods:")?;
+        for (method, counts) in &self.methods {
+            writeln!(
+                f,
+                "    {:<28} {:>5}/{:<5} ({:.0}% ok)",
+                method,
+                counts.successes,
+                counts.attempts,
+                percent(counts.successes, counts.attempts)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text_injection::types::AppInjections;
+
+    #[test]
+    fn collects_session_and_injection_counters() {
+        let stats = SessionStats::default();
+        stats.record_speech(1_500);
+        stats.record_speech(2_000);
+        stats.record_final("hello there");
+        stats.record_final("   ");
+        stats.record_final("one more time");
+
+        let pipeline = PipelineMetrics::default();
+        pipeline.stt_failover_count.fetch_add(2, Ordering::Relaxed);
+        let mut injection = InjectionMetricsSnapshot::default();
+        injection.app_injections.insert(
+            "kate".to_string(),
+            AppInjections {
+                requests: 2,
+                successes: 1,
+            },
+        );
+
+        let report = SessionReport::collect(&stats, &pipeline, Some(&injection));
+        assert_eq!(report.dictation_ms, 3_500);
+        assert_eq!(report.utterances, 2);
+        assert_eq!(report.words, 5);
+        assert_eq!(report.stt_failovers, 2);
+        assert_eq!(report.avg_latency_ms, None);
+        assert_eq!(report.apps["kate"].requests, 2);
+
+        let text = report.to_string();
+        assert!(text.contains("2 (5 words)"));
+        assert!(text.contains("kate"));
+        assert!(text.contains("50% ok"));
+    }
+
+    #[test]
+    fn appends_and_reads_back_the_last_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sessions.jsonl");
+        assert_eq!(SessionReport::load_last(&path).unwrap(), None);
+
+        let pipeline = PipelineMetrics::default();
+        let first = SessionReport::collect(&SessionStats::default(), &pipeline, None);
+        let stats = SessionStats::default();
+        stats.record_final("second session");
+        let second = SessionReport::collect(&stats, &pipeline, None);
+        first.append(&path).unwrap();
+        second.append(&path).unwrap();
+
+        let last = SessionReport::load_last(&path).unwrap().unwrap();
+        assert_eq!(last.words, 2);
+        assert!(last.to_string().contains("No text injected"));
+    }
+}
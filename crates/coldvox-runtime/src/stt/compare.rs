@@ -0,0 +1,308 @@
+//! A/B comparison of two STT plugins on the user's own voice.
+//!
+//! With `stt.compare_with` set, every utterance the active plugin transcribes
+//! is also given to a second plugin. Both transcripts are logged with the
+//! time each plugin took after the speech ended, appended to a JSON Lines
+//! report and published for the TUI. Only the active plugin's transcript is
+//! injected.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use coldvox_foundation::error::ColdVoxError;
+use coldvox_stt::plugin::SttPlugin;
+use coldvox_stt::{TranscriptionConfig, TranscriptionEvent};
+use serde::Serialize;
+use tokio::sync::{broadcast, Mutex};
+
+/// Which plugin to compare against and where to write the report
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonConfig {
+    /// Plugin id of the second plugin (e.g. "moonshine")
+    pub plugin_id: String,
+    /// JSON Lines report; None only logs the results
+    pub report_path: Option<PathBuf>,
+}
+
+impl ComparisonConfig {
+    /// Default location: `$XDG_STATE_HOME/coldvox/stt_compare.jsonl`,
+    /// falling back to `~/.local/state/coldvox/stt_compare.jsonl`.
+    pub fn default_report_path() -> Option<PathBuf> {
+        let state_dir = std::env::var_os("XDG_STATE_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local").join("state"))
+            })?;
+        Some(state_dir.join("coldvox").join("stt_compare.jsonl"))
+    }
+}
+
+/// One plugin's result for an utterance
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptOutcome {
+    pub plugin_id: String,
+    /// Final transcript (None on error or when nothing was recognized)
+    pub text: Option<String>,
+    pub error: Option<String>,
+    /// Time from the end of speech to the final transcript
+    pub latency_ms: u64,
+}
+
+impl TranscriptOutcome {
+    /// Outcome of a `finalize` call that started `started`
+    pub fn from_final(
+        plugin_id: String,
+        result: Result<Option<TranscriptionEvent>, String>,
+        started: Instant,
+    ) -> Self {
+        let latency_ms = started.elapsed().as_millis() as u64;
+        let (text, error) = match result {
+            Ok(Some(TranscriptionEvent::Final { text, .. })) => (Some(text), None),
+            Ok(Some(TranscriptionEvent::Error { message, .. })) => (None, Some(message)),
+            Ok(_) => (None, None),
+            Err(e) => (None, Some(e)),
+        };
+        Self {
+            plugin_id,
+            text,
+            error,
+            latency_ms,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match (&self.text, &self.error) {
+            (_, Some(error)) => format!(
+                "{} ({} ms) failed: {}",
+                self.plugin_id, self.latency_ms, error
+            ),
+            (Some(text), None) => {
+                format!("{} ({} ms): \"{}\"", self.plugin_id, self.latency_ms, text)
+            }
+            (None, None) => format!("{} ({} ms): no speech", self.plugin_id, self.latency_ms),
+        }
+    }
+}
+
+/// Both plugins' results for one utterance
+#[derive(Debug, Clone, Serialize)]
+pub struct ComparisonResult {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub utterance_id: Option<u64>,
+    pub audio_ms: u64,
+    pub primary: TranscriptOutcome,
+    pub secondary: TranscriptOutcome,
+    /// Word error rate of the secondary transcript against the primary one
+    pub word_difference: Option<f32>,
+}
+
+impl ComparisonResult {
+    pub fn new(
+        utterance_id: Option<u64>,
+        audio_samples: usize,
+        primary: TranscriptOutcome,
+        secondary: TranscriptOutcome,
+    ) -> Self {
+        let word_difference = match (&primary.text, &secondary.text) {
+            (Some(a), Some(b)) => Some(word_error_rate(a, b)),
+            _ => None,
+        };
+        Self {
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            utterance_id,
+            audio_ms: audio_samples as u64 / 16,
+            primary,
+            secondary,
+            word_difference,
+        }
+    }
+
+    /// One-line summary for logs and the TUI
+    pub fn summary(&self) -> String {
+        let difference = self
+            .word_difference
+            .map(|wer| format!(" — {:.0}% of words differ", wer * 100.0))
+            .unwrap_or_default();
+        format!(
+            "A/B {} | {}{}",
+            self.primary.describe(),
+            self.secondary.describe(),
+            difference
+        )
+    }
+}
+
+/// Word-level edit distance between `hypothesis` and `reference`, divided by
+/// the reference length. Case and punctuation are ignored.
+pub fn word_error_rate(reference: &str, hypothesis: &str) -> f32 {
+    let words = |text: &str| -> Vec<String> {
+        text.split_whitespace()
+            .map(|w| {
+                w.chars()
+                    .filter(|c| c.is_alphanumeric() || *c == '\'')
+                    .collect::<String>()
+                    .to_lowercase()
+            })
+            .filter(|w| !w.is_empty())
+            .collect()
+    };
+    let reference = words(reference);
+    let hypothesis = words(hypothesis);
+    if reference.is_empty() {
+        return if hypothesis.is_empty() { 0.0 } else { 1.0 };
+    }
+
+    let mut previous: Vec<usize> = (0..=hypothesis.len()).collect();
+    for (i, r) in reference.iter().enumerate() {
+        let mut current = vec![i + 1; hypothesis.len() + 1];
+        for (j, h) in hypothesis.iter().enumerate() {
+            let substitution = previous[j] + usize::from(r != h);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[hypothesis.len()] as f32 / reference.len() as f32
+}
+
+/// The second plugin and the report it feeds
+pub struct SttComparison {
+    plugin_id: String,
+    plugin: Mutex<Box<dyn SttPlugin>>,
+    report_path: Option<PathBuf>,
+    results_tx: broadcast::Sender<ComparisonResult>,
+}
+
+impl SttComparison {
+    /// Initialize `plugin` for comparison runs
+    pub async fn new(
+        config: ComparisonConfig,
+        mut plugin: Box<dyn SttPlugin>,
+        transcription: TranscriptionConfig,
+        results_tx: broadcast::Sender<ComparisonResult>,
+    ) -> Result<Self, ColdVoxError> {
+        plugin
+            .initialize(TranscriptionConfig {
+                partial_results: false,
+                ..transcription
+            })
+            .await?;
+        tracing::info!(
+            target: "stt",
+            plugin_id = %config.plugin_id,
+            report = ?config.report_path,
+            "A/B transcription comparison enabled"
+        );
+        Ok(Self {
+            plugin_id: config.plugin_id,
+            plugin: Mutex::new(plugin),
+            report_path: config.report_path,
+            results_tx,
+        })
+    }
+
+    /// Transcribe a whole utterance with the second plugin
+    pub async fn transcribe(&self, audio: &[i16]) -> TranscriptOutcome {
+        let started = Instant::now();
+        let mut plugin = self.plugin.lock().await;
+        let result: Result<Option<TranscriptionEvent>, ColdVoxError> = async {
+            plugin.reset().await?;
+            // A plugin that segments on its own may finish while audio is fed
+            let streamed = plugin.process_audio(audio).await?;
+            Ok(plugin
+                .finalize()
+                .await?
+                .or(streamed.filter(|e| matches!(e, TranscriptionEvent::Final { .. }))))
+        }
+        .await;
+        let result = result.map_err(|e| e.to_string());
+        TranscriptOutcome::from_final(self.plugin_id.clone(), result, started)
+    }
+
+    /// Log, append to the report and publish a result
+    pub fn report(&self, result: ComparisonResult) {
+        tracing::info!(target: "stt", "{}", result.summary());
+        if let Some(path) = &self.report_path {
+            if let Err(e) = append_report(path, &result) {
+                tracing::warn!(target: "stt", "Failed to write comparison report {}: {}", path.display(), e);
+            }
+        }
+        let _ = self.results_tx.send(result);
+    }
+}
+
+fn append_report(path: &Path, result: &ComparisonResult) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut line = serde_json::to_string(result).map_err(io::Error::other)?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coldvox_stt::plugins::mock::{MockConfig, MockPlugin};
+
+    #[test]
+    fn word_error_rate_ignores_case_and_punctuation() {
+        assert_eq!(word_error_rate("Hello, world.", "hello world"), 0.0);
+        assert_eq!(
+            word_error_rate("turn the lights on", "turn lights on"),
+            0.25
+        );
+        assert_eq!(word_error_rate("", ""), 0.0);
+        assert_eq!(word_error_rate("", "noise"), 1.0);
+    }
+
+    #[tokio::test]
+    async fn comparison_transcribes_and_appends_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let report_path = dir.path().join("nested").join("compare.jsonl");
+        let (results_tx, mut results_rx) = broadcast::channel(4);
+        let plugin = MockPlugin::new(MockConfig {
+            transcription_after_chunks: Some((1, "hello world".to_string())),
+            ..Default::default()
+        });
+        let comparison = SttComparison::new(
+            ComparisonConfig {
+                plugin_id: "mock".to_string(),
+                report_path: Some(report_path.clone()),
+            },
+            Box::new(plugin),
+            TranscriptionConfig::default(),
+            results_tx,
+        )
+        .await
+        .unwrap();
+
+        let secondary = comparison.transcribe(&[0i16; 1600]).await;
+        assert_eq!(secondary.text.as_deref(), Some("hello world"));
+
+        let primary = TranscriptOutcome {
+            plugin_id: "other".to_string(),
+            text: Some("Hello world!".to_string()),
+            error: None,
+            latency_ms: 5,
+        };
+        comparison.report(ComparisonResult::new(Some(1), 1600, primary, secondary));
+
+        let published = results_rx.try_recv().unwrap();
+        assert_eq!(published.audio_ms, 100);
+        assert_eq!(published.word_difference, Some(0.0));
+        let report = fs::read_to_string(&report_path).unwrap();
+        assert_eq!(report.lines().count(), 1);
+        assert!(report.contains("\"plugin_id\":\"mock\""));
+    }
+}
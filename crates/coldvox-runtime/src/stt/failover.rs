@@ -0,0 +1,172 @@
+//! User-facing description of STT plugin failovers.
+//!
+//! The plugin manager publishes a [`FailoverNotice`] whenever it gives up on a
+//! plugin, so the TUI and the headless binary can tell the user what broke and
+//! what to do about it instead of leaving it to the logs.
+
+use coldvox_foundation::error::{ColdVoxError, PluginError, SttError};
+
+/// Coarse classification of the error that triggered a failover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailoverErrorClass {
+    /// Model files are missing or could not be loaded.
+    ModelMissing,
+    /// The backend (feature, runtime, server) is not available.
+    BackendUnavailable,
+    /// The plugin is misconfigured.
+    Configuration,
+    /// Transcription itself failed at runtime.
+    Transcription,
+    /// Anything else.
+    Other,
+}
+
+impl FailoverErrorClass {
+    pub fn classify(err: &ColdVoxError) -> Self {
+        match err {
+            ColdVoxError::Stt(e) | ColdVoxError::Plugin(PluginError::Stt(e)) => match e {
+                SttError::ModelNotFound { .. } | SttError::LoadFailed(_) => Self::ModelMissing,
+                SttError::NotAvailable { .. } => Self::BackendUnavailable,
+                SttError::InvalidConfig(_) => Self::Configuration,
+                SttError::TranscriptionFailed(_) => Self::Transcription,
+            },
+            ColdVoxError::Config(_) => Self::Configuration,
+            _ => Self::Other,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ModelMissing => "model missing",
+            Self::BackendUnavailable => "backend unavailable",
+            Self::Configuration => "configuration error",
+            Self::Transcription => "transcription error",
+            Self::Other => "error",
+        }
+    }
+}
+
+/// A failover that the user should know about.
+#[derive(Debug, Clone)]
+pub struct FailoverNotice {
+    /// Plugin that exceeded its error threshold.
+    pub failed_plugin: String,
+    /// Plugin now in use, or `None` when no fallback could be loaded.
+    pub new_plugin: Option<String>,
+    pub error_class: FailoverErrorClass,
+    /// Last error reported by the failed plugin.
+    pub error: String,
+    /// Suggested fix.
+    pub hint: String,
+}
+
+impl FailoverNotice {
+    pub fn new(failed_plugin: &str, new_plugin: Option<String>, err: &ColdVoxError) -> Self {
+        let error_class = FailoverErrorClass::classify(err);
+        Self {
+            failed_plugin: failed_plugin.to_string(),
+            new_plugin,
+            error_class,
+            error: err.to_string(),
+            hint: suggested_fix(failed_plugin, error_class),
+        }
+    }
+
+    /// One-line summary for banners and notification titles.
+    pub fn summary(&self) -> String {
+        match &self.new_plugin {
+            Some(new) => format!(
+                "STT switched from {} to {} ({})",
+                self.failed_plugin,
+                new,
+                self.error_class.label()
+            ),
+            None => format!(
+                "STT plugin {} failed with no fallback ({})",
+                self.failed_plugin,
+                self.error_class.label()
+            ),
+        }
+    }
+}
+
+/// Actionable advice for a plugin/error combination.
+fn suggested_fix(plugin_id: &str, class: FailoverErrorClass) -> String {
+    match (plugin_id, class) {
+        ("http-remote", FailoverErrorClass::BackendUnavailable | FailoverErrorClass::Other) => {
+            "check that the STT server at stt.remote.base_url is running and reachable".to_string()
+        }
+        ("http-remote", FailoverErrorClass::Configuration) => {
+            "check the [stt.remote] settings in config/default.toml".to_string()
+        }
+        ("moonshine", FailoverErrorClass::BackendUnavailable | FailoverErrorClass::ModelMissing) => {
+            "install the Python dependencies: ./scripts/install-moonshine-deps.sh".to_string()
+        }
+        ("parakeet", FailoverErrorClass::BackendUnavailable) => {
+            "rebuild with --features parakeet and check the CUDA runtime".to_string()
+        }
+        (_, FailoverErrorClass::ModelMissing) => format!(
+            "the {} model could not be loaded; verify the model path and re-download it",
+            plugin_id
+        ),
+        (_, FailoverErrorClass::BackendUnavailable) => format!(
+            "the {} backend is not available in this build; pick another plugin in config/plugins.json",
+            plugin_id
+        ),
+        (_, FailoverErrorClass::Configuration) => {
+            "review the STT settings in config/default.toml".to_string()
+        }
+        (_, FailoverErrorClass::Transcription | FailoverErrorClass::Other) => {
+            "run with RUST_LOG=stt=debug for details".to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_stt_errors() {
+        let err = ColdVoxError::from(SttError::ModelNotFound {
+            path: "/models/base".into(),
+        });
+        assert_eq!(
+            FailoverErrorClass::classify(&err),
+            FailoverErrorClass::ModelMissing
+        );
+
+        let err = ColdVoxError::from(PluginError::Stt(SttError::NotAvailable {
+            plugin: "parakeet".to_string(),
+            reason: "feature not compiled".to_string(),
+        }));
+        assert_eq!(
+            FailoverErrorClass::classify(&err),
+            FailoverErrorClass::BackendUnavailable
+        );
+
+        let err = ColdVoxError::Transient("timeout".to_string());
+        assert_eq!(
+            FailoverErrorClass::classify(&err),
+            FailoverErrorClass::Other
+        );
+    }
+
+    #[test]
+    fn notice_names_plugins_and_hint() {
+        let err = ColdVoxError::from(SttError::NotAvailable {
+            plugin: "http-remote".to_string(),
+            reason: "connection refused".to_string(),
+        });
+        let notice = FailoverNotice::new("http-remote", Some("moonshine".to_string()), &err);
+        assert_eq!(
+            notice.summary(),
+            "STT switched from http-remote to moonshine (backend unavailable)"
+        );
+        assert!(notice.hint.contains("stt.remote.base_url"));
+
+        let notice = FailoverNotice::new("moonshine", None, &err);
+        assert!(notice.summary().contains("no fallback"));
+        assert!(notice.hint.contains("install-moonshine-deps"));
+    }
+}
@@ -0,0 +1,292 @@
+//! Sanity check of finals against the audio they came from.
+//!
+//! Whisper-style models are known to "transcribe" near-silence as stock
+//! phrases from their training data ("Thanks for watching!"). A final is
+//! suspect when its whole text is one of those phrases, when the utterance
+//! audio is too quiet to hold speech, or when there is more text than the
+//! audio could have said. `stt.hallucination_filter` decides what happens to
+//! a suspect final: `drop` discards it, `flag` marks its words with a near-zero
+//! confidence so `injection.min_confidence` holds it for confirmation.
+
+use coldvox_stt::{TranscriptionEvent, WordInfo};
+
+/// Built-in phrases, compared after [`normalize`]
+pub const DEFAULT_PHRASES: &[&str] = &[
+    "thanks for watching",
+    "thank you for watching",
+    "thanks for watching and see you next time",
+    "please subscribe",
+    "like and subscribe",
+    "subscribe to my channel",
+    "see you in the next video",
+    "subtitles by the amara org community",
+    "transcription by castingwords",
+];
+
+/// Word confidence given to flagged finals (0.0 means "unscored")
+pub const FLAGGED_CONFIDENCE: f32 = 0.01;
+
+/// What to do with a suspect final
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HallucinationAction {
+    /// No checks
+    #[default]
+    Off,
+    /// Log it and mark it low-confidence
+    Flag,
+    /// Log it and drop it
+    Drop,
+}
+
+impl std::str::FromStr for HallucinationAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "off" | "" => Ok(Self::Off),
+            "flag" => Ok(Self::Flag),
+            "drop" => Ok(Self::Drop),
+            other => Err(format!("unknown hallucination filter action '{}'", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HallucinationConfig {
+    pub action: HallucinationAction,
+    /// Phrases checked in addition to [`DEFAULT_PHRASES`]
+    pub phrases: Vec<String>,
+    /// Utterances quieter than this overall (dBFS) hold no speech
+    pub min_dbfs: f32,
+    /// More characters per second of audio than anyone speaks
+    pub max_chars_per_sec: f32,
+}
+
+impl Default for HallucinationConfig {
+    fn default() -> Self {
+        Self {
+            action: HallucinationAction::Off,
+            phrases: Vec::new(),
+            min_dbfs: -50.0,
+            max_chars_per_sec: 30.0,
+        }
+    }
+}
+
+/// Why a final looks hallucinated
+#[derive(Debug, Clone, PartialEq)]
+pub enum Suspicion {
+    /// The whole text is a known phrase
+    KnownPhrase(String),
+    /// The utterance audio is near-silent
+    QuietAudio { dbfs: f32 },
+    /// Too much text for the length of the audio
+    TooMuchText { chars: usize, audio_ms: u64 },
+}
+
+impl std::fmt::Display for Suspicion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Suspicion::KnownPhrase(phrase) => write!(f, "known hallucination \"{}\"", phrase),
+            Suspicion::QuietAudio { dbfs } => write!(f, "audio too quiet ({:.1} dBFS)", dbfs),
+            Suspicion::TooMuchText { chars, audio_ms } => {
+                write!(f, "{} chars from {} ms of audio", chars, audio_ms)
+            }
+        }
+    }
+}
+
+/// Lowercase, punctuation as spaces, single spaces
+pub fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Debug)]
+pub struct HallucinationFilter {
+    config: HallucinationConfig,
+    phrases: Vec<String>,
+}
+
+impl HallucinationFilter {
+    pub fn new(config: HallucinationConfig) -> Self {
+        let mut phrases: Vec<String> = DEFAULT_PHRASES
+            .iter()
+            .map(|p| p.to_string())
+            .chain(config.phrases.iter().map(|p| normalize(p)))
+            .filter(|p| !p.is_empty())
+            .collect();
+        phrases.sort();
+        phrases.dedup();
+        Self { config, phrases }
+    }
+
+    pub fn action(&self) -> HallucinationAction {
+        self.config.action
+    }
+
+    /// Check `text` against the 16 kHz `audio` it was transcribed from. With
+    /// no audio at hand only the phrase list applies.
+    pub fn check(&self, text: &str, audio: &[i16]) -> Option<Suspicion> {
+        let normalized = normalize(text);
+        if normalized.is_empty() {
+            return None;
+        }
+        if self.phrases.contains(&normalized) {
+            return Some(Suspicion::KnownPhrase(text.trim().to_string()));
+        }
+        if audio.is_empty() {
+            return None;
+        }
+        let dbfs = crate::idle::frame_dbfs(audio);
+        if dbfs < self.config.min_dbfs {
+            return Some(Suspicion::QuietAudio { dbfs });
+        }
+        let chars = text.trim().chars().count();
+        let audio_secs = audio.len() as f32 / 16_000.0;
+        if chars as f32 > self.config.max_chars_per_sec * audio_secs {
+            return Some(Suspicion::TooMuchText {
+                chars,
+                audio_ms: (audio_secs * 1000.0) as u64,
+            });
+        }
+        None
+    }
+
+    /// `event` as it should go on: unchanged, flagged, or None when dropped
+    pub fn apply(&self, event: TranscriptionEvent, audio: &[i16]) -> Option<TranscriptionEvent> {
+        if self.config.action == HallucinationAction::Off {
+            return Some(event);
+        }
+        let TranscriptionEvent::Final {
+            utterance_id,
+            text,
+            words,
+            source,
+        } = event
+        else {
+            return Some(event);
+        };
+        let Some(suspicion) = self.check(&text, audio) else {
+            return Some(TranscriptionEvent::Final {
+                utterance_id,
+                text,
+                words,
+                source,
+            });
+        };
+        match self.config.action {
+            HallucinationAction::Drop => {
+                tracing::warn!(
+                    target: "stt",
+                    "Dropping final [{}] as a likely hallucination: {}",
+                    utterance_id,
+                    suspicion
+                );
+                None
+            }
+            _ => {
+                tracing::warn!(
+                    target: "stt",
+                    "Final [{}] looks hallucinated: {}",
+                    utterance_id,
+                    suspicion
+                );
+                let words = match words {
+                    Some(words) if !words.is_empty() => words
+                        .into_iter()
+                        .map(|w| WordInfo {
+                            conf: FLAGGED_CONFIDENCE,
+                            ..w
+                        })
+                        .collect(),
+                    _ => vec![WordInfo {
+                        start: 0.0,
+                        end: audio.len() as f32 / 16_000.0,
+                        conf: FLAGGED_CONFIDENCE,
+                        text: text.clone(),
+                    }],
+                };
+                Some(TranscriptionEvent::Final {
+                    utterance_id,
+                    text,
+                    words: Some(words),
+                    source,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coldvox_stt::SourceId;
+
+    fn speech(ms: usize) -> Vec<i16> {
+        (0..ms * 16)
+            .map(|i| if i % 2 == 0 { 6_000 } else { -6_000 })
+            .collect()
+    }
+
+    #[test]
+    fn flags_phrases_silence_and_impossible_length() {
+        let filter = HallucinationFilter::new(HallucinationConfig {
+            action: HallucinationAction::Drop,
+            phrases: vec!["Ähm, okay.".to_string()],
+            ..Default::default()
+        });
+        let one_second = speech(1000);
+
+        assert!(matches!(
+            filter.check("Thanks for watching!", &one_second),
+            Some(Suspicion::KnownPhrase(_))
+        ));
+        assert!(matches!(
+            filter.check("ähm okay", &[]),
+            Some(Suspicion::KnownPhrase(_))
+        ));
+        assert!(matches!(
+            filter.check("hello there", &vec![3i16; 16_000]),
+            Some(Suspicion::QuietAudio { .. })
+        ));
+        assert!(matches!(
+            filter.check(&"word ".repeat(20), &one_second),
+            Some(Suspicion::TooMuchText { chars: 99, .. })
+        ));
+        assert_eq!(filter.check("hello there", &one_second), None);
+        // Without audio only the phrase list applies
+        assert_eq!(filter.check(&"word ".repeat(20), &[]), None);
+    }
+
+    #[test]
+    fn flag_marks_confidence_and_drop_discards() {
+        let final_event = || TranscriptionEvent::Final {
+            utterance_id: 1,
+            text: "Thank you for watching.".to_string(),
+            words: None,
+            source: SourceId::default(),
+        };
+
+        let drop = HallucinationFilter::new(HallucinationConfig {
+            action: HallucinationAction::Drop,
+            ..Default::default()
+        });
+        assert!(drop.apply(final_event(), &[]).is_none());
+
+        let flag = HallucinationFilter::new(HallucinationConfig {
+            action: HallucinationAction::Flag,
+            ..Default::default()
+        });
+        let flagged = flag.apply(final_event(), &speech(500)).unwrap();
+        assert_eq!(flagged.confidence(), Some(FLAGGED_CONFIDENCE));
+
+        let off = HallucinationFilter::new(HallucinationConfig::default());
+        assert_eq!(off.apply(final_event(), &[]).unwrap().confidence(), None);
+    }
+}
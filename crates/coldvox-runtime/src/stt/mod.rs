@@ -2,13 +2,17 @@
 
 // Re-export core STT types from the new crate
 pub use coldvox_stt::{
-    next_utterance_id, EventBasedTranscriber, Transcriber, TranscriptionConfig, TranscriptionEvent,
-    WordInfo,
+    next_utterance_id, EventBasedTranscriber, SourceId, Transcriber, TranscriptionConfig,
+    TranscriptionEvent, WordInfo,
 };
 
 #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
 pub mod processor;
 
+pub mod compare;
+pub mod failover;
+pub mod hallucination;
+pub mod model_benchmark;
 pub mod session;
 
 #[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
@@ -0,0 +1,285 @@
+//! First-run model benchmark: measures the real-time factor (RTF, decode time
+//! divided by audio duration) of each local CPU model on this machine and
+//! picks the largest one that meets the configured target.
+//!
+//! The result is stored next to the other persisted state
+//! (`$XDG_STATE_HOME/coldvox/model_benchmark.json`) together with a
+//! fingerprint of the hardware it was measured on. A stored result is reused
+//! until the fingerprint changes (new CPU, core count or memory size), at
+//! which point the benchmark runs again.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Seconds of audio decoded per model
+pub const BENCHMARK_AUDIO_SECS: u32 = 5;
+
+/// CPU models in ascending size (and accuracy) order
+pub const CPU_MODELS: &[&str] = &["tiny", "base"];
+
+/// What the benchmark results are valid for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardwareFingerprint {
+    pub cpu_model: String,
+    pub cores: usize,
+    pub total_mem_mb: u64,
+}
+
+impl HardwareFingerprint {
+    /// Fingerprint of the machine we are running on
+    pub fn current() -> Self {
+        let cpuinfo = fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+        let meminfo = fs::read_to_string("/proc/meminfo").unwrap_or_default();
+        Self {
+            cpu_model: parse_cpu_model(&cpuinfo).unwrap_or_else(|| std::env::consts::ARCH.into()),
+            cores: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            total_mem_mb: parse_total_mem_mb(&meminfo).unwrap_or(0),
+        }
+    }
+}
+
+/// Stored benchmark outcome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub hardware: HardwareFingerprint,
+    pub measured_at: DateTime<Utc>,
+    /// Measured RTF per model (`tiny`, `base`)
+    pub rtf: BTreeMap<String, f64>,
+    pub target_rtf: f64,
+    /// Largest model meeting `target_rtf`, if any did
+    pub recommended: Option<String>,
+}
+
+impl BenchmarkResult {
+    pub fn new(hardware: HardwareFingerprint, rtf: BTreeMap<String, f64>, target_rtf: f64) -> Self {
+        let recommended = recommend_model(&rtf, target_rtf).map(str::to_string);
+        Self {
+            hardware,
+            measured_at: Utc::now(),
+            rtf,
+            target_rtf,
+            recommended,
+        }
+    }
+
+    /// Whether this result still applies to `hardware` and `target_rtf`
+    pub fn is_current(&self, hardware: &HardwareFingerprint, target_rtf: f64) -> bool {
+        self.hardware == *hardware && (self.target_rtf - target_rtf).abs() < f64::EPSILON
+    }
+
+    /// Default location: `$XDG_STATE_HOME/coldvox/model_benchmark.json`,
+    /// falling back to `~/.local/state/coldvox/model_benchmark.json`.
+    pub fn default_path() -> Option<PathBuf> {
+        let state_dir = std::env::var_os("XDG_STATE_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local").join("state"))
+            })?;
+        Some(state_dir.join("coldvox").join("model_benchmark.json"))
+    }
+
+    /// Load a stored result. A missing file yields `None`.
+    pub fn load(path: &Path) -> io::Result<Option<Self>> {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+}
+
+/// Largest model in [`CPU_MODELS`] whose RTF is at most `target_rtf`
+pub fn recommend_model(rtf: &BTreeMap<String, f64>, target_rtf: f64) -> Option<&'static str> {
+    CPU_MODELS
+        .iter()
+        .rev()
+        .find(|model| rtf.get(**model).is_some_and(|&r| r <= target_rtf))
+        .copied()
+}
+
+fn parse_cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        matches!(key.trim(), "model name" | "Model" | "Hardware")
+            .then(|| value.trim().to_string())
+            .filter(|v| !v.is_empty())
+    })
+}
+
+fn parse_total_mem_mb(meminfo: &str) -> Option<u64> {
+    let line = meminfo.lines().find(|l| l.starts_with("MemTotal:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb / 1024)
+}
+
+/// Decode [`BENCHMARK_AUDIO_SECS`] of speech-band audio with each CPU model
+/// and return the measured RTF per model. Models that fail to load are
+/// skipped.
+#[cfg(feature = "moonshine")]
+pub async fn measure_cpu_models() -> BTreeMap<String, f64> {
+    use coldvox_stt::plugin::SttPlugin;
+    use coldvox_stt::plugins::{MoonshineModelSize, MoonshinePlugin};
+    use coldvox_stt::TranscriptionConfig;
+    use std::time::Instant;
+
+    let samples = benchmark_audio();
+    let mut rtf = BTreeMap::new();
+    for &model in CPU_MODELS {
+        let size = match model {
+            "tiny" => MoonshineModelSize::Tiny,
+            _ => MoonshineModelSize::Base,
+        };
+        let mut plugin = MoonshinePlugin::new().with_model_size(size);
+        let config = TranscriptionConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        if let Err(e) = plugin.initialize(config).await {
+            tracing::warn!("Benchmark: could not load Moonshine {}: {}", model, e);
+            continue;
+        }
+
+        let start = Instant::now();
+        let decoded = match plugin.process_audio(&samples).await {
+            Ok(_) => plugin.finalize().await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        let elapsed = start.elapsed().as_secs_f64();
+        let _ = plugin.unload().await;
+
+        match decoded {
+            Ok(()) => {
+                let value = elapsed / f64::from(BENCHMARK_AUDIO_SECS);
+                tracing::info!("Benchmark: Moonshine {} RTF {:.3}", model, value);
+                rtf.insert(model.to_string(), value);
+            }
+            Err(e) => tracing::warn!("Benchmark: Moonshine {} failed to decode: {}", model, e),
+        }
+    }
+    rtf
+}
+
+/// Without a local CPU backend there is nothing to measure
+#[cfg(not(feature = "moonshine"))]
+pub async fn measure_cpu_models() -> BTreeMap<String, f64> {
+    BTreeMap::new()
+}
+
+/// Speech-band test signal: a gliding tone with syllable-rate amplitude
+/// modulation, enough to make the decoder do real work
+#[cfg(feature = "moonshine")]
+fn benchmark_audio() -> Vec<i16> {
+    use coldvox_stt::constants::SAMPLE_RATE_HZ;
+    use std::f64::consts::PI;
+
+    let rate = f64::from(SAMPLE_RATE_HZ);
+    let total = (SAMPLE_RATE_HZ * BENCHMARK_AUDIO_SECS) as usize;
+    (0..total)
+        .map(|i| {
+            let t = i as f64 / rate;
+            let pitch = 140.0 + 60.0 * (2.0 * PI * 0.5 * t).sin();
+            let envelope = 0.5 + 0.5 * (2.0 * PI * 4.0 * t).sin();
+            (envelope * (2.0 * PI * pitch * t).sin() * 8000.0) as i16
+        })
+        .collect()
+}
+
+/// Return the stored benchmark for this machine, running (and storing) a new
+/// one on first run or when the hardware or target changed.
+pub async fn ensure_benchmark(
+    path: &Path,
+    target_rtf: f64,
+) -> Result<BenchmarkResult, Box<dyn std::error::Error>> {
+    let hardware = HardwareFingerprint::current();
+    match BenchmarkResult::load(path) {
+        Ok(Some(stored)) if stored.is_current(&hardware, target_rtf) => return Ok(stored),
+        Ok(Some(_)) => {
+            tracing::info!("Hardware or latency target changed; re-running model benchmark")
+        }
+        Ok(None) => tracing::info!("No model benchmark yet; measuring local models"),
+        Err(e) => tracing::warn!(
+            "Ignoring unreadable model benchmark {}: {}",
+            path.display(),
+            e
+        ),
+    }
+
+    let rtf = measure_cpu_models().await;
+    if rtf.is_empty() {
+        return Err("no local CPU model could be benchmarked".into());
+    }
+    let result = BenchmarkResult::new(hardware, rtf, target_rtf);
+    result.save(path)?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recommends_largest_model_meeting_target() {
+        let rtf: BTreeMap<String, f64> = [("tiny".to_string(), 0.1), ("base".to_string(), 0.4)]
+            .into_iter()
+            .collect();
+        assert_eq!(recommend_model(&rtf, 0.5), Some("base"));
+        assert_eq!(recommend_model(&rtf, 0.2), Some("tiny"));
+        assert_eq!(recommend_model(&rtf, 0.05), None);
+    }
+
+    #[test]
+    fn parses_proc_files() {
+        let cpuinfo =
+            "processor\t: 0\nvendor_id\t: GenuineIntel\nmodel name\t: Intel(R) Core(TM) i7\n";
+        assert_eq!(
+            parse_cpu_model(cpuinfo).as_deref(),
+            Some("Intel(R) Core(TM) i7")
+        );
+        assert_eq!(
+            parse_total_mem_mb("MemTotal:       16303420 kB\n"),
+            Some(15921)
+        );
+    }
+
+    #[test]
+    fn stored_result_is_invalidated_by_hardware_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("model_benchmark.json");
+        let hardware = HardwareFingerprint {
+            cpu_model: "cpu".to_string(),
+            cores: 8,
+            total_mem_mb: 16000,
+        };
+        let rtf = [("tiny".to_string(), 0.2)].into_iter().collect();
+        BenchmarkResult::new(hardware.clone(), rtf, 0.5)
+            .save(&path)
+            .unwrap();
+
+        let stored = BenchmarkResult::load(&path).unwrap().unwrap();
+        assert_eq!(stored.recommended.as_deref(), Some("tiny"));
+        assert!(stored.is_current(&hardware, 0.5));
+        assert!(!stored.is_current(&hardware, 0.3));
+        let upgraded = HardwareFingerprint {
+            cores: 16,
+            ..hardware
+        };
+        assert!(!stored.is_current(&upgraded, 0.5));
+    }
+}
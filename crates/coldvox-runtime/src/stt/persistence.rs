@@ -92,6 +92,16 @@ pub struct UtteranceRecord {
     pub audio_path: Option<PathBuf>,
     /// Word-level timing (if available)
     pub words: Option<Vec<WordTiming>>,
+    /// Audio source the utterance came from ("mic", "loopback", ...)
+    #[serde(default = "default_utterance_source")]
+    pub source: String,
+    /// Application that had focus when the utterance was finalized
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<String>,
+}
+
+fn default_utterance_source() -> String {
+    coldvox_stt::SourceId::MIC.to_string()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -245,6 +255,7 @@ impl TranscriptionWriter {
                 utterance_id,
                 text,
                 words,
+                source,
             } => {
                 // Get timing information from VAD events
                 let (start_ms, duration_ms) = {
@@ -332,6 +343,8 @@ impl TranscriptionWriter {
                             })
                             .collect()
                     }),
+                    source: source.to_string(),
+                    app_id: focused_app().await,
                 };
 
                 // Add to session
@@ -590,6 +603,14 @@ impl TranscriptionWriter {
     }
 }
 
+/// Class of the focused window, best-effort (e.g. for grouping exports by app)
+async fn focused_app() -> Option<String> {
+    tokio::task::spawn_blocking(crate::text_injection::window_manager::get_active_window_class)
+        .await
+        .ok()?
+        .ok()
+}
+
 /// Spawn the persistence handler task
 pub fn spawn_persistence_handler(
     config: PersistenceConfig,
@@ -10,11 +10,15 @@ use std::time::{Duration, Instant};
 use std::sync::atomic::Ordering;
 
 use coldvox_foundation::error::{ColdVoxError, ConfigError, PluginError, SttError};
-use coldvox_stt::plugin::{PluginSelectionConfig, SttPlugin, SttPluginRegistry};
+use coldvox_stt::plugin::{FailoverConfig, PluginSelectionConfig, SttPlugin, SttPluginRegistry};
 #[cfg(feature = "http-remote")]
 use coldvox_stt::plugins::http_remote::{HttpRemoteConfig, HttpRemotePluginFactory};
+#[cfg(feature = "ws-remote")]
+use coldvox_stt::plugins::ws_remote::{WsRemoteConfig, WsRemotePluginFactory};
 use coldvox_stt::TranscriptionConfig;
 use coldvox_telemetry::pipeline_metrics::PipelineMetrics;
+
+use crate::stt::failover::FailoverNotice;
 use serde_json;
 use tokio::fs;
 use tokio::sync::RwLock;
@@ -25,6 +29,8 @@ use tracing::{debug, error, info, trace, warn};
 pub struct SttPluginManager {
     registry: Arc<RwLock<SttPluginRegistry>>,
     current_plugin: Arc<RwLock<Option<Box<dyn SttPlugin>>>>,
+    /// Fallback plugin kept loaded and initialized for instant failover
+    standby_plugin: Arc<RwLock<Option<Box<dyn SttPlugin>>>>,
     selection_config: PluginSelectionConfig,
 
     // Failover tracking
@@ -48,6 +54,9 @@ pub struct SttPluginManager {
 
     // Idempotent unload tracking
     last_unloaded_plugin_id: Arc<RwLock<Option<String>>>,
+
+    // User-facing failover notices
+    failover_tx: tokio::sync::broadcast::Sender<FailoverNotice>,
 }
 
 impl Default for SttPluginManager {
@@ -63,6 +72,13 @@ impl SttPluginManager {
         registry.register_or_replace(Box::new(HttpRemotePluginFactory::new(config)));
     }
 
+    /// Point the `ws-remote` plugin at the configured streaming endpoint
+    #[cfg(feature = "ws-remote")]
+    pub async fn configure_ws_remote_factory(&mut self, config: WsRemoteConfig) {
+        let mut registry = self.registry.write().await;
+        registry.register_or_replace(Box::new(WsRemotePluginFactory::new(config)));
+    }
+
     /// Warn if legacy or duplicate plugin selection config files are present.
     /// Canonical location is `config/plugins.json`. Any other copies will be ignored.
     fn warn_on_duplicate_configs(&self) {
@@ -92,7 +108,7 @@ impl SttPluginManager {
     }
     /// Create a new plugin manager with default configuration
     pub fn new() -> Self {
-        let config_path = crate::discover_plugin_selection_config_path()
+        let config_path = crate::plugin_config::discover_plugin_selection_config_path()
             .unwrap_or_else(|| PathBuf::from("config/plugins.json"));
         Self::new_with_config_path(config_path)
     }
@@ -107,6 +123,7 @@ impl SttPluginManager {
         let mut manager = Self {
             registry: Arc::new(RwLock::new(registry)),
             current_plugin: Arc::new(RwLock::new(None)),
+            standby_plugin: Arc::new(RwLock::new(None)),
             selection_config: PluginSelectionConfig::default(),
             consecutive_errors: Arc::new(RwLock::new(HashMap::new())),
             last_failover: Arc::new(RwLock::new(None)),
@@ -120,6 +137,7 @@ impl SttPluginManager {
             metrics_task: Arc::new(RwLock::new(None)),
             config_path,
             last_unloaded_plugin_id: Arc::new(RwLock::new(None)),
+            failover_tx: tokio::sync::broadcast::channel(16).0,
         };
 
         if let Err(err) = manager.load_config_sync() {
@@ -501,6 +519,21 @@ impl SttPluginManager {
         );
     }
 
+    /// Count `slept` towards every model's idle time and collect the ones
+    /// past their TTL. For resumes where `Instant` stood still during the
+    /// suspend, which would otherwise keep idle models loaded that much longer.
+    pub async fn age_activity(&self, slept: Duration) {
+        {
+            let mut activity = self.last_activity.write().await;
+            for last_used in activity.values_mut() {
+                if let Some(earlier) = last_used.checked_sub(slept) {
+                    *last_used = earlier;
+                }
+            }
+        }
+        self.gc_inactive_models().await;
+    }
+
     /// Garbage collect inactive plugin models.
     pub async fn gc_inactive_models(&self) {
         if let Some(ref metrics) = self.metrics_sink {
@@ -627,6 +660,11 @@ impl SttPluginManager {
             _registry.register(Box::new(HttpRemotePluginFactory::parakeet_gpu()));
         }
 
+        #[cfg(feature = "ws-remote")]
+        _registry.register(Box::new(WsRemotePluginFactory::new(
+            WsRemoteConfig::default(),
+        )));
+
         // Register Parakeet plugin if the parakeet feature is enabled
         #[cfg(feature = "parakeet")]
         {
@@ -647,6 +685,27 @@ impl SttPluginManager {
             use coldvox_stt::plugins::moonshine::MoonshinePluginFactory;
             registry.register(Box::new(MoonshinePluginFactory::new()));
         }
+
+        // Out-of-tree plugins; built-ins keep their ids
+        #[cfg(feature = "plugin-host")]
+        if let Some(dir) = coldvox_stt::plugin_host::default_plugins_dir() {
+            let taken: Vec<String> = _registry
+                .available_plugins()
+                .into_iter()
+                .map(|p| p.id)
+                .collect();
+            for factory in coldvox_stt::plugin_host::discover_plugins(&dir) {
+                if taken.contains(&factory.manifest().id) {
+                    warn!(
+                        target: "coldvox::stt",
+                        "External STT plugin '{}' clashes with a built-in plugin; skipping it",
+                        factory.manifest().id
+                    );
+                    continue;
+                }
+                _registry.register(Box::new(factory));
+            }
+        }
     }
 
     /// Initialize the plugin manager and select the best available plugin
@@ -775,7 +834,12 @@ impl SttPluginManager {
 
         let mut plugin = plugin;
         // Initialize the plugin with a default config. The processor can re-initialize with specific settings if needed.
-        let init_result = plugin.initialize(TranscriptionConfig::default()).await;
+        let init_result = plugin
+            .initialize(TranscriptionConfig {
+                decode: self.selection_config.decode.clone(),
+                ..Default::default()
+            })
+            .await;
         let plugin_id = plugin.info().id.clone();
 
         match init_result {
@@ -816,6 +880,10 @@ impl SttPluginManager {
 
         tracing::info!(target: "coldvox::stt", selected_plugin = %plugin_id, "STT initialized with plugin");
 
+        drop(current);
+        drop(registry);
+        self.spawn_standby_load().await;
+
         Ok(plugin_id)
     }
 
@@ -879,6 +947,11 @@ impl SttPluginManager {
         current.as_ref().map(|p| p.info().id.clone())
     }
 
+    /// Create a separate, uninitialized instance of a registered plugin
+    pub async fn create_plugin(&self, plugin_id: &str) -> Result<Box<dyn SttPlugin>, ColdVoxError> {
+        self.registry.read().await.create_plugin(plugin_id)
+    }
+
     /// Switch to a different plugin
     pub async fn switch_plugin(&mut self, plugin_id: &str) -> Result<(), ColdVoxError> {
         let registry = self.registry.read().await;
@@ -1044,6 +1117,9 @@ impl SttPluginManager {
 
     /// Unload all plugins (for shutdown cleanup)
     pub async fn unload_all_plugins(&self) -> Result<(), ColdVoxError> {
+        if let Some(mut standby) = self.standby_plugin.write().await.take() {
+            let _ = standby.unload().await;
+        }
         let mut current = self.current_plugin.write().await;
 
         if let Some(ref mut plugin) = *current {
@@ -1207,11 +1283,19 @@ impl SttPluginManager {
                                     }
                                 }
 
+                                let _ = self.failover_tx.send(FailoverNotice::new(
+                                    &plugin_id,
+                                    Some(new_plugin_id.clone()),
+                                    &e,
+                                ));
+
                                 // Record cooldown for failed plugin
                                 {
                                     let mut cooldown = self.failed_plugins_cooldown.write().await;
                                     cooldown.insert(plugin_id, Instant::now());
                                 }
+                                // Stand by with the next fallback
+                                self.spawn_standby_load().await;
 
                                 // Try processing with new plugin
                                 let mut current = self.current_plugin.write().await;
@@ -1227,6 +1311,9 @@ impl SttPluginManager {
                             }
                             Err(failover_err) => {
                                 error!("Failover failed: {}", failover_err);
+                                let _ = self
+                                    .failover_tx
+                                    .send(FailoverNotice::new(&plugin_id, None, &e));
                                 Err(format!(
                                     "STT processing failed: {}, failover failed: {}",
                                     e, failover_err
@@ -1285,10 +1372,12 @@ impl SttPluginManager {
 
     /// Apply a TranscriptionConfig to the currently loaded plugin.
     /// This allows the app/processor to override defaults (e.g., enable=true).
+    /// Decode resources always come from the selection config.
     pub async fn apply_transcription_config(
         &mut self,
-        config: coldvox_stt::TranscriptionConfig,
+        mut config: coldvox_stt::TranscriptionConfig,
     ) -> Result<(), String> {
+        config.decode = self.selection_config.decode.clone();
         let mut current = self.current_plugin.write().await;
         if let Some(ref mut plugin) = *current {
             plugin.initialize(config).await.map_err(|e| e.to_string())
@@ -1297,6 +1386,90 @@ impl SttPluginManager {
         }
     }
 
+    /// Load the first eligible fallback plugin into standby in the
+    /// background, when warm standby is enabled
+    async fn spawn_standby_load(&self) {
+        let Some(failover) = self
+            .selection_config
+            .failover
+            .clone()
+            .filter(|f| f.warm_standby)
+        else {
+            return;
+        };
+        let Some(candidate) = self.standby_candidate(&failover).await else {
+            return;
+        };
+        let registry = self.registry.clone();
+        let standby = self.standby_plugin.clone();
+        let decode = self.selection_config.decode.clone();
+        tokio::spawn(async move {
+            load_standby(&registry, &standby, &candidate, decode).await;
+        });
+    }
+
+    /// First fallback that is not active, not cooling down and fits the
+    /// standby memory budget
+    async fn standby_candidate(&self, failover: &FailoverConfig) -> Option<String> {
+        let current_id = self.current_plugin().await;
+        let cooldown = self.failed_plugins_cooldown.read().await;
+        let cooldown_duration = Duration::from_secs(failover.failover_cooldown_secs as u64);
+        let registry = self.registry.read().await;
+        let available = registry.available_plugins();
+
+        self.selection_config
+            .fallback_plugins
+            .iter()
+            .filter(|id| current_id.as_ref() != Some(*id))
+            .filter(|id| {
+                cooldown
+                    .get(*id)
+                    .is_none_or(|failed| failed.elapsed() >= cooldown_duration)
+            })
+            .find(|id| {
+                let Some(info) = available.iter().find(|info| &info.id == *id) else {
+                    return false;
+                };
+                let fits = match (failover.standby_max_mem_mb, info.memory_usage_mb) {
+                    (Some(budget), Some(needed)) => needed <= budget,
+                    _ => true,
+                };
+                if !fits {
+                    debug!(
+                        target: "coldvox::stt",
+                        plugin_id = %info.id,
+                        "Fallback plugin exceeds the standby memory budget; it will load on demand"
+                    );
+                }
+                fits
+            })
+            .cloned()
+    }
+
+    /// Load the standby plugin now and return its id
+    pub async fn prepare_standby(&self) -> Option<String> {
+        let failover = self
+            .selection_config
+            .failover
+            .clone()
+            .filter(|f| f.warm_standby)?;
+        let candidate = self.standby_candidate(&failover).await?;
+        load_standby(
+            &self.registry,
+            &self.standby_plugin,
+            &candidate,
+            self.selection_config.decode.clone(),
+        )
+        .await
+        .then_some(candidate)
+    }
+
+    /// Id of the plugin held on warm standby
+    pub async fn standby_plugin(&self) -> Option<String> {
+        let standby = self.standby_plugin.read().await;
+        standby.as_ref().map(|p| p.info().id.clone())
+    }
+
     /// Attempt to failover to a different plugin
     async fn attempt_failover(&mut self, failed_plugin_id: &str) -> Result<String, String> {
         let registry = self.registry.read().await;
@@ -1327,9 +1500,34 @@ impl SttPluginManager {
                 }
             }
 
-            match registry.create_plugin(fallback_id) {
+            let warm = {
+                let mut standby = self.standby_plugin.write().await;
+                if standby
+                    .as_ref()
+                    .is_some_and(|p| p.info().id == *fallback_id)
+                {
+                    standby.take()
+                } else {
+                    None
+                }
+            };
+            let was_warm = warm.is_some();
+            let created = match warm {
+                Some(plugin) => Ok(plugin),
+                None => registry.create_plugin(fallback_id),
+            };
+
+            match created {
                 Ok(new_plugin) => {
                     let new_plugin_id = new_plugin.info().id.clone();
+                    info!(
+                        target: "coldvox::stt",
+                        plugin_id = %new_plugin_id,
+                        event = "failover_swap",
+                        warm = was_warm,
+                        swap_ms = now.elapsed().as_millis() as u64,
+                        "Swapped in fallback STT plugin"
+                    );
 
                     // Replace current plugin
                     {
@@ -1364,12 +1562,66 @@ impl SttPluginManager {
         (failover_count, total_errors)
     }
 
+    /// Subscribe to user-facing failover notices (plugin, error class, suggested fix)
+    pub fn subscribe_failover(&self) -> tokio::sync::broadcast::Receiver<FailoverNotice> {
+        self.failover_tx.subscribe()
+    }
+
     /// Get Instant of last failover (if any)
     pub async fn last_failover_instant(&self) -> Option<Instant> {
         *self.last_failover.read().await
     }
 }
 
+/// Create and initialize `plugin_id` and park it in `standby`, unloading
+/// whatever was there. Returns false if the plugin could not be loaded.
+async fn load_standby(
+    registry: &RwLock<SttPluginRegistry>,
+    standby: &RwLock<Option<Box<dyn SttPlugin>>>,
+    plugin_id: &str,
+    decode: coldvox_stt::DecodeResources,
+) -> bool {
+    if standby
+        .read()
+        .await
+        .as_ref()
+        .is_some_and(|p| p.info().id == plugin_id)
+    {
+        return true;
+    }
+
+    let started = Instant::now();
+    let created = registry.read().await.create_plugin(plugin_id);
+    let mut plugin = match created {
+        Ok(plugin) => plugin,
+        Err(e) => {
+            debug!(target: "coldvox::stt", plugin_id = %plugin_id, error = %e, "Standby plugin unavailable");
+            return false;
+        }
+    };
+    let config = TranscriptionConfig {
+        decode,
+        ..Default::default()
+    };
+    if let Err(e) = plugin.initialize(config).await {
+        warn!(target: "coldvox::stt", plugin_id = %plugin_id, error = %e, "Failed to warm up standby STT plugin");
+        return false;
+    }
+
+    let previous = standby.write().await.replace(plugin);
+    if let Some(mut previous) = previous {
+        let _ = previous.unload().await;
+    }
+    info!(
+        target: "coldvox::stt",
+        plugin_id = %plugin_id,
+        event = "standby_ready",
+        load_ms = started.elapsed().as_millis() as u64,
+        "Fallback STT plugin loaded on warm standby"
+    );
+    true
+}
+
 impl Drop for SttPluginManager {
     fn drop(&mut self) {
         if let Ok(mut guard) = self.gc_task.try_write() {
@@ -1522,6 +1774,40 @@ mod tests {
         assert_eq!(manager.current_plugin().await, Some("mock".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_failover_swaps_in_warm_standby() {
+        use coldvox_stt::plugins::noop::NoOpPluginFactory;
+
+        let mut manager = create_isolated_manager();
+        manager
+            .registry
+            .write()
+            .await
+            .register(Box::new(NoOpPluginFactory));
+        manager.selection_config.preferred_plugin = Some("noop".to_string());
+        manager.selection_config.fallback_plugins = vec!["mock".to_string()];
+        manager.selection_config.failover = Some(FailoverConfig {
+            warm_standby: true,
+            standby_max_mem_mb: Some(0),
+            ..Default::default()
+        });
+        manager.initialize().await.unwrap();
+
+        // Mock needs 1 MB, over the budget
+        assert_eq!(manager.prepare_standby().await, None);
+
+        if let Some(failover) = manager.selection_config.failover.as_mut() {
+            failover.standby_max_mem_mb = Some(64);
+        }
+        assert_eq!(manager.prepare_standby().await, Some("mock".to_string()));
+        assert_eq!(manager.standby_plugin().await, Some("mock".to_string()));
+
+        let new_plugin = manager.attempt_failover("noop").await.unwrap();
+        assert_eq!(new_plugin, "mock");
+        assert_eq!(manager.current_plugin().await, Some("mock".to_string()));
+        assert_eq!(manager.standby_plugin().await, None);
+    }
+
     #[tokio::test]
     async fn test_invalid_preferred_plugin_falls_back_to_best_available_plugin() {
         let mut manager = create_isolated_manager();
@@ -1563,6 +1849,7 @@ mod tests {
                 gc_policy: None,
                 metrics: None,
                 auto_extract_model: true,
+                decode: Default::default(),
             })
             .await
             .expect_err("canonical http-remote profile must reject mock fallback");
@@ -1588,6 +1875,7 @@ mod tests {
                 gc_policy: None,
                 metrics: None,
                 auto_extract_model: true,
+                decode: Default::default(),
             })
             .await
             .unwrap();
@@ -1619,6 +1907,7 @@ mod tests {
                 gc_policy: None,
                 metrics: None,
                 auto_extract_model: true,
+                decode: Default::default(),
             })
             .await
             .unwrap();
@@ -1837,6 +2126,8 @@ mod tests {
                 failover: Some(FailoverConfig {
                     failover_threshold: 3,
                     failover_cooldown_secs: 1,
+                    warm_standby: false,
+                    standby_max_mem_mb: None,
                 }),
                 gc_policy: Some(GcPolicy {
                     model_ttl_secs: 1, // Very short TTL for testing
@@ -1844,6 +2135,7 @@ mod tests {
                 }),
                 metrics: None,
                 auto_extract_model: false,
+                decode: Default::default(),
             })
             .await
             .unwrap();
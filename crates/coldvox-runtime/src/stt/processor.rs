@@ -0,0 +1,862 @@
+// ---
+// Unified STT Processor
+//
+// This file contains the unified STT processor for ColdVox. It replaces the
+// previous dual-architecture system (legacy batch vs. streaming) with a single,
+// plugin-based processor.
+//
+// Key Design Principles:
+// - Single `run` loop using `tokio::select!` for handling multiple event sources.
+// - Abstracted session lifecycle via `SessionEvent` (from VAD or Hotkey).
+// - Non-blocking finalization: When an utterance ends, a background `tokio::task`
+//   is spawned to handle the potentially slow process of finalizing the
+//   transcription with the STT plugin. This prevents the main loop from blocking
+//   and dropping audio frames from a subsequent utterance.
+// - State management via a `parking_lot::Mutex` to allow safe concurrent access
+//   from the main loop and spawned tasks.
+// - Segmentation: a VAD utterance longer than `max_utterance_ms` is finalized
+//   at the next pause while the session stays open, so long dictation is
+//   injected piece by piece.
+// - Hallucination filter: finals are checked against the utterance audio
+//   before they are sent on (see `stt::hallucination`).
+// ---
+
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+use crate::stt::session::SessionSource;
+use crate::stt::{
+    session::{HotkeyBehavior, SessionEvent, Settings},
+    SourceId, TranscriptionConfig, TranscriptionEvent,
+};
+use coldvox_audio::SharedAudioFrame;
+use coldvox_telemetry::{LatencyTracker, UtteranceTraces};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{broadcast, mpsc};
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+use tracing::{info_span, Instrument, Span};
+
+/// Represents the current state of the STT processor's utterance handling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UtteranceState {
+    /// Waiting for an utterance to begin.
+    Idle,
+    /// An utterance is actively being processed (either streaming or buffering).
+    SpeechActive,
+    /// The last utterance is being finalized. New audio is ignored until this completes.
+    Finalizing,
+}
+
+/// A snapshot of performance and activity metrics for the STT processor.
+#[derive(Debug, Clone, Default)]
+pub struct SttMetrics {
+    pub frames_in: u64,
+    pub partial_count: u64,
+    pub final_count: u64,
+    pub error_count: u64,
+    pub last_event_time: Option<Instant>,
+}
+
+// A safety guard to prevent the audio buffer from growing indefinitely in batch mode.
+// 30 seconds of 16kHz 16-bit mono audio.
+const BUFFER_CEILING_SAMPLES: usize = 16000 * 30;
+
+// Long utterances are split where the level stays below this for SEGMENT_PAUSE_SAMPLES.
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+const SEGMENT_PAUSE_DBFS: f32 = -45.0;
+// 250 ms; shorter than the VAD's end-of-speech silence, so it falls between words.
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+const SEGMENT_PAUSE_SAMPLES: usize = 16 * 250;
+
+/// Tracks how long the current segment of a VAD utterance has run and
+/// whether it has reached a pause to split at.
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+#[derive(Debug, Default)]
+struct SegmentTracker {
+    samples: usize,
+    quiet_samples: usize,
+}
+
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+impl SegmentTracker {
+    /// Account for a frame of the segment. True when the segment should be
+    /// finalized now: it has reached `max_samples` and the speaker paused, or
+    /// it ran half as long again without a pause.
+    fn observe(&mut self, samples: &[i16], max_samples: usize) -> bool {
+        self.samples += samples.len();
+        if crate::idle::frame_dbfs(samples) < SEGMENT_PAUSE_DBFS {
+            self.quiet_samples += samples.len();
+        } else {
+            self.quiet_samples = 0;
+        }
+        (self.samples >= max_samples && self.quiet_samples >= SEGMENT_PAUSE_SAMPLES)
+            || self.samples >= max_samples + max_samples / 2
+    }
+}
+
+/// Number of 16kHz samples prepended as pre-roll to a session started by
+/// `source`, or 0 when pre-roll is not used.
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+fn pre_roll_samples(settings: &Settings, source: SessionSource) -> usize {
+    let pre_roll_ms = match source {
+        SessionSource::Vad => settings.vad_pre_roll_ms,
+        SessionSource::Hotkey
+            if settings.activation_mode
+                == crate::stt::session::ActivationMode::AlwaysOnPushToTranscribe
+                || settings.hotkey_behavior == HotkeyBehavior::BatchOnRelease =>
+        {
+            settings.pre_roll_ms
+        }
+        SessionSource::Hotkey => 0,
+    };
+    pre_roll_ms as usize * 16
+}
+
+/// Samples kept in the rolling buffer: enough pre-roll for either source,
+/// since the activation mode can change while running
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+fn rolling_buffer_samples(settings: &Settings) -> usize {
+    pre_roll_samples(settings, SessionSource::Vad)
+        .max(pre_roll_samples(settings, SessionSource::Hotkey))
+}
+
+/// Take a session's pre-roll from the rolling buffer: its newest `keep`
+/// samples. Older audio, kept only for the other source's longer pre-roll,
+/// is dropped so it never reaches STT.
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+fn take_pre_roll(rolling: &mut std::collections::VecDeque<i16>, keep: usize) -> Vec<i16> {
+    let stale = rolling.len().saturating_sub(keep);
+    rolling.drain(..stale);
+    rolling.drain(..).collect()
+}
+
+/// The primary STT processor, designed to be unified and extensible.
+/// It uses the plugin manager to delegate STT work and handles different
+/// activation and processing strategies defined by `Settings`.
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+pub struct PluginSttProcessor {
+    audio_rx: broadcast::Receiver<SharedAudioFrame>,
+    session_event_rx: mpsc::Receiver<SessionEvent>,
+    event_tx: mpsc::Sender<TranscriptionEvent>,
+    plugin_manager: Arc<tokio::sync::RwLock<crate::stt::plugin_manager::SttPluginManager>>,
+    state: Arc<parking_lot::Mutex<State>>,
+    metrics: Arc<parking_lot::RwLock<SttMetrics>>,
+    config: TranscriptionConfig,
+    settings: Settings,
+    /// Audio source stamped on every partial and final this processor emits
+    source: SourceId,
+    /// Records speech-end to final latency per utterance
+    latency: Option<Arc<LatencyTracker>>,
+    /// Opens the per-utterance trace and hands it on to injection
+    traces: Option<Arc<UtteranceTraces>>,
+    /// Second plugin given every utterance for A/B comparison
+    comparison: Option<Arc<crate::stt::compare::SttComparison>>,
+    /// Checks finals for hallucinated text
+    hallucination: Option<Arc<crate::stt::hallucination::HallucinationFilter>>,
+}
+
+/// The internal, mutable state of the processor, protected by a Mutex.
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+struct State {
+    pub state: UtteranceState,
+    pub source: crate::stt::session::SessionSource,
+    pub buffer: Vec<i16>,
+    pub rolling_buffer: std::collections::VecDeque<i16>,
+    /// Capture time of the newest frame seen (speech end when the session ends)
+    pub last_captured_at: Option<Instant>,
+    /// Speech sessions started so far (the `session_id` of traces)
+    pub sessions: u64,
+    /// `utterance` span of the session in progress
+    pub root_span: Span,
+    /// `speech` span, open while the session captures audio
+    pub speech_span: Span,
+    /// Length of the current segment of a long VAD utterance
+    segment: SegmentTracker,
+}
+
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+impl PluginSttProcessor {
+    /// Creates a new instance of the unified STT processor.
+    pub fn new(
+        audio_rx: broadcast::Receiver<SharedAudioFrame>,
+        session_event_rx: mpsc::Receiver<SessionEvent>,
+        event_tx: mpsc::Sender<TranscriptionEvent>,
+        plugin_manager: Arc<tokio::sync::RwLock<crate::stt::plugin_manager::SttPluginManager>>,
+        config: TranscriptionConfig,
+        settings: Settings,
+    ) -> Self {
+        let internal_state = State {
+            state: UtteranceState::Idle,
+            source: crate::stt::session::SessionSource::Vad, // Default
+            buffer: Vec::with_capacity(16000 * 10),
+            rolling_buffer: std::collections::VecDeque::with_capacity(rolling_buffer_samples(
+                &settings,
+            )),
+            last_captured_at: None,
+            sessions: 0,
+            root_span: Span::none(),
+            speech_span: Span::none(),
+            segment: SegmentTracker::default(),
+        };
+
+        Self {
+            audio_rx,
+            session_event_rx,
+            event_tx,
+            plugin_manager,
+            state: Arc::new(parking_lot::Mutex::new(internal_state)),
+            metrics: Arc::new(parking_lot::RwLock::new(SttMetrics::default())),
+            config,
+            settings,
+            source: SourceId::default(),
+            latency: None,
+            traces: None,
+            comparison: None,
+            hallucination: None,
+        }
+    }
+
+    /// Tag emitted transcripts with `source` instead of the microphone
+    pub fn with_source(mut self, source: SourceId) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Record per-utterance latency, correlated by `utterance_id`
+    pub fn with_latency(mut self, latency: Arc<LatencyTracker>) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Trace each utterance from speech start through transcription
+    pub fn with_traces(mut self, traces: Arc<UtteranceTraces>) -> Self {
+        self.traces = Some(traces);
+        self
+    }
+
+    /// Also transcribe every utterance with a second plugin and report both
+    pub fn with_comparison(mut self, comparison: Arc<crate::stt::compare::SttComparison>) -> Self {
+        self.comparison = Some(comparison);
+        self
+    }
+
+    /// Check finals against their audio and drop or flag suspect ones
+    pub fn with_hallucination_filter(
+        mut self,
+        filter: Arc<crate::stt::hallucination::HallucinationFilter>,
+    ) -> Self {
+        self.hallucination = Some(filter);
+        self
+    }
+
+    /// Whether incremental sessions buffer their audio too
+    fn keeps_audio(&self) -> bool {
+        self.comparison.is_some() || self.hallucination.is_some()
+    }
+
+    /// The main run loop for the processor. It uses `tokio::select!` to concurrently
+    /// listen for session lifecycle events and incoming audio frames.
+    pub async fn run(mut self) {
+        tracing::info!(
+            target: "stt",
+            "Unified STT processor starting (behavior: {:?}, partials: {})",
+            self.settings.hotkey_behavior,
+            self.config.partial_results,
+        );
+
+        // Clear any stale audio frames from the channel before starting.
+        while self.audio_rx.try_recv().is_ok() {}
+
+        // Ensure the active plugin is initialized with the desired transcription config
+        {
+            let mut pm = self.plugin_manager.write().await;
+            if let Err(e) = pm.apply_transcription_config(self.config.clone()).await {
+                tracing::warn!(target: "stt", "Failed to apply transcription config to plugin: {}", e);
+            }
+        }
+
+        loop {
+            tokio::select! {
+                Some(event) = self.session_event_rx.recv() => {
+                    self.handle_session_event(event).await;
+                }
+                Ok(frame) = self.audio_rx.recv() => {
+                    self.handle_audio_frame(frame).await;
+                }
+                else => {
+                    tracing::info!(target: "stt", "Unified STT processor shutting down");
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Handles session lifecycle events (Start, End, Abort).
+    async fn handle_session_event(&self, event: SessionEvent) {
+        let mut state = self.state.lock();
+        match event {
+            SessionEvent::Start(source, _instant) => {
+                if state.state == UtteranceState::Idle {
+                    tracing::info!(target: "stt", "Session started via {:?}", source);
+                    state.source = source;
+                    state.state = UtteranceState::SpeechActive;
+                    state.buffer.clear();
+                    state.segment = SegmentTracker::default();
+                    state.sessions += 1;
+                    if let Some(traces) = &self.traces {
+                        let root = traces.begin(state.sessions);
+                        state.speech_span = info_span!(
+                            target: coldvox_telemetry::trace::TARGET,
+                            parent: &root,
+                            "speech",
+                            source = ?source,
+                        );
+                        state.root_span = root;
+                    }
+
+                    // Flush the rolling buffer into the main pipeline if we have pre-roll data
+                    let keep = pre_roll_samples(&self.settings, source);
+                    let pre_roll = take_pre_roll(&mut state.rolling_buffer, keep);
+                    if !pre_roll.is_empty() {
+                        tracing::debug!(target: "stt_debug", "Flushing {} samples of pre-roll audio", pre_roll.len());
+                        // Incremental sessions only buffer audio for the
+                        // comparison and the hallucination filter
+                        if self.settings.hotkey_behavior
+                            != crate::stt::session::HotkeyBehavior::Incremental
+                            || self.keeps_audio()
+                        {
+                            state.buffer.extend_from_slice(&pre_roll);
+                        }
+                    }
+
+                    let pm = self.plugin_manager.clone();
+                    let incremental = self.settings.hotkey_behavior
+                        == crate::stt::session::HotkeyBehavior::Incremental;
+                    tokio::spawn(async move {
+                        if let Err(e) = pm.write().await.begin_utterance().await {
+                            tracing::error!(target: "stt", "Plugin begin_utterance failed: {}", e);
+                            return;
+                        }
+                        if incremental && !pre_roll.is_empty() {
+                            if let Err(e) = pm.write().await.process_audio(&pre_roll).await {
+                                tracing::error!(target: "stt", "Plugin process_audio failed on pre-roll: {}", e);
+                            }
+                        }
+                    });
+                }
+            }
+            SessionEvent::End(source, _instant) => {
+                self.handle_session_end(source, false, &mut state);
+            }
+            SessionEvent::Abort(source, reason) => {
+                tracing::warn!(target: "stt", "Session aborted from {:?}: {}", source, reason);
+                self.handle_session_end(source, true, &mut state);
+            }
+        }
+    }
+
+    /// Handles the end of an utterance. This is a critical path that spawns a
+    /// non-blocking task to finalize the transcription, ensuring the main loop
+    /// can immediately start processing the next utterance.
+    fn handle_session_end(
+        &self,
+        _source: crate::stt::session::SessionSource,
+        is_abort: bool,
+        state: &mut parking_lot::MutexGuard<'_, State>,
+    ) {
+        if state.state != UtteranceState::SpeechActive {
+            return;
+        }
+
+        // Audio for this session is complete
+        state.speech_span = Span::none();
+        let root = std::mem::replace(&mut state.root_span, Span::none());
+
+        if is_abort {
+            state.state = UtteranceState::Idle;
+            state.buffer.clear();
+            let pm = self.plugin_manager.clone();
+            tokio::spawn(async move {
+                if let Err(e) = pm.write().await.cancel_utterance().await {
+                    tracing::error!(target: "stt", "Plugin cancel_utterance failed: {}", e);
+                }
+            });
+            return;
+        }
+
+        state.state = UtteranceState::Finalizing;
+
+        let pm = self.plugin_manager.clone();
+        let emitter = self.final_emitter();
+        let behavior = self.settings.hotkey_behavior.clone();
+        let buffer = state.buffer.clone();
+        let state_arc = self.state.clone();
+        let comparison = self.comparison.clone();
+        let speech_end = state.last_captured_at.unwrap_or_else(Instant::now);
+        let finalize_span = info_span!(
+            target: coldvox_telemetry::trace::TARGET,
+            parent: &root,
+            "stt.finalize",
+            samples = buffer.len(),
+        );
+
+        let finalize = async move {
+            tracing::debug!(target: "stt_debug", "Finalization task started.");
+            let finalize_start = Instant::now();
+            // The second plugin works on the same audio alongside the first
+            let secondary = comparison
+                .as_ref()
+                .filter(|_| !buffer.is_empty())
+                .map(|comparison| {
+                    let comparison = comparison.clone();
+                    let audio = buffer.clone();
+                    tokio::spawn(async move { comparison.transcribe(&audio).await })
+                });
+            // In batch mode, send the entire buffer to the plugin first.
+            if behavior != HotkeyBehavior::Incremental && !buffer.is_empty() {
+                let processed = async { pm.write().await.process_audio(&buffer).await }
+                    .instrument(info_span!(
+                        target: coldvox_telemetry::trace::TARGET,
+                        "stt.process_batch"
+                    ))
+                    .await;
+                if let Err(e) = processed {
+                    tracing::error!(target: "stt", "Plugin batch processing error: {}", e);
+                }
+            }
+
+            // Finalize the utterance to get the definitive transcription.
+            tracing::debug!(target: "stt_debug", "Calling plugin.finalize().");
+            let finalize_result = async { pm.write().await.finalize().await }
+                .instrument(info_span!(
+                    target: coldvox_telemetry::trace::TARGET,
+                    "stt.plugin_finalize"
+                ))
+                .await;
+            tracing::debug!(target: "stt_debug", "Plugin.finalize() returned.");
+
+            if let (Some(comparison), Some(secondary)) = (&comparison, secondary) {
+                let primary_id = pm.read().await.current_plugin().await.unwrap_or_default();
+                let primary = crate::stt::compare::TranscriptOutcome::from_final(
+                    primary_id,
+                    finalize_result.clone(),
+                    finalize_start,
+                );
+                let utterance_id = match &finalize_result {
+                    Ok(Some(TranscriptionEvent::Final { utterance_id, .. })) => Some(*utterance_id),
+                    _ => None,
+                };
+                let comparison = comparison.clone();
+                let audio_samples = buffer.len();
+                tokio::spawn(async move {
+                    if let Ok(secondary) = secondary.await {
+                        comparison.report(crate::stt::compare::ComparisonResult::new(
+                            utterance_id,
+                            audio_samples,
+                            primary,
+                            secondary,
+                        ));
+                    }
+                });
+            }
+
+            emitter
+                .emit(finalize_result, &root, speech_end, &buffer)
+                .await;
+
+            // Critical: Reset the state back to Idle so the next utterance can start.
+            let mut final_state = state_arc.lock();
+            final_state.state = UtteranceState::Idle;
+            final_state.buffer.clear();
+            tracing::debug!(target: "stt_debug", "Finalization task finished, state reset to Idle.");
+        };
+        tokio::spawn(finalize.instrument(finalize_span));
+    }
+
+    /// Handles an incoming chunk of audio frames.
+    async fn handle_audio_frame(&self, frame: SharedAudioFrame) {
+        let behavior = self.settings.hotkey_behavior.clone();
+        // Use i16 samples directly from SharedAudioFrame
+        let samples_slice: &[i16] = &frame.samples;
+
+        let max_segment_samples = self.settings.max_utterance_ms as usize * 16;
+        let mut split = false;
+        let should_process = {
+            let mut state = self.state.lock();
+            state.last_captured_at = Some(frame.captured_at);
+            match state.state {
+                UtteranceState::SpeechActive => {
+                    if max_segment_samples > 0 && state.source == SessionSource::Vad {
+                        split = state.segment.observe(samples_slice, max_segment_samples);
+                    }
+                    true
+                }
+                UtteranceState::Idle => {
+                    // Keep a rolling window of recent audio to use as pre-roll
+                    let max_samples = rolling_buffer_samples(&self.settings);
+                    if max_samples > 0 {
+                        state.rolling_buffer.extend(samples_slice.iter().copied());
+                        if state.rolling_buffer.len() > max_samples {
+                            let excess = state.rolling_buffer.len() - max_samples;
+                            state.rolling_buffer.drain(0..excess);
+                        }
+                    }
+                    false
+                }
+                UtteranceState::Finalizing => false,
+            }
+        };
+
+        if !should_process {
+            return;
+        }
+
+        if behavior != HotkeyBehavior::Incremental {
+            // Batch mode: lock, buffer, and return. The plugin sees the audio on release.
+            let mut state = self.state.lock();
+            if state.state == UtteranceState::SpeechActive {
+                state.buffer.extend_from_slice(samples_slice);
+                if split {
+                    self.split_batch_segment(&mut state);
+                } else if state.buffer.len() > BUFFER_CEILING_SAMPLES {
+                    tracing::warn!(target: "stt", "Audio buffer ceiling reached. Defensively finalizing.");
+                    self.handle_session_end(state.source, false, &mut state);
+                }
+            }
+            return;
+        }
+
+        if self.keeps_audio() {
+            let mut state = self.state.lock();
+            if state.state == UtteranceState::SpeechActive
+                && state.buffer.len() < BUFFER_CEILING_SAMPLES
+            {
+                state.buffer.extend_from_slice(samples_slice);
+            }
+        }
+
+        tracing::trace!(target: "stt_debug", "Dispatching {} samples to plugin.process_audio()", samples_slice.len());
+        match self
+            .plugin_manager
+            .write()
+            .await
+            .process_audio(samples_slice)
+            .await
+        {
+            Ok(Some(event)) => {
+                tracing::debug!(target: "stt_debug", "plugin.process_audio() produced event: {}", coldvox_foundation::scrub::scrub(&format!("{:?}", event)));
+                Self::send_event_static(&self.event_tx, &self.metrics, &self.source, event).await;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!(target: "stt_debug", "plugin.process_audio() returned error: {}", e);
+                let err_event = TranscriptionEvent::Error {
+                    code: "PLUGIN_PROCESS_ERROR".to_string(),
+                    message: e,
+                };
+                Self::send_event_static(&self.event_tx, &self.metrics, &self.source, err_event)
+                    .await;
+            }
+        }
+
+        if split {
+            self.split_incremental_segment().await;
+        }
+    }
+
+    /// Emitter for finals of this processor
+    fn final_emitter(&self) -> FinalEmitter {
+        FinalEmitter {
+            event_tx: self.event_tx.clone(),
+            metrics: self.metrics.clone(),
+            source: self.source.clone(),
+            latency: self.latency.clone(),
+            traces: self.traces.clone(),
+            hallucination: self.hallucination.clone(),
+        }
+    }
+
+    /// Finalize the buffered part of a long batch-mode utterance in the
+    /// background while the session keeps buffering the rest.
+    fn split_batch_segment(&self, state: &mut parking_lot::MutexGuard<'_, State>) {
+        let segment = std::mem::take(&mut state.buffer);
+        state.segment = SegmentTracker::default();
+        tracing::info!(
+            target: "stt",
+            "Long utterance: finalizing {} ms at a pause",
+            segment.len() / 16
+        );
+        let pm = self.plugin_manager.clone();
+        let emitter = self.final_emitter();
+        let root = state.root_span.clone();
+        let speech_end = state.last_captured_at.unwrap_or_else(Instant::now);
+        tokio::spawn(async move {
+            // One lock for the whole segment so the next one can't interleave
+            let mut pm = pm.write().await;
+            let result = match pm.process_audio(&segment).await {
+                Ok(_) => pm.finalize().await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = pm.begin_utterance().await {
+                tracing::error!(target: "stt", "Plugin begin_utterance failed after a split: {}", e);
+            }
+            drop(pm);
+            emitter.emit(result, &root, speech_end, &segment).await;
+        });
+    }
+
+    /// Finalize what a streaming plugin has heard of a long utterance and
+    /// carry on in a new segment, without leaving the session.
+    async fn split_incremental_segment(&self) {
+        let (root, speech_end, audio) = {
+            let mut state = self.state.lock();
+            if state.state != UtteranceState::SpeechActive {
+                return;
+            }
+            state.segment = SegmentTracker::default();
+            // The comparison covers the segment still being spoken
+            let audio = std::mem::take(&mut state.buffer);
+            (
+                state.root_span.clone(),
+                state.last_captured_at.unwrap_or_else(Instant::now),
+                audio,
+            )
+        };
+        tracing::info!(target: "stt", "Long utterance: finalizing at a pause");
+        let result = {
+            let mut pm = self.plugin_manager.write().await;
+            let result = pm.finalize().await;
+            if let Err(e) = pm.begin_utterance().await {
+                tracing::error!(target: "stt", "Plugin begin_utterance failed after a split: {}", e);
+            }
+            result
+        };
+        self.final_emitter()
+            .emit(result, &root, speech_end, &audio)
+            .await;
+    }
+
+    /// A static helper to send transcription events and update metrics, callable
+    /// from spawned tasks.
+    async fn send_event_static(
+        event_tx: &mpsc::Sender<TranscriptionEvent>,
+        metrics_arc: &Arc<parking_lot::RwLock<SttMetrics>>,
+        source: &SourceId,
+        event: TranscriptionEvent,
+    ) {
+        let event = event.with_source(source.clone());
+        {
+            let mut metrics = metrics_arc.write();
+            match &event {
+                TranscriptionEvent::Partial { .. } => metrics.partial_count += 1,
+                TranscriptionEvent::Final { .. } => metrics.final_count += 1,
+                TranscriptionEvent::Error { .. } => metrics.error_count += 1,
+            }
+            metrics.last_event_time = Some(Instant::now());
+        }
+
+        if event_tx.send(event).await.is_err() {
+            tracing::warn!(target: "stt", "Failed to send transcription event: channel closed");
+        }
+    }
+}
+
+/// Sends finals from the processor and its finalization tasks, recording
+/// latency and traces on the way
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+struct FinalEmitter {
+    event_tx: mpsc::Sender<TranscriptionEvent>,
+    metrics: Arc<parking_lot::RwLock<SttMetrics>>,
+    source: SourceId,
+    latency: Option<Arc<LatencyTracker>>,
+    traces: Option<Arc<UtteranceTraces>>,
+    hallucination: Option<Arc<crate::stt::hallucination::HallucinationFilter>>,
+}
+
+#[cfg(any(feature = "moonshine", feature = "parakeet", feature = "http-remote"))]
+impl FinalEmitter {
+    /// `audio` is what the final was transcribed from (may be empty in
+    /// incremental mode)
+    async fn emit(
+        &self,
+        result: Result<Option<TranscriptionEvent>, String>,
+        root: &Span,
+        speech_end: Instant,
+        audio: &[i16],
+    ) {
+        let result = match (result, &self.hallucination) {
+            (Ok(Some(event)), Some(filter)) => Ok(filter.apply(event, audio)),
+            (result, _) => result,
+        };
+        match result {
+            Ok(Some(event)) => {
+                tracing::debug!(target: "stt_debug", "Finalization produced event: {}", coldvox_foundation::scrub::scrub(&format!("{:?}", event)));
+                if let (Some(traces), TranscriptionEvent::Final { utterance_id, .. }) =
+                    (&self.traces, &event)
+                {
+                    traces.transcribed(*utterance_id, root.clone());
+                }
+                if let (Some(latency), TranscriptionEvent::Final { utterance_id, .. }) =
+                    (&self.latency, &event)
+                {
+                    latency.record_transcribed(*utterance_id, speech_end);
+                    tracing::debug!(
+                        target: "stt",
+                        utterance_id,
+                        stt_ms = speech_end.elapsed().as_millis() as u64,
+                        "Utterance transcribed"
+                    );
+                }
+                PluginSttProcessor::send_event_static(
+                    &self.event_tx,
+                    &self.metrics,
+                    &self.source,
+                    event,
+                )
+                .await;
+            }
+            Ok(None) => {
+                tracing::debug!(target: "stt_debug", "Finalization produced no event.");
+            }
+            Err(e) => {
+                let err_event = TranscriptionEvent::Error {
+                    code: "FINALIZE_FAILED".to_string(),
+                    message: e,
+                };
+                PluginSttProcessor::send_event_static(
+                    &self.event_tx,
+                    &self.metrics,
+                    &self.source,
+                    err_event,
+                )
+                .await;
+            }
+        }
+    }
+}
+
+#[cfg(all(
+    test,
+    any(feature = "moonshine", feature = "parakeet", feature = "http-remote")
+))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_segments_split_at_a_pause() {
+        let max = 16_000;
+        let speech = vec![8_000i16; 1_600];
+        let quiet = vec![10i16; 1_600];
+
+        let mut segment = SegmentTracker::default();
+        // A pause before the limit doesn't split
+        assert!(!segment.observe(&quiet, max));
+        assert!(!segment.observe(&quiet, max));
+        for _ in 0..8 {
+            assert!(!segment.observe(&speech, max));
+        }
+        // Past the limit, 250 ms of quiet splits
+        assert!(!segment.observe(&speech, max));
+        assert!(!segment.observe(&quiet, max));
+        assert!(!segment.observe(&quiet, max));
+        assert!(segment.observe(&quiet, max));
+
+        // Without a pause, it splits at half as long again
+        let mut segment = SegmentTracker::default();
+        let splits: Vec<bool> = (0..15).map(|_| segment.observe(&speech, max)).collect();
+        assert_eq!(splits.iter().position(|s| *s), Some(14));
+    }
+
+    #[test]
+    fn pre_roll_is_sized_per_source() {
+        use crate::stt::session::ActivationMode;
+
+        let settings = Settings {
+            pre_roll_ms: 2000,
+            vad_pre_roll_ms: 300,
+            ..Default::default()
+        };
+        assert_eq!(pre_roll_samples(&settings, SessionSource::Vad), 300 * 16);
+        // Incremental hotkey sessions stream from the press: no pre-roll
+        assert_eq!(pre_roll_samples(&settings, SessionSource::Hotkey), 0);
+        assert_eq!(rolling_buffer_samples(&settings), 300 * 16);
+
+        let batch = Settings {
+            hotkey_behavior: HotkeyBehavior::BatchOnRelease,
+            ..settings.clone()
+        };
+        assert_eq!(pre_roll_samples(&batch, SessionSource::Hotkey), 2000 * 16);
+        assert_eq!(rolling_buffer_samples(&batch), 2000 * 16);
+
+        let push_to_transcribe = Settings {
+            activation_mode: ActivationMode::AlwaysOnPushToTranscribe,
+            ..settings.clone()
+        };
+        assert_eq!(
+            pre_roll_samples(&push_to_transcribe, SessionSource::Hotkey),
+            2000 * 16
+        );
+
+        let no_vad_pre_roll = Settings {
+            vad_pre_roll_ms: 0,
+            ..settings
+        };
+        assert_eq!(pre_roll_samples(&no_vad_pre_roll, SessionSource::Vad), 0);
+        assert_eq!(rolling_buffer_samples(&no_vad_pre_roll), 0);
+    }
+
+    #[test]
+    fn pre_roll_keeps_only_the_newest_samples() {
+        // The buffer holds a batch hotkey's 2 s; a VAD start only wants 300 ms
+        let mut rolling: std::collections::VecDeque<i16> = (0..32_000).map(|i| i as i16).collect();
+        let pre_roll = take_pre_roll(&mut rolling, 300 * 16);
+        assert_eq!(pre_roll.len(), 4_800);
+        assert_eq!(pre_roll.first(), Some(&(32_000 - 4_800)));
+        assert_eq!(pre_roll.last(), Some(&31_999));
+        assert!(rolling.is_empty());
+
+        // Less buffered than wanted: all of it
+        let mut rolling: std::collections::VecDeque<i16> = vec![1, 2, 3].into();
+        assert_eq!(take_pre_roll(&mut rolling, 4_800), vec![1, 2, 3]);
+
+        // No pre-roll for this source: stale audio is still cleared
+        let mut rolling: std::collections::VecDeque<i16> = vec![1, 2, 3].into();
+        assert!(take_pre_roll(&mut rolling, 0).is_empty());
+        assert!(rolling.is_empty());
+    }
+}
+
+/// A stub implementation of the processor for when no STT feature is enabled.
+#[cfg(not(any(feature = "moonshine", feature = "parakeet", feature = "http-remote")))]
+pub struct PluginSttProcessor;
+
+#[cfg(not(any(feature = "moonshine", feature = "parakeet", feature = "http-remote")))]
+impl PluginSttProcessor {
+    pub fn new(
+        _audio_rx: broadcast::Receiver<SharedAudioFrame>,
+        _session_event_rx: mpsc::Receiver<SessionEvent>,
+        _event_tx: mpsc::Sender<TranscriptionEvent>,
+        _plugin_manager: Arc<tokio::sync::RwLock<crate::stt::plugin_manager::SttPluginManager>>,
+        _config: TranscriptionConfig,
+        _settings: Settings,
+    ) -> Self {
+        Self
+    }
+    pub fn with_source(self, _source: SourceId) -> Self {
+        self
+    }
+    pub fn with_latency(self, _latency: Arc<LatencyTracker>) -> Self {
+        self
+    }
+    pub fn with_traces(self, _traces: Arc<UtteranceTraces>) -> Self {
+        self
+    }
+    pub fn with_hallucination_filter(
+        self,
+        _filter: Arc<crate::stt::hallucination::HallucinationFilter>,
+    ) -> Self {
+        self
+    }
+    pub async fn run(self) {
+        tracing::info!("STT processor stub running - no actual processing (STT features disabled)");
+    }
+}
@@ -16,8 +16,6 @@ pub enum SessionEvent {
     End(SessionSource, Instant),
     /// A session was aborted.
     Abort(SessionSource, &'static str),
-    // Future: A long-running session has been split by a silence detector.
-    // SegmentSplit(SessionSource, Instant),
 }
 
 /// Defines the primary activation method for STT.
@@ -37,8 +35,9 @@ pub enum HotkeyBehavior {
     /// (Default) Process audio incrementally as it arrives.
     /// Partial results are emitted throughout the keypress.
     Incremental,
-    // Future: Buffer all audio during keypress and process it all on release.
-    // BatchOnRelease,
+    /// Buffer exactly the audio between press and release (plus pre-roll) and
+    /// hand it to the plugin as a single utterance on release.
+    BatchOnRelease,
     // Future: A hybrid approach combining Incremental and BatchOnRelease.
     // Hybrid,
 }
@@ -79,6 +78,16 @@ pub struct Settings {
     pub hotkey_behavior: HotkeyBehavior,
     pub partial_policy: PartialPolicy,
     pub long_hold: LongHoldStub,
+    /// Audio kept from before the session start and prepended to the utterance.
+    /// Applies to `BatchOnRelease` and `AlwaysOnPushToTranscribe`.
+    pub pre_roll_ms: u32,
+    /// Audio kept from before a VAD speech start and prepended to the
+    /// utterance. VAD only fires once speech has lasted a while, so without
+    /// it the first word loses its onset. 0 = off.
+    pub vad_pre_roll_ms: u32,
+    /// Finalize VAD utterances at the next pause once they run this long,
+    /// so long dictation is injected piece by piece. 0 = off.
+    pub max_utterance_ms: u32,
 }
 
 impl Default for Settings {
@@ -88,6 +97,9 @@ impl Default for Settings {
             hotkey_behavior: HotkeyBehavior::Incremental,
             partial_policy: PartialPolicy::Emit,
             long_hold: LongHoldStub::default(),
+            pre_roll_ms: 2000,
+            vad_pre_roll_ms: 300,
+            max_utterance_ms: 0,
         }
     }
 }
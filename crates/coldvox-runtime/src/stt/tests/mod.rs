@@ -59,6 +59,7 @@ mod stt_core_tests {
             text: "partial text".to_string(),
             t0: Some(0.0),
             t1: Some(1.0),
+            source: coldvox_stt::SourceId::default(),
         };
 
         match partial {
@@ -97,6 +97,8 @@ async fn test_mode_switch_during_speech() {
                 samples: std::sync::Arc::from(vec![0i16; 512]),
                 sample_rate: 16000,
                 timestamp: Instant::now(),
+                captured_at: Instant::now(),
+                discontinuity: false,
             };
             let _ = audio_tx.send(frame);
             tokio::time::sleep(Duration::from_millis(30)).await;
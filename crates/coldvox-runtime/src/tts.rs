@@ -0,0 +1,505 @@
+//! Spoken read-back of dictation.
+//!
+//! The `read_back` hotkey, the spoken command "read that back" and
+//! [`AppHandle::read_back`](crate::runtime::AppHandle::read_back) speak the
+//! held dictation draft, or the last final transcript when nothing is held.
+//! Speech comes from an external synthesizer that writes a WAV file to
+//! stdout (`espeak-ng --stdout` by default) and is played with
+//! [`AudioPlayer`]. The microphone is muted while it plays so ColdVox
+//! doesn't transcribe its own voice.
+//!
+//! One synthesis runs at a time: a new request, [`TtsEngine::stop_synthesis`]
+//! or the timeout kills the synthesizer process, and each outcome is
+//! published as a [`SynthesisEvent`].
+
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use coldvox_audio::{AudioPlayer, Clip};
+use parking_lot::Mutex;
+use tokio::io::AsyncReadExt;
+use tokio::process::Command;
+use tokio::sync::{broadcast, oneshot};
+
+/// Time the microphone stays muted after playback ends, so the tail of the
+/// speech (and any room echo) doesn't open an utterance
+const ECHO_TAIL: Duration = Duration::from_millis(250);
+
+/// Speech synthesizer and read-back settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TtsConfig {
+    /// Synthesizer taking espeak-ng style arguments and writing WAV to stdout
+    pub command: String,
+    /// Voice name (None = the synthesizer's default)
+    pub voice: Option<String>,
+    /// Speaking rate in words per minute
+    pub rate_wpm: u32,
+    /// Output device (None = system default)
+    pub output_device: Option<String>,
+    /// Mute the microphone while speaking
+    pub mute_mic: bool,
+    /// Longest a single synthesis may take before it is killed
+    pub timeout: Duration,
+}
+
+impl Default for TtsConfig {
+    fn default() -> Self {
+        Self {
+            command: "espeak-ng".to_string(),
+            voice: None,
+            rate_wpm: 175,
+            output_device: None,
+            mute_mic: true,
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Why a synthesis was cut short
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelReason {
+    /// [`TtsEngine::stop_synthesis`] was called
+    Stopped,
+    /// A newer request replaced it
+    Superseded,
+    /// It ran longer than [`TtsConfig::timeout`]
+    TimedOut,
+    /// The caller stopped waiting for it
+    Dropped,
+}
+
+/// Progress of a synthesis request, numbered by `id`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SynthesisEvent {
+    Started { id: u64 },
+    Finished { id: u64, bytes: usize },
+    Cancelled { id: u64, reason: CancelReason },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TtsError {
+    #[error("nothing to read back")]
+    NothingToSay,
+    #[error("failed to run speech synthesizer '{command}': {source}")]
+    Spawn {
+        command: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("speech synthesizer exited with {status}: {stderr}")]
+    Synthesizer {
+        status: std::process::ExitStatus,
+        stderr: String,
+    },
+    #[error("failed to read speech synthesizer output: {0}")]
+    Read(#[source] std::io::Error),
+    #[error("speech synthesis cancelled ({0:?})")]
+    Cancelled(CancelReason),
+    #[error("speech synthesis took longer than {0:?}")]
+    TimedOut(Duration),
+    #[error("speech synthesizer output unusable: {0}")]
+    Audio(coldvox_foundation::AudioError),
+    #[error("no audio output: {0}")]
+    Output(coldvox_foundation::AudioError),
+}
+
+/// The synthesis in flight; sending on `cancel` makes it kill its process
+struct Active {
+    id: u64,
+    cancel: oneshot::Sender<CancelReason>,
+}
+
+/// Text-to-speech through an external synthesizer
+pub struct TtsEngine {
+    config: TtsConfig,
+    next_id: AtomicU64,
+    active: Mutex<Option<Active>>,
+    events: broadcast::Sender<SynthesisEvent>,
+}
+
+/// Ends the synthesis as [`CancelReason::Dropped`] if its future is dropped
+/// (e.g. the task was aborted) before it settled
+struct Running<'a> {
+    engine: &'a TtsEngine,
+    id: u64,
+    armed: bool,
+}
+
+impl Drop for Running<'_> {
+    fn drop(&mut self) {
+        if self.armed {
+            self.engine.settle(
+                self.id,
+                Some(SynthesisEvent::Cancelled {
+                    id: self.id,
+                    reason: CancelReason::Dropped,
+                }),
+            );
+        }
+    }
+}
+
+impl TtsEngine {
+    pub fn new(config: TtsConfig) -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            config,
+            next_id: AtomicU64::new(1),
+            active: Mutex::new(None),
+            events,
+        }
+    }
+
+    /// Synthesis progress events
+    pub fn subscribe(&self) -> broadcast::Receiver<SynthesisEvent> {
+        self.events.subscribe()
+    }
+
+    /// Cancel the synthesis in flight, if any. Returns whether there was one.
+    pub fn stop_synthesis(&self) -> bool {
+        let active = self.active.lock().take();
+        match active {
+            Some(active) => {
+                self.cancel(active, CancelReason::Stopped);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn cancel(&self, active: Active, reason: CancelReason) {
+        let _ = active.cancel.send(reason);
+        let _ = self.events.send(SynthesisEvent::Cancelled {
+            id: active.id,
+            reason,
+        });
+    }
+
+    /// Clear synthesis `id` if it is still the active one, publishing `event`
+    fn settle(&self, id: u64, event: Option<SynthesisEvent>) {
+        let mut active = self.active.lock();
+        if active.as_ref().is_some_and(|a| a.id == id) {
+            *active = None;
+            drop(active);
+            if let Some(event) = event {
+                let _ = self.events.send(event);
+            }
+        }
+    }
+
+    /// Synthesizer arguments for `text`
+    fn args(&self, text: &str) -> Vec<String> {
+        let mut args = vec![
+            "--stdout".to_string(),
+            "-s".to_string(),
+            self.config.rate_wpm.to_string(),
+        ];
+        if let Some(voice) = &self.config.voice {
+            args.push("-v".to_string());
+            args.push(voice.clone());
+        }
+        // Text after `--` so a transcript starting with '-' isn't an option
+        args.push("--".to_string());
+        args.push(text.to_string());
+        args
+    }
+
+    /// Render `text` to audio, cancelling any synthesis still in flight
+    pub async fn synthesize(&self, text: &str) -> Result<Clip, TtsError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (cancel, cancelled) = oneshot::channel();
+        let previous = self.active.lock().replace(Active { id, cancel });
+        if let Some(previous) = previous {
+            self.cancel(previous, CancelReason::Superseded);
+        }
+        let mut running = Running {
+            engine: self,
+            id,
+            armed: true,
+        };
+        let _ = self.events.send(SynthesisEvent::Started { id });
+
+        let result = self.run(text, cancelled).await;
+        running.armed = false;
+        let event = match &result {
+            Ok(wav) => Some(SynthesisEvent::Finished {
+                id,
+                bytes: wav.len(),
+            }),
+            Err(TtsError::TimedOut(_)) => Some(SynthesisEvent::Cancelled {
+                id,
+                reason: CancelReason::TimedOut,
+            }),
+            // Cancellations were published by whoever cancelled
+            Err(_) => None,
+        };
+        self.settle(id, event);
+        Clip::from_wav(&result?).map_err(TtsError::Audio)
+    }
+
+    /// Run the synthesizer, streaming its WAV output until it exits, is
+    /// cancelled or times out
+    async fn run(
+        &self,
+        text: &str,
+        mut cancelled: oneshot::Receiver<CancelReason>,
+    ) -> Result<Vec<u8>, TtsError> {
+        let deadline = tokio::time::Instant::now() + self.config.timeout;
+        let mut child = Command::new(&self.config.command)
+            .args(self.args(text))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|source| TtsError::Spawn {
+                command: self.config.command.clone(),
+                source,
+            })?;
+        let mut stdout = child.stdout.take().expect("stdout is piped");
+        let mut stderr = child.stderr.take().expect("stderr is piped");
+        // Drained alongside stdout so a chatty synthesizer can't block on a full pipe
+        let stderr = tokio::spawn(async move {
+            let mut buf = Vec::new();
+            let _ = stderr.read_to_end(&mut buf).await;
+            buf
+        });
+
+        let mut wav = Vec::new();
+        let mut chunk = [0u8; 8192];
+        loop {
+            tokio::select! {
+                read = stdout.read(&mut chunk) => match read.map_err(TtsError::Read)? {
+                    0 => break,
+                    n => wav.extend_from_slice(&chunk[..n]),
+                },
+                reason = &mut cancelled => {
+                    let _ = child.start_kill();
+                    return Err(TtsError::Cancelled(reason.unwrap_or(CancelReason::Stopped)));
+                }
+                _ = tokio::time::sleep_until(deadline) => {
+                    let _ = child.start_kill();
+                    return Err(TtsError::TimedOut(self.config.timeout));
+                }
+            }
+        }
+        let status = match tokio::time::timeout_at(deadline, child.wait()).await {
+            Ok(status) => status.map_err(TtsError::Read)?,
+            Err(_) => {
+                let _ = child.start_kill();
+                return Err(TtsError::TimedOut(self.config.timeout));
+            }
+        };
+        if !status.success() {
+            let stderr = stderr.await.unwrap_or_default();
+            return Err(TtsError::Synthesizer {
+                status,
+                stderr: String::from_utf8_lossy(&stderr).trim().to_string(),
+            });
+        }
+        Ok(wav)
+    }
+}
+
+/// Mutes the microphone until dropped, also when speaking is interrupted
+struct MuteGuard<'a>(Option<&'a AtomicBool>);
+
+impl<'a> MuteGuard<'a> {
+    fn new(flag: Option<&'a AtomicBool>) -> Self {
+        if let Some(flag) = flag {
+            flag.store(true, Ordering::Relaxed);
+        }
+        Self(flag)
+    }
+}
+
+impl Drop for MuteGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(flag) = self.0 {
+            flag.store(false, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Speaks text with the configured engine while the microphone is muted
+pub struct ReadBack {
+    engine: TtsEngine,
+    output_device: Option<String>,
+    /// Capture mute flag ([`AudioCaptureThread::muted`](coldvox_audio::AudioCaptureThread::muted)),
+    /// None when the microphone should stay live
+    mic_muted: Option<Arc<AtomicBool>>,
+    /// Opened on first use so an idle ColdVox doesn't hold the output device
+    player: Mutex<Option<Arc<AudioPlayer>>>,
+}
+
+impl ReadBack {
+    pub fn new(config: TtsConfig, mic_muted: Arc<AtomicBool>) -> Self {
+        Self {
+            mic_muted: config.mute_mic.then_some(mic_muted),
+            output_device: config.output_device.clone(),
+            engine: TtsEngine::new(config),
+            player: Mutex::new(None),
+        }
+    }
+
+    fn player(&self) -> Result<Arc<AudioPlayer>, TtsError> {
+        let mut player = self.player.lock();
+        if let Some(p) = player.as_ref() {
+            return Ok(p.clone());
+        }
+        let opened =
+            Arc::new(AudioPlayer::open(self.output_device.as_deref()).map_err(TtsError::Output)?);
+        *player = Some(opened.clone());
+        Ok(opened)
+    }
+
+    /// Speak `text` and wait until it has been played
+    pub async fn speak(&self, text: &str) -> Result<(), TtsError> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(TtsError::NothingToSay);
+        }
+        let clip = self.engine.synthesize(text).await?;
+        let player = self.player()?;
+        // Anything still playing is stale now
+        player.stop();
+
+        let _mute = MuteGuard::new(self.mic_muted.as_deref());
+        if player.enqueue(&clip).finished().await {
+            tokio::time::sleep(ECHO_TAIL).await;
+        }
+        Ok(())
+    }
+
+    /// Synthesis progress of read-backs
+    pub fn subscribe(&self) -> broadcast::Receiver<SynthesisEvent> {
+        self.engine.subscribe()
+    }
+
+    /// Cut the current read-back short, whether it is still being
+    /// synthesized or already playing
+    pub fn stop(&self) {
+        self.engine.stop_synthesis();
+        if let Some(player) = self.player.lock().as_ref() {
+            player.stop();
+        }
+    }
+}
+
+/// Whether a final transcript is the spoken read-back command
+pub fn is_read_back_command(text: &str) -> bool {
+    const PHRASES: [&str; 4] = [
+        "read that back",
+        "read it back",
+        "read back",
+        "read back dictation",
+    ];
+    let normalized = text
+        .split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    PHRASES.contains(&normalized.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn synthesizer_arguments_keep_text_out_of_options() {
+        let engine = TtsEngine::new(TtsConfig {
+            voice: Some("en-us".to_string()),
+            rate_wpm: 200,
+            ..Default::default()
+        });
+        assert_eq!(
+            engine.args("-rf is not a flag"),
+            [
+                "--stdout",
+                "-s",
+                "200",
+                "-v",
+                "en-us",
+                "--",
+                "-rf is not a flag"
+            ]
+        );
+    }
+
+    #[test]
+    fn read_back_command_matches_whole_phrase_only() {
+        assert!(is_read_back_command("Read that back."));
+        assert!(is_read_back_command("  read back "));
+        assert!(!is_read_back_command("please read that back to the team"));
+        assert!(!is_read_back_command("feedback"));
+    }
+
+    /// Engine running a synthesizer that never finishes
+    #[cfg(unix)]
+    fn hanging_engine(dir: &std::path::Path, timeout: Duration) -> TtsEngine {
+        use std::os::unix::fs::PermissionsExt;
+        let script = dir.join("slow-tts");
+        std::fs::write(&script, "#!/bin/sh\nexec sleep 30\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        TtsEngine::new(TtsConfig {
+            command: script.to_string_lossy().into_owned(),
+            timeout,
+            ..Default::default()
+        })
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn synthesis_can_be_stopped_or_time_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let engine = Arc::new(hanging_engine(dir.path(), Duration::from_millis(200)));
+        let mut events = engine.subscribe();
+
+        let err = engine.synthesize("hello").await.unwrap_err();
+        assert!(matches!(err, TtsError::TimedOut(_)), "{err}");
+        assert_eq!(
+            events.recv().await.unwrap(),
+            SynthesisEvent::Started { id: 1 }
+        );
+        assert_eq!(
+            events.recv().await.unwrap(),
+            SynthesisEvent::Cancelled {
+                id: 1,
+                reason: CancelReason::TimedOut
+            }
+        );
+
+        let task = tokio::spawn({
+            let engine = engine.clone();
+            async move { engine.synthesize("hello").await }
+        });
+        assert_eq!(
+            events.recv().await.unwrap(),
+            SynthesisEvent::Started { id: 2 }
+        );
+        assert!(engine.stop_synthesis());
+        let err = task.await.unwrap().unwrap_err();
+        assert!(
+            matches!(err, TtsError::Cancelled(CancelReason::Stopped)),
+            "{err}"
+        );
+        assert!(!engine.stop_synthesis());
+    }
+
+    #[test]
+    fn mute_guard_unmutes_on_drop() {
+        let flag = AtomicBool::new(false);
+        {
+            let _mute = MuteGuard::new(Some(&flag));
+            assert!(flag.load(Ordering::Relaxed));
+        }
+        assert!(!flag.load(Ordering::Relaxed));
+    }
+}
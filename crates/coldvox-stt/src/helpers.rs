@@ -202,7 +202,7 @@ impl EventEmitter {
         // Logging and metrics
         match &event {
             TranscriptionEvent::Partial { text, .. } => {
-                info!(target: "stt", "Partial: {}", text);
+                info!(target: "stt", "Partial: {}", coldvox_foundation::scrub::scrub(text));
                 let mut m = self.metrics.write();
                 m.partial_count += 1;
                 m.last_event_time = Some(Instant::now());
@@ -210,7 +210,7 @@ impl EventEmitter {
             }
             TranscriptionEvent::Final { text, words, .. } => {
                 let word_count = words.as_ref().map(|w| w.len()).unwrap_or(0);
-                info!(target: "stt", "Final: {} (words: {})", text, word_count);
+                info!(target: "stt", "Final: {} (words: {})", coldvox_foundation::scrub::scrub(text), word_count);
                 let mut m = self.metrics.write();
                 m.final_count += 1;
                 m.last_event_time = Some(Instant::now());
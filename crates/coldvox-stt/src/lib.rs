@@ -10,6 +10,8 @@ pub mod constants;
 // pub mod helpers; // TODO: Requires coldvox_telemetry dependency - move to app crate
 pub mod plugin;
 pub mod plugin_adapter; // new adapter implementing StreamingStt
+#[cfg(feature = "plugin-host")]
+pub mod plugin_host;
 pub mod plugin_types;
 pub mod plugins;
 pub mod processor; // legacy (EventBasedTranscriber-based) processor
@@ -18,14 +20,35 @@ pub mod types;
 pub use coldvox_foundation::error::ColdVoxError;
 pub use plugin::SttPlugin;
 pub use plugin_adapter::PluginAdapter; // adapter for plugin → StreamingStt
-pub use types::{TranscriptionConfig, TranscriptionEvent, WordInfo};
+pub use types::{DecodeResources, SourceId, TranscriptionConfig, TranscriptionEvent, WordInfo};
+
+/// Source of utterance IDs. The process-wide source backs
+/// [`next_utterance_id`]; replay runs own one that starts from a known ID.
+#[derive(Debug)]
+pub struct UtteranceIds {
+    next: AtomicU64,
+}
+
+impl UtteranceIds {
+    /// Source whose first ID is `first`
+    pub const fn new(first: u64) -> Self {
+        Self {
+            next: AtomicU64::new(first),
+        }
+    }
+
+    /// Take the next ID
+    pub fn next_id(&self) -> u64 {
+        self.next.fetch_add(1, Ordering::SeqCst)
+    }
+}
 
 /// Generates unique utterance IDs
-static UTTERANCE_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
+static UTTERANCE_IDS: UtteranceIds = UtteranceIds::new(1);
 
 /// Generate a unique utterance ID
 pub fn next_utterance_id() -> u64 {
-    UTTERANCE_ID_COUNTER.fetch_add(1, Ordering::SeqCst)
+    UTTERANCE_IDS.next_id()
 }
 
 /// Core transcription interface
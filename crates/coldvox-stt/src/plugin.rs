@@ -7,7 +7,7 @@ use async_trait::async_trait;
 use std::fmt::Debug;
 use std::path::Path;
 
-use crate::types::{TranscriptionConfig, TranscriptionEvent};
+use crate::types::{DecodeResources, TranscriptionConfig, TranscriptionEvent};
 use coldvox_foundation::error::{ColdVoxError, ConfigError, SttError};
 
 /// Metadata about an STT plugin
@@ -225,6 +225,10 @@ pub struct PluginSelectionConfig {
 
     /// Automatically extract model from a zip archive if not found
     pub auto_extract_model: bool,
+
+    /// CPU resources for local decoding
+    #[serde(default)]
+    pub decode: DecodeResources,
 }
 
 impl Default for PluginSelectionConfig {
@@ -239,6 +243,7 @@ impl Default for PluginSelectionConfig {
             gc_policy: Some(GcPolicy::default()),
             metrics: Some(MetricsConfig::default()),
             auto_extract_model: true,
+            decode: DecodeResources::default(),
         }
     }
 }
@@ -282,6 +287,15 @@ pub struct FailoverConfig {
 
     /// Cooldown period in seconds before retrying a failed plugin
     pub failover_cooldown_secs: u32,
+
+    /// Keep the first fallback plugin loaded and initialized so failover
+    /// swaps it in without loading a model
+    #[serde(default)]
+    pub warm_standby: bool,
+
+    /// Largest model (in MB) kept on standby; larger fallbacks load on demand
+    #[serde(default)]
+    pub standby_max_mem_mb: Option<u32>,
 }
 
 impl Default for FailoverConfig {
@@ -289,6 +303,8 @@ impl Default for FailoverConfig {
         Self {
             failover_threshold: 3,
             failover_cooldown_secs: 30,
+            warm_standby: false,
+            standby_max_mem_mb: None,
         }
     }
 }
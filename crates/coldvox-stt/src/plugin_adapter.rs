@@ -51,20 +51,24 @@ impl StreamingStt for PluginAdapter {
                     text,
                     t0,
                     t1,
+                    source,
                 } => TranscriptionEvent::Partial {
                     utterance_id: self.current_utterance_id,
                     text,
                     t0,
                     t1,
+                    source,
                 },
                 TranscriptionEvent::Final {
                     utterance_id: _,
                     text,
                     words,
+                    source,
                 } => TranscriptionEvent::Final {
                     utterance_id: self.current_utterance_id,
                     text,
                     words,
+                    source,
                 },
                 TranscriptionEvent::Error { code, message } => {
                     TranscriptionEvent::Error { code, message }
@@ -91,20 +95,24 @@ impl StreamingStt for PluginAdapter {
                         text,
                         t0,
                         t1,
+                        source,
                     } => TranscriptionEvent::Partial {
                         utterance_id: self.current_utterance_id,
                         text,
                         t0,
                         t1,
+                        source,
                     },
                     TranscriptionEvent::Final {
                         utterance_id: _,
                         text,
                         words,
+                        source,
                     } => TranscriptionEvent::Final {
                         utterance_id: self.current_utterance_id,
                         text,
                         words,
+                        source,
                     },
                     TranscriptionEvent::Error { code, message } => {
                         TranscriptionEvent::Error { code, message }
@@ -0,0 +1,670 @@
+//! Out-of-tree STT plugins
+//!
+//! Third-party engines ship as a program that ColdVox starts and talks to
+//! over JSON-RPC 2.0, one JSON object per line on the program's stdin and
+//! stdout (stderr is forwarded to the log). Each plugin lives in its own
+//! directory under [`default_plugins_dir`] with a `plugin.json` manifest:
+//!
+//! ```json
+//! {
+//!   "id": "my-engine",
+//!   "name": "My Engine",
+//!   "command": "./my-engine-stt",
+//!   "args": ["--model", "small"],
+//!   "languages": ["en"]
+//! }
+//! ```
+//!
+//! A relative `command` is resolved against the plugin directory; a bare name
+//! is looked up on `PATH`. The program is started when the plugin is
+//! initialized and must answer these methods (protocol version 1):
+//!
+//! | method          | params                                                    | result                    |
+//! |-----------------|-----------------------------------------------------------|---------------------------|
+//! | `initialize`    | `{"protocol_version":1,"sample_rate":16000,"partial_results":…,"include_words":…}` | `{"capabilities":{…}}` (optional fields of [`PluginCapabilities`]) |
+//! | `process_audio` | `{"pcm":[…]}`, 16-bit mono samples                        | event or `null`           |
+//! | `finalize`      | none                                                      | event or `null`           |
+//! | `reset`         | none                                                      | `null`                    |
+//!
+//! An event is `{"type":"partial","text":"…"}` or
+//! `{"type":"final","text":"…","words":[{"start":0.0,"end":0.4,"conf":0.9,"text":"…"}]}`
+//! (`words` optional). Errors are JSON-RPC error objects. On unload ColdVox
+//! sends a `shutdown` notification and closes stdin.
+
+use crate::plugin::{PluginCapabilities, PluginInfo, SttPlugin, SttPluginFactory};
+use crate::types::{SourceId, TranscriptionConfig, TranscriptionEvent, WordInfo};
+use async_trait::async_trait;
+use coldvox_foundation::error::{ColdVoxError, SttError};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+
+/// Protocol version sent in `initialize`
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Manifest file name inside a plugin directory
+pub const MANIFEST_FILE: &str = "plugin.json";
+
+/// `$XDG_DATA_HOME/coldvox/plugins` (usually `~/.local/share/coldvox/plugins`)
+pub fn default_plugins_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("coldvox").join("plugins"))
+}
+
+/// `plugin.json` of an out-of-tree plugin
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PluginManifest {
+    /// Plugin id used in `stt.preferred` and on the command line
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Program to start; relative paths are resolved against the plugin directory
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Supported languages (ISO 639-1)
+    #[serde(default)]
+    pub languages: Vec<String>,
+    /// Whether the engine sends audio off the machine
+    #[serde(default)]
+    pub requires_network: bool,
+    #[serde(default)]
+    pub memory_usage_mb: Option<u32>,
+    /// Time allowed for any one request
+    #[serde(default = "default_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+}
+
+fn default_request_timeout_ms() -> u64 {
+    10_000
+}
+
+impl PluginManifest {
+    /// Read and check `dir/plugin.json`
+    pub fn load(dir: &Path) -> Result<Self, ColdVoxError> {
+        let path = dir.join(MANIFEST_FILE);
+        let raw = std::fs::read_to_string(&path).map_err(|e| {
+            SttError::InvalidConfig(format!("cannot read {}: {}", path.display(), e))
+        })?;
+        let manifest: Self = serde_json::from_str(&raw)
+            .map_err(|e| SttError::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+        if manifest.id.trim().is_empty() || manifest.command.trim().is_empty() {
+            return Err(SttError::InvalidConfig(format!(
+                "{}: id and command must not be empty",
+                path.display()
+            ))
+            .into());
+        }
+        Ok(manifest)
+    }
+}
+
+/// Find the plugins in `dir`. Directories with a broken manifest are
+/// skipped with a warning; a missing `dir` yields nothing.
+pub fn discover_plugins(dir: &Path) -> Vec<ExternalPluginFactory> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| p.join(MANIFEST_FILE).is_file())
+        .collect();
+    dirs.sort();
+
+    let mut factories: Vec<ExternalPluginFactory> = Vec::new();
+    for plugin_dir in dirs {
+        match PluginManifest::load(&plugin_dir) {
+            Ok(manifest) if factories.iter().any(|f| f.manifest.id == manifest.id) => {
+                tracing::warn!(
+                    target: "coldvox::stt",
+                    "Skipping {}: plugin id '{}' is already taken",
+                    plugin_dir.display(),
+                    manifest.id
+                );
+            }
+            Ok(manifest) => {
+                tracing::info!(
+                    target: "coldvox::stt",
+                    plugin_id = %manifest.id,
+                    "Found external STT plugin in {}",
+                    plugin_dir.display()
+                );
+                factories.push(ExternalPluginFactory::new(manifest, plugin_dir));
+            }
+            Err(e) => tracing::warn!(target: "coldvox::stt", "Skipping external STT plugin: {}", e),
+        }
+    }
+    factories
+}
+
+/// The program to run for `manifest`, if it exists
+fn resolve_command(manifest: &PluginManifest, dir: &Path) -> Option<PathBuf> {
+    let command = Path::new(&manifest.command);
+    if command.components().count() > 1 || command.is_absolute() {
+        let path = dir.join(command);
+        return path.is_file().then_some(path);
+    }
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|p| p.join(command))
+            .find(|p| p.is_file())
+    })
+}
+
+fn transcription_failed(msg: impl Into<String>) -> ColdVoxError {
+    SttError::TranscriptionFailed(msg.into()).into()
+}
+
+/// A running plugin program
+struct PluginProcess {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+    next_id: u64,
+}
+
+impl PluginProcess {
+    fn spawn(program: &Path, manifest: &PluginManifest, dir: &Path) -> Result<Self, ColdVoxError> {
+        let mut child = Command::new(program)
+            .args(&manifest.args)
+            .current_dir(dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                SttError::LoadFailed(format!("cannot start {}: {}", program.display(), e))
+            })?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(SttError::LoadFailed("plugin stdio unavailable".to_string()).into());
+        };
+        if let Some(stderr) = child.stderr.take() {
+            let plugin_id = manifest.id.clone();
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    tracing::debug!(target: "coldvox::stt", plugin_id = %plugin_id, "{}", line);
+                }
+            });
+        }
+        Ok(Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout).lines(),
+            next_id: 1,
+        })
+    }
+
+    async fn send_line(&mut self, message: &Value) -> Result<(), ColdVoxError> {
+        let mut line = message.to_string();
+        line.push('\n');
+        self.stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| transcription_failed(format!("plugin stdin closed: {e}")))
+    }
+
+    /// Send a request and wait for its response
+    async fn call(
+        &mut self,
+        method: &str,
+        params: Value,
+        timeout: Duration,
+    ) -> Result<Value, ColdVoxError> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send_line(&json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}))
+            .await?;
+
+        let response = async {
+            loop {
+                let line = self
+                    .stdout
+                    .next_line()
+                    .await
+                    .map_err(|e| transcription_failed(format!("plugin stdout failed: {e}")))?
+                    .ok_or_else(|| transcription_failed("plugin exited"))?;
+                let Ok(message) = serde_json::from_str::<Value>(&line) else {
+                    tracing::debug!(target: "coldvox::stt", "Ignoring non-JSON plugin output: {}", line);
+                    continue;
+                };
+                if message.get("id").and_then(Value::as_u64) == Some(id) {
+                    return Ok::<Value, ColdVoxError>(message);
+                }
+            }
+        };
+        let message = tokio::time::timeout(timeout, response)
+            .await
+            .map_err(|_| {
+                transcription_failed(format!(
+                    "plugin did not answer '{}' within {}ms",
+                    method,
+                    timeout.as_millis()
+                ))
+            })??;
+
+        if let Some(error) = message.get("error") {
+            let text = error
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            return Err(transcription_failed(format!(
+                "plugin error in '{method}': {text}"
+            )));
+        }
+        Ok(message.get("result").cloned().unwrap_or(Value::Null))
+    }
+
+    async fn shutdown(mut self) {
+        let _ = self
+            .send_line(&json!({"jsonrpc": "2.0", "method": "shutdown"}))
+            .await;
+        drop(self.stdin);
+        if tokio::time::timeout(Duration::from_secs(2), self.child.wait())
+            .await
+            .is_err()
+        {
+            let _ = self.child.kill().await;
+        }
+    }
+}
+
+/// Wire form of an event in a `process_audio`/`finalize` result
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum WireEvent {
+    Partial {
+        text: String,
+    },
+    Final {
+        text: String,
+        #[serde(default)]
+        words: Option<Vec<WireWord>>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct WireWord {
+    start: f32,
+    end: f32,
+    #[serde(default)]
+    conf: f32,
+    text: String,
+}
+
+/// Capabilities a plugin reports from `initialize`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct WireCapabilities {
+    streaming: bool,
+    batch: bool,
+    word_timestamps: bool,
+    confidence_scores: bool,
+    speaker_diarization: bool,
+    auto_punctuation: bool,
+    custom_vocabulary: bool,
+}
+
+impl From<WireCapabilities> for PluginCapabilities {
+    fn from(c: WireCapabilities) -> Self {
+        Self {
+            streaming: c.streaming,
+            batch: c.batch,
+            word_timestamps: c.word_timestamps,
+            confidence_scores: c.confidence_scores,
+            speaker_diarization: c.speaker_diarization,
+            auto_punctuation: c.auto_punctuation,
+            custom_vocabulary: c.custom_vocabulary,
+        }
+    }
+}
+
+/// Turn a result into an event of utterance `utterance_id`
+fn parse_event(
+    result: Value,
+    utterance_id: u64,
+) -> Result<Option<TranscriptionEvent>, ColdVoxError> {
+    if result.is_null() {
+        return Ok(None);
+    }
+    let event: WireEvent = serde_json::from_value(result)
+        .map_err(|e| transcription_failed(format!("malformed plugin event: {e}")))?;
+    Ok(Some(match event {
+        WireEvent::Partial { text } => TranscriptionEvent::Partial {
+            utterance_id,
+            text,
+            t0: None,
+            t1: None,
+            source: SourceId::default(),
+        },
+        WireEvent::Final { text, words } => TranscriptionEvent::Final {
+            utterance_id,
+            text,
+            words: words.map(|words| {
+                words
+                    .into_iter()
+                    .map(|w| WordInfo {
+                        start: w.start,
+                        end: w.end,
+                        conf: w.conf,
+                        text: w.text,
+                    })
+                    .collect()
+            }),
+            source: SourceId::default(),
+        },
+    }))
+}
+
+fn plugin_info_from_manifest(manifest: &PluginManifest) -> PluginInfo {
+    PluginInfo {
+        id: manifest.id.clone(),
+        name: manifest.name.clone(),
+        description: if manifest.description.is_empty() {
+            "External STT plugin".to_string()
+        } else {
+            manifest.description.clone()
+        },
+        requires_network: manifest.requires_network,
+        is_local: !manifest.requires_network,
+        is_available: false,
+        supported_languages: manifest.languages.clone(),
+        memory_usage_mb: manifest.memory_usage_mb,
+    }
+}
+
+/// STT plugin backed by an out-of-tree program
+pub struct ExternalPlugin {
+    manifest: PluginManifest,
+    dir: PathBuf,
+    process: Option<PluginProcess>,
+    config: TranscriptionConfig,
+    capabilities: PluginCapabilities,
+    utterance_id: u64,
+}
+
+impl Debug for ExternalPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExternalPlugin")
+            .field("id", &self.manifest.id)
+            .field("dir", &self.dir)
+            .field("running", &self.process.is_some())
+            .finish()
+    }
+}
+
+impl ExternalPlugin {
+    pub fn new(manifest: PluginManifest, dir: PathBuf) -> Self {
+        Self {
+            manifest,
+            dir,
+            process: None,
+            config: TranscriptionConfig::default(),
+            capabilities: PluginCapabilities::default(),
+            utterance_id: 1,
+        }
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(self.manifest.request_timeout_ms)
+    }
+
+    /// Start the program and send `initialize`
+    async fn start(&mut self) -> Result<(), ColdVoxError> {
+        let program = resolve_command(&self.manifest, &self.dir).ok_or_else(|| {
+            ColdVoxError::from(SttError::NotAvailable {
+                plugin: self.manifest.id.clone(),
+                reason: format!("command '{}' not found", self.manifest.command),
+            })
+        })?;
+        let mut process = PluginProcess::spawn(&program, &self.manifest, &self.dir)?;
+        let params = json!({
+            "protocol_version": PROTOCOL_VERSION,
+            "sample_rate": 16_000,
+            "partial_results": self.config.partial_results,
+            "include_words": self.config.include_words,
+        });
+        let result = process.call("initialize", params, self.timeout()).await?;
+        let capabilities: WireCapabilities = result
+            .get("capabilities")
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| SttError::LoadFailed(format!("malformed capabilities: {e}")))?
+            .unwrap_or_default();
+        self.capabilities = capabilities.into();
+        tracing::info!(
+            target: "coldvox::stt",
+            plugin_id = %self.manifest.id,
+            "External STT plugin started"
+        );
+        self.process = Some(process);
+        Ok(())
+    }
+
+    /// Call `method`, restarting the program first if it is not running.
+    /// A failed call stops the program so the next one starts it afresh.
+    async fn call(&mut self, method: &str, params: Value) -> Result<Value, ColdVoxError> {
+        if self.process.is_none() {
+            self.start().await?;
+        }
+        let timeout = self.timeout();
+        let process = self.process.as_mut().expect("started above");
+        let result = process.call(method, params, timeout).await;
+        if result.is_err() {
+            if let Some(process) = self.process.take() {
+                process.shutdown().await;
+            }
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl SttPlugin for ExternalPlugin {
+    fn info(&self) -> PluginInfo {
+        let mut info = plugin_info_from_manifest(&self.manifest);
+        info.is_available = resolve_command(&self.manifest, &self.dir).is_some();
+        info
+    }
+
+    fn capabilities(&self) -> PluginCapabilities {
+        self.capabilities.clone()
+    }
+
+    async fn is_available(&self) -> Result<bool, ColdVoxError> {
+        Ok(resolve_command(&self.manifest, &self.dir).is_some())
+    }
+
+    async fn initialize(&mut self, config: TranscriptionConfig) -> Result<(), ColdVoxError> {
+        self.config = config;
+        if let Some(process) = self.process.take() {
+            process.shutdown().await;
+        }
+        self.start().await
+    }
+
+    async fn process_audio(
+        &mut self,
+        samples: &[i16],
+    ) -> Result<Option<TranscriptionEvent>, ColdVoxError> {
+        let result = self
+            .call("process_audio", json!({ "pcm": samples }))
+            .await?;
+        let event = parse_event(result, self.utterance_id)?;
+        Ok(event.filter(|e| {
+            self.config.partial_results || !matches!(e, TranscriptionEvent::Partial { .. })
+        }))
+    }
+
+    async fn finalize(&mut self) -> Result<Option<TranscriptionEvent>, ColdVoxError> {
+        let result = self.call("finalize", Value::Null).await?;
+        parse_event(result, self.utterance_id)
+    }
+
+    async fn reset(&mut self) -> Result<(), ColdVoxError> {
+        self.utterance_id += 1;
+        if self.process.is_some() {
+            self.call("reset", Value::Null).await?;
+        }
+        Ok(())
+    }
+
+    async fn unload(&mut self) -> Result<(), ColdVoxError> {
+        if let Some(process) = self.process.take() {
+            process.shutdown().await;
+        }
+        Ok(())
+    }
+}
+
+/// Factory for an out-of-tree plugin found by [`discover_plugins`]
+pub struct ExternalPluginFactory {
+    manifest: PluginManifest,
+    dir: PathBuf,
+}
+
+impl ExternalPluginFactory {
+    pub fn new(manifest: PluginManifest, dir: PathBuf) -> Self {
+        Self { manifest, dir }
+    }
+
+    pub fn manifest(&self) -> &PluginManifest {
+        &self.manifest
+    }
+}
+
+impl SttPluginFactory for ExternalPluginFactory {
+    fn create(&self) -> Result<Box<dyn SttPlugin>, ColdVoxError> {
+        Ok(Box::new(ExternalPlugin::new(
+            self.manifest.clone(),
+            self.dir.clone(),
+        )))
+    }
+
+    fn plugin_info(&self) -> PluginInfo {
+        plugin_info_from_manifest(&self.manifest)
+    }
+
+    fn check_requirements(&self) -> Result<(), ColdVoxError> {
+        match resolve_command(&self.manifest, &self.dir) {
+            Some(_) => Ok(()),
+            None => Err(SttError::NotAvailable {
+                plugin: self.manifest.id.clone(),
+                reason: format!("command '{}' not found", self.manifest.command),
+            }
+            .into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovery_skips_broken_and_duplicate_manifests() {
+        let root = tempfile::tempdir().unwrap();
+        let write = |name: &str, manifest: &str| {
+            let dir = root.path().join(name);
+            std::fs::create_dir(&dir).unwrap();
+            std::fs::write(dir.join(MANIFEST_FILE), manifest).unwrap();
+        };
+        write(
+            "a-engine",
+            r#"{"id":"engine","name":"Engine","command":"./run"}"#,
+        );
+        write(
+            "b-copy",
+            r#"{"id":"engine","name":"Copy","command":"./run"}"#,
+        );
+        write("c-broken", r#"{"id":"broken""#);
+        std::fs::create_dir(root.path().join("d-no-manifest")).unwrap();
+
+        let found = discover_plugins(root.path());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].manifest().name, "Engine");
+        assert_eq!(found[0].manifest().request_timeout_ms, 10_000);
+        // ./run does not exist
+        assert!(found[0].check_requirements().is_err());
+
+        assert!(discover_plugins(&root.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn results_map_to_events() {
+        assert!(parse_event(Value::Null, 3).unwrap().is_none());
+        let event = parse_event(
+            json!({"type":"final","text":"hi","words":[{"start":0.0,"end":0.3,"conf":0.8,"text":"hi"}]}),
+            3,
+        )
+        .unwrap();
+        assert!(matches!(
+            event,
+            Some(TranscriptionEvent::Final { utterance_id: 3, ref text, words: Some(ref w), .. })
+                if text == "hi" && w.len() == 1
+        ));
+        assert!(parse_event(json!({"type":"bogus"}), 3).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn plugin_program_speaks_json_rpc() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("engine.sh");
+        std::fs::write(
+            &script,
+            r#"#!/bin/sh
+while read -r line; do
+  id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9]*\).*/\1/p')
+  [ -z "$id" ] && continue
+  case "$line" in
+    *'"finalize"'*) printf '{"jsonrpc":"2.0","id":%s,"result":{"type":"final","text":"hello"}}\n' "$id" ;;
+    *'"initialize"'*) printf '{"jsonrpc":"2.0","id":%s,"result":{"capabilities":{"batch":true}}}\n' "$id" ;;
+    *) printf '{"jsonrpc":"2.0","id":%s,"result":null}\n' "$id" ;;
+  esac
+done
+"#,
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let manifest = PluginManifest {
+            id: "sh-engine".to_string(),
+            name: "Shell Engine".to_string(),
+            description: String::new(),
+            command: "./engine.sh".to_string(),
+            args: Vec::new(),
+            languages: Vec::new(),
+            requires_network: false,
+            memory_usage_mb: None,
+            request_timeout_ms: 5_000,
+        };
+        let factory = ExternalPluginFactory::new(manifest, dir.path().to_path_buf());
+        factory.check_requirements().unwrap();
+        let mut plugin = factory.create().unwrap();
+
+        plugin
+            .initialize(TranscriptionConfig::default())
+            .await
+            .unwrap();
+        assert!(plugin.capabilities().batch);
+        assert!(plugin.process_audio(&[0i16; 320]).await.unwrap().is_none());
+        let event = plugin.finalize().await.unwrap();
+        assert!(matches!(
+            event,
+            Some(TranscriptionEvent::Final { ref text, .. }) if text == "hello"
+        ));
+        plugin.reset().await.unwrap();
+        plugin.unload().await.unwrap();
+    }
+}
@@ -4,7 +4,7 @@
 //! Buffers PCM frames during speech, encodes to WAV on finalize, and POSTs to the service.
 
 use crate::plugin::{PluginCapabilities, PluginInfo, SttPlugin, SttPluginFactory};
-use crate::types::{TranscriptionConfig, TranscriptionEvent};
+use crate::types::{SourceId, TranscriptionConfig, TranscriptionEvent};
 use async_trait::async_trait;
 use coldvox_foundation::error::{ColdVoxError, SttError};
 use reqwest::{multipart, Client, Url};
@@ -566,6 +566,7 @@ impl SttPlugin for HttpRemotePlugin {
             utterance_id: self.utterance_id,
             text: stt_res.text,
             words: None,
+            source: SourceId::default(),
         };
 
         self.audio_buffer.clear();
@@ -884,6 +885,7 @@ mod tests {
                 utterance_id,
                 text,
                 words,
+                ..
             }) => {
                 assert_eq!(utterance_id, 0);
                 assert_eq!(text, "stub transcript");
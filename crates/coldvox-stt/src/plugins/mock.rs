@@ -1,7 +1,7 @@
 //! Mock STT plugin for testing
 
 use crate::plugin::*;
-use crate::types::{TranscriptionConfig, TranscriptionEvent, WordInfo};
+use crate::types::{SourceId, TranscriptionConfig, TranscriptionEvent, WordInfo};
 use async_trait::async_trait;
 use std::sync::{Arc, Mutex};
 use tracing::info;
@@ -233,6 +233,7 @@ fn create_mock_event(text: String) -> TranscriptionEvent {
         utterance_id: crate::next_utterance_id(),
         text,
         words: Some(words),
+        source: SourceId::default(),
     }
 }
 
@@ -14,6 +14,9 @@ pub mod moonshine;
 #[cfg(feature = "http-remote")]
 pub mod http_remote;
 
+#[cfg(feature = "ws-remote")]
+pub mod ws_remote;
+
 // Re-export commonly used plugins
 pub use mock::MockPlugin;
 pub use noop::NoOpPlugin;
@@ -24,3 +27,6 @@ pub use parakeet::ParakeetPluginFactory;
 
 #[cfg(feature = "moonshine")]
 pub use moonshine::{MoonshineModelSize, MoonshinePlugin, MoonshinePluginFactory};
+
+#[cfg(feature = "ws-remote")]
+pub use ws_remote::{WsRemoteConfig, WsRemotePluginFactory};
@@ -9,7 +9,7 @@
 
 use crate::constants::SAMPLE_RATE_HZ;
 use crate::plugin::*;
-use crate::types::{TranscriptionConfig, TranscriptionEvent};
+use crate::types::{SourceId, TranscriptionConfig, TranscriptionEvent};
 use async_trait::async_trait;
 use coldvox_foundation::error::{ColdVoxError, SttError};
 use std::env;
@@ -162,7 +162,10 @@ impl MoonshinePlugin {
     /// This is called once during initialize() to avoid the 5-10 second
     /// model loading delay on every transcription.
     #[cfg(feature = "moonshine")]
-    fn load_model_and_processor(&mut self) -> Result<(), ColdVoxError> {
+    fn load_model_and_processor(
+        &mut self,
+        decode: &crate::types::DecodeResources,
+    ) -> Result<(), ColdVoxError> {
         // Use custom model path if provided, otherwise use HuggingFace model identifier
         let model_id = self
             .model_path
@@ -175,6 +178,10 @@ impl MoonshinePlugin {
             locals
                 .set_item("model_id", model_id)
                 .map_err(|e| SttError::LoadFailed(format!("Failed to set model_id: {}", e)))?;
+            let threads = decode.effective_threads();
+            locals
+                .set_item("num_threads", threads)
+                .map_err(|e| SttError::LoadFailed(format!("Failed to set num_threads: {}", e)))?;
 
             // Load model and processor using safe variable passing
             // NOTE: Must use run_bound (not eval_bound) because this contains statements
@@ -184,6 +191,7 @@ from transformers import AutoModelForSpeechSeq2Seq, AutoProcessor
 
 device = "cpu"
 torch_dtype = torch.float32
+torch.set_num_threads(num_threads)
 
 _model = AutoModelForSpeechSeq2Seq.from_pretrained(
     model_id,
@@ -216,6 +224,7 @@ _processor = AutoProcessor.from_pretrained(model_id)
             info!(
                 target: "coldvox::stt::moonshine",
                 model = %self.model_size.model_identifier(),
+                threads,
                 "Model and processor cached successfully"
             );
 
@@ -257,11 +266,34 @@ _processor = AutoProcessor.from_pretrained(model_id)
                 SttError::TranscriptionFailed(format!("Failed to set audio_path: {}", e))
             })?;
 
+            let decode = self
+                .active_config
+                .as_ref()
+                .map(|c| c.decode.clone())
+                .unwrap_or_default();
+            locals
+                .set_item("cpu_affinity", decode.cpu_affinity)
+                .and_then(|_| locals.set_item("niceness", decode.niceness))
+                .map_err(|e| {
+                    SttError::TranscriptionFailed(format!("Failed to set decode resources: {}", e))
+                })?;
+
             // NOTE: Must use run_bound (not eval_bound) because this contains statements
             let transcribe_code = r#"
+import os
 import torch
 import librosa
 
+# Pin/renice the decoding thread (pid 0 = calling thread on Linux). Best
+# effort: raising priority without CAP_SYS_NICE must not fail the decode.
+try:
+    if cpu_affinity and hasattr(os, "sched_setaffinity"):
+        os.sched_setaffinity(0, cpu_affinity)
+    if niceness is not None and hasattr(os, "setpriority"):
+        os.setpriority(os.PRIO_PROCESS, 0, niceness)
+except OSError:
+    pass
+
 # Load audio using the safely-passed path variable
 audio_array, sampling_rate = librosa.load(audio_path, sr=16000, mono=True)
 
@@ -383,7 +415,7 @@ impl SttPlugin for MoonshinePlugin {
 
             // Load and cache model + processor (this takes 5-10 seconds on first run)
             // Subsequent transcriptions will reuse the cached model
-            self.load_model_and_processor()?;
+            self.load_model_and_processor(&config.decode)?;
 
             self.audio_buffer.clear();
             self.active_config = Some(config);
@@ -474,12 +506,13 @@ impl SttPlugin for MoonshinePlugin {
 
             self.audio_buffer.clear();
 
-            debug!(target: "coldvox::stt::moonshine", text = %text, "Transcription complete");
+            debug!(target: "coldvox::stt::moonshine", text = %coldvox_foundation::scrub::scrub(&text), "Transcription complete");
 
             Ok(Some(TranscriptionEvent::Final {
                 utterance_id: 0,
                 text,
                 words: None,
+                source: SourceId::default(),
             }))
         }
 
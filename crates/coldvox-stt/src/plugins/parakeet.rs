@@ -23,7 +23,7 @@
 //! - `PARAKEET_DEVICE`: Must be "cuda" or "tensorrt" (CPU not supported)
 
 use crate::plugin::*;
-use crate::types::{TranscriptionConfig, TranscriptionEvent, WordInfo};
+use crate::types::{SourceId, TranscriptionConfig, TranscriptionEvent, WordInfo};
 use async_trait::async_trait;
 use coldvox_foundation::error::{ColdVoxError, SttError};
 use std::env;
@@ -557,7 +557,7 @@ impl SttPlugin for ParakeetPlugin {
 
             debug!(
                 target: "coldvox::stt::parakeet",
-                text = %text,
+                text = %coldvox_foundation::scrub::scrub(&text),
                 token_count = result.tokens.len(),
                 "Parakeet transcription complete"
             );
@@ -588,6 +588,7 @@ impl SttPlugin for ParakeetPlugin {
                 utterance_id: 0,
                 text,
                 words,
+                source: SourceId::default(),
             }))
         }
 
@@ -0,0 +1,636 @@
+//! WebSocket Remote STT Plugin
+//!
+//! Streams PCM to a transcription server while the user speaks, so a home
+//! server or cloud endpoint does the decoding and only the tail of the
+//! utterance is left to transcribe when it ends. Each utterance gets its own
+//! connection, opened on the first audio frame:
+//!
+//! 1. client → `{"type":"start","sample_rate":16000,"model":"…","language":"en"}`
+//! 2. client → binary frames of 16-bit little-endian mono PCM
+//! 3. server → `{"type":"partial","text":"…"}` (optional, at any time)
+//! 4. client → `{"type":"end"}`
+//! 5. server → `{"type":"final","text":"…"}` or `{"type":"error","message":"…"}`
+//!
+//! Servers that send bare `{"text":"…"}` messages work too: `"is_final": true`
+//! marks the final transcript, and otherwise the last text before the server
+//! closes the connection is used.
+
+use crate::plugin::{PluginCapabilities, PluginInfo, SttPlugin, SttPluginFactory};
+use crate::types::{SourceId, TranscriptionConfig, TranscriptionEvent};
+use async_trait::async_trait;
+use coldvox_foundation::error::{ColdVoxError, SttError};
+use coldvox_telemetry::SttPerformanceMetrics;
+use futures_util::{FutureExt, SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+const PLUGIN_ID: &str = "ws-remote";
+
+/// Configuration for the WebSocket remote plugin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WsRemoteConfig {
+    /// Streaming endpoint, `ws://` or `wss://` (e.g., "ws://homeserver:8765/v1/stream")
+    pub url: String,
+    /// Model name sent in the start message
+    pub model_name: String,
+    /// Language sent in the start message (ISO 639-1); None lets the server decide
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Display name for logging/UI
+    pub display_name: String,
+    /// Connect and handshake timeout in milliseconds
+    pub connect_timeout_ms: u64,
+    /// Time allowed for the final transcript after the end of an utterance
+    pub final_timeout_ms: u64,
+    /// Sample rate of the audio being sent (typically 16000)
+    pub sample_rate: u32,
+    /// Optional extra handshake headers
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Optional bearer-token environment variable name
+    #[serde(default)]
+    pub bearer_token_env_var: Option<String>,
+    /// Send the token as this URL query parameter instead of an
+    /// `Authorization` header, for servers that only read the URL
+    #[serde(default)]
+    pub token_query_param: Option<String>,
+}
+
+impl Default for WsRemoteConfig {
+    fn default() -> Self {
+        Self {
+            url: "ws://localhost:8765/v1/stream".into(),
+            model_name: "whisper-small".into(),
+            language: None,
+            display_name: "Remote (WebSocket)".into(),
+            connect_timeout_ms: 3_000,
+            final_timeout_ms: 10_000,
+            sample_rate: 16_000,
+            headers: HashMap::new(),
+            bearer_token_env_var: None,
+            token_query_param: None,
+        }
+    }
+}
+
+/// Start-of-utterance message.
+#[derive(Debug, Serialize)]
+struct StartMessage<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    sample_rate: u32,
+    model: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<&'a str>,
+}
+
+/// Message from the server; only the fields ColdVox understands.
+#[derive(Debug, Default, Deserialize)]
+struct ServerMessage {
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    is_final: bool,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// What a server message means for the utterance.
+#[derive(Debug, PartialEq)]
+enum Reply {
+    Partial(String),
+    Final(String),
+    Error(String),
+    Ignore,
+}
+
+impl ServerMessage {
+    fn reply(self) -> Reply {
+        match self.kind.as_deref() {
+            Some("error") => Reply::Error(
+                self.message
+                    .or(self.text)
+                    .unwrap_or_else(|| "unspecified server error".to_string()),
+            ),
+            Some("final") => Reply::Final(self.text.unwrap_or_default()),
+            _ => match self.text {
+                Some(text) if self.is_final => Reply::Final(text),
+                Some(text) => Reply::Partial(text),
+                None => Reply::Ignore,
+            },
+        }
+    }
+}
+
+fn parse_reply(text: &str) -> Reply {
+    match serde_json::from_str::<ServerMessage>(text) {
+        Ok(message) => message.reply(),
+        Err(e) => {
+            tracing::debug!(target: "coldvox::stt", "Ignoring unparseable ws-remote message: {e}");
+            Reply::Ignore
+        }
+    }
+}
+
+fn transcription_failed(message: impl Into<String>) -> ColdVoxError {
+    SttError::TranscriptionFailed(message.into()).into()
+}
+
+fn is_local_url(url: &str) -> bool {
+    url.into_client_request()
+        .ok()
+        .and_then(|request| request.uri().host().map(str::to_owned))
+        .is_some_and(|host| {
+            host.eq_ignore_ascii_case("localhost")
+                || host
+                    .trim_matches(['[', ']'])
+                    .parse::<IpAddr>()
+                    .map(|addr| addr.is_loopback())
+                    .unwrap_or(false)
+        })
+}
+
+fn plugin_info_from_config(config: &WsRemoteConfig) -> PluginInfo {
+    PluginInfo {
+        id: PLUGIN_ID.to_string(),
+        name: config.display_name.clone(),
+        description: format!(
+            "Stream audio over WebSocket ({}, model {})",
+            config.url, config.model_name
+        ),
+        requires_network: true,
+        is_local: is_local_url(&config.url),
+        is_available: true,
+        supported_languages: config.language.iter().cloned().collect(),
+        memory_usage_mb: Some(10),
+    }
+}
+
+pub struct WsRemotePlugin {
+    config: WsRemoteConfig,
+    socket: Option<Socket>,
+    utterance_id: u64,
+    /// Latest partial of the current utterance
+    last_text: String,
+    partial_results: bool,
+    metrics: Arc<SttPerformanceMetrics>,
+}
+
+impl Debug for WsRemotePlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WsRemotePlugin")
+            .field("config", &self.config)
+            .field("connected", &self.socket.is_some())
+            .field("utterance_id", &self.utterance_id)
+            .finish()
+    }
+}
+
+impl WsRemotePlugin {
+    pub fn new(config: WsRemoteConfig) -> Self {
+        Self::with_metrics(config, Arc::new(SttPerformanceMetrics::new()))
+    }
+
+    /// Plugin recording connect time and end-of-utterance round trips in `metrics`
+    pub fn with_metrics(config: WsRemoteConfig, metrics: Arc<SttPerformanceMetrics>) -> Self {
+        Self {
+            config,
+            socket: None,
+            utterance_id: 0,
+            last_text: String::new(),
+            partial_results: true,
+            metrics,
+        }
+    }
+
+    fn handshake_request(
+        &self,
+    ) -> Result<tokio_tungstenite::tungstenite::handshake::client::Request, ColdVoxError> {
+        let token = match &self.config.bearer_token_env_var {
+            Some(env_var_name) => Some(std::env::var(env_var_name).map_err(|_| {
+                transcription_failed(format!(
+                    "Configured bearer token env var '{}' is not set",
+                    env_var_name
+                ))
+            })?),
+            None => None,
+        };
+
+        let mut url = self.config.url.clone();
+        if let (Some(token), Some(param)) = (&token, &self.config.token_query_param) {
+            let separator = if url.contains('?') { '&' } else { '?' };
+            url = format!("{url}{separator}{param}={token}");
+        }
+
+        let mut request = url.into_client_request().map_err(|e| {
+            SttError::InvalidConfig(format!("Invalid ws-remote URL '{}': {e}", self.config.url))
+        })?;
+        let headers = request.headers_mut();
+        for (name, value) in &self.config.headers {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                SttError::InvalidConfig(format!("Invalid ws-remote header '{name}': {e}"))
+            })?;
+            let value = HeaderValue::from_str(value).map_err(|e| {
+                SttError::InvalidConfig(format!("Invalid value for ws-remote header '{name}': {e}"))
+            })?;
+            headers.insert(name, value);
+        }
+        if let (Some(token), None) = (&token, &self.config.token_query_param) {
+            let value = HeaderValue::from_str(&format!("Bearer {token}")).map_err(|_| {
+                SttError::InvalidConfig("Bearer token is not a valid header value".to_string())
+            })?;
+            headers.insert(
+                tokio_tungstenite::tungstenite::http::header::AUTHORIZATION,
+                value,
+            );
+        }
+        Ok(request)
+    }
+
+    async fn connect(&self) -> Result<Socket, ColdVoxError> {
+        let request = self.handshake_request()?;
+        let timeout = Duration::from_millis(self.config.connect_timeout_ms);
+        let started = Instant::now();
+        let (socket, _) = tokio::time::timeout(timeout, tokio_tungstenite::connect_async(request))
+            .await
+            .map_err(|_| {
+                transcription_failed(format!(
+                    "ws-remote connect to {} timed out after {}ms",
+                    self.config.url, self.config.connect_timeout_ms
+                ))
+            })?
+            .map_err(|e| {
+                transcription_failed(format!(
+                    "ws-remote connect to {} failed: {e}",
+                    self.config.url
+                ))
+            })?;
+        self.metrics.record_preprocessing_latency(started.elapsed());
+        tracing::debug!(
+            target: "coldvox::stt",
+            connect_ms = started.elapsed().as_millis() as u64,
+            "Connected to {}",
+            self.config.url
+        );
+        Ok(socket)
+    }
+
+    /// Open the utterance's connection and announce the stream
+    async fn start_utterance(&mut self) -> Result<&mut Socket, ColdVoxError> {
+        if self.socket.is_none() {
+            let mut socket = self.connect().await?;
+            let start = StartMessage {
+                kind: "start",
+                sample_rate: self.config.sample_rate,
+                model: &self.config.model_name,
+                language: self.config.language.as_deref(),
+            };
+            let start = serde_json::to_string(&start).map_err(|e| {
+                transcription_failed(format!("Failed to encode start message: {e}"))
+            })?;
+            socket
+                .send(Message::text(start))
+                .await
+                .map_err(|e| transcription_failed(format!("ws-remote send failed: {e}")))?;
+            self.metrics.increment_requests();
+            self.socket = Some(socket);
+        }
+        Ok(self.socket.as_mut().expect("socket was just opened"))
+    }
+
+    fn final_event(&mut self, text: String) -> TranscriptionEvent {
+        self.metrics.record_final_transcription();
+        self.metrics.record_transcription_success();
+        let event = TranscriptionEvent::Final {
+            utterance_id: self.utterance_id,
+            text,
+            words: None,
+            source: SourceId::default(),
+        };
+        self.utterance_id += 1;
+        self.last_text.clear();
+        event
+    }
+
+    async fn close(&mut self) {
+        if let Some(mut socket) = self.socket.take() {
+            let _ = socket.close(None).await;
+        }
+    }
+
+    /// Drop the connection after a failure and report it
+    async fn fail(&mut self, error: ColdVoxError) -> ColdVoxError {
+        self.metrics.record_transcription_failure();
+        self.metrics.record_error();
+        self.close().await;
+        self.last_text.clear();
+        error
+    }
+}
+
+#[async_trait]
+impl SttPlugin for WsRemotePlugin {
+    fn info(&self) -> PluginInfo {
+        plugin_info_from_config(&self.config)
+    }
+
+    fn capabilities(&self) -> PluginCapabilities {
+        PluginCapabilities {
+            streaming: true,
+            batch: false,
+            word_timestamps: false,
+            confidence_scores: false,
+            speaker_diarization: false,
+            auto_punctuation: true,
+            custom_vocabulary: false,
+        }
+    }
+
+    async fn is_available(&self) -> Result<bool, ColdVoxError> {
+        match self.connect().await {
+            Ok(mut socket) => {
+                let _ = socket.close(None).await;
+                Ok(true)
+            }
+            Err(_) => Ok(false),
+        }
+    }
+
+    async fn initialize(&mut self, config: TranscriptionConfig) -> Result<(), ColdVoxError> {
+        self.partial_results = config.partial_results;
+        self.close().await;
+        self.last_text.clear();
+        Ok(())
+    }
+
+    async fn process_audio(
+        &mut self,
+        samples: &[i16],
+    ) -> Result<Option<TranscriptionEvent>, ColdVoxError> {
+        let pcm: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let sent = match self.start_utterance().await {
+            Ok(socket) => socket
+                .send(Message::binary(pcm))
+                .await
+                .map_err(|e| transcription_failed(format!("ws-remote send failed: {e}"))),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = sent {
+            return Err(self.fail(e).await);
+        }
+
+        // Pick up whatever the server has sent so far without waiting
+        let mut partial = None;
+        while let Some(next) = self
+            .socket
+            .as_mut()
+            .and_then(|socket| socket.next().now_or_never())
+        {
+            let reply = match next {
+                Some(Ok(Message::Text(text))) => parse_reply(&text),
+                Some(Ok(_)) => Reply::Ignore,
+                Some(Err(e)) => {
+                    let e = transcription_failed(format!("ws-remote receive failed: {e}"));
+                    return Err(self.fail(e).await);
+                }
+                None => {
+                    let e =
+                        transcription_failed("ws-remote server closed the stream mid-utterance");
+                    return Err(self.fail(e).await);
+                }
+            };
+            match reply {
+                Reply::Partial(text) => {
+                    self.last_text = text;
+                    partial = Some(self.last_text.clone());
+                }
+                // Server-side endpointing: the segment is done, the stream goes on
+                Reply::Final(text) => return Ok(Some(self.final_event(text))),
+                Reply::Error(message) => {
+                    let e = transcription_failed(format!("ws-remote server error: {message}"));
+                    return Err(self.fail(e).await);
+                }
+                Reply::Ignore => {}
+            }
+        }
+
+        Ok(partial.filter(|_| self.partial_results).map(|text| {
+            self.metrics.record_partial_transcription();
+            TranscriptionEvent::Partial {
+                utterance_id: self.utterance_id,
+                text,
+                t0: None,
+                t1: None,
+                source: SourceId::default(),
+            }
+        }))
+    }
+
+    async fn finalize(&mut self) -> Result<Option<TranscriptionEvent>, ColdVoxError> {
+        let Some(socket) = self.socket.as_mut() else {
+            return Ok(None);
+        };
+        let started = Instant::now();
+        if let Err(e) = socket.send(Message::text(r#"{"type":"end"}"#)).await {
+            let e = transcription_failed(format!("ws-remote send failed: {e}"));
+            return Err(self.fail(e).await);
+        }
+
+        let deadline =
+            tokio::time::Instant::now() + Duration::from_millis(self.config.final_timeout_ms);
+        let text = loop {
+            let socket = self.socket.as_mut().expect("socket checked above");
+            let next = match tokio::time::timeout_at(deadline, socket.next()).await {
+                Ok(next) => next,
+                Err(_) => {
+                    let e = transcription_failed(format!(
+                        "ws-remote final transcript timed out after {}ms",
+                        self.config.final_timeout_ms
+                    ));
+                    return Err(self.fail(e).await);
+                }
+            };
+            match next {
+                Some(Ok(Message::Text(text))) => match parse_reply(&text) {
+                    Reply::Partial(text) => self.last_text = text,
+                    Reply::Final(text) => break text,
+                    Reply::Error(message) => {
+                        let e = transcription_failed(format!("ws-remote server error: {message}"));
+                        return Err(self.fail(e).await);
+                    }
+                    Reply::Ignore => {}
+                },
+                // Closing without a final message settles on the last partial
+                Some(Ok(Message::Close(_))) | None => break std::mem::take(&mut self.last_text),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => {
+                    let e = transcription_failed(format!("ws-remote receive failed: {e}"));
+                    return Err(self.fail(e).await);
+                }
+            }
+        };
+
+        self.metrics
+            .record_engine_processing_time(started.elapsed());
+        tracing::debug!(
+            target: "coldvox::stt",
+            finalize_ms = started.elapsed().as_millis() as u64,
+            "ws-remote final transcript received"
+        );
+        self.close().await;
+        Ok(Some(self.final_event(text)))
+    }
+
+    async fn reset(&mut self) -> Result<(), ColdVoxError> {
+        self.close().await;
+        self.last_text.clear();
+        self.utterance_id += 1;
+        Ok(())
+    }
+
+    async fn unload(&mut self) -> Result<(), ColdVoxError> {
+        self.close().await;
+        Ok(())
+    }
+}
+
+pub struct WsRemotePluginFactory {
+    config: WsRemoteConfig,
+    metrics: Arc<SttPerformanceMetrics>,
+}
+
+impl WsRemotePluginFactory {
+    pub fn new(config: WsRemoteConfig) -> Self {
+        Self {
+            config,
+            metrics: Arc::new(SttPerformanceMetrics::new()),
+        }
+    }
+
+    /// Connect, round-trip and success counters shared by every plugin
+    /// instance this factory creates
+    pub fn metrics(&self) -> Arc<SttPerformanceMetrics> {
+        self.metrics.clone()
+    }
+}
+
+impl SttPluginFactory for WsRemotePluginFactory {
+    fn create(&self) -> Result<Box<dyn SttPlugin>, ColdVoxError> {
+        Ok(Box::new(WsRemotePlugin::with_metrics(
+            self.config.clone(),
+            self.metrics.clone(),
+        )))
+    }
+
+    fn plugin_info(&self) -> PluginInfo {
+        plugin_info_from_config(&self.config)
+    }
+
+    fn check_requirements(&self) -> Result<(), ColdVoxError> {
+        let scheme_ok =
+            self.config.url.starts_with("ws://") || self.config.url.starts_with("wss://");
+        if !scheme_ok {
+            return Err(SttError::InvalidConfig(format!(
+                "ws-remote URL '{}' must start with ws:// or wss://",
+                self.config.url
+            ))
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn server_messages_map_to_replies() {
+        assert_eq!(
+            parse_reply(r#"{"type":"partial","text":"hel"}"#),
+            Reply::Partial("hel".to_string())
+        );
+        assert_eq!(
+            parse_reply(r#"{"type":"final","text":"hello"}"#),
+            Reply::Final("hello".to_string())
+        );
+        assert_eq!(
+            parse_reply(r#"{"text":"hello","is_final":true}"#),
+            Reply::Final("hello".to_string())
+        );
+        assert_eq!(
+            parse_reply(r#"{"type":"error","message":"model missing"}"#),
+            Reply::Error("model missing".to_string())
+        );
+        assert_eq!(parse_reply(r#"{"type":"ready"}"#), Reply::Ignore);
+        assert_eq!(parse_reply("not json"), Reply::Ignore);
+        assert!(is_local_url("ws://127.0.0.1:8765/v1/stream"));
+        assert!(!is_local_url("wss://stt.example.com/stream"));
+    }
+
+    #[tokio::test]
+    async fn streams_audio_and_returns_final_transcript() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let mut pcm_bytes = 0;
+            while let Some(Ok(message)) = ws.next().await {
+                match message {
+                    Message::Text(text) if text.contains("\"end\"") => break,
+                    Message::Text(text) => assert!(text.contains("\"start\"")),
+                    Message::Binary(data) => {
+                        pcm_bytes += data.len();
+                        ws.send(Message::text(r#"{"type":"partial","text":"hel"}"#))
+                            .await
+                            .unwrap();
+                    }
+                    _ => {}
+                }
+            }
+            ws.send(Message::text(r#"{"type":"final","text":"hello"}"#))
+                .await
+                .unwrap();
+            pcm_bytes
+        });
+
+        let mut plugin = WsRemotePlugin::new(WsRemoteConfig {
+            url: format!("ws://{addr}/v1/stream"),
+            ..Default::default()
+        });
+        plugin
+            .initialize(TranscriptionConfig::default())
+            .await
+            .unwrap();
+        plugin.process_audio(&[1, 2, 3, 4]).await.unwrap();
+        plugin.process_audio(&[5, 6, 7, 8]).await.unwrap();
+
+        match plugin.finalize().await.unwrap() {
+            Some(TranscriptionEvent::Final {
+                text, utterance_id, ..
+            }) => {
+                assert_eq!(text, "hello");
+                assert_eq!(utterance_id, 0);
+            }
+            other => panic!("expected final transcript, got {other:?}"),
+        }
+        assert_eq!(server.await.unwrap(), 16);
+        // Nothing streamed since: nothing to finalize
+        assert!(plugin.finalize().await.unwrap().is_none());
+    }
+}
@@ -1,5 +1,39 @@
 //! Core types for speech-to-text functionality
 
+use std::borrow::Cow;
+
+/// Audio source a transcription came from (e.g. the microphone or a loopback
+/// capture of desktop audio). Routing decides per source where transcripts go.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SourceId(Cow<'static, str>);
+
+impl SourceId {
+    /// Default capture device
+    pub const MIC: SourceId = SourceId(Cow::Borrowed("mic"));
+    /// Desktop audio (monitor/loopback capture)
+    pub const LOOPBACK: SourceId = SourceId(Cow::Borrowed("loopback"));
+
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(Cow::Owned(name.into()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for SourceId {
+    fn default() -> Self {
+        Self::MIC
+    }
+}
+
+impl std::fmt::Display for SourceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 /// Transcription event types
 #[derive(Debug, Clone)]
 pub enum TranscriptionEvent {
@@ -11,6 +45,8 @@ pub enum TranscriptionEvent {
         t0: Option<f32>,
         /// Optional end time offset in seconds
         t1: Option<f32>,
+        /// Audio source the speech came from
+        source: SourceId,
     },
     /// Final transcription result (speech segment complete)
     Final {
@@ -18,11 +54,51 @@ pub enum TranscriptionEvent {
         text: String,
         /// Optional word-level timing information
         words: Option<Vec<WordInfo>>,
+        /// Audio source the speech came from
+        source: SourceId,
     },
     /// Transcription error
     Error { code: String, message: String },
 }
 
+impl TranscriptionEvent {
+    /// Source of a partial or final (errors have none)
+    pub fn source(&self) -> Option<&SourceId> {
+        match self {
+            TranscriptionEvent::Partial { source, .. }
+            | TranscriptionEvent::Final { source, .. } => Some(source),
+            TranscriptionEvent::Error { .. } => None,
+        }
+    }
+
+    /// Re-tag a partial or final with `new_source`; errors pass through
+    pub fn with_source(mut self, new_source: SourceId) -> Self {
+        if let TranscriptionEvent::Partial { source, .. }
+        | TranscriptionEvent::Final { source, .. } = &mut self
+        {
+            *source = new_source;
+        }
+        self
+    }
+
+    /// Mean word confidence of a final, when the plugin scored its words.
+    /// A confidence of 0.0 is the sentinel for an unscored word.
+    pub fn confidence(&self) -> Option<f32> {
+        let TranscriptionEvent::Final {
+            words: Some(words), ..
+        } = self
+        else {
+            return None;
+        };
+        let scored: Vec<f32> = words
+            .iter()
+            .map(|w| w.conf)
+            .filter(|conf| *conf > 0.0)
+            .collect();
+        (!scored.is_empty()).then(|| scored.iter().sum::<f32>() / scored.len() as f32)
+    }
+}
+
 /// Word-level timing and confidence information
 #[derive(Debug, Clone)]
 pub struct WordInfo {
@@ -55,6 +131,8 @@ pub struct TranscriptionConfig {
     pub streaming: bool,
     /// Automatically extract model from a zip archive if not found
     pub auto_extract_model: bool,
+    /// CPU resources for local decoding
+    pub decode: DecodeResources,
 }
 
 impl Default for TranscriptionConfig {
@@ -71,6 +149,76 @@ impl Default for TranscriptionConfig {
             buffer_size_ms: 512,
             streaming: false, // Default to batch mode for backward compatibility
             auto_extract_model: true,
+            decode: DecodeResources::default(),
         }
     }
 }
+
+/// CPU resources given to local (CPU) decoders
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DecodeResources {
+    /// Decoder threads; `None` picks a count from the available cores
+    pub threads: Option<usize>,
+    /// CPU cores to pin the decoding thread to; empty leaves affinity alone
+    pub cpu_affinity: Vec<usize>,
+    /// Niceness for the decoding thread (-20..=19); `None` leaves it alone
+    pub niceness: Option<i32>,
+}
+
+impl DecodeResources {
+    /// Thread count to use: the configured one, or one less than the
+    /// available cores (leaving a core for audio capture), capped at 8
+    pub fn effective_threads(&self) -> usize {
+        self.threads.filter(|&n| n > 0).unwrap_or_else(|| {
+            let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+            default_decode_threads(cores)
+        })
+    }
+}
+
+/// Decoder threads for a machine with `cores` cores
+pub fn default_decode_threads(cores: usize) -> usize {
+    cores.saturating_sub(1).clamp(1, 8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_threads_leave_a_core_free() {
+        assert_eq!(default_decode_threads(1), 1);
+        assert_eq!(default_decode_threads(4), 3);
+        assert_eq!(default_decode_threads(32), 8);
+
+        let explicit = DecodeResources {
+            threads: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(explicit.effective_threads(), 2);
+    }
+
+    #[test]
+    fn confidence_averages_scored_words() {
+        let word = |conf: f32| WordInfo {
+            start: 0.0,
+            end: 0.5,
+            conf,
+            text: "word".to_string(),
+        };
+        let final_with = |words: Option<Vec<WordInfo>>| TranscriptionEvent::Final {
+            utterance_id: 1,
+            text: "word word".to_string(),
+            words,
+            source: SourceId::default(),
+        };
+
+        assert_eq!(
+            final_with(Some(vec![word(0.5), word(0.75), word(0.0)])).confidence(),
+            Some(0.625)
+        );
+        assert_eq!(final_with(Some(vec![word(0.0)])).confidence(), None);
+        assert_eq!(final_with(None).confidence(), None);
+    }
+}
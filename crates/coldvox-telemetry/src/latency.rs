@@ -0,0 +1,225 @@
+//! End-to-end utterance latency.
+//!
+//! Each utterance is correlated by its STT `utterance_id`. The STT processor
+//! records when speech ended (the capture time of the last frame it saw
+//! before the session ended) and when the final transcript was produced; the
+//! injection worker records when the text was delivered. From those three
+//! points the tracker keeps rolling windows for:
+//!
+//! - `stt`: speech end to final transcript
+//! - `injection`: final transcript to text visible
+//! - `end_to_end`: speech end to text visible
+//!
+//! and reports p50/p95 over the most recent [`WINDOW_SIZE`] utterances.
+
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Utterances kept per rolling window
+pub const WINDOW_SIZE: usize = 512;
+
+/// Transcribed utterances waiting for injection; older ones are forgotten
+/// (e.g. discarded drafts or text that was never injected)
+const MAX_PENDING: usize = 64;
+
+/// Rolling window of latency samples
+#[derive(Debug, Clone, Default)]
+pub struct LatencyWindow {
+    samples_ms: VecDeque<u64>,
+    /// All samples ever recorded (not just the window)
+    pub total_count: u64,
+    pub total_ms: u64,
+}
+
+impl LatencyWindow {
+    pub fn record(&mut self, latency: Duration) {
+        let ms = latency.as_millis() as u64;
+        if self.samples_ms.len() == WINDOW_SIZE {
+            self.samples_ms.pop_front();
+        }
+        self.samples_ms.push_back(ms);
+        self.total_count += 1;
+        self.total_ms += ms;
+    }
+
+    /// Nearest-rank percentile (0-100) over the window
+    pub fn percentile(&self, p: f64) -> Option<u64> {
+        if self.samples_ms.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<u64> = self.samples_ms.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.clamp(1, sorted.len()) - 1])
+    }
+
+    pub fn percentiles(&self) -> LatencyPercentiles {
+        LatencyPercentiles {
+            count: self.samples_ms.len(),
+            p50_ms: self.percentile(50.0),
+            p95_ms: self.percentile(95.0),
+            max_ms: self.samples_ms.iter().max().copied(),
+            total_count: self.total_count,
+            total_ms: self.total_ms,
+        }
+    }
+}
+
+/// Summary of one latency window
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencyPercentiles {
+    /// Samples in the window
+    pub count: usize,
+    pub p50_ms: Option<u64>,
+    pub p95_ms: Option<u64>,
+    pub max_ms: Option<u64>,
+    pub total_count: u64,
+    pub total_ms: u64,
+}
+
+/// Latency of one delivered utterance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UtteranceLatency {
+    pub utterance_id: u64,
+    pub stt: Duration,
+    pub injection: Duration,
+    pub end_to_end: Duration,
+}
+
+/// Point-in-time view of all windows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PipelineLatency {
+    pub stt: LatencyPercentiles,
+    pub injection: LatencyPercentiles,
+    pub end_to_end: LatencyPercentiles,
+}
+
+#[derive(Debug)]
+struct Pending {
+    utterance_id: u64,
+    speech_end: Instant,
+    transcribed: Instant,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    pending: VecDeque<Pending>,
+    stt: LatencyWindow,
+    injection: LatencyWindow,
+    end_to_end: LatencyWindow,
+}
+
+/// Correlates pipeline stages per utterance and keeps latency windows
+#[derive(Debug, Default)]
+pub struct LatencyTracker {
+    inner: Mutex<Inner>,
+}
+
+impl LatencyTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A final transcript for `utterance_id` was produced now; speech ended at `speech_end`
+    pub fn record_transcribed(&self, utterance_id: u64, speech_end: Instant) {
+        self.record_transcribed_at(utterance_id, speech_end, Instant::now());
+    }
+
+    fn record_transcribed_at(&self, utterance_id: u64, speech_end: Instant, now: Instant) {
+        let mut inner = self.inner.lock();
+        inner.stt.record(now.saturating_duration_since(speech_end));
+        inner.pending.retain(|p| p.utterance_id != utterance_id);
+        if inner.pending.len() == MAX_PENDING {
+            inner.pending.pop_front();
+        }
+        inner.pending.push_back(Pending {
+            utterance_id,
+            speech_end,
+            transcribed: now,
+        });
+    }
+
+    /// Text from `utterance_ids` became visible now. Returns the latency of
+    /// each utterance that was being tracked.
+    pub fn record_injected(&self, utterance_ids: &[u64]) -> Vec<UtteranceLatency> {
+        self.record_injected_at(utterance_ids, Instant::now())
+    }
+
+    fn record_injected_at(&self, utterance_ids: &[u64], now: Instant) -> Vec<UtteranceLatency> {
+        let mut inner = self.inner.lock();
+        let mut delivered = Vec::new();
+        for &id in utterance_ids {
+            let Some(pos) = inner.pending.iter().position(|p| p.utterance_id == id) else {
+                continue;
+            };
+            let Some(p) = inner.pending.remove(pos) else {
+                continue;
+            };
+            let latency = UtteranceLatency {
+                utterance_id: id,
+                stt: p.transcribed.saturating_duration_since(p.speech_end),
+                injection: now.saturating_duration_since(p.transcribed),
+                end_to_end: now.saturating_duration_since(p.speech_end),
+            };
+            inner.injection.record(latency.injection);
+            inner.end_to_end.record(latency.end_to_end);
+            delivered.push(latency);
+        }
+        delivered
+    }
+
+    /// Stop tracking utterances still in flight, e.g. after a suspend whose
+    /// length would otherwise count as latency
+    pub fn forget_pending(&self) {
+        self.inner.lock().pending.clear();
+    }
+
+    pub fn snapshot(&self) -> PipelineLatency {
+        let inner = self.inner.lock();
+        PipelineLatency {
+            stt: inner.stt.percentiles(),
+            injection: inner.injection.percentiles(),
+            end_to_end: inner.end_to_end.percentiles(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let mut window = LatencyWindow::default();
+        for ms in 1..=100 {
+            window.record(Duration::from_millis(ms));
+        }
+        assert_eq!(window.percentile(50.0), Some(50));
+        assert_eq!(window.percentile(95.0), Some(95));
+        assert_eq!(window.percentiles().max_ms, Some(100));
+        assert_eq!(LatencyWindow::default().percentile(50.0), None);
+    }
+
+    #[test]
+    fn correlates_stages_by_utterance() {
+        let tracker = LatencyTracker::new();
+        let t0 = Instant::now();
+        let ms = Duration::from_millis;
+        tracker.record_transcribed_at(1, t0, t0 + ms(300));
+        tracker.record_transcribed_at(2, t0 + ms(1000), t0 + ms(1200));
+
+        // Both coalesced into one injection, plus an unknown id
+        let delivered = tracker.record_injected_at(&[1, 2, 99], t0 + ms(1250));
+        assert_eq!(delivered.len(), 2);
+        assert_eq!(delivered[0].end_to_end, ms(1250));
+        assert_eq!(delivered[1].stt, ms(200));
+        assert_eq!(delivered[1].injection, ms(50));
+
+        let snap = tracker.snapshot();
+        assert_eq!(snap.stt.count, 2);
+        assert_eq!(snap.end_to_end.count, 2);
+        assert_eq!(snap.end_to_end.p95_ms, Some(1250));
+        assert!(tracker.record_injected_at(&[1], t0 + ms(2000)).is_empty());
+    }
+}
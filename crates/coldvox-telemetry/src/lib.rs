@@ -1,9 +1,17 @@
 pub mod integration;
+pub mod latency;
 pub mod metrics;
+#[cfg(feature = "metrics-http")]
+pub mod metrics_http;
 pub mod pipeline_metrics;
+pub mod prometheus;
 pub mod stt_metrics;
+pub mod trace;
 
 pub use integration::*;
+pub use latency::*;
 pub use metrics::*;
 pub use pipeline_metrics::*;
+pub use prometheus::{MetricsSource, PrometheusText};
 pub use stt_metrics::*;
+pub use trace::UtteranceTraces;
@@ -0,0 +1,156 @@
+//! Embedded HTTP endpoint serving `/metrics` for Prometheus scrapers.
+//!
+//! Deliberately minimal: one request per connection, `GET /metrics` (or
+//! `HEAD`) only, no TLS or authentication. Bind it to localhost unless the
+//! network in between is trusted.
+
+use crate::prometheus::{render, MetricsSource, CONTENT_TYPE};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Largest request head we read before giving up
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// How long a client may take to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Bind `addr` and serve the sources until the returned task is aborted.
+/// Returns the bound address (useful with port 0) and the server task.
+pub async fn spawn_metrics_server(
+    addr: SocketAddr,
+    sources: Vec<Arc<dyn MetricsSource>>,
+) -> io::Result<(SocketAddr, JoinHandle<()>)> {
+    let listener = TcpListener::bind(addr).await?;
+    let local_addr = listener.local_addr()?;
+    info!(
+        "Serving Prometheus metrics on http://{}/metrics",
+        local_addr
+    );
+
+    let sources: Arc<[Arc<dyn MetricsSource>]> = sources.into();
+    let handle = tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    let sources = sources.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_connection(stream, &sources).await {
+                            debug!("Metrics request from {} failed: {}", peer, e);
+                        }
+                    });
+                }
+                Err(e) => {
+                    warn!("Metrics endpoint accept failed: {}", e);
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+            }
+        }
+    });
+    Ok((local_addr, handle))
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    sources: &[Arc<dyn MetricsSource>],
+) -> io::Result<()> {
+    let head = tokio::time::timeout(READ_TIMEOUT, read_request_head(&mut stream))
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "request timed out"))??;
+
+    let mut parts = head.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+
+    let response = match (method, path) {
+        ("GET" | "HEAD", "/metrics") => {
+            let body = render(sources);
+            let mut response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                CONTENT_TYPE,
+                body.len()
+            );
+            if method == "GET" {
+                response.push_str(&body);
+            }
+            response
+        }
+        ("GET" | "HEAD", _) => plain_response("404 Not Found", "not found\n"),
+        _ => plain_response("405 Method Not Allowed", "method not allowed\n"),
+    };
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Read up to the end of the request headers
+async fn read_request_head(stream: &mut TcpStream) -> io::Result<String> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if buf.windows(4).any(|w| w == b"\r\n\r\n") {
+            break;
+        }
+        if buf.len() > MAX_REQUEST_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request head too large",
+            ));
+        }
+    }
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+fn plain_response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline_metrics::PipelineMetrics;
+
+    async fn get(addr: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn serves_metrics_and_rejects_other_paths() {
+        let metrics = Arc::new(PipelineMetrics::default());
+        metrics.increment_capture_frames();
+        let sources: Vec<Arc<dyn MetricsSource>> = vec![metrics.clone()];
+        let (addr, handle) = spawn_metrics_server("127.0.0.1:0".parse().unwrap(), sources)
+            .await
+            .unwrap();
+
+        let response = get(addr, "/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(CONTENT_TYPE));
+        assert!(response.contains("\ncoldvox_capture_frames_total 1\n"));
+
+        assert!(get(addr, "/").await.starts_with("HTTP/1.1 404"));
+        handle.abort();
+    }
+}
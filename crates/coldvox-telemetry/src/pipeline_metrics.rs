@@ -1,3 +1,5 @@
+use crate::latency::LatencyTracker;
+use crate::trace::UtteranceTraces;
 use parking_lot::RwLock;
 use std::sync::atomic::{AtomicBool, AtomicI16, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -45,6 +47,14 @@ pub struct PipelineMetrics {
     pub capture_errors: Arc<AtomicU64>,
     pub chunker_errors: Arc<AtomicU64>,
 
+    // Capture ring buffer overruns and the samples they cost
+    pub capture_overflows: Arc<AtomicU64>,
+    pub capture_dropped_samples: Arc<AtomicU64>,
+
+    // Capture device failover
+    pub device_failovers: Arc<AtomicU64>,
+    pub device_failover_failures: Arc<AtomicU64>,
+
     // STT metrics (plugin manager)
     pub stt_failover_count: Arc<AtomicU64>,
     pub stt_total_errors: Arc<AtomicU64>,
@@ -67,6 +77,35 @@ pub struct PipelineMetrics {
     pub stt_gc_runs: Arc<AtomicU64>,
     pub vad_detection_latency_ms: Arc<AtomicU64>,
     pub vad_to_stt_handoff_latency_ms: Arc<AtomicU64>,
+
+    // Text injection queue
+    pub injection_queue_depth: Arc<AtomicUsize>,
+    pub injection_queue_max_depth: Arc<AtomicUsize>,
+    pub injection_queue_coalesced: Arc<AtomicU64>,
+    pub injection_queue_dropped: Arc<AtomicU64>,
+
+    // Energy-saver idle mode
+    pub capture_suspended: Arc<AtomicBool>,
+    pub idle_suspend_count: Arc<AtomicU64>,
+    pub idle_resume_count: Arc<AtomicU64>,
+    pub last_resume_latency_ms: Arc<AtomicU64>, // Wake request to first captured frame
+
+    // Pipeline paused by the user (pause hotkey, TUI or API)
+    pub pipeline_paused: Arc<AtomicBool>,
+
+    // Microphone ignored while system audio plays (suppress_on_playback)
+    pub playback_suppressed: Arc<AtomicBool>,
+    pub playback_suppressions: Arc<AtomicU64>,
+
+    // Audio quality warnings raised, by kind
+    pub quality_clipping_warnings: Arc<AtomicU64>,
+    pub quality_too_quiet_warnings: Arc<AtomicU64>,
+    pub quality_off_axis_warnings: Arc<AtomicU64>,
+
+    // Per-utterance speech-end to text-visible latency
+    pub latency: Arc<LatencyTracker>,
+    // Per-utterance tracing spans across STT and injection
+    pub traces: Arc<UtteranceTraces>,
 }
 
 impl Default for PipelineMetrics {
@@ -103,6 +142,12 @@ impl Default for PipelineMetrics {
             capture_errors: Arc::new(AtomicU64::new(0)),
             chunker_errors: Arc::new(AtomicU64::new(0)),
 
+            capture_overflows: Arc::new(AtomicU64::new(0)),
+            capture_dropped_samples: Arc::new(AtomicU64::new(0)),
+
+            device_failovers: Arc::new(AtomicU64::new(0)),
+            device_failover_failures: Arc::new(AtomicU64::new(0)),
+
             stt_failover_count: Arc::new(AtomicU64::new(0)),
             stt_total_errors: Arc::new(AtomicU64::new(0)),
             stt_last_failover_secs: Arc::new(AtomicU64::new(0)),
@@ -124,6 +169,28 @@ impl Default for PipelineMetrics {
             stt_gc_runs: Arc::new(AtomicU64::new(0)),
             vad_detection_latency_ms: Arc::new(AtomicU64::new(0)),
             vad_to_stt_handoff_latency_ms: Arc::new(AtomicU64::new(0)),
+
+            injection_queue_depth: Arc::new(AtomicUsize::new(0)),
+            injection_queue_max_depth: Arc::new(AtomicUsize::new(0)),
+            injection_queue_coalesced: Arc::new(AtomicU64::new(0)),
+            injection_queue_dropped: Arc::new(AtomicU64::new(0)),
+
+            capture_suspended: Arc::new(AtomicBool::new(false)),
+            idle_suspend_count: Arc::new(AtomicU64::new(0)),
+            idle_resume_count: Arc::new(AtomicU64::new(0)),
+            last_resume_latency_ms: Arc::new(AtomicU64::new(0)),
+
+            pipeline_paused: Arc::new(AtomicBool::new(false)),
+
+            playback_suppressed: Arc::new(AtomicBool::new(false)),
+            playback_suppressions: Arc::new(AtomicU64::new(0)),
+
+            quality_clipping_warnings: Arc::new(AtomicU64::new(0)),
+            quality_too_quiet_warnings: Arc::new(AtomicU64::new(0)),
+            quality_off_axis_warnings: Arc::new(AtomicU64::new(0)),
+
+            latency: Arc::new(LatencyTracker::new()),
+            traces: Arc::new(UtteranceTraces::new()),
         }
     }
 }
@@ -188,6 +255,49 @@ impl PipelineMetrics {
         self.vad_fps.store((fps * 10.0) as u64, Ordering::Relaxed);
     }
 
+    /// Record the outcome of a capture device failover attempt.
+    pub fn record_device_failover(&self, success: bool) {
+        if success {
+            self.device_failovers.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.device_failover_failures
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record the current injection queue depth and its high-water mark.
+    pub fn update_injection_queue_depth(&self, depth: usize) {
+        self.injection_queue_depth.store(depth, Ordering::Relaxed);
+        self.injection_queue_max_depth
+            .fetch_max(depth, Ordering::Relaxed);
+    }
+
+    /// Record that capture was suspended by the idle monitor.
+    pub fn record_idle_suspend(&self) {
+        self.capture_suspended.store(true, Ordering::Relaxed);
+        self.idle_suspend_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a resume from idle and how long audio took to flow again.
+    pub fn record_idle_resume(&self, latency_ms: u64) {
+        self.capture_suspended.store(false, Ordering::Relaxed);
+        self.idle_resume_count.fetch_add(1, Ordering::Relaxed);
+        self.last_resume_latency_ms
+            .store(latency_ms, Ordering::Relaxed);
+    }
+
+    /// Record an audio quality warning by its type (`clipping`, `too_quiet`
+    /// or `off_axis`); other types are ignored.
+    pub fn record_quality_warning(&self, warning_type: &str) {
+        let counter = match warning_type {
+            "clipping" => &self.quality_clipping_warnings,
+            "too_quiet" => &self.quality_too_quiet_warnings,
+            "off_axis" => &self.quality_off_axis_warnings,
+            _ => return,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
     pub fn increment_capture_frames(&self) {
         self.capture_frames.fetch_add(1, Ordering::Relaxed);
     }
@@ -228,6 +338,9 @@ pub enum BufferType {
     Vad,
 }
 
+/// Rate windows longer than this are discarded instead of reported
+const FPS_MAX_WINDOW: Duration = Duration::from_secs(5);
+
 #[derive(Debug)]
 pub struct FpsTracker {
     last_update: Instant,
@@ -246,7 +359,13 @@ impl FpsTracker {
         self.frame_count += 1;
         let elapsed = self.last_update.elapsed();
 
-        if elapsed >= Duration::from_secs(1) {
+        if elapsed >= FPS_MAX_WINDOW {
+            // Frames stopped for a while (suspend, idle capture); a rate over
+            // the gap would be meaningless, so start a fresh window
+            self.last_update = Instant::now();
+            self.frame_count = 0;
+            None
+        } else if elapsed >= Duration::from_secs(1) {
             let fps = self.frame_count as f64 / elapsed.as_secs_f64();
             self.last_update = Instant::now();
             self.frame_count = 0;
@@ -0,0 +1,450 @@
+//! Prometheus text exposition format (version 0.0.4).
+//!
+//! Metric holders implement [`MetricsSource`] to write their current values
+//! into a [`PrometheusText`]; the `metrics-http` feature serves the result on
+//! `/metrics`. All metric names carry the `coldvox_` prefix, counters end in
+//! `_total` and scaled internal values (dB * 10, fps * 10) are converted back
+//! to their natural units.
+
+use crate::latency::LatencyPercentiles;
+use crate::pipeline_metrics::PipelineMetrics;
+use crate::stt_metrics::SttPerformanceMetrics;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Content type of the text exposition format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Something that can report its current values as Prometheus metrics
+pub trait MetricsSource: Send + Sync {
+    fn write_metrics(&self, out: &mut PrometheusText);
+}
+
+impl<F> MetricsSource for F
+where
+    F: Fn(&mut PrometheusText) + Send + Sync,
+{
+    fn write_metrics(&self, out: &mut PrometheusText) {
+        self(out)
+    }
+}
+
+/// Builder for a Prometheus text exposition body
+#[derive(Debug, Default)]
+pub struct PrometheusText {
+    body: String,
+}
+
+impl PrometheusText {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Monotonic counter; `name` should end in `_total`
+    pub fn counter(&mut self, name: &str, help: &str, value: u64) {
+        self.header(name, help, "counter");
+        let _ = writeln!(self.body, "{} {}", name, value);
+    }
+
+    /// Value that can go up and down
+    pub fn gauge(&mut self, name: &str, help: &str, value: f64) {
+        self.header(name, help, "gauge");
+        let _ = writeln!(self.body, "{} {}", name, format_value(value));
+    }
+
+    /// Quantiles (as `(quantile, value)`) plus running sum and count
+    pub fn summary(
+        &mut self,
+        name: &str,
+        help: &str,
+        quantiles: &[(f64, f64)],
+        sum: f64,
+        count: u64,
+    ) {
+        self.header(name, help, "summary");
+        for (q, value) in quantiles {
+            let _ = writeln!(
+                self.body,
+                "{}{{quantile=\"{}\"}} {}",
+                name,
+                q,
+                format_value(*value)
+            );
+        }
+        let _ = writeln!(self.body, "{}_sum {}", name, format_value(sum));
+        let _ = writeln!(self.body, "{}_count {}", name, count);
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.body
+    }
+
+    pub fn into_string(self) -> String {
+        self.body
+    }
+
+    fn header(&mut self, name: &str, help: &str, kind: &str) {
+        let help = help.replace('\\', "\\\\").replace('\n', "\\n");
+        let _ = writeln!(self.body, "# HELP {} {}", name, help);
+        let _ = writeln!(self.body, "# TYPE {} {}", name, kind);
+    }
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Latency window as a summary in seconds (quantiles omitted while empty)
+fn latency_summary(out: &mut PrometheusText, name: &str, help: &str, p: &LatencyPercentiles) {
+    let secs = |ms: u64| ms as f64 / 1000.0;
+    let quantiles: Vec<(f64, f64)> = [(0.5, p.p50_ms), (0.95, p.p95_ms)]
+        .into_iter()
+        .filter_map(|(q, ms)| ms.map(|ms| (q, secs(ms))))
+        .collect();
+    out.summary(name, help, &quantiles, secs(p.total_ms), p.total_count);
+}
+
+/// Render every source into one exposition body
+pub fn render(sources: &[Arc<dyn MetricsSource>]) -> String {
+    let mut out = PrometheusText::new();
+    for source in sources {
+        source.write_metrics(&mut out);
+    }
+    out.into_string()
+}
+
+impl MetricsSource for PipelineMetrics {
+    fn write_metrics(&self, out: &mut PrometheusText) {
+        let load = |v: &AtomicU64| v.load(Ordering::Relaxed);
+        let flag = |v: &AtomicBool| f64::from(u8::from(v.load(Ordering::Relaxed)));
+
+        // Audio
+        out.gauge(
+            "coldvox_audio_level_dbfs",
+            "Peak input level in dBFS",
+            f64::from(self.audio_level_db.load(Ordering::Relaxed)) / 10.0,
+        );
+        out.gauge(
+            "coldvox_capture_fps",
+            "Captured frames per second",
+            load(&self.capture_fps) as f64 / 10.0,
+        );
+        out.gauge(
+            "coldvox_chunker_fps",
+            "Chunks per second",
+            load(&self.chunker_fps) as f64 / 10.0,
+        );
+        out.gauge(
+            "coldvox_vad_fps",
+            "VAD frames per second",
+            load(&self.vad_fps) as f64 / 10.0,
+        );
+        out.gauge(
+            "coldvox_capture_buffer_fill_ratio",
+            "Capture ring buffer fill (0-1)",
+            self.capture_buffer_fill.load(Ordering::Relaxed) as f64 / 100.0,
+        );
+        out.counter(
+            "coldvox_capture_frames_total",
+            "Frames captured",
+            load(&self.capture_frames),
+        );
+        out.counter(
+            "coldvox_chunker_frames_total",
+            "Chunks produced",
+            load(&self.chunker_frames),
+        );
+        out.counter(
+            "coldvox_capture_errors_total",
+            "Audio capture errors",
+            load(&self.capture_errors),
+        );
+        out.counter(
+            "coldvox_chunker_errors_total",
+            "Chunker errors",
+            load(&self.chunker_errors),
+        );
+        out.counter(
+            "coldvox_capture_overflows_total",
+            "Writes that found the capture ring buffer full",
+            load(&self.capture_overflows),
+        );
+        out.counter(
+            "coldvox_capture_dropped_samples_total",
+            "Samples lost to capture ring buffer overflows",
+            load(&self.capture_dropped_samples),
+        );
+        out.counter(
+            "coldvox_device_failovers_total",
+            "Successful capture device failovers",
+            load(&self.device_failovers),
+        );
+        out.counter(
+            "coldvox_device_failover_failures_total",
+            "Failed capture device failovers",
+            load(&self.device_failover_failures),
+        );
+        out.gauge(
+            "coldvox_capture_suspended",
+            "1 while capture is suspended by idle mode",
+            flag(&self.capture_suspended),
+        );
+        out.gauge(
+            "coldvox_pipeline_paused",
+            "1 while the pipeline is paused by the user",
+            flag(&self.pipeline_paused),
+        );
+        out.gauge(
+            "coldvox_playback_suppressed",
+            "1 while the microphone is ignored because system audio is playing",
+            flag(&self.playback_suppressed),
+        );
+        out.counter(
+            "coldvox_playback_suppressions_total",
+            "Times system audio playback started suppressing dictation",
+            load(&self.playback_suppressions),
+        );
+        out.counter(
+            "coldvox_idle_suspends_total",
+            "Times capture was suspended by idle mode",
+            load(&self.idle_suspend_count),
+        );
+        out.counter(
+            "coldvox_idle_resumes_total",
+            "Times capture resumed from idle mode",
+            load(&self.idle_resume_count),
+        );
+        out.gauge(
+            "coldvox_idle_resume_latency_seconds",
+            "Wake request to first captured frame on the last resume",
+            load(&self.last_resume_latency_ms) as f64 / 1000.0,
+        );
+        out.counter(
+            "coldvox_quality_clipping_warnings_total",
+            "Audio quality warnings: input clipping",
+            load(&self.quality_clipping_warnings),
+        );
+        out.counter(
+            "coldvox_quality_too_quiet_warnings_total",
+            "Audio quality warnings: speech too quiet",
+            load(&self.quality_too_quiet_warnings),
+        );
+        out.counter(
+            "coldvox_quality_off_axis_warnings_total",
+            "Audio quality warnings: speaker off-axis",
+            load(&self.quality_off_axis_warnings),
+        );
+
+        // Speech activity
+        out.gauge(
+            "coldvox_speaking",
+            "1 while speech is in progress",
+            flag(&self.is_speaking),
+        );
+        out.counter(
+            "coldvox_speech_segments_total",
+            "Speech segments detected",
+            load(&self.speech_segments_count),
+        );
+        out.gauge(
+            "coldvox_end_to_end_latency_seconds",
+            "Capture to output latency",
+            load(&self.end_to_end_ms) as f64 / 1000.0,
+        );
+        out.gauge(
+            "coldvox_vad_detection_latency_seconds",
+            "Highest VAD detection latency seen",
+            load(&self.vad_detection_latency_ms) as f64 / 1000.0,
+        );
+
+        // Per-utterance latency
+        let latency = self.latency.snapshot();
+        latency_summary(
+            out,
+            "coldvox_utterance_stt_latency_seconds",
+            "Speech end to final transcript",
+            &latency.stt,
+        );
+        latency_summary(
+            out,
+            "coldvox_utterance_injection_latency_seconds",
+            "Final transcript to text visible",
+            &latency.injection,
+        );
+        latency_summary(
+            out,
+            "coldvox_utterance_end_to_end_latency_seconds",
+            "Speech end to text visible",
+            &latency.end_to_end,
+        );
+
+        // STT plugins
+        out.gauge(
+            "coldvox_stt_active_plugins",
+            "Loaded STT plugins",
+            self.stt_active_plugins.load(Ordering::Relaxed) as f64,
+        );
+        out.counter(
+            "coldvox_stt_transcription_requests_total",
+            "Transcription requests",
+            load(&self.stt_transcription_requests),
+        );
+        out.counter(
+            "coldvox_stt_transcription_success_total",
+            "Successful transcriptions",
+            load(&self.stt_transcription_success),
+        );
+        out.counter(
+            "coldvox_stt_transcription_failures_total",
+            "Failed transcriptions",
+            load(&self.stt_transcription_failures),
+        );
+        out.gauge(
+            "coldvox_stt_transcription_latency_seconds",
+            "Latency of the last transcription",
+            load(&self.stt_last_transcription_latency_ms) as f64 / 1000.0,
+        );
+        out.counter(
+            "coldvox_stt_errors_total",
+            "STT plugin errors",
+            load(&self.stt_total_errors),
+        );
+        out.counter(
+            "coldvox_stt_failovers_total",
+            "STT plugin failovers",
+            load(&self.stt_failover_count),
+        );
+        out.counter(
+            "coldvox_stt_loads_total",
+            "STT plugin loads",
+            load(&self.stt_load_count),
+        );
+        out.counter(
+            "coldvox_stt_load_errors_total",
+            "STT plugin load errors",
+            load(&self.stt_load_errors),
+        );
+        out.counter(
+            "coldvox_stt_unloads_total",
+            "STT plugin unloads",
+            load(&self.stt_unload_count),
+        );
+        out.gauge(
+            "coldvox_stt_load_duration_seconds",
+            "Duration of the last STT plugin load",
+            load(&self.stt_last_load_duration_ms) as f64 / 1000.0,
+        );
+
+        // Injection queue
+        out.gauge(
+            "coldvox_injection_queue_depth",
+            "Utterances waiting for injection",
+            self.injection_queue_depth.load(Ordering::Relaxed) as f64,
+        );
+        out.gauge(
+            "coldvox_injection_queue_max_depth",
+            "Highest injection queue depth seen",
+            self.injection_queue_max_depth.load(Ordering::Relaxed) as f64,
+        );
+        out.counter(
+            "coldvox_injection_queue_coalesced_total",
+            "Utterances merged into the previous queued one",
+            load(&self.injection_queue_coalesced),
+        );
+        out.counter(
+            "coldvox_injection_queue_dropped_total",
+            "Utterances dropped by the queue policy",
+            load(&self.injection_queue_dropped),
+        );
+    }
+}
+
+impl MetricsSource for SttPerformanceMetrics {
+    fn write_metrics(&self, out: &mut PrometheusText) {
+        let (latency, accuracy, resources, operational) = self.snapshot();
+        out.gauge(
+            "coldvox_stt_end_to_end_latency_seconds",
+            "Last end-to-end STT latency",
+            latency.end_to_end_us as f64 / 1_000_000.0,
+        );
+        out.gauge(
+            "coldvox_stt_engine_processing_seconds",
+            "Last engine processing time",
+            latency.engine_processing_us as f64 / 1_000_000.0,
+        );
+        out.gauge(
+            "coldvox_stt_average_confidence",
+            "Average transcription confidence (0-1)",
+            self.get_average_confidence(),
+        );
+        out.counter(
+            "coldvox_stt_partials_total",
+            "Partial transcriptions",
+            accuracy.partial_count,
+        );
+        out.counter(
+            "coldvox_stt_finals_total",
+            "Final transcriptions",
+            accuracy.final_count,
+        );
+        out.gauge(
+            "coldvox_stt_memory_bytes",
+            "STT engine memory usage",
+            resources.memory_usage_bytes as f64,
+        );
+        out.counter(
+            "coldvox_stt_requests_total",
+            "Requests handled by the STT engine",
+            operational.request_count,
+        );
+        out.counter(
+            "coldvox_stt_engine_errors_total",
+            "Errors reported by the STT engine",
+            operational.error_count,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_help_type_and_sample() {
+        let mut out = PrometheusText::new();
+        out.counter("coldvox_things_total", "Things seen", 3);
+        out.gauge("coldvox_level", "Current level", f64::NEG_INFINITY);
+        assert_eq!(
+            out.as_str(),
+            "# HELP coldvox_things_total Things seen\n\
+             # TYPE coldvox_things_total counter\n\
+             coldvox_things_total 3\n\
+             # HELP coldvox_level Current level\n\
+             # TYPE coldvox_level gauge\n\
+             coldvox_level -Inf\n"
+        );
+    }
+
+    #[test]
+    fn pipeline_metrics_use_natural_units() {
+        let metrics = PipelineMetrics::default();
+        metrics.update_capture_fps(31.25);
+        metrics.record_idle_resume(250);
+        let mut out = PrometheusText::new();
+        metrics.write_metrics(&mut out);
+        let body = out.into_string();
+        assert!(body.contains("\ncoldvox_audio_level_dbfs -90\n"));
+        assert!(body.contains("\ncoldvox_capture_fps 31.2\n"));
+        assert!(body.contains("\ncoldvox_idle_resume_latency_seconds 0.25\n"));
+        assert!(body.contains("\ncoldvox_idle_resumes_total 1\n"));
+    }
+}
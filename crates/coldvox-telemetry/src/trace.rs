@@ -0,0 +1,110 @@
+//! Per-utterance tracing spans.
+//!
+//! Every speech session gets an `utterance` span that stays open until its
+//! text has been injected. The components owning each stage open their spans
+//! as its children (`speech` while audio is captured, `stt.finalize` while
+//! the plugin transcribes, `inject` while the text is typed), so with an
+//! OpenTelemetry layer installed each utterance is one trace showing where
+//! its latency went.
+//!
+//! The root span carries `session_id` (speech session, numbered by the STT
+//! processor) and `utterance_id` (assigned by the STT plugin, recorded once
+//! the final transcript exists). Stages are correlated by `utterance_id`,
+//! like [`LatencyTracker`](crate::LatencyTracker).
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use parking_lot::Mutex;
+use tracing::{field, info_span, Span};
+
+/// Target of the spans, for filtering (`RUST_LOG=coldvox::trace=info`)
+pub const TARGET: &str = "coldvox::trace";
+
+/// Utterances awaiting injection; older ones are ended unclaimed
+const MAX_OPEN: usize = 32;
+
+/// Open `utterance` spans, handed from STT to injection
+#[derive(Debug, Default)]
+pub struct UtteranceTraces {
+    open: Mutex<VecDeque<(u64, Span)>>,
+    injection: AtomicBool,
+}
+
+impl UtteranceTraces {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Root span for speech session `session_id`
+    pub fn begin(&self, session_id: u64) -> Span {
+        info_span!(
+            target: TARGET,
+            "utterance",
+            session_id,
+            utterance_id = field::Empty,
+        )
+    }
+
+    /// Text will be injected: keep transcribed utterances open for the
+    /// injection stage instead of ending them at the final transcript
+    pub fn expect_injection(&self) {
+        self.injection.store(true, Ordering::Relaxed);
+    }
+
+    /// STT produced the final transcript for `root` as `utterance_id`
+    pub fn transcribed(&self, utterance_id: u64, root: Span) {
+        root.record("utterance_id", utterance_id);
+        if !self.injection.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut open = self.open.lock();
+        if open.len() == MAX_OPEN {
+            open.pop_front();
+        }
+        open.push_back((utterance_id, root));
+    }
+
+    /// Span to parent the injection of `utterance_ids` under (the first one
+    /// still open; coalesced utterances are injected together)
+    pub fn parent(&self, utterance_ids: &[u64]) -> Option<Span> {
+        let open = self.open.lock();
+        utterance_ids.iter().find_map(|id| {
+            open.iter()
+                .find(|(open_id, _)| open_id == id)
+                .map(|(_, span)| span.clone())
+        })
+    }
+
+    /// End the traces of `utterance_ids`
+    pub fn finish(&self, utterance_ids: &[u64]) {
+        self.open
+            .lock()
+            .retain(|(id, _)| !utterance_ids.contains(id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_utterances_are_bounded_and_finished_by_id() {
+        let traces = UtteranceTraces::new();
+        // Without an injection stage nothing is kept
+        traces.transcribed(1, traces.begin(1));
+        assert!(traces.parent(&[1]).is_none());
+
+        traces.expect_injection();
+        for id in 0..(MAX_OPEN as u64 + 2) {
+            traces.transcribed(id, traces.begin(id));
+        }
+        assert_eq!(traces.open.lock().len(), MAX_OPEN);
+        assert!(traces.parent(&[0]).is_none(), "oldest was dropped");
+        assert!(traces.parent(&[99, 5]).is_some());
+
+        traces.finish(&[5, 6]);
+        assert!(traces.parent(&[5, 6]).is_none());
+        assert_eq!(traces.open.lock().len(), MAX_OPEN - 2);
+    }
+}
@@ -0,0 +1,87 @@
+//! In-process injection paths at the live benchmark's text sizes.
+//!
+//! Desktop backends are measured by `coldvox bench-injection`; these cover
+//! the parts that run regardless of backend (paste chunking and the
+//! manager's dispatch) so regressions show up without a desktop session.
+
+use async_trait::async_trait;
+use coldvox_text_injection::bench::{sample_text, DEFAULT_SIZES};
+use coldvox_text_injection::progress::split_paste_chunks;
+use coldvox_text_injection::types::InjectionMetrics;
+use coldvox_text_injection::{
+    register_custom_method, InjectionConfig, InjectionContext, InjectionResult, StrategyManager,
+    TextInjector,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::hint::black_box;
+use std::sync::Arc;
+
+/// Accepts text and drops it
+struct SinkInjector;
+
+#[async_trait]
+impl TextInjector for SinkInjector {
+    async fn inject_text(
+        &self,
+        text: &str,
+        _context: Option<&InjectionContext>,
+    ) -> InjectionResult<()> {
+        black_box(text);
+        Ok(())
+    }
+
+    async fn is_available(&self) -> bool {
+        true
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "sink"
+    }
+
+    fn backend_info(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+}
+
+fn bench_paste_chunking(c: &mut Criterion) {
+    let mut group = c.benchmark_group("paste_chunking");
+    let chunk_chars = InjectionConfig::default().paste_chunk_chars as usize;
+
+    for size in DEFAULT_SIZES {
+        let text = sample_text(size);
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &text, |b, t| {
+            b.iter(|| split_paste_chunks(black_box(t), chunk_chars))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_manager_dispatch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("manager_dispatch");
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let method = register_custom_method("bench-sink", "benchmark sink").unwrap();
+    let manager = rt.block_on(async {
+        let metrics = Arc::new(InjectionMetrics::default());
+        let mut manager = StrategyManager::new(InjectionConfig::default(), metrics).await;
+        manager
+            .register_injector(method, Arc::new(SinkInjector))
+            .await
+            .unwrap();
+        manager
+    });
+
+    for size in DEFAULT_SIZES {
+        let text = sample_text(size);
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &text, |b, t| {
+            b.iter(|| rt.block_on(manager.inject_with_method(method, black_box(t))))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_paste_chunking, bench_manager_dispatch);
+criterion_main!(benches);
@@ -7,7 +7,29 @@ fn main() {
     // This avoids adding build-time dependencies for regular users.
     if env::var("CARGO_FEATURE_REAL_INJECTION_TESTS").is_ok() {
         build_gtk_test_app();
-        build_terminal_test_app();
+        build_qt_test_app();
+        build_cargo_test_app("terminal-test-app");
+        build_cargo_test_app("winit-test-app");
+    }
+    #[cfg(feature = "ffi")]
+    generate_ffi_header();
+}
+
+/// Write the C header for `src/ffi.rs` to `$OUT_DIR/coldvox_inject.h`
+#[cfg(feature = "ffi")]
+fn generate_ffi_header() {
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let config = cbindgen::Config::from_file(Path::new(&crate_dir).join("cbindgen.toml"))
+        .expect("Failed to read cbindgen.toml");
+    match cbindgen::generate_with_config(&crate_dir, config) {
+        Ok(bindings) => {
+            bindings.write_to_file(Path::new(&out_dir).join("coldvox_inject.h"));
+        }
+        Err(e) => println!("cargo:warning=Failed to generate coldvox_inject.h: {}", e),
     }
 }
 
@@ -57,42 +79,99 @@ fn build_gtk_test_app() {
 
     if !status.success() {
         println!("cargo:warning=Failed to compile GTK test app. Real injection tests against GTK may fail.");
+        return;
+    }
+
+    // Lets the injection benchmark find the app without OUT_DIR at runtime
+    println!(
+        "cargo:rustc-env=COLDVOX_GTK_TEST_APP={}",
+        executable_path.display()
+    );
+}
+
+fn build_qt_test_app() {
+    println!("cargo:rerun-if-changed=test-apps/qt_test_app.cpp");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let executable_path = Path::new(&out_dir).join("qt_test_app");
+
+    // Prefer Qt 6, which KDE Plasma 6 applications use; Qt 5 behaves the
+    // same for a line edit.
+    let Some(package) = ["Qt6Widgets", "Qt5Widgets"].into_iter().find(|p| {
+        Command::new("pkg-config")
+            .arg("--exists")
+            .arg(p)
+            .status()
+            .is_ok_and(|s| s.success())
+    }) else {
+        println!("cargo:warning=Skipping Qt test app build: Qt6Widgets/Qt5Widgets not found by pkg-config.");
+        return;
+    };
+
+    let flags = |arg: &str| {
+        Command::new("pkg-config")
+            .arg(arg)
+            .arg(package)
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+            .unwrap_or_default()
+    };
+    let cflags = flags("--cflags");
+    let libs = flags("--libs");
+
+    let status = Command::new("g++")
+        .arg("-std=c++17")
+        .arg("-fPIC") // Qt refuses to build without position-independent code
+        .arg("test-apps/qt_test_app.cpp")
+        .arg("-o")
+        .arg(&executable_path)
+        .args(cflags.split_whitespace())
+        .args(libs.split_whitespace())
+        .status();
+
+    if !status.is_ok_and(|s| s.success()) {
+        println!("cargo:warning=Failed to compile Qt test app. Real injection tests against Qt will be skipped.");
     }
 }
 
-fn build_terminal_test_app() {
-    println!("cargo:rerun-if-changed=test-apps/terminal-test-app/src/main.rs");
-    println!("cargo:rerun-if-changed=test-apps/terminal-test-app/Cargo.toml");
-    println!("cargo:rerun-if-changed=test-apps/terminal-test-app/Cargo.lock");
+/// Build one of the standalone Cargo projects under `test-apps/` and copy its
+/// executable to `$OUT_DIR/<name>`
+fn build_cargo_test_app(name: &str) {
+    let manifest = format!("test-apps/{}/Cargo.toml", name);
+    let lockfile = format!("test-apps/{}/Cargo.lock", name);
+    println!("cargo:rerun-if-changed=test-apps/{}/src/main.rs", name);
+    println!("cargo:rerun-if-changed={}", manifest);
+    println!("cargo:rerun-if-changed={}", lockfile);
 
     let out_dir = env::var("OUT_DIR").unwrap();
-    let target_dir = Path::new(&out_dir).join("terminal-test-app-target");
+    let target_dir = Path::new(&out_dir).join(format!("{}-target", name));
 
-    // Build the terminal test app using `cargo build`.
-    // Note: the test app is NOT part of the main workspace, so we must specify --manifest-path.
+    // Build the test app using `cargo build`.
+    // Note: the test apps are NOT part of the main workspace, so we must specify --manifest-path.
     let status = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
         .arg("build")
         .arg("--manifest-path")
-        .arg("test-apps/terminal-test-app/Cargo.toml")
+        .arg(&manifest)
         .arg("--release") // Build in release mode for faster startup.
         .arg("--locked")
         .arg("--target-dir")
         .arg(&target_dir)
         .status()
-        .expect("Failed to execute cargo build for terminal-test-app");
+        .unwrap_or_else(|e| panic!("Failed to execute cargo build for {}: {}", name, e));
 
     if !status.success() {
         println!(
-            "cargo:warning=Failed to build the terminal test app. Real injection tests may fail."
+            "cargo:warning=Failed to build {}. Real injection tests against it will be skipped.",
+            name
         );
     } else {
         // Copy the executable to a known location in OUT_DIR for the tests to find easily.
-        let src_path = target_dir.join("release/terminal-test-app");
-        let dest_path = Path::new(&out_dir).join("terminal-test-app");
+        let src_path = target_dir.join("release").join(name);
+        let dest_path = Path::new(&out_dir).join(name);
         if let Err(e) = std::fs::copy(&src_path, &dest_path) {
             println!(
-                "cargo:warning=Failed to copy terminal test app executable from {:?} to {:?}: {}",
-                src_path, dest_path, e
+                "cargo:warning=Failed to copy {} executable from {:?} to {:?}: {}",
+                name, src_path, dest_path, e
             );
         }
     }
@@ -12,7 +12,7 @@
 use std::env;
 use std::fs;
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 use anyhow::{bail, Context, Result};
 use coldvox_text_injection::types::InjectionMetrics;
@@ -125,7 +125,7 @@ async fn main() -> Result<()> {
         "Loaded injection configuration"
     );
 
-    let metrics = Arc::new(Mutex::new(InjectionMetrics::default()));
+    let metrics = Arc::new(InjectionMetrics::default());
     let mut manager = StrategyManager::new(config.clone(), metrics.clone()).await;
 
     let order_preview = manager.get_method_order_uncached();
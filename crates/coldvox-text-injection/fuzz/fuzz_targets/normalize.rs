@@ -0,0 +1,17 @@
+//! Text normalization must leave only newline and tab as control
+//! characters, keep everything else in order, and be idempotent.
+#![no_main]
+
+use coldvox_text_injection::normalize::for_injection;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let out = for_injection(text);
+    assert!(!out
+        .chars()
+        .any(|c| c.is_control() && !matches!(c, '\n' | '\t')));
+    assert_eq!(for_injection(&out), out);
+
+    let kept = |s: &str| s.chars().filter(|c| !c.is_control()).collect::<String>();
+    assert_eq!(kept(&out), kept(text));
+});
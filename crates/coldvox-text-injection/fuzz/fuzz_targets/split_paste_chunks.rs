@@ -0,0 +1,30 @@
+//! Chunked paste and typing bursts must hand backends every grapheme of the
+//! input, in order, without splitting a cluster.
+#![no_main]
+
+use coldvox_text_injection::progress::split_paste_chunks;
+use libfuzzer_sys::fuzz_target;
+use unicode_segmentation::UnicodeSegmentation;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&max_chars, text)) = data.split_first() else {
+        return;
+    };
+    let Ok(text) = std::str::from_utf8(text) else {
+        return;
+    };
+    let max_chars = usize::from(max_chars);
+
+    let chunks = split_paste_chunks(text, max_chars);
+    assert_eq!(chunks.concat(), text);
+    for chunk in &chunks {
+        assert!(!chunk.is_empty());
+        assert!(
+            chunk.chars().count() <= max_chars.max(1) || chunk.graphemes(true).count() == 1,
+            "oversized chunk {:?}",
+            chunk
+        );
+    }
+    let rejoined: Vec<&str> = chunks.iter().flat_map(|c| c.graphemes(true)).collect();
+    assert_eq!(rejoined, text.graphemes(true).collect::<Vec<_>>());
+});
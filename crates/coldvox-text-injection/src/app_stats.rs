@@ -0,0 +1,537 @@
+//! Persistent per-application injection statistics.
+//!
+//! The strategy manager's success cache only lives for one run. When
+//! `InjectionConfig::stats_path` is set, every injection attempt is also
+//! folded into a small JSON file keyed by application id so that
+//! `coldvox stats --per-app` can show which apps need tuning across runs.
+//! The same file keeps daily per-backend aggregates (outcomes and a latency
+//! histogram) for `coldvox stats --trend`. The injection path records into
+//! a [`SharedAppStats`], which writes the file from a background task.
+
+use crate::types::InjectionMethod;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use tokio::sync::Notify;
+use tracing::debug;
+
+/// Per-method outcome counters for one application.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MethodStats {
+    pub successes: u64,
+    pub failures: u64,
+}
+
+/// Aggregated injection outcomes for one application.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AppStats {
+    /// Injection requests (one per flushed utterance)
+    pub utterances: u64,
+    /// Requests where some method succeeded
+    pub successes: u64,
+    /// Sum of end-to-end latency over successful requests
+    pub total_latency_ms: u64,
+    #[serde(default)]
+    pub methods: BTreeMap<InjectionMethod, MethodStats>,
+    /// Unix timestamp (seconds) of the last request
+    #[serde(default)]
+    pub last_seen: u64,
+}
+
+impl AppStats {
+    pub fn success_rate(&self) -> f64 {
+        if self.utterances == 0 {
+            0.0
+        } else {
+            self.successes as f64 / self.utterances as f64
+        }
+    }
+
+    pub fn avg_latency_ms(&self) -> Option<f64> {
+        (self.successes > 0).then(|| self.total_latency_ms as f64 / self.successes as f64)
+    }
+
+    /// Method with the most successes (ties broken by fewer failures).
+    pub fn preferred_method(&self) -> Option<InjectionMethod> {
+        self.methods
+            .iter()
+            .filter(|(_, s)| s.successes > 0)
+            .max_by(|(_, a), (_, b)| {
+                a.successes
+                    .cmp(&b.successes)
+                    .then(b.failures.cmp(&a.failures))
+            })
+            .map(|(m, _)| *m)
+    }
+}
+
+/// Upper bounds (ms) of the latency histogram buckets. One more open-ended
+/// bucket counts everything slower.
+pub const LATENCY_BUCKETS_MS: [u64; 15] = [
+    5, 10, 20, 35, 50, 75, 100, 150, 200, 300, 500, 750, 1000, 2000, 5000,
+];
+
+/// Daily aggregates older than this are dropped on the next record.
+const DAILY_RETENTION_DAYS: i64 = 90;
+
+/// One backend's attempts on one day.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DailyMethodStats {
+    pub successes: u64,
+    pub failures: u64,
+    /// Attempt counts per [`LATENCY_BUCKETS_MS`] bucket, open-ended bucket last
+    #[serde(default)]
+    pub latency_buckets: Vec<u64>,
+}
+
+impl DailyMethodStats {
+    pub fn record(&mut self, ok: bool, latency_ms: u64) {
+        if ok {
+            self.successes += 1;
+        } else {
+            self.failures += 1;
+        }
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&bound| latency_ms <= bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.latency_buckets.resize(LATENCY_BUCKETS_MS.len() + 1, 0);
+        self.latency_buckets[bucket] += 1;
+    }
+
+    pub fn merge(&mut self, other: &DailyMethodStats) {
+        self.successes += other.successes;
+        self.failures += other.failures;
+        if self.latency_buckets.len() < other.latency_buckets.len() {
+            self.latency_buckets.resize(other.latency_buckets.len(), 0);
+        }
+        for (mine, theirs) in self.latency_buckets.iter_mut().zip(&other.latency_buckets) {
+            *mine += theirs;
+        }
+    }
+
+    pub fn attempts(&self) -> u64 {
+        self.successes + self.failures
+    }
+
+    pub fn success_rate(&self) -> Option<f64> {
+        let attempts = self.attempts();
+        (attempts > 0).then(|| self.successes as f64 / attempts as f64)
+    }
+
+    /// Latency at quantile `q` (0..=1), reported as the upper bound of the
+    /// bucket it falls in. `Some(u64::MAX)` means slower than the last bound.
+    pub fn latency_percentile_ms(&self, q: f64) -> Option<u64> {
+        let total: u64 = self.latency_buckets.iter().sum();
+        if total == 0 {
+            return None;
+        }
+        let rank = ((q.clamp(0.0, 1.0) * total as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (i, count) in self.latency_buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Some(LATENCY_BUCKETS_MS.get(i).copied().unwrap_or(u64::MAX));
+            }
+        }
+        Some(u64::MAX)
+    }
+}
+
+/// On-disk store of [`AppStats`] keyed by application id.
+#[derive(Debug, Clone, Default)]
+pub struct AppStatsStore {
+    path: PathBuf,
+    apps: BTreeMap<String, AppStats>,
+    daily: BTreeMap<NaiveDate, BTreeMap<InjectionMethod, DailyMethodStats>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoreFile {
+    version: u32,
+    apps: BTreeMap<String, AppStats>,
+    #[serde(default)]
+    daily: BTreeMap<NaiveDate, BTreeMap<InjectionMethod, DailyMethodStats>>,
+}
+
+const STORE_VERSION: u32 = 2;
+
+impl AppStatsStore {
+    /// Default location: `$XDG_STATE_HOME/coldvox/injection_stats.json`,
+    /// falling back to `~/.local/state/coldvox/injection_stats.json`.
+    pub fn default_path() -> Option<PathBuf> {
+        let state_dir = std::env::var_os("XDG_STATE_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local").join("state"))
+            })?;
+        Some(state_dir.join("coldvox").join("injection_stats.json"))
+    }
+
+    /// Load the store at `path`. A missing file yields an empty store.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let (apps, daily) = match fs::read_to_string(&path) {
+            Ok(content) => {
+                let file: StoreFile = serde_json::from_str(&content)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                (file.apps, file.daily)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (BTreeMap::new(), BTreeMap::new()),
+            Err(e) => return Err(e),
+        };
+        Ok(Self { path, apps, daily })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn apps(&self) -> &BTreeMap<String, AppStats> {
+        &self.apps
+    }
+
+    /// Daily per-backend aggregates, oldest day first.
+    pub fn daily(&self) -> &BTreeMap<NaiveDate, BTreeMap<InjectionMethod, DailyMethodStats>> {
+        &self.daily
+    }
+
+    /// Per-backend totals over the `days` days ending with `last_day`.
+    pub fn window(
+        &self,
+        last_day: NaiveDate,
+        days: i64,
+    ) -> BTreeMap<InjectionMethod, DailyMethodStats> {
+        let first_day = last_day - Duration::days(days - 1);
+        let mut totals: BTreeMap<InjectionMethod, DailyMethodStats> = BTreeMap::new();
+        for (_, methods) in self.daily.range(first_day..=last_day) {
+            for (method, stats) in methods {
+                totals.entry(*method).or_default().merge(stats);
+            }
+        }
+        totals
+    }
+
+    /// Fold each attempt's outcome and latency into `day`'s per-backend
+    /// aggregates, dropping days past the retention window.
+    pub fn record_attempts(&mut self, day: NaiveDate, attempts: &[(InjectionMethod, bool, u64)]) {
+        let methods = self.daily.entry(day).or_default();
+        for (method, ok, latency_ms) in attempts {
+            methods.entry(*method).or_default().record(*ok, *latency_ms);
+        }
+        let cutoff = day - Duration::days(DAILY_RETENTION_DAYS);
+        self.daily.retain(|d, _| *d > cutoff);
+    }
+
+    /// Record the outcome of one injection request.
+    ///
+    /// `attempts` lists each method tried in order with its result; the
+    /// request counts as successful if any attempt succeeded.
+    pub fn record(
+        &mut self,
+        app_id: &str,
+        attempts: &[(InjectionMethod, bool)],
+        latency_ms: u64,
+        now_unix_secs: u64,
+    ) {
+        let stats = self.apps.entry(app_id.to_string()).or_default();
+        stats.utterances += 1;
+        stats.last_seen = now_unix_secs;
+        let mut succeeded = false;
+        for (method, ok) in attempts {
+            let m = stats.methods.entry(*method).or_default();
+            if *ok {
+                m.successes += 1;
+                succeeded = true;
+            } else {
+                m.failures += 1;
+            }
+        }
+        if succeeded {
+            stats.successes += 1;
+            stats.total_latency_ms += latency_ms;
+        }
+    }
+
+    /// Write the store atomically (temp file + rename).
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = StoreFile {
+            version: STORE_VERSION,
+            apps: self.apps.clone(),
+            daily: self.daily.clone(),
+        };
+        let json = serde_json::to_string_pretty(&file)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, &self.path)
+    }
+}
+
+/// How long a burst of records is coalesced into one write
+pub const SAVE_DEBOUNCE: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// An [`AppStatsStore`] shared with the injection path.
+///
+/// Updates only touch memory; a background task writes the file at most once
+/// per debounce interval, off the async workers. Whatever is still unsaved
+/// when the last handle is dropped is written then.
+#[derive(Clone)]
+pub struct SharedAppStats {
+    inner: Arc<SharedInner>,
+}
+
+struct SharedInner {
+    store: Mutex<AppStatsStore>,
+    dirty: AtomicBool,
+    wake: Arc<Notify>,
+}
+
+impl SharedAppStats {
+    /// Share `store`, saving it `debounce` after each burst of updates.
+    /// Must be called from within a tokio runtime.
+    pub fn new(store: AppStatsStore, debounce: std::time::Duration) -> Self {
+        let wake = Arc::new(Notify::new());
+        let inner = Arc::new(SharedInner {
+            store: Mutex::new(store),
+            dirty: AtomicBool::new(false),
+            wake: wake.clone(),
+        });
+        tokio::spawn(save_in_background(Arc::downgrade(&inner), wake, debounce));
+        Self { inner }
+    }
+
+    /// Read the store
+    pub fn read<R>(&self, f: impl FnOnce(&AppStatsStore) -> R) -> R {
+        f(&self.inner.store.lock().unwrap())
+    }
+
+    /// Change the store in memory and schedule a save
+    pub fn update(&self, f: impl FnOnce(&mut AppStatsStore)) {
+        f(&mut self.inner.store.lock().unwrap());
+        self.inner.dirty.store(true, Ordering::Release);
+        self.inner.wake.notify_one();
+    }
+}
+
+impl SharedInner {
+    /// Copy of the store if it changed since the last save
+    fn take_unsaved(&self) -> Option<AppStatsStore> {
+        let store = self.store.lock().unwrap();
+        self.dirty
+            .swap(false, Ordering::AcqRel)
+            .then(|| store.clone())
+    }
+}
+
+impl Drop for SharedInner {
+    fn drop(&mut self) {
+        if let Some(store) = self.take_unsaved() {
+            save_logged(&store);
+        }
+        // Let the background task notice it has nothing left to save
+        self.wake.notify_one();
+    }
+}
+
+fn save_logged(store: &AppStatsStore) {
+    if let Err(e) = store.save() {
+        debug!(
+            "Failed to save injection stats to {}: {}",
+            store.path().display(),
+            e
+        );
+    }
+}
+
+async fn save_in_background(
+    inner: Weak<SharedInner>,
+    wake: Arc<Notify>,
+    debounce: std::time::Duration,
+) {
+    loop {
+        wake.notified().await;
+        tokio::time::sleep(debounce).await;
+        // Holding the handle keeps the final save on drop from racing this one
+        let Some(inner) = inner.upgrade() else {
+            return;
+        };
+        let Some(store) = inner.take_unsaved() else {
+            continue;
+        };
+        if let Err(e) = tokio::task::spawn_blocking(move || save_logged(&store)).await {
+            debug!("Injection stats save task failed: {}", e);
+        }
+        drop(inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_and_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("stats.json");
+
+        let mut store = AppStatsStore::load(&path).unwrap();
+        assert!(store.apps().is_empty());
+
+        store.record(
+            "kate",
+            &[
+                (InjectionMethod::AtspiInsert, false),
+                (InjectionMethod::ClipboardPasteFallback, true),
+            ],
+            120,
+            1,
+        );
+        store.record(
+            "kate",
+            &[(InjectionMethod::ClipboardPasteFallback, true)],
+            80,
+            2,
+        );
+        store.record("kate", &[(InjectionMethod::AtspiInsert, false)], 50, 3);
+        store.save().unwrap();
+
+        let loaded = AppStatsStore::load(&path).unwrap();
+        let kate = &loaded.apps()["kate"];
+        assert_eq!(kate.utterances, 3);
+        assert_eq!(kate.successes, 2);
+        assert_eq!(kate.avg_latency_ms(), Some(100.0));
+        assert!((kate.success_rate() - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(
+            kate.preferred_method(),
+            Some(InjectionMethod::ClipboardPasteFallback)
+        );
+        assert_eq!(kate.methods[&InjectionMethod::AtspiInsert].failures, 2);
+        assert_eq!(kate.last_seen, 3);
+    }
+
+    #[test]
+    fn custom_methods_round_trip_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stats.json");
+        let custom = crate::types::register_custom_method("stats-test", "test").unwrap();
+
+        let mut store = AppStatsStore::load(&path).unwrap();
+        store.record("kate", &[(custom, true)], 10, 1);
+        store.save().unwrap();
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("\"stats-test\""));
+
+        let loaded = AppStatsStore::load(&path).unwrap();
+        assert_eq!(loaded.apps()["kate"].preferred_method(), Some(custom));
+        assert_eq!(
+            InjectionMethod::from_name("ClipboardPasteFallback"),
+            InjectionMethod::ClipboardPasteFallback
+        );
+        assert!(crate::types::register_custom_method("NoOp", "clash").is_err());
+    }
+
+    #[test]
+    fn daily_aggregates_window_and_retention() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stats.json");
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+
+        let mut store = AppStatsStore::load(&path).unwrap();
+        store.record_attempts(
+            day(1),
+            &[
+                (InjectionMethod::AtspiInsert, false, 40),
+                (InjectionMethod::ClipboardPasteFallback, true, 180),
+            ],
+        );
+        for ms in [8, 30, 90] {
+            store.record_attempts(day(5), &[(InjectionMethod::AtspiInsert, true, ms)]);
+        }
+        store.save().unwrap();
+
+        let loaded = AppStatsStore::load(&path).unwrap();
+        let week = loaded.window(day(7), 7);
+        let atspi = &week[&InjectionMethod::AtspiInsert];
+        assert_eq!((atspi.successes, atspi.failures), (3, 1));
+        assert_eq!(atspi.success_rate(), Some(0.75));
+        assert_eq!(atspi.latency_percentile_ms(0.5), Some(35));
+        assert_eq!(atspi.latency_percentile_ms(0.95), Some(100));
+        assert!(!loaded
+            .window(day(7), 2)
+            .contains_key(&InjectionMethod::ClipboardPasteFallback));
+
+        let mut slow = DailyMethodStats::default();
+        slow.record(true, 9000);
+        assert_eq!(slow.latency_percentile_ms(0.5), Some(u64::MAX));
+
+        // A record far in the future ages out the old days
+        let mut store = loaded;
+        store.record_attempts(
+            NaiveDate::from_ymd_opt(2026, 12, 1).unwrap(),
+            &[(InjectionMethod::AtspiInsert, true, 10)],
+        );
+        assert_eq!(store.daily().len(), 1);
+    }
+
+    #[test]
+    fn loads_version_one_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stats.json");
+        fs::write(&path, r#"{"version":1,"apps":{}}"#).unwrap();
+        let store = AppStatsStore::load(&path).unwrap();
+        assert!(store.daily().is_empty());
+    }
+
+    #[test]
+    fn rejects_corrupt_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stats.json");
+        fs::write(&path, "not json").unwrap();
+        assert!(AppStatsStore::load(&path).is_err());
+    }
+
+    #[tokio::test]
+    async fn shared_store_saves_in_the_background_and_on_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stats.json");
+        let shared = SharedAppStats::new(
+            AppStatsStore::load(&path).unwrap(),
+            std::time::Duration::from_millis(10),
+        );
+
+        shared.update(|store| store.record("kate", &[(InjectionMethod::AtspiInsert, true)], 5, 1));
+        assert_eq!(shared.read(|store| store.apps()["kate"].utterances), 1);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while !path.exists() {
+            assert!(std::time::Instant::now() < deadline, "stats never saved");
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(
+            AppStatsStore::load(&path).unwrap().apps()["kate"].utterances,
+            1
+        );
+
+        // Not yet written when the last handle goes away: saved right then
+        let shared = SharedAppStats::new(
+            AppStatsStore::load(&path).unwrap(),
+            std::time::Duration::from_secs(3600),
+        );
+        shared.update(|store| store.record("kate", &[(InjectionMethod::AtspiInsert, true)], 5, 2));
+        drop(shared);
+        assert_eq!(
+            AppStatsStore::load(&path).unwrap().apps()["kate"].utterances,
+            2
+        );
+    }
+}
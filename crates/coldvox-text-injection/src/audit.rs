@@ -0,0 +1,336 @@
+//! Injection audit log.
+//!
+//! When `InjectionConfig::audit_log_path` is set, every injection request
+//! appends one JSON line: when, which app, which method, how long it took,
+//! whether the text was seen in the target afterwards, and the text itself as
+//! [`AuditTextMode`] allows (full, a SHA-256, or only its length). `audit_text`
+//! defaults to hashed while `redact_logs` is on. Nothing is written when the
+//! path is unset.
+//!
+//! The file rotates by size: at `max_bytes` it becomes `<file>.1`, older
+//! rotations shift up and the oldest beyond `keep` is deleted. It is created
+//! readable by the owner only. `coldvox audit` queries it.
+
+use crate::types::{AuditTextMode, InjectionMethod};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Rotate once the file reaches this size
+pub const DEFAULT_MAX_BYTES: u64 = 5 * 1024 * 1024;
+/// Rotated files kept next to the live one
+pub const DEFAULT_KEEP: usize = 3;
+
+/// Whether the injected text showed up in the target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Confirmation {
+    /// The focused element's text contains what was injected
+    Confirmed,
+    /// The focused element's text could be read and doesn't contain it
+    NotSeen,
+    /// Not checked, or the target doesn't expose its text
+    Unknown,
+}
+
+/// One injection request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub timestamp: DateTime<Utc>,
+    pub app_id: String,
+    /// Method that delivered the text, or the last one tried
+    pub method: Option<InjectionMethod>,
+    pub success: bool,
+    /// End-to-end latency over all attempts
+    pub latency_ms: u64,
+    pub confirmation: Confirmation,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Length of the text in characters, always recorded
+    pub chars: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_sha256: Option<String>,
+}
+
+impl AuditRecord {
+    /// A successful, unconfirmed record for `text`, kept as `mode` allows
+    pub fn new(app_id: &str, text: &str, mode: AuditTextMode) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            app_id: app_id.to_string(),
+            method: None,
+            success: true,
+            latency_ms: 0,
+            confirmation: Confirmation::Unknown,
+            error: None,
+            chars: text.chars().count(),
+            text: (mode == AuditTextMode::Full).then(|| text.to_string()),
+            text_sha256: (mode == AuditTextMode::Hashed).then(|| sha256_hex(text)),
+        }
+    }
+}
+
+/// Lowercase hex SHA-256, as `sha256sum` prints it
+pub fn sha256_hex(text: &str) -> String {
+    use std::fmt::Write as _;
+    let mut hex = String::with_capacity(64);
+    for byte in Sha256::digest(text.as_bytes()) {
+        let _ = write!(hex, "{:02x}", byte);
+    }
+    hex
+}
+
+/// Size-rotated JSON-lines audit file
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    path: PathBuf,
+    max_bytes: u64,
+    keep: usize,
+}
+
+impl AuditLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            max_bytes: DEFAULT_MAX_BYTES,
+            keep: DEFAULT_KEEP,
+        }
+    }
+
+    pub fn with_rotation(mut self, max_bytes: u64, keep: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self.keep = keep;
+        self
+    }
+
+    /// Default location: `$XDG_STATE_HOME/coldvox/injection_audit.jsonl`,
+    /// falling back to `~/.local/state/coldvox/injection_audit.jsonl`.
+    pub fn default_path() -> Option<PathBuf> {
+        let state_dir = std::env::var_os("XDG_STATE_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local").join("state"))
+            })?;
+        Some(state_dir.join("coldvox").join("injection_audit.jsonl"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append one record, rotating first if the file is full
+    pub fn append(&self, record: &AuditRecord) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        if fs::metadata(&self.path).is_ok_and(|m| m.len() >= self.max_bytes) {
+            self.rotate()?;
+        }
+        let mut line = serde_json::to_string(record)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        line.push('\n');
+
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options.open(&self.path)?.write_all(line.as_bytes())
+    }
+
+    fn rotate(&self) -> io::Result<()> {
+        if self.keep == 0 {
+            return fs::remove_file(&self.path);
+        }
+        let _ = fs::remove_file(rotated_path(&self.path, self.keep));
+        for n in (1..self.keep).rev() {
+            let from = rotated_path(&self.path, n);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, n + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))
+    }
+}
+
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+/// Every record in `path` and its rotations, oldest first. Unreadable lines
+/// are skipped; a missing log yields nothing.
+pub fn read_records(path: &Path) -> io::Result<Vec<AuditRecord>> {
+    let mut files: Vec<PathBuf> = (1..)
+        .map(|n| rotated_path(path, n))
+        .take_while(|p| p.exists())
+        .collect();
+    files.reverse();
+    files.push(path.to_path_buf());
+
+    let mut records = Vec::new();
+    for file in files {
+        let reader = match fs::File::open(&file) {
+            Ok(f) => BufReader::new(f),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        for line in reader.lines() {
+            if let Ok(record) = serde_json::from_str(&line?) {
+                records.push(record);
+            }
+        }
+    }
+    Ok(records)
+}
+
+/// Filter for `coldvox audit`
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    /// Application id, matched case-insensitively
+    pub app: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub failures_only: bool,
+    /// Keep only the newest this many matches
+    pub limit: Option<usize>,
+}
+
+impl AuditQuery {
+    pub fn matches(&self, record: &AuditRecord) -> bool {
+        self.app
+            .as_ref()
+            .is_none_or(|app| record.app_id.eq_ignore_ascii_case(app))
+            && self.since.is_none_or(|since| record.timestamp >= since)
+            && !(self.failures_only && record.success)
+    }
+
+    pub fn apply(&self, records: Vec<AuditRecord>) -> Vec<AuditRecord> {
+        let mut matched: Vec<AuditRecord> =
+            records.into_iter().filter(|r| self.matches(r)).collect();
+        if let Some(limit) = self.limit {
+            let skip = matched.len().saturating_sub(limit);
+            matched.drain(..skip);
+        }
+        matched
+    }
+}
+
+/// One line per record for the terminal
+pub fn format_record(record: &AuditRecord) -> String {
+    let outcome = if record.success {
+        match record.confirmation {
+            Confirmation::Confirmed => "ok, confirmed",
+            Confirmation::NotSeen => "ok, not seen",
+            Confirmation::Unknown => "ok",
+        }
+    } else {
+        "FAILED"
+    };
+    let text = match (&record.text, &record.text_sha256) {
+        (Some(text), _) => format!("{:?}", text),
+        (None, Some(hash)) => format!("sha256:{}", hash),
+        (None, None) => "(text omitted)".to_string(),
+    };
+    let mut line = format!(
+        "{}  {:<20} {:<24} {:>6}ms  {:<14} {} chars  {}",
+        record
+            .timestamp
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S"),
+        record.app_id,
+        record.method.map(|m| m.name()).unwrap_or("-"),
+        record.latency_ms,
+        outcome,
+        record.chars,
+        text
+    );
+    if let Some(error) = &record.error {
+        line.push_str(&format!("  ({})", error));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_is_kept_as_the_mode_allows() {
+        let full = AuditRecord::new("kate", "hello", AuditTextMode::Full);
+        assert_eq!(full.text.as_deref(), Some("hello"));
+        assert!(full.text_sha256.is_none());
+
+        let hashed = AuditRecord::new("kate", "hello", AuditTextMode::Hashed);
+        assert!(hashed.text.is_none());
+        assert_eq!(
+            hashed.text_sha256.as_deref(),
+            Some("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+        );
+
+        let omitted = AuditRecord::new("kate", "hello", AuditTextMode::Omitted);
+        assert!(omitted.text.is_none() && omitted.text_sha256.is_none());
+        assert_eq!(omitted.chars, 5);
+        // Nothing of the text reaches the serialized line
+        assert!(!serde_json::to_string(&omitted).unwrap().contains("hello"));
+    }
+
+    #[test]
+    fn rotates_by_size_and_reads_back_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let log = AuditLog::new(&path).with_rotation(1, 2);
+
+        for app in ["a", "b", "c", "d"] {
+            log.append(&AuditRecord::new(app, "x", AuditTextMode::Omitted))
+                .unwrap();
+        }
+        // One record per file; the oldest fell off
+        assert!(rotated_path(&path, 2).exists());
+        assert!(!rotated_path(&path, 3).exists());
+        let apps: Vec<String> = read_records(&path)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.app_id)
+            .collect();
+        assert_eq!(apps, ["b", "c", "d"]);
+    }
+
+    #[test]
+    fn query_filters_by_app_outcome_and_limit() {
+        let mut records: Vec<AuditRecord> = ["kate", "firefox", "Kate", "kate"]
+            .iter()
+            .map(|app| AuditRecord::new(app, "x", AuditTextMode::Omitted))
+            .collect();
+        records[3].success = false;
+
+        let query = AuditQuery {
+            app: Some("kate".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(query.apply(records.clone()).len(), 3);
+
+        let query = AuditQuery {
+            app: Some("kate".to_string()),
+            failures_only: true,
+            ..Default::default()
+        };
+        assert_eq!(query.apply(records.clone()).len(), 1);
+
+        let query = AuditQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let newest = query.apply(records);
+        assert_eq!(newest[0].app_id, "Kate");
+        assert!(!newest[1].success);
+    }
+}
@@ -113,6 +113,12 @@ impl BackendDetector {
         ]
     }
 
+    /// No display server to inject into (a TTY or SSH session); only
+    /// transcription can run
+    pub fn is_headless(&self) -> bool {
+        !(self.is_wayland() || self.is_x11() || self.is_macos() || self.is_windows())
+    }
+
     /// Check if running on Wayland
     fn is_wayland(&self) -> bool {
         env::var("XDG_SESSION_TYPE")
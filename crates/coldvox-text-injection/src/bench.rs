@@ -0,0 +1,302 @@
+//! Live injection benchmark against the bundled GTK test app.
+//!
+//! For every registered backend and text size, each run launches a fresh
+//! `gtk_test_app`, injects through that backend only and polls the app's
+//! output file until the text appears. Latency runs from the injection call
+//! to the text being visible; a run fails when the injector errors or the
+//! exact text does not show up within the timeout.
+//!
+//! Needs a desktop session and the test app, which is built by
+//! `cargo build -p coldvox-text-injection --features real-injection-tests`
+//! (set `COLDVOX_GTK_TEST_APP` to use a copy elsewhere).
+
+use crate::manager::StrategyManager;
+use crate::types::{InjectionConfig, InjectionMethod, InjectionMetrics};
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Text sizes measured when none are given
+pub const DEFAULT_SIZES: [usize; 4] = [10, 100, 1_000, 10_000];
+
+/// How long the test app may take to create its ready file
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(5);
+/// Pause after the window appears so it can take focus
+const FOCUS_SETTLE: Duration = Duration::from_millis(300);
+/// Output file poll interval
+const POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    /// `gtk_test_app` executable
+    pub target_app: PathBuf,
+    /// Text sizes in characters
+    pub sizes: Vec<usize>,
+    /// Runs per backend and size
+    pub iterations: u32,
+    /// How long injected text may take to appear
+    pub timeout: Duration,
+    /// Methods to measure (every registered one except NoOp when empty)
+    pub methods: Vec<InjectionMethod>,
+}
+
+impl BenchOptions {
+    pub fn new(target_app: PathBuf) -> Self {
+        Self {
+            target_app,
+            sizes: DEFAULT_SIZES.to_vec(),
+            iterations: 5,
+            timeout: Duration::from_secs(10),
+            methods: Vec::new(),
+        }
+    }
+}
+
+/// Results for one backend at one text size
+#[derive(Debug, Clone)]
+pub struct BenchRow {
+    pub method: InjectionMethod,
+    pub backend: &'static str,
+    pub chars: usize,
+    pub runs: u32,
+    pub successes: u32,
+    /// Latencies of successful runs
+    pub latencies: Vec<Duration>,
+    pub last_error: Option<String>,
+}
+
+impl BenchRow {
+    pub fn success_rate(&self) -> f64 {
+        if self.runs == 0 {
+            0.0
+        } else {
+            f64::from(self.successes) / f64::from(self.runs)
+        }
+    }
+
+    /// Nearest-rank percentile (0-100) of successful runs
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        let mut sorted = self.latencies.clone();
+        sorted.sort_unstable();
+        let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.clamp(1, sorted.len()) - 1])
+    }
+}
+
+/// The bundled test app: `COLDVOX_GTK_TEST_APP` at runtime, else the copy
+/// built alongside this crate
+pub fn gtk_test_app_path() -> Option<PathBuf> {
+    std::env::var_os("COLDVOX_GTK_TEST_APP")
+        .map(PathBuf::from)
+        .or_else(|| option_env!("COLDVOX_GTK_TEST_APP").map(PathBuf::from))
+        .filter(|path| path.exists())
+}
+
+/// Measure every selected backend at every size
+pub async fn run(config: InjectionConfig, opts: &BenchOptions) -> anyhow::Result<Vec<BenchRow>> {
+    if !opts.target_app.exists() {
+        anyhow::bail!("test app not found at {}", opts.target_app.display());
+    }
+    let metrics = Arc::new(InjectionMetrics::default());
+    let manager = StrategyManager::new(config, metrics).await;
+
+    let backends: Vec<_> = manager
+        .list_backends()
+        .await
+        .into_iter()
+        .filter(|b| b.available && b.method != InjectionMethod::NoOp)
+        .filter(|b| opts.methods.is_empty() || opts.methods.contains(&b.method))
+        .collect();
+    if backends.is_empty() {
+        anyhow::bail!("no injection backend available to benchmark");
+    }
+
+    let mut rows = Vec::new();
+    for backend in &backends {
+        for &chars in &opts.sizes {
+            let text = sample_text(chars);
+            let mut row = BenchRow {
+                method: backend.method,
+                backend: backend.name,
+                chars,
+                runs: 0,
+                successes: 0,
+                latencies: Vec::new(),
+                last_error: None,
+            };
+            for _ in 0..opts.iterations {
+                row.runs += 1;
+                match run_once(&manager, backend.method, &text, opts).await {
+                    Ok(latency) => {
+                        row.successes += 1;
+                        row.latencies.push(latency);
+                    }
+                    Err(e) => {
+                        debug!(method = %backend.method, chars, "Benchmark run failed: {}", e);
+                        row.last_error = Some(e.to_string());
+                    }
+                }
+            }
+            info!(
+                method = %backend.method,
+                chars,
+                successes = row.successes,
+                runs = row.runs,
+                "Benchmarked injection backend"
+            );
+            rows.push(row);
+        }
+    }
+    Ok(rows)
+}
+
+async fn run_once(
+    manager: &StrategyManager,
+    method: InjectionMethod,
+    text: &str,
+    opts: &BenchOptions,
+) -> anyhow::Result<Duration> {
+    let app = TargetApp::launch(&opts.target_app).await?;
+    tokio::time::sleep(FOCUS_SETTLE).await;
+
+    let start = Instant::now();
+    manager.inject_with_method(method, text).await?;
+    loop {
+        if fs::read_to_string(&app.output_file).is_ok_and(|content| content == text) {
+            return Ok(start.elapsed());
+        }
+        if start.elapsed() > opts.timeout {
+            anyhow::bail!("text did not appear within {:?}", opts.timeout);
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Comparison table, one line per backend and size
+pub fn render_table(rows: &[BenchRow]) -> String {
+    let ms = |d: Option<Duration>| match d {
+        Some(d) => format!("{:.1}", d.as_secs_f64() * 1000.0),
+        None => "-".to_string(),
+    };
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:<24} {:<18} {:>6} {:>5} {:>8} {:>9} {:>9}",
+        "Method", "Backend", "Chars", "Runs", "Success", "p50 ms", "p95 ms"
+    );
+    for row in rows {
+        let _ = writeln!(
+            out,
+            "{:<24} {:<18} {:>6} {:>5} {:>7.0}% {:>9} {:>9}",
+            row.method.name(),
+            row.backend,
+            row.chars,
+            row.runs,
+            row.success_rate() * 100.0,
+            ms(row.percentile(50.0)),
+            ms(row.percentile(95.0))
+        );
+    }
+    let failures: Vec<&BenchRow> = rows.iter().filter(|r| r.last_error.is_some()).collect();
+    if !failures.is_empty() {
+        let _ = writeln!(out, "\nLast errors:");
+        for row in failures {
+            let _ = writeln!(
+                out,
+                "  {} @ {} chars: {}",
+                row.method.name(),
+                row.chars,
+                row.last_error.as_deref().unwrap_or_default()
+            );
+        }
+    }
+    out
+}
+
+/// Deterministic single-line text of exactly `chars` characters (the test
+/// app uses a single-line entry)
+pub fn sample_text(chars: usize) -> String {
+    const WORDS: &str = "the quick brown fox jumps over the lazy dog ";
+    WORDS.chars().cycle().take(chars).collect()
+}
+
+/// Running test app; killed and cleaned up on drop
+struct TargetApp {
+    process: Child,
+    output_file: PathBuf,
+}
+
+impl TargetApp {
+    async fn launch(exe: &Path) -> anyhow::Result<Self> {
+        let process = Command::new(exe)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let output_file = PathBuf::from(format!("/tmp/coldvox_gtk_test_{}.txt", process.id()));
+        let app = Self {
+            process,
+            output_file,
+        };
+
+        let start = Instant::now();
+        while !app.output_file.exists() {
+            if start.elapsed() > LAUNCH_TIMEOUT {
+                anyhow::bail!("test app did not start within {:?}", LAUNCH_TIMEOUT);
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        Ok(app)
+    }
+}
+
+impl Drop for TargetApp {
+    fn drop(&mut self) {
+        if let Err(e) = self.process.kill() {
+            warn!("Failed to stop test app {}: {}", self.process.id(), e);
+        }
+        let _ = self.process.wait();
+        let _ = fs::remove_file(&self.output_file);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_text_has_exact_length() {
+        for chars in DEFAULT_SIZES {
+            let text = sample_text(chars);
+            assert_eq!(text.chars().count(), chars);
+            assert!(!text.contains('\n'));
+        }
+    }
+
+    #[test]
+    fn table_reports_rate_and_percentiles() {
+        let row = BenchRow {
+            method: InjectionMethod::AtspiInsert,
+            backend: "atspi",
+            chars: 100,
+            runs: 4,
+            successes: 3,
+            latencies: [30, 10, 20].map(Duration::from_millis).to_vec(),
+            last_error: Some("text did not appear".to_string()),
+        };
+        assert_eq!(row.percentile(50.0), Some(Duration::from_millis(20)));
+        assert_eq!(row.percentile(95.0), Some(Duration::from_millis(30)));
+
+        let table = render_table(&[row]);
+        assert!(table.contains("AtspiInsert"));
+        assert!(table.contains("75%"));
+        assert!(table.contains("20.0"));
+        assert!(table.contains("AtspiInsert @ 100 chars: text did not appear"));
+    }
+}
@@ -0,0 +1,383 @@
+//! Spoken editing commands ("enter", "undo", ...) and their low-latency path.
+//!
+//! Dictation waits for the final transcript and the session's silence timeout
+//! before anything is typed. Short command utterances skip that: once the same
+//! command has been recognized in enough consecutive partials, and the
+//! utterance is still short, its key chord is sent straight away and the
+//! matching final transcript is swallowed. A final that turns out to say
+//! something else is not typed after the chord; it is held for the user.
+//!
+//! In buffered dictation the held draft has its own commands
+//! ([`BufferCommand`]): they edit or flush the draft instead of pressing keys
+//! in the target application.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+// Linux input keycodes (linux/input-event-codes.h)
+const KEY_ESC: u16 = 1;
+const KEY_BACKSPACE: u16 = 14;
+const KEY_TAB: u16 = 15;
+const KEY_Y: u16 = 21;
+const KEY_ENTER: u16 = 28;
+const KEY_LEFTCTRL: u16 = 29;
+const KEY_A: u16 = 30;
+const KEY_Z: u16 = 44;
+
+/// A spoken command mapped to a key chord
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VoiceCommand {
+    Enter,
+    Tab,
+    Escape,
+    Backspace,
+    Undo,
+    Redo,
+    SelectAll,
+}
+
+impl VoiceCommand {
+    pub const ALL: [VoiceCommand; 7] = [
+        VoiceCommand::Enter,
+        VoiceCommand::Tab,
+        VoiceCommand::Escape,
+        VoiceCommand::Backspace,
+        VoiceCommand::Undo,
+        VoiceCommand::Redo,
+        VoiceCommand::SelectAll,
+    ];
+
+    /// Normalized phrases that trigger this command
+    pub fn phrases(&self) -> &'static [&'static str] {
+        match self {
+            VoiceCommand::Enter => &["enter", "press enter", "new line", "newline"],
+            VoiceCommand::Tab => &["tab", "press tab"],
+            VoiceCommand::Escape => &["escape", "press escape"],
+            VoiceCommand::Backspace => &["backspace", "press backspace"],
+            VoiceCommand::Undo => &["undo", "undo that"],
+            VoiceCommand::Redo => &["redo", "redo that"],
+            VoiceCommand::SelectAll => &["select all"],
+        }
+    }
+
+    /// Key chord as Linux input keycodes, pressed in order
+    pub fn key_chord(&self) -> &'static [u16] {
+        match self {
+            VoiceCommand::Enter => &[KEY_ENTER],
+            VoiceCommand::Tab => &[KEY_TAB],
+            VoiceCommand::Escape => &[KEY_ESC],
+            VoiceCommand::Backspace => &[KEY_BACKSPACE],
+            VoiceCommand::Undo => &[KEY_LEFTCTRL, KEY_Z],
+            VoiceCommand::Redo => &[KEY_LEFTCTRL, KEY_Y],
+            VoiceCommand::SelectAll => &[KEY_LEFTCTRL, KEY_A],
+        }
+    }
+
+    /// Match a whole utterance against the command grammar. Case, surrounding
+    /// whitespace and punctuation are ignored; anything else must match exactly.
+    pub fn parse(text: &str) -> Option<Self> {
+        let normalized = normalize(text);
+        if normalized.is_empty() {
+            return None;
+        }
+        Self::ALL
+            .into_iter()
+            .find(|cmd| cmd.phrases().contains(&normalized.as_str()))
+    }
+}
+
+impl fmt::Display for VoiceCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.phrases()[0])
+    }
+}
+
+/// A spoken command that edits or flushes the buffered dictation draft
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BufferCommand {
+    /// Inject the draft
+    Flush,
+    /// Drop the most recent utterance
+    ScratchThat,
+    /// Remove the last word
+    DeleteWord,
+    /// Discard the whole draft
+    Clear,
+}
+
+impl BufferCommand {
+    pub const ALL: [BufferCommand; 4] = [
+        BufferCommand::Flush,
+        BufferCommand::ScratchThat,
+        BufferCommand::DeleteWord,
+        BufferCommand::Clear,
+    ];
+
+    /// Normalized phrases that trigger this command
+    pub fn phrases(&self) -> &'static [&'static str] {
+        match self {
+            BufferCommand::Flush => &["send it", "insert it", "flush draft"],
+            BufferCommand::ScratchThat => &["scratch that", "delete that"],
+            BufferCommand::DeleteWord => &["delete last word", "delete word"],
+            BufferCommand::Clear => &["clear draft", "discard draft"],
+        }
+    }
+
+    /// Match a whole utterance; key-chord commands with a draft equivalent
+    /// ("undo", "backspace") map onto it.
+    pub fn parse(text: &str) -> Option<Self> {
+        let normalized = normalize(text);
+        if normalized.is_empty() {
+            return None;
+        }
+        Self::ALL
+            .into_iter()
+            .find(|cmd| cmd.phrases().contains(&normalized.as_str()))
+            .or_else(|| match VoiceCommand::parse(text)? {
+                VoiceCommand::Undo => Some(BufferCommand::ScratchThat),
+                VoiceCommand::Backspace => Some(BufferCommand::DeleteWord),
+                _ => None,
+            })
+    }
+}
+
+impl fmt::Display for BufferCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.phrases()[0])
+    }
+}
+
+pub(crate) fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// What to do with a final transcript
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinalDisposition {
+    /// Not a command: buffer it for injection as usual
+    Text,
+    /// A command the fast path did not catch: send it now
+    Dispatch(VoiceCommand),
+    /// Already sent from partials: drop the final
+    Handled,
+    /// Sent from partials, but the final says something else: hold the final
+    /// instead of typing it after the chord
+    Unconfirmed(VoiceCommand),
+}
+
+#[derive(Debug)]
+struct Tracking {
+    utterance_id: u64,
+    started: Instant,
+    candidate: Option<VoiceCommand>,
+    stable: u32,
+    fired: Option<VoiceCommand>,
+}
+
+/// Tracks partials of the current utterance and decides when a command can be
+/// dispatched before final decoding.
+#[derive(Debug)]
+pub struct CommandFastPath {
+    max_duration: Duration,
+    min_stable_partials: u32,
+    current: Option<Tracking>,
+}
+
+impl CommandFastPath {
+    pub fn new(max_duration: Duration, min_stable_partials: u32) -> Self {
+        Self {
+            max_duration,
+            min_stable_partials: min_stable_partials.max(1),
+            current: None,
+        }
+    }
+
+    fn tracking(&mut self, utterance_id: u64, now: Instant) -> &mut Tracking {
+        if self
+            .current
+            .as_ref()
+            .is_none_or(|t| t.utterance_id != utterance_id)
+        {
+            self.current = Some(Tracking {
+                utterance_id,
+                started: now,
+                candidate: None,
+                stable: 0,
+                fired: None,
+            });
+        }
+        self.current.as_mut().expect("tracking initialized above")
+    }
+
+    /// Feed a partial. Returns the command to send now, at most once per
+    /// utterance. `t1` is the partial's end offset in seconds when the STT
+    /// backend reports it; otherwise time since the first partial is used.
+    pub fn on_partial(
+        &mut self,
+        utterance_id: u64,
+        text: &str,
+        t1: Option<f32>,
+        now: Instant,
+    ) -> Option<VoiceCommand> {
+        let max_duration = self.max_duration;
+        let min_stable = self.min_stable_partials;
+        let tracking = self.tracking(utterance_id, now);
+        if tracking.fired.is_some() {
+            return None;
+        }
+
+        let elapsed = t1
+            .filter(|t| t.is_finite() && *t >= 0.0)
+            .map(Duration::from_secs_f32)
+            .unwrap_or_else(|| now.saturating_duration_since(tracking.started));
+        if elapsed > max_duration {
+            // Too long to be a command; let the final decide
+            tracking.candidate = None;
+            tracking.stable = 0;
+            return None;
+        }
+
+        match VoiceCommand::parse(text) {
+            Some(cmd) if tracking.candidate == Some(cmd) => tracking.stable += 1,
+            Some(cmd) => {
+                tracking.candidate = Some(cmd);
+                tracking.stable = 1;
+            }
+            None => {
+                tracking.candidate = None;
+                tracking.stable = 0;
+            }
+        }
+
+        if tracking.stable >= min_stable {
+            tracking.fired = tracking.candidate;
+            return tracking.fired;
+        }
+        None
+    }
+
+    /// Feed the final transcript of an utterance.
+    pub fn on_final(&mut self, utterance_id: u64, text: &str) -> FinalDisposition {
+        let fired = self
+            .current
+            .take()
+            .filter(|t| t.utterance_id == utterance_id)
+            .and_then(|t| t.fired);
+        match (fired, VoiceCommand::parse(text)) {
+            (Some(sent), Some(cmd)) if sent == cmd => FinalDisposition::Handled,
+            (Some(sent), _) => {
+                tracing::warn!(
+                    "Fast-path command '{}' was sent but the final transcript differs",
+                    sent
+                );
+                FinalDisposition::Unconfirmed(sent)
+            }
+            (None, Some(cmd)) => FinalDisposition::Dispatch(cmd),
+            (None, None) => FinalDisposition::Text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_whole_utterances_only() {
+        assert_eq!(VoiceCommand::parse("Enter."), Some(VoiceCommand::Enter));
+        assert_eq!(
+            VoiceCommand::parse("  undo that! "),
+            Some(VoiceCommand::Undo)
+        );
+        assert_eq!(
+            VoiceCommand::parse("Select all"),
+            Some(VoiceCommand::SelectAll)
+        );
+        assert_eq!(VoiceCommand::parse("enter the room"), None);
+        assert_eq!(VoiceCommand::parse(""), None);
+    }
+
+    #[test]
+    fn buffer_commands_cover_draft_edits() {
+        assert_eq!(BufferCommand::parse("Send it."), Some(BufferCommand::Flush));
+        assert_eq!(
+            BufferCommand::parse("scratch that"),
+            Some(BufferCommand::ScratchThat)
+        );
+        assert_eq!(
+            BufferCommand::parse("Undo that"),
+            Some(BufferCommand::ScratchThat)
+        );
+        assert_eq!(
+            BufferCommand::parse("backspace"),
+            Some(BufferCommand::DeleteWord)
+        );
+        assert_eq!(BufferCommand::parse("enter"), None);
+        assert_eq!(BufferCommand::parse("send it to me"), None);
+    }
+
+    #[test]
+    fn fires_once_after_stable_partials() {
+        let mut fast = CommandFastPath::new(Duration::from_millis(1500), 2);
+        let t = Instant::now();
+        assert_eq!(fast.on_partial(1, "enter", None, t), None);
+        assert_eq!(
+            fast.on_partial(1, "Enter", None, t + Duration::from_millis(100)),
+            Some(VoiceCommand::Enter)
+        );
+        assert_eq!(
+            fast.on_partial(1, "enter", None, t + Duration::from_millis(200)),
+            None
+        );
+        assert_eq!(fast.on_final(1, "Enter."), FinalDisposition::Handled);
+    }
+
+    #[test]
+    fn long_or_changing_utterances_do_not_fire() {
+        let mut fast = CommandFastPath::new(Duration::from_millis(1000), 2);
+        let t = Instant::now();
+        fast.on_partial(1, "undo", None, t);
+        assert_eq!(fast.on_partial(1, "undo it", None, t), None);
+        assert_eq!(fast.on_partial(1, "undo", None, t), None);
+        assert_eq!(fast.on_final(1, "undo it all"), FinalDisposition::Text);
+
+        // Reported end offset beyond the limit disqualifies the fast path,
+        // but a matching final is still dispatched
+        fast.on_partial(2, "tab", Some(1.5), t);
+        assert_eq!(fast.on_partial(2, "tab", Some(1.6), t), None);
+        assert_eq!(
+            fast.on_final(2, "tab"),
+            FinalDisposition::Dispatch(VoiceCommand::Tab)
+        );
+    }
+
+    #[test]
+    fn finals_that_contradict_a_sent_command_are_not_text() {
+        let mut fast = CommandFastPath::new(Duration::from_millis(1500), 2);
+        let t = Instant::now();
+        fast.on_partial(1, "undo", None, t);
+        assert_eq!(
+            fast.on_partial(1, "undo", None, t + Duration::from_millis(100)),
+            Some(VoiceCommand::Undo)
+        );
+        assert_eq!(
+            fast.on_final(1, "undo the last paragraph"),
+            FinalDisposition::Unconfirmed(VoiceCommand::Undo)
+        );
+
+        // A different command in the final is not sent on top of the first
+        fast.on_partial(2, "tab", None, t);
+        fast.on_partial(2, "tab", None, t);
+        assert_eq!(
+            fast.on_final(2, "escape"),
+            FinalDisposition::Unconfirmed(VoiceCommand::Tab)
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! Focus backends for wlroots compositors, over their IPC sockets.
+//!
+//! AT-SPI focus tracking doesn't cover every toolkit, and without it wlroots
+//! sessions fall back to an unknown focus, which either blocks injection or
+//! skips the allow/block lists (app id "unknown"). Hyprland and sway both
+//! report the focused window over IPC:
+//!
+//! - Hyprland: `j/activewindow` on `$XDG_RUNTIME_DIR/hypr/$HYPRLAND_INSTANCE_SIGNATURE/.socket.sock`
+//!   (`/tmp/hypr/...` before Hyprland 0.40)
+//! - sway: i3-IPC `GET_SEATS` and `GET_TREE` on `$SWAYSOCK`
+//!
+//! The compositor knows which window has keyboard focus but not which widget
+//! inside it, so a focused client window is reported as
+//! [`FocusStatus::EditableText`] and no window at all (an empty workspace) as
+//! [`FocusStatus::NonEditable`]. The page URL still comes from AT-SPI.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use coldvox_foundation::error::InjectionError;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::UnixStream;
+use tracing::debug;
+
+use crate::focus::{FocusBackend, FocusStatus, FocusedWindow, SystemFocusAdapter};
+use crate::window_manager;
+
+/// Upper bound for one IPC round trip
+const IPC_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Focus backend for the running compositor, when it has one
+pub fn detect() -> Option<Arc<dyn FocusBackend>> {
+    if let Some(hyprland) = HyprlandFocus::from_env() {
+        debug!("Using Hyprland IPC for focus tracking");
+        return Some(Arc::new(hyprland));
+    }
+    if let Some(sway) = SwayFocus::from_env() {
+        debug!("Using sway IPC for focus tracking");
+        return Some(Arc::new(sway));
+    }
+    None
+}
+
+fn ipc_error(context: &str, e: impl std::fmt::Display) -> InjectionError {
+    InjectionError::Process(format!("{}: {}", context, e))
+}
+
+async fn with_timeout<T>(
+    future: impl std::future::Future<Output = Result<T, InjectionError>>,
+) -> Result<T, InjectionError> {
+    tokio::time::timeout(IPC_TIMEOUT, future)
+        .await
+        .map_err(|_| InjectionError::Timeout(IPC_TIMEOUT.as_millis() as u64))?
+}
+
+fn status_for(window: &Option<FocusedWindow>) -> FocusStatus {
+    match window {
+        Some(_) => FocusStatus::EditableText,
+        None => FocusStatus::NonEditable,
+    }
+}
+
+/// Hyprland's request socket
+pub struct HyprlandFocus {
+    socket: PathBuf,
+}
+
+impl HyprlandFocus {
+    pub fn new(socket: PathBuf) -> Self {
+        Self { socket }
+    }
+
+    /// The socket of the Hyprland instance this session runs in
+    pub fn from_env() -> Option<Self> {
+        let signature = std::env::var("HYPRLAND_INSTANCE_SIGNATURE").ok()?;
+        let runtime_dir = std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from);
+        runtime_dir
+            .into_iter()
+            .map(|dir| dir.join("hypr"))
+            .chain(std::iter::once(PathBuf::from("/tmp/hypr")))
+            .map(|dir| dir.join(&signature).join(".socket.sock"))
+            .find(|path| path.exists())
+            .map(Self::new)
+    }
+
+    async fn request(&self, command: &str) -> Result<serde_json::Value, InjectionError> {
+        with_timeout(async {
+            let mut stream = UnixStream::connect(&self.socket)
+                .await
+                .map_err(|e| ipc_error("Hyprland socket connect failed", e))?;
+            stream
+                .write_all(command.as_bytes())
+                .await
+                .map_err(|e| ipc_error("Hyprland request failed", e))?;
+            // Hyprland answers and closes the connection
+            let mut reply = Vec::new();
+            stream
+                .read_to_end(&mut reply)
+                .await
+                .map_err(|e| ipc_error("Hyprland reply failed", e))?;
+            serde_json::from_slice(&reply)
+                .map_err(|e| InjectionError::Other(format!("Invalid Hyprland reply: {}", e)))
+        })
+        .await
+    }
+
+    async fn active_window(&self) -> Result<Option<FocusedWindow>, InjectionError> {
+        Ok(parse_hyprland_active_window(
+            &self.request("j/activewindow").await?,
+        ))
+    }
+}
+
+/// `j/activewindow` replies `{}` when no window has focus
+fn parse_hyprland_active_window(json: &serde_json::Value) -> Option<FocusedWindow> {
+    let app_id = json
+        .get("class")
+        .and_then(|v| v.as_str())
+        .filter(|class| !class.is_empty())
+        .or_else(|| json.get("initialClass").and_then(|v| v.as_str()))
+        .filter(|class| !class.is_empty())?;
+    Some(FocusedWindow {
+        app_id: app_id.to_string(),
+        title: json
+            .get("title")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+    })
+}
+
+#[async_trait]
+impl FocusBackend for HyprlandFocus {
+    async fn query_focus(&self) -> Result<FocusStatus, InjectionError> {
+        Ok(status_for(&self.active_window().await?))
+    }
+
+    async fn query_focused_url(&self) -> Result<Option<String>, InjectionError> {
+        SystemFocusAdapter.query_focused_url().await
+    }
+
+    async fn query_focused_window(&self) -> Result<Option<FocusedWindow>, InjectionError> {
+        self.active_window().await
+    }
+}
+
+/// i3-IPC message types used here
+const I3_GET_TREE: u32 = 4;
+const I3_GET_SEATS: u32 = 101;
+
+/// sway's i3-IPC socket
+pub struct SwayFocus {
+    socket: PathBuf,
+    seat: String,
+}
+
+impl SwayFocus {
+    pub fn new(socket: PathBuf, seat: String) -> Self {
+        Self { socket, seat }
+    }
+
+    /// `$SWAYSOCK`, following this session's seat
+    pub fn from_env() -> Option<Self> {
+        let socket = PathBuf::from(std::env::var_os("SWAYSOCK")?);
+        socket
+            .exists()
+            .then(|| Self::new(socket, window_manager::current_seat()))
+    }
+
+    async fn focused_window(&self) -> Result<Option<FocusedWindow>, InjectionError> {
+        // Older sway versions without GET_SEATS still flag the focused node
+        let seat_focus = i3_ipc_request(&self.socket, I3_GET_SEATS)
+            .await
+            .ok()
+            .and_then(|seats| window_manager::sway_seat_focus(&seats, &self.seat));
+        let tree = i3_ipc_request(&self.socket, I3_GET_TREE).await?;
+        Ok(window_manager::find_sway_node(&tree, seat_focus)
+            .and_then(|(node, _)| sway_window(node)))
+    }
+}
+
+/// The window behind a sway tree node; workspaces and outputs have no pid
+fn sway_window(node: &serde_json::Value) -> Option<FocusedWindow> {
+    node.get("pid")?;
+    let app_id = node
+        .get("app_id")
+        .and_then(|v| v.as_str())
+        .or_else(|| {
+            node.get("window_properties")
+                .and_then(|p| p.get("class"))
+                .and_then(|v| v.as_str())
+        })
+        .filter(|id| !id.is_empty())?;
+    Some(FocusedWindow {
+        app_id: app_id.to_string(),
+        title: node
+            .get("name")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+    })
+}
+
+/// One i3-IPC round trip: `i3-ipc`, payload length and type (native endian),
+/// then the payload; the reply uses the same framing.
+async fn i3_ipc_request(
+    socket: &Path,
+    message_type: u32,
+) -> Result<serde_json::Value, InjectionError> {
+    with_timeout(async {
+        let mut stream = UnixStream::connect(socket)
+            .await
+            .map_err(|e| ipc_error("sway socket connect failed", e))?;
+        let mut message = Vec::with_capacity(14);
+        message.extend_from_slice(b"i3-ipc");
+        message.extend_from_slice(&0u32.to_ne_bytes());
+        message.extend_from_slice(&message_type.to_ne_bytes());
+        stream
+            .write_all(&message)
+            .await
+            .map_err(|e| ipc_error("sway request failed", e))?;
+
+        let mut header = [0u8; 14];
+        stream
+            .read_exact(&mut header)
+            .await
+            .map_err(|e| ipc_error("sway reply failed", e))?;
+        if &header[..6] != b"i3-ipc" {
+            return Err(InjectionError::Other(
+                "Invalid i3-IPC reply header".to_string(),
+            ));
+        }
+        let length = u32::from_ne_bytes([header[6], header[7], header[8], header[9]]) as usize;
+        let mut payload = vec![0u8; length];
+        stream
+            .read_exact(&mut payload)
+            .await
+            .map_err(|e| ipc_error("sway reply failed", e))?;
+        serde_json::from_slice(&payload)
+            .map_err(|e| InjectionError::Other(format!("Invalid sway reply: {}", e)))
+    })
+    .await
+}
+
+#[async_trait]
+impl FocusBackend for SwayFocus {
+    async fn query_focus(&self) -> Result<FocusStatus, InjectionError> {
+        Ok(status_for(&self.focused_window().await?))
+    }
+
+    async fn query_focused_url(&self) -> Result<Option<String>, InjectionError> {
+        SystemFocusAdapter.query_focused_url().await
+    }
+
+    async fn query_focused_window(&self) -> Result<Option<FocusedWindow>, InjectionError> {
+        self.focused_window().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::UnixListener;
+
+    #[test]
+    fn hyprland_active_window_parses() {
+        let json = serde_json::json!({
+            "address": "0x55d0", "class": "kitty", "title": "~/src", "pid": 4242
+        });
+        assert_eq!(
+            parse_hyprland_active_window(&json),
+            Some(FocusedWindow {
+                app_id: "kitty".to_string(),
+                title: Some("~/src".to_string()),
+            })
+        );
+        assert_eq!(parse_hyprland_active_window(&serde_json::json!({})), None);
+    }
+
+    #[tokio::test]
+    async fn hyprland_backend_queries_the_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".socket.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 64];
+            let n = stream.read(&mut request).await.unwrap();
+            assert_eq!(&request[..n], b"j/activewindow");
+            stream
+                .write_all(br#"{"class":"firefox","title":"News"}"#)
+                .await
+                .unwrap();
+        });
+
+        let backend = HyprlandFocus::new(path);
+        let window = backend.query_focused_window().await.unwrap().unwrap();
+        assert_eq!(window.app_id, "firefox");
+        assert_eq!(window.title.as_deref(), Some("News"));
+    }
+
+    #[tokio::test]
+    async fn sway_backend_follows_the_seat_over_i3_ipc() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sway-ipc.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+        tokio::spawn(async move {
+            let seats = serde_json::json!([
+                {"name": "seat0", "focus": 10},
+                {"name": "seat1", "focus": 20}
+            ]);
+            let tree = serde_json::json!({
+                "id": 1, "type": "root", "nodes": [
+                    {"id": 2, "type": "output", "name": "DP-1", "nodes": [
+                        {"id": 10, "type": "con", "pid": 1, "app_id": "foot", "name": "shell", "focused": true},
+                        {"id": 20, "type": "con", "pid": 2, "app_id": null,
+                         "window_properties": {"class": "Gimp"}, "name": "GIMP"}
+                    ]}
+                ]
+            });
+            // One connection per request
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut header = [0u8; 14];
+                stream.read_exact(&mut header).await.unwrap();
+                let message_type =
+                    u32::from_ne_bytes([header[10], header[11], header[12], header[13]]);
+                let body = match message_type {
+                    I3_GET_SEATS => seats.to_string(),
+                    I3_GET_TREE => tree.to_string(),
+                    other => panic!("unexpected message type {}", other),
+                };
+                let mut reply = b"i3-ipc".to_vec();
+                reply.extend_from_slice(&(body.len() as u32).to_ne_bytes());
+                reply.extend_from_slice(&message_type.to_ne_bytes());
+                reply.extend_from_slice(body.as_bytes());
+                stream.write_all(&reply).await.unwrap();
+            }
+        });
+
+        let backend = SwayFocus::new(path.clone(), "seat1".to_string());
+        let window = backend.query_focused_window().await.unwrap().unwrap();
+        assert_eq!(window.app_id, "Gimp");
+        assert_eq!(
+            backend.query_focus().await.unwrap(),
+            FocusStatus::EditableText
+        );
+
+        let backend = SwayFocus::new(path, "seat0".to_string());
+        let window = backend.query_focused_window().await.unwrap().unwrap();
+        assert_eq!(window.app_id, "foot");
+        assert_eq!(window.title.as_deref(), Some("shell"));
+    }
+}
@@ -299,8 +299,8 @@ pub async fn text_changed(
                                 // Check if text has changed and matches our prefix
                                 if current_text != last_text {
                                     trace!(
-                                        old_text = %last_text,
-                                        new_text = %current_text,
+                                        old_text = %coldvox_foundation::scrub::scrub(&last_text),
+                                        new_text = %coldvox_foundation::scrub::scrub(&current_text),
                                         "Text content changed during polling"
                                     );
 
@@ -359,6 +359,76 @@ pub async fn text_changed(
     }
 }
 
+/// Full text of the focused editable element, or `None` when nothing
+/// editable has focus. Used to check whether typed text arrived intact.
+pub async fn focused_text() -> InjectionResult<Option<String>> {
+    #[cfg(feature = "atspi")]
+    {
+        use atspi::{
+            connection::AccessibilityConnection, proxy::collection::CollectionProxy,
+            proxy::text::TextProxy, Interface, MatchType, State,
+        };
+        use tokio::time;
+
+        let step_timeout = Duration::from_millis(50);
+        let conn = time::timeout(step_timeout, AccessibilityConnection::new())
+            .await
+            .map_err(|_| InjectionError::Timeout(50))?
+            .map_err(|e| InjectionError::Other(format!("AT-SPI connect failed: {e}")))?;
+        let zbus_conn = conn.connection();
+
+        let collection = CollectionProxy::builder(zbus_conn)
+            .destination("org.a11y.atspi.Registry")
+            .map_err(|e| InjectionError::Other(format!("CollectionProxy destination failed: {e}")))?
+            .path("/org/a11y/atspi/accessible/root")
+            .map_err(|e| InjectionError::Other(format!("CollectionProxy path failed: {e}")))?
+            .build()
+            .await
+            .map_err(|e| InjectionError::Other(format!("CollectionProxy build failed: {e}")))?;
+
+        let mut rule = atspi::ObjectMatchRule::default();
+        rule.states = State::Focused.into();
+        rule.states_mt = MatchType::All;
+        rule.ifaces = Interface::EditableText.into();
+        rule.ifaces_mt = MatchType::All;
+
+        let matches = time::timeout(
+            step_timeout,
+            collection.get_matches(rule, atspi::SortOrder::Canonical, 1, false),
+        )
+        .await
+        .map_err(|_| InjectionError::Timeout(50))?
+        .map_err(|e| InjectionError::Other(format!("Get matches failed: {e}")))?;
+        let Some(obj_ref) = matches.first() else {
+            return Ok(None);
+        };
+
+        let text_proxy = TextProxy::builder(zbus_conn)
+            .destination(
+                obj_ref
+                    .name()
+                    .ok_or_else(|| InjectionError::Other("Object has no name".to_string()))?
+                    .clone(),
+            )
+            .map_err(|e| InjectionError::Other(format!("TextProxy destination failed: {e}")))?
+            .path(obj_ref.path().clone())
+            .map_err(|e| InjectionError::Other(format!("TextProxy path failed: {e}")))?
+            .build()
+            .await
+            .map_err(|e| InjectionError::Other(format!("TextProxy build failed: {e}")))?;
+        let text = time::timeout(step_timeout, text_proxy.get_text(0, -1))
+            .await
+            .map_err(|_| InjectionError::Timeout(50))?
+            .map_err(|e| InjectionError::Other(format!("Get text failed: {e}")))?;
+        Ok(Some(text))
+    }
+
+    #[cfg(not(feature = "atspi"))]
+    {
+        Ok(None)
+    }
+}
+
 /// Utility function to create a confirmation context for injection operations
 pub fn create_confirmation_context(config: InjectionConfig) -> ConfirmationContext {
     ConfirmationContext {
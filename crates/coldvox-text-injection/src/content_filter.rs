@@ -0,0 +1,113 @@
+//! Secret/PII filter for dictated text.
+//!
+//! The built-in detectors live in [`coldvox_foundation::scrub`] (card numbers,
+//! email addresses, API-key-looking strings) so every crate can scrub its
+//! logs. This adds `sensitive_patterns`: regular expressions with feature
+//! `regex`, substrings without it. `sensitive_content` decides what happens to
+//! a final transcript with a match before injection
+//! ([`SensitiveContentAction`](crate::types::SensitiveContentAction)).
+
+pub use coldvox_foundation::scrub::{describe, scrub, SensitiveKind, SensitiveMatch};
+use coldvox_foundation::scrub::{find_sensitive, merge_matches, redact_matches};
+
+#[derive(Debug)]
+enum CustomPattern {
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+    #[cfg(not(feature = "regex"))]
+    Substring(String),
+}
+
+/// Built-in detectors plus `sensitive_patterns`
+#[derive(Debug, Default)]
+pub struct ContentFilter {
+    custom: Vec<CustomPattern>,
+}
+
+impl ContentFilter {
+    /// Invalid regex patterns are logged and skipped
+    pub fn new(patterns: &[String]) -> Self {
+        let patterns = patterns.iter().filter(|p| !p.is_empty());
+        #[cfg(feature = "regex")]
+        let custom = patterns
+            .filter_map(|pattern| match regex::Regex::new(pattern) {
+                Ok(regex) => Some(CustomPattern::Regex(regex)),
+                Err(e) => {
+                    tracing::warn!(
+                        "Invalid sensitive_patterns regex '{}': {}, skipping",
+                        pattern,
+                        e
+                    );
+                    None
+                }
+            })
+            .collect();
+        #[cfg(not(feature = "regex"))]
+        let custom = patterns
+            .map(|pattern| CustomPattern::Substring(pattern.clone()))
+            .collect();
+        Self { custom }
+    }
+
+    /// All matches, ordered by position, overlaps merged into the first
+    pub fn find(&self, text: &str) -> Vec<SensitiveMatch> {
+        let mut matches = find_sensitive(text);
+        for pattern in &self.custom {
+            match pattern {
+                #[cfg(feature = "regex")]
+                CustomPattern::Regex(regex) => {
+                    matches.extend(regex.find_iter(text).map(|m| SensitiveMatch {
+                        kind: SensitiveKind::Custom,
+                        range: m.range(),
+                    }))
+                }
+                #[cfg(not(feature = "regex"))]
+                CustomPattern::Substring(needle) => {
+                    matches.extend(text.match_indices(needle.as_str()).map(|(start, s)| {
+                        SensitiveMatch {
+                            kind: SensitiveKind::Custom,
+                            range: start..start + s.len(),
+                        }
+                    }))
+                }
+            }
+        }
+        merge_matches(matches)
+    }
+
+    /// Distinct kinds found in `text`
+    pub fn kinds(&self, text: &str) -> Vec<SensitiveKind> {
+        let mut kinds: Vec<SensitiveKind> = self.find(text).into_iter().map(|m| m.kind).collect();
+        kinds.sort();
+        kinds.dedup();
+        kinds
+    }
+
+    /// `text` with every match replaced by `[redacted <kind>]`
+    pub fn redact(&self, text: &str) -> String {
+        redact_matches(text, &self.find(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_patterns_join_the_built_ins() {
+        let filter = ContentFilter::new(&["Project Nightjar".to_string()]);
+        assert_eq!(
+            filter.redact("Project Nightjar status to bob@example.org"),
+            "[redacted sensitive pattern] status to [redacted email address]"
+        );
+        assert_eq!(
+            filter.kinds("Project Nightjar and Project Nightjar"),
+            vec![SensitiveKind::Custom]
+        );
+        // Logs are scrubbed of built-in matches only
+        assert_eq!(
+            scrub("Project Nightjar status to bob@example.org"),
+            "Project Nightjar status to [redacted email address]"
+        );
+    }
+}
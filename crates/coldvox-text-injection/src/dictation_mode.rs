@@ -0,0 +1,470 @@
+//! Dictation modes for identifiers, codes and email addresses.
+//!
+//! Ordinary dictation suits prose but mangles "x7 dash b" or a phone number.
+//! The processor applies the active mode to each final transcript before it
+//! is buffered:
+//!
+//! - **spell**: letters are typed one by one and joined without spaces. NATO
+//!   words ("alpha", "bravo") and single letters both count; "capital" or
+//!   "cap" uppercases the next letter; digits and a few symbol words ("dot",
+//!   "at", "dash", "underscore", "slash", "space") are understood, so "john at
+//!   example dot com" types `john@example.com`.
+//! - **number**: number words become digits ("twenty four" → `24`, "five five
+//!   five one two" → `55512`, "three point one four" → `3.14`); other words are
+//!   left alone.
+//! - **literal**: the transcript is typed exactly as recognized. Voice commands
+//!   other than mode switches are not interpreted either.
+//!
+//! Modes are switched by saying "spell mode", "number mode", "literal mode" or
+//! "normal mode" (with `voice_commands` on), by the `cycle_dictation_mode`
+//! hotkey, or set at startup with `dictation_mode`.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands;
+
+/// How final transcripts are turned into text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DictationMode {
+    /// Dictated text as recognized, voice commands interpreted
+    #[default]
+    Normal,
+    /// Letters (NATO alphabet or single letters), digits and symbols, no spaces
+    Spell,
+    /// Number words as digits
+    Number,
+    /// No post-processing and no voice commands besides mode switches
+    Literal,
+}
+
+impl DictationMode {
+    pub const ALL: [DictationMode; 4] = [
+        DictationMode::Normal,
+        DictationMode::Spell,
+        DictationMode::Number,
+        DictationMode::Literal,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DictationMode::Normal => "normal",
+            DictationMode::Spell => "spell",
+            DictationMode::Number => "number",
+            DictationMode::Literal => "literal",
+        }
+    }
+
+    /// Normalized phrases that switch to this mode
+    pub fn phrases(&self) -> &'static [&'static str] {
+        match self {
+            DictationMode::Normal => &["normal mode", "dictation mode"],
+            DictationMode::Spell => &["spell mode", "spelling mode"],
+            DictationMode::Number => &["number mode", "numbers mode"],
+            DictationMode::Literal => &["literal mode"],
+        }
+    }
+
+    /// The mode after this one, for the cycle hotkey
+    pub fn next(&self) -> Self {
+        match self {
+            DictationMode::Normal => DictationMode::Spell,
+            DictationMode::Spell => DictationMode::Number,
+            DictationMode::Number => DictationMode::Literal,
+            DictationMode::Literal => DictationMode::Normal,
+        }
+    }
+
+    /// Whether voice commands other than mode switches are interpreted
+    pub fn interprets_commands(&self) -> bool {
+        *self != DictationMode::Literal
+    }
+
+    /// Match a whole utterance against the mode switch phrases
+    pub fn parse_switch(text: &str) -> Option<Self> {
+        let normalized = commands::normalize(text);
+        if normalized.is_empty() {
+            return None;
+        }
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.phrases().contains(&normalized.as_str()))
+    }
+
+    /// Text to buffer for a final transcript in this mode
+    pub fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let converted = match self {
+            DictationMode::Normal | DictationMode::Literal => return Cow::Borrowed(text),
+            DictationMode::Spell => spell(text),
+            DictationMode::Number => numbers(text),
+        };
+        if converted == text {
+            Cow::Borrowed(text)
+        } else {
+            Cow::Owned(converted)
+        }
+    }
+}
+
+impl fmt::Display for DictationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for DictationMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "normal" | "" => Ok(DictationMode::Normal),
+            "spell" => Ok(DictationMode::Spell),
+            "number" => Ok(DictationMode::Number),
+            "literal" => Ok(DictationMode::Literal),
+            other => Err(format!("unknown dictation mode '{}'", other)),
+        }
+    }
+}
+
+const NATO: [(&str, char); 29] = [
+    ("alfa", 'a'),
+    ("alpha", 'a'),
+    ("bravo", 'b'),
+    ("charlie", 'c'),
+    ("delta", 'd'),
+    ("echo", 'e'),
+    ("foxtrot", 'f'),
+    ("golf", 'g'),
+    ("hotel", 'h'),
+    ("india", 'i'),
+    ("juliet", 'j'),
+    ("juliett", 'j'),
+    ("kilo", 'k'),
+    ("lima", 'l'),
+    ("mike", 'm'),
+    ("november", 'n'),
+    ("oscar", 'o'),
+    ("papa", 'p'),
+    ("quebec", 'q'),
+    ("romeo", 'r'),
+    ("sierra", 's'),
+    ("tango", 't'),
+    ("uniform", 'u'),
+    ("victor", 'v'),
+    ("whiskey", 'w'),
+    ("whisky", 'w'),
+    ("xray", 'x'),
+    ("yankee", 'y'),
+    ("zulu", 'z'),
+];
+
+const SYMBOLS: [(&str, char); 11] = [
+    ("dot", '.'),
+    ("period", '.'),
+    ("at", '@'),
+    ("dash", '-'),
+    ("hyphen", '-'),
+    ("underscore", '_'),
+    ("slash", '/'),
+    ("plus", '+'),
+    ("colon", ':'),
+    ("hash", '#'),
+    ("space", ' '),
+];
+
+const UNITS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+const TEENS: [&str; 10] = [
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// A token split into leading punctuation, the word, and trailing punctuation
+fn split_token(token: &str) -> (&str, &str, &str) {
+    let start = token.find(char::is_alphanumeric).unwrap_or(token.len());
+    let end = token[start..]
+        .char_indices()
+        .rfind(|(_, c)| c.is_alphanumeric())
+        .map_or(start, |(i, c)| start + i + c.len_utf8());
+    (&token[..start], &token[start..end], &token[end..])
+}
+
+/// Append `word`, uppercasing its first letter if "capital" came before it
+fn push_spelled(out: &mut String, word: &str, capital: &mut bool) {
+    let mut chars = word.chars();
+    if let Some(first) = chars.next() {
+        if std::mem::take(capital) {
+            out.extend(first.to_uppercase());
+        } else {
+            out.push(first);
+        }
+        out.push_str(chars.as_str());
+    }
+}
+
+fn spell(text: &str) -> String {
+    let mut out = String::new();
+    let mut capital = false;
+    for token in text.split_whitespace() {
+        let (_, kept, _) = split_token(token);
+        // "x-ray" and "X-ray" as well as "xray"
+        let word = kept.to_lowercase().replace('-', "");
+        if word.is_empty() {
+            continue;
+        }
+        if matches!(word.as_str(), "capital" | "cap" | "uppercase") {
+            capital = true;
+            continue;
+        }
+        if let Some(&(_, letter)) = NATO.iter().find(|(name, _)| *name == word) {
+            push_spelled(&mut out, &letter.to_string(), &mut capital);
+        } else if let Some(&(_, symbol)) = SYMBOLS.iter().find(|(name, _)| *name == word) {
+            out.push(symbol);
+        } else if let Some(digit) = UNITS.iter().position(|unit| *unit == word) {
+            out.push_str(&digit.to_string());
+        } else if word.chars().count() == 1 {
+            push_spelled(&mut out, &word, &mut capital);
+        } else {
+            // Anything else is typed as said, e.g. "john" in an address
+            push_spelled(&mut out, kept, &mut capital);
+        }
+    }
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NumberWord {
+    Unit(u64),
+    Teen(u64),
+    Tens(u64),
+    Hundred,
+    Scale(u64),
+}
+
+fn number_word(word: &str) -> Option<NumberWord> {
+    if let Some(n) = UNITS.iter().position(|w| *w == word) {
+        return Some(NumberWord::Unit(n as u64));
+    }
+    if let Some(n) = TEENS.iter().position(|w| *w == word) {
+        return Some(NumberWord::Teen(10 + n as u64));
+    }
+    if let Some(n) = TENS.iter().position(|w| *w == word) {
+        return Some(NumberWord::Tens(20 + 10 * n as u64));
+    }
+    match word {
+        "hundred" => Some(NumberWord::Hundred),
+        "thousand" => Some(NumberWord::Scale(1_000)),
+        "million" => Some(NumberWord::Scale(1_000_000)),
+        "billion" => Some(NumberWord::Scale(1_000_000_000)),
+        _ => None,
+    }
+}
+
+/// Digits of a run of number words. A word that cannot extend the current
+/// number starts another one right after it, so digit-by-digit dictation
+/// ("five five five") and year-style pairs ("nineteen eighty four") come out
+/// as the digits that were said.
+#[derive(Debug, Default)]
+struct NumberRun {
+    digits: String,
+    total: u64,
+    current: u64,
+    last: Option<NumberWord>,
+}
+
+impl NumberRun {
+    fn is_empty(&self) -> bool {
+        self.digits.is_empty() && self.last.is_none()
+    }
+
+    fn extends(&self, word: NumberWord) -> bool {
+        use NumberWord::*;
+        match (self.last, word) {
+            (None, _) => true,
+            (Some(Unit(0)), _) => false,
+            (Some(Tens(_) | Hundred | Scale(_)), Unit(n)) => n > 0,
+            (Some(Hundred | Scale(_)), Teen(_) | Tens(_)) => true,
+            (Some(Unit(_) | Teen(_) | Tens(_)), Hundred) => self.current < 100,
+            (Some(Unit(_) | Teen(_) | Tens(_) | Hundred), Scale(scale)) => {
+                self.current > 0 && (self.total == 0 || self.total >= scale * 1000)
+            }
+            _ => false,
+        }
+    }
+
+    fn push(&mut self, word: NumberWord) {
+        if !self.extends(word) {
+            self.end_number();
+        }
+        match word {
+            NumberWord::Unit(n) | NumberWord::Teen(n) | NumberWord::Tens(n) => self.current += n,
+            NumberWord::Hundred => self.current = self.current.max(1) * 100,
+            NumberWord::Scale(scale) => {
+                self.total += self.current.max(1) * scale;
+                self.current = 0;
+            }
+        }
+        self.last = Some(word);
+    }
+
+    /// "point" between numbers
+    fn push_point(&mut self) {
+        self.end_number();
+        self.digits.push('.');
+    }
+
+    fn end_number(&mut self) {
+        if self.last.is_some() {
+            self.digits
+                .push_str(&(self.total + self.current).to_string());
+        }
+        self.total = 0;
+        self.current = 0;
+        self.last = None;
+    }
+
+    fn finish(&mut self) -> String {
+        self.end_number();
+        std::mem::take(&mut self.digits)
+    }
+}
+
+fn numbers(text: &str) -> String {
+    let tokens: Vec<(&str, &str, &str)> = text.split_whitespace().map(split_token).collect();
+    // Number words of each token; empty unless the whole word is one
+    let words: Vec<Vec<NumberWord>> = tokens
+        .iter()
+        .map(|(_, word, _)| {
+            // "twenty-four" is two number words
+            word.to_lowercase()
+                .split('-')
+                .map(number_word)
+                .collect::<Option<Vec<_>>>()
+                .unwrap_or_default()
+        })
+        .collect();
+    let continues_run = |i: usize| {
+        tokens
+            .get(i)
+            .is_some_and(|(lead, _, _)| lead.is_empty() && !words[i].is_empty())
+    };
+
+    let mut out: Vec<String> = Vec::new();
+    let mut run = NumberRun::default();
+    // Punctuation before the first word of the run
+    let mut run_lead = "";
+    for (i, &(lead, word, trail)) in tokens.iter().enumerate() {
+        if !words[i].is_empty() {
+            if !lead.is_empty() && !run.is_empty() {
+                out.push(format!("{}{}", run_lead, run.finish()));
+            }
+            if run.is_empty() {
+                run_lead = lead;
+            }
+            for &number in &words[i] {
+                run.push(number);
+            }
+            if !trail.is_empty() {
+                out.push(format!("{}{}{}", run_lead, run.finish(), trail));
+            }
+            continue;
+        }
+        if !run.is_empty() && lead.is_empty() && trail.is_empty() && continues_run(i + 1) {
+            match word.to_lowercase().as_str() {
+                "point" => {
+                    run.push_point();
+                    continue;
+                }
+                "and" if matches!(run.last, Some(NumberWord::Hundred | NumberWord::Scale(_))) => {
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        if !run.is_empty() {
+            out.push(format!("{}{}", run_lead, run.finish()));
+        }
+        out.push(format!("{}{}{}", lead, word, trail));
+    }
+    if !run.is_empty() {
+        out.push(format!("{}{}", run_lead, run.finish()));
+    }
+    out.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spell_mode_joins_letters() {
+        let spell = |text| DictationMode::Spell.apply(text).into_owned();
+        assert_eq!(spell("Alpha bravo capital Charlie 7 dash x-ray"), "abC7-x");
+        assert_eq!(spell("A, B, C."), "abc");
+        assert_eq!(spell("john at example dot com"), "john@example.com");
+        assert_eq!(spell("cap delta one two underscore echo"), "D12_e");
+    }
+
+    #[test]
+    fn number_mode_writes_digits() {
+        let number = |text| DictationMode::Number.apply(text).into_owned();
+        assert_eq!(number("call five five five one two"), "call 55512");
+        assert_eq!(
+            number("I owe you twenty-four dollars."),
+            "I owe you 24 dollars."
+        );
+        assert_eq!(number("nineteen eighty four"), "1984");
+        assert_eq!(number("one hundred and five"), "105");
+        assert_eq!(number("two thousand twenty four"), "2024");
+        assert_eq!(number("pi is three point one four"), "pi is 3.14");
+        assert_eq!(number("zero zero seven, shaken"), "007, shaken");
+        assert_eq!(number("a point of order"), "a point of order");
+        assert_eq!(number("one, two and three"), "1, 2 and 3");
+    }
+
+    #[test]
+    fn literal_and_normal_leave_text_alone() {
+        for mode in [DictationMode::Normal, DictationMode::Literal] {
+            assert!(matches!(
+                mode.apply("alpha twenty four"),
+                Cow::Borrowed("alpha twenty four")
+            ));
+        }
+        assert!(!DictationMode::Literal.interprets_commands());
+    }
+
+    #[test]
+    fn parses_switch_phrases_and_cycles() {
+        assert_eq!(
+            DictationMode::parse_switch("Spell mode."),
+            Some(DictationMode::Spell)
+        );
+        assert_eq!(
+            DictationMode::parse_switch("numbers mode"),
+            Some(DictationMode::Number)
+        );
+        assert_eq!(DictationMode::parse_switch("spell mode please"), None);
+        let mut mode = DictationMode::Normal;
+        for _ in DictationMode::ALL {
+            mode = mode.next();
+        }
+        assert_eq!(mode, DictationMode::Normal);
+    }
+}
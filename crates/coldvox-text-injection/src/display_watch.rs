@@ -0,0 +1,140 @@
+//! Display-server restart detection.
+//!
+//! When the Wayland compositor or X server goes away (compositor crash,
+//! logout/login of a nested session), every backend keeps a dead connection.
+//! The injection worker polls the display socket named by `WAYLAND_DISPLAY`
+//! or `DISPLAY`; while it is unreachable injection is held, and once it
+//! accepts connections again the backends are rebuilt.
+
+use crate::types::InjectionConfig;
+use std::env;
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Display-server transitions seen by [`DisplayWatch`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayEvent {
+    /// The display socket stopped accepting connections
+    Lost,
+    /// The display socket is reachable again
+    Restored,
+}
+
+/// Socket of the display server this process was started against, if it is
+/// local. Wayland wins when both are set, matching backend detection.
+pub fn display_socket() -> Option<PathBuf> {
+    if let Ok(name) = env::var("WAYLAND_DISPLAY") {
+        let path = PathBuf::from(&name);
+        if path.is_absolute() {
+            return Some(path);
+        }
+        return env::var_os("XDG_RUNTIME_DIR").map(|dir| PathBuf::from(dir).join(name));
+    }
+    env::var("DISPLAY").ok().and_then(|d| x11_socket(&d))
+}
+
+/// `/tmp/.X11-unix/X<n>` for a local `DISPLAY`; None for remote displays
+fn x11_socket(display: &str) -> Option<PathBuf> {
+    let (host, rest) = display.split_once(':')?;
+    if !host.is_empty() && host != "unix" {
+        return None;
+    }
+    let number = rest.split('.').next()?;
+    if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(PathBuf::from(format!("/tmp/.X11-unix/X{number}")))
+}
+
+/// Whether the display server accepts connections (None when there is no
+/// local display to check)
+pub fn display_reachable() -> Option<bool> {
+    display_socket().map(|path| UnixStream::connect(path).is_ok())
+}
+
+/// Tracks display-server reachability between polls
+#[derive(Debug)]
+pub struct DisplayWatch {
+    interval: Option<Duration>,
+    down: bool,
+}
+
+impl DisplayWatch {
+    /// Watch at `display_watch_interval_ms` (0 disables watching)
+    pub fn new(config: &InjectionConfig) -> Self {
+        Self {
+            interval: (config.display_watch_interval_ms > 0)
+                .then(|| Duration::from_millis(config.display_watch_interval_ms)),
+            down: false,
+        }
+    }
+
+    /// Probe the display socket and report a transition, if any
+    pub fn poll(&mut self) -> Option<DisplayEvent> {
+        self.interval?;
+        self.observe(display_reachable())
+    }
+
+    fn observe(&mut self, reachable: Option<bool>) -> Option<DisplayEvent> {
+        let down = !reachable?;
+        if down == self.down {
+            return None;
+        }
+        self.down = down;
+        if down {
+            warn!("Display server is unreachable; holding injection until the session returns");
+            Some(DisplayEvent::Lost)
+        } else {
+            info!("Display server is reachable again; rebuilding injection backends");
+            Some(DisplayEvent::Restored)
+        }
+    }
+
+    /// Whether injection is being held for a missing display server
+    pub fn is_down(&self) -> bool {
+        self.down
+    }
+
+    /// Wait until the next poll is due (forever when watching is disabled)
+    pub async fn tick(&self) {
+        match self.interval {
+            Some(interval) => tokio::time::sleep(interval).await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn x11_socket_only_for_local_displays() {
+        assert_eq!(x11_socket(":0"), Some(PathBuf::from("/tmp/.X11-unix/X0")));
+        assert_eq!(
+            x11_socket(":12.1"),
+            Some(PathBuf::from("/tmp/.X11-unix/X12"))
+        );
+        assert_eq!(
+            x11_socket("unix:3"),
+            Some(PathBuf::from("/tmp/.X11-unix/X3"))
+        );
+        assert_eq!(x11_socket("remote:0"), None);
+        assert_eq!(x11_socket("garbage"), None);
+    }
+
+    #[test]
+    fn reports_only_transitions() {
+        let mut watch = DisplayWatch::new(&InjectionConfig::default());
+        assert_eq!(watch.observe(Some(true)), None);
+        assert_eq!(watch.observe(Some(false)), Some(DisplayEvent::Lost));
+        assert!(watch.is_down());
+        assert_eq!(watch.observe(Some(false)), None);
+        // No local display to check says nothing either way
+        assert_eq!(watch.observe(None), None);
+        assert_eq!(watch.observe(Some(true)), Some(DisplayEvent::Restored));
+        assert!(!watch.is_down());
+    }
+}
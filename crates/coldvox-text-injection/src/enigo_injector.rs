@@ -1,3 +1,4 @@
+use crate::keysym::{self, TypingReach};
 use crate::types::{InjectionConfig, InjectionResult};
 use crate::TextInjector;
 use async_trait::async_trait;
@@ -32,6 +33,12 @@ impl EnigoInjector {
 
     /// Type text using enigo
     async fn type_text(&self, text: &str) -> Result<(), InjectionError> {
+        if let Some(c) = keysym::first_untypeable(text, TypingReach::Keysym) {
+            return Err(InjectionError::MethodFailed(format!(
+                "enigo cannot type {:?}; use clipboard paste",
+                c
+            )));
+        }
         let text_clone = text.to_string();
 
         let result = tokio::task::spawn_blocking(move || {
@@ -0,0 +1,136 @@
+//! User-facing feedback when dictated text could not be injected.
+//!
+//! Without this a failed injection only shows up in the logs and the text is
+//! gone. When every method fails (or fail-fast is about to exit) the text is
+//! put on the clipboard so the user can paste it themselves, and a desktop
+//! notification (feature `notifications`) says why injection failed. The
+//! notification never contains the dictated text. Text that was refused on
+//! purpose (injection paused, screen shared, application blocked) is not
+//! copied: the policy that refused it is also keeping it off the clipboard.
+
+use crate::injectors::unified_clipboard::UnifiedClipboardInjector;
+use crate::types::InjectionConfig;
+use coldvox_foundation::error::{InjectionError, Refusal};
+use tracing::{info, warn};
+
+/// Short, user-facing reason for an injection failure
+pub fn failure_reason(err: &InjectionError) -> String {
+    match err {
+        InjectionError::NoEditableFocus => "No editable text field has focus".to_string(),
+        InjectionError::MethodUnavailable(_) | InjectionError::MethodNotAvailable(_) => {
+            "No injection backend is available".to_string()
+        }
+        InjectionError::BudgetExhausted | InjectionError::Timeout(_) => {
+            "Injection timed out".to_string()
+        }
+        InjectionError::PermissionDenied(msg) => format!("Permission denied: {}", msg),
+        InjectionError::FocusUnknown => "Could not tell which window has focus".to_string(),
+        InjectionError::LockedTargetUnavailable => {
+            "The locked target window could not be brought to the front".to_string()
+        }
+        InjectionError::Refused(Refusal::Paused) => "Injection is paused".to_string(),
+        InjectionError::Refused(Refusal::ScreenShared) => {
+            "Injection is paused while the screen is shared".to_string()
+        }
+        InjectionError::Refused(Refusal::Blocked(_)) => {
+            "The focused application is blocked for injection".to_string()
+        }
+        InjectionError::MethodFailed(_) | InjectionError::AllMethodsFailed(_) => {
+            "Every injection method failed".to_string()
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Whether undelivered text may be put on the clipboard; not when a policy
+/// refused it
+fn may_recover(err: &InjectionError) -> bool {
+    !matches!(err, InjectionError::Refused(_))
+}
+
+/// Put `text` on the clipboard (when enabled and safe) and notify the user
+/// that it was not injected.
+pub async fn report_failed_injection(config: &InjectionConfig, text: &str, err: &InjectionError) {
+    if text.is_empty() {
+        return;
+    }
+    let reason = failure_reason(err);
+
+    let recovered = if config.recover_failed_text && may_recover(err) {
+        match UnifiedClipboardInjector::new(config.clone())
+            .write_clipboard(text.as_bytes(), "text/plain")
+            .await
+        {
+            Ok(()) => {
+                info!(
+                    "Copied {} undelivered characters to the clipboard",
+                    text.len()
+                );
+                true
+            }
+            Err(e) => {
+                warn!("Could not copy undelivered text to the clipboard: {}", e);
+                false
+            }
+        }
+    } else {
+        false
+    };
+
+    if config.notify_on_failure {
+        let body = if recovered {
+            format!("{}. Your dictation was copied to the clipboard.", reason)
+        } else {
+            format!("{}. Your dictation was not typed.", reason)
+        };
+        notify("Dictation not inserted", &body).await;
+    }
+}
+
+#[cfg(feature = "notifications")]
+async fn notify(summary: &str, body: &str) {
+    coldvox_foundation::notify::send(summary, body, coldvox_foundation::notify::Urgency::Normal)
+        .await;
+}
+
+#[cfg(not(feature = "notifications"))]
+async fn notify(summary: &str, body: &str) {
+    warn!("{}: {}", summary, body);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reasons_name_the_cause() {
+        assert_eq!(
+            failure_reason(&InjectionError::NoEditableFocus),
+            "No editable text field has focus"
+        );
+        assert_eq!(
+            failure_reason(&InjectionError::Refused(Refusal::Blocked(
+                "firefox".to_string()
+            ))),
+            "The focused application is blocked for injection"
+        );
+        assert_eq!(
+            failure_reason(&InjectionError::MethodFailed(
+                "All injection methods failed".to_string()
+            )),
+            "Every injection method failed"
+        );
+    }
+
+    #[test]
+    fn refused_text_is_not_recovered() {
+        assert!(!may_recover(&InjectionError::Refused(
+            Refusal::ScreenShared
+        )));
+        assert!(!may_recover(&InjectionError::Refused(Refusal::Blocked(
+            "keepassxc".to_string()
+        ))));
+        assert!(may_recover(&InjectionError::NoEditableFocus));
+        assert!(may_recover(&InjectionError::LockedTargetUnavailable));
+    }
+}
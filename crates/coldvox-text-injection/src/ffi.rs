@@ -0,0 +1,298 @@
+//! C ABI for the injection backends (feature `ffi`).
+//!
+//! Lets non-Rust dictation and accessibility tools drive the same backend
+//! ladder ([`StrategyManager`]) ColdVox uses. The header is generated by
+//! cbindgen into `$OUT_DIR/coldvox_inject.h` when the feature is on; build the
+//! library itself with `cargo rustc -p coldvox-text-injection --features ffi
+//! --release --crate-type cdylib`.
+//!
+//! ```c
+//! ColdvoxInjector *injector = coldvox_injector_new(NULL);
+//! ColdvoxStatus status = coldvox_inject_text(injector, "hello world");
+//! if (status != COLDVOX_STATUS_OK)
+//!     fprintf(stderr, "%s\n", coldvox_injector_last_error(injector));
+//! coldvox_injector_free(injector);
+//! ```
+//!
+//! An injector is not thread-safe: use one per thread or serialize calls.
+//! Every call blocks until the injection finished or failed.
+
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+
+use coldvox_foundation::error::InjectionError;
+
+use crate::manager::StrategyManager;
+use crate::types::{InjectionConfig, InjectionMetrics};
+
+/// Result of a call. 0 is success; new codes are only ever appended.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColdvoxStatus {
+    Ok = 0,
+    /// A null pointer, invalid UTF-8 or invalid configuration JSON
+    InvalidArgument = 1,
+    /// No focused element accepts text
+    NoEditableFocus = 2,
+    /// No backend is available for the focused application
+    MethodUnavailable = 3,
+    /// Every backend failed
+    AllMethodsFailed = 4,
+    /// The injection timed out or ran out of its time budget
+    Timeout = 5,
+    /// A backend was refused access (e.g. uinput, portal consent)
+    PermissionDenied = 6,
+    /// Clipboard could not be read or restored
+    Clipboard = 7,
+    /// Any other failure
+    Failed = 8,
+    /// The library panicked; the injector should be freed
+    Panic = 9,
+    /// Injection was declined on purpose (paused, screen shared, application
+    /// blocked)
+    Refused = 10,
+}
+
+impl ColdvoxStatus {
+    /// The status a raw code from C stands for, `None` for codes this build
+    /// does not know (e.g. from a newer header)
+    fn from_raw(code: c_int) -> Option<Self> {
+        const ALL: [ColdvoxStatus; 11] = [
+            ColdvoxStatus::Ok,
+            ColdvoxStatus::InvalidArgument,
+            ColdvoxStatus::NoEditableFocus,
+            ColdvoxStatus::MethodUnavailable,
+            ColdvoxStatus::AllMethodsFailed,
+            ColdvoxStatus::Timeout,
+            ColdvoxStatus::PermissionDenied,
+            ColdvoxStatus::Clipboard,
+            ColdvoxStatus::Failed,
+            ColdvoxStatus::Panic,
+            ColdvoxStatus::Refused,
+        ];
+        ALL.into_iter().find(|status| *status as c_int == code)
+    }
+}
+
+impl From<&InjectionError> for ColdvoxStatus {
+    fn from(error: &InjectionError) -> Self {
+        match error {
+            InjectionError::NoEditableFocus
+            | InjectionError::FocusUnknown
+            | InjectionError::TargetNotReady(_) => ColdvoxStatus::NoEditableFocus,
+            InjectionError::MethodNotAvailable(_) | InjectionError::MethodUnavailable(_) => {
+                ColdvoxStatus::MethodUnavailable
+            }
+            InjectionError::AllMethodsFailed(_) => ColdvoxStatus::AllMethodsFailed,
+            InjectionError::Timeout(_) | InjectionError::BudgetExhausted => ColdvoxStatus::Timeout,
+            InjectionError::PermissionDenied(_) => ColdvoxStatus::PermissionDenied,
+            InjectionError::Clipboard(_) | InjectionError::ClipboardBusy(_) => {
+                ColdvoxStatus::Clipboard
+            }
+            InjectionError::Refused(_) => ColdvoxStatus::Refused,
+            _ => ColdvoxStatus::Failed,
+        }
+    }
+}
+
+/// Opaque injector handle
+pub struct ColdvoxInjector {
+    runtime: tokio::runtime::Runtime,
+    manager: StrategyManager,
+    last_error: Option<CString>,
+}
+
+impl ColdvoxInjector {
+    fn fail(&mut self, status: ColdvoxStatus, message: impl Into<String>) -> ColdvoxStatus {
+        // Interior NULs would truncate the message; drop them
+        let message: String = message.into().chars().filter(|c| *c != '\0').collect();
+        self.last_error = CString::new(message).ok();
+        status
+    }
+}
+
+/// Create an injector. `config_json` is an `InjectionConfig` as JSON (missing
+/// fields take their defaults) or NULL for the defaults. Returns NULL when the
+/// configuration is invalid or the injector cannot start.
+///
+/// # Safety
+///
+/// `config_json` must be NULL or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn coldvox_injector_new(config_json: *const c_char) -> *mut ColdvoxInjector {
+    let config = if config_json.is_null() {
+        InjectionConfig::default()
+    } else {
+        let Ok(json) = CStr::from_ptr(config_json).to_str() else {
+            return std::ptr::null_mut();
+        };
+        match serde_json::from_str::<InjectionConfig>(json) {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::warn!("coldvox_injector_new: invalid configuration: {}", e);
+                return std::ptr::null_mut();
+            }
+        }
+    };
+    let created = catch_unwind(AssertUnwindSafe(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .ok()?;
+        let manager = runtime.block_on(StrategyManager::new(
+            config,
+            Arc::new(InjectionMetrics::default()),
+        ));
+        Some(ColdvoxInjector {
+            runtime,
+            manager,
+            last_error: None,
+        })
+    }));
+    match created {
+        Ok(Some(injector)) => Box::into_raw(Box::new(injector)),
+        _ => std::ptr::null_mut(),
+    }
+}
+
+/// Free an injector from [`coldvox_injector_new`]. NULL is ignored.
+///
+/// # Safety
+///
+/// `injector` must be NULL or a pointer from [`coldvox_injector_new`] that
+/// has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn coldvox_injector_free(injector: *mut ColdvoxInjector) {
+    if !injector.is_null() {
+        drop(Box::from_raw(injector));
+    }
+}
+
+/// Inject NUL-terminated UTF-8 `text` into the focused application, trying
+/// backends in the usual order
+///
+/// # Safety
+///
+/// `injector` must be NULL or a live pointer from [`coldvox_injector_new`]
+/// that no other thread is using; `text` must be NULL or NUL-terminated.
+#[no_mangle]
+pub unsafe extern "C" fn coldvox_inject_text(
+    injector: *mut ColdvoxInjector,
+    text: *const c_char,
+) -> ColdvoxStatus {
+    let Some(injector) = injector.as_mut() else {
+        return ColdvoxStatus::InvalidArgument;
+    };
+    injector.last_error = None;
+    if text.is_null() {
+        return injector.fail(ColdvoxStatus::InvalidArgument, "text is NULL");
+    }
+    let text = match CStr::from_ptr(text).to_str() {
+        Ok(text) => text,
+        Err(e) => return injector.fail(ColdvoxStatus::InvalidArgument, e.to_string()),
+    };
+    let ColdvoxInjector {
+        runtime, manager, ..
+    } = &mut *injector;
+    let result = catch_unwind(AssertUnwindSafe(|| runtime.block_on(manager.inject(text))));
+    match result {
+        Ok(Ok(())) => ColdvoxStatus::Ok,
+        Ok(Err(e)) => injector.fail(ColdvoxStatus::from(&e), e.to_string()),
+        Err(_) => injector.fail(ColdvoxStatus::Panic, "injection panicked"),
+    }
+}
+
+/// Message for the last failed call on `injector`, or NULL after a success.
+/// Valid until the next call on the same injector.
+///
+/// # Safety
+///
+/// `injector` must be NULL or a live pointer from [`coldvox_injector_new`].
+#[no_mangle]
+pub unsafe extern "C" fn coldvox_injector_last_error(
+    injector: *const ColdvoxInjector,
+) -> *const c_char {
+    injector
+        .as_ref()
+        .and_then(|i| i.last_error.as_ref())
+        .map_or(std::ptr::null(), |e| e.as_ptr())
+}
+
+/// Static description of the status code `status`
+///
+/// Takes a plain integer so codes added after this library was built are
+/// answered with "unknown status" instead of being undefined behaviour.
+#[no_mangle]
+pub extern "C" fn coldvox_status_message(status: c_int) -> *const c_char {
+    let Some(status) = ColdvoxStatus::from_raw(status) else {
+        return c"unknown status".as_ptr();
+    };
+    let message: &'static CStr = match status {
+        ColdvoxStatus::Ok => c"ok",
+        ColdvoxStatus::InvalidArgument => c"invalid argument",
+        ColdvoxStatus::NoEditableFocus => c"no editable focus",
+        ColdvoxStatus::MethodUnavailable => c"no injection method available",
+        ColdvoxStatus::AllMethodsFailed => c"all injection methods failed",
+        ColdvoxStatus::Timeout => c"injection timed out",
+        ColdvoxStatus::PermissionDenied => c"permission denied",
+        ColdvoxStatus::Clipboard => c"clipboard error",
+        ColdvoxStatus::Failed => c"injection failed",
+        ColdvoxStatus::Panic => c"internal error",
+        ColdvoxStatus::Refused => c"injection refused",
+    };
+    message.as_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_map_to_status_codes() {
+        assert_eq!(
+            ColdvoxStatus::from(&InjectionError::NoEditableFocus),
+            ColdvoxStatus::NoEditableFocus
+        );
+        assert_eq!(
+            ColdvoxStatus::from(&InjectionError::BudgetExhausted),
+            ColdvoxStatus::Timeout
+        );
+        assert_eq!(
+            ColdvoxStatus::from(&InjectionError::Refused(
+                coldvox_foundation::error::Refusal::ScreenShared
+            )),
+            ColdvoxStatus::Refused
+        );
+        assert_eq!(
+            ColdvoxStatus::from(&InjectionError::Other("x".into())),
+            ColdvoxStatus::Failed
+        );
+        let message = unsafe { CStr::from_ptr(coldvox_status_message(ColdvoxStatus::Ok as c_int)) };
+        assert_eq!(message.to_str().unwrap(), "ok");
+        let message =
+            unsafe { CStr::from_ptr(coldvox_status_message(ColdvoxStatus::Refused as c_int)) };
+        assert_eq!(message.to_str().unwrap(), "injection refused");
+    }
+
+    #[test]
+    fn unknown_status_codes_have_a_message() {
+        for code in [-1, 11, 4096] {
+            let message = unsafe { CStr::from_ptr(coldvox_status_message(code)) };
+            assert_eq!(message.to_str().unwrap(), "unknown status");
+        }
+    }
+
+    #[test]
+    fn null_and_invalid_arguments_are_rejected() {
+        unsafe {
+            assert_eq!(
+                coldvox_inject_text(std::ptr::null_mut(), c"hi".as_ptr()),
+                ColdvoxStatus::InvalidArgument
+            );
+            assert!(coldvox_injector_last_error(std::ptr::null()).is_null());
+            assert!(coldvox_injector_new(c"{not json".as_ptr()).is_null());
+            coldvox_injector_free(std::ptr::null_mut());
+        }
+    }
+}
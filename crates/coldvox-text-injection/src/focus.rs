@@ -7,11 +7,31 @@ use tracing::debug;
 #[async_trait]
 pub trait FocusProvider: Send + Sync {
     async fn get_focus_status(&mut self) -> Result<FocusStatus, InjectionError>;
+
+    /// URL of the page the focus is in, when it is inside a browser document
+    async fn get_focused_url(&mut self) -> Result<Option<String>, InjectionError> {
+        Ok(None)
+    }
+
+    /// The window with keyboard focus, for providers that can tell
+    async fn get_focused_window(&mut self) -> Result<Option<FocusedWindow>, InjectionError> {
+        Ok(None)
+    }
 }
 
 #[async_trait]
 pub trait FocusBackend: Send + Sync {
     async fn query_focus(&self) -> Result<FocusStatus, InjectionError>;
+
+    /// URL of the focused web document, if any
+    async fn query_focused_url(&self) -> Result<Option<String>, InjectionError> {
+        Ok(None)
+    }
+
+    /// The window with keyboard focus (None: no window, or not known)
+    async fn query_focused_window(&self) -> Result<Option<FocusedWindow>, InjectionError> {
+        Ok(None)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -21,6 +41,14 @@ pub enum FocusStatus {
     Unknown,
 }
 
+/// A window as the compositor identifies it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FocusedWindow {
+    /// Wayland app_id, or the X11 class for XWayland windows
+    pub app_id: String,
+    pub title: Option<String>,
+}
+
 pub struct FocusTracker<B: FocusBackend = SystemFocusAdapter> {
     config: InjectionConfig,
     backend: B,
@@ -72,6 +100,28 @@ impl<B: FocusBackend> FocusTracker<B> {
         }
     }
 
+    /// Not cached: a page can navigate without the focus changing
+    pub async fn get_focused_url(&self) -> Result<Option<String>, InjectionError> {
+        match self.backend.query_focused_url().await {
+            Ok(url) => Ok(url),
+            Err(err) => {
+                debug!("Focused URL query failed: {}", err);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Not cached, like the URL
+    pub async fn get_focused_window(&self) -> Result<Option<FocusedWindow>, InjectionError> {
+        match self.backend.query_focused_window().await {
+            Ok(window) => Ok(window),
+            Err(err) => {
+                debug!("Focused window query failed: {}", err);
+                Ok(None)
+            }
+        }
+    }
+
     pub fn config(&self) -> &InjectionConfig {
         &self.config
     }
@@ -82,6 +132,14 @@ impl<B: FocusBackend> FocusProvider for FocusTracker<B> {
     async fn get_focus_status(&mut self) -> Result<FocusStatus, InjectionError> {
         FocusTracker::get_focus_status(self).await
     }
+
+    async fn get_focused_url(&mut self) -> Result<Option<String>, InjectionError> {
+        FocusTracker::get_focused_url(self).await
+    }
+
+    async fn get_focused_window(&mut self) -> Result<Option<FocusedWindow>, InjectionError> {
+        FocusTracker::get_focused_window(self).await
+    }
 }
 
 #[derive(Default, Clone)]
@@ -94,6 +152,109 @@ impl FocusBackend for SystemFocusAdapter {
         // TODO(#38): Update to work with current atspi crate API
         Ok(FocusStatus::Unknown)
     }
+
+    async fn query_focused_url(&self) -> Result<Option<String>, InjectionError> {
+        #[cfg(feature = "atspi")]
+        return atspi_focused_document_url().await;
+        #[cfg(not(feature = "atspi"))]
+        Ok(None)
+    }
+}
+
+/// Most ancestors walked from the focused element to its document
+#[cfg(feature = "atspi")]
+const MAX_DOCUMENT_DEPTH: usize = 64;
+
+/// Walk up from the focused accessible to the enclosing web document and read
+/// its URL. Firefox and Chromium expose it as the `DocURL` document attribute
+/// (some toolkits use `URI`).
+#[cfg(feature = "atspi")]
+async fn atspi_focused_document_url() -> Result<Option<String>, InjectionError> {
+    use atspi::{
+        connection::AccessibilityConnection, proxy::accessible::AccessibleProxy,
+        proxy::collection::CollectionProxy, proxy::document::DocumentProxy, MatchType,
+        ObjectMatchRule, Role, SortOrder, State,
+    };
+
+    fn atspi_err(context: &str, e: impl std::fmt::Display) -> InjectionError {
+        InjectionError::Other(format!("{context}: {e}"))
+    }
+
+    let conn = AccessibilityConnection::new()
+        .await
+        .map_err(|e| atspi_err("AT-SPI connect failed", e))?;
+    let zbus_conn = conn.connection();
+
+    let collection = CollectionProxy::builder(zbus_conn)
+        .destination("org.a11y.atspi.Registry")
+        .map_err(|e| atspi_err("CollectionProxy destination failed", e))?
+        .path("/org/a11y/atspi/accessible/root")
+        .map_err(|e| atspi_err("CollectionProxy path failed", e))?
+        .build()
+        .await
+        .map_err(|e| atspi_err("CollectionProxy build failed", e))?;
+
+    let mut rule = ObjectMatchRule::default();
+    rule.states = State::Focused.into();
+    rule.states_mt = MatchType::All;
+    let Some(focused) = collection
+        .get_matches(rule, SortOrder::Canonical, 1, false)
+        .await
+        .map_err(|e| atspi_err("Collection.get_matches failed", e))?
+        .pop()
+    else {
+        return Ok(None);
+    };
+    let Some(app) = focused.name().cloned() else {
+        return Ok(None);
+    };
+
+    // Ancestors live in the same application, so only the path changes
+    let mut path = focused.path().clone();
+    for _ in 0..MAX_DOCUMENT_DEPTH {
+        let accessible = AccessibleProxy::builder(zbus_conn)
+            .destination(app.clone())
+            .map_err(|e| atspi_err("AccessibleProxy destination failed", e))?
+            .path(path.clone())
+            .map_err(|e| atspi_err("AccessibleProxy path failed", e))?
+            .build()
+            .await
+            .map_err(|e| atspi_err("AccessibleProxy build failed", e))?;
+
+        let role = accessible
+            .get_role()
+            .await
+            .map_err(|e| atspi_err("Accessible.get_role failed", e))?;
+        if role == Role::DocumentWeb {
+            let document = DocumentProxy::builder(zbus_conn)
+                .destination(app.clone())
+                .map_err(|e| atspi_err("DocumentProxy destination failed", e))?
+                .path(path.clone())
+                .map_err(|e| atspi_err("DocumentProxy path failed", e))?
+                .build()
+                .await
+                .map_err(|e| atspi_err("DocumentProxy build failed", e))?;
+            let attributes = document
+                .get_attributes()
+                .await
+                .map_err(|e| atspi_err("Document.get_attributes failed", e))?;
+            return Ok(attributes
+                .get("DocURL")
+                .or_else(|| attributes.get("URI"))
+                .filter(|url| !url.is_empty())
+                .cloned());
+        }
+
+        let parent = accessible
+            .parent()
+            .await
+            .map_err(|e| atspi_err("Accessible.parent failed", e))?;
+        if parent.is_null() || parent.path() == &path {
+            break;
+        }
+        path = parent.path().clone();
+    }
+    Ok(None)
 }
 
 #[async_trait]
@@ -104,4 +265,12 @@ where
     async fn query_focus(&self) -> Result<FocusStatus, InjectionError> {
         (**self).query_focus().await
     }
+
+    async fn query_focused_url(&self) -> Result<Option<String>, InjectionError> {
+        (**self).query_focused_url().await
+    }
+
+    async fn query_focused_window(&self) -> Result<Option<FocusedWindow>, InjectionError> {
+        (**self).query_focused_window().await
+    }
 }
@@ -0,0 +1,198 @@
+//! Size and rate guards for dictated text.
+//!
+//! A runaway STT result (a model repeating a phrase hundreds of times) would
+//! otherwise be typed into the focused application keystroke by keystroke.
+//! Three limits stand in the way, each off when set to 0:
+//!
+//! - `max_utterance_chars`: a longer final transcript is dropped.
+//! - `max_chars_per_minute`: an injection that would go over the budget of the
+//!   last 60 seconds is dropped.
+//! - `paste_above_chars`: longer text is pasted from the clipboard even when
+//!   `injection_mode` asks for keystrokes.
+//!
+//! Every limit that triggers is announced as a [`GuardEvent`].
+
+use crate::types::InjectionConfig;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Window `max_chars_per_minute` applies to
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// A guard limit that triggered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardEvent {
+    /// Utterance `utterance_id` was dropped for being longer than `limit`
+    UtteranceTooLong {
+        utterance_id: u64,
+        chars: usize,
+        limit: usize,
+    },
+    /// Text was dropped: `used` characters already went out in the last
+    /// minute and `chars` more would exceed `limit`
+    RateLimited {
+        chars: usize,
+        used: usize,
+        limit: usize,
+    },
+    /// Text longer than `threshold` is pasted instead of typed
+    SwitchedToPaste { chars: usize, threshold: usize },
+}
+
+impl GuardEvent {
+    /// Whether text was dropped (as opposed to delivered another way)
+    pub fn is_error(&self) -> bool {
+        !matches!(self, GuardEvent::SwitchedToPaste { .. })
+    }
+}
+
+impl std::fmt::Display for GuardEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GuardEvent::UtteranceTooLong {
+                utterance_id,
+                chars,
+                limit,
+            } => write!(
+                f,
+                "Dropped utterance {}: {} chars exceeds max_utterance_chars ({})",
+                utterance_id, chars, limit
+            ),
+            GuardEvent::RateLimited { chars, used, limit } => write!(
+                f,
+                "Dropped {} chars: {} already injected in the last minute (limit {})",
+                chars, used, limit
+            ),
+            GuardEvent::SwitchedToPaste { chars, threshold } => write!(
+                f,
+                "Pasting {} chars instead of typing (above {})",
+                chars, threshold
+            ),
+        }
+    }
+}
+
+/// Per-utterance size limit
+pub fn check_utterance(
+    config: &InjectionConfig,
+    utterance_id: u64,
+    text: &str,
+) -> Result<(), GuardEvent> {
+    let limit = config.max_utterance_chars as usize;
+    let chars = text.chars().count();
+    if limit > 0 && chars > limit {
+        return Err(GuardEvent::UtteranceTooLong {
+            utterance_id,
+            chars,
+            limit,
+        });
+    }
+    Ok(())
+}
+
+/// Whether `text` goes out by paste regardless of `injection_mode`
+pub fn paste_switch(config: &InjectionConfig, text: &str) -> Option<GuardEvent> {
+    let threshold = config.paste_above_chars as usize;
+    let chars = text.chars().count();
+    (threshold > 0 && chars > threshold).then_some(GuardEvent::SwitchedToPaste { chars, threshold })
+}
+
+/// Characters injected over the last minute
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: usize,
+    sent: VecDeque<(Instant, usize)>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &InjectionConfig) -> Self {
+        Self {
+            limit: config.max_chars_per_minute as usize,
+            sent: VecDeque::new(),
+        }
+    }
+
+    /// Count `text` against the budget, or reject it without counting
+    pub fn admit(&mut self, text: &str, now: Instant) -> Result<(), GuardEvent> {
+        if self.limit == 0 {
+            return Ok(());
+        }
+        while self
+            .sent
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) >= RATE_WINDOW)
+        {
+            self.sent.pop_front();
+        }
+        let chars = text.chars().count();
+        let used: usize = self.sent.iter().map(|(_, n)| n).sum();
+        if used + chars > self.limit {
+            return Err(GuardEvent::RateLimited {
+                chars,
+                used,
+                limit: self.limit,
+            });
+        }
+        self.sent.push_back((now, chars));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_apply_only_when_set() {
+        let mut config = InjectionConfig {
+            max_utterance_chars: 10,
+            paste_above_chars: 5,
+            ..Default::default()
+        };
+        assert!(check_utterance(&config, 1, "short").is_ok());
+        assert_eq!(
+            check_utterance(&config, 1, "much too long"),
+            Err(GuardEvent::UtteranceTooLong {
+                utterance_id: 1,
+                chars: 13,
+                limit: 10
+            })
+        );
+        assert!(paste_switch(&config, "short").is_none());
+        assert!(paste_switch(&config, "longer").is_some());
+
+        config.max_utterance_chars = 0;
+        config.paste_above_chars = 0;
+        assert!(check_utterance(&config, 1, &"a".repeat(100_000)).is_ok());
+        assert!(paste_switch(&config, &"a".repeat(100_000)).is_none());
+    }
+
+    #[test]
+    fn rate_limit_slides_over_a_minute() {
+        let config = InjectionConfig {
+            max_chars_per_minute: 10,
+            ..Default::default()
+        };
+        let mut limiter = RateLimiter::new(&config);
+        let start = Instant::now();
+
+        assert!(limiter.admit("123456", start).is_ok());
+        // Rejected text doesn't use up the budget
+        assert_eq!(
+            limiter.admit("123456", start + Duration::from_secs(1)),
+            Err(GuardEvent::RateLimited {
+                chars: 6,
+                used: 6,
+                limit: 10
+            })
+        );
+        assert!(limiter
+            .admit("1234", start + Duration::from_secs(2))
+            .is_ok());
+        assert!(limiter.admit("1", start + Duration::from_secs(59)).is_err());
+        // The first six characters have aged out
+        assert!(limiter
+            .admit("123456", start + Duration::from_secs(60))
+            .is_ok());
+    }
+}
@@ -0,0 +1,57 @@
+//! Outcomes of injection as they happen, for UIs and embedders.
+//!
+//! The processor announces each utterance it queues and the worker's result
+//! for it; the strategy manager announces every method it tries and the one
+//! that delivered. Events of one utterance arrive in order: `Queued`, one
+//! `Attempting` per method tried, then `Succeeded` or `Failed`.
+
+use crate::types::InjectionMethod;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Capacity of the injection event channel
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Something that happened to dictated text on its way to the target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InjectionEvent {
+    /// Text of `utterance_ids` is waiting for the injection worker
+    Queued {
+        utterance_ids: Vec<u64>,
+        chars: usize,
+    },
+    /// `method` is being tried
+    Attempting { method: InjectionMethod },
+    /// `method` delivered the text; `latency` covers every method tried
+    Succeeded {
+        method: InjectionMethod,
+        latency: Duration,
+    },
+    /// The text was not injected
+    Failed { reason: String },
+}
+
+impl InjectionEvent {
+    /// Sender for a new event channel
+    pub(crate) fn channel() -> broadcast::Sender<InjectionEvent> {
+        broadcast::channel(CHANNEL_CAPACITY).0
+    }
+}
+
+impl std::fmt::Display for InjectionEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InjectionEvent::Queued { chars, .. } => {
+                write!(f, "Queued {} characters for injection", chars)
+            }
+            InjectionEvent::Attempting { method } => write!(f, "Trying {}", method.name()),
+            InjectionEvent::Succeeded { method, latency } => write!(
+                f,
+                "Injected via {} in {} ms",
+                method.name(),
+                latency.as_millis()
+            ),
+            InjectionEvent::Failed { reason } => write!(f, "Injection failed: {}", reason),
+        }
+    }
+}
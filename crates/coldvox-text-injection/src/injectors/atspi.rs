@@ -10,11 +10,14 @@ use crate::log_throttle::log_atspi_connection_failure;
 #[allow(unused_imports)]
 use crate::logging::utils;
 use crate::types::{
-    InjectionConfig, InjectionContext, InjectionMethod, InjectionMode, InjectionResult,
+    AtspiTextAttributes, InjectionConfig, InjectionContext, InjectionMethod, InjectionMode,
+    InjectionResult,
 };
 use crate::TextInjector;
 use async_trait::async_trait;
 use coldvox_foundation::error::InjectionError;
+use std::collections::HashMap;
+use std::sync::Mutex;
 use std::time::Instant;
 use tracing::{debug, trace, warn};
 
@@ -25,6 +28,74 @@ use tracing::{debug, trace, warn};
 )]
 pub type Context = InjectionContext;
 
+/// Where an AT-SPI insertion lands, derived from the target's caret and
+/// selection state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(feature = "atspi"), allow(dead_code))]
+pub(crate) struct CaretPlan {
+    /// Range to delete before inserting (the selection being replaced)
+    pub delete: Option<(i32, i32)>,
+    /// Character offset to insert at
+    pub insert_at: i32,
+    /// Offset to move the caret to afterwards
+    pub caret_after: Option<i32>,
+}
+
+/// Plan a caret-relative insertion of `text_chars` characters.
+///
+/// `caret` is the AT-SPI caret offset (-1 when the object has no caret), in
+/// which case text is appended at `char_count`. Returns None when neither is
+/// usable.
+#[cfg_attr(not(feature = "atspi"), allow(dead_code))]
+pub(crate) fn plan_caret_insertion(
+    caret: i32,
+    char_count: Option<i32>,
+    selection: Option<(i32, i32)>,
+    text_chars: i32,
+    replace_selection: bool,
+    move_caret: bool,
+) -> Option<CaretPlan> {
+    let char_count = char_count.filter(|c| *c >= 0);
+    let caret = if caret >= 0 {
+        char_count.map_or(caret, |count| caret.min(count))
+    } else {
+        char_count?
+    };
+
+    let selection = selection
+        .filter(|_| replace_selection)
+        .map(|(a, b)| (a.min(b), a.max(b)))
+        .filter(|(start, end)| start < end && (*start..=*end).contains(&caret));
+
+    let insert_at = selection.map_or(caret, |(start, _)| start);
+    Some(CaretPlan {
+        delete: selection,
+        insert_at,
+        caret_after: move_caret.then_some(insert_at + text_chars),
+    })
+}
+
+/// Offset whose attributes text inserted at `insert_at` will pick up: the
+/// character before the insertion point, as when typing
+#[cfg_attr(not(feature = "atspi"), allow(dead_code))]
+pub(crate) fn attribute_probe_offset(insert_at: i32) -> i32 {
+    (insert_at - 1).max(0)
+}
+
+/// Formatting the inserted text is expected to end up with under `mode`, given
+/// the non-default attributes at the caret. None means no expectation.
+#[cfg_attr(not(feature = "atspi"), allow(dead_code))]
+pub(crate) fn expected_attributes(
+    mode: AtspiTextAttributes,
+    caret_attrs: &HashMap<String, String>,
+) -> Option<HashMap<String, String>> {
+    match mode {
+        AtspiTextAttributes::Inherit => None,
+        AtspiTextAttributes::Plain => Some(HashMap::new()),
+        AtspiTextAttributes::MatchCaret => Some(caret_attrs.clone()),
+    }
+}
+
 /// AT-SPI Text Injector with support for both insert and paste operations
 pub struct AtspiInjector {
     /// Configuration for injection
@@ -32,6 +103,9 @@ pub struct AtspiInjector {
     /// Confirmation context for injection verification
     #[allow(dead_code)]
     confirmation_context: ConfirmationContext,
+    /// Whether each application (by bus name) reports text attributes
+    #[cfg_attr(not(feature = "atspi"), allow(dead_code))]
+    attribute_support: Mutex<HashMap<String, bool>>,
 }
 
 impl AtspiInjector {
@@ -41,6 +115,7 @@ impl AtspiInjector {
         Self {
             config,
             confirmation_context,
+            attribute_support: Mutex::new(HashMap::new()),
         }
     }
 
@@ -185,21 +260,148 @@ impl AtspiInjector {
 
             trace!("Current caret position: {}", caret);
 
+            // Caret/selection state is best-effort: a failed query just means
+            // we insert at the raw caret offset
+            let char_count = time::timeout(per_method_timeout, text_iface.character_count())
+                .await
+                .ok()
+                .and_then(Result::ok);
+            let selection = if self.config.atspi_replace_selection {
+                let nselections = time::timeout(per_method_timeout, text_iface.get_nselections())
+                    .await
+                    .ok()
+                    .and_then(Result::ok)
+                    .unwrap_or(0);
+                if nselections > 0 {
+                    time::timeout(per_method_timeout, text_iface.get_selection(0))
+                        .await
+                        .ok()
+                        .and_then(Result::ok)
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+
+            let text_chars = text.chars().count() as i32;
+            let plan = plan_caret_insertion(
+                caret,
+                char_count,
+                selection,
+                text_chars,
+                self.config.atspi_replace_selection,
+                self.config.atspi_move_caret,
+            )
+            .ok_or_else(|| {
+                InjectionError::MethodFailed(
+                    "Focused element exposes no caret or text length".to_string(),
+                )
+            })?;
+            trace!("Caret insertion plan: {:?}", plan);
+
+            // Formatting control: read the non-default attributes the text
+            // would pick up, for applications that report attributes at all
+            let attr_mode = self.config.atspi_text_attributes;
+            let mut expected_attrs = None;
+            if attr_mode != AtspiTextAttributes::Inherit {
+                let app = obj_ref.name().map(|n| n.to_string()).unwrap_or_default();
+                let supported = self.attribute_support.lock().unwrap().get(&app).copied();
+                let caret_attrs = if supported == Some(false) {
+                    None
+                } else {
+                    let probe =
+                        text_iface.get_attribute_run(attribute_probe_offset(plan.insert_at), false);
+                    let attrs = time::timeout(per_method_timeout, probe)
+                        .await
+                        .ok()
+                        .and_then(Result::ok)
+                        .map(|(attrs, _, _)| attrs);
+                    if supported.is_none() {
+                        debug!("Text attribute support for {}: {}", app, attrs.is_some());
+                        self.attribute_support
+                            .lock()
+                            .unwrap()
+                            .insert(app, attrs.is_some());
+                    }
+                    attrs
+                };
+
+                if let Some(caret_attrs) = caret_attrs {
+                    if attr_mode == AtspiTextAttributes::Plain && !caret_attrs.is_empty() {
+                        debug!("Caret sits in formatted text: {:?}", caret_attrs);
+                        return Err(InjectionError::MethodUnavailable(
+                            "AT-SPI cannot insert unformatted text at a formatted caret"
+                                .to_string(),
+                        ));
+                    }
+                    expected_attrs = expected_attributes(attr_mode, &caret_attrs);
+                }
+            }
+
+            if let Some((start, end)) = plan.delete {
+                time::timeout(per_method_timeout, editable.delete_text(start, end))
+                    .await
+                    .map_err(|_| InjectionError::Timeout(per_method_timeout.as_millis() as u64))?
+                    .map_err(|e| {
+                        InjectionError::Other(format!("EditableText.delete_text failed: {e}"))
+                    })?;
+            }
+
             // Insert text at caret position
-            let insert_fut = editable.insert_text(caret, text, text.chars().count() as i32);
+            let insert_fut = editable.insert_text(plan.insert_at, text, text_chars);
             time::timeout(per_method_timeout, insert_fut)
                 .await
                 .map_err(|_| InjectionError::Timeout(per_method_timeout.as_millis() as u64))?
                 .map_err(|e| {
                     warn!(
                         "Failed to insert text at position {} in {:?}: {}",
-                        caret,
+                        plan.insert_at,
                         obj_ref.path(),
                         e
                     );
                     InjectionError::Other(format!("EditableText.insert_text failed: {e}"))
                 })?;
 
+            if let Some(expected) = expected_attrs {
+                let actual = time::timeout(
+                    per_method_timeout,
+                    text_iface.get_attribute_run(plan.insert_at, false),
+                )
+                .await
+                .ok()
+                .and_then(Result::ok)
+                .map(|(attrs, _, _)| attrs);
+                if actual.as_ref().is_some_and(|a| *a != expected) {
+                    debug!(
+                        "Inserted text got {:?}, expected {:?}; rolling back",
+                        actual, expected
+                    );
+                    let rollback =
+                        editable.delete_text(plan.insert_at, plan.insert_at + text_chars);
+                    if !matches!(
+                        time::timeout(per_method_timeout, rollback).await,
+                        Ok(Ok(true))
+                    ) {
+                        warn!("Could not roll back mis-formatted AT-SPI insert");
+                    }
+                    return Err(InjectionError::MethodFailed(
+                        "Target applied different formatting to inserted text".to_string(),
+                    ));
+                }
+            }
+
+            // Some toolkits leave the caret in front of inserted text; move it
+            // so consecutive utterances land in order
+            if let Some(offset) = plan.caret_after {
+                match time::timeout(per_method_timeout, text_iface.set_caret_offset(offset)).await {
+                    Ok(Ok(true)) => {}
+                    Ok(Ok(false)) => debug!("Target refused caret move to {}", offset),
+                    Ok(Err(e)) => debug!("Text.set_caret_offset failed: {}", e),
+                    Err(_) => debug!("Text.set_caret_offset timed out"),
+                }
+            }
+
             let elapsed = start_time.elapsed();
 
             // Log successful insertion
@@ -680,6 +882,70 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn caret_plan_inserts_at_caret_and_moves_past_text() {
+        let plan = plan_caret_insertion(4, Some(10), Some((2, 6)), 3, false, true).unwrap();
+        assert_eq!(
+            plan,
+            CaretPlan {
+                delete: None,
+                insert_at: 4,
+                caret_after: Some(7),
+            }
+        );
+
+        let plan = plan_caret_insertion(4, Some(10), None, 3, false, false).unwrap();
+        assert_eq!(plan.caret_after, None);
+    }
+
+    #[test]
+    fn caret_plan_replaces_selection_at_caret() {
+        // Reversed selection is normalized
+        let plan = plan_caret_insertion(6, Some(10), Some((6, 2)), 3, true, true).unwrap();
+        assert_eq!(plan.delete, Some((2, 6)));
+        assert_eq!(plan.insert_at, 2);
+        assert_eq!(plan.caret_after, Some(5));
+
+        // A stale selection away from the caret is left alone
+        let plan = plan_caret_insertion(9, Some(10), Some((2, 6)), 3, true, true).unwrap();
+        assert_eq!(plan.delete, None);
+        assert_eq!(plan.insert_at, 9);
+    }
+
+    #[test]
+    fn caret_plan_without_caret_appends() {
+        let plan = plan_caret_insertion(-1, Some(10), None, 3, false, true).unwrap();
+        assert_eq!(plan.insert_at, 10);
+        // Caret beyond the end is clamped
+        let plan = plan_caret_insertion(42, Some(10), None, 3, false, true).unwrap();
+        assert_eq!(plan.insert_at, 10);
+        assert!(plan_caret_insertion(-1, None, None, 3, false, true).is_none());
+    }
+
+    #[test]
+    fn attribute_expectations_follow_mode() {
+        let bold: HashMap<String, String> = [("weight".to_string(), "700".to_string())].into();
+        assert_eq!(
+            expected_attributes(AtspiTextAttributes::Inherit, &bold),
+            None
+        );
+        assert_eq!(
+            expected_attributes(AtspiTextAttributes::Plain, &bold),
+            Some(HashMap::new())
+        );
+        assert_eq!(
+            expected_attributes(AtspiTextAttributes::MatchCaret, &bold),
+            Some(bold.clone())
+        );
+
+        assert_eq!(attribute_probe_offset(5), 4);
+        assert_eq!(attribute_probe_offset(0), 0);
+        assert_eq!(
+            "match-caret".parse::<AtspiTextAttributes>(),
+            Ok(AtspiTextAttributes::MatchCaret)
+        );
+    }
+
     #[tokio::test]
     async fn test_legacy_inject_text() {
         let config = InjectionConfig::default();
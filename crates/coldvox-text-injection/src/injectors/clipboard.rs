@@ -613,6 +613,12 @@ impl ClipboardInjector {
 
     /// Try ydotool paste
     async fn try_ydotool_paste(&self) -> InjectionResult<()> {
+        // ydotool's virtual keyboard belongs to seat0
+        if !crate::window_manager::on_default_seat() {
+            return Err(InjectionError::MethodUnavailable(
+                "ydotool cannot reach this seat".to_string(),
+            ));
+        }
         let output = Command::new("ydotool")
             .args(["key", "ctrl+v"])
             .output()
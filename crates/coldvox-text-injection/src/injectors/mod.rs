@@ -6,6 +6,7 @@
 
 pub mod atspi;
 pub mod clipboard;
+pub mod primary_selection;
 pub mod unified_clipboard;
 
 // Re-export common types for convenience
@@ -13,6 +14,8 @@ pub mod unified_clipboard;
 pub use atspi::Context as AtspiContext;
 #[allow(deprecated)]
 pub use clipboard::{ClipboardBackup, ClipboardInjector, Context as ClipboardContext};
+pub use primary_selection::PrimarySelectionInjector;
 pub use unified_clipboard::{
-    ClipboardBackup as UnifiedClipboardBackup, ClipboardInjectionMode, UnifiedClipboardInjector,
+    ClipboardBackup as UnifiedClipboardBackup, ClipboardInjectionMode, ClipboardPath,
+    UnifiedClipboardInjector,
 };
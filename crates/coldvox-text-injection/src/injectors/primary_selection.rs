@@ -0,0 +1,277 @@
+//! Primary Selection Text Injector
+//!
+//! Sets the primary selection (the middle-click buffer) instead of the clipboard and,
+//! when `primary_selection_middle_click` is on, clicks the middle mouse button to paste
+//! it. Terminals and other X11-heritage apps paste primary at their cursor; most other
+//! apps paste at the mouse pointer, which is why this method is opt-in via
+//! `allow_primary_selection`. The regular clipboard is never touched.
+
+use crate::detection::{detect_display_protocol, DisplayProtocol};
+use crate::logging::utils;
+use crate::types::{InjectionConfig, InjectionContext, InjectionMethod, InjectionResult};
+use crate::TextInjector;
+use async_trait::async_trait;
+use coldvox_foundation::error::InjectionError;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time::timeout;
+use tracing::trace;
+
+/// ydotool button code for a middle click (press + release)
+const YDOTOOL_MIDDLE_CLICK: &str = "0xC2";
+
+/// Primary-selection based text injector
+pub struct PrimarySelectionInjector {
+    config: InjectionConfig,
+    protocol: DisplayProtocol,
+}
+
+impl PrimarySelectionInjector {
+    /// Create a new primary selection injector
+    pub fn new(config: InjectionConfig) -> Self {
+        Self {
+            config,
+            protocol: detect_display_protocol(),
+        }
+    }
+
+    /// Put `text` in the primary selection
+    pub async fn set_primary(&self, text: &str) -> InjectionResult<()> {
+        match self.protocol {
+            DisplayProtocol::Wayland => {
+                #[cfg(feature = "wl_clipboard")]
+                {
+                    match self.set_primary_wayland_native(text).await {
+                        Ok(()) => return Ok(()),
+                        Err(e) => {
+                            tracing::debug!("Native primary selection failed, using wl-copy: {}", e)
+                        }
+                    }
+                }
+                self.set_primary_command("wl-copy", &["--primary"], text)
+                    .await
+            }
+            DisplayProtocol::X11 => {
+                self.set_primary_command("xclip", &["-selection", "primary"], text)
+                    .await
+            }
+            DisplayProtocol::Unknown => Err(InjectionError::MethodUnavailable(
+                "No display server detected for primary selection".to_string(),
+            )),
+        }
+    }
+
+    /// Set the primary selection using native wl-clipboard-rs
+    #[cfg(feature = "wl_clipboard")]
+    async fn set_primary_wayland_native(&self, text: &str) -> InjectionResult<()> {
+        use wl_clipboard_rs::copy::{ClipboardType, MimeType, Options, Source};
+
+        let data = text.as_bytes().to_vec().into_boxed_slice();
+        tokio::task::spawn_blocking(move || {
+            let mut opts = Options::new();
+            opts.clipboard(ClipboardType::Primary);
+            opts.copy(Source::Bytes(data), MimeType::Text).map_err(|e| {
+                InjectionError::Other(format!("Failed to set Wayland primary selection: {}", e))
+            })
+        })
+        .await
+        .map_err(|e| InjectionError::Other(format!("Tokio spawn_blocking failed: {}", e)))?
+    }
+
+    /// Set the primary selection with wl-copy or xclip
+    async fn set_primary_command(
+        &self,
+        program: &str,
+        args: &[&str],
+        text: &str,
+    ) -> InjectionResult<()> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| InjectionError::Process(format!("Failed to spawn {}: {}", program, e)))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            timeout(
+                self.config.per_method_timeout(),
+                stdin.write_all(text.as_bytes()),
+            )
+            .await
+            .map_err(|_| InjectionError::Timeout(self.config.per_method_timeout_ms))?
+            .map_err(|e| {
+                InjectionError::Process(format!("Failed to write to {} stdin: {}", program, e))
+            })?;
+        }
+
+        match timeout(self.config.paste_action_timeout(), child.wait()).await {
+            Ok(Ok(status)) if status.success() => Ok(()),
+            Ok(Ok(status)) => Err(InjectionError::Process(format!(
+                "{} failed to set primary selection (status {})",
+                program, status
+            ))),
+            Ok(Err(e)) => Err(InjectionError::Process(format!(
+                "Failed to wait for {}: {}",
+                program, e
+            ))),
+            Err(_) => {
+                let _ = child.kill().await;
+                Err(InjectionError::Timeout(self.config.paste_action_timeout_ms))
+            }
+        }
+    }
+
+    /// Paste the primary selection with a synthetic middle click
+    async fn middle_click(&self) -> InjectionResult<()> {
+        #[cfg(feature = "enigo")]
+        if self.config.allow_enigo && self.try_enigo_middle_click().await.is_ok() {
+            return Ok(());
+        }
+
+        let mut command = Command::new("ydotool");
+        #[cfg(feature = "ydotool")]
+        crate::ydotool_injector::apply_socket_env(&mut command);
+        command.args(["click", YDOTOOL_MIDDLE_CLICK]);
+
+        let output = timeout(self.config.paste_action_timeout(), command.output())
+            .await
+            .map_err(|_| InjectionError::Timeout(self.config.paste_action_timeout_ms))?
+            .map_err(|e| InjectionError::Process(format!("Failed to execute ydotool: {}", e)))?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(InjectionError::MethodFailed(format!(
+                "ydotool middle click failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )))
+        }
+    }
+
+    /// Middle click via Enigo
+    #[cfg(feature = "enigo")]
+    async fn try_enigo_middle_click(&self) -> InjectionResult<()> {
+        use enigo::{Button, Direction, Enigo, Mouse, Settings};
+
+        tokio::task::spawn_blocking(|| {
+            let mut enigo = Enigo::new(&Settings::default()).map_err(|e| {
+                InjectionError::MethodFailed(format!("Failed to create Enigo: {}", e))
+            })?;
+            enigo
+                .button(Button::Middle, Direction::Click)
+                .map_err(|e| InjectionError::MethodFailed(format!("Middle click failed: {}", e)))
+        })
+        .await
+        .map_err(|e| InjectionError::Other(format!("Tokio spawn_blocking failed: {}", e)))?
+    }
+
+    fn selection_tool(&self) -> Option<&'static str> {
+        match self.protocol {
+            DisplayProtocol::Wayland => Some("wl-copy"),
+            DisplayProtocol::X11 => Some("xclip"),
+            DisplayProtocol::Unknown => None,
+        }
+    }
+}
+
+async fn binary_available(binary: &str) -> bool {
+    Command::new("which")
+        .arg(binary)
+        .output()
+        .await
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+#[async_trait]
+impl TextInjector for PrimarySelectionInjector {
+    async fn inject_text(
+        &self,
+        text: &str,
+        _context: Option<&InjectionContext>,
+    ) -> InjectionResult<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        let start = Instant::now();
+        trace!("Setting primary selection ({} chars)", text.len());
+        self.set_primary(text).await?;
+
+        if self.config.primary_selection_middle_click {
+            // Give the selection owner a moment to announce itself
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.middle_click().await?;
+        }
+
+        utils::log_injection_success(
+            InjectionMethod::PrimarySelection,
+            text,
+            start.elapsed(),
+            self.config.redact_logs,
+        );
+        Ok(())
+    }
+
+    async fn is_available(&self) -> bool {
+        let can_select = match self.protocol {
+            DisplayProtocol::Wayland if cfg!(feature = "wl_clipboard") => true,
+            DisplayProtocol::Unknown => false,
+            _ => match self.selection_tool() {
+                Some(tool) => binary_available(tool).await,
+                None => false,
+            },
+        };
+        if !can_select || !self.config.primary_selection_middle_click {
+            return can_select;
+        }
+        (cfg!(feature = "enigo") && self.config.allow_enigo) || binary_available("ydotool").await
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "primary-selection"
+    }
+
+    fn backend_info(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("type", "primary selection".to_string()),
+            (
+                "description",
+                "Sets the primary (middle-click) selection and optionally middle-clicks to paste"
+                    .to_string(),
+            ),
+            ("protocol", format!("{:?}", self.protocol)),
+            (
+                "middle_click",
+                self.config.primary_selection_middle_click.to_string(),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_empty_text_is_noop() {
+        let injector = PrimarySelectionInjector::new(InjectionConfig::default());
+        assert!(injector.inject_text("", None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_protocol_is_unavailable() {
+        let injector = PrimarySelectionInjector {
+            config: InjectionConfig::default(),
+            protocol: DisplayProtocol::Unknown,
+        };
+        assert!(!injector.is_available().await);
+        assert!(matches!(
+            injector.set_primary("hi").await,
+            Err(InjectionError::MethodUnavailable(_))
+        ));
+    }
+}
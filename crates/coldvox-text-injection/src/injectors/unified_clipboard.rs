@@ -3,9 +3,24 @@
 //! This module provides a consolidated clipboard-based text injector that combines the best
 //! features from ClipboardInjector, ClipboardPasteInjector, and ComboClipboardYdotool.
 //! It supports both strict and best-effort injection modes with configurable behavior.
+//!
+//! The backup keeps every MIME type the clipboard offers (images, rich text), and the
+//! restore is read back and retried when a clipboard manager such as Klipper re-takes
+//! the selection with the pasted payload. Seeded payloads carry
+//! `x-kde-passwordManagerHint` so managers keep them out of history. Offering several
+//! types at once needs the native Wayland path; the wl-copy/xclip fallbacks restore
+//! the primary type only and seed without the hint. Structured text is seeded
+//! with a `text/html` rendering next to the plain text when the target wants it
+//! ([`rich_text`](crate::rich_text)).
+//!
+//! On Wayland every clipboard operation goes through wl-clipboard-rs first and only
+//! shells out to wl-copy/wl-paste when the native call fails, so missing binaries
+//! don't matter while the native path works. `backend_info()` reports which path
+//! served the last operation.
 
 use crate::detection::{detect_display_protocol, DisplayProtocol};
 use crate::logging::utils;
+use crate::rich_text;
 use crate::types::{InjectionConfig, InjectionContext, InjectionMethod, InjectionResult};
 use crate::TextInjector;
 use async_trait::async_trait;
@@ -27,6 +42,24 @@ pub enum ClipboardInjectionMode {
     BestEffort,
 }
 
+/// MIME type KDE clipboard managers check to keep an entry out of history
+const PASSWORD_MANAGER_HINT: &str = "x-kde-passwordManagerHint";
+
+/// Upper bound on clipboard data kept in one backup, across all types
+const MAX_BACKUP_BYTES: usize = 16 * 1024 * 1024;
+
+/// X11 targets that describe the selection rather than carry its data
+const X11_META_TARGETS: &[&str] = &["TARGETS", "TIMESTAMP", "MULTIPLE", "SAVE_TARGETS", "DELETE"];
+
+/// Text types in order of preference for the primary backup entry
+const TEXT_TYPES: &[&str] = &[
+    "text/plain;charset=utf-8",
+    "text/plain",
+    "UTF8_STRING",
+    "STRING",
+    "TEXT",
+];
+
 /// Clipboard backup data with MIME type information
 #[derive(Debug, Clone)]
 pub struct ClipboardBackup {
@@ -34,6 +67,8 @@ pub struct ClipboardBackup {
     pub content: Vec<u8>,
     /// MIME type of the content
     pub mime_type: String,
+    /// Other representations the clipboard offered, as (MIME type, content)
+    pub alternates: Vec<(String, Vec<u8>)>,
     /// Timestamp when backup was created
     pub timestamp: std::time::Instant,
 }
@@ -44,6 +79,7 @@ impl ClipboardBackup {
         Self {
             content,
             mime_type,
+            alternates: Vec::new(),
             timestamp: std::time::Instant::now(),
         }
     }
@@ -52,6 +88,62 @@ impl ClipboardBackup {
     pub fn is_valid(&self, max_age: Duration) -> bool {
         self.timestamp.elapsed() < max_age
     }
+
+    /// All representations, primary first
+    pub fn entries(&self) -> Vec<(&str, &[u8])> {
+        std::iter::once((self.mime_type.as_str(), self.content.as_slice()))
+            .chain(
+                self.alternates
+                    .iter()
+                    .map(|(mime, content)| (mime.as_str(), content.as_slice())),
+            )
+            .collect()
+    }
+}
+
+fn is_text_type(mime: &str) -> bool {
+    TEXT_TYPES.contains(&mime) || mime.starts_with("text/plain")
+}
+
+/// Drop meta targets and duplicates, then move the preferred type to the front:
+/// the best plain-text type if there is one, otherwise the first offered.
+fn order_backup_types(offered: &[String]) -> Vec<String> {
+    let mut types: Vec<String> = Vec::new();
+    for mime in offered {
+        let mime = mime.trim();
+        if mime.is_empty() || X11_META_TARGETS.contains(&mime) || types.iter().any(|t| t == mime) {
+            continue;
+        }
+        types.push(mime.to_string());
+    }
+    if let Some(pos) = TEXT_TYPES
+        .iter()
+        .find_map(|preferred| types.iter().position(|t| t == preferred))
+    {
+        let primary = types.remove(pos);
+        types.insert(0, primary);
+    }
+    types
+}
+
+/// Representations to seed for `text`, plain text first, plus its HTML
+/// rendering if any; optionally tagged for clipboard managers
+fn seed_entries<'a>(
+    text: &'a str,
+    html: Option<&'a str>,
+    manager_hint: bool,
+) -> Vec<(&'static str, &'a [u8])> {
+    let mut entries: Vec<(&'static str, &[u8])> = TEXT_TYPES
+        .iter()
+        .map(|mime| (*mime, text.as_bytes()))
+        .collect();
+    if let Some(html) = html {
+        entries.push((rich_text::HTML_MIME, html.as_bytes()));
+    }
+    if manager_hint {
+        entries.push((PASSWORD_MANAGER_HINT, &b"secret"[..]));
+    }
+    entries
 }
 
 /// Clipboard backend types
@@ -65,7 +157,26 @@ pub enum ClipboardBackend {
     Unknown,
 }
 
+/// How clipboard data is moved in and out of the compositor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardPath {
+    /// In-process wl-clipboard-rs
+    Native,
+    /// wl-copy/wl-paste or xclip subprocesses
+    Subprocess,
+}
+
+impl std::fmt::Display for ClipboardPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClipboardPath::Native => write!(f, "native (wl-clipboard-rs)"),
+            ClipboardPath::Subprocess => write!(f, "subprocess"),
+        }
+    }
+}
+
 /// Unified clipboard-based text injector with configurable injection modes
+#[derive(Clone)]
 pub struct UnifiedClipboardInjector {
     /// Configuration for injection
     config: InjectionConfig,
@@ -75,6 +186,17 @@ pub struct UnifiedClipboardInjector {
     backend_type: ClipboardBackend,
     /// Injection mode (strict vs best-effort)
     injection_mode: ClipboardInjectionMode,
+    /// Path that served the most recent Wayland clipboard operation
+    wayland_path: Arc<std::sync::Mutex<ClipboardPath>>,
+}
+
+/// Path tried first on Wayland
+fn preferred_wayland_path() -> ClipboardPath {
+    if cfg!(feature = "wl_clipboard") {
+        ClipboardPath::Native
+    } else {
+        ClipboardPath::Subprocess
+    }
 }
 
 impl UnifiedClipboardInjector {
@@ -86,6 +208,7 @@ impl UnifiedClipboardInjector {
             available: Arc::new(tokio::sync::RwLock::new(false)),
             backend_type,
             injection_mode: ClipboardInjectionMode::BestEffort, // Default to best-effort
+            wayland_path: Arc::new(std::sync::Mutex::new(preferred_wayland_path())),
         }
     }
 
@@ -97,6 +220,7 @@ impl UnifiedClipboardInjector {
             available: Arc::new(tokio::sync::RwLock::new(false)),
             backend_type,
             injection_mode: mode,
+            wayland_path: Arc::new(std::sync::Mutex::new(preferred_wayland_path())),
         }
     }
 
@@ -136,7 +260,10 @@ impl UnifiedClipboardInjector {
     {
         // Try native implementation first
         match native_attempt().await {
-            Ok(result) => return Ok(result),
+            Ok(result) => {
+                self.set_wayland_path(ClipboardPath::Native);
+                return Ok(result);
+            }
             Err(e) => {
                 debug!(
                     "Native implementation failed, falling back to {}: {}",
@@ -146,7 +273,27 @@ impl UnifiedClipboardInjector {
         }
 
         // Fallback to command
-        fallback_command().await
+        let result = fallback_command().await;
+        if result.is_ok() {
+            self.set_wayland_path(ClipboardPath::Subprocess);
+        }
+        result
+    }
+
+    fn set_wayland_path(&self, path: ClipboardPath) {
+        let mut current = self.wayland_path.lock().unwrap();
+        if *current != path {
+            debug!("Wayland clipboard now served by {}", path);
+            *current = path;
+        }
+    }
+
+    /// Path clipboard data currently takes (always a subprocess on X11)
+    pub fn clipboard_path(&self) -> ClipboardPath {
+        match self.backend_type {
+            ClipboardBackend::Wayland => *self.wayland_path.lock().unwrap(),
+            _ => ClipboardPath::Subprocess,
+        }
     }
 
     /// Execute a command with stdin, stdout, stderr and consistent timeout/kill handling
@@ -164,7 +311,12 @@ impl UnifiedClipboardInjector {
             .stderr(Stdio::piped())
             .spawn()
             .map_err(|e| {
-                InjectionError::Process(format!("Failed to spawn {}: {}", command_name, e))
+                let msg = format!("Failed to spawn {}: {}", command_name, e);
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    InjectionError::MethodUnavailable(msg)
+                } else {
+                    InjectionError::Process(msg)
+                }
             })?;
 
         // Take stderr for later diagnostics if the process fails
@@ -226,10 +378,16 @@ impl UnifiedClipboardInjector {
         let start_time = Instant::now();
         trace!("Reading clipboard content for backup");
 
-        let backup = match self.backend_type {
-            ClipboardBackend::Wayland => self.read_wayland_clipboard().await?,
-            ClipboardBackend::X11 => self.read_x11_clipboard().await?,
-            ClipboardBackend::Unknown => {
+        let all_types = if self.config.clipboard_preserve_mime_types {
+            self.read_all_types().await
+        } else {
+            None
+        };
+        let backup = match (all_types, self.backend_type) {
+            (Some(backup), _) => backup,
+            (None, ClipboardBackend::Wayland) => self.read_wayland_clipboard().await?,
+            (None, ClipboardBackend::X11) => self.read_x11_clipboard().await?,
+            (None, ClipboardBackend::Unknown) => {
                 return Err(InjectionError::MethodUnavailable(
                     "No supported clipboard backend detected".to_string(),
                 ))
@@ -247,6 +405,177 @@ impl UnifiedClipboardInjector {
         Ok(backup)
     }
 
+    /// Back up every type the clipboard offers. `None` when the types can't be
+    /// listed or the primary one can't be read, so the caller falls back to text.
+    async fn read_all_types(&self) -> Option<ClipboardBackup> {
+        let offered = match self.list_clipboard_types().await {
+            Ok(offered) => offered,
+            Err(e) => {
+                trace!("Could not list clipboard types: {}", e);
+                return None;
+            }
+        };
+        let mut types = order_backup_types(&offered).into_iter();
+        let primary = types.next()?;
+        let content = self.read_clipboard_type(&primary).await.ok()?;
+        let mut backup = ClipboardBackup::new(content, primary);
+        let mut total = backup.content.len();
+        for mime in types {
+            match self.read_clipboard_type(&mime).await {
+                Ok(data) if total + data.len() <= MAX_BACKUP_BYTES => {
+                    total += data.len();
+                    backup.alternates.push((mime, data));
+                }
+                Ok(data) => {
+                    debug!(
+                        "Not backing up clipboard type {} ({} bytes): backup size limit reached",
+                        mime,
+                        data.len()
+                    );
+                }
+                Err(e) => trace!("Skipping clipboard type {}: {}", mime, e),
+            }
+        }
+        Some(backup)
+    }
+
+    /// List the MIME types (X11 targets) the clipboard currently offers
+    async fn list_clipboard_types(&self) -> InjectionResult<Vec<String>> {
+        #[cfg(feature = "wl_clipboard")]
+        if self.backend_type == ClipboardBackend::Wayland {
+            return self
+                .native_attempt_with_fallback(
+                    || self.list_wayland_types_native(),
+                    "wl-paste",
+                    || self.list_types_subprocess(),
+                )
+                .await;
+        }
+        self.list_types_subprocess().await
+    }
+
+    /// List clipboard types using native wl-clipboard-rs
+    #[cfg(feature = "wl_clipboard")]
+    async fn list_wayland_types_native(&self) -> InjectionResult<Vec<String>> {
+        use wl_clipboard_rs::paste::{get_mime_types_ordered, ClipboardType, Seat};
+
+        tokio::task::spawn_blocking(|| {
+            get_mime_types_ordered(ClipboardType::Regular, Seat::Unspecified)
+        })
+        .await
+        .map_err(|e| InjectionError::Other(format!("Failed to spawn clipboard task: {}", e)))?
+        .map_err(|e| {
+            InjectionError::Other(format!("Failed to list Wayland clipboard types: {}", e))
+        })
+    }
+
+    /// List clipboard types with wl-paste or xclip
+    async fn list_types_subprocess(&self) -> InjectionResult<Vec<String>> {
+        let (program, args): (&str, &[&str]) = match self.backend_type {
+            ClipboardBackend::Wayland => ("wl-paste", &["--list-types"][..]),
+            ClipboardBackend::X11 => (
+                "xclip",
+                &["-selection", "clipboard", "-t", "TARGETS", "-o"][..],
+            ),
+            ClipboardBackend::Unknown => {
+                return Err(InjectionError::MethodUnavailable(
+                    "No supported clipboard backend detected".to_string(),
+                ))
+            }
+        };
+        let output = Command::new(program)
+            .args(args)
+            .output()
+            .await
+            .map_err(|e| {
+                InjectionError::Process(format!("Failed to execute {}: {}", program, e))
+            })?;
+        if !output.status.success() {
+            return Err(InjectionError::Process(format!(
+                "{} could not list clipboard types",
+                program
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Read one representation of the clipboard
+    async fn read_clipboard_type(&self, mime: &str) -> InjectionResult<Vec<u8>> {
+        #[cfg(feature = "wl_clipboard")]
+        if self.backend_type == ClipboardBackend::Wayland {
+            return self
+                .native_attempt_with_fallback(
+                    || self.read_wayland_type_native(mime),
+                    "wl-paste",
+                    || self.read_type_subprocess(mime),
+                )
+                .await;
+        }
+        self.read_type_subprocess(mime).await
+    }
+
+    /// Read one clipboard representation using native wl-clipboard-rs
+    #[cfg(feature = "wl_clipboard")]
+    async fn read_wayland_type_native(&self, mime: &str) -> InjectionResult<Vec<u8>> {
+        use wl_clipboard_rs::paste::{get_contents, ClipboardType, MimeType, Seat};
+
+        let mime = mime.to_string();
+        tokio::task::spawn_blocking(move || {
+            let (mut reader, _) = get_contents(
+                ClipboardType::Regular,
+                Seat::Unspecified,
+                MimeType::Specific(&mime),
+            )
+            .map_err(|e| {
+                InjectionError::Other(format!("Failed to read Wayland clipboard: {}", e))
+            })?;
+            let mut buf = Vec::new();
+            use std::io::Read;
+            reader.read_to_end(&mut buf).map_err(|e| {
+                InjectionError::Other(format!("Failed to read clipboard data: {}", e))
+            })?;
+            Ok(buf)
+        })
+        .await
+        .map_err(|e| InjectionError::Other(format!("Failed to spawn clipboard task: {}", e)))?
+    }
+
+    /// Read one clipboard representation with wl-paste or xclip
+    async fn read_type_subprocess(&self, mime: &str) -> InjectionResult<Vec<u8>> {
+        let mut command = match self.backend_type {
+            ClipboardBackend::Wayland => {
+                let mut command = Command::new("wl-paste");
+                command.args(["--no-newline", "--type", mime]);
+                command
+            }
+            ClipboardBackend::X11 => {
+                let mut command = Command::new("xclip");
+                command.args(["-selection", "clipboard", "-t", mime, "-o"]);
+                command
+            }
+            ClipboardBackend::Unknown => {
+                return Err(InjectionError::MethodUnavailable(
+                    "No supported clipboard backend detected".to_string(),
+                ))
+            }
+        };
+        let output = command
+            .output()
+            .await
+            .map_err(|e| InjectionError::Process(format!("Failed to read clipboard: {}", e)))?;
+        if output.status.success() {
+            Ok(output.stdout)
+        } else {
+            Err(InjectionError::Process(format!(
+                "Reading clipboard type {} failed",
+                mime
+            )))
+        }
+    }
+
     /// Read clipboard content using native Wayland wl-clipboard-rs
     #[cfg(feature = "wl_clipboard")]
     async fn read_wayland_clipboard_native(&self) -> InjectionResult<ClipboardBackup> {
@@ -296,7 +625,7 @@ impl UnifiedClipboardInjector {
     /// Fallback implementation using wl-paste command
     async fn read_wayland_clipboard_fallback(&self) -> InjectionResult<ClipboardBackup> {
         let output = Command::new("wl-paste")
-            .args(["--type", "text/plain"])
+            .args(["--no-newline", "--type", "text/plain"])
             .output()
             .await
             .map_err(|e| InjectionError::Process(format!("Failed to execute wl-paste: {}", e)))?;
@@ -425,16 +754,90 @@ impl UnifiedClipboardInjector {
         .await
     }
 
+    /// Offer several representations at once. Only the native Wayland path can
+    /// do this; command-line tools get the first entry alone.
+    async fn write_clipboard_entries(&self, entries: &[(&str, &[u8])]) -> InjectionResult<()> {
+        let Some(&(primary_mime, primary)) = entries.first() else {
+            return Ok(());
+        };
+        match self.backend_type {
+            ClipboardBackend::Wayland => {
+                #[cfg(feature = "wl_clipboard")]
+                {
+                    self.native_attempt_with_fallback(
+                        || self.write_wayland_entries_native(entries),
+                        "wl-copy",
+                        || self.write_typed_fallback("wl-copy", primary_mime, primary),
+                    )
+                    .await
+                }
+                #[cfg(not(feature = "wl_clipboard"))]
+                {
+                    self.write_typed_fallback("wl-copy", primary_mime, primary)
+                        .await
+                }
+            }
+            ClipboardBackend::X11 => {
+                self.write_typed_fallback("xclip", primary_mime, primary)
+                    .await
+            }
+            ClipboardBackend::Unknown => Err(InjectionError::MethodUnavailable(
+                "No supported clipboard backend detected".to_string(),
+            )),
+        }
+    }
+
+    /// Offer all entries using native wl-clipboard-rs
+    #[cfg(feature = "wl_clipboard")]
+    async fn write_wayland_entries_native(&self, entries: &[(&str, &[u8])]) -> InjectionResult<()> {
+        use wl_clipboard_rs::copy::{MimeSource, MimeType, Options, Source};
+
+        let sources: Vec<MimeSource> = entries
+            .iter()
+            .map(|(mime, content)| MimeSource {
+                source: Source::Bytes(content.to_vec().into_boxed_slice()),
+                mime_type: MimeType::Specific(mime.to_string()),
+            })
+            .collect();
+        tokio::task::spawn_blocking(move || {
+            Options::new().copy_multi(sources).map_err(|e| {
+                InjectionError::Other(format!("Failed to write Wayland clipboard: {}", e))
+            })
+        })
+        .await
+        .map_err(|e| InjectionError::Other(format!("Tokio spawn_blocking failed: {}", e)))?
+    }
+
+    /// Write one representation with wl-copy or xclip. Text goes without an
+    /// explicit type so the tool offers its usual set of text targets.
+    async fn write_typed_fallback(
+        &self,
+        program: &str,
+        mime: &str,
+        content: &[u8],
+    ) -> InjectionResult<()> {
+        let mut args: Vec<&str> = Vec::new();
+        if program == "xclip" {
+            args.extend(["-selection", "clipboard"]);
+        }
+        if !is_text_type(mime) {
+            args.extend([if program == "xclip" { "-t" } else { "--type" }, mime]);
+        }
+        let paste_timeout = self.config.paste_action_timeout();
+        self.execute_command_with_stdin(program, &args, content, paste_timeout)
+            .await
+    }
+
     /// Restore clipboard content from backup
     pub async fn restore_clipboard(&self, backup: &ClipboardBackup) -> InjectionResult<()> {
         let start_time = Instant::now();
         trace!(
-            "Restoring clipboard from backup ({} bytes)",
-            backup.content.len()
+            "Restoring clipboard from backup ({} bytes, {} extra types)",
+            backup.content.len(),
+            backup.alternates.len()
         );
 
-        self.write_clipboard(&backup.content, &backup.mime_type)
-            .await?;
+        self.write_clipboard_entries(&backup.entries()).await?;
 
         let elapsed = start_time.elapsed();
         debug!("Restored clipboard in {}ms", elapsed.as_millis());
@@ -442,6 +845,56 @@ impl UnifiedClipboardInjector {
         Ok(())
     }
 
+    /// Restore `backup`, then read the clipboard back and retry up to
+    /// `clipboard_restore_retries` times if it doesn't hold the backup (e.g. a
+    /// clipboard manager re-took the selection with the pasted payload).
+    /// Returns the number of attempts made.
+    pub async fn restore_clipboard_verified(
+        &self,
+        backup: &ClipboardBackup,
+    ) -> InjectionResult<u32> {
+        let retries = self.config.clipboard_restore_retries;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let restored = self.restore_clipboard(backup).await;
+            if retries == 0 {
+                return restored.map(|_| attempt);
+            }
+            let result = match restored {
+                Ok(()) => {
+                    // Let a racing clipboard manager act before checking
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    self.verify_restore(backup).await
+                }
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => return Ok(attempt),
+                Err(e) if attempt > retries => return Err(e),
+                Err(e) => {
+                    debug!("Clipboard restore attempt {} not confirmed: {}", attempt, e);
+                    tokio::time::sleep(Duration::from_millis(50 * u64::from(attempt))).await;
+                }
+            }
+        }
+    }
+
+    /// Check that the clipboard holds the backup's primary representation
+    async fn verify_restore(&self, backup: &ClipboardBackup) -> InjectionResult<()> {
+        let current = self.read_clipboard_type(&backup.mime_type).await?;
+        if current == backup.content {
+            Ok(())
+        } else {
+            Err(InjectionError::MethodFailed(format!(
+                "clipboard holds {} bytes of {} after restore, expected {}",
+                current.len(),
+                backup.mime_type,
+                backup.content.len()
+            )))
+        }
+    }
+
     /// Perform paste action after seeding clipboard
     async fn perform_paste(&self) -> InjectionResult<&'static str> {
         trace!("Performing paste action");
@@ -500,10 +953,18 @@ impl UnifiedClipboardInjector {
 
     /// Try ydotool paste
     async fn try_ydotool_paste(&self) -> InjectionResult<()> {
+        // ydotool's virtual keyboard belongs to seat0
+        if !crate::window_manager::on_default_seat() {
+            return Err(InjectionError::MethodUnavailable(
+                "ydotool cannot reach this seat".to_string(),
+            ));
+        }
         let mut command = Command::new("ydotool");
         #[cfg(feature = "ydotool")]
         crate::ydotool_injector::apply_socket_env(&mut command);
-        command.args(["key", "ctrl+v"]);
+        command.arg("key").args(crate::layout::ydotool_paste_args(
+            self.config.keyboard_layout.as_deref(),
+        ));
 
         let output = command
             .output()
@@ -549,112 +1010,31 @@ impl UnifiedClipboardInjector {
         }
     }
 
-    /// Schedule clipboard restoration with improved async handling
+    /// Schedule verified clipboard restoration after the configured delay
     async fn schedule_clipboard_restore(&self, backup: Option<ClipboardBackup>) {
         if let Some(backup) = backup {
             let delay_ms = self.config.clipboard_restore_delay_ms.unwrap_or(500);
-            // Move only data needed into the task to avoid capturing &self
-            let content = backup.content.clone();
-            let content_len = content.len();
+            let injector = self.clone();
 
             tokio::spawn(async move {
                 tokio::time::sleep(Duration::from_millis(delay_ms)).await;
-
-                // Restore clipboard content regardless of backend
-
-                #[cfg(feature = "wl_clipboard")]
-                {
-                    use wl_clipboard_rs::copy::{MimeType, Options, Source};
-                    let src = Source::Bytes(content.clone().into_boxed_slice());
-                    let opts = Options::new();
-                    let _ = opts.copy(src, MimeType::Text);
-                    debug!(
-                        "Restored original clipboard via wl-clipboard ({} chars)",
-                        content_len
-                    );
-                }
-
-                #[cfg(not(feature = "wl_clipboard"))]
-                {
-                    // Restore via command-line tools for X11/other backends without borrowing self
-                    let restored = Self::restore_clipboard_direct(content.clone()).await;
-                    match restored {
-                        Ok(_) => debug!(
-                            "Restored original clipboard via command-line ({} chars)",
-                            content_len
-                        ),
-                        Err(e) => warn!("Failed to restore clipboard: {}", e),
-                    }
+                match injector.restore_clipboard_verified(&backup).await {
+                    Ok(attempts) => debug!(
+                        "Restored original clipboard ({} bytes, {} extra types, {} attempt(s))",
+                        backup.content.len(),
+                        backup.alternates.len(),
+                        attempts
+                    ),
+                    Err(e) => warn!("Failed to restore clipboard: {}", e),
                 }
             });
         }
     }
 
-    /// Helper to restore clipboard content without borrowing &self
-    /// Uses wl-copy if available (feature-enabled path handled earlier), otherwise xclip.
-    #[allow(dead_code)]
-    async fn restore_clipboard_direct(content: Vec<u8>) -> InjectionResult<()> {
-        // Try wl-copy first if present at runtime
-        let wl_copy_ok = tokio::process::Command::new("which")
-            .arg("wl-copy")
-            .output()
-            .await
-            .map(|o| o.status.success())
-            .unwrap_or(false);
-
-        if wl_copy_ok {
-            let mut child = tokio::process::Command::new("wl-copy")
-                .stdin(Stdio::piped())
-                .spawn()
-                .map_err(|e| InjectionError::Process(format!("Failed to spawn wl-copy: {}", e)))?;
-            if let Some(mut stdin) = child.stdin.take() {
-                timeout(Duration::from_millis(1000), stdin.write_all(&content))
-                    .await
-                    .map_err(|_| InjectionError::Timeout(1000))
-                    .and_then(|r| {
-                        r.map_err(|e| InjectionError::Process(format!("wl-copy stdin: {}", e)))
-                    })?;
-            }
-            let status = child
-                .wait()
-                .await
-                .map_err(|e| InjectionError::Process(format!("wl-copy wait: {}", e)))?;
-            return if status.success() {
-                Ok(())
-            } else {
-                Err(InjectionError::Process("wl-copy failed".into()))
-            };
-        }
-
-        // Fallback to xclip
-        let mut child = tokio::process::Command::new("xclip")
-            .args(["-selection", "clipboard"])
-            .stdin(Stdio::piped())
-            .spawn()
-            .map_err(|e| InjectionError::Process(format!("Failed to spawn xclip: {}", e)))?;
-        if let Some(mut stdin) = child.stdin.take() {
-            timeout(Duration::from_millis(1000), stdin.write_all(&content))
-                .await
-                .map_err(|_| InjectionError::Timeout(1000))
-                .and_then(|r| {
-                    r.map_err(|e| InjectionError::Process(format!("xclip stdin: {}", e)))
-                })?;
-        }
-        let status = child
-            .wait()
-            .await
-            .map_err(|e| InjectionError::Process(format!("xclip wait: {}", e)))?;
-        if status.success() {
-            Ok(())
-        } else {
-            Err(InjectionError::Process("xclip failed".into()))
-        }
-    }
-
     // ...existing code...
 
     /// Main injection method with configurable behavior
-    pub async fn inject(&self, text: &str, _context: &InjectionContext) -> InjectionResult<()> {
+    pub async fn inject(&self, text: &str, context: &InjectionContext) -> InjectionResult<()> {
         if text.is_empty() {
             return Ok(());
         }
@@ -668,8 +1048,18 @@ impl UnifiedClipboardInjector {
         // Always read fresh clipboard for backup
         let backup = self.read_clipboard().await?;
 
-        // Seed clipboard with payload
-        self.write_clipboard(text.as_bytes(), "text/plain").await?;
+        // Seed clipboard with payload, with HTML for structured text when the
+        // target wants it
+        let html = context
+            .offer_html
+            .then(|| rich_text::to_html(text))
+            .flatten();
+        self.write_clipboard_entries(&seed_entries(
+            text,
+            html.as_deref(),
+            self.config.clipboard_manager_hint,
+        ))
+        .await?;
 
         // Stabilize clipboard
         tokio::time::sleep(Duration::from_millis(20)).await;
@@ -769,6 +1159,7 @@ impl TextInjector for UnifiedClipboardInjector {
                 ),
             ),
             ("backend", format!("{:?}", self.backend_type)),
+            ("clipboard_path", self.clipboard_path().to_string()),
             ("paste_methods", "AT-SPI, Enigo, ydotool".to_string()),
         ]
     }
@@ -834,6 +1225,73 @@ mod tests {
         assert!(!backup.is_valid(Duration::from_nanos(1)));
     }
 
+    #[test]
+    fn test_backup_type_order_prefers_text() {
+        let offered: Vec<String> = [
+            "TARGETS",
+            "image/png",
+            "text/html",
+            "UTF8_STRING",
+            "TIMESTAMP",
+            "image/png",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert_eq!(
+            order_backup_types(&offered),
+            vec!["UTF8_STRING", "image/png", "text/html"]
+        );
+
+        let offered = vec!["image/png".to_string(), "text/uri-list".to_string()];
+        assert_eq!(order_backup_types(&offered)[0], "image/png");
+
+        let mut backup = ClipboardBackup::new(b"hi".to_vec(), "UTF8_STRING".to_string());
+        backup
+            .alternates
+            .push(("image/png".to_string(), vec![1, 2]));
+        let mimes: Vec<&str> = backup.entries().iter().map(|(m, _)| *m).collect();
+        assert_eq!(mimes, vec!["UTF8_STRING", "image/png"]);
+    }
+
+    #[test]
+    fn test_seed_entries_manager_hint() {
+        let hinted = seed_entries("hello", None, true);
+        assert_eq!(hinted[0], ("text/plain;charset=utf-8", &b"hello"[..]));
+        assert_eq!(hinted.last().unwrap().0, PASSWORD_MANAGER_HINT);
+
+        let plain = seed_entries("hello", None, false);
+        assert!(plain.iter().all(|(mime, _)| is_text_type(mime)));
+    }
+
+    #[test]
+    fn test_seed_entries_offer_html_after_plain_text() {
+        let text = "- one\n- two";
+        let html = rich_text::to_html(text).unwrap();
+        let entries = seed_entries(text, Some(&html), false);
+        // Fallback tools only take the first entry, which stays plain text
+        assert!(is_text_type(entries[0].0));
+        assert_eq!(entries.last().unwrap(), &("text/html", html.as_bytes()));
+    }
+
+    #[test]
+    fn test_backend_info_reports_clipboard_path() {
+        let injector = UnifiedClipboardInjector::new(InjectionConfig::default());
+        let path = injector
+            .backend_info()
+            .into_iter()
+            .find(|(key, _)| *key == "clipboard_path")
+            .map(|(_, value)| value)
+            .unwrap();
+        assert_eq!(path, injector.clipboard_path().to_string());
+        if injector.backend_type != ClipboardBackend::Wayland {
+            assert_eq!(injector.clipboard_path(), ClipboardPath::Subprocess);
+        }
+
+        injector.set_wayland_path(ClipboardPath::Subprocess);
+        assert_eq!(injector.clipboard_path(), ClipboardPath::Subprocess);
+    }
+
     #[tokio::test]
     async fn test_empty_text_handling() {
         let config = InjectionConfig::default();
@@ -87,7 +87,7 @@ impl KdotoolInjector {
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(InjectionError::MethodFailed(format!(
+            return Err(InjectionError::TargetNotReady(format!(
                 "kdotool windowactivate failed: {}",
                 stderr
             )));
@@ -112,7 +112,7 @@ impl KdotoolInjector {
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(InjectionError::MethodFailed(format!(
+            return Err(InjectionError::TargetNotReady(format!(
                 "kdotool windowfocus failed: {}",
                 stderr
             )));
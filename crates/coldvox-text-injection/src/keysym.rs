@@ -0,0 +1,75 @@
+//! Unicode to keysym mapping shared by the typing backends.
+//!
+//! Backends that synthesize key presses differ in which characters they can
+//! reach:
+//!
+//! - X11 typing (xdotool, enigo) can type any character that has a keysym:
+//!   characters missing from the active layout are bound to a spare keycode
+//!   for the duration of the keystroke. That covers non-Latin scripts, emoji
+//!   and the characters normally produced with dead keys.
+//! - uinput typing (ydotool) only presses physical keys, so it reaches what
+//!   the active keyboard layout has keys for (see [`crate::layout`]); on a US
+//!   layout that is printable ASCII, newline and tab.
+//!
+//! Backends reject text with characters they can't reach, so the manager
+//! falls through to clipboard paste instead of typing the wrong characters.
+
+use xkeysym::Keysym;
+
+/// Characters a typing backend can produce
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypingReach {
+    /// Anything with a keysym (keycode remapping on X11)
+    Keysym,
+    /// Keys on a US layout, as pressed through uinput
+    UsLayout,
+}
+
+/// X keysym for `c` (xkbcommon's UTF-32 mapping), None when there is none
+pub fn keysym_for(c: char) -> Option<u32> {
+    let keysym = Keysym::from_char(c);
+    (keysym != Keysym::NoSymbol).then(|| keysym.raw())
+}
+
+/// Whether `c` can be typed by a backend with the given reach
+pub fn can_type(c: char, reach: TypingReach) -> bool {
+    match reach {
+        TypingReach::Keysym => keysym_for(c).is_some(),
+        TypingReach::UsLayout => c.is_ascii_graphic() || matches!(c, ' ' | '\n' | '\t'),
+    }
+}
+
+/// First character of `text` that can't be typed, if any
+pub fn first_untypeable(text: &str, reach: TypingReach) -> Option<char> {
+    text.chars().find(|&c| !can_type(c, reach))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_latin1_legacy_and_unicode_keysyms() {
+        assert_eq!(keysym_for('a'), Some(0x61));
+        assert_eq!(keysym_for('é'), Some(0xe9));
+        // Legacy keysym tables win over the Unicode range
+        assert_eq!(keysym_for('Ж'), Some(0x6f6));
+        assert_eq!(keysym_for('😀'), Some(0x0100_0000 + 0x1f600));
+        assert_eq!(keysym_for('\n'), Some(0xff0a));
+        assert_eq!(keysym_for('\u{fffe}'), None);
+    }
+
+    #[test]
+    fn reach_decides_what_can_be_typed() {
+        assert_eq!(
+            first_untypeable("plain text\n", TypingReach::UsLayout),
+            None
+        );
+        assert_eq!(first_untypeable("café", TypingReach::UsLayout), Some('é'));
+        assert_eq!(first_untypeable("café 😀 日本", TypingReach::Keysym), None);
+        assert_eq!(
+            first_untypeable("bad \u{fffe}", TypingReach::Keysym),
+            Some('\u{fffe}')
+        );
+    }
+}
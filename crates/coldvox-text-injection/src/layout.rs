@@ -0,0 +1,610 @@
+//! Keyboard layout awareness for typing through uinput.
+//!
+//! ydotool presses physical keys, so what comes out depends on the layout the
+//! desktop applies to them: on QWERTZ "z" and "y" swap, on AZERTY the digits
+//! need Shift, and `ydotool type`, which assumes a US layout, gets both wrong.
+//! The active layout is looked up once per process (`keyboard_layout`, then
+//! `XKB_DEFAULT_LAYOUT`, `setxkbmap -query` on X11 and `localectl status`;
+//! US when none of them says) and text is typed as the key presses that
+//! produce each character on it. Ctrl+<letter> shortcuts press the key that
+//! carries the letter. A character without a key on the layout (accents
+//! behind dead keys, for example) fails the backend, so the manager pastes
+//! instead.
+//!
+//! When the desktop has several layouts configured (`de,us`), the user can
+//! switch between them at any time and nothing here can see which one is
+//! active, so typing through uinput is refused and text is pasted. Setting
+//! `keyboard_layout` pins the layout and types on it again.
+//!
+//! US layouts use a built-in table. Other layouts need feature `xkb`, which
+//! compiles them with libxkbcommon; without it typing on them is refused and
+//! text is pasted.
+//!
+//! Keysym-based backends (xdotool, enigo, the portal) resolve characters
+//! against the live keymap themselves and don't use this.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+use tracing::{debug, warn};
+
+// Linux input keycodes (linux/input-event-codes.h)
+const KEY_LEFTCTRL: u16 = 29;
+const KEY_LEFTSHIFT: u16 = 42;
+const KEY_V: u16 = 47;
+const KEY_RIGHTALT: u16 = 100;
+
+/// Characters on a US layout: (character, keycode, needs Shift)
+const US_KEYS: [(char, u16, bool); 97] = [
+    ('1', 2, false),
+    ('2', 3, false),
+    ('3', 4, false),
+    ('4', 5, false),
+    ('5', 6, false),
+    ('6', 7, false),
+    ('7', 8, false),
+    ('8', 9, false),
+    ('9', 10, false),
+    ('0', 11, false),
+    ('-', 12, false),
+    ('=', 13, false),
+    ('\t', 15, false),
+    ('q', 16, false),
+    ('w', 17, false),
+    ('e', 18, false),
+    ('r', 19, false),
+    ('t', 20, false),
+    ('y', 21, false),
+    ('u', 22, false),
+    ('i', 23, false),
+    ('o', 24, false),
+    ('p', 25, false),
+    ('[', 26, false),
+    (']', 27, false),
+    ('\n', 28, false),
+    ('a', 30, false),
+    ('s', 31, false),
+    ('d', 32, false),
+    ('f', 33, false),
+    ('g', 34, false),
+    ('h', 35, false),
+    ('j', 36, false),
+    ('k', 37, false),
+    ('l', 38, false),
+    (';', 39, false),
+    ('\'', 40, false),
+    ('`', 41, false),
+    ('\\', 43, false),
+    ('z', 44, false),
+    ('x', 45, false),
+    ('c', 46, false),
+    ('v', 47, false),
+    ('b', 48, false),
+    ('n', 49, false),
+    ('m', 50, false),
+    (',', 51, false),
+    ('.', 52, false),
+    ('/', 53, false),
+    (' ', 57, false),
+    ('!', 2, true),
+    ('@', 3, true),
+    ('#', 4, true),
+    ('$', 5, true),
+    ('%', 6, true),
+    ('^', 7, true),
+    ('&', 8, true),
+    ('*', 9, true),
+    ('(', 10, true),
+    (')', 11, true),
+    ('_', 12, true),
+    ('+', 13, true),
+    ('Q', 16, true),
+    ('W', 17, true),
+    ('E', 18, true),
+    ('R', 19, true),
+    ('T', 20, true),
+    ('Y', 21, true),
+    ('U', 22, true),
+    ('I', 23, true),
+    ('O', 24, true),
+    ('P', 25, true),
+    ('{', 26, true),
+    ('}', 27, true),
+    ('A', 30, true),
+    ('S', 31, true),
+    ('D', 32, true),
+    ('F', 33, true),
+    ('G', 34, true),
+    ('H', 35, true),
+    ('J', 36, true),
+    ('K', 37, true),
+    ('L', 38, true),
+    (':', 39, true),
+    ('"', 40, true),
+    ('~', 41, true),
+    ('|', 43, true),
+    ('Z', 44, true),
+    ('X', 45, true),
+    ('C', 46, true),
+    ('V', 47, true),
+    ('B', 48, true),
+    ('N', 49, true),
+    ('M', 50, true),
+    ('<', 51, true),
+    ('>', 52, true),
+    ('?', 53, true),
+];
+
+/// An XKB layout and variant, e.g. `de` and `nodeadkeys`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutName {
+    pub layout: String,
+    /// Empty for the layout's default variant
+    pub variant: String,
+}
+
+impl LayoutName {
+    pub fn us() -> Self {
+        Self {
+            layout: "us".to_string(),
+            variant: String::new(),
+        }
+    }
+
+    /// Parse `de`, `de(nodeadkeys)` or `de:nodeadkeys`
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim();
+        let (layout, variant) = match name.split_once('(') {
+            Some((layout, rest)) => (layout, rest.trim_end_matches(')')),
+            None => name.split_once(':').unwrap_or((name, "")),
+        };
+        Self::first_group(layout, Some(variant))
+    }
+
+    /// The groups of comma-separated layout and variant lists, default first
+    fn groups(layouts: &str, variants: Option<&str>) -> Vec<Self> {
+        let mut variants = variants.unwrap_or("").split(',');
+        layouts
+            .split(',')
+            .map(|layout| (layout.trim(), variants.next().unwrap_or("").trim()))
+            .filter(|(layout, _)| !layout.is_empty())
+            .map(|(layout, variant)| Self {
+                layout: layout.to_string(),
+                variant: variant.to_string(),
+            })
+            .collect()
+    }
+
+    /// The first (default) group of comma-separated layout and variant lists
+    fn first_group(layouts: &str, variants: Option<&str>) -> Option<Self> {
+        Self::groups(layouts, variants).into_iter().next()
+    }
+
+    pub fn is_us(&self) -> bool {
+        self.layout == "us" && self.variant.is_empty()
+    }
+
+    /// The layout groups the desktop reports, default first; None when
+    /// nothing does
+    pub fn detect() -> Option<Vec<Self>> {
+        if let Ok(layouts) = std::env::var("XKB_DEFAULT_LAYOUT") {
+            let variants = std::env::var("XKB_DEFAULT_VARIANT").ok();
+            let groups = Self::groups(&layouts, variants.as_deref());
+            if !groups.is_empty() {
+                return Some(groups);
+            }
+        }
+        // Under Wayland, setxkbmap only sees Xwayland's keymap
+        let x11 =
+            std::env::var_os("DISPLAY").is_some() && std::env::var_os("WAYLAND_DISPLAY").is_none();
+        if x11 {
+            if let Some(name) = command_output("setxkbmap", &["-query"])
+                .as_deref()
+                .and_then(parse_setxkbmap)
+            {
+                return Some(name);
+            }
+        }
+        command_output("localectl", &["status"])
+            .as_deref()
+            .and_then(parse_localectl)
+    }
+}
+
+impl fmt::Display for LayoutName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.variant.is_empty() {
+            f.write_str(&self.layout)
+        } else {
+            write!(f, "{}({})", self.layout, self.variant)
+        }
+    }
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Value of a `name: value` line
+fn field<'a>(output: &'a str, name: &str) -> Option<&'a str> {
+    output.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == name).then(|| value.trim())
+    })
+}
+
+/// `setxkbmap -query`: `layout:     de,us` and `variant:    nodeadkeys,`
+fn parse_setxkbmap(output: &str) -> Option<Vec<LayoutName>> {
+    let groups = LayoutName::groups(field(output, "layout")?, field(output, "variant"));
+    (!groups.is_empty()).then_some(groups)
+}
+
+/// `localectl status`: `X11 Layout: de` and `X11 Variant: nodeadkeys`
+fn parse_localectl(output: &str) -> Option<Vec<LayoutName>> {
+    let groups = LayoutName::groups(field(output, "X11 Layout")?, field(output, "X11 Variant"));
+    (!groups.is_empty()).then_some(groups)
+}
+
+/// Key press that produces a character
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyStroke {
+    /// Linux input keycode
+    pub keycode: u16,
+    pub shift: bool,
+    /// AltGr (ISO level 3 shift)
+    pub altgr: bool,
+}
+
+impl KeyStroke {
+    /// ydotool `key` events: modifiers down, the key down and up, modifiers up
+    fn push_events(&self, events: &mut Vec<String>) {
+        let modifiers: Vec<u16> = [(self.shift, KEY_LEFTSHIFT), (self.altgr, KEY_RIGHTALT)]
+            .into_iter()
+            .filter_map(|(held, code)| held.then_some(code))
+            .collect();
+        events.extend(modifiers.iter().map(|code| format!("{code}:1")));
+        events.push(format!("{}:1", self.keycode));
+        events.push(format!("{}:0", self.keycode));
+        events.extend(modifiers.iter().rev().map(|code| format!("{code}:0")));
+    }
+}
+
+/// Which key presses produce which characters on a layout
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    name: LayoutName,
+    keys: HashMap<char, KeyStroke>,
+}
+
+impl KeyMap {
+    pub fn us() -> Self {
+        let keys = US_KEYS
+            .iter()
+            .map(|&(c, keycode, shift)| {
+                let stroke = KeyStroke {
+                    keycode,
+                    shift,
+                    altgr: false,
+                };
+                (c, stroke)
+            })
+            .collect();
+        Self {
+            name: LayoutName::us(),
+            keys,
+        }
+    }
+
+    /// Key map for `name`; None when it can't be built (non-US layouts
+    /// without feature `xkb`, or layouts xkbcommon doesn't know)
+    pub fn for_layout(name: &LayoutName) -> Option<Self> {
+        if name.is_us() {
+            return Some(Self::us());
+        }
+        Self::compile(name)
+    }
+
+    #[cfg(feature = "xkb")]
+    fn compile(name: &LayoutName) -> Option<Self> {
+        use xkbcommon::xkb;
+
+        let context = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+        let keymap = xkb::Keymap::new_from_names(
+            &context,
+            "evdev",
+            "pc105",
+            &name.layout,
+            &name.variant,
+            None,
+            xkb::KEYMAP_COMPILE_NO_FLAGS,
+        )?;
+        let mod_mask = |name: &str| {
+            let index = keymap.mod_get_index(name);
+            (index < 32).then(|| 1u32 << index)
+        };
+        let shift = mod_mask(xkb::MOD_NAME_SHIFT);
+        let altgr = mod_mask(xkb::MOD_NAME_ISO_LEVEL3_SHIFT);
+        let levels = [
+            (Some(0), false, false),
+            (shift, true, false),
+            (altgr, false, true),
+            (shift.zip(altgr).map(|(s, a)| s | a), true, true),
+        ];
+
+        let mut state = xkb::State::new(&keymap);
+        let mut keys = HashMap::new();
+        // The plainest stroke wins: no modifiers, then Shift, then AltGr
+        for (mask, shift, altgr) in levels {
+            let Some(mask) = mask else { continue };
+            state.update_mask(mask, 0, 0, 0, 0, 0);
+            for raw in keymap.min_keycode().raw()..=keymap.max_keycode().raw() {
+                // XKB keycodes are evdev keycodes plus 8
+                let Some(keycode) = raw.checked_sub(8).and_then(|k| u16::try_from(k).ok()) else {
+                    continue;
+                };
+                if !is_main_block(keycode) {
+                    continue;
+                }
+                let c = match char::from_u32(state.key_get_utf32(xkb::Keycode::new(raw))) {
+                    Some('\r') => '\n',
+                    Some(c) if c == '\t' || c == '\n' || !c.is_control() => c,
+                    _ => continue,
+                };
+                keys.entry(c).or_insert(KeyStroke {
+                    keycode,
+                    shift,
+                    altgr,
+                });
+            }
+        }
+        Some(Self {
+            name: name.clone(),
+            keys,
+        })
+    }
+
+    #[cfg(not(feature = "xkb"))]
+    fn compile(_name: &LayoutName) -> Option<Self> {
+        None
+    }
+
+    pub fn name(&self) -> &LayoutName {
+        &self.name
+    }
+
+    pub fn is_us(&self) -> bool {
+        self.name.is_us()
+    }
+
+    pub fn stroke(&self, c: char) -> Option<KeyStroke> {
+        self.keys.get(&c).copied()
+    }
+
+    /// First character of `text` with no key on this layout, if any
+    pub fn first_untypeable(&self, text: &str) -> Option<char> {
+        text.chars().find(|c| !self.keys.contains_key(c))
+    }
+
+    /// ydotool `key` events that type `text`, or the first character with
+    /// no key on this layout
+    pub fn key_events(&self, text: &str) -> Result<Vec<String>, char> {
+        let mut events = Vec::new();
+        for c in text.chars() {
+            self.stroke(c).ok_or(c)?.push_events(&mut events);
+        }
+        Ok(events)
+    }
+
+    /// The key for a Ctrl+<letter> shortcut given as its US keycode: the one
+    /// carrying that letter here. Other keys are returned unchanged.
+    pub fn shortcut_key(&self, us_keycode: u16) -> u16 {
+        let letter = US_KEYS
+            .iter()
+            .find(|&&(c, code, shift)| code == us_keycode && !shift && c.is_ascii_lowercase())
+            .map(|&(c, _, _)| c);
+        match letter.and_then(|c| self.stroke(c)) {
+            Some(stroke) if !stroke.shift && !stroke.altgr => stroke.keycode,
+            _ => us_keycode,
+        }
+    }
+}
+
+/// Keys of the main typing block, including the ISO key next to left Shift.
+/// Keypad keys depend on NumLock and media keys may be missing from the
+/// virtual keyboard.
+#[cfg(feature = "xkb")]
+fn is_main_block(keycode: u16) -> bool {
+    matches!(keycode, 2..=13 | 15..=28 | 30..=41 | 43..=53 | 57 | 86)
+}
+
+/// Why typing through uinput has no key map to go by
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NoKeyMap {
+    /// The layout can't be mapped (non-US without feature `xkb`, or unknown
+    /// to xkbcommon)
+    Unmapped(LayoutName),
+    /// Several layouts are configured and the user may switch between them
+    Switchable(Vec<LayoutName>),
+}
+
+impl fmt::Display for NoKeyMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NoKeyMap::Unmapped(name) => write!(f, "keyboard layout {}", name),
+            NoKeyMap::Switchable(groups) => {
+                f.write_str("switchable keyboard layouts ")?;
+                for (i, name) in groups.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", name)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+type Active = Result<Arc<KeyMap>, NoKeyMap>;
+
+/// Last lookup, keyed by the configured `keyboard_layout`
+static ACTIVE: Mutex<Option<(Option<String>, Active)>> = Mutex::new(None);
+
+/// Key map for the `keyboard_layout` setting (`configured`) or the detected
+/// layout groups. Several groups without a configured layout have no key map:
+/// the active group can change with every keystroke.
+fn resolve(configured: Option<&str>, detected: impl FnOnce() -> Option<Vec<LayoutName>>) -> Active {
+    let name = match configured.and_then(LayoutName::parse) {
+        Some(name) => name,
+        None => match detected() {
+            Some(groups) if groups.len() > 1 => return Err(NoKeyMap::Switchable(groups)),
+            Some(groups) => groups.into_iter().next().unwrap_or_else(LayoutName::us),
+            None => LayoutName::us(),
+        },
+    };
+    KeyMap::for_layout(&name)
+        .map(Arc::new)
+        .ok_or(NoKeyMap::Unmapped(name))
+}
+
+/// Key map of the active layout, or why there is none. `configured` is the
+/// `keyboard_layout` setting; the desktop's layouts are looked up once per
+/// process, so changing them needs a restart.
+pub fn active(configured: Option<&str>) -> Active {
+    let mut cached = ACTIVE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((key, active)) = cached.as_ref() {
+        if key.as_deref() == configured {
+            return active.clone();
+        }
+    }
+    let active = resolve(configured, LayoutName::detect);
+    match &active {
+        Ok(keymap) => debug!("Typing through uinput for keyboard layout {}", keymap.name()),
+        Err(NoKeyMap::Unmapped(name)) => warn!(
+            "No key map for keyboard layout {} (needs feature `xkb`); text typed through uinput is pasted instead",
+            name
+        ),
+        Err(reason @ NoKeyMap::Switchable(_)) => warn!(
+            "Cannot tell which of the {} is active; text typed through uinput is pasted instead (set injection.keyboard_layout to pin one)",
+            reason
+        ),
+    }
+    *cached = Some((configured.map(str::to_string), active.clone()));
+    active
+}
+
+/// `ydotool key` arguments pressing Ctrl+V on the active layout
+pub fn ydotool_paste_args(configured: Option<&str>) -> Vec<String> {
+    match active(configured) {
+        Ok(keymap) if keymap.shortcut_key(KEY_V) != KEY_V => {
+            let v = keymap.shortcut_key(KEY_V);
+            [(KEY_LEFTCTRL, 1), (v, 1), (v, 0), (KEY_LEFTCTRL, 0)]
+                .iter()
+                .map(|(code, state)| format!("{code}:{state}"))
+                .collect()
+        }
+        _ => vec!["ctrl+v".to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_layout_names_and_tool_output() {
+        let name = |layout: &str, variant: &str| LayoutName {
+            layout: layout.to_string(),
+            variant: variant.to_string(),
+        };
+        assert_eq!(LayoutName::parse("de"), Some(name("de", "")));
+        assert_eq!(
+            LayoutName::parse("de(nodeadkeys)"),
+            Some(name("de", "nodeadkeys"))
+        );
+        assert_eq!(LayoutName::parse("fr:bepo"), Some(name("fr", "bepo")));
+        assert_eq!(LayoutName::parse(" "), None);
+
+        let setxkbmap =
+            "rules:      evdev\nmodel:      pc105\nlayout:     de,us\nvariant:    nodeadkeys,\n";
+        assert_eq!(
+            parse_setxkbmap(setxkbmap),
+            Some(vec![name("de", "nodeadkeys"), name("us", "")])
+        );
+        let localectl = "   System Locale: LANG=fr_FR.UTF-8\n       VC Keymap: fr\n      X11 Layout: fr\n       X11 Model: pc105\n";
+        assert_eq!(parse_localectl(localectl), Some(vec![name("fr", "")]));
+        assert_eq!(parse_localectl("   System Locale: LANG=C\n"), None);
+    }
+
+    #[test]
+    fn switchable_layouts_are_not_typed_on_unless_pinned() {
+        let groups = || LayoutName::groups("us,de", Some(",nodeadkeys"));
+        let Err(NoKeyMap::Switchable(switchable)) = resolve(None, || Some(groups())) else {
+            panic!("several groups must not pick one");
+        };
+        assert_eq!(switchable, groups());
+        assert_eq!(
+            NoKeyMap::Switchable(switchable).to_string(),
+            "switchable keyboard layouts us,de(nodeadkeys)"
+        );
+
+        // Pinned by `keyboard_layout`, or only one group: typed on
+        let pinned = resolve(Some("us"), || Some(groups())).unwrap();
+        assert!(pinned.is_us());
+        let single = resolve(None, || Some(LayoutName::groups("us", None))).unwrap();
+        assert!(single.is_us());
+        assert!(resolve(None, || None).unwrap().is_us());
+    }
+
+    #[test]
+    fn us_map_types_with_shift_and_keeps_shortcuts() {
+        let us = KeyMap::us();
+        assert_eq!(
+            us.key_events("aZ!").unwrap(),
+            ["30:1", "30:0", "42:1", "44:1", "44:0", "42:0", "42:1", "2:1", "2:0", "42:0"]
+        );
+        assert_eq!(us.key_events("café"), Err('é'));
+        assert_eq!(us.first_untypeable("plain text\n"), None);
+        assert_eq!(us.shortcut_key(KEY_V), KEY_V);
+        assert_eq!(us.shortcut_key(KEY_LEFTCTRL), KEY_LEFTCTRL);
+    }
+
+    #[test]
+    fn shortcuts_follow_the_letter() {
+        // A QWERTZ-like map: y and z swapped
+        let mut qwertz = KeyMap::us();
+        qwertz.name = LayoutName::parse("de").unwrap();
+        for (c, keycode) in [('z', 21), ('y', 44)] {
+            qwertz.keys.insert(
+                c,
+                KeyStroke {
+                    keycode,
+                    shift: false,
+                    altgr: false,
+                },
+            );
+        }
+        // Ctrl+Z (undo) presses the key labelled Z
+        assert_eq!(qwertz.shortcut_key(44), 21);
+        assert_eq!(qwertz.key_events("z").unwrap(), ["21:1", "21:0"]);
+    }
+
+    #[cfg(feature = "xkb")]
+    #[test]
+    fn compiles_layouts_with_xkbcommon() {
+        let Some(de) = KeyMap::for_layout(&LayoutName::parse("de").unwrap()) else {
+            eprintln!("Skipping: xkeyboard-config data not installed");
+            return;
+        };
+        assert_eq!(de.stroke('z').map(|s| s.keycode), Some(21));
+        assert_eq!(de.stroke('ä').map(|s| s.keycode), Some(40));
+        let at = de.stroke('@').unwrap();
+        assert!(at.altgr && !at.shift);
+    }
+}
@@ -12,6 +12,7 @@
 //! | Enigo        | Cross    | Input simulation   | Beta   |
 //! | KDotool      | Linux    | X11 automation     | Beta   |
 //! | YDotool      | Linux    | uinput automation  | Beta   |
+//! | XDotool      | Linux    | X11 typing         | Beta   |
 //!
 //! ## Features
 //!
@@ -20,20 +21,48 @@
 //! - `enigo`: Cross-platform input simulation
 //! - `ydotool`: Linux uinput automation fallback for paste
 //! - `kdotool`: KDE/X11 window activation assistance
+//! - `xdotool`: Direct typing on plain X11 sessions
 //!
 //! - `regex`: Precompile allow/block list patterns
+//! - `ffi`: C ABI (`coldvox_inject_text` and friends) with a cbindgen header
+//! - `notifications`: Desktop notification when dictated text could not be injected
 //! - `all-backends`: Enable all available backends
 //! - `linux-desktop`: Enable recommended Linux desktop backends
 
+pub mod app_stats;
+pub mod audit;
 pub mod backend;
+pub mod bench;
+pub mod commands;
 pub mod compat;
+#[cfg(unix)]
+pub mod compositor_focus;
+pub mod content_filter;
 pub mod detection;
+pub mod dictation_mode;
+pub mod display_watch;
+pub mod failure_notice;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod focus;
+pub mod guard;
+pub mod injection_event;
+pub mod keysym;
+pub mod layout;
 pub mod log_throttle;
 pub mod logging;
 pub mod manager;
+pub mod normalize;
+pub mod pacing;
+pub mod prerequisites;
 pub mod processor;
+pub mod progress;
+pub mod queue;
+pub mod rich_text;
+pub mod screen_share;
 pub mod session;
+pub mod target_filter;
+pub mod target_lock;
 pub mod types;
 
 // NOTE: window_manager intentionally violates the "no-sprawl" principle.
@@ -68,6 +97,12 @@ pub mod enigo_injector;
 #[cfg(feature = "kdotool")]
 pub mod kdotool_injector;
 
+#[cfg(feature = "xdotool")]
+pub mod xdotool_injector;
+
+#[cfg(feature = "portal")]
+pub mod portal_injector;
+
 // Ydotool is Linux-only; provide real module on Unix and a stub elsewhere
 #[cfg(all(unix, feature = "ydotool"))]
 pub mod ydotool_injector;
@@ -97,6 +132,18 @@ pub mod ydotool_injector {
 
         #[allow(dead_code)]
         pub(crate) fn apply_socket_env(_command: &mut tokio::process::Command) {}
+
+        pub async fn erase_chars(&self, _count: usize) -> InjectionResult<()> {
+            Err(crate::InjectionError::MethodUnavailable(
+                "ydotool is not available on this platform".to_string(),
+            ))
+        }
+
+        pub async fn send_key_chord(&self, _keycodes: &[u16]) -> InjectionResult<()> {
+            Err(crate::InjectionError::MethodUnavailable(
+                "ydotool is not available on this platform".to_string(),
+            ))
+        }
     }
 
     #[async_trait]
@@ -128,16 +175,32 @@ pub mod ydotool_injector {
 // NoOp fallback is always available
 pub mod noop_injector;
 
+// Terminal clipboard for sessions without a display server
+pub mod osc52_injector;
+
 // Re-export key components for easy access
 pub use backend::Backend;
-pub use coldvox_foundation::error::InjectionError;
+pub use coldvox_foundation::error::{FailureClass, InjectionError, Refusal};
+pub use dictation_mode::DictationMode;
 pub use focus::{FocusProvider, FocusStatus};
-pub use manager::StrategyManager;
-pub use processor::{AsyncInjectionProcessor, InjectionProcessor, ProcessorMetrics};
+pub use guard::GuardEvent;
+pub use injection_event::InjectionEvent;
+pub use manager::{BackendCatalog, StrategyManager};
+pub use processor::{
+    AsyncInjectionProcessor, DictationModeHandle, DraftHandle, HeldTranscript, HoldReason,
+    InjectionProcessor, LowConfidenceHandle, ProcessorMetrics,
+};
+pub use progress::{PasteControl, PasteProgress};
 pub use session::{InjectionSession, SessionConfig, SessionState};
+pub use target_filter::{InjectionTarget, TargetField, TargetFilter};
+pub use target_lock::TargetLock;
 pub use types::{
-    InjectionConfig, InjectionContext, InjectionMethod, InjectionMode, InjectionResult,
+    register_custom_method, registered_custom_methods, AtspiTextAttributes, BackendCapability,
+    BackendError, BackendStatus, CustomMethodInfo, InjectionConfig, InjectionContext,
+    InjectionMethod, InjectionMode, InjectionResult, QueueDropPolicy, ScreenSharePolicy,
+    SensitiveContentAction, UnavailableReason,
 };
+pub use window_manager::WindowRef;
 
 /// Trait defining the core text injection interface
 #[async_trait::async_trait]
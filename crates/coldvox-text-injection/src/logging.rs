@@ -281,7 +281,7 @@ pub mod utils {
             let hash = hasher.finish() & 0xFFFFFFFF;
             format!("[REDACTED] len={} hash={:08x}", text.len(), hash)
         } else {
-            text.to_string()
+            crate::content_filter::scrub(text)
         };
 
         info!(
@@ -307,7 +307,7 @@ pub mod utils {
             let hash = hasher.finish() & 0xFFFFFFFF;
             format!("[REDACTED] len={} hash={:08x}", text.len(), hash)
         } else {
-            text.to_string()
+            crate::content_filter::scrub(text)
         };
 
         info!(
@@ -335,7 +335,7 @@ pub mod utils {
             let hash = hasher.finish() & 0xFFFFFFFF;
             format!("[REDACTED] len={} hash={:08x}", text.len(), hash)
         } else {
-            text.to_string()
+            crate::content_filter::scrub(text)
         };
 
         warn!(
@@ -1,39 +1,62 @@
+use crate::app_stats::{self, AppStatsStore, SharedAppStats};
+use crate::audit::{AuditLog, AuditRecord, Confirmation};
 use crate::backend::{Backend, BackendDetector};
 use crate::focus::{FocusProvider, FocusStatus, FocusTracker};
+use crate::injection_event::InjectionEvent;
 use crate::log_throttle::LogThrottle;
 use crate::logging::utils as log_utils;
+use crate::normalize;
+use crate::pacing::{self, KeystrokePacer, PaceSignal};
 use crate::prewarm::PrewarmController;
+use crate::progress::{split_paste_chunks, PasteControl, PasteProgress};
+use crate::rich_text::HtmlPolicy;
+use crate::screen_share::{self, ScreenShareDetector};
 use crate::session::{InjectionSession, SessionState};
+use crate::target_filter::{InjectionTarget, TargetField, TargetFilter};
+use crate::target_lock::{self, TargetLock};
 use crate::types::{
-    InjectionConfig, InjectionContext, InjectionMethod, InjectionMetrics, InjectionMode,
+    BackendCapability, BackendError, BackendStatus, InjectionConfig, InjectionContext,
+    InjectionMethod, InjectionMetrics, InjectionMode, ScreenSharePolicy, UnavailableReason,
+    CAPABILITY_HISTORY_DAYS,
 };
 use crate::TextInjector;
 
 /// Type alias for cached method ordering: (app_id, methods)
 type CachedMethodOrder = Option<(String, Vec<InjectionMethod>)>;
-use coldvox_foundation::error::InjectionError;
+use coldvox_foundation::error::{FailureClass, InjectionError, Refusal};
+use coldvox_foundation::events;
 
 // Import injectors
 #[cfg(feature = "enigo")]
 use crate::enigo_injector::EnigoInjector;
 #[cfg(feature = "atspi")]
 use crate::injectors::atspi::AtspiInjector;
+use crate::injectors::primary_selection::PrimarySelectionInjector;
 #[cfg(feature = "wl_clipboard")]
 use crate::injectors::unified_clipboard::UnifiedClipboardInjector;
 #[cfg(feature = "kdotool")]
 use crate::kdotool_injector::KdotoolInjector;
+#[cfg(feature = "portal")]
+use crate::portal_injector::PortalInjector;
+#[cfg(feature = "xdotool")]
+use crate::xdotool_injector::XdotoolInjector;
 
 use crate::noop_injector::NoOpInjector;
+use crate::osc52_injector::Osc52Injector;
 use std::collections::hash_map::DefaultHasher;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::hash::{Hash, Hasher};
 use std::io::Write;
 use std::process;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use tracing::{debug, error, info, trace, warn};
 
+/// Focused-text checks after typing before text counts as dropped
+const PACE_CONFIRM_POLLS: u32 = 5;
+const PACE_CONFIRM_INTERVAL: Duration = Duration::from_millis(20);
+
 /// Key for identifying a specific app-method combination
 type AppMethodKey = (String, InjectionMethod);
 
@@ -46,7 +69,7 @@ fn redact_text(text: &str, redact: bool) -> String {
         let hash = hasher.finish();
         format!("len={} hash={:08x}", text.len(), (hash & 0xFFFFFFFF))
     } else {
-        text.to_string()
+        crate::content_filter::scrub(text)
     }
 }
 
@@ -70,6 +93,7 @@ struct CooldownState {
 }
 
 /// Registry of available text injectors
+#[derive(Clone)]
 struct InjectorRegistry {
     injectors: HashMap<InjectionMethod, Arc<dyn TextInjector>>,
 }
@@ -114,6 +138,13 @@ impl InjectorRegistry {
             }
         }
 
+        if config.allow_primary_selection {
+            let primary = PrimarySelectionInjector::new(config.clone());
+            if primary.is_available().await {
+                injectors.insert(InjectionMethod::PrimarySelection, Arc::new(primary));
+            }
+        }
+
         // Do not register YdoTool as a standalone method: ClipboardPaste already falls back to ydotool.
         // This keeps a single paste path in the strategy manager.
 
@@ -133,6 +164,30 @@ impl InjectorRegistry {
             }
         }
 
+        #[cfg(feature = "xdotool")]
+        if config.allow_xdotool {
+            let xdotool = XdotoolInjector::new(config.clone());
+            if xdotool.is_available().await {
+                injectors.insert(InjectionMethod::XdoToolType, Arc::new(xdotool));
+            }
+        }
+
+        #[cfg(feature = "portal")]
+        if config.allow_portal && backends.contains(&Backend::WaylandXdgDesktopPortal) {
+            let portal = PortalInjector::new(config.clone());
+            if portal.is_available().await {
+                injectors.insert(InjectionMethod::PortalKeyboard, Arc::new(portal));
+            }
+        }
+
+        // In a TTY or SSH session the terminal's clipboard is all there is
+        if backend_detector.is_headless() {
+            let osc52 = Osc52Injector::new(config.clone());
+            if osc52.is_available().await {
+                injectors.insert(InjectionMethod::Osc52Clipboard, Arc::new(osc52));
+            }
+        }
+
         // Add NoOpInjector as final fallback if no other injectors are available
         if injectors.is_empty() {
             injectors.insert(
@@ -151,6 +206,212 @@ impl InjectorRegistry {
     fn contains(&self, method: InjectionMethod) -> bool {
         self.injectors.contains_key(&method)
     }
+
+    fn insert(&mut self, method: InjectionMethod, injector: Arc<dyn TextInjector>) {
+        self.injectors.insert(method, injector);
+    }
+
+    /// Registered custom methods, sorted by name
+    fn custom_methods(&self) -> Vec<InjectionMethod> {
+        let mut methods: Vec<InjectionMethod> = self
+            .injectors
+            .keys()
+            .copied()
+            .filter(InjectionMethod::is_custom)
+            .collect();
+        methods.sort();
+        methods
+    }
+}
+
+/// Injector registry shared between a manager and its catalogs; replaced
+/// wholesale when backends are rebuilt after a display-server restart
+type SharedRegistry = Arc<Mutex<Arc<InjectorRegistry>>>;
+
+/// Why `method` is not available. Registration follows
+/// [`InjectorRegistry::build`]: a cargo feature, an `allow_*` setting, then a
+/// startup probe.
+fn unavailable_reason(
+    method: InjectionMethod,
+    registered: bool,
+    config: &InjectionConfig,
+) -> UnavailableReason {
+    if registered {
+        return UnavailableReason::CheckFailing;
+    }
+    // Cargo feature, whether it is compiled in, and the allow_* setting
+    let (feature, compiled, setting) = match method {
+        InjectionMethod::AtspiInsert => ("atspi", cfg!(feature = "atspi"), None),
+        InjectionMethod::ClipboardPasteFallback => {
+            ("wl_clipboard", cfg!(feature = "wl_clipboard"), None)
+        }
+        InjectionMethod::KdoToolAssist => (
+            "kdotool",
+            cfg!(feature = "kdotool"),
+            Some(("injection.allow_kdotool", config.allow_kdotool)),
+        ),
+        InjectionMethod::XdoToolType => (
+            "xdotool",
+            cfg!(feature = "xdotool"),
+            Some(("injection.allow_xdotool", config.allow_xdotool)),
+        ),
+        InjectionMethod::PortalKeyboard => (
+            "portal",
+            cfg!(feature = "portal"),
+            Some(("injection.allow_portal", config.allow_portal)),
+        ),
+        InjectionMethod::EnigoText => (
+            "enigo",
+            cfg!(feature = "enigo"),
+            Some(("injection.allow_enigo", config.allow_enigo)),
+        ),
+        InjectionMethod::PrimarySelection => (
+            "",
+            true,
+            Some((
+                "injection.allow_primary_selection",
+                config.allow_primary_selection,
+            )),
+        ),
+        // Registered in TTY sessions without a display server
+        InjectionMethod::Osc52Clipboard => return UnavailableReason::NotDetected,
+        InjectionMethod::NoOp => return UnavailableReason::FallbackOnly,
+        InjectionMethod::Custom(_) => return UnavailableReason::NotDetected,
+    };
+    if !compiled {
+        return UnavailableReason::NotCompiled(feature);
+    }
+    match setting {
+        Some((key, false)) => UnavailableReason::Disabled(key),
+        _ => UnavailableReason::NotDetected,
+    }
+}
+
+/// Cloneable view of a manager's backends, still usable after the manager
+/// has moved into the injection worker
+#[derive(Clone)]
+pub struct BackendCatalog {
+    injectors: SharedRegistry,
+    cooldowns: Arc<Mutex<HashMap<AppMethodKey, CooldownState>>>,
+    global_start: Arc<Mutex<Option<Instant>>>,
+    metrics: Arc<InjectionMetrics>,
+    config: Arc<InjectionConfig>,
+    app_stats: Option<SharedAppStats>,
+}
+
+impl BackendCatalog {
+    /// End running cooldowns and restart the latency budget of an injection
+    /// in progress. Used after a system suspend, when `Instant` deadlines no
+    /// longer say anything about how long ago a backend failed.
+    pub fn reset_timers(&self) {
+        let now = Instant::now();
+        if let Ok(mut cooldowns) = self.cooldowns.lock() {
+            for cooldown in cooldowns.values_mut() {
+                cooldown.until = cooldown.until.min(now);
+            }
+        }
+        if let Ok(mut start) = self.global_start.lock() {
+            if start.is_some() {
+                *start = Some(now);
+            }
+        }
+    }
+
+    /// Status of every built-in method, then registered custom methods by
+    /// name. The NoOp fallback is only listed when it is registered.
+    pub async fn list_backends(&self) -> Vec<BackendStatus> {
+        let registry = self.injectors.lock().unwrap().clone();
+        let mut methods: Vec<InjectionMethod> = InjectionMethod::BUILTIN
+            .into_iter()
+            .filter(|m| *m != InjectionMethod::NoOp || registry.contains(*m))
+            .collect();
+        methods.extend(registry.custom_methods());
+
+        // Probe availability before taking any locks
+        let mut backends = Vec::with_capacity(methods.len());
+        for method in methods {
+            let status = match registry.get(method) {
+                Some(injector) => BackendStatus {
+                    method,
+                    name: injector.backend_name(),
+                    registered: true,
+                    available: injector.is_available().await,
+                    cooling_down: false,
+                    attempts: 0,
+                    successes: 0,
+                    failures: 0,
+                    last_error: None,
+                    capabilities: injector.backend_info(),
+                },
+                None => BackendStatus {
+                    method,
+                    name: method.name(),
+                    registered: false,
+                    available: false,
+                    cooling_down: false,
+                    attempts: 0,
+                    successes: 0,
+                    failures: 0,
+                    last_error: None,
+                    capabilities: Vec::new(),
+                },
+            };
+            backends.push(status);
+        }
+
+        let now = Instant::now();
+        if let Ok(cooldowns) = self.cooldowns.lock() {
+            for status in &mut backends {
+                status.cooling_down = cooldowns
+                    .iter()
+                    .any(|((_, m), cd)| *m == status.method && now < cd.until);
+            }
+        }
+        for status in &mut backends {
+            if let Some(m) = self.metrics.method(status.method) {
+                status.attempts = m.attempts;
+                status.successes = m.successes;
+                status.failures = m.failures;
+                status.last_error = m.last_failure.map(|(at, message)| BackendError {
+                    message,
+                    age: now.saturating_duration_since(at),
+                });
+            }
+        }
+        backends
+    }
+
+    /// [`list_backends`](Self::list_backends) with each backend's
+    /// unavailability reason, supported modes and measured performance
+    pub async fn capability_report(&self) -> Vec<BackendCapability> {
+        let backends = self.list_backends().await;
+        let history = self.app_stats.as_ref().map(|stats| {
+            stats.read(|store| {
+                store.window(chrono::Local::now().date_naive(), CAPABILITY_HISTORY_DAYS)
+            })
+        });
+        backends
+            .into_iter()
+            .map(|status| {
+                let unavailable_reason = (!status.available)
+                    .then(|| unavailable_reason(status.method, status.registered, &self.config));
+                let session_avg_ms = self
+                    .metrics
+                    .method(status.method)
+                    .filter(|m| m.attempts > 0)
+                    .map(|m| m.total_duration_ms / m.attempts);
+                BackendCapability {
+                    unavailable_reason,
+                    modes: status.method.supported_modes(),
+                    session_avg_ms,
+                    history: history
+                        .as_ref()
+                        .and_then(|h| h.get(&status.method).cloned()),
+                    status,
+                }
+            })
+            .collect()
+    }
 }
 
 /// Strategy manager for adaptive text injection
@@ -161,36 +422,54 @@ pub struct StrategyManager {
     focus_provider: Box<dyn FocusProvider>,
     /// Cache of success records per app-method combination
     success_cache: Arc<Mutex<HashMap<AppMethodKey, SuccessRecord>>>,
+    /// Keystroke rates learned per app for typing backends
+    pacer: Arc<Mutex<KeystrokePacer>>,
     /// Cooldown states per app-method combination
     cooldowns: Arc<Mutex<HashMap<AppMethodKey, CooldownState>>>,
     /// Global start time for budget tracking
     global_start: Arc<Mutex<Option<Instant>>>,
     /// Metrics for the strategy manager
-    metrics: Arc<Mutex<InjectionMetrics>>,
+    metrics: Arc<InjectionMetrics>,
     /// Backend detector for platform-specific capabilities
     #[allow(dead_code)]
     backend_detector: BackendDetector,
     /// Registry of available injectors
-    injectors: Arc<InjectorRegistry>,
+    injectors: SharedRegistry,
     /// Cached method ordering for the current app_id
     cached_method_order: Arc<RwLock<CachedMethodOrder>>,
-    /// Cached compiled allowlist regex patterns
-    #[cfg(feature = "regex")]
-    allowlist_regexes: Vec<regex::Regex>,
-    /// Cached compiled blocklist regex patterns
-    #[cfg(feature = "regex")]
-    blocklist_regexes: Vec<regex::Regex>,
+    /// Compiled allow/block lists (app id, window title and page URL patterns)
+    target_filter: TargetFilter,
+    /// Targets offered HTML on clipboard pastes
+    html_policy: HtmlPolicy,
     /// Log throttle to reduce backend selection noise
     log_throttle: Mutex<LogThrottle>,
     /// Pre-warm controller for caching resources
     prewarm_controller: Arc<PrewarmController>,
     /// Session state for buffering (when available)
     session: Option<Arc<RwLock<InjectionSession>>>,
+    /// Persistent per-app statistics (when `stats_path` is configured)
+    app_stats: Option<SharedAppStats>,
+    /// Injection audit log (when `audit_log_path` is configured)
+    audit: Option<AuditLog>,
+    /// Progress events and cancellation for chunked pastes
+    paste_control: PasteControl,
+    /// Methods tried and the one that delivered
+    events_tx: broadcast::Sender<InjectionEvent>,
+    /// Window injection is locked to, if any
+    target_lock: TargetLock,
+    /// Screen-share detection for `screen_share_policy`
+    screen_share: ScreenShareDetector,
 }
 
 impl StrategyManager {
     /// Create a new strategy manager with default focus tracker
-    pub async fn new(config: InjectionConfig, metrics: Arc<Mutex<InjectionMetrics>>) -> Self {
+    pub async fn new(config: InjectionConfig, metrics: Arc<InjectionMetrics>) -> Self {
+        #[cfg(unix)]
+        let focus: Box<dyn FocusProvider> = match crate::compositor_focus::detect() {
+            Some(backend) => Box::new(FocusTracker::with_backend(config.clone(), backend)),
+            None => Box::new(FocusTracker::new(config.clone())),
+        };
+        #[cfg(not(unix))]
         let focus = Box::new(FocusTracker::new(config.clone()));
         Self::new_with_focus_provider(config, metrics, focus).await
     }
@@ -198,7 +477,7 @@ impl StrategyManager {
     /// Create a new strategy manager with an injected focus provider (for tests)
     pub async fn new_with_focus_provider(
         config: InjectionConfig,
-        metrics: Arc<Mutex<InjectionMetrics>>,
+        metrics: Arc<InjectionMetrics>,
         focus_provider: Box<dyn FocusProvider>,
     ) -> Self {
         let backend_detector = BackendDetector::new(config.clone());
@@ -218,71 +497,134 @@ impl StrategyManager {
                     warn!("No suitable backend found for text injection");
                 }
             }
-            if let Ok(mut m) = metrics.lock() {
-                m.record_backend_denied();
-            }
+            metrics.record_backend_denied();
         }
 
         // Build injector registry
         let injectors = InjectorRegistry::build(&config, &backend_detector).await;
 
-        // Compile regex patterns once for performance
-        #[cfg(feature = "regex")]
-        let allowlist_regexes: Vec<regex::Regex> = config
-            .allowlist
-            .iter()
-            .filter_map(|pattern| match regex::Regex::new(pattern) {
-                Ok(re) => Some(re),
-                Err(e) => {
-                    warn!(
-                        "Invalid allowlist regex pattern '{}': {}, skipping",
-                        pattern, e
-                    );
-                    None
-                }
-            })
-            .collect();
-
-        #[cfg(feature = "regex")]
-        let blocklist_regexes: Vec<regex::Regex> = config
-            .blocklist
-            .iter()
-            .filter_map(|pattern| match regex::Regex::new(pattern) {
-                Ok(re) => Some(re),
-                Err(e) => {
-                    warn!(
-                        "Invalid blocklist regex pattern '{}': {}, skipping",
-                        pattern, e
-                    );
-                    None
-                }
-            })
-            .collect();
+        // Compile allow/block patterns once for performance
+        let target_filter = TargetFilter::new(&config.allowlist, &config.blocklist);
 
         // Record regex cache sizes in metrics (when enabled)
         #[cfg(feature = "regex")]
-        if let Ok(mut m) = metrics.lock() {
-            m.set_allowlist_regex_count(allowlist_regexes.len());
-            m.set_blocklist_regex_count(blocklist_regexes.len());
+        {
+            metrics.set_allowlist_regex_count(target_filter.allowlist_len());
+            metrics.set_blocklist_regex_count(target_filter.blocklist_len());
         }
 
+        let app_stats =
+            config
+                .stats_path
+                .as_ref()
+                .and_then(|path| match AppStatsStore::load(path.clone()) {
+                    Ok(store) => Some(SharedAppStats::new(store, app_stats::SAVE_DEBOUNCE)),
+                    Err(e) => {
+                        warn!(
+                            "Not persisting injection stats: failed to load {}: {}",
+                            path.display(),
+                            e
+                        );
+                        None
+                    }
+                });
+
         Self {
             config: config.clone(),
             focus_provider,
             success_cache: Arc::new(Mutex::new(HashMap::new())),
+            pacer: Arc::new(Mutex::new(KeystrokePacer::new(&config))),
             cooldowns: Arc::new(Mutex::new(HashMap::new())),
             global_start: Arc::new(Mutex::new(None)),
             metrics,
             backend_detector,
-            injectors: Arc::new(injectors),
+            injectors: Arc::new(Mutex::new(Arc::new(injectors))),
             cached_method_order: Arc::new(RwLock::new(None)),
-            #[cfg(feature = "regex")]
-            allowlist_regexes,
-            #[cfg(feature = "regex")]
-            blocklist_regexes,
+            target_filter,
+            html_policy: HtmlPolicy::new(&config),
             log_throttle,
-            prewarm_controller: Arc::new(PrewarmController::new(config)),
+            prewarm_controller: Arc::new(PrewarmController::new(config.clone())),
             session: None, // Session management is optional for backward compatibility
+            app_stats,
+            audit: config.audit_log_path.clone().map(AuditLog::new),
+            paste_control: PasteControl::new(),
+            events_tx: InjectionEvent::channel(),
+            target_lock: TargetLock::new(),
+            screen_share: ScreenShareDetector::new(),
+        }
+    }
+
+    /// Fold one injection request into the session's per-app counters, the
+    /// persistent per-app statistics and today's per-backend aggregates.
+    /// `attempts` carry each method's latency.
+    fn record_app_stats(
+        &self,
+        app_id: &str,
+        attempts: &[(InjectionMethod, bool, u64)],
+        latency_ms: u64,
+    ) {
+        self.metrics
+            .record_app_request(app_id, attempts.iter().any(|(_, ok, _)| *ok));
+        let Some(stats) = &self.app_stats else {
+            return;
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let outcomes: Vec<(InjectionMethod, bool)> = attempts
+            .iter()
+            .map(|(method, ok, _)| (*method, *ok))
+            .collect();
+        // Saved to disk by the store's background writer
+        stats.update(|store| {
+            store.record(app_id, &outcomes, latency_ms, now);
+            store.record_attempts(chrono::Local::now().date_naive(), attempts);
+        });
+    }
+
+    /// Append one injection request to the audit log. Successes are checked
+    /// against the focused element's text when it can be read.
+    async fn record_audit(
+        &self,
+        app_id: &str,
+        text: &str,
+        method: Option<InjectionMethod>,
+        error: Option<&InjectionError>,
+        latency_ms: u64,
+    ) {
+        let Some(audit) = &self.audit else {
+            return;
+        };
+        let mut record = AuditRecord::new(app_id, text, self.config.audit_text_mode());
+        record.method = method;
+        record.latency_ms = latency_ms;
+        match error {
+            Some(e) => {
+                record.success = false;
+                record.error = Some(e.to_string());
+            }
+            None => {
+                let focused = tokio::time::timeout(
+                    self.config.per_method_timeout(),
+                    crate::confirm::focused_text(),
+                )
+                .await;
+                record.confirmation = match focused {
+                    Ok(Ok(Some(content))) if content.contains(text.trim()) => {
+                        Confirmation::Confirmed
+                    }
+                    Ok(Ok(Some(_))) => Confirmation::NotSeen,
+                    _ => Confirmation::Unknown,
+                };
+            }
+        }
+        if let Err(e) = audit.append(&record) {
+            warn!(
+                "Failed to write injection audit log {}: {}",
+                audit.path().display(),
+                e
+            );
         }
     }
 
@@ -346,72 +688,10 @@ impl StrategyManager {
 
     /// Get active window class via window manager
     #[cfg(target_os = "linux")]
-    #[allow(clippy::unused_async)] // Function needs to be async to match trait/interface expectations
     async fn get_active_window_class(&self) -> Result<String, InjectionError> {
-        use std::process::Command;
-
-        // Try xprop for X11
-        if let Ok(output) = Command::new("xprop")
-            .args(["-root", "_NET_ACTIVE_WINDOW"])
-            .output()
-        {
-            if output.status.success() {
-                let window_str = String::from_utf8_lossy(&output.stdout);
-                if let Some(window_id) = window_str.split("# ").nth(1) {
-                    let window_id = window_id.trim();
-
-                    // Get window class
-                    if let Ok(class_output) = Command::new("xprop")
-                        .args(["-id", window_id, "WM_CLASS"])
-                        .output()
-                    {
-                        if class_output.status.success() {
-                            let class_str = String::from_utf8_lossy(&class_output.stdout);
-                            // Parse WM_CLASS string
-                            if let Some(class_part) = class_str.split('"').nth(3) {
-                                return Ok(class_part.to_string());
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        // Try swaymsg for Wayland
-        if let Ok(output) = Command::new("swaymsg").args(["-t", "get_tree"]).output() {
-            if output.status.success() {
-                let tree = String::from_utf8_lossy(&output.stdout);
-                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&tree) {
-                    // Find focused window
-                    fn find_focused_window(node: &serde_json::Value) -> Option<String> {
-                        if node.get("focused").and_then(|v| v.as_bool()) == Some(true) {
-                            if let Some(app_id) = node.get("app_id").and_then(|v| v.as_str()) {
-                                return Some(app_id.to_string());
-                            }
-                        }
-
-                        // Check children
-                        if let Some(nodes) = node.get("nodes").and_then(|v| v.as_array()) {
-                            for n in nodes {
-                                if let Some(found) = find_focused_window(n) {
-                                    return Some(found);
-                                }
-                            }
-                        }
-
-                        None
-                    }
-
-                    if let Some(app_id) = find_focused_window(&json) {
-                        return Ok(app_id);
-                    }
-                }
-            }
-        }
-
-        Err(InjectionError::Other(
-            "Could not determine active window class".to_string(),
-        ))
+        tokio::task::spawn_blocking(crate::window_manager::get_active_window_class)
+            .await
+            .map_err(|e| InjectionError::Other(format!("Window class lookup failed: {}", e)))?
     }
 
     /// Check if injection is currently paused
@@ -421,53 +701,53 @@ impl StrategyManager {
         false
     }
 
-    /// Check if the current application is allowed for injection
-    /// When feature `regex` is enabled, patterns are compiled once at
-    /// StrategyManager construction and stored as `Regex` objects; otherwise we
-    /// fallback to substring match semantics. Invalid regex patterns are logged
-    /// and skipped.
-    pub(crate) fn is_app_allowed(&self, app_id: &str) -> bool {
-        // If allowlist is not empty, only allow apps in the allowlist
-        if !self.config.allowlist.is_empty() {
-            #[cfg(feature = "regex")]
-            return self.allowlist_regexes.iter().any(|re| re.is_match(app_id));
-            #[cfg(not(feature = "regex"))]
-            return self
-                .config
-                .allowlist
-                .iter()
-                .map(|pattern| Self::_strip_anchors_local(pattern))
-                .any(|pattern| app_id.contains(&pattern));
-        }
-
-        // If blocklist is not empty, block apps in the blocklist
-        if !self.config.blocklist.is_empty() {
-            #[cfg(feature = "regex")]
-            return !self.blocklist_regexes.iter().any(|re| re.is_match(app_id));
-            #[cfg(not(feature = "regex"))]
-            return !self
-                .config
-                .blocklist
-                .iter()
-                .map(|pattern| Self::_strip_anchors_local(pattern))
-                .any(|pattern| app_id.contains(&pattern));
-        }
-
-        // If neither allowlist nor blocklist is set, allow all apps
-        true
+    fn needs_target_field(&self, field: TargetField) -> bool {
+        self.target_filter.uses(field) || self.html_policy.uses(field)
     }
 
-    #[cfg(not(feature = "regex"))]
-    fn _strip_anchors_local(pattern: &str) -> String {
-        // Remove a leading '^' and trailing '$' to make simple substring semantics
-        let mut s = pattern;
-        if let Some(stripped) = s.strip_prefix('^') {
-            s = stripped;
-        }
-        if let Some(stripped) = s.strip_suffix('$') {
-            s = stripped;
+    /// Describe the injection target for the allow/block lists. The window
+    /// title and page URL are only looked up when a pattern needs them.
+    async fn current_target(
+        &mut self,
+        app_id: String,
+        window_title: Option<String>,
+    ) -> InjectionTarget {
+        let window_title = if window_title.is_some() {
+            window_title
+        } else if self.needs_target_field(TargetField::WindowTitle) {
+            tokio::task::spawn_blocking(crate::window_manager::get_active_window_title)
+                .await
+                .ok()
+                .and_then(Result::ok)
+        } else {
+            None
+        };
+        let url = if self.needs_target_field(TargetField::Url) {
+            let timeout = self.config.per_method_timeout();
+            match tokio::time::timeout(timeout, self.focus_provider.get_focused_url()).await {
+                Ok(Ok(url)) => url,
+                Ok(Err(e)) => {
+                    debug!("Focused URL unavailable: {}", e);
+                    None
+                }
+                Err(_) => {
+                    debug!("Focused URL lookup timed out");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        InjectionTarget {
+            app_id,
+            window_title,
+            url,
         }
-        s.to_string()
+    }
+
+    /// Current injector registry
+    fn registry(&self) -> Arc<InjectorRegistry> {
+        self.injectors.lock().unwrap().clone()
     }
 
     /// Check if a method is in cooldown for the current app
@@ -562,6 +842,24 @@ impl StrategyManager {
         );
     }
 
+    /// Cool a method down for `cooldown_max_ms` straight away; it can't work
+    /// here until the environment changes
+    fn apply_unavailable_cooldown(&self, app_id: &str, method: InjectionMethod, error: &str) {
+        let key = (app_id.to_string(), method);
+        let mut cooldowns = self.cooldowns.lock().unwrap();
+        let cooldown = cooldowns.entry(key).or_insert_with(|| CooldownState {
+            until: Instant::now(),
+            backoff_level: 0,
+            last_error: String::new(),
+        });
+        cooldown.until = Instant::now() + Duration::from_millis(self.config.cooldown_max_ms);
+        cooldown.last_error = error.to_string();
+        debug!(
+            "{}/{:?} unavailable, cooling down for {}ms: {}",
+            app_id, method, self.config.cooldown_max_ms, error
+        );
+    }
+
     /// Update cooldown state for a failed method
     fn update_cooldown(&self, app_id: &str, method: InjectionMethod, error: &str) {
         self.apply_cooldown(app_id, method, error);
@@ -592,6 +890,79 @@ impl StrategyManager {
         }
     }
 
+    /// Register an injector for a custom method provided by an external crate.
+    ///
+    /// The method then participates in ordering, cooldowns, success tracking
+    /// and metrics like the built-in methods. Built-in methods cannot be
+    /// replaced.
+    pub async fn register_injector(
+        &mut self,
+        method: InjectionMethod,
+        injector: Arc<dyn TextInjector>,
+    ) -> Result<(), InjectionError> {
+        if !method.is_custom() {
+            return Err(InjectionError::Other(format!(
+                "cannot replace built-in injection method {}",
+                method
+            )));
+        }
+        info!(
+            "Registered custom injection method {} (backend {})",
+            method,
+            injector.backend_name()
+        );
+        Arc::make_mut(&mut *self.injectors.lock().unwrap()).insert(method, injector);
+        *self.cached_method_order.write().await = None;
+        Ok(())
+    }
+
+    /// Rebuild backends after the display server came back. Built-in
+    /// injectors hold connections to the old session, so backend detection
+    /// runs again and they are all replaced; registered custom injectors are
+    /// kept. Cooldowns and the cached method order learned against the dead
+    /// session are dropped, and pre-warming starts over.
+    pub async fn reinitialize(&mut self) {
+        let backend_detector = BackendDetector::new(self.config.clone());
+        match backend_detector.get_preferred_backend() {
+            Some(backend) => info!("Re-detected backend: {:?}", backend),
+            None => {
+                warn!("No suitable backend found for text injection after session restart");
+                self.metrics.record_backend_denied();
+            }
+        }
+
+        let mut registry = InjectorRegistry::build(&self.config, &backend_detector).await;
+        let previous = self.registry();
+        for method in previous.custom_methods() {
+            if let Some(injector) = previous.get(method) {
+                registry.insert(method, injector.clone());
+            }
+        }
+        *self.injectors.lock().unwrap() = Arc::new(registry);
+        self.backend_detector = backend_detector;
+        self.cooldowns.lock().unwrap().clear();
+        *self.cached_method_order.write().await = None;
+
+        let prewarm = Arc::new(PrewarmController::new(self.config.clone()));
+        self.prewarm_controller = prewarm.clone();
+        tokio::spawn(async move { prewarm.execute_all_prewarming().await });
+    }
+
+    /// Custom methods with a registered injector: those named in
+    /// `custom_method_order` first, then the rest by name.
+    fn custom_method_order(&self) -> Vec<InjectionMethod> {
+        let registered = self.registry().custom_methods();
+        let mut order: Vec<InjectionMethod> = self
+            .config
+            .custom_method_order
+            .iter()
+            .map(|name| InjectionMethod::from_name(name))
+            .filter(|m| registered.contains(m))
+            .collect();
+        order.extend(registered);
+        order
+    }
+
     /// Get ordered list of methods to try based on backend availability and success rates.
     /// Includes NoOp as a final fallback so the list is never empty.
     pub(crate) fn _get_method_priority(&self, app_id: &str) -> Vec<InjectionMethod> {
@@ -611,6 +982,10 @@ impl StrategyManager {
         if on_wayland {
             // Prefer AT-SPI direct insert first on Wayland when available; delay clipboard paste to last.
             base_order.push(InjectionMethod::AtspiInsert);
+            // Portal typing works where AT-SPI has no editable target
+            if self.config.allow_portal {
+                base_order.push(InjectionMethod::PortalKeyboard);
+            }
         }
 
         if on_x11 {
@@ -621,9 +996,19 @@ impl StrategyManager {
         if self.config.allow_kdotool {
             base_order.push(InjectionMethod::KdoToolAssist);
         }
+        if self.config.allow_xdotool {
+            base_order.push(InjectionMethod::XdoToolType);
+        }
         if self.config.allow_enigo {
             base_order.push(InjectionMethod::EnigoText);
         }
+        if self.config.allow_primary_selection {
+            base_order.push(InjectionMethod::PrimarySelection);
+        }
+        base_order.extend(self.custom_method_order());
+        if !on_wayland && !on_x11 {
+            base_order.push(InjectionMethod::Osc52Clipboard);
+        }
 
         // Clipboard paste (with fallback) is intentionally last to avoid clipboard disruption unless needed
         base_order.push(InjectionMethod::ClipboardPasteFallback);
@@ -688,14 +1073,29 @@ impl StrategyManager {
         if on_wayland || on_x11 {
             base_order.push(InjectionMethod::AtspiInsert);
         }
+        if on_wayland && self.config.allow_portal {
+            base_order.push(InjectionMethod::PortalKeyboard);
+        }
 
         // Add optional methods if enabled
         if self.config.allow_kdotool {
             base_order.push(InjectionMethod::KdoToolAssist);
         }
+        if self.config.allow_xdotool {
+            base_order.push(InjectionMethod::XdoToolType);
+        }
         if self.config.allow_enigo {
             base_order.push(InjectionMethod::EnigoText);
         }
+        if self.config.allow_primary_selection {
+            base_order.push(InjectionMethod::PrimarySelection);
+        }
+        // Out-of-tree backends
+        base_order.extend(self.custom_method_order());
+        // Terminal clipboard only without a display server
+        if !on_wayland && !on_x11 {
+            base_order.push(InjectionMethod::Osc52Clipboard);
+        }
 
         // Ensure ClipboardPaste (with internal fallback) is tried last
         base_order.push(InjectionMethod::ClipboardPasteFallback);
@@ -782,17 +1182,17 @@ impl StrategyManager {
             .lock()
             .map(|guard| guard.clone())
             .unwrap_or_else(|_| HashMap::new());
+        let registry = self.registry();
         let now = Instant::now();
 
         methods
             .iter()
             .map(|method| {
-                let backend_name = self
-                    .injectors
+                let backend_name = registry
                     .get(*method)
                     .map(|inj| inj.backend_name().to_string())
                     .unwrap_or_else(|| "unregistered".to_string());
-                let available = self.injectors.contains(*method);
+                let available = registry.contains(*method);
                 let key = (app_id.to_string(), *method);
                 let stats = success_snapshot
                     .get(&key)
@@ -842,53 +1242,152 @@ impl StrategyManager {
         }
     }
 
-    /// Chunk text and paste with delays between chunks
-    #[allow(dead_code)]
+    /// Paste text in paragraph-aligned chunks, waiting for each chunk's paste
+    /// to be confirmed before sending the next. On failure, also returns how
+    /// many chunks had already been pasted.
     async fn chunk_and_paste(
-        &mut self,
-        injector: &mut Box<dyn TextInjector>,
+        &self,
+        injector: &Arc<dyn TextInjector>,
         text: &str,
-    ) -> Result<(), InjectionError> {
-        let chunk_size = self.config.paste_chunk_chars as usize;
-
-        // Use iterator-based chunking without collecting
-        let mut start = 0;
+        context: &InjectionContext,
+    ) -> Result<(), (InjectionError, usize)> {
+        let chunks = split_paste_chunks(text, self.config.paste_chunk_chars as usize);
+        let total_chunks = chunks.len();
+        let total_chars = text.chars().count();
+        let mut chars_done = 0;
+
+        self.paste_control.reset();
+        self.paste_control.emit(PasteProgress::Started {
+            total_chunks,
+            total_chars,
+        });
+        info!(total_chunks, total_chars, "Starting chunked paste");
+
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            if i > 0 {
+                if self.paste_control.take_cancel() {
+                    self.paste_control.emit(PasteProgress::Cancelled {
+                        chunks_done: i,
+                        total_chunks,
+                    });
+                    return Err((
+                        InjectionError::Other(format!(
+                            "Paste cancelled after {}/{} chunks",
+                            i, total_chunks
+                        )),
+                        i,
+                    ));
+                }
+                if !self.has_budget_remaining() {
+                    self.paste_control.emit(PasteProgress::Failed {
+                        index: i + 1,
+                        total_chunks,
+                        error: InjectionError::BudgetExhausted.to_string(),
+                    });
+                    return Err((InjectionError::BudgetExhausted, i));
+                }
+                tokio::time::sleep(Duration::from_millis(self.config.chunk_delay_ms)).await;
+            }
 
-        // Record paste operation
-        if let Ok(mut m) = self.metrics.lock() {
-            m.record_paste();
+            if let Err(e) = injector.inject_text(chunk, Some(context)).await {
+                self.paste_control.emit(PasteProgress::Failed {
+                    index: i + 1,
+                    total_chunks,
+                    error: e.to_string(),
+                });
+                return Err((e, i));
+            }
+            chars_done += chunk.chars().count();
+            self.paste_control.emit(PasteProgress::ChunkPasted {
+                index: i + 1,
+                total_chunks,
+                chars_done,
+                total_chars,
+            });
         }
 
-        while start < text.len() {
-            // Check budget before each chunk
-            if !self.has_budget_remaining() {
-                return Err(InjectionError::BudgetExhausted);
-            }
+        self.paste_control.emit(PasteProgress::Completed {
+            total_chunks,
+            total_chars,
+        });
+        Ok(())
+    }
 
-            // Find chunk boundary at character boundary
-            let mut end = (start + chunk_size).min(text.len());
-            while !text.is_char_boundary(end) && end < text.len() {
-                end += 1;
-            }
+    /// Handle for following and cancelling chunked pastes
+    pub fn paste_control(&self) -> PasteControl {
+        self.paste_control.clone()
+    }
 
-            let chunk = &text[start..end];
-            injector.inject_text(chunk, None).await?;
+    /// Sender of the methods tried and the one that delivered; the
+    /// processor adds queue and failure events
+    pub fn injection_events(&self) -> broadcast::Sender<InjectionEvent> {
+        self.events_tx.clone()
+    }
 
-            start = end;
+    /// Lock injection to a window
+    pub fn target_lock(&self) -> TargetLock {
+        self.target_lock.clone()
+    }
 
-            // Delay between chunks (except after last)
-            if start < text.len() {
-                tokio::time::sleep(Duration::from_millis(self.config.chunk_delay_ms)).await;
-            }
+    /// Handle for listing backends from outside the injection worker
+    pub fn backend_catalog(&self) -> BackendCatalog {
+        BackendCatalog {
+            injectors: self.injectors.clone(),
+            cooldowns: self.cooldowns.clone(),
+            global_start: self.global_start.clone(),
+            metrics: self.metrics.clone(),
+            config: Arc::new(self.config.clone()),
+            app_stats: self.app_stats.clone(),
         }
+    }
 
-        // Record metrics
-        if let Ok(mut m) = self.metrics.lock() {
-            m.record_injected_chars(text.len() as u64);
-            m.record_flush(text.len() as u64);
+    /// Name, availability, last error and capabilities of each backend
+    pub async fn list_backends(&self) -> Vec<BackendStatus> {
+        self.backend_catalog().list_backends().await
+    }
+
+    /// Every backend with why it is unavailable, the modes it supports and
+    /// its measured success and latency, this session and historically
+    pub async fn capability_report(&self) -> Vec<BackendCapability> {
+        self.backend_catalog().capability_report().await
+    }
+
+    /// Whether injections through `method` feed adaptive pacing
+    fn paces_keystrokes(&self, method: InjectionMethod) -> bool {
+        self.config.adaptive_pacing
+            && matches!(
+                method,
+                InjectionMethod::XdoToolType | InjectionMethod::PortalKeyboard
+            )
+    }
+
+    /// Check whether typed text arrived intact and adjust the app's learned
+    /// keystroke rate. Without AT-SPI (or a focused editable) there is no
+    /// verdict and the rate stays put.
+    async fn learn_pace(&self, app_id: &str, before: Option<String>, text: &str) {
+        let Some(before) = before else {
+            return;
+        };
+        let mut signal = PaceSignal::Dropped;
+        // The app may still be processing queued key events
+        for _ in 0..PACE_CONFIRM_POLLS {
+            tokio::time::sleep(PACE_CONFIRM_INTERVAL).await;
+            match crate::confirm::focused_text().await {
+                Ok(Some(after)) => {
+                    signal = pacing::signal_from_text(&before, &after, text);
+                    if signal == PaceSignal::KeptUp {
+                        break;
+                    }
+                }
+                _ => return,
+            }
         }
+        self.pacer.lock().unwrap().record(app_id, signal);
+    }
 
-        Ok(())
+    /// Keystroke rates learned so far, per app
+    pub fn learned_keystroke_rates(&self) -> Vec<(String, u32)> {
+        self.pacer.lock().unwrap().learned_rates()
     }
 
     /// Type text with pacing based on keystroke rate
@@ -902,47 +1401,81 @@ impl StrategyManager {
         let max_burst = self.config.max_burst_chars as usize;
 
         // Record keystroke operation
-        if let Ok(mut m) = self.metrics.lock() {
-            m.record_keystroke();
-        }
-
-        // Use iterator-based chunking without collecting
-        let mut start = 0;
+        self.metrics.record_keystroke();
 
-        while start < text.len() {
+        // Bursts of at most `max_burst` characters that never split a
+        // grapheme cluster
+        for burst in split_paste_chunks(text, max_burst) {
             // Check budget before each burst
             if !self.has_budget_remaining() {
                 return Err(InjectionError::BudgetExhausted);
             }
 
-            // Find burst boundary at character boundary
-            let mut end = (start + max_burst).min(text.len());
-            while !text.is_char_boundary(end) && end < text.len() {
-                end += 1;
-            }
-
-            let burst = &text[start..end];
             injector.inject_text(burst, None).await?;
 
             // Calculate delay based on burst size and rate
-            let delay_ms = (burst.len() as f64 / rate_cps as f64 * 1000.0) as u64;
+            let delay_ms = (burst.chars().count() as f64 / rate_cps as f64 * 1000.0) as u64;
             if delay_ms > 0 {
                 tokio::time::sleep(Duration::from_millis(delay_ms)).await;
             }
-
-            start = end;
         }
 
         // Record metrics
-        if let Ok(mut m) = self.metrics.lock() {
-            m.record_injected_chars(text.len() as u64);
-        }
+        self.metrics.record_injected_chars(text.len() as u64);
 
         Ok(())
     }
 
+    /// Inject with one specific method, skipping focus checks, method
+    /// ordering, cooldowns and success tracking. Long clipboard pastes still
+    /// take the chunked path. Meant for benchmarks and diagnostics.
+    pub async fn inject_with_method(
+        &self,
+        method: InjectionMethod,
+        text: &str,
+    ) -> Result<(), InjectionError> {
+        let injector = self
+            .registry()
+            .get(method)
+            .cloned()
+            .ok_or_else(|| InjectionError::MethodNotAvailable(method.to_string()))?;
+        let long = text.chars().count() > self.config.paste_chunk_chars as usize;
+        let context = InjectionContext {
+            mode_override: Some(if long {
+                InjectionMode::Paste
+            } else {
+                InjectionMode::Keystroke
+            }),
+            ..Default::default()
+        };
+
+        *self.global_start.lock().unwrap() = Some(Instant::now());
+        if method == InjectionMethod::ClipboardPasteFallback && long {
+            self.chunk_and_paste(&injector, text, &context)
+                .await
+                .map_err(|(e, _)| e)
+        } else {
+            injector.inject_text(text, Some(&context)).await
+        }
+    }
+
     /// Try to inject text using the best available method
     pub async fn inject(&mut self, text: &str) -> Result<(), InjectionError> {
+        self.inject_as(text, None).await
+    }
+
+    /// Like [`inject`](Self::inject), with `mode` in place of the paste vs
+    /// keystroke decision from `injection_mode` (keystroke-only screen
+    /// sharing still wins)
+    pub async fn inject_as(
+        &mut self,
+        text: &str,
+        mode: Option<InjectionMode>,
+    ) -> Result<(), InjectionError> {
+        // Carriage returns and control characters would become stray key
+        // presses in typing backends
+        let normalized = normalize::for_injection(text);
+        let text = normalized.as_ref();
         if text.is_empty() {
             return Ok(());
         }
@@ -951,25 +1484,34 @@ impl StrategyManager {
         let redacted = redact_text(text, self.config.redact_logs);
         debug!("Injection requested for text: {}", redacted);
         if !self.config.redact_logs {
-            trace!("Full text to inject: {}", text);
+            trace!("Full text to inject: {}", redacted);
         }
 
         // Check if injection is paused
         if self.is_paused() {
-            return Err(InjectionError::Other(
-                "Injection is currently paused".to_string(),
-            ));
+            return Err(InjectionError::Refused(Refusal::Paused));
+        }
+
+        // Keep dictated text off screen casts, per policy
+        let sharing = self.config.screen_share_policy != ScreenSharePolicy::Ignore
+            && self.screen_share.is_sharing().await;
+        if sharing && self.config.screen_share_policy == ScreenSharePolicy::Pause {
+            info!("Screen share active; holding injection per screen_share_policy");
+            return Err(InjectionError::Refused(Refusal::ScreenShared));
         }
 
         // Start global timer
         *self.global_start.lock().unwrap() = Some(Instant::now());
         if self.config.max_total_latency_ms <= 1 {
-            if let Ok(mut metrics) = self.metrics.lock() {
-                metrics.record_rate_limited();
-            }
+            self.metrics.record_rate_limited();
             return Err(InjectionError::BudgetExhausted);
         }
 
+        // Bring a locked target back to the front before looking at the focus
+        if let Some(window) = self.target_lock.current() {
+            target_lock::bring_to_front(&window, &self.config).await?;
+        }
+
         // Get current focus status
         let focus_status = match self.focus_provider.get_focus_status().await {
             Ok(status) => status,
@@ -989,39 +1531,46 @@ impl StrategyManager {
 
         // Check if we should inject on unknown focus
         if focus_status == FocusStatus::Unknown && !self.config.inject_on_unknown_focus {
-            if let Ok(mut metrics) = self.metrics.lock() {
-                metrics.record_focus_missing();
-            }
+            self.metrics.record_focus_missing();
             warn!(
                 "Aborting injection: focus state unknown and config prohibits injection in this state"
             );
-            return Err(InjectionError::Other(
-                "Unknown focus state and injection disabled".to_string(),
-            ));
+            return Err(InjectionError::FocusUnknown);
         }
 
         // Check if focus is required
         if self.config.require_focus && focus_status == FocusStatus::NonEditable {
-            if let Ok(mut metrics) = self.metrics.lock() {
-                metrics.record_focus_missing();
-            }
+            self.metrics.record_focus_missing();
             warn!("Aborting injection: focused element is not editable and require_focus=true");
             return Err(InjectionError::NoEditableFocus);
         }
 
-        // Get current application ID
-        let app_id = self.get_current_app_id().await?;
+        // Get current application ID, from the compositor when it can tell
+        let focused_window = match self.focus_provider.get_focused_window().await {
+            Ok(window) => window,
+            Err(e) => {
+                debug!("Focused window unavailable: {}", e);
+                None
+            }
+        };
+        let (app_id, window_title) = match focused_window {
+            Some(window) => (window.app_id, window.title),
+            None => (self.get_current_app_id().await?, None),
+        };
 
         // Check allowlist/blocklist
-        if !self.is_app_allowed(&app_id) {
+        let target = self.current_target(app_id.clone(), window_title).await;
+        if !self.target_filter.allows(&target) {
+            // The title and URL can be private; keep them out of warn logs
             warn!(
                 "Skipping injection: app '{}' is blocked by allow/block list",
                 app_id
             );
-            return Err(InjectionError::Other(format!(
-                "Application {} is not allowed for injection",
-                app_id
-            )));
+            debug!(
+                "Blocked target: title={:?} url={:?}",
+                target.window_title, target.url
+            );
+            return Err(InjectionError::Refused(Refusal::Blocked(app_id)));
         }
 
         // Check if we should trigger pre-warming
@@ -1029,7 +1578,7 @@ impl StrategyManager {
 
         // Determine injection method based on config
         let configured_mode = self.config.injection_mode.as_str();
-        let injection_mode = match configured_mode {
+        let mut injection_mode = match configured_mode {
             "paste" => InjectionMode::Paste,
             "keystroke" => InjectionMode::Keystroke,
             "auto" => {
@@ -1048,6 +1597,15 @@ impl StrategyManager {
             }
         };
 
+        if let Some(mode) = mode {
+            injection_mode = mode;
+        }
+
+        if sharing {
+            // Keystroke-only: AT-SPI inserts instead of pasting
+            injection_mode = InjectionMode::Keystroke;
+        }
+
         let mode_label = match injection_mode {
             InjectionMode::Paste => "paste",
             InjectionMode::Keystroke => "keystroke",
@@ -1068,10 +1626,22 @@ impl StrategyManager {
             atspi_focused_node_path: None,
             clipboard_backup: None,
             mode_override: Some(injection_mode),
+            keystroke_rate_cps: {
+                let pacer = self.pacer.lock().unwrap();
+                pacer.is_active().then(|| pacer.next_rate_for(&app_id))
+            },
+            offer_html: self.html_policy.offers_html(&target),
         };
 
         // Get ordered list of methods to try
-        let method_order = self.get_method_order_cached(&app_id).await;
+        let mut method_order = self.get_method_order_cached(&app_id).await;
+        if sharing {
+            method_order.retain(|m| !screen_share::exposes_text(*m));
+            debug!(
+                "Screen share active; keystroke-only methods: {:?}",
+                method_order
+            );
+        }
         let method_path_summary = self.describe_method_path(&app_id, &method_order);
         info!(
             app_id = %app_id,
@@ -1086,8 +1656,11 @@ impl StrategyManager {
         // Try each method in order
         let total_start = Instant::now();
         let mut attempts = 0;
+        let mut attempt_log: Vec<(InjectionMethod, bool, u64)> = Vec::new();
         let total_methods = method_order.len();
-        for method in method_order.clone() {
+        let mut pending: VecDeque<InjectionMethod> = method_order.iter().copied().collect();
+        let mut transient_retries_left = self.config.transient_retries;
+        while let Some(method) = pending.pop_front() {
             attempts += 1;
             // Skip if in cooldown
             if self.is_in_cooldown(method) {
@@ -1110,18 +1683,24 @@ impl StrategyManager {
 
             // Check budget
             if !self.has_budget_remaining() {
-                if let Ok(mut metrics) = self.metrics.lock() {
-                    metrics.record_rate_limited();
-                }
+                self.metrics.record_rate_limited();
                 debug!(
                     "Aborting injection - global budget exhausted before attempt {}",
                     attempts
                 );
+                self.record_audit(
+                    &app_id,
+                    text,
+                    attempt_log.last().map(|(method, _, _)| *method),
+                    Some(&InjectionError::BudgetExhausted),
+                    total_start.elapsed().as_millis() as u64,
+                )
+                .await;
                 return Err(InjectionError::BudgetExhausted);
             }
 
             // Skip if injector not available
-            if !self.injectors.contains(method) {
+            if !self.registry().contains(method) {
                 debug!(
                     method = ?method,
                     attempt = attempts,
@@ -1130,13 +1709,15 @@ impl StrategyManager {
                 continue;
             }
 
-            let injector_entry = self.injectors.get(method).cloned();
+            let injector_entry = self.registry().get(method).cloned();
             let backend_name = injector_entry
                 .as_ref()
                 .map(|inj| inj.backend_name().to_string())
                 .unwrap_or_else(|| "unregistered".to_string());
 
             log_utils::log_injection_attempt(method, text, self.config.redact_logs);
+            let _ = self.events_tx.send(InjectionEvent::Attempting { method });
+            events::injection_attempt(method.name(), &app_id, text.chars().count(), attempts);
             debug!(
                 method = ?method,
                 backend = %backend_name,
@@ -1145,10 +1726,52 @@ impl StrategyManager {
                 "Invoking injector"
             );
 
-            // Try injection with the real injector
+            // Focused text before typing, to tell whether the app kept up
+            let pace_before = if self.paces_keystrokes(method) {
+                crate::confirm::focused_text().await.ok().flatten()
+            } else {
+                None
+            };
+
+            // Try injection with the real injector; long text goes through the
+            // clipboard in chunks
             let start = Instant::now();
+            let chunked = method == InjectionMethod::ClipboardPasteFallback
+                && text.chars().count() > self.config.paste_chunk_chars as usize;
             let result = if let Some(injector) = injector_entry {
-                injector.inject_text(text, Some(&context)).await
+                if chunked {
+                    match self.chunk_and_paste(&injector, text, &context).await {
+                        Ok(()) => Ok(()),
+                        Err((e, 0)) => Err(e),
+                        Err((e, chunks_done)) => {
+                            // Part of the text is already in the target; falling
+                            // back to another method would paste it twice
+                            let duration_ms = start.elapsed().as_millis() as u64;
+                            warn!(
+                                method = ?method,
+                                chunks_done,
+                                error = %e,
+                                "Chunked paste stopped part-way; not trying other methods"
+                            );
+                            events::injection_result(
+                                method.name(),
+                                &app_id,
+                                duration_ms,
+                                Some(&e.to_string()),
+                            );
+                            self.metrics
+                                .record_failure(method, duration_ms, e.to_string());
+                            attempt_log.push((method, false, duration_ms));
+                            let total_ms = total_start.elapsed().as_millis() as u64;
+                            self.record_app_stats(&app_id, &attempt_log, total_ms);
+                            self.record_audit(&app_id, text, Some(method), Some(&e), total_ms)
+                                .await;
+                            return Err(e);
+                        }
+                    }
+                } else {
+                    injector.inject_text(text, Some(&context)).await
+                }
             } else {
                 debug!(method = ?method, attempt = attempts, "Injector dropped before invocation");
                 continue;
@@ -1164,12 +1787,28 @@ impl StrategyManager {
                         method_duration,
                         self.config.redact_logs,
                     );
-                    if let Ok(mut m) = self.metrics.lock() {
-                        m.record_success(method, duration_ms);
-                    }
+                    events::injection_result(method.name(), &app_id, duration_ms, None);
+                    self.metrics.record_success(method, duration_ms);
                     self.update_success_record(&app_id, method, true);
                     self.clear_cooldown(&app_id, method);
+                    if self.paces_keystrokes(method) {
+                        self.learn_pace(&app_id, pace_before, text).await;
+                    }
                     let total_elapsed = total_start.elapsed();
+                    let _ = self.events_tx.send(InjectionEvent::Succeeded {
+                        method,
+                        latency: total_elapsed,
+                    });
+                    attempt_log.push((method, true, duration_ms));
+                    self.record_app_stats(&app_id, &attempt_log, total_elapsed.as_millis() as u64);
+                    self.record_audit(
+                        &app_id,
+                        text,
+                        Some(method),
+                        None,
+                        total_elapsed.as_millis() as u64,
+                    )
+                    .await;
                     info!(
                         app_id = %app_id,
                         method = ?method,
@@ -1183,7 +1822,7 @@ impl StrategyManager {
                         "Injection method succeeded"
                     );
                     if !self.config.redact_logs {
-                        trace!("Full text injected: {}", text);
+                        trace!("Full text injected: {}", crate::content_filter::scrub(text));
                     }
                     return Ok(());
                 }
@@ -1191,6 +1830,7 @@ impl StrategyManager {
                     let method_duration = start.elapsed();
                     let duration_ms = method_duration.as_millis() as u64;
                     let error_string = e.to_string();
+                    let class = e.class();
                     log_utils::log_injection_failure(
                         method,
                         text,
@@ -1205,13 +1845,59 @@ impl StrategyManager {
                         total_attempts = total_methods,
                         duration_ms = duration_ms,
                         error = %error_string,
+                        failure_class = ?class,
                         "Injection method failed"
                     );
-                    if let Ok(mut m) = self.metrics.lock() {
-                        m.record_failure(method, duration_ms, error_string.clone());
+                    events::injection_result(
+                        method.name(),
+                        &app_id,
+                        duration_ms,
+                        Some(&error_string),
+                    );
+                    self.metrics
+                        .record_failure(method, duration_ms, error_string.clone());
+                    // A typing tool that times out is falling behind the app
+                    if self.paces_keystrokes(method) && matches!(e, InjectionError::Timeout(_)) {
+                        self.pacer
+                            .lock()
+                            .unwrap()
+                            .record(&app_id, PaceSignal::Dropped);
+                    }
+                    attempt_log.push((method, false, duration_ms));
+                    match class {
+                        FailureClass::Transient if transient_retries_left > 0 => {
+                            transient_retries_left -= 1;
+                            debug!(method = ?method, "Transient failure; retrying the same method");
+                            tokio::time::sleep(Duration::from_millis(
+                                self.config.transient_retry_delay_ms,
+                            ))
+                            .await;
+                            pending.push_front(method);
+                            continue;
+                        }
+                        // Counts against the method, but a retry may already
+                        // have waited out the condition; no backoff yet
+                        FailureClass::Transient => {
+                            self.update_success_record(&app_id, method, false)
+                        }
+                        FailureClass::Failed => {
+                            self.update_success_record(&app_id, method, false);
+                            self.update_cooldown(&app_id, method, &error_string);
+                        }
+                        // Not the app's fault: park the method, keep its stats
+                        FailureClass::Unavailable => {
+                            self.apply_unavailable_cooldown(&app_id, method, &error_string)
+                        }
+                        // The target turned this method down; the next may fit
+                        FailureClass::Target => {}
+                        FailureClass::Aborted => {
+                            let total_ms = total_start.elapsed().as_millis() as u64;
+                            self.record_app_stats(&app_id, &attempt_log, total_ms);
+                            self.record_audit(&app_id, text, Some(method), Some(&e), total_ms)
+                                .await;
+                            return Err(e);
+                        }
                     }
-                    self.update_success_record(&app_id, method, false);
-                    self.update_cooldown(&app_id, method, &error_string);
                     debug!("Continuing to next method in fallback chain");
                     // Continue to next method
                 }
@@ -1220,6 +1906,17 @@ impl StrategyManager {
 
         // If we get here, all methods failed
         let total_elapsed = total_start.elapsed();
+        self.record_app_stats(&app_id, &attempt_log, total_elapsed.as_millis() as u64);
+        self.record_audit(
+            &app_id,
+            text,
+            attempt_log.last().map(|(method, _, _)| *method),
+            Some(&InjectionError::MethodFailed(
+                "All injection methods failed".to_string(),
+            )),
+            total_elapsed.as_millis() as u64,
+        )
+        .await;
         let final_method_snapshot = self.describe_method_path(&app_id, &method_order);
         error!(
             app_id = %app_id,
@@ -1242,6 +1939,13 @@ impl StrategyManager {
 
         if self.config.fail_fast {
             error!("Fail-fast mode enabled: {}", diag);
+            // Last chance to hand the text back before the process exits
+            crate::failure_notice::report_failed_injection(
+                &self.config,
+                text,
+                &InjectionError::MethodFailed("All injection methods failed".to_string()),
+            )
+            .await;
             let _ = std::io::stderr().write_all(diag.as_bytes());
             process::exit(1);
         } else {
@@ -1252,7 +1956,7 @@ impl StrategyManager {
     }
 
     /// Get metrics for the strategy manager
-    pub fn metrics(&self) -> Arc<Mutex<InjectionMetrics>> {
+    pub fn metrics(&self) -> Arc<InjectionMetrics> {
         self.metrics.clone()
     }
 
@@ -1266,27 +1970,26 @@ impl StrategyManager {
 
     /// Print injection statistics for debugging
     pub fn print_stats(&self) {
-        if let Ok(metrics) = self.metrics.lock() {
-            info!("Injection Statistics:");
-            info!("  Total attempts: {}", metrics.attempts);
-            info!("  Successes: {}", metrics.successes);
-            info!("  Failures: {}", metrics.failures);
+        let metrics = self.metrics.snapshot();
+        info!("Injection Statistics:");
+        info!("  Total attempts: {}", metrics.attempts);
+        info!("  Successes: {}", metrics.successes);
+        info!("  Failures: {}", metrics.failures);
+        info!(
+            "  Success rate: {:.1}%",
+            if metrics.attempts > 0 {
+                metrics.successes as f64 / metrics.attempts as f64 * 100.0
+            } else {
+                0.0
+            }
+        );
+
+        // Print method-specific stats
+        for (method, m) in &metrics.method_metrics {
             info!(
-                "  Success rate: {:.1}%",
-                if metrics.attempts > 0 {
-                    metrics.successes as f64 / metrics.attempts as f64 * 100.0
-                } else {
-                    0.0
-                }
+                "  Method {:?}: {} attempts, {} successes, {} failures",
+                method, m.attempts, m.successes, m.failures
             );
-
-            // Print method-specific stats
-            for (method, m) in &metrics.method_metrics {
-                info!(
-                    "  Method {:?}: {} attempts, {} successes, {} failures",
-                    method, m.attempts, m.successes, m.failures
-                );
-            }
         }
     }
 }
@@ -1393,22 +2096,20 @@ mod tests {
     #[tokio::test]
     async fn test_strategy_manager_creation() {
         let config = InjectionConfig::default();
-        let metrics = Arc::new(Mutex::new(InjectionMetrics::default()));
+        let metrics = Arc::new(InjectionMetrics::default());
         let manager = StrategyManager::new(config, metrics).await;
 
-        {
-            let metrics = manager.metrics.lock().unwrap();
-            assert_eq!(metrics.attempts, 0);
-            assert_eq!(metrics.successes, 0);
-            assert_eq!(metrics.failures, 0);
-        }
+        let metrics = manager.metrics.snapshot();
+        assert_eq!(metrics.attempts, 0);
+        assert_eq!(metrics.successes, 0);
+        assert_eq!(metrics.failures, 0);
     }
 
     // Test method ordering
     #[tokio::test]
     async fn test_method_ordering() {
         let config = InjectionConfig::default();
-        let metrics = Arc::new(Mutex::new(InjectionMetrics::default()));
+        let metrics = Arc::new(InjectionMetrics::default());
         let manager = StrategyManager::new(config, metrics).await;
 
         let order = manager.get_method_order_uncached();
@@ -1442,7 +2143,7 @@ mod tests {
             ..Default::default()
         };
 
-        let metrics = Arc::new(Mutex::new(InjectionMetrics::default()));
+        let metrics = Arc::new(InjectionMetrics::default());
         let manager = StrategyManager::new(config, metrics).await;
         let order = manager.get_method_order_uncached();
 
@@ -1465,7 +2166,7 @@ mod tests {
     #[tokio::test]
     async fn test_success_record_update() {
         let config = InjectionConfig::default();
-        let metrics = Arc::new(Mutex::new(InjectionMetrics::default()));
+        let metrics = Arc::new(InjectionMetrics::default());
         let manager = StrategyManager::new(config.clone(), metrics).await;
 
         // Test success
@@ -1492,7 +2193,7 @@ mod tests {
     #[tokio::test]
     async fn test_cooldown_update() {
         let config = InjectionConfig::default();
-        let metrics = Arc::new(Mutex::new(InjectionMetrics::default()));
+        let metrics = Arc::new(InjectionMetrics::default());
         let manager = StrategyManager::new(config.clone(), metrics).await;
 
         // First failure
@@ -1519,6 +2220,37 @@ mod tests {
         assert!(actual_duration >= expected_duration - Duration::from_millis(10));
     }
 
+    // A missing backend is parked for the maximum cooldown and doesn't count
+    // against the app's success rate
+    #[tokio::test]
+    async fn test_unavailable_failures_park_the_method() {
+        let config = InjectionConfig::default();
+        let metrics = Arc::new(InjectionMetrics::default());
+        let manager = StrategyManager::new(config.clone(), metrics).await;
+
+        let err = InjectionError::MethodUnavailable("ydotool not installed".to_string());
+        assert_eq!(err.class(), FailureClass::Unavailable);
+        assert_eq!(
+            InjectionError::ClipboardBusy("held".to_string()).class(),
+            FailureClass::Transient
+        );
+        assert_eq!(
+            InjectionError::BudgetExhausted.class(),
+            FailureClass::Aborted
+        );
+
+        let method = InjectionMethod::KdoToolAssist;
+        manager.apply_unavailable_cooldown("app", method, &err.to_string());
+        let key = ("app".to_string(), method);
+        let remaining = manager.cooldowns.lock().unwrap()[&key]
+            .until
+            .duration_since(Instant::now());
+        assert!(
+            remaining > Duration::from_millis(config.cooldown_max_ms) - Duration::from_millis(100)
+        );
+        assert!(!manager.success_cache.lock().unwrap().contains_key(&key));
+    }
+
     // Test budget checking
     #[tokio::test]
     async fn test_budget_checking() {
@@ -1527,7 +2259,7 @@ mod tests {
             ..Default::default()
         };
 
-        let metrics = Arc::new(Mutex::new(InjectionMetrics::default()));
+        let metrics = Arc::new(InjectionMetrics::default());
         let manager = StrategyManager::new(config, metrics).await;
 
         // No start time - budget should be available
@@ -1557,7 +2289,7 @@ mod tests {
         }
 
         let config = InjectionConfig::default();
-        let metrics = Arc::new(Mutex::new(InjectionMetrics::default()));
+        let metrics = Arc::new(InjectionMetrics::default());
         let mut manager = StrategyManager::new(config, metrics).await;
 
         // Test with text and timeout protection
@@ -1589,7 +2321,7 @@ mod tests {
             ..Default::default()
         };
 
-        let metrics = Arc::new(Mutex::new(InjectionMetrics::default()));
+        let metrics = Arc::new(InjectionMetrics::default());
         let mut manager = StrategyManager::new(config, metrics).await;
 
         // This should fail due to budget exhaustion
@@ -1605,7 +2337,7 @@ mod tests {
     #[tokio::test]
     async fn test_empty_text() {
         let config = InjectionConfig::default();
-        let metrics = Arc::new(Mutex::new(InjectionMetrics::default()));
+        let metrics = Arc::new(InjectionMetrics::default());
         let mut manager = StrategyManager::new(config, metrics).await;
 
         // Inject empty text
@@ -1613,4 +2345,215 @@ mod tests {
         // Note: inject is async; here we simply ensure calling path compiles
         let _ = manager.inject("").await;
     }
+
+    // Custom methods from external crates join the ordering and cooldown machinery
+    #[tokio::test]
+    async fn test_custom_method_registration() {
+        let alpha = crate::types::register_custom_method("test-alpha", "alpha").unwrap();
+        let zeta = crate::types::register_custom_method("test-zeta", "zeta").unwrap();
+        let config = InjectionConfig {
+            custom_method_order: vec!["test-zeta".to_string()],
+            ..Default::default()
+        };
+        let metrics = Arc::new(InjectionMetrics::default());
+        let mut manager = StrategyManager::new(config, metrics).await;
+
+        assert!(manager
+            .register_injector(
+                InjectionMethod::AtspiInsert,
+                Arc::new(MockInjector::new("mock", true, 1.0))
+            )
+            .await
+            .is_err());
+        for method in [alpha, zeta] {
+            manager
+                .register_injector(method, Arc::new(MockInjector::new("mock", true, 1.0)))
+                .await
+                .unwrap();
+        }
+
+        let order = manager.get_method_order_uncached();
+        let pos = |m| order.iter().position(|x| *x == m).unwrap();
+        assert!(pos(zeta) < pos(alpha));
+        assert!(pos(alpha) < pos(InjectionMethod::ClipboardPasteFallback));
+
+        manager.update_cooldown("app", zeta, "boom");
+        assert!(manager.is_in_cooldown(zeta));
+        assert!(!manager.is_in_cooldown(alpha));
+    }
+
+    #[tokio::test]
+    async fn test_list_backends_reports_status() {
+        let method = crate::types::register_custom_method("test-listed", "listed").unwrap();
+        let metrics = Arc::new(InjectionMetrics::default());
+        let mut manager = StrategyManager::new(InjectionConfig::default(), metrics.clone()).await;
+        manager
+            .register_injector(method, Arc::new(MockInjector::new("mock", false, 1.0)))
+            .await
+            .unwrap();
+        let catalog = manager.backend_catalog();
+
+        metrics.record_failure(method, 5, "no target".to_string());
+        manager.update_cooldown("app", method, "no target");
+
+        let backends = catalog.list_backends().await;
+        let listed = backends.iter().find(|b| b.method == method).unwrap();
+        assert_eq!(listed.name, "mock");
+        assert!(listed.registered);
+        assert!(!listed.available);
+        assert!(listed.cooling_down);
+        assert_eq!(listed.failures, 1);
+        assert_eq!(listed.last_error.as_ref().unwrap().message, "no target");
+
+        // Every built-in method is listed, registered or not
+        for builtin in InjectionMethod::BUILTIN {
+            if builtin != InjectionMethod::NoOp {
+                assert!(backends.iter().any(|b| b.method == builtin));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_capability_report_explains_unavailable_backends() {
+        let method = crate::types::register_custom_method("test-capability", "capability").unwrap();
+        let metrics = Arc::new(InjectionMetrics::default());
+        let config = InjectionConfig {
+            allow_primary_selection: false,
+            ..Default::default()
+        };
+        let mut manager = StrategyManager::new(config, metrics.clone()).await;
+        manager
+            .register_injector(method, Arc::new(MockInjector::new("mock", false, 1.0)))
+            .await
+            .unwrap();
+        metrics.record_failure(method, 30, "no target".to_string());
+        metrics.record_failure(method, 50, "no target".to_string());
+
+        let report = manager.capability_report().await;
+        let custom = report.iter().find(|b| b.status.method == method).unwrap();
+        assert_eq!(
+            custom.unavailable_reason,
+            Some(UnavailableReason::CheckFailing)
+        );
+        assert_eq!(custom.session_avg_ms, Some(40));
+        assert!(custom.modes.is_empty());
+        assert!(custom.history.is_none());
+
+        let primary = report
+            .iter()
+            .find(|b| b.status.method == InjectionMethod::PrimarySelection)
+            .unwrap();
+        assert_eq!(
+            primary.unavailable_reason,
+            Some(UnavailableReason::Disabled(
+                "injection.allow_primary_selection"
+            ))
+        );
+        assert_eq!(primary.modes, &[InjectionMode::Paste]);
+
+        let xdotool = unavailable_reason(
+            InjectionMethod::XdoToolType,
+            false,
+            &InjectionConfig::default(),
+        );
+        if cfg!(feature = "xdotool") {
+            assert_eq!(
+                xdotool,
+                UnavailableReason::Disabled("injection.allow_xdotool")
+            );
+        } else {
+            assert_eq!(xdotool, UnavailableReason::NotCompiled("xdotool"));
+        }
+    }
+
+    /// Records pasted chunks; optionally cancels the paste after the first one
+    struct RecordingInjector {
+        chunks: Mutex<Vec<String>>,
+        cancel_with: Option<PasteControl>,
+    }
+
+    #[async_trait]
+    impl TextInjector for RecordingInjector {
+        fn backend_name(&self) -> &'static str {
+            "recording"
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+
+        async fn inject_text(
+            &self,
+            text: &str,
+            _context: Option<&crate::types::InjectionContext>,
+        ) -> crate::types::InjectionResult<()> {
+            self.chunks.lock().unwrap().push(text.to_string());
+            if let Some(control) = &self.cancel_with {
+                control.cancel();
+            }
+            Ok(())
+        }
+
+        fn backend_info(&self) -> Vec<(&'static str, String)> {
+            Vec::new()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chunked_paste_progress_and_cancel() {
+        let config = InjectionConfig {
+            paste_chunk_chars: 12,
+            chunk_delay_ms: 1,
+            ..Default::default()
+        };
+        let metrics = Arc::new(InjectionMetrics::default());
+        let manager = StrategyManager::new(config, metrics).await;
+        let control = manager.paste_control();
+        let mut progress = control.subscribe();
+        let text = "One para.\n\nTwo para.\n\nThree.";
+
+        let recorder = Arc::new(RecordingInjector {
+            chunks: Mutex::new(Vec::new()),
+            cancel_with: None,
+        });
+        let injector: Arc<dyn TextInjector> = recorder.clone();
+        manager
+            .chunk_and_paste(&injector, text, &InjectionContext::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            *recorder.chunks.lock().unwrap(),
+            vec!["One para.\n\n", "Two para.\n\n", "Three."]
+        );
+        assert!(matches!(
+            progress.recv().await.unwrap(),
+            PasteProgress::Started {
+                total_chunks: 3,
+                ..
+            }
+        ));
+        for _ in 0..3 {
+            assert!(matches!(
+                progress.recv().await.unwrap(),
+                PasteProgress::ChunkPasted { .. }
+            ));
+        }
+        assert!(matches!(
+            progress.recv().await.unwrap(),
+            PasteProgress::Completed { .. }
+        ));
+
+        // Cancelling during the first chunk stops before the second
+        let recorder = Arc::new(RecordingInjector {
+            chunks: Mutex::new(Vec::new()),
+            cancel_with: Some(control.clone()),
+        });
+        let injector: Arc<dyn TextInjector> = recorder.clone();
+        let err = manager
+            .chunk_and_paste(&injector, text, &InjectionContext::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.1, 1);
+        assert_eq!(recorder.chunks.lock().unwrap().len(), 1);
+    }
 }
@@ -0,0 +1,84 @@
+//! Clean-up applied to text before any backend sees it.
+//!
+//! Transcripts and text coming in over IPC can carry carriage returns and
+//! stray control characters. Typing backends would turn those into key
+//! presses (Escape, Backspace, Ctrl shortcuts), so everything but line feed
+//! and tab is dropped, and CRLF or a lone CR becomes a line feed. Format
+//! characters (ZWJ, ZWNJ, bidi marks) are not controls and are kept: emoji
+//! sequences and right-to-left text depend on them.
+
+use std::borrow::Cow;
+
+fn is_stripped(c: char) -> bool {
+    c.is_control() && !matches!(c, '\n' | '\t')
+}
+
+/// `text` with CR and CRLF turned into LF and the other control characters
+/// (C0, DEL, C1) except tab removed. Borrowed when nothing changes.
+pub fn for_injection(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_stripped) {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\r' => out.push('\n'),
+            c if is_stripped(c) => {}
+            c => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::test_utils::tricky_text;
+    use proptest::prelude::*;
+    use unicode_segmentation::UnicodeSegmentation;
+
+    #[test]
+    fn normalizes_line_endings_and_strips_controls() {
+        assert_eq!(for_injection("a\r\nb\rc\nd"), "a\nb\nc\nd");
+        assert_eq!(
+            for_injection("tab\there\x1b[2J\x08\x7f\u{85}"),
+            "tab\there[2J"
+        );
+        assert!(matches!(
+            for_injection("שלום 👩‍👩‍👧 e\u{301}\u{200f}"),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn output_has_only_newline_and_tab_controls(text in tricky_text()) {
+            let out = for_injection(&text);
+            prop_assert!(!out.chars().any(is_stripped));
+            prop_assert_eq!(for_injection(&out), out.clone());
+        }
+
+        #[test]
+        fn keeps_every_other_character_in_order(text in tricky_text()) {
+            let out = for_injection(&text);
+            let kept = |s: &str| s.chars().filter(|c| !c.is_control()).collect::<String>();
+            prop_assert_eq!(kept(&out), kept(&text));
+            prop_assert_eq!(
+                out.matches('\n').count(),
+                text.replace("\r\n", "\n").matches(['\n', '\r']).count()
+            );
+        }
+
+        #[test]
+        fn text_without_controls_keeps_its_graphemes(text in tricky_text()) {
+            let text: String = text.chars().filter(|c| !c.is_control()).collect();
+            let out = for_injection(&text);
+            prop_assert_eq!(
+                out.graphemes(true).collect::<Vec<_>>(),
+                text.graphemes(true).collect::<Vec<_>>()
+            );
+        }
+    }
+}
@@ -6,7 +6,11 @@
 
 use crate::confirm::{text_changed, ConfirmationResult};
 use crate::injectors::atspi::AtspiInjector;
+use crate::injectors::primary_selection::PrimarySelectionInjector;
 use crate::injectors::unified_clipboard::UnifiedClipboardInjector;
+use crate::osc52_injector::Osc52Injector;
+#[cfg(feature = "portal")]
+use crate::portal_injector::PortalInjector;
 use crate::prewarm::PrewarmController;
 use crate::session::{InjectionSession, SessionState};
 use crate::types::{InjectionConfig, InjectionMethod, InjectionResult};
@@ -83,6 +87,12 @@ pub struct StrategyOrchestrator {
     atspi_injector: Option<AtspiInjector>,
     /// Clipboard-based injector fallback
     clipboard_fallback: Option<UnifiedClipboardInjector>,
+    /// Primary selection injector (opt-in)
+    primary_selection: Option<PrimarySelectionInjector>,
+    /// RemoteDesktop portal keyboard (opt-in, Wayland)
+    portal_keyboard: Option<Arc<dyn TextInjector>>,
+    /// OSC 52 terminal clipboard (no display server, terminal attached)
+    terminal_clipboard: Option<Osc52Injector>,
     /// Session state for buffering
     session: Arc<RwLock<InjectionSession>>,
     /// Last known app context
@@ -104,12 +114,22 @@ impl StrategyOrchestrator {
             None
         };
         let clipboard_fallback = Some(UnifiedClipboardInjector::new(config.clone()));
+        let primary_selection = config
+            .allow_primary_selection
+            .then(|| PrimarySelectionInjector::new(config.clone()));
+        #[cfg(feature = "portal")]
+        let portal_keyboard = config
+            .allow_portal
+            .then(|| Arc::new(PortalInjector::new(config.clone())) as Arc<dyn TextInjector>);
+        #[cfg(not(feature = "portal"))]
+        let portal_keyboard = None;
+        let terminal_clipboard = (desktop_env == DesktopEnvironment::Unknown
+            && Osc52Injector::tty_available())
+        .then(|| Osc52Injector::new(config.clone()));
 
         // Create session with default config
         let session_config = crate::session::SessionConfig::default();
-        let metrics = Arc::new(std::sync::Mutex::new(
-            crate::types::InjectionMetrics::default(),
-        ));
+        let metrics = Arc::new(crate::types::InjectionMetrics::default());
         let session = Arc::new(RwLock::new(InjectionSession::new(session_config, metrics)));
 
         Self {
@@ -118,6 +138,9 @@ impl StrategyOrchestrator {
             prewarm_controller,
             atspi_injector,
             clipboard_fallback,
+            primary_selection,
+            portal_keyboard,
+            terminal_clipboard,
             session,
             last_context: Arc::new(RwLock::new(None)),
         }
@@ -180,7 +203,7 @@ impl StrategyOrchestrator {
 
     /// Get the injection strategy order for the current environment
     fn get_strategy_order(&self) -> Vec<InjectionMethod> {
-        match self.desktop_env {
+        let mut order = match self.desktop_env {
             DesktopEnvironment::KdeWayland => vec![
                 InjectionMethod::AtspiInsert,
                 InjectionMethod::ClipboardPasteFallback,
@@ -194,7 +217,35 @@ impl StrategyOrchestrator {
                 InjectionMethod::AtspiInsert,
                 InjectionMethod::ClipboardPasteFallback,
             ],
+        };
+        // Portal typing follows AT-SPI where the compositor implements
+        // RemoteDesktop (not wlroots-based ones like Hyprland)
+        if self.portal_keyboard.is_some()
+            && matches!(
+                self.desktop_env,
+                DesktopEnvironment::KdeWayland
+                    | DesktopEnvironment::GnomeWayland
+                    | DesktopEnvironment::OtherWayland
+            )
+        {
+            let atspi_pos = order
+                .iter()
+                .position(|m| *m == InjectionMethod::AtspiInsert)
+                .map_or(0, |i| i + 1);
+            order.insert(atspi_pos, InjectionMethod::PortalKeyboard);
+        }
+        // Primary selection goes ahead of the clipboard so the clipboard stays untouched
+        if self.primary_selection.is_some() {
+            let clipboard_pos = order
+                .iter()
+                .position(|m| *m == InjectionMethod::ClipboardPasteFallback)
+                .unwrap_or(order.len());
+            order.insert(clipboard_pos, InjectionMethod::PrimarySelection);
         }
+        if self.terminal_clipboard.is_some() {
+            order.push(InjectionMethod::Osc52Clipboard);
+        }
+        order
     }
 
     /// Trigger targeted pre-warming for the first method we'll try
@@ -289,6 +340,44 @@ impl StrategyOrchestrator {
                         continue;
                     }
                 }
+                InjectionMethod::PortalKeyboard => {
+                    if let Some(ref injector) = self.portal_keyboard {
+                        // Typing outlasts the stage budget; the injector
+                        // bounds itself by the per-method timeout
+                        injector.inject_text(text, Some(&context)).await
+                    } else {
+                        continue;
+                    }
+                }
+                InjectionMethod::PrimarySelection => {
+                    if let Some(ref injector) = self.primary_selection {
+                        tokio::time::timeout(
+                            stage_budget,
+                            injector.inject_text(text, Some(&context)),
+                        )
+                        .await
+                        .map_err(|_| InjectionError::Timeout(stage_budget.as_millis() as u64))?
+                    } else {
+                        continue;
+                    }
+                }
+                InjectionMethod::Osc52Clipboard => {
+                    if let Some(ref injector) = self.terminal_clipboard {
+                        // The terminal gives no acknowledgement to confirm against
+                        let result = injector.inject_text(text, Some(&context)).await;
+                        if result.is_ok() {
+                            info!(
+                                "Injection sent: method={:?}, total={}ms (unconfirmed)",
+                                method,
+                                total_start.elapsed().as_millis()
+                            );
+                            return result;
+                        }
+                        result
+                    } else {
+                        continue;
+                    }
+                }
                 _ => {
                     debug!("Unsupported method {:?} in fast-fail loop", method);
                     continue;
@@ -456,11 +545,12 @@ mod tests {
                 prewarm_controller: Arc::new(PrewarmController::new(config.clone())),
                 atspi_injector: None,
                 clipboard_fallback: Some(UnifiedClipboardInjector::new(config.clone())),
+                primary_selection: None,
+                portal_keyboard: None,
+                terminal_clipboard: None,
                 session: Arc::new(RwLock::new(InjectionSession::new(
                     crate::session::SessionConfig::default(),
-                    Arc::new(std::sync::Mutex::new(
-                        crate::types::InjectionMetrics::default(),
-                    )),
+                    Arc::new(crate::types::InjectionMetrics::default()),
                 ))),
                 last_context: Arc::new(RwLock::new(None)),
             };
@@ -472,6 +562,67 @@ mod tests {
                 env
             );
         }
+
+        // Primary selection, when enabled, is tried before the clipboard
+        let mut orchestrator = StrategyOrchestrator {
+            config: config.clone(),
+            desktop_env: DesktopEnvironment::KdeWayland,
+            prewarm_controller: Arc::new(PrewarmController::new(config.clone())),
+            atspi_injector: None,
+            clipboard_fallback: None,
+            primary_selection: Some(PrimarySelectionInjector::new(config.clone())),
+            portal_keyboard: None,
+            terminal_clipboard: None,
+            session: Arc::new(RwLock::new(InjectionSession::new(
+                crate::session::SessionConfig::default(),
+                Arc::new(crate::types::InjectionMetrics::default()),
+            ))),
+            last_context: Arc::new(RwLock::new(None)),
+        };
+        assert_eq!(
+            orchestrator.get_strategy_order(),
+            vec![
+                InjectionMethod::AtspiInsert,
+                InjectionMethod::PrimarySelection,
+                InjectionMethod::ClipboardPasteFallback,
+            ]
+        );
+        orchestrator.desktop_env = DesktopEnvironment::Windows;
+        assert_eq!(
+            orchestrator.get_strategy_order(),
+            vec![
+                InjectionMethod::PrimarySelection,
+                InjectionMethod::ClipboardPasteFallback,
+            ]
+        );
+
+        // Portal typing ranks right after AT-SPI on compositors that implement it
+        orchestrator.primary_selection = None;
+        orchestrator.portal_keyboard = Some(Arc::new(crate::noop_injector::NoOpInjector::new(
+            config.clone(),
+        )));
+        orchestrator.desktop_env = DesktopEnvironment::GnomeWayland;
+        assert_eq!(
+            orchestrator.get_strategy_order(),
+            vec![
+                InjectionMethod::AtspiInsert,
+                InjectionMethod::PortalKeyboard,
+                InjectionMethod::ClipboardPasteFallback,
+            ]
+        );
+        orchestrator.desktop_env = DesktopEnvironment::Hyprland;
+        assert!(!orchestrator
+            .get_strategy_order()
+            .contains(&InjectionMethod::PortalKeyboard));
+
+        // The terminal clipboard is the last resort without a display
+        orchestrator.portal_keyboard = None;
+        orchestrator.terminal_clipboard = Some(Osc52Injector::new(config.clone()));
+        orchestrator.desktop_env = DesktopEnvironment::Unknown;
+        assert_eq!(
+            orchestrator.get_strategy_order().last(),
+            Some(&InjectionMethod::Osc52Clipboard)
+        );
     }
 
     #[tokio::test]
@@ -0,0 +1,176 @@
+//! OSC 52 terminal clipboard injector
+//!
+//! Writes an OSC 52 escape sequence to the controlling terminal (`/dev/tty`),
+//! which asks the terminal emulator to put the text on its clipboard. Over SSH
+//! that is the clipboard of the local machine, so this is the one backend that
+//! works in TTY and remote sessions with no Wayland or X11 display. It only
+//! sets the clipboard; the user pastes. Terminals may ignore the sequence
+//! (some need OSC 52 enabled), and there is no way to confirm it arrived.
+//!
+//! Only registered when there is no display server and a terminal is attached.
+
+use crate::logging::utils;
+use crate::types::{InjectionConfig, InjectionContext, InjectionMethod, InjectionResult};
+use crate::TextInjector;
+use async_trait::async_trait;
+use coldvox_foundation::error::InjectionError;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::trace;
+
+/// Controlling terminal of this process
+const CONTROLLING_TTY: &str = "/dev/tty";
+
+/// Largest payload terminals reliably accept (xterm's default limit is
+/// about 100 kB of base64)
+const MAX_TEXT_BYTES: usize = 74_000;
+
+/// Escape sequence that sets the terminal's clipboard to `text`; wrapped for
+/// passthrough when running inside tmux
+pub fn osc52(text: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    if tmux {
+        format!("\x1bPtmux;\x1b{}\x1b\\", sequence)
+    } else {
+        sequence
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | ((*b as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Clipboard injector for terminal sessions
+pub struct Osc52Injector {
+    config: InjectionConfig,
+    tty: PathBuf,
+}
+
+impl Osc52Injector {
+    /// Create a new OSC 52 injector writing to the controlling terminal
+    pub fn new(config: InjectionConfig) -> Self {
+        Self::with_tty(config, CONTROLLING_TTY)
+    }
+
+    /// Write to `tty` instead of the controlling terminal
+    pub fn with_tty(config: InjectionConfig, tty: impl Into<PathBuf>) -> Self {
+        Self {
+            config,
+            tty: tty.into(),
+        }
+    }
+
+    /// Whether a terminal is attached that sequences can be written to
+    pub fn tty_available() -> bool {
+        Self::open(Path::new(CONTROLLING_TTY)).is_ok()
+    }
+
+    fn open(tty: &Path) -> std::io::Result<std::fs::File> {
+        std::fs::OpenOptions::new().write(true).open(tty)
+    }
+}
+
+#[async_trait]
+impl TextInjector for Osc52Injector {
+    async fn inject_text(
+        &self,
+        text: &str,
+        _context: Option<&InjectionContext>,
+    ) -> InjectionResult<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        if text.len() > MAX_TEXT_BYTES {
+            return Err(InjectionError::MethodFailed(format!(
+                "{} bytes exceed the OSC 52 limit of {}",
+                text.len(),
+                MAX_TEXT_BYTES
+            )));
+        }
+
+        let start = Instant::now();
+        trace!("Writing OSC 52 sequence ({} chars)", text.len());
+        let sequence = osc52(text, std::env::var_os("TMUX").is_some());
+        let tty = self.tty.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut terminal = Self::open(&tty)?;
+            terminal.write_all(sequence.as_bytes())?;
+            terminal.flush()
+        })
+        .await
+        .map_err(|e| InjectionError::Other(format!("Tokio spawn_blocking failed: {}", e)))?
+        .map_err(|e| InjectionError::MethodFailed(format!("Writing to the terminal: {}", e)))?;
+
+        utils::log_injection_success(
+            InjectionMethod::Osc52Clipboard,
+            text,
+            start.elapsed(),
+            self.config.redact_logs,
+        );
+        Ok(())
+    }
+
+    async fn is_available(&self) -> bool {
+        Self::open(&self.tty).is_ok()
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "osc52"
+    }
+
+    fn backend_info(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("type", "terminal clipboard".to_string()),
+            ("tty", self.tty.display().to_string()),
+            ("tmux", std::env::var_os("TMUX").is_some().to_string()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn osc52_encodes_text_as_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(osc52("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(osc52("hi", true), "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\");
+    }
+
+    #[tokio::test]
+    async fn writes_the_sequence_to_the_terminal() {
+        let dir = tempfile::tempdir().unwrap();
+        let tty = dir.path().join("tty");
+        let injector = Osc52Injector::with_tty(InjectionConfig::default(), &tty);
+        assert!(!injector.is_available().await);
+
+        std::fs::write(&tty, b"").unwrap();
+        assert!(injector.is_available().await);
+        injector.inject_text("hello", None).await.unwrap();
+        let written = std::fs::read_to_string(&tty).unwrap();
+        assert!(written.contains("]52;c;aGVsbG8=\x07"));
+
+        let too_long = "x".repeat(MAX_TEXT_BYTES + 1);
+        assert!(injector.inject_text(&too_long, None).await.is_err());
+    }
+}
@@ -0,0 +1,264 @@
+//! Adaptive keystroke pacing per target application.
+//!
+//! Typing backends start each app at the configured `keystroke_rate_cps`.
+//! After every typed injection the manager reports whether the app kept up
+//! (AT-SPI saw the text arrive) or dropped characters (confirmation timed
+//! out, the text didn't match, or the typing tool itself timed out). The
+//! learned rate halves on a drop and creeps back up after a run of clean
+//! injections, bounded by `min_keystroke_rate_cps` and
+//! `max_keystroke_rate_cps`.
+//!
+//! With `keystroke_jitter_pct` set (off by default), each injection types at
+//! the learned rate varied by up to that percentage, drawn from a
+//! [`SharedRng`] so a fixed seed reproduces the same rates.
+
+use crate::types::InjectionConfig;
+use coldvox_foundation::rng::{global_rng, SharedRng};
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Consecutive clean injections before the rate is raised
+const SPEEDUP_AFTER: u32 = 3;
+/// Rate multiplier after a drop
+const SLOWDOWN_FACTOR: f64 = 0.5;
+/// Rate multiplier after `SPEEDUP_AFTER` clean injections
+const SPEEDUP_FACTOR: f64 = 1.1;
+
+/// How a typed injection landed in the target app
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaceSignal {
+    /// The full text showed up
+    KeptUp,
+    /// Characters went missing or the typing tool backed up
+    Dropped,
+}
+
+/// Judge a typed injection from the focused text before and after it: the
+/// app kept up when the whole typed text is there and wasn't there before
+/// (or the text grew by at least as much as was typed)
+pub fn signal_from_text(before: &str, after: &str, typed: &str) -> PaceSignal {
+    let grew = after.chars().count().saturating_sub(before.chars().count());
+    let arrived =
+        after.contains(typed) && (!before.contains(typed) || grew >= typed.chars().count());
+    if arrived {
+        PaceSignal::KeptUp
+    } else {
+        PaceSignal::Dropped
+    }
+}
+
+/// Learned rate for one app
+#[derive(Debug, Clone)]
+struct PaceState {
+    rate_cps: f64,
+    clean_streak: u32,
+}
+
+/// Keystroke rates learned per app
+#[derive(Debug)]
+pub struct KeystrokePacer {
+    enabled: bool,
+    base_cps: f64,
+    min_cps: f64,
+    max_cps: f64,
+    /// Largest relative deviation per injection, as a fraction
+    jitter: f64,
+    rng: SharedRng,
+    rates: HashMap<String, PaceState>,
+}
+
+impl KeystrokePacer {
+    /// Pacer drawing its jitter from the process-wide RNG
+    pub fn new(config: &InjectionConfig) -> Self {
+        Self::with_rng(config, global_rng())
+    }
+
+    /// Pacer drawing its jitter from `rng`
+    pub fn with_rng(config: &InjectionConfig, rng: SharedRng) -> Self {
+        let min_cps = f64::from(config.min_keystroke_rate_cps.max(1));
+        let max_cps = f64::from(config.max_keystroke_rate_cps).max(min_cps);
+        Self {
+            enabled: config.adaptive_pacing,
+            base_cps: f64::from(config.keystroke_rate_cps).clamp(min_cps, max_cps),
+            min_cps,
+            max_cps,
+            jitter: f64::from(config.keystroke_jitter_pct.min(100)) / 100.0,
+            rng,
+            rates: HashMap::new(),
+        }
+    }
+
+    /// Whether typed injections need a rate from the pacer at all
+    pub fn is_active(&self) -> bool {
+        self.enabled || self.jitter > 0.0
+    }
+
+    fn learned_cps(&self, app_id: &str) -> f64 {
+        if !self.enabled {
+            return self.base_cps;
+        }
+        self.rates
+            .get(app_id)
+            .map_or(self.base_cps, |state| state.rate_cps)
+    }
+
+    /// Keystroke rate learned for `app_id`
+    pub fn rate_for(&self, app_id: &str) -> u32 {
+        self.learned_cps(app_id).round() as u32
+    }
+
+    /// Keystroke rate for the next typed injection into `app_id`: the
+    /// learned rate with this injection's jitter applied
+    pub fn next_rate_for(&self, app_id: &str) -> u32 {
+        let cps = self.learned_cps(app_id);
+        if self.jitter == 0.0 {
+            return cps.round() as u32;
+        }
+        let offset = self.rng.lock().unwrap().f64() * 2.0 - 1.0;
+        (cps * (1.0 + self.jitter * offset))
+            .clamp(self.min_cps, self.max_cps)
+            .round() as u32
+    }
+
+    /// Adjust the rate for `app_id` after a typed injection
+    pub fn record(&mut self, app_id: &str, signal: PaceSignal) {
+        if !self.enabled {
+            return;
+        }
+        let state = self
+            .rates
+            .entry(app_id.to_string())
+            .or_insert_with(|| PaceState {
+                rate_cps: self.base_cps,
+                clean_streak: 0,
+            });
+        let before = state.rate_cps;
+        match signal {
+            PaceSignal::Dropped => {
+                state.rate_cps = (state.rate_cps * SLOWDOWN_FACTOR).max(self.min_cps);
+                state.clean_streak = 0;
+            }
+            PaceSignal::KeptUp => {
+                state.clean_streak += 1;
+                if state.clean_streak >= SPEEDUP_AFTER {
+                    state.rate_cps = (state.rate_cps * SPEEDUP_FACTOR).min(self.max_cps);
+                    state.clean_streak = 0;
+                }
+            }
+        }
+        if state.rate_cps != before {
+            debug!(
+                app_id,
+                ?signal,
+                from_cps = before,
+                to_cps = state.rate_cps,
+                "Adjusted keystroke rate"
+            );
+        }
+    }
+
+    /// Learned rates, for diagnostics
+    pub fn learned_rates(&self) -> Vec<(String, u32)> {
+        let mut rates: Vec<_> = self
+            .rates
+            .iter()
+            .map(|(app, state)| (app.clone(), state.rate_cps.round() as u32))
+            .collect();
+        rates.sort();
+        rates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coldvox_foundation::rng::seeded_rng;
+
+    fn config() -> InjectionConfig {
+        InjectionConfig {
+            keystroke_rate_cps: 40,
+            min_keystroke_rate_cps: 5,
+            max_keystroke_rate_cps: 60,
+            adaptive_pacing: true,
+            keystroke_jitter_pct: 0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn slows_on_drops_and_recovers_per_app() {
+        let mut pacer = KeystrokePacer::new(&config());
+        assert_eq!(pacer.rate_for("slow-app"), 40);
+
+        pacer.record("slow-app", PaceSignal::Dropped);
+        assert_eq!(pacer.rate_for("slow-app"), 20);
+        for _ in 0..4 {
+            pacer.record("slow-app", PaceSignal::Dropped);
+        }
+        assert_eq!(pacer.rate_for("slow-app"), 5);
+        // Other apps keep the configured rate
+        assert_eq!(pacer.rate_for("fast-app"), 40);
+
+        for _ in 0..SPEEDUP_AFTER - 1 {
+            pacer.record("slow-app", PaceSignal::KeptUp);
+        }
+        assert_eq!(pacer.rate_for("slow-app"), 5);
+        pacer.record("slow-app", PaceSignal::KeptUp);
+        assert_eq!(pacer.rate_for("slow-app"), 6);
+
+        for _ in 0..100 {
+            pacer.record("fast-app", PaceSignal::KeptUp);
+        }
+        assert_eq!(pacer.rate_for("fast-app"), 60);
+        assert_eq!(
+            pacer.learned_rates(),
+            vec![("fast-app".to_string(), 60), ("slow-app".to_string(), 6)]
+        );
+    }
+
+    #[test]
+    fn judges_dropped_characters_from_focused_text() {
+        assert_eq!(
+            signal_from_text("Dear ", "Dear team, hello", "team, hello"),
+            PaceSignal::KeptUp
+        );
+        assert_eq!(
+            signal_from_text("Dear ", "Dear tem, hllo", "team, hello"),
+            PaceSignal::Dropped
+        );
+        // Same text typed twice in a row
+        assert_eq!(signal_from_text("ok", "okok", "ok"), PaceSignal::KeptUp);
+        assert_eq!(signal_from_text("ok", "ok", "ok"), PaceSignal::Dropped);
+    }
+
+    #[test]
+    fn fixed_rate_when_disabled() {
+        let mut pacer = KeystrokePacer::new(&InjectionConfig {
+            adaptive_pacing: false,
+            ..config()
+        });
+        pacer.record("app", PaceSignal::Dropped);
+        assert_eq!(pacer.rate_for("app"), 40);
+        assert!(pacer.learned_rates().is_empty());
+    }
+
+    #[test]
+    fn jitter_is_bounded_and_seeded() {
+        let config = InjectionConfig {
+            keystroke_jitter_pct: 25,
+            ..config()
+        };
+        let rates = |seed| {
+            let pacer = KeystrokePacer::with_rng(&config, seeded_rng(seed));
+            (0..32)
+                .map(|_| pacer.next_rate_for("app"))
+                .collect::<Vec<_>>()
+        };
+        let first = rates(1);
+        assert_eq!(first, rates(1));
+        assert_ne!(first, rates(2));
+        // 40 cps ±25%
+        assert!(first.iter().all(|cps| (30..=50).contains(cps)));
+        assert!(first.iter().any(|cps| *cps != 40));
+    }
+}
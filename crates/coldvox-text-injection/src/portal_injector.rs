@@ -0,0 +1,377 @@
+//! XDG RemoteDesktop portal injector for Wayland sessions
+//!
+//! Types text as keysym presses through `org.freedesktop.portal.RemoteDesktop`
+//! (implemented by the GNOME and KDE portal backends). Starting a session
+//! shows the compositor's consent dialog, so the session is opened on first
+//! use and kept for the life of the injector. The restore token handed back
+//! by the portal is saved (readable by the user only) under the config dir,
+//! so rebuilt backends and later runs reconnect without prompting again until
+//! the user revokes access.
+
+use crate::detection::{detect_display_protocol, DisplayProtocol};
+use crate::keysym;
+use crate::logging::utils;
+use crate::types::{InjectionConfig, InjectionContext, InjectionMethod, InjectionResult};
+use crate::TextInjector;
+use ashpd::desktop::remote_desktop::{DeviceType, KeyState, RemoteDesktop};
+use ashpd::desktop::{PersistMode, Session};
+use async_trait::async_trait;
+use coldvox_foundation::error::InjectionError;
+use std::fs::{self, DirBuilder, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, OnceCell};
+use tokio::time::timeout;
+use tracing::{debug, info, warn};
+
+/// Delay between keystrokes when no keystroke rate is configured
+const DEFAULT_KEY_DELAY_MS: u64 = 12;
+/// Time the user gets to answer the consent dialog
+const START_TIMEOUT: Duration = Duration::from_secs(60);
+/// Keysyms for control characters; `keysym_for` maps '\n' to Linefeed,
+/// which most applications ignore
+const XK_RETURN: u32 = 0xff0d;
+const XK_TAB: u32 = 0xff09;
+
+/// An open RemoteDesktop session with keyboard access
+struct PortalSession {
+    proxy: RemoteDesktop<'static>,
+    session: Session<'static, RemoteDesktop<'static>>,
+}
+
+/// Keyboard injection through the RemoteDesktop portal
+pub struct PortalInjector {
+    config: InjectionConfig,
+    /// Session reused across injections (None until first use or after it was lost)
+    session: Mutex<Option<PortalSession>>,
+    /// Whether the portal offers keyboard access, probed once
+    available: OnceCell<bool>,
+    /// Where the restore token is kept between runs
+    token_path: Option<PathBuf>,
+}
+
+impl PortalInjector {
+    /// Create a new portal injector. Nothing is sent to the portal until the
+    /// first availability check.
+    pub fn new(config: InjectionConfig) -> Self {
+        Self {
+            config,
+            session: Mutex::new(None),
+            available: OnceCell::new(),
+            token_path: Self::default_token_path(),
+        }
+    }
+
+    /// Keep the restore token at `path` (None: don't persist it)
+    pub fn with_token_path(mut self, path: Option<PathBuf>) -> Self {
+        self.token_path = path;
+        self
+    }
+
+    /// Default location: `$XDG_CONFIG_HOME/coldvox/portal_restore_token`,
+    /// falling back to `~/.config/coldvox/portal_restore_token`.
+    pub fn default_token_path() -> Option<PathBuf> {
+        let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+        Some(config_dir.join("coldvox").join("portal_restore_token"))
+    }
+
+    /// The portal is only useful on Wayland, and only when its RemoteDesktop
+    /// implementation offers a keyboard
+    pub(crate) async fn probe() -> bool {
+        if detect_display_protocol() != DisplayProtocol::Wayland {
+            return false;
+        }
+        let proxy = match RemoteDesktop::new().await {
+            Ok(proxy) => proxy,
+            Err(e) => {
+                debug!("RemoteDesktop portal not available: {}", e);
+                return false;
+            }
+        };
+        match proxy.available_device_types().await {
+            Ok(types) => types.contains(DeviceType::Keyboard),
+            Err(e) => {
+                debug!("RemoteDesktop portal device query failed: {}", e);
+                false
+            }
+        }
+    }
+
+    fn load_token(&self) -> Option<String> {
+        let token = fs::read_to_string(self.token_path.as_ref()?).ok()?;
+        let token = token.trim();
+        (!token.is_empty()).then(|| token.to_string())
+    }
+
+    fn save_token(&self, token: &str) {
+        let Some(path) = self.token_path.as_ref() else {
+            return;
+        };
+        match write_private(path, token) {
+            Ok(()) => debug!("Saved portal restore token to {}", path.display()),
+            Err(e) => warn!(
+                "Failed to save portal restore token to {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+
+    /// Drop a saved grant the portal no longer honours
+    fn forget_token(&self) {
+        let Some(path) = self.token_path.as_ref() else {
+            return;
+        };
+        match fs::remove_file(path) {
+            Ok(()) => info!("Removed revoked portal restore token"),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to remove {}: {}", path.display(), e),
+        }
+    }
+
+    /// Open a session with keyboard access, reusing the saved grant if the
+    /// portal still honours it
+    async fn open_session(&self) -> InjectionResult<PortalSession> {
+        let proxy = RemoteDesktop::new().await.map_err(portal_error)?;
+        let session = proxy.create_session().await.map_err(portal_error)?;
+        let token = self.load_token();
+        proxy
+            .select_devices(
+                &session,
+                DeviceType::Keyboard.into(),
+                token.as_deref(),
+                PersistMode::ExplicitlyRevoked,
+            )
+            .await
+            .map_err(portal_error)?;
+
+        info!(
+            restored = token.is_some(),
+            "Starting RemoteDesktop portal session"
+        );
+        let selected = timeout(START_TIMEOUT, async {
+            proxy.start(&session, None).await?.response()
+        })
+        .await
+        .map_err(|_| InjectionError::Timeout(START_TIMEOUT.as_millis() as u64))?
+        .map_err(portal_error);
+        let selected = match selected {
+            Ok(selected) if selected.devices().contains(DeviceType::Keyboard) => selected,
+            Ok(_) => {
+                self.forget_token();
+                return Err(InjectionError::PermissionDenied(
+                    "RemoteDesktop portal did not grant keyboard access".to_string(),
+                ));
+            }
+            Err(e) => {
+                // The user said no; a saved grant would only be stale now
+                if matches!(e, InjectionError::PermissionDenied(_)) {
+                    self.forget_token();
+                }
+                return Err(e);
+            }
+        };
+        if let Some(new_token) = selected.restore_token() {
+            if token.as_deref() != Some(new_token) {
+                self.save_token(new_token);
+            }
+        }
+        Ok(PortalSession { proxy, session })
+    }
+
+    /// Delay between keystrokes in milliseconds, preferring the rate learned
+    /// for the target app
+    fn key_delay_ms(&self, context: Option<&InjectionContext>) -> u64 {
+        let rate_cps = context
+            .and_then(|c| c.keystroke_rate_cps)
+            .unwrap_or(self.config.keystroke_rate_cps);
+        match rate_cps {
+            0 => DEFAULT_KEY_DELAY_MS,
+            cps => (1000 / u64::from(cps)).max(1),
+        }
+    }
+
+    /// Type `text` key by key. On failure, reports how many characters went
+    /// through so a lost session is only retried when nothing was typed.
+    async fn type_keys(
+        portal: &PortalSession,
+        text: &str,
+        delay: Duration,
+    ) -> Result<(), (usize, ashpd::Error)> {
+        for (typed, c) in text.chars().enumerate() {
+            // Checked by the caller
+            let Some(sym) = portal_keysym(c) else {
+                continue;
+            };
+            for state in [KeyState::Pressed, KeyState::Released] {
+                portal
+                    .proxy
+                    .notify_keyboard_keysym(&portal.session, sym as i32, state)
+                    .await
+                    .map_err(|e| (typed, e))?;
+            }
+            tokio::time::sleep(delay).await;
+        }
+        Ok(())
+    }
+}
+
+/// Write `contents` to `path` readable by the owner only, creating the
+/// parent directory (also owner-only) if needed
+fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        DirBuilder::new().recursive(true).mode(0o700).create(dir)?;
+    }
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // `mode` only applies to new files
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    file.write_all(contents.as_bytes())
+}
+
+/// Keysym to press for `c` through the portal
+fn portal_keysym(c: char) -> Option<u32> {
+    match c {
+        '\n' => Some(XK_RETURN),
+        '\t' => Some(XK_TAB),
+        c => keysym::keysym_for(c),
+    }
+}
+
+fn portal_error(e: ashpd::Error) -> InjectionError {
+    match e {
+        ashpd::Error::Response(ashpd::desktop::ResponseError::Cancelled) => {
+            InjectionError::PermissionDenied("RemoteDesktop portal request was cancelled".into())
+        }
+        e => InjectionError::MethodFailed(format!("RemoteDesktop portal: {}", e)),
+    }
+}
+
+#[async_trait]
+impl TextInjector for PortalInjector {
+    async fn inject_text(
+        &self,
+        text: &str,
+        context: Option<&InjectionContext>,
+    ) -> InjectionResult<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        if !self.is_available().await {
+            return Err(InjectionError::MethodUnavailable(
+                "RemoteDesktop portal is not available".to_string(),
+            ));
+        }
+        if let Some(c) = text.chars().find(|&c| portal_keysym(c).is_none()) {
+            return Err(InjectionError::MethodFailed(format!(
+                "portal cannot type {:?}; use clipboard paste",
+                c
+            )));
+        }
+
+        let start = Instant::now();
+        let delay_ms = self.key_delay_ms(context);
+        let budget_ms = self.config.per_method_timeout_ms + delay_ms * text.chars().count() as u64;
+        let mut portal = self.session.lock().await;
+
+        // A session closed by the compositor (or revoked by the user) is only
+        // noticed on the next keystroke; reopen once if nothing was typed yet
+        let mut reopened = false;
+        loop {
+            if portal.is_none() {
+                *portal = Some(self.open_session().await?);
+            }
+            let session = portal.as_ref().expect("session opened above");
+            let typed = timeout(
+                Duration::from_millis(budget_ms),
+                Self::type_keys(session, text, Duration::from_millis(delay_ms)),
+            )
+            .await
+            .map_err(|_| InjectionError::Timeout(budget_ms))?;
+            match typed {
+                Ok(()) => {
+                    utils::log_injection_success(
+                        InjectionMethod::PortalKeyboard,
+                        text,
+                        start.elapsed(),
+                        self.config.redact_logs,
+                    );
+                    return Ok(());
+                }
+                Err((0, e)) if !reopened => {
+                    warn!("RemoteDesktop portal session lost ({}); reopening", e);
+                    *portal = None;
+                    reopened = true;
+                }
+                Err((typed, e)) => {
+                    *portal = None;
+                    return Err(InjectionError::MethodFailed(format!(
+                        "RemoteDesktop portal session lost after {} characters: {}",
+                        typed, e
+                    )));
+                }
+            }
+        }
+    }
+
+    async fn is_available(&self) -> bool {
+        *self.available.get_or_init(Self::probe).await
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "Portal"
+    }
+
+    fn backend_info(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("type", "Wayland portal".to_string()),
+            (
+                "description",
+                "Types text through the XDG RemoteDesktop portal".to_string(),
+            ),
+            ("key_delay_ms", self.key_delay_ms(None).to_string()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn control_characters_press_their_keys() {
+        assert_eq!(portal_keysym('\n'), Some(XK_RETURN));
+        assert_eq!(portal_keysym('\t'), Some(XK_TAB));
+        assert_eq!(portal_keysym('a'), Some(0x61));
+        assert_eq!(portal_keysym('\u{fffe}'), None);
+    }
+
+    #[test]
+    fn restore_token_is_private_and_forgettable() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("coldvox").join("portal_restore_token");
+        let injector =
+            PortalInjector::new(InjectionConfig::default()).with_token_path(Some(path.clone()));
+        assert_eq!(injector.load_token(), None);
+
+        injector.save_token("token-1");
+        assert_eq!(injector.load_token().as_deref(), Some("token-1"));
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        injector.save_token("token-2");
+        assert_eq!(injector.load_token().as_deref(), Some("token-2"));
+
+        injector.forget_token();
+        assert_eq!(injector.load_token(), None);
+    }
+}
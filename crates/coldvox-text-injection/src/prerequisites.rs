@@ -0,0 +1,320 @@
+//! Environment checks behind `coldvox doctor`.
+//!
+//! Backends only find out at startup whether they can run, and log why not at
+//! debug level. These checks look at the same prerequisites directly (the
+//! display session, ydotoold and `/dev/uinput`, the AT-SPI bus, wl-clipboard
+//! and the RemoteDesktop portal) and say how to fix what is missing.
+
+use std::env;
+use std::fs::OpenOptions;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::detection::{detect_display_protocol, DisplayProtocol};
+
+/// How long a bus probe may take before it counts as unreachable
+#[cfg(any(feature = "atspi", feature = "portal"))]
+const PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// Works, but something is off
+    Warn,
+    Fail,
+    /// Not applicable to this build or session
+    Skip,
+}
+
+/// Outcome of one prerequisite check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrerequisiteCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// How to fix a failure or warning
+    pub hint: Option<String>,
+}
+
+impl PrerequisiteCheck {
+    pub fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    pub fn warn(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    pub fn fail(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    pub fn skip(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Skip,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+}
+
+/// Run every text-injection check
+pub async fn check_injection_prerequisites() -> Vec<PrerequisiteCheck> {
+    let protocol = detect_display_protocol();
+    vec![
+        display_session(protocol),
+        binary_check(
+            "ydotool",
+            "ydotool",
+            "install ydotool (paste fallback on Wayland)",
+        ),
+        ydotool_socket(),
+        uinput_access(Path::new("/dev/uinput")),
+        atspi_bus().await,
+        wl_clipboard(protocol),
+        portal(protocol).await,
+    ]
+}
+
+/// Executable `name` on `PATH`
+pub fn find_in_path(name: &str) -> Option<PathBuf> {
+    let path = env::var_os("PATH")?;
+    env::split_paths(&path)
+        .map(|dir| dir.join(name))
+        .find(|candidate| is_executable(candidate))
+}
+
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        path.metadata()
+            .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
+}
+
+fn display_session(protocol: DisplayProtocol) -> PrerequisiteCheck {
+    const NAME: &str = "display session";
+    match protocol {
+        DisplayProtocol::Wayland => PrerequisiteCheck::pass(NAME, "Wayland"),
+        DisplayProtocol::X11 if protocol.is_xwayland() => {
+            PrerequisiteCheck::pass(NAME, "X11 (XWayland)")
+        }
+        DisplayProtocol::X11 => PrerequisiteCheck::pass(NAME, "X11"),
+        DisplayProtocol::Unknown => PrerequisiteCheck::fail(
+            NAME,
+            "neither WAYLAND_DISPLAY nor DISPLAY is set",
+            "run coldvox inside the desktop session (or import its environment, e.g. \
+             `systemctl --user import-environment WAYLAND_DISPLAY DISPLAY`)",
+        ),
+    }
+}
+
+fn binary_check(name: &'static str, binary: &str, hint: &str) -> PrerequisiteCheck {
+    match find_in_path(binary) {
+        Some(path) => PrerequisiteCheck::pass(name, path.display().to_string()),
+        None => PrerequisiteCheck::fail(name, format!("{} not found on PATH", binary), hint),
+    }
+}
+
+#[cfg(all(unix, feature = "ydotool"))]
+fn ydotool_socket() -> PrerequisiteCheck {
+    use crate::ydotool_injector::{candidate_socket_paths, locate_existing_socket};
+
+    const NAME: &str = "ydotoold socket";
+    match locate_existing_socket() {
+        Some(socket) => PrerequisiteCheck::pass(NAME, socket.display().to_string()),
+        None => {
+            let searched: Vec<String> = candidate_socket_paths()
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect();
+            PrerequisiteCheck::fail(
+                NAME,
+                format!("no socket at {}", searched.join(", ")),
+                "start the daemon (`systemctl --user enable --now ydotool`, or run `ydotoold`) \
+                 or point YDOTOOL_SOCKET at its socket",
+            )
+        }
+    }
+}
+
+#[cfg(not(all(unix, feature = "ydotool")))]
+fn ydotool_socket() -> PrerequisiteCheck {
+    PrerequisiteCheck::skip("ydotoold socket", "built without the ydotool feature")
+}
+
+/// Write access to `/dev/uinput`, which ydotoold needs to create its keyboard
+fn uinput_access(device: &Path) -> PrerequisiteCheck {
+    const NAME: &str = "uinput access";
+    match OpenOptions::new().write(true).open(device) {
+        Ok(_) => PrerequisiteCheck::pass(NAME, format!("{} is writable", device.display())),
+        Err(e) if e.kind() == ErrorKind::NotFound => PrerequisiteCheck::fail(
+            NAME,
+            format!("{} does not exist", device.display()),
+            "load the uinput module: `sudo modprobe uinput`",
+        ),
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+            let in_input_group = Command::new("id")
+                .arg("-nG")
+                .output()
+                .map(|out| {
+                    String::from_utf8_lossy(&out.stdout)
+                        .split_whitespace()
+                        .any(|g| g == "input")
+                })
+                .unwrap_or(false);
+            let hint = if in_input_group {
+                "the input group cannot write it; add a udev rule: \
+                 KERNEL==\"uinput\", GROUP=\"input\", MODE=\"0660\""
+            } else {
+                "add yourself to the input group (`sudo usermod -aG input $USER`), then log \
+                 out and back in"
+            };
+            PrerequisiteCheck::fail(
+                NAME,
+                format!("permission denied opening {}", device.display()),
+                hint,
+            )
+        }
+        Err(e) => PrerequisiteCheck::fail(
+            NAME,
+            format!("cannot open {}: {}", device.display(), e),
+            "check the device node's permissions",
+        ),
+    }
+}
+
+#[cfg(feature = "atspi")]
+async fn atspi_bus() -> PrerequisiteCheck {
+    use atspi::connection::AccessibilityConnection;
+
+    const NAME: &str = "AT-SPI bus";
+    match tokio::time::timeout(PROBE_TIMEOUT, AccessibilityConnection::new()).await {
+        Ok(Ok(_)) => PrerequisiteCheck::pass(NAME, "accessibility bus reachable"),
+        Ok(Err(e)) => PrerequisiteCheck::fail(
+            NAME,
+            format!("cannot connect: {}", e),
+            "install at-spi2-core and enable accessibility (GNOME: `gsettings set \
+             org.gnome.desktop.interface toolkit-accessibility true`)",
+        ),
+        Err(_) => PrerequisiteCheck::fail(
+            NAME,
+            format!("no answer within {}s", PROBE_TIMEOUT.as_secs()),
+            "check that at-spi-bus-launcher is running in this session",
+        ),
+    }
+}
+
+#[cfg(not(feature = "atspi"))]
+async fn atspi_bus() -> PrerequisiteCheck {
+    PrerequisiteCheck::skip("AT-SPI bus", "built without the atspi feature")
+}
+
+fn wl_clipboard(protocol: DisplayProtocol) -> PrerequisiteCheck {
+    const NAME: &str = "wl-clipboard";
+    if !protocol.is_wayland() {
+        return PrerequisiteCheck::skip(NAME, "not a Wayland session");
+    }
+    let missing: Vec<&str> = ["wl-copy", "wl-paste"]
+        .into_iter()
+        .filter(|binary| find_in_path(binary).is_none())
+        .collect();
+    if missing.is_empty() {
+        PrerequisiteCheck::pass(NAME, "wl-copy and wl-paste on PATH")
+    } else if cfg!(feature = "wl_clipboard") {
+        // The built-in client covers copying; the binaries are a fallback
+        PrerequisiteCheck::warn(
+            NAME,
+            format!("{} not on PATH", missing.join(" and ")),
+            "install wl-clipboard for the command-line clipboard fallback",
+        )
+    } else {
+        PrerequisiteCheck::fail(
+            NAME,
+            format!("{} not on PATH", missing.join(" and ")),
+            "install wl-clipboard (clipboard paste needs it on Wayland)",
+        )
+    }
+}
+
+#[cfg(feature = "portal")]
+async fn portal(protocol: DisplayProtocol) -> PrerequisiteCheck {
+    const NAME: &str = "RemoteDesktop portal";
+    if !protocol.is_wayland() {
+        return PrerequisiteCheck::skip(NAME, "not a Wayland session");
+    }
+    match tokio::time::timeout(
+        PROBE_TIMEOUT,
+        crate::portal_injector::PortalInjector::probe(),
+    )
+    .await
+    {
+        Ok(true) => PrerequisiteCheck::pass(NAME, "keyboard devices offered"),
+        Ok(false) => PrerequisiteCheck::fail(
+            NAME,
+            "portal missing or offers no keyboard",
+            "install xdg-desktop-portal and your desktop's backend \
+             (xdg-desktop-portal-gnome or -kde)",
+        ),
+        Err(_) => PrerequisiteCheck::fail(
+            NAME,
+            format!("no answer within {}s", PROBE_TIMEOUT.as_secs()),
+            "check that xdg-desktop-portal is running (`systemctl --user status \
+             xdg-desktop-portal`)",
+        ),
+    }
+}
+
+#[cfg(not(feature = "portal"))]
+async fn portal(_protocol: DisplayProtocol) -> PrerequisiteCheck {
+    PrerequisiteCheck::skip("RemoteDesktop portal", "built without the portal feature")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_uinput_device_fails_with_a_hint() {
+        let dir = tempfile::tempdir().unwrap();
+        let check = uinput_access(&dir.path().join("uinput"));
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.hint.unwrap().contains("modprobe uinput"));
+
+        let writable = dir.path().join("fake-uinput");
+        std::fs::write(&writable, b"").unwrap();
+        assert_eq!(uinput_access(&writable).status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn display_session_needs_a_protocol() {
+        assert_eq!(
+            display_session(DisplayProtocol::Unknown).status,
+            CheckStatus::Fail
+        );
+        assert_eq!(wl_clipboard(DisplayProtocol::X11).status, CheckStatus::Skip);
+        assert!(find_in_path("definitely-not-a-coldvox-binary").is_none());
+    }
+}
@@ -414,7 +414,7 @@ impl PrewarmController {
     }
 
     /// Execute all pre-warming steps in parallel
-    async fn execute_all_prewarming(&self) {
+    pub(crate) async fn execute_all_prewarming(&self) {
         let start_time = Instant::now();
         info!("Starting pre-warming of all injection components");
 
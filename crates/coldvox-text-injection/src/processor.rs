@@ -1,20 +1,23 @@
 use coldvox_stt::TranscriptionEvent;
-
-/// Placeholder for pipeline metrics - to be provided by the main app
-#[derive(Debug, Clone, Default)]
-pub struct PipelineMetrics {
-    pub processed_events: u64,
-    pub injection_latency_ms: u64,
-}
+use coldvox_telemetry::{MetricsSource, PipelineMetrics, PrometheusText, UtteranceLatency};
+use std::borrow::Cow;
+use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc, oneshot, Notify};
 use tokio::time::{self, Duration, Instant};
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, info_span, trace, warn, Instrument};
 
-use super::manager::StrategyManager;
+use super::manager::{BackendCatalog, StrategyManager};
 use super::session::{InjectionSession, SessionConfig, SessionState};
 use super::InjectionConfig;
-use crate::types::InjectionMetrics;
+use crate::commands::{BufferCommand, CommandFastPath, FinalDisposition, VoiceCommand};
+use crate::content_filter::{self, ContentFilter, SensitiveKind};
+use crate::dictation_mode::DictationMode;
+use crate::display_watch::{DisplayEvent, DisplayWatch};
+use crate::guard::{self, GuardEvent, RateLimiter};
+use crate::injection_event::InjectionEvent;
+use crate::queue::{Enqueued, InjectionQueue, QueuedItem};
+use crate::types::{BackendStatus, InjectionMetrics, InjectionMode, SensitiveContentAction};
 
 /// Local metrics for the injection processor (UI/state), distinct from types::InjectionMetrics
 #[derive(Debug, Clone, Default)]
@@ -33,6 +36,8 @@ pub struct ProcessorMetrics {
     pub failed_injections: u64,
     /// Last injection timestamp
     pub last_injection_time: Option<Instant>,
+    /// How final transcripts are currently turned into text
+    pub dictation_mode: DictationMode,
 }
 
 impl ProcessorMetrics {
@@ -47,6 +52,59 @@ impl ProcessorMetrics {
     }
 }
 
+impl MetricsSource for ProcessorMetrics {
+    fn write_metrics(&self, out: &mut PrometheusText) {
+        out.gauge(
+            "coldvox_injection_session_active",
+            "1 while the injection session is buffering or about to inject",
+            f64::from(u8::from(self.session_state != SessionState::Idle)),
+        );
+        out.gauge(
+            "coldvox_injection_buffer_transcriptions",
+            "Transcriptions buffered in the current session",
+            self.buffer_size as f64,
+        );
+        out.gauge(
+            "coldvox_injection_buffer_chars",
+            "Characters buffered in the current session",
+            self.buffer_chars as f64,
+        );
+        out.counter(
+            "coldvox_injection_success_total",
+            "Successful injections",
+            self.successful_injections,
+        );
+        out.counter(
+            "coldvox_injection_failures_total",
+            "Failed injections",
+            self.failed_injections,
+        );
+    }
+}
+
+/// Why a final transcript is held
+#[derive(Debug, Clone, PartialEq)]
+pub enum HoldReason {
+    /// Confidence below `min_confidence`
+    LowConfidence,
+    /// `sensitive_content = "confirm"` and the filter found these
+    SensitiveContent(Vec<SensitiveKind>),
+    /// This command was sent from partials, but the final says something else
+    UnconfirmedCommand(VoiceCommand),
+}
+
+/// A final transcript held back because its confidence was below
+/// `min_confidence`, it looks sensitive or it contradicts a command already
+/// sent; typed only if the user accepts it
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeldTranscript {
+    pub utterance_id: u64,
+    pub text: String,
+    /// Mean word confidence (0.0-1.0; 1.0 when the plugin reports none)
+    pub confidence: f32,
+    pub reason: HoldReason,
+}
+
 /// Processor that manages session-based text injection
 pub struct InjectionProcessor {
     /// The injection session
@@ -59,9 +117,25 @@ pub struct InjectionProcessor {
     /// Metrics for telemetry
     metrics: Arc<Mutex<ProcessorMetrics>>,
     /// Shared injection metrics for all components
-    injection_metrics: Arc<Mutex<crate::types::InjectionMetrics>>,
+    injection_metrics: Arc<crate::types::InjectionMetrics>,
     /// Pipeline metrics for integration
     _pipeline_metrics: Option<Arc<PipelineMetrics>>,
+    /// Spoken command recognition (None when voice commands are disabled)
+    commands: Option<CommandFastPath>,
+    /// Post-processing applied to final transcripts
+    mode: DictationMode,
+    /// Utterances whose text is in the session buffer (latency correlation)
+    pending_utterances: Vec<u64>,
+    /// Latest low-confidence transcript awaiting the user's decision
+    held: Option<HeldTranscript>,
+    /// Announces transcripts as they are held
+    held_tx: broadcast::Sender<HeldTranscript>,
+    /// Secret/PII detection for `sensitive_content`
+    content_filter: ContentFilter,
+    /// Announces size and rate limits as they trigger
+    guard_tx: broadcast::Sender<GuardEvent>,
+    /// Characters of the last successful injection that Undo may erase
+    undoable_chars: usize,
 }
 
 impl InjectionProcessor {
@@ -69,26 +143,47 @@ impl InjectionProcessor {
     pub async fn new(
         config: InjectionConfig,
         pipeline_metrics: Option<Arc<PipelineMetrics>>,
-        injection_metrics: Arc<Mutex<InjectionMetrics>>,
+        injection_metrics: Arc<InjectionMetrics>,
     ) -> Self {
         // Create session with shared metrics
-        let session_config = SessionConfig::default(); // TODO: Expose this if needed (config refinement)
+        let session_config = SessionConfig {
+            buffered: config.buffered_dictation,
+            final_merge_window_ms: config.final_merge_window_ms,
+            ..Default::default() // TODO: Expose the rest if needed (config refinement)
+        };
         let session = InjectionSession::new(session_config, injection_metrics.clone());
 
         let injector = StrategyManager::new(config.clone(), injection_metrics.clone()).await;
 
         let metrics = Arc::new(Mutex::new(ProcessorMetrics {
             session_state: SessionState::Idle,
+            dictation_mode: config.dictation_mode,
             ..Default::default()
         }));
 
+        let commands = config.voice_commands.then(|| {
+            CommandFastPath::new(
+                std::time::Duration::from_millis(config.command_max_duration_ms),
+                config.command_min_stable_partials,
+            )
+        });
+        let content_filter = ContentFilter::new(&config.sensitive_patterns);
+
         Self {
             session,
             injector,
+            mode: config.dictation_mode,
             config,
             metrics,
             injection_metrics,
             _pipeline_metrics: pipeline_metrics,
+            commands,
+            pending_utterances: Vec::new(),
+            held: None,
+            held_tx: broadcast::channel(16).0,
+            content_filter,
+            guard_tx: broadcast::channel(16).0,
+            undoable_chars: 0,
         }
     }
 
@@ -105,52 +200,291 @@ impl InjectionProcessor {
         None
     }
 
-    /// Record the result of an injection attempt and refresh metrics.
-    pub fn record_injection_result(&mut self, success: bool) {
+    /// Release whatever the session holds, silence timeout or not, so a
+    /// spoken command queued next lands after it. Returns the text and its
+    /// utterances, or None when the session is empty.
+    pub fn take_before_command(&mut self) -> Option<(String, Vec<u64>)> {
+        self.session.force_inject();
+        let text = self.prepare_injection()?;
+        Some((text, self.take_utterance_ids()))
+    }
+
+    /// Utterances behind the text last returned by [`Self::prepare_injection`]
+    pub fn take_utterance_ids(&mut self) -> Vec<u64> {
+        std::mem::take(&mut self.pending_utterances)
+    }
+
+    /// Record the result of injecting `text` and refresh metrics. Only a
+    /// successful injection can be undone; a failed one may have typed part
+    /// of the text, so nothing is left to erase.
+    pub fn record_injection_result(&mut self, text: &str, success: bool) {
         if success {
             self.metrics.lock().unwrap().successful_injections += 1;
             self.metrics.lock().unwrap().last_injection_time = Some(Instant::now());
+            self.undoable_chars = text.chars().count();
         } else {
             self.metrics.lock().unwrap().failed_injections += 1;
+            self.undoable_chars = 0;
         }
         self.update_metrics();
     }
 
+    /// Characters Undo should erase, once: the last successful injection,
+    /// unless a key chord was sent after it
+    pub fn take_undoable_chars(&mut self) -> usize {
+        std::mem::take(&mut self.undoable_chars)
+    }
+
+    /// A key chord moved the cursor or changed the text; erasing the last
+    /// injection by character count is no longer safe
+    pub fn forget_undoable(&mut self) {
+        self.undoable_chars = 0;
+    }
+
     /// Get current metrics
     pub fn metrics(&self) -> ProcessorMetrics {
         self.metrics.lock().unwrap().clone()
     }
 
-    /// Handle a transcription event from the STT processor
-    pub fn handle_transcription(&mut self, event: TranscriptionEvent) {
+    /// Live metrics, for readers that cannot await the processor lock
+    pub fn shared_metrics(&self) -> Arc<Mutex<ProcessorMetrics>> {
+        self.metrics.clone()
+    }
+
+    /// Handle a transcription event from the STT processor.
+    ///
+    /// Returns a spoken command the caller should send as a key chord, either
+    /// early from stable partials or from the final transcript.
+    pub fn handle_transcription(&mut self, event: TranscriptionEvent) -> Option<VoiceCommand> {
+        if let Some(source) = event.source() {
+            if !self.config.injects_source(source.as_str()) {
+                trace!(
+                    "Ignoring transcription from non-injecting source '{}'",
+                    source
+                );
+                return None;
+            }
+        }
+        let confidence = event.confidence();
         match event {
             TranscriptionEvent::Partial {
-                text, utterance_id, ..
+                text,
+                utterance_id,
+                t1,
+                ..
             } => {
                 debug!(
                     "Received partial transcription [{}]: {}",
                     utterance_id, text
                 );
                 self.update_metrics();
+                if self.session.is_buffered() || !self.mode.interprets_commands() {
+                    // Nothing is typed until a flush, so there is no latency to save
+                    return None;
+                }
+                let command = self.commands.as_mut()?.on_partial(
+                    utterance_id,
+                    &text,
+                    t1,
+                    std::time::Instant::now(),
+                );
+                if let Some(cmd) = command {
+                    info!(
+                        "Command '{}' recognized from partials [{}]; dispatching early",
+                        cmd, utterance_id
+                    );
+                }
+                command
             }
             TranscriptionEvent::Final {
                 text, utterance_id, ..
             } => {
+                info!(
+                    "Received final transcription [{}]: {}",
+                    utterance_id,
+                    content_filter::scrub(&text)
+                );
+                if let Err(event) = guard::check_utterance(&self.config, utterance_id, &text) {
+                    self.report_guard(event);
+                    return None;
+                }
+                if let (Some(min), Some(confidence)) = (self.config.min_confidence, confidence) {
+                    if confidence < min {
+                        self.hold(HeldTranscript {
+                            utterance_id,
+                            text,
+                            confidence,
+                            reason: HoldReason::LowConfidence,
+                        });
+                        return None;
+                    }
+                }
+                if self.commands.is_some() {
+                    if let Some(mode) = DictationMode::parse_switch(&text) {
+                        info!("Mode switch to '{}' recognized [{}]", mode, utterance_id);
+                        self.set_dictation_mode(mode);
+                        return None;
+                    }
+                }
+                let interprets_commands = self.mode.interprets_commands();
+                if interprets_commands && self.session.is_buffered() && self.commands.is_some() {
+                    if let Some(cmd) = BufferCommand::parse(&text) {
+                        info!("Draft command '{}' recognized [{}]", cmd, utterance_id);
+                        self.apply_buffer_command(cmd);
+                        return None;
+                    }
+                }
+                let disposition = self
+                    .commands
+                    .as_mut()
+                    .filter(|_| interprets_commands)
+                    .map_or(FinalDisposition::Text, |c| c.on_final(utterance_id, &text));
+                match disposition {
+                    FinalDisposition::Handled => {
+                        debug!("Final [{}] already dispatched as a command", utterance_id);
+                        return None;
+                    }
+                    FinalDisposition::Dispatch(cmd) => {
+                        info!("Command '{}' recognized [{}]", cmd, utterance_id);
+                        return Some(cmd);
+                    }
+                    FinalDisposition::Unconfirmed(cmd) => {
+                        self.hold(HeldTranscript {
+                            utterance_id,
+                            text,
+                            confidence: confidence.unwrap_or(1.0),
+                            reason: HoldReason::UnconfirmedCommand(cmd),
+                        });
+                        return None;
+                    }
+                    FinalDisposition::Text => {}
+                }
+                let text = match self.mode.apply(&text) {
+                    Cow::Owned(converted) => converted,
+                    Cow::Borrowed(_) => text,
+                };
+                let text = self.screen_sensitive(utterance_id, text, confidence)?;
                 let text_len = text.len();
-                info!("Received final transcription [{}]: {}", utterance_id, text);
                 self.session.add_transcription(text);
+                self.pending_utterances.push(utterance_id);
                 // Record the number of characters buffered
-                if let Ok(mut metrics) = self.injection_metrics.lock() {
-                    metrics.record_buffered_chars(text_len as u64);
-                }
+                self.injection_metrics
+                    .record_buffered_chars(text_len as u64);
                 self.update_metrics();
+                None
             }
             TranscriptionEvent::Error { code, message } => {
                 warn!("Transcription error [{}]: {}", code, message);
+                None
             }
         }
     }
 
+    /// Apply `sensitive_content` to a final transcript about to be buffered.
+    /// Returns the text to inject, or None when it was held.
+    fn screen_sensitive(
+        &mut self,
+        utterance_id: u64,
+        text: String,
+        confidence: Option<f32>,
+    ) -> Option<String> {
+        let action = self.config.sensitive_content;
+        if action == SensitiveContentAction::Off {
+            return Some(text);
+        }
+        let kinds = self.content_filter.kinds(&text);
+        if kinds.is_empty() {
+            return Some(text);
+        }
+        match action {
+            SensitiveContentAction::Off => Some(text),
+            SensitiveContentAction::Warn => {
+                warn!(
+                    "Final transcription [{}] looks like it contains: {}",
+                    utterance_id,
+                    content_filter::describe(&kinds)
+                );
+                Some(text)
+            }
+            SensitiveContentAction::Redact => {
+                info!(
+                    "Redacting {} from final transcription [{}]",
+                    content_filter::describe(&kinds),
+                    utterance_id
+                );
+                Some(self.content_filter.redact(&text))
+            }
+            SensitiveContentAction::Confirm => {
+                self.hold(HeldTranscript {
+                    utterance_id,
+                    text,
+                    confidence: confidence.unwrap_or(1.0),
+                    reason: HoldReason::SensitiveContent(kinds),
+                });
+                None
+            }
+        }
+    }
+
+    fn report_guard(&self, event: GuardEvent) {
+        warn!("{}", event);
+        let _ = self.guard_tx.send(event);
+    }
+
+    /// Hold a transcript for the user's decision, replacing any older one
+    fn hold(&mut self, held: HeldTranscript) {
+        match &held.reason {
+            HoldReason::LowConfidence => info!(
+                "Holding final transcription [{}] with confidence {:.2} (below {:.2})",
+                held.utterance_id,
+                held.confidence,
+                self.config.min_confidence.unwrap_or_default()
+            ),
+            HoldReason::SensitiveContent(kinds) => info!(
+                "Holding final transcription [{}] for confirmation: looks like it contains {}",
+                held.utterance_id,
+                content_filter::describe(kinds)
+            ),
+            HoldReason::UnconfirmedCommand(cmd) => info!(
+                "Holding final transcription [{}]: '{}' was already sent from partials",
+                held.utterance_id, cmd
+            ),
+        }
+        if let Some(previous) = self.held.replace(held.clone()) {
+            debug!(
+                "Discarding earlier held transcription [{}]",
+                previous.utterance_id
+            );
+        }
+        let _ = self.held_tx.send(held);
+    }
+
+    /// Type the held transcript after all. Returns it, or None when nothing
+    /// is held.
+    pub fn accept_held(&mut self) -> Option<HeldTranscript> {
+        let held = self.held.take()?;
+        info!(
+            "Accepted held transcription [{}] ({:.2})",
+            held.utterance_id, held.confidence
+        );
+        self.session.add_transcription(held.text.clone());
+        self.pending_utterances.push(held.utterance_id);
+        self.injection_metrics
+            .record_buffered_chars(held.text.len() as u64);
+        self.update_metrics();
+        Some(held)
+    }
+
+    /// Drop the held transcript
+    pub fn discard_held(&mut self) -> Option<HeldTranscript> {
+        self.held.take()
+    }
+
+    /// Held transcripts as they are held
+    pub fn subscribe_held(&self) -> broadcast::Receiver<HeldTranscript> {
+        self.held_tx.subscribe()
+    }
+
     /// Check if injection should be performed and execute if needed
     pub async fn check_and_inject(&mut self) -> anyhow::Result<()> {
         if self.session.should_inject() {
@@ -171,9 +505,60 @@ impl InjectionProcessor {
         Ok(())
     }
 
+    /// Apply a spoken edit to the buffered dictation draft
+    pub fn apply_buffer_command(&mut self, cmd: BufferCommand) {
+        match cmd {
+            BufferCommand::Flush => self.flush_buffer(),
+            BufferCommand::ScratchThat => match self.session.scratch_last() {
+                Some(removed) => debug!("Dropped {} characters from the draft", removed.len()),
+                None => info!("Draft is empty; nothing to scratch"),
+            },
+            BufferCommand::DeleteWord => {
+                if !self.session.delete_last_word() {
+                    info!("Draft is empty; nothing to delete");
+                }
+            }
+            BufferCommand::Clear => {
+                self.session.clear();
+                self.pending_utterances.clear();
+                info!("Draft discarded");
+            }
+        }
+        self.update_metrics();
+    }
+
+    /// Current dictation mode
+    pub fn dictation_mode(&self) -> DictationMode {
+        self.mode
+    }
+
+    /// Switch the dictation mode for the following final transcripts
+    pub fn set_dictation_mode(&mut self, mode: DictationMode) {
+        if mode != self.mode {
+            info!("Dictation mode: {} -> {}", self.mode, mode);
+        }
+        self.mode = mode;
+        self.metrics.lock().unwrap().dictation_mode = mode;
+    }
+
+    /// Release the buffered dictation draft for injection on the next check
+    pub fn flush_buffer(&mut self) {
+        if self.session.has_content() {
+            info!(
+                "Flushing {} buffered characters",
+                self.session.total_chars()
+            );
+            self.session.force_inject();
+        } else {
+            debug!("Flush requested with an empty buffer");
+        }
+        self.update_metrics();
+    }
+
     /// Clear current session buffer
     pub fn clear_session(&mut self) {
         self.session.clear();
+        self.pending_utterances.clear();
         self.update_metrics();
         info!("Session cleared manually");
     }
@@ -181,6 +566,7 @@ impl InjectionProcessor {
     /// Perform the actual text injection
     async fn perform_injection(&mut self) -> anyhow::Result<()> {
         let text = self.session.take_buffer();
+        let utterance_ids = self.take_utterance_ids();
         if text.is_empty() {
             return Ok(());
         }
@@ -199,25 +585,39 @@ impl InjectionProcessor {
         );
 
         // Record the latency in metrics
-        if let Ok(mut metrics) = self.injection_metrics.lock() {
-            metrics.record_latency_from_final(latency);
-            metrics.update_last_injection();
+        self.injection_metrics.record_latency_from_final(latency);
+        self.injection_metrics.update_last_injection();
+
+        let parent = self
+            ._pipeline_metrics
+            .as_ref()
+            .and_then(|m| m.traces.parent(&utterance_ids));
+        let result = self
+            .injector
+            .inject(&text)
+            .instrument(info_span!(
+                parent: parent.as_ref().and_then(|p| p.id()),
+                "inject",
+                utterance_ids = ?utterance_ids
+            ))
+            .await;
+        if let Some(m) = &self._pipeline_metrics {
+            m.traces.finish(&utterance_ids);
         }
-
-        match self.injector.inject(&text).await {
+        match result {
             Ok(()) => {
                 info!("Successfully injected text");
-                self.metrics.lock().unwrap().successful_injections += 1;
-                self.metrics.lock().unwrap().last_injection_time = Some(Instant::now());
+                if let Some(m) = &self._pipeline_metrics {
+                    log_utterance_latency(&m.latency.record_injected(&utterance_ids));
+                }
+                self.record_injection_result(&text, true);
             }
             Err(e) => {
                 error!("Failed to inject text: {}", e);
-                self.metrics.lock().unwrap().failed_injections += 1;
+                self.record_injection_result(&text, false);
                 return Err(e.into());
             }
         }
-
-        self.update_metrics();
         Ok(())
     }
 
@@ -268,6 +668,26 @@ pub struct AsyncInjectionProcessor {
     shutdown_rx: mpsc::Receiver<()>,
     // dedicated injector to avoid awaiting while holding the processor lock
     injector: StrategyManager,
+    // sends the key chords of spoken commands
+    key_injector: crate::ydotool_injector::YdotoolInjector,
+    // recovery, notification and queue settings
+    config: InjectionConfig,
+    // queue depth, coalescing and drop counters
+    pipeline_metrics: Option<Arc<PipelineMetrics>>,
+    // flush requests for buffered dictation
+    flush: Arc<Notify>,
+    // requests to erase the last injection
+    undo: Arc<Notify>,
+    // live processor metrics, readable without the processor lock
+    metrics: Arc<Mutex<ProcessorMetrics>>,
+    // size and rate limit events
+    guard_tx: broadcast::Sender<GuardEvent>,
+    // utterances the worker is done with, injected or not
+    settled_tx: broadcast::Sender<Vec<u64>>,
+    // queued text, methods tried and outcomes
+    events_tx: broadcast::Sender<InjectionEvent>,
+    // counters shared with the processor and injector
+    injection_metrics: Arc<InjectionMetrics>,
 }
 
 impl AsyncInjectionProcessor {
@@ -279,74 +699,175 @@ impl AsyncInjectionProcessor {
         pipeline_metrics: Option<Arc<PipelineMetrics>>,
     ) -> Self {
         // Create shared injection metrics
-        let injection_metrics = Arc::new(Mutex::new(crate::types::InjectionMetrics::default()));
+        let injection_metrics = Arc::new(crate::types::InjectionMetrics::default());
 
         // Create processor with shared metrics
-        let processor = Arc::new(tokio::sync::Mutex::new(
-            InjectionProcessor::new(config.clone(), pipeline_metrics, injection_metrics.clone())
-                .await,
-        ));
+        let processor = InjectionProcessor::new(
+            config.clone(),
+            pipeline_metrics.clone(),
+            injection_metrics.clone(),
+        )
+        .await;
+        let metrics = processor.shared_metrics();
+        let guard_tx = processor.guard_tx.clone();
+        let processor = Arc::new(tokio::sync::Mutex::new(processor));
+        if let Some(m) = &pipeline_metrics {
+            m.traces.expect_injection();
+        }
+
+        let key_injector = crate::ydotool_injector::YdotoolInjector::new(config.clone());
 
         // Create injector with shared metrics
-        let injector = StrategyManager::new(config, injection_metrics.clone()).await;
+        let injector = StrategyManager::new(config.clone(), injection_metrics.clone()).await;
+        let events_tx = injector.injection_events();
 
         Self {
             processor,
             transcription_rx,
             shutdown_rx,
             injector,
+            key_injector,
+            config,
+            pipeline_metrics,
+            flush: Arc::new(Notify::new()),
+            undo: Arc::new(Notify::new()),
+            metrics,
+            guard_tx,
+            settled_tx: broadcast::channel(32).0,
+            events_tx,
+            injection_metrics,
         }
     }
 
     /// Run the injection processor loop
-    pub async fn run(mut self) -> anyhow::Result<()> {
+    ///
+    /// Finished utterances go through a bounded [`InjectionQueue`] to a
+    /// worker task that injects them in order, so transcripts keep arriving
+    /// while an injection is in flight. Spoken commands are queued the same
+    /// way, behind any dictation spoken before them.
+    pub async fn run(self) -> anyhow::Result<()> {
+        let Self {
+            processor,
+            mut transcription_rx,
+            mut shutdown_rx,
+            injector,
+            key_injector,
+            config,
+            pipeline_metrics,
+            flush,
+            undo,
+            metrics: _,
+            guard_tx,
+            settled_tx,
+            events_tx,
+            injection_metrics: _,
+        } = self;
         let check_interval = Duration::from_millis(100); // TODO: Make configurable (config refinement)
         let mut interval = time::interval(check_interval);
 
+        let queue = Arc::new(Mutex::new(InjectionQueue::new(&config)));
+        let queue_ready = Arc::new(Notify::new());
+        let (stop_tx, stop_rx) = oneshot::channel();
+        let worker = tokio::spawn(run_injection_worker(
+            injector,
+            key_injector,
+            processor.clone(),
+            queue.clone(),
+            queue_ready.clone(),
+            config.clone(),
+            pipeline_metrics.clone(),
+            guard_tx,
+            settled_tx,
+            events_tx.clone(),
+            stop_rx,
+        ));
+
         info!("Injection processor started");
 
         loop {
+            // A full queue under the Block policy stops intake, which backs up
+            // the transcription channel instead of losing utterances
+            let blocked = queue.lock().unwrap().is_blocking();
+
             tokio::select! {
                 // Handle transcription events
-                Some(event) = self.transcription_rx.recv() => {
-                    let mut processor = self.processor.lock().await;
-                    processor.handle_transcription(event);
+                Some(event) = transcription_rx.recv(), if !blocked => {
+                    let (command, before) = {
+                        let mut processor = processor.lock().await;
+                        let command = processor.handle_transcription(event);
+                        let before = command.and_then(|_| processor.take_before_command());
+                        (command, before)
+                    };
+                    if let Some(cmd) = command {
+                        if let Some((text, utterance_ids)) = before {
+                            enqueue(
+                                &queue,
+                                text,
+                                utterance_ids,
+                                pipeline_metrics.as_deref(),
+                                &events_tx,
+                            );
+                        }
+                        debug!("Queued command '{}'", cmd);
+                        queue.lock().unwrap().push_command(cmd);
+                        queue_ready.notify_one();
+                    }
                 }
 
                 // Periodic check for silence timeout
                 _ = interval.tick() => {
-                    // Prepare any pending injection without holding the lock across await
+                    if blocked {
+                        trace!("Injection queue full; holding buffered text");
+                        continue;
+                    }
                     let maybe_text = {
-                        let mut processor = self.processor.lock().await;
-                        // Extract text to inject if session criteria are met
-                        processor.prepare_injection()
+                        let mut processor = processor.lock().await;
+                        processor
+                            .prepare_injection()
+                            .map(|text| (text, processor.take_utterance_ids()))
                     };
-
-                    if let Some(text) = maybe_text {
-                        // Perform the async injection outside the lock
-                        info!("Attempting injection of {} characters", text.len());
-                        let result = self.injector.inject(&text).await;
-                        let success = result.is_ok();
-
-                        // Record result back into the processor state/metrics
-                        let mut processor = self.processor.lock().await;
-                        processor.record_injection_result(success);
-                        if let Err(e) = result {
-                            error!("Injection failed: {}", e);
-                        } else {
-                            info!("Injection completed successfully");
-                        }
+                    if let Some((text, utterance_ids)) = maybe_text {
+                        enqueue(
+                            &queue,
+                            text,
+                            utterance_ids,
+                            pipeline_metrics.as_deref(),
+                            &events_tx,
+                        );
+                        queue_ready.notify_one();
                     }
                 }
 
+                // Flush of the buffered dictation draft
+                _ = flush.notified() => {
+                    processor.lock().await.flush_buffer();
+                }
+
+                // Undo of the last injection, after anything queued before it
+                _ = undo.notified() => {
+                    queue.lock().unwrap().push_undo();
+                    queue_ready.notify_one();
+                }
+
                 // Shutdown signal
-                _ = self.shutdown_rx.recv() => {
+                _ = shutdown_rx.recv() => {
                     info!("Received shutdown signal, graceful exit initiated");
                     break;
                 }
             }
         }
 
+        // Let the in-flight injection finish; anything still queued is dropped
+        drop(stop_tx);
+        queue_ready.notify_one();
+        if let Err(e) = worker.await {
+            error!("Injection worker failed: {}", e);
+        }
+        let pending = queue.lock().unwrap().drain();
+        if !pending.is_empty() {
+            warn!("Dropping {} queued utterance(s) on shutdown", pending.len());
+        }
+
         Ok(())
     }
 
@@ -369,11 +890,378 @@ impl AsyncInjectionProcessor {
     pub async fn last_partial_text(&self) -> Option<String> {
         self.processor.lock().await.last_partial_text()
     }
+
+    /// Live processor metrics (e.g. for the Prometheus endpoint)
+    pub fn metrics_handle(&self) -> Arc<Mutex<ProcessorMetrics>> {
+        self.metrics.clone()
+    }
+
+    /// Injection counters (per method, per application) for this session
+    pub fn injection_metrics(&self) -> Arc<InjectionMetrics> {
+        self.injection_metrics.clone()
+    }
+
+    /// Handle for listing injection backends while the processor runs
+    pub fn backend_catalog(&self) -> BackendCatalog {
+        self.injector.backend_catalog()
+    }
+
+    /// Name, availability, last error and capabilities of each backend
+    pub async fn list_backends(&self) -> Vec<BackendStatus> {
+        self.injector.list_backends().await
+    }
+
+    /// Trigger that flushes the buffered dictation draft (no-op otherwise,
+    /// apart from injecting anything already pending)
+    pub fn flush_signal(&self) -> Arc<Notify> {
+        self.flush.clone()
+    }
+
+    /// Trigger that erases the last successfully injected text, with the
+    /// configured injection settings (no-op when nothing is left to undo)
+    pub fn undo_signal(&self) -> Arc<Notify> {
+        self.undo.clone()
+    }
+
+    /// Progress events and cancellation for chunked pastes of long text
+    pub fn paste_control(&self) -> crate::progress::PasteControl {
+        self.injector.paste_control()
+    }
+
+    /// Size and rate limits as they trigger
+    pub fn guard_events(&self) -> broadcast::Sender<GuardEvent> {
+        self.guard_tx.clone()
+    }
+
+    /// Utterance ids of each queued item once the worker is done with it:
+    /// injected, failed or dropped by a rate limit
+    pub fn subscribe_settled(&self) -> broadcast::Receiver<Vec<u64>> {
+        self.settled_tx.subscribe()
+    }
+
+    /// Queued text, injection methods tried and the outcome of each queued
+    /// item
+    pub fn injection_events(&self) -> broadcast::Sender<InjectionEvent> {
+        self.events_tx.clone()
+    }
+
+    /// Lock injection to a window for the following utterances
+    pub fn target_lock(&self) -> crate::target_lock::TargetLock {
+        self.injector.target_lock()
+    }
+
+    /// Read access to the dictation buffered for injection
+    pub fn draft_handle(&self) -> DraftHandle {
+        DraftHandle {
+            processor: self.processor.clone(),
+        }
+    }
+
+    /// Read and switch the dictation mode while the processor runs
+    pub fn dictation_mode_handle(&self) -> DictationModeHandle {
+        DictationModeHandle {
+            processor: self.processor.clone(),
+        }
+    }
+
+    /// Notifications of, and decisions on, low-confidence transcripts
+    pub async fn low_confidence_handle(&self) -> LowConfidenceHandle {
+        LowConfidenceHandle {
+            held_tx: self.processor.lock().await.held_tx.clone(),
+            processor: self.processor.clone(),
+        }
+    }
+}
+
+/// Cloneable access to the transcript held back for low confidence
+#[derive(Clone)]
+pub struct LowConfidenceHandle {
+    processor: Arc<tokio::sync::Mutex<InjectionProcessor>>,
+    held_tx: broadcast::Sender<HeldTranscript>,
+}
+
+impl LowConfidenceHandle {
+    /// Transcripts as they are held
+    pub fn subscribe(&self) -> broadcast::Receiver<HeldTranscript> {
+        self.held_tx.subscribe()
+    }
+
+    /// Type the held transcript; None when nothing is held
+    pub async fn accept(&self) -> Option<HeldTranscript> {
+        self.processor.lock().await.accept_held()
+    }
+
+    /// Drop the held transcript
+    pub async fn discard(&self) -> Option<HeldTranscript> {
+        self.processor.lock().await.discard_held()
+    }
+}
+
+/// Cloneable access to the dictation mode of a running processor
+#[derive(Clone)]
+pub struct DictationModeHandle {
+    processor: Arc<tokio::sync::Mutex<InjectionProcessor>>,
+}
+
+impl DictationModeHandle {
+    pub async fn get(&self) -> DictationMode {
+        self.processor.lock().await.dictation_mode()
+    }
+
+    pub async fn set(&self, mode: DictationMode) {
+        self.processor.lock().await.set_dictation_mode(mode);
+    }
+
+    /// Switch to the next mode and return it
+    pub async fn cycle(&self) -> DictationMode {
+        let mut processor = self.processor.lock().await;
+        let mode = processor.dictation_mode().next();
+        processor.set_dictation_mode(mode);
+        mode
+    }
+}
+
+/// Cloneable view of the text buffered by a running processor
+#[derive(Clone)]
+pub struct DraftHandle {
+    processor: Arc<tokio::sync::Mutex<InjectionProcessor>>,
+}
+
+impl DraftHandle {
+    /// Full text buffered but not yet injected (empty when nothing is held)
+    pub async fn text(&self) -> String {
+        self.processor.lock().await.session.buffer_preview()
+    }
+}
+
+/// Queue `text` for injection and update queue telemetry
+fn enqueue(
+    queue: &Mutex<InjectionQueue>,
+    text: String,
+    utterance_ids: Vec<u64>,
+    metrics: Option<&PipelineMetrics>,
+    events: &broadcast::Sender<InjectionEvent>,
+) {
+    let chars = text.chars().count();
+    let queued = InjectionEvent::Queued {
+        utterance_ids: utterance_ids.clone(),
+        chars,
+    };
+    let (outcome, depth) = {
+        let mut queue = queue.lock().unwrap();
+        let outcome = queue.push(text, utterance_ids, std::time::Instant::now());
+        (outcome, queue.len())
+    };
+    match outcome {
+        Enqueued::Queued => {
+            debug!(
+                "Queued {} characters for injection (depth {})",
+                chars, depth
+            );
+            let _ = events.send(queued);
+        }
+        Enqueued::Coalesced => {
+            debug!("Merged {} characters into the queued utterance", chars);
+            if let Some(m) = metrics {
+                m.injection_queue_coalesced.fetch_add(1, Ordering::Relaxed);
+            }
+            let _ = events.send(queued);
+        }
+        Enqueued::DroppedOldest(dropped) => {
+            warn!(
+                "Injection queue full; dropped the oldest utterance ({} characters)",
+                dropped.chars().count()
+            );
+            if let Some(m) = metrics {
+                m.injection_queue_dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            let _ = events.send(InjectionEvent::Failed {
+                reason: "injection queue full; oldest utterance dropped".to_string(),
+            });
+            let _ = events.send(queued);
+        }
+        Enqueued::DroppedNewest => {
+            warn!(
+                "Injection queue full; dropped a new utterance ({} characters)",
+                chars
+            );
+            if let Some(m) = metrics {
+                m.injection_queue_dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            let _ = events.send(InjectionEvent::Failed {
+                reason: "injection queue full; utterance dropped".to_string(),
+            });
+        }
+    }
+    if let Some(m) = metrics {
+        m.update_injection_queue_depth(depth);
+    }
+}
+
+/// Per-utterance latency breakdown once text is visible
+fn log_utterance_latency(delivered: &[UtteranceLatency]) {
+    for l in delivered {
+        debug!(
+            utterance_id = l.utterance_id,
+            stt_ms = l.stt.as_millis() as u64,
+            injection_ms = l.injection.as_millis() as u64,
+            end_to_end_ms = l.end_to_end.as_millis() as u64,
+            "Utterance latency"
+        );
+    }
+}
+
+/// Time for session services (AT-SPI registry, clipboard managers) to come
+/// up after the display server returns, before backends are re-detected
+const DISPLAY_RESTORE_SETTLE: Duration = Duration::from_secs(1);
+
+/// Inject queued utterances and send queued commands one at a time, oldest
+/// first, until `stop` fires
+#[allow(clippy::too_many_arguments)]
+async fn run_injection_worker(
+    mut injector: StrategyManager,
+    key_injector: crate::ydotool_injector::YdotoolInjector,
+    processor: Arc<tokio::sync::Mutex<InjectionProcessor>>,
+    queue: Arc<Mutex<InjectionQueue>>,
+    ready: Arc<Notify>,
+    config: InjectionConfig,
+    metrics: Option<Arc<PipelineMetrics>>,
+    guard_tx: broadcast::Sender<GuardEvent>,
+    settled_tx: broadcast::Sender<Vec<u64>>,
+    events_tx: broadcast::Sender<InjectionEvent>,
+    mut stop: oneshot::Receiver<()>,
+) {
+    let mut display = DisplayWatch::new(&config);
+    let mut rate = RateLimiter::new(&config);
+    loop {
+        // Hold queued text while the display server is gone; rebuild the
+        // backends once it is back
+        if display.poll() == Some(DisplayEvent::Restored) {
+            tokio::time::sleep(DISPLAY_RESTORE_SETTLE).await;
+            injector.reinitialize().await;
+        }
+        if display.is_down() {
+            tokio::select! {
+                _ = display.tick() => continue,
+                _ = &mut stop => break,
+            }
+        }
+
+        let (next, depth) = {
+            let mut queue = queue.lock().unwrap();
+            let next = queue.pop();
+            (next, queue.len())
+        };
+        let Some(next) = next else {
+            tokio::select! {
+                _ = ready.notified() => continue,
+                _ = display.tick() => continue,
+                _ = &mut stop => break,
+            }
+        };
+        if let Some(m) = metrics.as_deref() {
+            m.update_injection_queue_depth(depth);
+        }
+        let item = match next {
+            QueuedItem::Text(item) => item,
+            QueuedItem::Command(cmd) => {
+                processor.lock().await.forget_undoable();
+                match key_injector.send_key_chord(cmd.key_chord()).await {
+                    Ok(()) => info!("Sent command '{}'", cmd),
+                    Err(e) => error!("Failed to send command '{}': {}", cmd, e),
+                }
+                if !matches!(stop.try_recv(), Err(oneshot::error::TryRecvError::Empty)) {
+                    break;
+                }
+                continue;
+            }
+            QueuedItem::Undo => {
+                let chars = processor.lock().await.take_undoable_chars();
+                if chars == 0 {
+                    info!("Nothing to undo");
+                } else if let Err(e) = key_injector.erase_chars(chars).await {
+                    warn!("Undo failed: {}", e);
+                }
+                if !matches!(stop.try_recv(), Err(oneshot::error::TryRecvError::Empty)) {
+                    break;
+                }
+                continue;
+            }
+        };
+
+        let text = item.text;
+        if let Err(event) = rate.admit(&text, std::time::Instant::now()) {
+            warn!(utterance_ids = ?item.utterance_ids, "{}", event);
+            let _ = events_tx.send(InjectionEvent::Failed {
+                reason: event.to_string(),
+            });
+            let _ = guard_tx.send(event);
+            if let Some(m) = metrics.as_deref() {
+                m.traces.finish(&item.utterance_ids);
+            }
+            let _ = settled_tx.send(item.utterance_ids);
+            if !matches!(stop.try_recv(), Err(oneshot::error::TryRecvError::Empty)) {
+                break;
+            }
+            continue;
+        }
+        let mode = guard::paste_switch(&config, &text).map(|event| {
+            info!("{}", event);
+            let _ = guard_tx.send(event);
+            InjectionMode::Paste
+        });
+        info!(
+            utterance_ids = ?item.utterance_ids,
+            "Attempting injection of {} characters (queued {} ms)",
+            text.len(),
+            item.enqueued_at.elapsed().as_millis()
+        );
+        // Part of the first utterance's trace when tracing is on
+        let parent = metrics
+            .as_deref()
+            .and_then(|m| m.traces.parent(&item.utterance_ids));
+        let result = injector
+            .inject_as(&text, mode)
+            .instrument(info_span!(
+                parent: parent.as_ref().and_then(|p| p.id()),
+                "inject",
+                utterance_ids = ?item.utterance_ids
+            ))
+            .await;
+        if let Some(m) = metrics.as_deref() {
+            m.traces.finish(&item.utterance_ids);
+        }
+        processor
+            .lock()
+            .await
+            .record_injection_result(&text, result.is_ok());
+        match result {
+            Ok(()) => {
+                info!("Injection completed successfully");
+                if let Some(m) = metrics.as_deref() {
+                    log_utterance_latency(&m.latency.record_injected(&item.utterance_ids));
+                }
+            }
+            Err(e) => {
+                error!("Injection failed: {}", e);
+                let _ = events_tx.send(InjectionEvent::Failed {
+                    reason: e.to_string(),
+                });
+                crate::failure_notice::report_failed_injection(&config, &text, &e).await;
+            }
+        }
+        let _ = settled_tx.send(item.utterance_ids);
+
+        if !matches!(stop.try_recv(), Err(oneshot::error::TryRecvError::Empty)) {
+            break;
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use coldvox_stt::SourceId;
     use std::thread;
     use std::time::Duration;
 
@@ -381,7 +1269,7 @@ mod tests {
     async fn test_injection_processor_basic_flow() {
         let config = InjectionConfig::default();
 
-        let injection_metrics = Arc::new(Mutex::new(crate::types::InjectionMetrics::default()));
+        let injection_metrics = Arc::new(crate::types::InjectionMetrics::default());
         let mut processor = InjectionProcessor::new(config, None, injection_metrics).await;
 
         // Start with idle state
@@ -392,6 +1280,7 @@ mod tests {
             utterance_id: 1,
             text: "Hello world".to_string(),
             words: None,
+            source: SourceId::default(),
         });
 
         assert_eq!(processor.session_state(), SessionState::Buffering);
@@ -421,7 +1310,7 @@ mod tests {
     #[tokio::test]
     async fn test_metrics_update() {
         let config = InjectionConfig::default();
-        let injection_metrics = Arc::new(Mutex::new(crate::types::InjectionMetrics::default()));
+        let injection_metrics = Arc::new(crate::types::InjectionMetrics::default());
         let mut processor = InjectionProcessor::new(config, None, injection_metrics).await;
 
         // Add transcription
@@ -429,6 +1318,7 @@ mod tests {
             utterance_id: 1,
             text: "Test transcription".to_string(),
             words: None,
+            source: SourceId::default(),
         });
 
         let metrics = processor.metrics();
@@ -440,7 +1330,7 @@ mod tests {
     #[tokio::test]
     async fn test_partial_transcription_handling() {
         let config = InjectionConfig::default();
-        let injection_metrics = Arc::new(Mutex::new(crate::types::InjectionMetrics::default()));
+        let injection_metrics = Arc::new(crate::types::InjectionMetrics::default());
         let mut processor = InjectionProcessor::new(config, None, injection_metrics).await;
 
         // Start with idle state
@@ -452,6 +1342,7 @@ mod tests {
             text: "Hello".to_string(),
             t0: None,
             t1: None,
+            source: SourceId::default(),
         });
 
         // Should still be idle since partial events don't change session state
@@ -462,10 +1353,418 @@ mod tests {
             utterance_id: 1,
             text: "Hello world".to_string(),
             words: None,
+            source: SourceId::default(),
         });
 
         // Now should be buffering
         assert_eq!(processor.session_state(), SessionState::Buffering);
         assert_eq!(processor.session.buffer_len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_voice_command_is_not_buffered() {
+        let config = InjectionConfig {
+            voice_commands: true,
+            command_min_stable_partials: 2,
+            ..Default::default()
+        };
+        let injection_metrics = Arc::new(crate::types::InjectionMetrics::default());
+        let mut processor = InjectionProcessor::new(config, None, injection_metrics).await;
+
+        let partial = |text: &str| TranscriptionEvent::Partial {
+            utterance_id: 1,
+            text: text.to_string(),
+            t0: None,
+            t1: Some(0.4),
+            source: SourceId::default(),
+        };
+        assert_eq!(processor.handle_transcription(partial("undo")), None);
+        assert_eq!(
+            processor.handle_transcription(partial("Undo")),
+            Some(VoiceCommand::Undo)
+        );
+
+        // The matching final was already dispatched and must not be typed
+        let command = processor.handle_transcription(TranscriptionEvent::Final {
+            utterance_id: 1,
+            text: "Undo.".to_string(),
+            words: None,
+            source: SourceId::default(),
+        });
+        assert_eq!(command, None);
+        assert_eq!(processor.session_state(), SessionState::Idle);
+        assert_eq!(processor.session.buffer_len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_unconfirmed_fast_path_final_is_held() {
+        let config = InjectionConfig {
+            voice_commands: true,
+            command_min_stable_partials: 2,
+            ..Default::default()
+        };
+        let injection_metrics = Arc::new(crate::types::InjectionMetrics::default());
+        let mut processor = InjectionProcessor::new(config, None, injection_metrics).await;
+        let mut held_rx = processor.subscribe_held();
+
+        let partial = |text: &str| TranscriptionEvent::Partial {
+            utterance_id: 1,
+            text: text.to_string(),
+            t0: None,
+            t1: Some(0.4),
+            source: SourceId::default(),
+        };
+        processor.handle_transcription(partial("undo"));
+        assert_eq!(
+            processor.handle_transcription(partial("undo")),
+            Some(VoiceCommand::Undo)
+        );
+
+        // Typing the final after Ctrl+Z would land on top of the undo
+        let command = processor.handle_transcription(TranscriptionEvent::Final {
+            utterance_id: 1,
+            text: "undo the last paragraph".to_string(),
+            words: None,
+            source: SourceId::default(),
+        });
+        assert_eq!(command, None);
+        assert_eq!(processor.session.buffer_len(), 0);
+        let held = held_rx.try_recv().unwrap();
+        assert_eq!(held.text, "undo the last paragraph");
+        assert_eq!(
+            held.reason,
+            HoldReason::UnconfirmedCommand(VoiceCommand::Undo)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_command_releases_dictation_spoken_before_it() {
+        let config = InjectionConfig {
+            voice_commands: true,
+            ..Default::default()
+        };
+        let injection_metrics = Arc::new(crate::types::InjectionMetrics::default());
+        let mut processor = InjectionProcessor::new(config, None, injection_metrics).await;
+
+        let mut id = 0;
+        let mut say = |processor: &mut InjectionProcessor, text: &str| {
+            id += 1;
+            processor.handle_transcription(TranscriptionEvent::Final {
+                utterance_id: id,
+                text: text.to_string(),
+                words: None,
+                source: SourceId::default(),
+            })
+        };
+        assert_eq!(say(&mut processor, "Dear team,"), None);
+        // Still waiting for the silence timeout
+        assert_eq!(processor.prepare_injection(), None);
+        assert_eq!(say(&mut processor, "new line"), Some(VoiceCommand::Enter));
+        assert_eq!(
+            processor.take_before_command(),
+            Some(("Dear team,".to_string(), vec![1]))
+        );
+        assert_eq!(processor.take_before_command(), None);
+    }
+
+    #[tokio::test]
+    async fn test_buffered_dictation_holds_until_flush() {
+        let config = InjectionConfig {
+            voice_commands: true,
+            buffered_dictation: true,
+            ..Default::default()
+        };
+        let injection_metrics = Arc::new(crate::types::InjectionMetrics::default());
+        let mut processor = InjectionProcessor::new(config, None, injection_metrics).await;
+
+        let mut id = 0;
+        let mut say = |processor: &mut InjectionProcessor, text: &str| {
+            id += 1;
+            processor.handle_transcription(TranscriptionEvent::Final {
+                utterance_id: id,
+                text: text.to_string(),
+                words: None,
+                source: SourceId::default(),
+            })
+        };
+        assert_eq!(say(&mut processor, "Dear team."), None);
+        assert_eq!(say(&mut processor, "the build is broken"), None);
+        assert_eq!(processor.prepare_injection(), None);
+
+        // Edits apply to the draft instead of pressing keys
+        assert_eq!(say(&mut processor, "undo"), None);
+        assert_eq!(say(&mut processor, "the build is green now"), None);
+        assert_eq!(say(&mut processor, "delete last word"), None);
+        assert_eq!(processor.prepare_injection(), None);
+
+        assert_eq!(say(&mut processor, "send it"), None);
+        assert_eq!(
+            processor.prepare_injection().as_deref(),
+            Some("Dear team. the build is green")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dictation_modes_switch_by_voice() {
+        let config = InjectionConfig {
+            voice_commands: true,
+            buffered_dictation: true,
+            ..Default::default()
+        };
+        let injection_metrics = Arc::new(crate::types::InjectionMetrics::default());
+        let mut processor = InjectionProcessor::new(config, None, injection_metrics).await;
+
+        let mut id = 0;
+        let mut say = |processor: &mut InjectionProcessor, text: &str| {
+            id += 1;
+            processor.handle_transcription(TranscriptionEvent::Final {
+                utterance_id: id,
+                text: text.to_string(),
+                words: None,
+                source: SourceId::default(),
+            })
+        };
+        assert_eq!(say(&mut processor, "Spell mode."), None);
+        assert_eq!(processor.metrics().dictation_mode, DictationMode::Spell);
+        assert_eq!(say(&mut processor, "x-ray capital alpha seven"), None);
+        assert_eq!(say(&mut processor, "number mode"), None);
+        assert_eq!(say(&mut processor, "room four oh four"), None);
+
+        // Literal mode types commands instead of acting on them
+        assert_eq!(say(&mut processor, "literal mode"), None);
+        assert_eq!(say(&mut processor, "scratch that"), None);
+        assert_eq!(processor.dictation_mode(), DictationMode::Literal);
+
+        processor.flush_buffer();
+        assert_eq!(
+            processor.prepare_injection().as_deref(),
+            Some("xA7 room 4 oh 4 scratch that")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_non_injecting_source_is_ignored() {
+        let config = InjectionConfig::default();
+        let injection_metrics = Arc::new(crate::types::InjectionMetrics::default());
+        let mut processor = InjectionProcessor::new(config, None, injection_metrics).await;
+
+        processor.handle_transcription(TranscriptionEvent::Final {
+            utterance_id: 1,
+            text: "from the speakers".to_string(),
+            words: None,
+            source: SourceId::LOOPBACK,
+        });
+        assert_eq!(processor.session_state(), SessionState::Idle);
+        assert_eq!(processor.session.buffer_len(), 0);
+
+        processor.handle_transcription(TranscriptionEvent::Final {
+            utterance_id: 2,
+            text: "from the mic".to_string(),
+            words: None,
+            source: SourceId::MIC,
+        });
+        assert_eq!(processor.session.buffer_len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_low_confidence_final_is_held_until_accepted() {
+        let config = InjectionConfig {
+            min_confidence: Some(0.6),
+            ..Default::default()
+        };
+        let injection_metrics = Arc::new(crate::types::InjectionMetrics::default());
+        let mut processor = InjectionProcessor::new(config, None, injection_metrics).await;
+        let mut held_rx = processor.subscribe_held();
+
+        let final_with = |utterance_id: u64, text: &str, conf: f32| TranscriptionEvent::Final {
+            utterance_id,
+            text: text.to_string(),
+            words: Some(vec![coldvox_stt::WordInfo {
+                start: 0.0,
+                end: 0.5,
+                conf,
+                text: text.to_string(),
+            }]),
+            source: SourceId::default(),
+        };
+
+        processor.handle_transcription(final_with(1, "clear words", 0.9));
+        assert_eq!(processor.session.buffer_len(), 1);
+
+        processor.handle_transcription(final_with(2, "mumble", 0.3));
+        assert_eq!(processor.session.buffer_len(), 1);
+        let held = held_rx.try_recv().unwrap();
+        assert_eq!(held.utterance_id, 2);
+        assert_eq!(held.text, "mumble");
+
+        assert_eq!(processor.accept_held().map(|h| h.utterance_id), Some(2));
+        assert_eq!(processor.session.buffer_len(), 2);
+        assert_eq!(processor.accept_held(), None);
+
+        // Transcripts without word confidences are never held
+        processor.handle_transcription(TranscriptionEvent::Final {
+            utterance_id: 3,
+            text: "unscored".to_string(),
+            words: None,
+            source: SourceId::default(),
+        });
+        assert_eq!(processor.session.buffer_len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_sensitive_content_actions() {
+        let final_text = |utterance_id: u64, text: &str| TranscriptionEvent::Final {
+            utterance_id,
+            text: text.to_string(),
+            words: None,
+            source: SourceId::default(),
+        };
+        let dictated = "my card is 4111 1111 1111 1111 thanks";
+
+        let config = InjectionConfig {
+            sensitive_content: SensitiveContentAction::Redact,
+            ..Default::default()
+        };
+        let metrics = Arc::new(crate::types::InjectionMetrics::default());
+        let mut processor = InjectionProcessor::new(config, None, metrics).await;
+        processor.handle_transcription(final_text(1, dictated));
+        assert_eq!(
+            processor.session.take_buffer(),
+            "my card is [redacted card number] thanks"
+        );
+
+        let config = InjectionConfig {
+            sensitive_content: SensitiveContentAction::Confirm,
+            ..Default::default()
+        };
+        let metrics = Arc::new(crate::types::InjectionMetrics::default());
+        let mut processor = InjectionProcessor::new(config, None, metrics).await;
+        let mut held_rx = processor.subscribe_held();
+        processor.handle_transcription(final_text(2, dictated));
+        assert_eq!(processor.session.buffer_len(), 0);
+        let held = held_rx.try_recv().unwrap();
+        assert_eq!(
+            held.reason,
+            HoldReason::SensitiveContent(vec![SensitiveKind::CardNumber])
+        );
+        processor.handle_transcription(final_text(3, "nothing to see here"));
+        assert_eq!(processor.session.buffer_len(), 1);
+    }
+
+    #[test]
+    fn test_enqueue_reports_queued_and_dropped_text() {
+        let config = InjectionConfig {
+            queue_capacity: 1,
+            queue_coalesce_ms: 0,
+            queue_drop_policy: crate::types::QueueDropPolicy::DropNewest,
+            ..Default::default()
+        };
+        let queue = Mutex::new(InjectionQueue::new(&config));
+        let events = InjectionEvent::channel();
+        let mut rx = events.subscribe();
+
+        enqueue(&queue, "hello".to_string(), vec![1], None, &events);
+        enqueue(&queue, "world".to_string(), vec![2], None, &events);
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            InjectionEvent::Queued {
+                utterance_ids: vec![1],
+                chars: 5
+            }
+        );
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            InjectionEvent::Failed { .. }
+        ));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_enqueue_counts_characters_not_bytes() {
+        let config = InjectionConfig::default();
+        let queue = Mutex::new(InjectionQueue::new(&config));
+        let events = InjectionEvent::channel();
+        let mut rx = events.subscribe();
+
+        enqueue(&queue, "grüße".to_string(), vec![1], None, &events);
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            InjectionEvent::Queued {
+                utterance_ids: vec![1],
+                chars: 5
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_overlong_final_is_dropped() {
+        let config = InjectionConfig {
+            max_utterance_chars: 20,
+            ..Default::default()
+        };
+        let metrics = Arc::new(crate::types::InjectionMetrics::default());
+        let mut processor = InjectionProcessor::new(config, None, metrics).await;
+        let mut guard_rx = processor.guard_tx.subscribe();
+
+        for (utterance_id, text) in [(1, "thank you"), (2, &"thank you ".repeat(50)[..])] {
+            processor.handle_transcription(TranscriptionEvent::Final {
+                utterance_id,
+                text: text.to_string(),
+                words: None,
+                source: SourceId::default(),
+            });
+        }
+        assert_eq!(processor.session.take_buffer(), "thank you");
+        assert_eq!(
+            guard_rx.try_recv().unwrap(),
+            GuardEvent::UtteranceTooLong {
+                utterance_id: 2,
+                chars: 500,
+                limit: 20
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_undo_covers_only_injected_text() {
+        let config = InjectionConfig {
+            min_confidence: Some(0.6),
+            max_utterance_chars: 20,
+            ..Default::default()
+        };
+        let metrics = Arc::new(crate::types::InjectionMetrics::default());
+        let mut processor = InjectionProcessor::new(config, None, metrics).await;
+        let final_with = |utterance_id: u64, text: &str, conf: f32| TranscriptionEvent::Final {
+            utterance_id,
+            text: text.to_string(),
+            words: Some(vec![coldvox_stt::WordInfo {
+                start: 0.0,
+                end: 0.5,
+                conf,
+                text: text.to_string(),
+            }]),
+            source: SourceId::default(),
+        };
+
+        // Held for low confidence, then dropped as too long: never typed
+        processor.handle_transcription(final_with(1, "mumble", 0.3));
+        processor.handle_transcription(final_with(2, &"thank you ".repeat(5), 0.9));
+        assert_eq!(processor.take_undoable_chars(), 0);
+
+        processor.handle_transcription(final_with(3, "grüße", 0.9));
+        processor.session.force_inject();
+        let text = processor.prepare_injection().unwrap();
+        processor.record_injection_result(&text, true);
+        processor.handle_transcription(final_with(4, "mumble again", 0.3));
+        assert_eq!(processor.take_undoable_chars(), 5);
+        assert_eq!(processor.take_undoable_chars(), 0);
+
+        // A failed injection or a key chord after it leaves nothing to erase
+        processor.record_injection_result("typed", true);
+        processor.record_injection_result("half typed", false);
+        assert_eq!(processor.take_undoable_chars(), 0);
+        processor.record_injection_result("typed", true);
+        processor.forget_undoable();
+        assert_eq!(processor.take_undoable_chars(), 0);
+    }
 }
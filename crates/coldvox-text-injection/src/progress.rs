@@ -0,0 +1,254 @@
+//! Chunked clipboard paste: splitting, progress events and cancellation.
+//!
+//! Pasting multi-kilobyte text in one go can hang some applications, so text
+//! longer than `paste_chunk_chars` is pasted in pieces split at paragraph
+//! boundaries. Each chunk must be confirmed by the paste backend before the
+//! next one goes out; UIs follow along via [`PasteProgress`] events and can
+//! stop the paste between chunks through [`PasteControl::cancel`].
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Progress of a chunked paste
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasteProgress {
+    /// A chunked paste is starting
+    Started {
+        total_chunks: usize,
+        total_chars: usize,
+    },
+    /// Chunk `index` (1-based) was pasted and confirmed
+    ChunkPasted {
+        index: usize,
+        total_chunks: usize,
+        chars_done: usize,
+        total_chars: usize,
+    },
+    /// All chunks were pasted
+    Completed {
+        total_chunks: usize,
+        total_chars: usize,
+    },
+    /// Cancelled after `chunks_done` chunks
+    Cancelled {
+        chunks_done: usize,
+        total_chunks: usize,
+    },
+    /// Chunk `index` (1-based) failed; earlier chunks stay pasted
+    Failed {
+        index: usize,
+        total_chunks: usize,
+        error: String,
+    },
+}
+
+impl std::fmt::Display for PasteProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PasteProgress::Started {
+                total_chunks,
+                total_chars,
+            } => write!(
+                f,
+                "Pasting {} chars in {} chunks",
+                total_chars, total_chunks
+            ),
+            PasteProgress::ChunkPasted {
+                index,
+                total_chunks,
+                chars_done,
+                total_chars,
+            } => write!(
+                f,
+                "Pasted chunk {}/{} ({}/{} chars)",
+                index, total_chunks, chars_done, total_chars
+            ),
+            PasteProgress::Completed { total_chunks, .. } => {
+                write!(f, "Paste complete ({} chunks)", total_chunks)
+            }
+            PasteProgress::Cancelled {
+                chunks_done,
+                total_chunks,
+            } => write!(
+                f,
+                "Paste cancelled after {}/{} chunks",
+                chunks_done, total_chunks
+            ),
+            PasteProgress::Failed {
+                index,
+                total_chunks,
+                error,
+            } => write!(
+                f,
+                "Paste chunk {}/{} failed: {}",
+                index, total_chunks, error
+            ),
+        }
+    }
+}
+
+/// Shared handle for observing and cancelling chunked pastes
+#[derive(Debug, Clone)]
+pub struct PasteControl {
+    tx: broadcast::Sender<PasteProgress>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl Default for PasteControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PasteControl {
+    pub fn new() -> Self {
+        Self {
+            tx: broadcast::channel(32).0,
+            cancel: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Subscribe to progress events
+    pub fn subscribe(&self) -> broadcast::Receiver<PasteProgress> {
+        self.tx.subscribe()
+    }
+
+    /// Stop the chunked paste in progress before its next chunk. No effect
+    /// when no chunked paste is running.
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn emit(&self, progress: PasteProgress) {
+        let _ = self.tx.send(progress);
+    }
+
+    /// Clear a stale cancel request at the start of a paste
+    pub(crate) fn reset(&self) {
+        self.cancel.store(false, Ordering::SeqCst);
+    }
+
+    pub(crate) fn take_cancel(&self) -> bool {
+        self.cancel.swap(false, Ordering::SeqCst)
+    }
+}
+
+/// Split `text` into chunks of at most `max_chars` characters, preferring
+/// paragraph breaks, then line breaks, then whitespace. Chunks concatenate
+/// back to `text` exactly and never split a grapheme cluster (combining
+/// marks, emoji sequences, CRLF); a single cluster longer than `max_chars`
+/// becomes a chunk of its own.
+pub fn split_paste_chunks(text: &str, max_chars: usize) -> Vec<&str> {
+    let max_chars = max_chars.max(1);
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while rest.chars().count() > max_chars {
+        // Byte offset just past the first `max_chars` characters, backed up
+        // to the last grapheme boundary
+        let limit = rest
+            .char_indices()
+            .nth(max_chars)
+            .map_or(rest.len(), |(i, _)| i);
+        let limit = rest
+            .grapheme_indices(true)
+            .map(|(i, _)| i)
+            .take_while(|&i| i <= limit)
+            .last()
+            .filter(|&i| i > 0)
+            .unwrap_or_else(|| rest.graphemes(true).next().map_or(limit, str::len));
+        let window = &rest[..limit];
+        // A line feed always ends a grapheme cluster
+        let cut = ["\n\n", "\n"]
+            .iter()
+            .find_map(|sep| window.rfind(sep).map(|i| i + sep.len()))
+            .or_else(|| {
+                window
+                    .grapheme_indices(true)
+                    .rev()
+                    .find(|(_, g)| g.starts_with(char::is_whitespace))
+                    .map(|(i, g)| i + g.len())
+            })
+            .filter(|&i| i > 0)
+            .unwrap_or(limit);
+        chunks.push(&rest[..cut]);
+        rest = &rest[cut..];
+    }
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::test_utils::tricky_text;
+    use proptest::prelude::*;
+
+    #[test]
+    fn splits_at_paragraphs_and_round_trips() {
+        let text = "First paragraph here.\n\nSecond one.\n\nThird paragraph is longer.";
+        let chunks = split_paste_chunks(text, 40);
+        assert_eq!(chunks.concat(), text);
+        assert_eq!(chunks[0], "First paragraph here.\n\nSecond one.\n\n");
+        assert!(chunks.iter().all(|c| c.chars().count() <= 40));
+    }
+
+    #[test]
+    fn falls_back_to_words_then_hard_cut() {
+        let chunks = split_paste_chunks("alpha beta gamma", 8);
+        assert_eq!(chunks, vec!["alpha ", "beta ", "gamma"]);
+
+        let chunks = split_paste_chunks("ééééé", 2);
+        assert_eq!(chunks, vec!["éé", "éé", "é"]);
+        assert!(split_paste_chunks("", 10).is_empty());
+    }
+
+    #[test]
+    fn never_splits_grapheme_clusters() {
+        // Decomposed é, a family emoji and a flag; each must stay whole
+        let text = "e\u{301}e\u{301}e\u{301} 👩\u{200d}👩\u{200d}👧🇩🇪\r\nx";
+        let chunks = split_paste_chunks(text, 2);
+        assert_eq!(chunks.concat(), text);
+        assert_eq!(chunks[..3], ["e\u{301}", "e\u{301}", "e\u{301}"]);
+        assert!(chunks.contains(&"👩\u{200d}👩\u{200d}👧"));
+        assert!(chunks.contains(&"🇩🇪"));
+        assert!(chunks.contains(&"\r\n"));
+
+        // A space carrying a combining mark is cut after the mark
+        assert_eq!(
+            split_paste_chunks("ab \u{308}cd", 4),
+            vec!["ab \u{308}", "cd"]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn chunks_round_trip_without_splitting_graphemes(
+            text in tricky_text(),
+            max_chars in 1usize..24,
+        ) {
+            let chunks = split_paste_chunks(&text, max_chars);
+            prop_assert_eq!(chunks.concat(), text.clone());
+            prop_assert!(chunks.iter().all(|c| !c.is_empty()));
+            prop_assert!(chunks
+                .iter()
+                .all(|c| c.chars().count() <= max_chars || c.graphemes(true).count() == 1));
+            let rejoined: Vec<&str> = chunks.iter().flat_map(|c| c.graphemes(true)).collect();
+            prop_assert_eq!(rejoined, text.graphemes(true).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn cancel_is_consumed_once() {
+        let control = PasteControl::new();
+        control.cancel();
+        assert!(control.take_cancel());
+        assert!(!control.take_cancel());
+        control.cancel();
+        control.reset();
+        assert!(!control.take_cancel());
+    }
+}
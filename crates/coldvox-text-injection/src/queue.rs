@@ -0,0 +1,264 @@
+//! Bounded, ordered queue of utterances waiting for injection.
+//!
+//! The session hands over finished utterances here; a single worker injects
+//! them front to back, so text always lands in the order it was spoken even
+//! when the user talks faster than injection completes. Utterances arriving
+//! within `queue_coalesce_ms` of the previous one are merged into it, and a
+//! full queue either pushes back on the transcription channel
+//! ([`QueueDropPolicy::Block`]) or drops an utterance.
+//!
+//! Key chords of spoken commands and Undo share the queue, so "new line"
+//! after a sentence presses Enter after that sentence is typed, not before,
+//! and Undo erases the text that was typed last.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::commands::VoiceCommand;
+use crate::types::{InjectionConfig, QueueDropPolicy};
+
+/// An utterance (or several merged ones) waiting for injection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedText {
+    pub text: String,
+    /// STT utterances the text came from, for latency correlation
+    pub utterance_ids: Vec<u64>,
+    /// When the first merged utterance was queued
+    pub enqueued_at: Instant,
+    /// When the last merged utterance was queued
+    last_at: Instant,
+}
+
+/// Next piece of work for the injection worker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueuedItem {
+    Text(QueuedText),
+    /// Key chord of a spoken command
+    Command(VoiceCommand),
+    /// Erase the text of the last successful injection
+    Undo,
+}
+
+/// What happened to an utterance passed to [`InjectionQueue::push`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Enqueued {
+    /// Added at the back of the queue
+    Queued,
+    /// Merged into the utterance at the back of the queue
+    Coalesced,
+    /// Added after dropping the oldest queued utterance (returned)
+    DroppedOldest(String),
+    /// Not added; the queue was full
+    DroppedNewest,
+}
+
+/// Bounded FIFO of utterances
+#[derive(Debug)]
+pub struct InjectionQueue {
+    items: VecDeque<QueuedItem>,
+    capacity: usize,
+    coalesce: Duration,
+    policy: QueueDropPolicy,
+}
+
+impl InjectionQueue {
+    pub fn new(config: &InjectionConfig) -> Self {
+        Self {
+            items: VecDeque::new(),
+            capacity: config.queue_capacity.max(1),
+            coalesce: Duration::from_millis(config.queue_coalesce_ms),
+            policy: config.queue_drop_policy,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Whether new utterances should wait (only under [`QueueDropPolicy::Block`])
+    pub fn is_blocking(&self) -> bool {
+        self.policy == QueueDropPolicy::Block && self.items.len() >= self.capacity
+    }
+
+    /// Queue `text`, merging it into the previous utterance or applying the
+    /// drop policy as configured. Under [`QueueDropPolicy::Block`] callers
+    /// should hold back while [`Self::is_blocking`]; a push anyway is queued
+    /// over capacity rather than lost.
+    pub fn push(&mut self, text: String, utterance_ids: Vec<u64>, now: Instant) -> Enqueued {
+        if !self.coalesce.is_zero() {
+            if let Some(QueuedItem::Text(back)) = self.items.back_mut() {
+                if now.saturating_duration_since(back.last_at) < self.coalesce {
+                    back.text.push(' ');
+                    back.text.push_str(&text);
+                    back.utterance_ids.extend(utterance_ids);
+                    back.last_at = now;
+                    return Enqueued::Coalesced;
+                }
+            }
+        }
+
+        let item = QueuedItem::Text(QueuedText {
+            text,
+            utterance_ids,
+            enqueued_at: now,
+            last_at: now,
+        });
+        if self.items.len() < self.capacity {
+            self.items.push_back(item);
+            return Enqueued::Queued;
+        }
+        match self.policy {
+            QueueDropPolicy::Block => {
+                self.items.push_back(item);
+                Enqueued::Queued
+            }
+            QueueDropPolicy::DropOldest => {
+                let oldest = self
+                    .items
+                    .iter()
+                    .position(|i| matches!(i, QueuedItem::Text(_)));
+                let dropped = match oldest.and_then(|at| self.items.remove(at)) {
+                    Some(QueuedItem::Text(dropped)) => dropped.text,
+                    _ => String::new(),
+                };
+                self.items.push_back(item);
+                Enqueued::DroppedOldest(dropped)
+            }
+            QueueDropPolicy::DropNewest => Enqueued::DroppedNewest,
+        }
+    }
+
+    /// Queue a command's key chord behind the text already queued. Commands
+    /// are never merged or dropped.
+    pub fn push_command(&mut self, cmd: VoiceCommand) {
+        self.items.push_back(QueuedItem::Command(cmd));
+    }
+
+    /// Queue an undo of the last injection, behind anything still waiting
+    /// to be typed
+    pub fn push_undo(&mut self) {
+        self.items.push_back(QueuedItem::Undo);
+    }
+
+    /// Next utterance, command or undo
+    pub fn pop(&mut self) -> Option<QueuedItem> {
+        self.items.pop_front()
+    }
+
+    /// Remove everything still waiting
+    pub fn drain(&mut self) -> Vec<QueuedItem> {
+        self.items.drain(..).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pop_text(q: &mut InjectionQueue) -> Option<QueuedText> {
+        match q.pop()? {
+            QueuedItem::Text(item) => Some(item),
+            other => panic!("expected text, got {:?}", other),
+        }
+    }
+
+    fn queue(capacity: usize, coalesce_ms: u64, policy: QueueDropPolicy) -> InjectionQueue {
+        InjectionQueue::new(&InjectionConfig {
+            queue_capacity: capacity,
+            queue_coalesce_ms: coalesce_ms,
+            queue_drop_policy: policy,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn delivers_in_order() {
+        let mut q = queue(4, 0, QueueDropPolicy::Block);
+        let now = Instant::now();
+        for text in ["one", "two", "three"] {
+            assert_eq!(q.push(text.to_string(), vec![], now), Enqueued::Queued);
+        }
+        let order: Vec<_> = std::iter::from_fn(|| pop_text(&mut q))
+            .map(|i| i.text)
+            .collect();
+        assert_eq!(order, ["one", "two", "three"]);
+    }
+
+    #[test]
+    fn coalesces_close_utterances() {
+        let mut q = queue(4, 300, QueueDropPolicy::Block);
+        let t0 = Instant::now();
+        q.push("hello".to_string(), vec![1], t0);
+        assert_eq!(
+            q.push(
+                "world".to_string(),
+                vec![2],
+                t0 + Duration::from_millis(200)
+            ),
+            Enqueued::Coalesced
+        );
+        assert_eq!(
+            q.push("again".to_string(), vec![], t0 + Duration::from_millis(600)),
+            Enqueued::Queued
+        );
+        let merged = pop_text(&mut q).unwrap();
+        assert_eq!(merged.text, "hello world");
+        assert_eq!(merged.utterance_ids, [1, 2]);
+        assert_eq!(pop_text(&mut q).unwrap().text, "again");
+    }
+
+    #[test]
+    fn applies_drop_policy_when_full() {
+        let now = Instant::now();
+
+        let mut q = queue(2, 0, QueueDropPolicy::DropOldest);
+        q.push("a".to_string(), vec![], now);
+        q.push("b".to_string(), vec![], now);
+        assert_eq!(
+            q.push("c".to_string(), vec![], now),
+            Enqueued::DroppedOldest("a".to_string())
+        );
+        assert_eq!(pop_text(&mut q).unwrap().text, "b");
+
+        let mut q = queue(1, 0, QueueDropPolicy::DropNewest);
+        q.push("a".to_string(), vec![], now);
+        assert_eq!(
+            q.push("b".to_string(), vec![], now),
+            Enqueued::DroppedNewest
+        );
+        assert_eq!(q.len(), 1);
+
+        let mut q = queue(1, 0, QueueDropPolicy::Block);
+        q.push("a".to_string(), vec![], now);
+        assert!(q.is_blocking());
+    }
+
+    #[test]
+    fn commands_keep_their_place_between_utterances() {
+        let mut q = queue(3, 300, QueueDropPolicy::DropOldest);
+        let now = Instant::now();
+        q.push("first line".to_string(), vec![1], now);
+        q.push_command(VoiceCommand::Enter);
+        // Not merged into the text ahead of the command
+        assert_eq!(
+            q.push("second line".to_string(), vec![2], now),
+            Enqueued::Queued
+        );
+        // A full queue drops text, never the command
+        assert_eq!(
+            q.push(
+                "third line".to_string(),
+                vec![3],
+                now + Duration::from_secs(1)
+            ),
+            Enqueued::DroppedOldest("first line".to_string())
+        );
+        assert_eq!(q.pop(), Some(QueuedItem::Command(VoiceCommand::Enter)));
+        assert_eq!(pop_text(&mut q).unwrap().text, "second line");
+        assert_eq!(pop_text(&mut q).unwrap().text, "third line");
+    }
+}
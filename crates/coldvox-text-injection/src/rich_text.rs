@@ -0,0 +1,223 @@
+//! HTML for clipboard pastes of structured dictation.
+//!
+//! Dictated text laid out as paragraphs or lists (`- item`, `* item`,
+//! `• item`, `1. item`) pastes as plain text, which editors that prefer HTML
+//! (word processors, mail composers, rich web editors) flatten. With
+//! `clipboard_html` the clipboard also offers a `text/html` rendering next to
+//! the plain text: those editors keep the structure, everything else still
+//! takes the plain text. `clipboard_html_apps` turns it on for matching
+//! targets only and `clipboard_plain_apps` opts targets out; both use the
+//! [allowlist syntax](crate::target_filter).
+//!
+//! Offering both types at once needs the native Wayland clipboard path; the
+//! wl-copy/xclip fallbacks paste plain text only.
+
+use crate::target_filter::{InjectionTarget, TargetField, TargetPatterns};
+use crate::types::InjectionConfig;
+
+/// MIME type of the HTML rendering
+pub const HTML_MIME: &str = "text/html";
+
+/// Browsers read clipboard HTML as Latin-1 unless told otherwise
+const HTML_PREFIX: &str = r#"<meta http-equiv="content-type" content="text/html; charset=utf-8">"#;
+
+/// Which targets are offered HTML
+#[derive(Debug, Default)]
+pub struct HtmlPolicy {
+    enabled: bool,
+    html_apps: TargetPatterns,
+    plain_apps: TargetPatterns,
+}
+
+impl HtmlPolicy {
+    pub fn new(config: &InjectionConfig) -> Self {
+        Self {
+            enabled: config.clipboard_html,
+            html_apps: TargetPatterns::new(&config.clipboard_html_apps, "clipboard_html_apps"),
+            plain_apps: TargetPatterns::new(&config.clipboard_plain_apps, "clipboard_plain_apps"),
+        }
+    }
+
+    pub fn offers_html(&self, target: &InjectionTarget) -> bool {
+        (self.enabled || self.html_apps.matches(target)) && !self.plain_apps.matches(target)
+    }
+
+    /// Whether a pattern needs `field`, so the manager looks it up
+    pub fn uses(&self, field: TargetField) -> bool {
+        self.html_apps.uses(field) || self.plain_apps.uses(field)
+    }
+}
+
+enum Block<'a> {
+    Paragraph(Vec<&'a str>),
+    /// `start` is None for a bullet list
+    List {
+        start: Option<u32>,
+        items: Vec<&'a str>,
+    },
+}
+
+/// A list item line: its number (None for bullets) and text
+fn list_item(line: &str) -> Option<(Option<u32>, &str)> {
+    let line = line.trim_start();
+    for marker in ["- ", "* ", "• "] {
+        if let Some(item) = line.strip_prefix(marker) {
+            return Some((None, item.trim()));
+        }
+    }
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if !(1..=3).contains(&digits) {
+        return None;
+    }
+    let rest = &line[digits..];
+    let item = rest
+        .strip_prefix(". ")
+        .or_else(|| rest.strip_prefix(") "))?;
+    Some((line[..digits].parse().ok(), item.trim()))
+}
+
+fn escape(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// HTML rendering of `text`, or None when it has no paragraphs or lists and
+/// plain text says it all
+pub fn to_html(text: &str) -> Option<String> {
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut paragraphs = 0;
+    let mut new_paragraph = true;
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            new_paragraph = true;
+            continue;
+        }
+        match (list_item(line), blocks.last_mut()) {
+            (Some((number, item)), Some(Block::List { start, items }))
+                if !new_paragraph && start.is_some() == number.is_some() =>
+            {
+                items.push(item);
+            }
+            (Some((number, item)), _) => blocks.push(Block::List {
+                start: number,
+                items: vec![item],
+            }),
+            (None, Some(Block::Paragraph(lines))) if !new_paragraph => lines.push(line.trim()),
+            (None, _) => {
+                paragraphs += 1;
+                blocks.push(Block::Paragraph(vec![line.trim()]));
+            }
+        }
+        new_paragraph = false;
+    }
+
+    let has_list = blocks.iter().any(|b| matches!(b, Block::List { .. }));
+    if !has_list && paragraphs < 2 {
+        return None;
+    }
+
+    let mut html = String::from(HTML_PREFIX);
+    for block in &blocks {
+        match block {
+            Block::Paragraph(lines) => {
+                html.push_str("<p>");
+                for (i, line) in lines.iter().enumerate() {
+                    if i > 0 {
+                        html.push_str("<br>");
+                    }
+                    escape(line, &mut html);
+                }
+                html.push_str("</p>");
+            }
+            Block::List { start, items } => {
+                let close = match start {
+                    None => {
+                        html.push_str("<ul>");
+                        "</ul>"
+                    }
+                    Some(1) => {
+                        html.push_str("<ol>");
+                        "</ol>"
+                    }
+                    Some(n) => {
+                        html.push_str(&format!("<ol start=\"{}\">", n));
+                        "</ol>"
+                    }
+                };
+                for item in items {
+                    html.push_str("<li>");
+                    escape(item, &mut html);
+                    html.push_str("</li>");
+                }
+                html.push_str(close);
+            }
+        }
+    }
+    Some(html)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(html: &str) -> &str {
+        html.strip_prefix(HTML_PREFIX).unwrap()
+    }
+
+    #[test]
+    fn renders_lists_and_paragraphs() {
+        let html =
+            to_html("Shopping:\n- milk\n- eggs & <ham>\n\nThen:\n3. call Bob\n4. done").unwrap();
+        assert_eq!(
+            body(&html),
+            "<p>Shopping:</p><ul><li>milk</li><li>eggs &amp; &lt;ham&gt;</li></ul>\
+             <p>Then:</p><ol start=\"3\"><li>call Bob</li><li>done</li></ol>"
+        );
+
+        let html = to_html("First paragraph\nsame one.\n\nSecond.").unwrap();
+        assert_eq!(
+            body(&html),
+            "<p>First paragraph<br>same one.</p><p>Second.</p>"
+        );
+    }
+
+    #[test]
+    fn plain_sentences_stay_plain() {
+        assert_eq!(to_html("Just one sentence - with a dash."), None);
+        assert_eq!(to_html("2024 was a year.\nA second line."), None);
+        assert_eq!(to_html(""), None);
+    }
+
+    #[test]
+    fn per_target_lists_override_the_default() {
+        let target = |app: &str| InjectionTarget {
+            app_id: app.to_string(),
+            ..Default::default()
+        };
+        let policy = HtmlPolicy::new(&InjectionConfig {
+            clipboard_html_apps: vec!["libreoffice".into()],
+            ..Default::default()
+        });
+        assert!(policy.offers_html(&target("libreoffice-writer")));
+        assert!(!policy.offers_html(&target("kate")));
+
+        let policy = HtmlPolicy::new(&InjectionConfig {
+            clipboard_html: true,
+            clipboard_plain_apps: vec!["title:Terminal".into()],
+            ..Default::default()
+        });
+        assert!(policy.offers_html(&target("kate")));
+        assert!(policy.uses(TargetField::WindowTitle));
+        assert!(!policy.offers_html(&InjectionTarget {
+            window_title: Some("Terminal - bash".into()),
+            ..target("konsole")
+        }));
+    }
+}
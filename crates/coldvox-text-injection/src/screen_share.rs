@@ -0,0 +1,148 @@
+//! Screen-share detection for guarding clipboard-based injection.
+//!
+//! Screen casts started through the xdg-desktop-portal ScreenCast interface
+//! show up in two places we can read without joining the cast ourselves:
+//!
+//! - GNOME: the portal backend opens a session on Mutter's ScreenCast service,
+//!   exported under `/org/gnome/Mutter/ScreenCast/Session/`.
+//! - Everywhere else: the portal hands the stream to the client as a PipeWire
+//!   `Video/Source` node. Cameras are `Video/Source` nodes too but carry a
+//!   `device.api`; cast streams don't.
+//!
+//! Results are cached briefly so back-to-back injections don't spawn a
+//! `busctl`/`pw-dump` pair each time.
+
+use crate::types::InjectionMethod;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+use tokio::time::timeout;
+use tracing::debug;
+
+/// How long a detection result is reused
+const CACHE_TTL: Duration = Duration::from_secs(2);
+/// Upper bound for each probe command
+const PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+
+const MUTTER_SESSION_PREFIX: &str = "/org/gnome/Mutter/ScreenCast/Session/";
+
+/// Cached screen-share detector
+#[derive(Debug, Default)]
+pub struct ScreenShareDetector {
+    cached: Mutex<Option<(Instant, bool)>>,
+}
+
+impl ScreenShareDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a screen cast appears to be running. Probe failures count as
+    /// not sharing.
+    pub async fn is_sharing(&self) -> bool {
+        if let Some((at, sharing)) = *self.cached.lock().unwrap() {
+            if at.elapsed() < CACHE_TTL {
+                return sharing;
+            }
+        }
+
+        let sharing = mutter_sessions().await > 0 || pipewire_cast_streams().await > 0;
+        debug!("Screen share detected: {}", sharing);
+        *self.cached.lock().unwrap() = Some((Instant::now(), sharing));
+        sharing
+    }
+}
+
+/// Methods that can show the text outside the target while sharing: the
+/// clipboard, primary selection and terminal clipboard (and their paste
+/// popups), and Enigo, which presses the paste shortcut before falling back
+/// to typing
+pub fn exposes_text(method: InjectionMethod) -> bool {
+    matches!(
+        method,
+        InjectionMethod::ClipboardPasteFallback
+            | InjectionMethod::PrimarySelection
+            | InjectionMethod::Osc52Clipboard
+            | InjectionMethod::EnigoText
+    )
+}
+
+/// Run a probe command, returning stdout on success
+async fn probe(program: &str, args: &[&str]) -> Option<String> {
+    let output = timeout(PROBE_TIMEOUT, Command::new(program).args(args).output())
+        .await
+        .ok()?
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+async fn mutter_sessions() -> usize {
+    probe(
+        "busctl",
+        &["--user", "tree", "--list", "org.gnome.Mutter.ScreenCast"],
+    )
+    .await
+    .map_or(0, |tree| count_mutter_sessions(&tree))
+}
+
+async fn pipewire_cast_streams() -> usize {
+    probe("pw-dump", &[])
+        .await
+        .map_or(0, |dump| count_cast_streams(&dump))
+}
+
+/// Count Mutter ScreenCast session objects in `busctl tree --list` output
+fn count_mutter_sessions(tree: &str) -> usize {
+    tree.lines()
+        .filter_map(|line| line.trim().strip_prefix(MUTTER_SESSION_PREFIX))
+        .filter(|rest| !rest.is_empty() && !rest.contains('/'))
+        .count()
+}
+
+/// Count screen-cast stream nodes in `pw-dump` JSON output
+fn count_cast_streams(dump: &str) -> usize {
+    let Ok(serde_json::Value::Array(objects)) = serde_json::from_str(dump) else {
+        return 0;
+    };
+    objects
+        .iter()
+        .filter(|obj| obj["type"] == "PipeWire:Interface:Node")
+        .filter(|obj| {
+            let props = &obj["info"]["props"];
+            props["media.class"] == "Video/Source" && props.get("device.api").is_none()
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_only_mutter_session_objects() {
+        let tree = "/\n/org\n/org/gnome/Mutter/ScreenCast\n\
+                    /org/gnome/Mutter/ScreenCast/Session\n\
+                    /org/gnome/Mutter/ScreenCast/Session/u3\n\
+                    /org/gnome/Mutter/ScreenCast/Stream/u4\n";
+        assert_eq!(count_mutter_sessions(tree), 1);
+        assert_eq!(count_mutter_sessions(""), 0);
+    }
+
+    #[test]
+    fn counts_cast_streams_but_not_cameras() {
+        let dump = r#"[
+            {"type": "PipeWire:Interface:Node",
+             "info": {"props": {"media.class": "Video/Source", "node.name": "xdph-streaming-0"}}},
+            {"type": "PipeWire:Interface:Node",
+             "info": {"props": {"media.class": "Video/Source", "device.api": "v4l2"}}},
+            {"type": "PipeWire:Interface:Node",
+             "info": {"props": {"media.class": "Audio/Source"}}},
+            {"type": "PipeWire:Interface:Client", "info": {"props": {}}}
+        ]"#;
+        assert_eq!(count_cast_streams(dump), 1);
+        assert_eq!(count_cast_streams("not json"), 0);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::types::InjectionMetrics;
+use coldvox_foundation::clock::{self, SharedClock};
 use std::time::{Duration, Instant};
 use tracing::{debug, warn};
 
@@ -44,6 +45,12 @@ pub struct SessionConfig {
     pub punctuation_marks: Vec<char>,
     /// Whether to normalize whitespace
     pub normalize_whitespace: bool,
+    /// Buffered dictation: hold text until an explicit flush instead of
+    /// injecting after silence or punctuation
+    pub buffered: bool,
+    /// Finals arriving within this window of the previous one are merged
+    /// into the same injection (default: 0, disabled)
+    pub final_merge_window_ms: u64,
 }
 
 impl Default for SessionConfig {
@@ -56,12 +63,13 @@ impl Default for SessionConfig {
             flush_on_punctuation: true,
             punctuation_marks: vec!['.', '!', '?', ';'],
             normalize_whitespace: true,
+            buffered: false,
+            final_merge_window_ms: 0,
         }
     }
 }
 
 /// Manages a single dictation session with buffering and silence detection
-#[derive(Debug)]
 pub struct InjectionSession {
     /// Current state in the session state machine
     state: SessionState,
@@ -85,17 +93,45 @@ pub struct InjectionSession {
     punctuation_marks: Vec<char>,
     /// Whether to normalize whitespace
     normalize_whitespace: bool,
+    /// Hold text until [`Self::force_inject`] (buffered dictation)
+    buffered: bool,
+    /// Window for merging rapid-fire finals into one injection
+    final_merge_window: Duration,
+    /// Injection was forced (manual trigger or overflow); skips the merge window
+    forced: bool,
     /// Reference to injection metrics for telemetry
-    metrics: std::sync::Arc<std::sync::Mutex<InjectionMetrics>>,
+    metrics: std::sync::Arc<InjectionMetrics>,
     /// Throttled timestamp for diagnostic logging to avoid log spam
     last_diagnostic_log: Option<Instant>,
+    /// Time source for silence and pause timeouts
+    clock: SharedClock,
+}
+
+impl std::fmt::Debug for InjectionSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InjectionSession")
+            .field("state", &self.state)
+            .field("buffer", &self.buffer)
+            .field("last_transcription", &self.last_transcription)
+            .field("silence_timeout", &self.silence_timeout)
+            .field("buffer_pause_timeout", &self.buffer_pause_timeout)
+            .field("max_buffer_size", &self.max_buffer_size)
+            .field("buffered", &self.buffered)
+            .finish_non_exhaustive()
+    }
 }
 
 impl InjectionSession {
     /// Create a new session with the given configuration
-    pub fn new(
+    pub fn new(config: SessionConfig, metrics: std::sync::Arc<InjectionMetrics>) -> Self {
+        Self::new_with_clock(config, metrics, clock::real_clock())
+    }
+
+    /// Create a session driven by the given clock (virtual time for replay/tests)
+    pub fn new_with_clock(
         config: SessionConfig,
-        metrics: std::sync::Arc<std::sync::Mutex<InjectionMetrics>>,
+        metrics: std::sync::Arc<InjectionMetrics>,
+        clock: SharedClock,
     ) -> Self {
         Self {
             state: SessionState::Idle,
@@ -109,8 +145,12 @@ impl InjectionSession {
             flush_on_punctuation: config.flush_on_punctuation,
             punctuation_marks: config.punctuation_marks,
             normalize_whitespace: config.normalize_whitespace,
+            buffered: config.buffered,
+            final_merge_window: Duration::from_millis(config.final_merge_window_ms),
+            forced: false,
             metrics,
             last_diagnostic_log: None,
+            clock,
         }
     }
 
@@ -132,11 +172,15 @@ impl InjectionSession {
         // Reset diagnostic throttling on new input
         self.last_diagnostic_log = None;
 
+        // A final close behind the previous one joins its pending injection
+        let merging = self.within_merge_window();
+
         // Record the number of characters being buffered
         self.record_buffered_chars(text.len() as u64);
 
         // Check if text ends with punctuation that should trigger flushing
         let ends_with_punctuation = self.flush_on_punctuation
+            && !self.buffered
             && !text.is_empty()
             && self
                 .punctuation_marks
@@ -144,13 +188,13 @@ impl InjectionSession {
 
         // Add to buffer
         self.buffer.push(text);
-        self.last_transcription = Some(Instant::now());
+        self.last_transcription = Some(self.clock.now());
 
         // Update state based on current state
         match self.state {
             SessionState::Idle => {
                 self.state = SessionState::Buffering;
-                self.buffering_start = Some(Instant::now());
+                self.buffering_start = Some(self.clock.now());
                 debug!("Session started - first transcription buffered");
             }
             SessionState::Buffering => {
@@ -162,20 +206,31 @@ impl InjectionSession {
             SessionState::WaitingForSilence => {
                 // New transcription resets the silence timer and transitions back to Buffering
                 self.state = SessionState::Buffering;
-                self.buffering_start = Some(Instant::now());
+                self.buffering_start = Some(self.clock.now());
                 debug!("Silence timer reset by new transcription");
             }
+            SessionState::ReadyToInject if self.buffered => {
+                // Flushed draft not picked up yet; the new text goes with it
+                debug!("Transcription joined a pending flush");
+            }
+            SessionState::ReadyToInject if merging => {
+                debug!(
+                    "Final merged into pending injection, {} items in session",
+                    self.buffer.len()
+                );
+            }
             SessionState::ReadyToInject => {
                 // This shouldn't happen in normal flow, but handle gracefully
                 warn!("Received transcription while ready to inject - resetting session");
                 self.state = SessionState::Buffering;
-                self.buffering_start = Some(Instant::now());
+                self.buffering_start = Some(self.clock.now());
             }
         }
 
         // Check if buffer is too large and force injection
         if self.total_chars() > self.max_buffer_size {
             self.state = SessionState::ReadyToInject;
+            self.forced = true;
             warn!("Buffer size limit reached, forcing injection");
             return;
         }
@@ -192,7 +247,9 @@ impl InjectionSession {
     pub fn check_for_silence_transition(&mut self) {
         if self.state == SessionState::Buffering {
             if let Some(_buffering_start) = self.buffering_start {
-                let time_since_last_transcription = self.last_transcription.map(|t| t.elapsed());
+                let time_since_last_transcription = self
+                    .last_transcription
+                    .map(|t| self.clock.now().saturating_duration_since(t));
 
                 // If we haven't received a transcription for buffer_pause_timeout,
                 // transition to WaitingForSilence
@@ -209,7 +266,7 @@ impl InjectionSession {
     /// Determine if we should emit a diagnostic log message (throttled to avoid noise)
     fn should_log_diagnostic(&mut self) -> bool {
         const DIAGNOSTIC_INTERVAL: Duration = Duration::from_millis(500);
-        let now = Instant::now();
+        let now = self.clock.now();
         if let Some(last) = self.last_diagnostic_log {
             if now.duration_since(last) < DIAGNOSTIC_INTERVAL {
                 return false;
@@ -219,8 +276,26 @@ impl InjectionSession {
         true
     }
 
+    /// Whether the last final is recent enough for the next one to merge
+    /// into the same injection
+    fn within_merge_window(&self) -> bool {
+        !self.final_merge_window.is_zero()
+            && !self.forced
+            && self
+                .time_since_last_transcription()
+                .is_some_and(|since| since < self.final_merge_window)
+    }
+
     /// Check if the session should inject based on silence timeout
     pub fn should_inject(&mut self) -> bool {
+        // Buffered dictation only injects on an explicit flush (or overflow)
+        if self.buffered && self.state != SessionState::ReadyToInject {
+            return false;
+        }
+        // Hold off while another final could still be merged in
+        if self.within_merge_window() {
+            return false;
+        }
         match self.state {
             SessionState::Buffering => {
                 // Check if we should transition to WaitingForSilence first
@@ -243,7 +318,8 @@ impl InjectionSession {
             }
             SessionState::WaitingForSilence => {
                 if let Some(last_time) = self.last_transcription {
-                    if last_time.elapsed() >= self.silence_timeout {
+                    if self.clock.now().saturating_duration_since(last_time) >= self.silence_timeout
+                    {
                         // Silence timeout reached, transition to ready to inject
                         self.state = SessionState::ReadyToInject;
                         self.last_diagnostic_log = None;
@@ -254,7 +330,7 @@ impl InjectionSession {
                         true
                     } else {
                         if self.should_log_diagnostic() {
-                            let elapsed = last_time.elapsed();
+                            let elapsed = self.clock.now().saturating_duration_since(last_time);
                             let remaining = self.silence_timeout.saturating_sub(elapsed);
                             debug!(
                                 state = "WAITING_FOR_SILENCE",
@@ -289,12 +365,13 @@ impl InjectionSession {
 
     /// Take the buffered text and reset the session to idle
     pub fn take_buffer(&mut self) -> String {
-        let text = self.buffer.join(&self.join_separator);
+        let text = self.joined();
         let size = text.len();
         self.buffer.clear();
         self.last_transcription = None;
         self.buffering_start = None;
         self.state = SessionState::Idle;
+        self.forced = false;
         self.last_diagnostic_log = None;
         debug!("Session buffer cleared, {} chars taken", text.len());
 
@@ -321,7 +398,8 @@ impl InjectionSession {
 
     /// Get time since last transcription (None if no transcriptions)
     pub fn time_since_last_transcription(&self) -> Option<Duration> {
-        self.last_transcription.map(|t| t.elapsed())
+        self.last_transcription
+            .map(|t| self.clock.now().saturating_duration_since(t))
     }
 
     /// Check if session has any buffered content
@@ -333,38 +411,84 @@ impl InjectionSession {
     pub fn force_inject(&mut self) {
         if self.has_content() {
             self.state = SessionState::ReadyToInject;
+            self.forced = true;
             self.last_diagnostic_log = None;
             debug!("Session forced to inject state");
         }
     }
 
+    /// Whether text is held until an explicit flush
+    pub fn is_buffered(&self) -> bool {
+        self.buffered
+    }
+
+    /// Drop the most recent transcription from the buffer ("scratch that").
+    /// Returns the removed text.
+    pub fn scratch_last(&mut self) -> Option<String> {
+        let removed = self.buffer.pop();
+        if self.buffer.is_empty() {
+            self.clear();
+        }
+        removed
+    }
+
+    /// Delete the last word of the buffered text. Returns false when the
+    /// buffer is empty.
+    pub fn delete_last_word(&mut self) -> bool {
+        let Some(last) = self.buffer.last_mut() else {
+            return false;
+        };
+        match last.trim_end().rfind(char::is_whitespace) {
+            Some(i) => last.truncate(last[..i].trim_end().len()),
+            None => {
+                self.buffer.pop();
+            }
+        }
+        if self.buffer.is_empty() {
+            self.clear();
+        }
+        true
+    }
+
     /// Clear the session buffer and reset to idle (for cancellation)
     pub fn clear(&mut self) {
         self.buffer.clear();
         self.last_transcription = None;
         self.buffering_start = None;
         self.state = SessionState::Idle;
+        self.forced = false;
         self.last_diagnostic_log = None;
         debug!("Session cleared and reset to idle");
     }
 
     /// Get buffer preview without taking the buffer (for debugging/UI)
     pub fn buffer_preview(&self) -> String {
-        self.buffer.join(&self.join_separator)
+        self.joined()
+    }
+
+    /// Join the buffered finals with the separator, except before pieces
+    /// that open with closing punctuation (a split final like "Hello" + ",
+    /// world" becomes "Hello, world")
+    fn joined(&self) -> String {
+        let mut text = String::with_capacity(self.total_chars());
+        for (i, piece) in self.buffer.iter().enumerate() {
+            let attaches = piece.starts_with([',', '.', '!', '?', ';', ':', ')', ']', '}']);
+            if i > 0 && !attaches {
+                text.push_str(&self.join_separator);
+            }
+            text.push_str(piece);
+        }
+        text
     }
 
     /// Record characters that have been buffered
     pub fn record_buffered_chars(&self, count: u64) {
-        if let Ok(mut metrics) = self.metrics.lock() {
-            metrics.record_buffered_chars(count);
-        }
+        self.metrics.record_buffered_chars(count);
     }
 
     /// Record a flush event
     pub fn record_flush(&self, size: u64) {
-        if let Ok(mut metrics) = self.metrics.lock() {
-            metrics.record_flush(size);
-        }
+        self.metrics.record_flush(size);
     }
 }
 
@@ -380,7 +504,7 @@ mod tests {
             buffer_pause_timeout_ms: 50, // Short pause timeout for testing
             ..Default::default()
         };
-        let metrics = std::sync::Arc::new(std::sync::Mutex::new(InjectionMetrics::default()));
+        let metrics = std::sync::Arc::new(InjectionMetrics::default());
         let mut session = InjectionSession::new(config, metrics);
 
         // Start with idle state
@@ -421,7 +545,7 @@ mod tests {
             max_buffer_size: 10, // Very small limit
             ..Default::default()
         };
-        let metrics = std::sync::Arc::new(std::sync::Mutex::new(InjectionMetrics::default()));
+        let metrics = std::sync::Arc::new(InjectionMetrics::default());
         let mut session = InjectionSession::new(config, metrics);
 
         // Add text that exceeds limit
@@ -431,7 +555,7 @@ mod tests {
 
     #[test]
     fn test_empty_transcription_filtering() {
-        let metrics = std::sync::Arc::new(std::sync::Mutex::new(InjectionMetrics::default()));
+        let metrics = std::sync::Arc::new(InjectionMetrics::default());
         let mut session = InjectionSession::new(SessionConfig::default(), metrics);
 
         session.add_transcription("".to_string());
@@ -449,7 +573,7 @@ mod tests {
             buffer_pause_timeout_ms: 50,
             ..Default::default()
         };
-        let metrics = std::sync::Arc::new(std::sync::Mutex::new(InjectionMetrics::default()));
+        let metrics = std::sync::Arc::new(InjectionMetrics::default());
         let mut session = InjectionSession::new(config, metrics);
 
         // Add transcription
@@ -470,4 +594,86 @@ mod tests {
         session.check_for_silence_transition();
         assert_eq!(session.state(), SessionState::WaitingForSilence);
     }
+
+    #[test]
+    fn test_buffered_session_waits_for_flush() {
+        let config = SessionConfig {
+            buffered: true,
+            ..Default::default()
+        };
+        let metrics = std::sync::Arc::new(InjectionMetrics::default());
+        let clock = std::sync::Arc::new(coldvox_foundation::clock::TestClock::new());
+        let mut session = InjectionSession::new_with_clock(config, metrics, clock.clone());
+
+        session.add_transcription("First sentence.".to_string());
+        session.add_transcription("second one is wrong".to_string());
+        clock.advance(Duration::from_secs(5));
+        assert!(!session.should_inject());
+
+        assert_eq!(
+            session.scratch_last().as_deref(),
+            Some("second one is wrong")
+        );
+        session.add_transcription("Second try here".to_string());
+        assert!(session.delete_last_word());
+        assert!(!session.should_inject());
+
+        session.force_inject();
+        assert!(session.should_inject());
+        assert_eq!(session.take_buffer(), "First sentence. Second try");
+    }
+
+    #[test]
+    fn test_virtual_clock_drives_timeouts() {
+        let config = SessionConfig {
+            silence_timeout_ms: 200,
+            buffer_pause_timeout_ms: 50,
+            ..Default::default()
+        };
+        let metrics = std::sync::Arc::new(InjectionMetrics::default());
+        let clock = coldvox_foundation::clock::TestClock::new();
+        let clock = std::sync::Arc::new(clock);
+        let mut session = InjectionSession::new_with_clock(config, metrics, clock.clone());
+
+        session.add_transcription("Test".to_string());
+        clock.advance(Duration::from_millis(60));
+        assert!(!session.should_inject());
+        assert_eq!(session.state(), SessionState::WaitingForSilence);
+
+        clock.advance(Duration::from_millis(150));
+        assert!(session.should_inject());
+        assert_eq!(session.take_buffer(), "Test");
+    }
+
+    #[test]
+    fn test_rapid_finals_merge_into_one_injection() {
+        let config = SessionConfig {
+            final_merge_window_ms: 300,
+            ..Default::default()
+        };
+        let metrics = std::sync::Arc::new(InjectionMetrics::default());
+        let clock = std::sync::Arc::new(coldvox_foundation::clock::TestClock::new());
+        let mut session = InjectionSession::new_with_clock(config, metrics, clock.clone());
+
+        // VAD chatter splits one sentence into three finals
+        session.add_transcription("Send the report".to_string());
+        clock.advance(Duration::from_millis(120));
+        assert!(!session.should_inject());
+        session.add_transcription(", please.".to_string());
+        clock.advance(Duration::from_millis(200));
+        assert!(!session.should_inject());
+        session.add_transcription("Thanks".to_string());
+        clock.advance(Duration::from_millis(299));
+        assert!(!session.should_inject());
+
+        clock.advance(Duration::from_millis(1));
+        assert!(session.should_inject());
+        assert_eq!(session.take_buffer(), "Send the report, please. Thanks");
+
+        // A manual trigger doesn't wait out the window
+        session.add_transcription("Now".to_string());
+        session.force_inject();
+        assert!(session.should_inject());
+        assert_eq!(session.take_buffer(), "Now");
+    }
 }
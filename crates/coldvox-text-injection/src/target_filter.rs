@@ -0,0 +1,237 @@
+//! Allow/block list matching for injection targets.
+//!
+//! Each `allowlist`/`blocklist` entry matches the application id unless it
+//! starts with a field prefix:
+//!
+//! - `title:` matches the active window title
+//! - `url:` matches the URL of the focused browser page, read from the
+//!   accessibility tree
+//! - `app:` is the explicit form of the default
+//!
+//! So `url:^https://online\.mybank\.example/` in the blocklist stops dictation
+//! into a banking site while the browser itself stays allowed. A non-empty
+//! allowlist requires the target to match one of its entries; any blocklist
+//! match then still blocks. When the title or URL cannot be determined,
+//! patterns on that field don't match.
+//!
+//! With feature `regex` the patterns are regular expressions, compiled once;
+//! invalid ones are logged and skipped. Without it they are substrings, with a
+//! leading `^` and trailing `$` ignored.
+
+/// What the user is about to dictate into
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InjectionTarget {
+    pub app_id: String,
+    /// Active window title, when it could be read
+    pub window_title: Option<String>,
+    /// Focused page URL, when the focus is inside a browser document
+    pub url: Option<String>,
+}
+
+/// The part of the target a pattern looks at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetField {
+    AppId,
+    WindowTitle,
+    Url,
+}
+
+impl TargetField {
+    /// Split a list entry into its field and pattern
+    fn parse(entry: &str) -> (Self, &str) {
+        for (prefix, field) in [
+            ("app:", Self::AppId),
+            ("title:", Self::WindowTitle),
+            ("url:", Self::Url),
+        ] {
+            if let Some(pattern) = entry.strip_prefix(prefix) {
+                return (field, pattern);
+            }
+        }
+        (Self::AppId, entry)
+    }
+
+    fn value<'a>(&self, target: &'a InjectionTarget) -> Option<&'a str> {
+        match self {
+            Self::AppId => Some(target.app_id.as_str()),
+            Self::WindowTitle => target.window_title.as_deref(),
+            Self::Url => target.url.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TargetPattern {
+    field: TargetField,
+    #[cfg(feature = "regex")]
+    regex: regex::Regex,
+    #[cfg(not(feature = "regex"))]
+    substring: String,
+}
+
+impl TargetPattern {
+    fn compile(entry: &str, list: &str) -> Option<Self> {
+        let (field, pattern) = TargetField::parse(entry);
+        #[cfg(feature = "regex")]
+        {
+            match regex::Regex::new(pattern) {
+                Ok(regex) => Some(Self { field, regex }),
+                Err(e) => {
+                    tracing::warn!(
+                        "Invalid {} regex pattern '{}': {}, skipping",
+                        list,
+                        entry,
+                        e
+                    );
+                    None
+                }
+            }
+        }
+        #[cfg(not(feature = "regex"))]
+        {
+            let _ = list;
+            let pattern = pattern.strip_prefix('^').unwrap_or(pattern);
+            let pattern = pattern.strip_suffix('$').unwrap_or(pattern);
+            Some(Self {
+                field,
+                substring: pattern.to_string(),
+            })
+        }
+    }
+
+    fn matches(&self, target: &InjectionTarget) -> bool {
+        let Some(value) = self.field.value(target) else {
+            return false;
+        };
+        #[cfg(feature = "regex")]
+        return self.regex.is_match(value);
+        #[cfg(not(feature = "regex"))]
+        return value.contains(&self.substring);
+    }
+}
+
+/// A compiled list of target patterns, e.g. `clipboard_html_apps`
+#[derive(Debug, Default)]
+pub struct TargetPatterns(Vec<TargetPattern>);
+
+impl TargetPatterns {
+    /// `list` names the setting in warnings about invalid patterns
+    pub fn new(entries: &[String], list: &str) -> Self {
+        Self(
+            entries
+                .iter()
+                .filter_map(|entry| TargetPattern::compile(entry, list))
+                .collect(),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether any pattern needs `field`
+    pub fn uses(&self, field: TargetField) -> bool {
+        self.0.iter().any(|p| p.field == field)
+    }
+
+    pub fn matches(&self, target: &InjectionTarget) -> bool {
+        self.0.iter().any(|p| p.matches(target))
+    }
+}
+
+/// Compiled allow and block lists
+#[derive(Debug, Default)]
+pub struct TargetFilter {
+    allow: TargetPatterns,
+    block: TargetPatterns,
+    /// An allowlist was configured, even if none of its entries compiled
+    allowlist_set: bool,
+}
+
+impl TargetFilter {
+    pub fn new(allowlist: &[String], blocklist: &[String]) -> Self {
+        Self {
+            allow: TargetPatterns::new(allowlist, "allowlist"),
+            block: TargetPatterns::new(blocklist, "blocklist"),
+            allowlist_set: !allowlist.is_empty(),
+        }
+    }
+
+    pub fn allowlist_len(&self) -> usize {
+        self.allow.len()
+    }
+
+    pub fn blocklist_len(&self) -> usize {
+        self.block.len()
+    }
+
+    /// Whether any pattern needs `field`, so callers can skip looking it up
+    pub fn uses(&self, field: TargetField) -> bool {
+        self.allow.uses(field) || self.block.uses(field)
+    }
+
+    pub fn allows(&self, target: &InjectionTarget) -> bool {
+        if self.allowlist_set && !self.allow.matches(target) {
+            return false;
+        }
+        !self.block.matches(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn target(app_id: &str, title: Option<&str>, url: Option<&str>) -> InjectionTarget {
+        InjectionTarget {
+            app_id: app_id.to_string(),
+            window_title: title.map(str::to_string),
+            url: url.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn url_blocklist_blocks_a_site_but_not_the_browser() {
+        let filter = TargetFilter::new(&[], &list(&["url:^https://bank.example/"]));
+        assert!(filter.uses(TargetField::Url));
+        assert!(!filter.uses(TargetField::WindowTitle));
+
+        assert!(!filter.allows(&target(
+            "firefox",
+            None,
+            Some("https://bank.example/transfer")
+        )));
+        assert!(filter.allows(&target("firefox", None, Some("https://news.example/"))));
+        // No URL (not in a browser document): the pattern can't match
+        assert!(filter.allows(&target("firefox", None, None)));
+    }
+
+    #[test]
+    fn allowlist_matches_any_field_and_blocklist_still_applies() {
+        let filter = TargetFilter::new(
+            &list(&["code", "title:Meeting notes"]),
+            &list(&["title:Private"]),
+        );
+        assert!(filter.allows(&target("code", Some("main.rs"), None)));
+        assert!(filter.allows(&target("gedit", Some("Meeting notes.txt"), None)));
+        assert!(!filter.allows(&target("gedit", Some("todo.txt"), None)));
+        assert!(!filter.allows(&target("code", Some("Private - diary.md"), None)));
+    }
+
+    #[test]
+    fn unprefixed_and_app_prefixed_entries_match_the_app_id() {
+        let filter = TargetFilter::new(&[], &list(&["^slack$", "app:discord"]));
+        assert!(!filter.allows(&target("slack", None, None)));
+        assert!(!filter.allows(&target("discord", None, None)));
+        assert!(filter.allows(&target("kate", None, None)));
+        assert!(!filter.uses(TargetField::Url));
+    }
+}
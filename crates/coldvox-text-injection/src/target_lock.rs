@@ -0,0 +1,185 @@
+//! Lock injection to one window.
+//!
+//! Text normally goes to whatever has focus when a transcript arrives, so a
+//! glance at another window mid-dictation sends it to the wrong app. Locking
+//! captures the active window through [`window_manager`](crate::window_manager).
+//! Until the lock is released every injection first brings that window back
+//! to the front (kdotool on KDE, `xdotool windowactivate` on X11) and is
+//! refused if that fails, instead of typing into whatever has focus. The
+//! injection backends then act on the focused element of the locked window.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use coldvox_foundation::error::InjectionError;
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use crate::types::InjectionConfig;
+use crate::window_manager::{self, WindowRef};
+
+/// Shared handle for locking injection to a window
+#[derive(Debug, Clone)]
+pub struct TargetLock {
+    locked: Arc<Mutex<Option<WindowRef>>>,
+    tx: broadcast::Sender<Option<WindowRef>>,
+}
+
+impl Default for TargetLock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TargetLock {
+    pub fn new() -> Self {
+        Self {
+            locked: Arc::new(Mutex::new(None)),
+            tx: broadcast::channel(8).0,
+        }
+    }
+
+    /// Lock changes: the new target, or None when released
+    pub fn subscribe(&self) -> broadcast::Receiver<Option<WindowRef>> {
+        self.tx.subscribe()
+    }
+
+    /// The window injection is locked to
+    pub fn current(&self) -> Option<WindowRef> {
+        self.locked.lock().ok().and_then(|locked| locked.clone())
+    }
+
+    /// Lock to `window`, replacing any previous lock
+    pub fn lock(&self, window: WindowRef) {
+        info!(
+            "Injection locked to '{}' ({})",
+            window.class,
+            window
+                .kwin_id
+                .as_deref()
+                .or(window.x11_id.as_deref())
+                .unwrap_or("no window id")
+        );
+        if let Ok(mut locked) = self.locked.lock() {
+            *locked = Some(window.clone());
+        }
+        let _ = self.tx.send(Some(window));
+    }
+
+    /// Lock to the window that is active now
+    pub async fn lock_active_window(&self) -> Result<WindowRef, InjectionError> {
+        let window = tokio::task::spawn_blocking(window_manager::capture_active_window)
+            .await
+            .map_err(|e| InjectionError::Other(format!("Window capture task failed: {}", e)))??;
+        self.lock(window.clone());
+        Ok(window)
+    }
+
+    /// Release the lock, returning the window it was on
+    pub fn release(&self) -> Option<WindowRef> {
+        let previous = self.locked.lock().ok().and_then(|mut locked| locked.take());
+        if let Some(window) = &previous {
+            info!("Injection lock on '{}' released", window.class);
+            let _ = self.tx.send(None);
+        }
+        previous
+    }
+
+    /// Release a held lock, or lock to the active window. Returns the new target.
+    pub async fn toggle(&self) -> Result<Option<WindowRef>, InjectionError> {
+        if self.release().is_some() {
+            return Ok(None);
+        }
+        self.lock_active_window().await.map(Some)
+    }
+}
+
+/// Bring `window` to the front before injecting, or fail
+pub(crate) async fn bring_to_front(
+    window: &WindowRef,
+    config: &InjectionConfig,
+) -> Result<(), InjectionError> {
+    if is_active(window).await {
+        return Ok(());
+    }
+    debug!("Re-activating locked window '{}'", window.class);
+
+    #[cfg(feature = "kdotool")]
+    if let Some(id) = &window.kwin_id {
+        let kdotool = crate::kdotool_injector::KdotoolInjector::new(config.clone());
+        if let Err(e) = kdotool.ensure_focus(Some(id)).await {
+            debug!("kdotool could not activate locked window: {}", e);
+        }
+    }
+
+    #[cfg(feature = "xdotool")]
+    if let Some(id) = &window.x11_id {
+        let timeout_ms = config.per_method_timeout_ms;
+        let activate = tokio::process::Command::new("xdotool")
+            .args(["windowactivate", "--sync", id])
+            .output();
+        match tokio::time::timeout(Duration::from_millis(timeout_ms), activate).await {
+            Ok(Ok(output)) if output.status.success() => {}
+            Ok(Ok(output)) => debug!(
+                "xdotool windowactivate failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Ok(Err(e)) => debug!("xdotool windowactivate failed: {}", e),
+            Err(_) => debug!("xdotool windowactivate timed out after {}ms", timeout_ms),
+        }
+    }
+
+    // Give the compositor a moment to move the focus
+    tokio::time::sleep(Duration::from_millis(
+        config.focus_cache_duration_ms.min(100),
+    ))
+    .await;
+    if is_active(window).await {
+        return Ok(());
+    }
+    warn!(
+        "Locked window '{}' could not be activated; not injecting",
+        window.class
+    );
+    Err(InjectionError::LockedTargetUnavailable)
+}
+
+async fn is_active(window: &WindowRef) -> bool {
+    tokio::task::spawn_blocking(window_manager::capture_active_window)
+        .await
+        .ok()
+        .and_then(Result::ok)
+        .is_some_and(|active| window.is_same_window(&active))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(class: &str) -> WindowRef {
+        WindowRef {
+            class: class.to_string(),
+            title: String::new(),
+            kwin_id: None,
+            x11_id: Some("0x1".to_string()),
+        }
+    }
+
+    #[test]
+    fn lock_and_release_are_published() {
+        let lock = TargetLock::new();
+        let mut rx = lock.subscribe();
+
+        lock.lock(window("kate"));
+        assert_eq!(lock.current().map(|w| w.class), Some("kate".to_string()));
+        assert_eq!(rx.try_recv().unwrap(), Some(window("kate")));
+
+        assert_eq!(lock.release(), Some(window("kate")));
+        assert_eq!(rx.try_recv().unwrap(), None);
+        assert!(lock.current().is_none());
+
+        // Releasing again is a no-op and publishes nothing
+        assert!(lock.release().is_none());
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -6,6 +6,13 @@
 //!
 //! To run these tests, use the following command:
 //! `cargo test -p coldvox-text-injection --features real-injection-tests`
+//!
+//! Each backend test runs against every test app that was built (GTK, Qt and
+//! winit). Set `COLDVOX_TEST_TARGETS=gtk,qt` to restrict the targets.
+//!
+//! Without a desktop session (e.g. in CI), set `COLDVOX_TEST_COMPOSITOR` to
+//! `sway`, `cage`, `weston` or `auto` to run each test inside a nested
+//! headless compositor; run with `--test-threads=1`, as tests take turns.
 
 #![cfg(feature = "real-injection-tests")]
 
@@ -27,7 +34,7 @@ use crate::ydotool_injector::YdotoolInjector;
 ))]
 use crate::TextInjector;
 
-use crate::tests::test_harness::{TestApp, TestAppManager, TestEnvironment};
+use crate::tests::test_harness::{CompositorFixture, TestApp, TestAppManager, TestEnvironment};
 
 #[cfg(any(
     feature = "atspi",
@@ -35,7 +42,7 @@ use crate::tests::test_harness::{TestApp, TestAppManager, TestEnvironment};
     feature = "wl_clipboard",
     feature = "ydotool"
 ))]
-use crate::tests::test_harness::verify_injection;
+use crate::tests::test_harness::{verify_injection, TestTarget};
 use std::time::Duration;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
@@ -60,6 +67,7 @@ async fn harness_self_test_launch_gtk_app() {
         .with(tracing_subscriber::fmt::layer().with_test_writer())
         .try_init();
 
+    let _compositor = CompositorFixture::from_env();
     let env = TestEnvironment::current();
     if !env.can_run_real_tests() {
         eprintln!("Skipping real injection test: no display server found.");
@@ -81,6 +89,31 @@ async fn harness_self_test_launch_gtk_app() {
     );
 }
 
+/// Checks that a requested headless compositor comes up with its socket
+/// exported, and that dropping it restores the session variables.
+#[tokio::test]
+
+async fn harness_self_test_compositor_fixture() {
+    let original = std::env::var_os("WAYLAND_DISPLAY");
+    let Some(compositor) = CompositorFixture::from_env() else {
+        println!("Skipping compositor self-test: COLDVOX_TEST_COMPOSITOR not set.");
+        return;
+    };
+
+    assert_eq!(
+        std::env::var("WAYLAND_DISPLAY").ok().as_deref(),
+        Some(compositor.wayland_display())
+    );
+    assert!(compositor
+        .runtime_dir()
+        .join(compositor.wayland_display())
+        .exists());
+    assert!(TestEnvironment::current().can_run_real_tests());
+
+    drop(compositor);
+    assert_eq!(std::env::var_os("WAYLAND_DISPLAY"), original);
+}
+
 /// Waits for the test application to be ready by polling for its output file.
 /// This is much faster than a fixed-duration sleep.
 async fn wait_for_app_ready(app: &TestApp) {
@@ -129,18 +162,13 @@ async fn run_atspi_test(test_text: &str) {
             .with(tracing_subscriber::fmt::layer().with_test_writer())
             .try_init();
 
+        let _compositor = CompositorFixture::from_env();
         let env = TestEnvironment::current();
         if !env.can_run_real_tests() {
             eprintln!("Skipping AT-SPI test: no display server found.");
             return;
         }
 
-        let app = TestAppManager::launch_gtk_app().expect("Failed to launch GTK app.");
-
-        // Allow time for the app to initialize and for the AT-SPI bus to register it.
-        tokio::time::sleep(Duration::from_millis(500)).await;
-        wait_for_app_ready(&app).await;
-
         let injector = AtspiInjector::new(Default::default());
         if !injector.is_available().await {
             println!(
@@ -149,21 +177,38 @@ async fn run_atspi_test(test_text: &str) {
             return;
         }
 
-        injector
-            .inject_text(test_text, None)
-            .await
-            .unwrap_or_else(|e| {
-                panic!("AT-SPI injection failed for text '{}': {:?}", test_text, e)
-            });
-
-        verify_injection(&app.output_file, test_text)
-            .await
-            .unwrap_or_else(|e| {
-                panic!(
-                    "Verification failed for AT-SPI with text '{}': {}",
-                    test_text, e
-                )
-            });
+        for target in TestTarget::available() {
+            if !target.supports_atspi() {
+                println!("Skipping AT-SPI test on {}: no accessibility tree.", target);
+                continue;
+            }
+
+            let app = TestAppManager::launch(target)
+                .unwrap_or_else(|e| panic!("Failed to launch {} app: {}", target, e));
+
+            // Allow time for the app to initialize and for the AT-SPI bus to register it.
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            wait_for_app_ready(&app).await;
+
+            injector
+                .inject_text(test_text, None)
+                .await
+                .unwrap_or_else(|e| {
+                    panic!(
+                        "AT-SPI injection into {} failed for text '{}': {:?}",
+                        target, test_text, e
+                    )
+                });
+
+            verify_injection(&app.output_file, test_text)
+                .await
+                .unwrap_or_else(|e| {
+                    panic!(
+                        "Verification failed for AT-SPI on {} with text '{}': {}",
+                        target, test_text, e
+                    )
+                });
+        }
     }
 }
 
@@ -217,6 +262,16 @@ async fn run_ydotool_test(test_text: &str) {
         .with(tracing_subscriber::fmt::layer().with_test_writer())
         .try_init();
 
+    let compositor = CompositorFixture::from_env();
+    if let Some(compositor) = &compositor {
+        if !compositor.kind().supports_uinput() {
+            println!(
+                "Skipping ydotool test: {} has no seat for uinput devices.",
+                compositor.kind()
+            );
+            return;
+        }
+    }
     let env = TestEnvironment::current();
     if !env.can_run_real_tests() {
         eprintln!("Skipping ydotool test: no display server found.");
@@ -244,24 +299,32 @@ async fn run_ydotool_test(test_text: &str) {
         "Clipboard content was not set correctly."
     );
 
-    let app = TestAppManager::launch_gtk_app().expect("Failed to launch GTK app.");
-    tokio::time::sleep(Duration::from_millis(500)).await;
-    wait_for_app_ready(&app).await;
-
-    // The inject_text for ydotool will trigger a paste (Ctrl+V).
-    injector
-        .inject_text(test_text, None)
-        .await
-        .unwrap_or_else(|e| panic!("ydotool injection failed for text '{}': {:?}", test_text, e));
-
-    verify_injection(&app.output_file, test_text)
-        .await
-        .unwrap_or_else(|e| {
-            panic!(
-                "Verification failed for ydotool with text '{}': {}",
-                test_text, e
-            )
-        });
+    for target in TestTarget::available() {
+        let app = TestAppManager::launch(target)
+            .unwrap_or_else(|e| panic!("Failed to launch {} app: {}", target, e));
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        wait_for_app_ready(&app).await;
+
+        // The inject_text for ydotool will trigger a paste (Ctrl+V).
+        injector
+            .inject_text(test_text, None)
+            .await
+            .unwrap_or_else(|e| {
+                panic!(
+                    "ydotool injection into {} failed for text '{}': {:?}",
+                    target, test_text, e
+                )
+            });
+
+        verify_injection(&app.output_file, test_text)
+            .await
+            .unwrap_or_else(|e| {
+                panic!(
+                    "Verification failed for ydotool on {} with text '{}': {}",
+                    target, test_text, e
+                )
+            });
+    }
 }
 
 #[tokio::test]
@@ -314,6 +377,7 @@ async fn run_clipboard_paste_test(test_text: &str) {
         .with(tracing_subscriber::fmt::layer().with_test_writer())
         .try_init();
 
+    let _compositor = CompositorFixture::from_env();
     let env = TestEnvironment::current();
     if !env.can_run_real_tests() {
         eprintln!("Skipping clipboard test: no display server found.");
@@ -331,26 +395,31 @@ async fn run_clipboard_paste_test(test_text: &str) {
             return;
         }
 
-        // Launch the app to paste into.
-        let app = TestAppManager::launch_gtk_app().expect("Failed to launch GTK app.");
-        tokio::time::sleep(Duration::from_millis(500)).await;
-        wait_for_app_ready(&app).await;
-
-        // Perform clipboard+paste using the combined injector (it will try AT-SPI first then ydotool).
-        clipboard_paste
-            .inject_text(test_text, None)
-            .await
-            .expect("Clipboard+paste injection failed.");
-
-        // Verify the result.
-        verify_injection(&app.output_file, test_text)
-            .await
-            .unwrap_or_else(|e| {
-                panic!(
-                    "Verification failed for clipboard paste with text '{}': {}",
-                    test_text, e
-                )
-            });
+        for target in TestTarget::available() {
+            // Launch the app to paste into.
+            let app = TestAppManager::launch(target)
+                .unwrap_or_else(|e| panic!("Failed to launch {} app: {}", target, e));
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            wait_for_app_ready(&app).await;
+
+            // Perform clipboard+paste using the combined injector (it will try AT-SPI first then ydotool).
+            clipboard_paste
+                .inject_text(test_text, None)
+                .await
+                .unwrap_or_else(|e| {
+                    panic!("Clipboard+paste injection into {} failed: {:?}", target, e)
+                });
+
+            // Verify the result.
+            verify_injection(&app.output_file, test_text)
+                .await
+                .unwrap_or_else(|e| {
+                    panic!(
+                        "Verification failed for clipboard paste on {} with text '{}': {}",
+                        target, test_text, e
+                    )
+                });
+        }
     }
 
     #[cfg(not(all(feature = "wl_clipboard", feature = "enigo")))]
@@ -392,6 +461,7 @@ async fn run_enigo_typing_test(test_text: &str) {
         .with(tracing_subscriber::fmt::layer().with_test_writer())
         .try_init();
 
+    let _compositor = CompositorFixture::from_env();
     let env = TestEnvironment::current();
     if !env.can_run_real_tests() {
         eprintln!("Skipping enigo typing test: no display server found.");
@@ -406,24 +476,32 @@ async fn run_enigo_typing_test(test_text: &str) {
             return;
         }
 
-        let app = TestAppManager::launch_gtk_app().expect("Failed to launch GTK app.");
-        tokio::time::sleep(Duration::from_millis(500)).await;
-        wait_for_app_ready(&app).await;
-
-        // Use the test-only helper to force typing instead of pasting.
-        injector
-            .type_text_directly(test_text)
-            .await
-            .unwrap_or_else(|e| panic!("Enigo typing failed for text '{}': {:?}", test_text, e));
-
-        verify_injection(&app.output_file, test_text)
-            .await
-            .unwrap_or_else(|e| {
-                panic!(
-                    "Verification failed for enigo typing with text '{}': {}",
-                    test_text, e
-                )
-            });
+        for target in TestTarget::available() {
+            let app = TestAppManager::launch(target)
+                .unwrap_or_else(|e| panic!("Failed to launch {} app: {}", target, e));
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            wait_for_app_ready(&app).await;
+
+            // Use the test-only helper to force typing instead of pasting.
+            injector
+                .type_text_directly(test_text)
+                .await
+                .unwrap_or_else(|e| {
+                    panic!(
+                        "Enigo typing into {} failed for text '{}': {:?}",
+                        target, test_text, e
+                    )
+                });
+
+            verify_injection(&app.output_file, test_text)
+                .await
+                .unwrap_or_else(|e| {
+                    panic!(
+                        "Verification failed for enigo typing on {} with text '{}': {}",
+                        target, test_text, e
+                    )
+                });
+        }
     }
 
     #[cfg(not(feature = "enigo"))]
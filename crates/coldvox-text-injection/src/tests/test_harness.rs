@@ -2,6 +2,7 @@ use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
+use std::sync::{Mutex, MutexGuard};
 use std::time::{Duration, Instant};
 
 /// Represents a running instance of a test application.
@@ -109,6 +110,95 @@ impl Drop for TestApp {
     }
 }
 
+/// A GUI application real injection tests can type into.
+///
+/// Each one mirrors its text field to `/tmp/coldvox_<name>_test_<pid>.txt`
+/// and creates that file, holding its PID, once it is ready for input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestTarget {
+    /// GTK 3 `GtkEntry` (GNOME and most X11-era apps)
+    Gtk,
+    /// Qt `QLineEdit` (KDE); native Wayland when `WAYLAND_DISPLAY` is set
+    Qt,
+    /// Toolkit-free winit window with its own text buffer, like Electron and
+    /// game-engine clients; it has no accessibility tree
+    Winit,
+}
+
+impl TestTarget {
+    pub const ALL: [TestTarget; 3] = [TestTarget::Gtk, TestTarget::Qt, TestTarget::Winit];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TestTarget::Gtk => "gtk",
+            TestTarget::Qt => "qt",
+            TestTarget::Winit => "winit",
+        }
+    }
+
+    /// Executable name in `OUT_DIR`, as built by `build.rs`
+    fn executable(&self) -> &'static str {
+        match self {
+            TestTarget::Gtk => "gtk_test_app",
+            TestTarget::Qt => "qt_test_app",
+            TestTarget::Winit => "winit-test-app",
+        }
+    }
+
+    /// Whether the app exposes its text field over AT-SPI
+    pub fn supports_atspi(&self) -> bool {
+        !matches!(self, TestTarget::Winit)
+    }
+
+    /// Targets named in `COLDVOX_TEST_TARGETS` (comma-separated, e.g.
+    /// `gtk,qt`), or all of them
+    pub fn selected() -> Vec<TestTarget> {
+        match env::var("COLDVOX_TEST_TARGETS") {
+            Ok(list) if !list.trim().is_empty() => list
+                .split(',')
+                .map(|name| name.trim().to_ascii_lowercase())
+                .filter(|name| !name.is_empty())
+                .filter_map(|name| {
+                    let target = Self::ALL.into_iter().find(|t| t.name() == name);
+                    if target.is_none() {
+                        eprintln!("Test harness: ignoring unknown test target '{}'", name);
+                    }
+                    target
+                })
+                .collect(),
+            _ => Self::ALL.to_vec(),
+        }
+    }
+
+    /// Selected targets whose app was built, reporting the ones that were not
+    pub fn available() -> Vec<TestTarget> {
+        Self::selected()
+            .into_iter()
+            .filter(|t| {
+                let built = t.executable_path().exists();
+                if !built {
+                    println!(
+                        "Test harness: {} test app not built; skipping it (see build.rs warnings)",
+                        t.name()
+                    );
+                }
+                built
+            })
+            .collect()
+    }
+
+    fn executable_path(&self) -> PathBuf {
+        let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set, build script did not run?");
+        Path::new(&out_dir).join(self.executable())
+    }
+}
+
+impl std::fmt::Display for TestTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 /// A manager responsible for launching test applications.
 ///
 /// This acts as a factory for creating `TestApp` instances.
@@ -119,15 +209,21 @@ impl TestAppManager {
     ///
     /// The application is expected to have been compiled by the `build.rs` script.
     pub fn launch_gtk_app() -> Result<TestApp, std::io::Error> {
-        let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set, build script did not run?");
-        let exe_path = Path::new(&out_dir).join("gtk_test_app");
+        Self::launch(TestTarget::Gtk)
+    }
+
+    /// Launches the test application for `target`.
+    ///
+    /// The application is expected to have been compiled by the `build.rs` script.
+    pub fn launch(target: TestTarget) -> Result<TestApp, std::io::Error> {
+        let exe_path = target.executable_path();
 
         if !exe_path.exists() {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::NotFound,
                 format!(
-                    "GTK test app executable not found at {:?}. Did build.rs fail to build it?",
-                    exe_path
+                    "{} test app executable not found at {:?}. Did build.rs fail to build it?",
+                    target, exe_path
                 ),
             ));
         }
@@ -138,16 +234,29 @@ impl TestAppManager {
         // Explicitly pass the DISPLAY variable to the test application.
         // This is crucial for ensuring it connects to the correct X server, especially in CI.
         if let Ok(display) = env::var("DISPLAY") {
-            println!("Test harness: Launching GTK app on DISPLAY={}", display);
+            println!(
+                "Test harness: Launching {} app on DISPLAY={}",
+                target, display
+            );
             command.env("DISPLAY", display);
         } else {
             println!("Test harness: DISPLAY environment variable not set.");
         }
+        // Qt defaults to XWayland; test it as a native Wayland client when
+        // there is a compositor (winit picks Wayland by itself)
+        if target == TestTarget::Qt {
+            let platform = if env::var("WAYLAND_DISPLAY").is_ok() {
+                "wayland"
+            } else {
+                "xcb"
+            };
+            command.env("QT_QPA_PLATFORM", platform);
+        }
 
         let process = command.spawn()?;
 
         let pid = process.id();
-        let output_file = PathBuf::from(format!("/tmp/coldvox_gtk_test_{}.txt", pid));
+        let output_file = PathBuf::from(format!("/tmp/coldvox_{}_test_{}.txt", target, pid));
 
         Ok(TestApp {
             process,
@@ -259,3 +368,306 @@ impl TestEnvironment {
         self.has_display
     }
 }
+
+/// Serializes compositor fixtures, which repoint process-wide env vars.
+static COMPOSITOR_LOCK: Mutex<()> = Mutex::new(());
+
+/// How long a nested compositor gets to create its Wayland socket.
+const COMPOSITOR_STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Headless Wayland compositors `CompositorFixture` can run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompositorKind {
+    Sway,
+    Cage,
+    Weston,
+}
+
+impl CompositorKind {
+    pub const ALL: [CompositorKind; 3] = [
+        CompositorKind::Sway,
+        CompositorKind::Cage,
+        CompositorKind::Weston,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CompositorKind::Sway => "sway",
+            CompositorKind::Cage => "cage",
+            CompositorKind::Weston => "weston",
+        }
+    }
+
+    /// Whether uinput devices (ydotool) reach the compositor's seat. Weston's
+    /// headless backend has no libinput, so only the wlroots ones qualify.
+    pub fn supports_uinput(&self) -> bool {
+        !matches!(self, CompositorKind::Weston)
+    }
+
+    pub fn is_installed(&self) -> bool {
+        crate::prerequisites::find_in_path(self.name()).is_some()
+    }
+
+    /// `XDG_CURRENT_DESKTOP` for clients, which portal and backend selection key off
+    fn desktop_name(&self) -> &'static str {
+        match self {
+            CompositorKind::Sway => "sway",
+            CompositorKind::Cage => "wlroots",
+            CompositorKind::Weston => "weston",
+        }
+    }
+
+    fn command(&self, runtime_dir: &Path, virtual_seat: bool) -> std::io::Result<Command> {
+        let mut command = Command::new(self.name());
+        match self {
+            CompositorKind::Sway => {
+                let config = runtime_dir.join("sway.conf");
+                fs::write(&config, "xwayland disable\ndefault_border none\n")?;
+                command.arg("--config").arg(config);
+            }
+            // cage exits with its client, so give it one that never does
+            CompositorKind::Cage => {
+                command.args(["--", "sleep", "infinity"]);
+            }
+            CompositorKind::Weston => {
+                command.args([
+                    "--backend=headless",
+                    "--socket=wayland-coldvox",
+                    "--idle-time=0",
+                    "--no-config",
+                ]);
+            }
+        }
+        if self.supports_uinput() {
+            // The libinput backend picks up ydotool's uinput device; without
+            // uinput access it would only fail to find a seat.
+            let backends = if virtual_seat {
+                "headless,libinput"
+            } else {
+                "headless"
+            };
+            command
+                .env("WLR_BACKENDS", backends)
+                .env("WLR_LIBINPUT_NO_DEVICES", "1")
+                .env("WLR_RENDERER", "pixman")
+                .env("WLR_HEADLESS_OUTPUTS", "1");
+            if virtual_seat && env::var_os("LIBSEAT_BACKEND").is_none() {
+                command.env("LIBSEAT_BACKEND", "noop");
+            }
+        }
+        Ok(command)
+    }
+}
+
+impl std::fmt::Display for CompositorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A nested headless Wayland compositor for real injection tests in CI.
+///
+/// Starting one gives it a private `XDG_RUNTIME_DIR` and points this
+/// process's `WAYLAND_DISPLAY`, `XDG_RUNTIME_DIR`, `XDG_SESSION_TYPE` and
+/// `XDG_CURRENT_DESKTOP` at it (unsetting `DISPLAY`), so test apps and
+/// injectors land inside it. Dropping it stops the compositor and restores
+/// the variables. Only one fixture runs at a time.
+pub struct CompositorFixture {
+    kind: CompositorKind,
+    process: Child,
+    wayland_display: String,
+    saved_env: Vec<(&'static str, Option<std::ffi::OsString>)>,
+    runtime_dir: tempfile::TempDir,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl CompositorFixture {
+    /// Starts the compositor named by `COLDVOX_TEST_COMPOSITOR` (`sway`,
+    /// `cage`, `weston`, or `auto` for the first installed one). Returns
+    /// `None` when it is unset, leaving tests on the current session.
+    ///
+    /// Panics if the requested compositor cannot be started, so a CI run
+    /// never silently falls back to the host session.
+    pub fn from_env() -> Option<Self> {
+        let requested = env::var("COLDVOX_TEST_COMPOSITOR").ok()?;
+        let requested = requested.trim().to_ascii_lowercase();
+        let kind = match requested.as_str() {
+            "" | "none" => return None,
+            "auto" => CompositorKind::ALL
+                .into_iter()
+                .find(CompositorKind::is_installed)
+                .expect(
+                    "COLDVOX_TEST_COMPOSITOR=auto but none of sway, cage or weston is installed",
+                ),
+            name => CompositorKind::ALL
+                .into_iter()
+                .find(|k| k.name() == name)
+                .unwrap_or_else(|| panic!("Unknown COLDVOX_TEST_COMPOSITOR '{}'", name)),
+        };
+        Some(
+            Self::start(kind)
+                .unwrap_or_else(|e| panic!("Failed to start headless {}: {}", kind, e)),
+        )
+    }
+
+    /// Starts `kind` headless and waits for its Wayland socket.
+    pub fn start(kind: CompositorKind) -> std::io::Result<Self> {
+        let lock = COMPOSITOR_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        let runtime_dir = tempfile::Builder::new()
+            .prefix("coldvox-compositor-")
+            .tempdir()?;
+        let virtual_seat = kind.supports_uinput()
+            && fs::OpenOptions::new()
+                .write(true)
+                .open("/dev/uinput")
+                .is_ok();
+        let log_path = runtime_dir.path().join("compositor.log");
+
+        let mut command = kind.command(runtime_dir.path(), virtual_seat)?;
+        command
+            .env("XDG_RUNTIME_DIR", runtime_dir.path())
+            .env_remove("WAYLAND_DISPLAY")
+            .env_remove("DISPLAY")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(fs::File::create(&log_path)?);
+        let mut process = command.spawn()?;
+        println!(
+            "Test harness: started headless {} (PID {}, virtual seat: {})",
+            kind,
+            process.id(),
+            virtual_seat
+        );
+
+        let start = Instant::now();
+        let wayland_display = loop {
+            if let Some(socket) = find_wayland_socket(runtime_dir.path()) {
+                break socket;
+            }
+            let failure = if let Some(status) = process.try_wait()? {
+                format!("{} exited with {}", kind, status)
+            } else if start.elapsed() > COMPOSITOR_STARTUP_TIMEOUT {
+                let _ = process.kill();
+                let _ = process.wait();
+                format!(
+                    "{} created no Wayland socket within {:?}",
+                    kind, COMPOSITOR_STARTUP_TIMEOUT
+                )
+            } else {
+                std::thread::sleep(Duration::from_millis(50));
+                continue;
+            };
+            let log = fs::read_to_string(&log_path).unwrap_or_default();
+            return Err(std::io::Error::other(format!(
+                "{}; log:\n{}",
+                failure,
+                log.trim_end()
+            )));
+        };
+
+        // ydotoold's default socket lives in the old runtime dir
+        let ydotool_socket = env::var_os("XDG_RUNTIME_DIR")
+            .map(|dir| PathBuf::from(dir).join(".ydotool_socket"))
+            .filter(|socket| socket.exists() && env::var_os("YDOTOOL_SOCKET").is_none());
+
+        let mut fixture = Self {
+            kind,
+            process,
+            wayland_display,
+            saved_env: Vec::new(),
+            runtime_dir,
+            _lock: lock,
+        };
+        fixture.set_env(
+            "XDG_RUNTIME_DIR",
+            Some(fixture.runtime_dir.path().as_os_str().into()),
+        );
+        fixture.set_env(
+            "WAYLAND_DISPLAY",
+            Some(fixture.wayland_display.clone().into()),
+        );
+        fixture.set_env("XDG_SESSION_TYPE", Some("wayland".into()));
+        fixture.set_env("XDG_CURRENT_DESKTOP", Some(kind.desktop_name().into()));
+        fixture.set_env("DISPLAY", None);
+        if let Some(socket) = ydotool_socket {
+            fixture.set_env("YDOTOOL_SOCKET", Some(socket.into_os_string()));
+        }
+        Ok(fixture)
+    }
+
+    pub fn kind(&self) -> CompositorKind {
+        self.kind
+    }
+
+    /// The compositor's socket name, as exported in `WAYLAND_DISPLAY`.
+    pub fn wayland_display(&self) -> &str {
+        &self.wayland_display
+    }
+
+    /// The private runtime dir holding the compositor's socket.
+    pub fn runtime_dir(&self) -> &Path {
+        self.runtime_dir.path()
+    }
+
+    fn set_env(&mut self, key: &'static str, value: Option<std::ffi::OsString>) {
+        self.saved_env.push((key, env::var_os(key)));
+        match value {
+            Some(value) => env::set_var(key, value),
+            None => env::remove_var(key),
+        }
+    }
+}
+
+impl Drop for CompositorFixture {
+    fn drop(&mut self) {
+        for (key, value) in self.saved_env.drain(..).rev() {
+            match value {
+                Some(value) => env::set_var(key, value),
+                None => env::remove_var(key),
+            }
+        }
+
+        #[cfg(unix)]
+        {
+            let _ = Command::new("kill")
+                .arg("-TERM")
+                .arg(self.process.id().to_string())
+                .output();
+        }
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(2) {
+            if matches!(self.process.try_wait(), Ok(Some(_))) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+/// The `wayland-*` socket a compositor created in `runtime_dir`, if any.
+fn find_wayland_socket(runtime_dir: &Path) -> Option<String> {
+    #[cfg(unix)]
+    use std::os::unix::fs::FileTypeExt;
+
+    fs::read_dir(runtime_dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            #[cfg(unix)]
+            {
+                entry.file_type().is_ok_and(|t| t.is_socket())
+            }
+            #[cfg(not(unix))]
+            {
+                let _ = entry;
+                false
+            }
+        })
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .find(|name| name.starts_with("wayland-"))
+}
@@ -1,9 +1,11 @@
-//! Shared helpers for integration tests that exercise wl-copy behavior.
+//! Shared helpers for integration tests that exercise wl-copy behavior, and
+//! text strategies for the property tests on the injection text path.
 
 use std::process::Command;
 use std::time::Duration;
 
 use anyhow::{Context, Result};
+use proptest::prelude::*;
 
 const DEFAULT_CLIPBOARD_TIMEOUT: Duration = Duration::from_secs(5);
 
@@ -56,3 +58,33 @@ pub async fn read_clipboard_with_wl_paste_with_timeout(limit: Duration) -> Resul
 
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
+
+/// Text that is hard to split or type: multibyte and astral characters,
+/// combining marks (also on whitespace), ZWJ emoji and flag sequences,
+/// right-to-left runs with bidi marks, CR/CRLF/LF, tabs and stray control
+/// characters, mixed with plain words.
+pub fn tricky_text() -> impl Strategy<Value = String> {
+    let piece = prop_oneof![
+        3 => "[a-z]{1,8}",
+        2 => Just(" ".to_string()),
+        1 => any::<char>().prop_map(String::from),
+        1 => Just("e\u{301}".to_string()),
+        1 => Just(" \u{308}".to_string()),
+        1 => Just("a\u{301}\u{323}\u{35c}".to_string()),
+        1 => Just("👩\u{200d}👩\u{200d}👧".to_string()),
+        1 => Just("🇩🇪".to_string()),
+        1 => Just("👍🏽".to_string()),
+        1 => Just("שלום".to_string()),
+        1 => Just("مرحبا\u{200f}".to_string()),
+        1 => Just("\u{202b}עברית\u{202c}".to_string()),
+        1 => Just("한국어".to_string()),
+        1 => Just("\n".to_string()),
+        1 => Just("\n\n".to_string()),
+        1 => Just("\r\n".to_string()),
+        1 => Just("\r".to_string()),
+        1 => Just("\t".to_string()),
+        1 => prop::char::range('\u{0}', '\u{1f}').prop_map(String::from),
+        1 => Just("\u{7f}\u{85}".to_string()),
+    ];
+    prop::collection::vec(piece, 0..48).prop_map(|pieces| pieces.concat())
+}
@@ -183,7 +183,11 @@ async fn test_inject_text_uses_paste_by_default() {
     let socket_path = harness.home_dir.join(".ydotool/socket");
     harness.create_mock_socket(&socket_path).unwrap();
 
-    let injector = YdotoolInjector::new(InjectionConfig::default());
+    // Pin the layout so the host's doesn't change the commands
+    let injector = YdotoolInjector::new(InjectionConfig {
+        keyboard_layout: Some("us".to_string()),
+        ..Default::default()
+    });
     let result = injector.inject_text("hello", None).await;
 
     assert!(result.is_ok());
@@ -213,7 +217,11 @@ fi
     let socket_path = harness.home_dir.join(".ydotool/socket");
     harness.create_mock_socket(&socket_path).unwrap();
 
-    let injector = YdotoolInjector::new(InjectionConfig::default());
+    // Pin the layout so the host's doesn't change the commands
+    let injector = YdotoolInjector::new(InjectionConfig {
+        keyboard_layout: Some("us".to_string()),
+        ..Default::default()
+    });
     let result = injector.inject_text("world", None).await;
 
     assert!(result.is_ok());
@@ -1,5 +1,11 @@
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::dictation_mode::DictationMode;
 
 /// Behavior when all injection methods fail. Used for debugging/CI to cause
 /// immediate termination or panic when injection cannot succeed.
@@ -8,7 +14,8 @@ fn default_fail_fast() -> bool {
 }
 
 /// Injection mode override (paste vs keystroke decision)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum InjectionMode {
     /// Use paste-based injection
     Paste,
@@ -16,6 +23,147 @@ pub enum InjectionMode {
     Keystroke,
 }
 
+/// How text inserted via AT-SPI treats the formatting around the caret in
+/// rich text targets. AT-SPI can read text attributes but not set them, so
+/// the non-default modes check attributes and hand the text to the next
+/// method when they cannot be honored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AtspiTextAttributes {
+    /// Leave formatting to the target (usually whatever is at the caret)
+    #[default]
+    Inherit,
+    /// Text must come out unformatted: skip AT-SPI insert when the caret
+    /// sits in formatted text
+    Plain,
+    /// Text must carry the caret's formatting: undo the insert when the
+    /// target applied something else
+    MatchCaret,
+}
+
+impl std::str::FromStr for AtspiTextAttributes {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "inherit" | "" => Ok(AtspiTextAttributes::Inherit),
+            "plain" | "unformatted" => Ok(AtspiTextAttributes::Plain),
+            "match-caret" | "match_caret" => Ok(AtspiTextAttributes::MatchCaret),
+            other => Err(format!("unknown AT-SPI text attribute mode '{}'", other)),
+        }
+    }
+}
+
+/// What injection does while the screen is being shared (see
+/// [`crate::screen_share`]). Clipboard contents and paste popups can show the
+/// dictated text on stream even when the target window is not shared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScreenSharePolicy {
+    /// Don't check for screen sharing
+    #[default]
+    Ignore,
+    /// Only use methods that type or insert directly; skip clipboard,
+    /// primary selection and paste shortcuts
+    KeystrokeOnly,
+    /// Don't inject at all while sharing
+    Pause,
+}
+
+impl std::str::FromStr for ScreenSharePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "ignore" | "" => Ok(ScreenSharePolicy::Ignore),
+            "keystroke-only" | "keystroke_only" => Ok(ScreenSharePolicy::KeystrokeOnly),
+            "pause" => Ok(ScreenSharePolicy::Pause),
+            other => Err(format!("unknown screen share policy '{}'", other)),
+        }
+    }
+}
+
+/// What happens to a final transcript containing secrets or personal data
+/// (see [`crate::content_filter`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SensitiveContentAction {
+    /// Don't check
+    #[default]
+    Off,
+    /// Log a warning (naming only what was found) and inject as dictated
+    Warn,
+    /// Inject with the matches replaced by `[redacted <kind>]`
+    Redact,
+    /// Hold the transcript until the user accepts it, like a low-confidence one
+    Confirm,
+}
+
+impl std::str::FromStr for SensitiveContentAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "off" | "" => Ok(SensitiveContentAction::Off),
+            "warn" => Ok(SensitiveContentAction::Warn),
+            "redact" => Ok(SensitiveContentAction::Redact),
+            "confirm" => Ok(SensitiveContentAction::Confirm),
+            other => Err(format!("unknown sensitive content action '{}'", other)),
+        }
+    }
+}
+
+/// How much of the dictated text the audit log keeps (see [`crate::audit`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditTextMode {
+    /// The text as injected
+    Full,
+    /// SHA-256 of the text, so a known text can be checked against the log
+    Hashed,
+    /// Nothing but the character count
+    Omitted,
+}
+
+impl std::str::FromStr for AuditTextMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "full" => Ok(AuditTextMode::Full),
+            "hashed" | "hash" => Ok(AuditTextMode::Hashed),
+            "omitted" | "none" => Ok(AuditTextMode::Omitted),
+            other => Err(format!("unknown audit text mode '{}'", other)),
+        }
+    }
+}
+
+/// What the injection queue does with a new utterance when it is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum QueueDropPolicy {
+    /// Stop taking transcripts until the queue drains (backpressure to STT)
+    #[default]
+    Block,
+    /// Drop the oldest queued utterance to make room
+    DropOldest,
+    /// Drop the new utterance
+    DropNewest,
+}
+
+impl std::str::FromStr for QueueDropPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "block" | "" => Ok(QueueDropPolicy::Block),
+            "drop-oldest" | "drop_oldest" => Ok(QueueDropPolicy::DropOldest),
+            "drop-newest" | "drop_newest" => Ok(QueueDropPolicy::DropNewest),
+            other => Err(format!("unknown queue drop policy '{}'", other)),
+        }
+    }
+}
+
 /// Unified injection context passed to all injectors
 /// Contains pre-warmed data, focus info, and mode overrides
 #[derive(Debug, Clone, Default)]
@@ -31,10 +179,21 @@ pub struct InjectionContext {
     /// Injection mode override (paste vs keystroke)
     /// When Some, this overrides the config-based decision
     pub mode_override: Option<InjectionMode>,
+    /// Keystroke rate paced for this injection (learned for the target app,
+    /// with jitter); typing backends use it instead of `keystroke_rate_cps`
+    pub keystroke_rate_cps: Option<u32>,
+    /// Offer an HTML rendering next to plain text on clipboard pastes
+    /// (`clipboard_html` and the per-target lists)
+    pub offer_html: bool,
 }
 
 /// Enumeration of all available text injection methods
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Out-of-tree backends use [`InjectionMethod::Custom`] with a name obtained
+/// from [`register_custom_method`]; custom methods take part in metrics,
+/// cooldowns and ordering exactly like the built-in ones. Methods serialize
+/// as their name (e.g. `"AtspiInsert"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum InjectionMethod {
     /// Insert text directly using AT-SPI2 EditableText interface
     AtspiInsert,
@@ -43,11 +202,193 @@ pub enum InjectionMethod {
     ClipboardPasteFallback,
     /// Use kdotool for window activation/focus assistance (opt-in)
     KdoToolAssist,
+    /// Type with xdotool on plain X11 sessions (opt-in)
+    XdoToolType,
+    /// Type through the XDG RemoteDesktop portal on Wayland (opt-in)
+    PortalKeyboard,
     /// Use enigo library for synthetic text/paste (opt-in)
     EnigoText,
+    /// Set the primary selection and middle-click to paste (opt-in)
+    PrimarySelection,
+    /// Set the terminal's clipboard with OSC 52 (no display server, TTY only)
+    Osc52Clipboard,
 
     /// No-op fallback injector (always succeeds, does nothing)
     NoOp,
+
+    /// Method provided by an external backend crate
+    Custom(&'static str),
+}
+
+impl InjectionMethod {
+    /// Built-in methods, in declaration order
+    pub const BUILTIN: [InjectionMethod; 9] = [
+        InjectionMethod::AtspiInsert,
+        InjectionMethod::ClipboardPasteFallback,
+        InjectionMethod::KdoToolAssist,
+        InjectionMethod::XdoToolType,
+        InjectionMethod::PortalKeyboard,
+        InjectionMethod::EnigoText,
+        InjectionMethod::PrimarySelection,
+        InjectionMethod::Osc52Clipboard,
+        InjectionMethod::NoOp,
+    ];
+
+    /// Stable name used in config files, persisted stats and logs
+    pub fn name(&self) -> &'static str {
+        match self {
+            InjectionMethod::AtspiInsert => "AtspiInsert",
+            InjectionMethod::ClipboardPasteFallback => "ClipboardPasteFallback",
+            InjectionMethod::KdoToolAssist => "KdoToolAssist",
+            InjectionMethod::XdoToolType => "XdoToolType",
+            InjectionMethod::PortalKeyboard => "PortalKeyboard",
+            InjectionMethod::EnigoText => "EnigoText",
+            InjectionMethod::PrimarySelection => "PrimarySelection",
+            InjectionMethod::Osc52Clipboard => "Osc52Clipboard",
+            InjectionMethod::NoOp => "NoOp",
+            InjectionMethod::Custom(name) => name,
+        }
+    }
+
+    /// How this method can deliver text. AT-SPI inserts directly or pastes
+    /// depending on the mode override; assist-only and custom methods report
+    /// none.
+    pub fn supported_modes(&self) -> &'static [InjectionMode] {
+        match self {
+            InjectionMethod::AtspiInsert | InjectionMethod::EnigoText => {
+                &[InjectionMode::Keystroke, InjectionMode::Paste]
+            }
+            InjectionMethod::ClipboardPasteFallback
+            | InjectionMethod::PrimarySelection
+            | InjectionMethod::Osc52Clipboard => &[InjectionMode::Paste],
+            InjectionMethod::XdoToolType | InjectionMethod::PortalKeyboard => {
+                &[InjectionMode::Keystroke]
+            }
+            InjectionMethod::KdoToolAssist | InjectionMethod::NoOp | InjectionMethod::Custom(_) => {
+                &[]
+            }
+        }
+    }
+
+    /// Resolve a method by name. Unknown names become `Custom` methods; they
+    /// are only tried if an injector is registered for them.
+    pub fn from_name(name: &str) -> Self {
+        Self::BUILTIN
+            .into_iter()
+            .find(|m| m.name() == name)
+            .unwrap_or_else(|| InjectionMethod::Custom(intern_method_name(name)))
+    }
+
+    pub fn is_custom(&self) -> bool {
+        matches!(self, InjectionMethod::Custom(_))
+    }
+
+    /// Metadata registered for a custom method (None for built-ins)
+    pub fn custom_info(&self) -> Option<CustomMethodInfo> {
+        match self {
+            InjectionMethod::Custom(name) => custom_registry().lock().ok()?.info.get(name).cloned(),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for InjectionMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Serialize for InjectionMethod {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for InjectionMethod {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        if name.is_empty() {
+            return Err(serde::de::Error::custom("empty injection method name"));
+        }
+        Ok(InjectionMethod::from_name(&name))
+    }
+}
+
+/// Metadata describing a method provided by an external backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomMethodInfo {
+    pub name: &'static str,
+    /// Human-readable description for logs and diagnostics
+    pub description: String,
+}
+
+#[derive(Default)]
+struct CustomMethodRegistry {
+    /// Every custom name seen so far (registered or read from config/stats)
+    names: std::collections::HashSet<&'static str>,
+    info: std::collections::HashMap<&'static str, CustomMethodInfo>,
+}
+
+fn custom_registry() -> &'static std::sync::Mutex<CustomMethodRegistry> {
+    static REGISTRY: std::sync::OnceLock<std::sync::Mutex<CustomMethodRegistry>> =
+        std::sync::OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Return a `'static` copy of `name`, leaking it only the first time it is seen.
+fn intern_method_name(name: &str) -> &'static str {
+    let mut registry = custom_registry()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(existing) = registry.names.get(name) {
+        return existing;
+    }
+    let leaked: &'static str = Box::leak(name.to_owned().into_boxed_str());
+    registry.names.insert(leaked);
+    leaked
+}
+
+/// Register a custom injection method and return its identifier.
+///
+/// Registering the same name again updates its description. Names of
+/// built-in methods are rejected.
+pub fn register_custom_method(
+    name: &str,
+    description: impl Into<String>,
+) -> InjectionResult<InjectionMethod> {
+    if name.trim().is_empty() {
+        return Err(coldvox_foundation::error::InjectionError::Other(
+            "custom injection method name must not be empty".to_string(),
+        ));
+    }
+    if InjectionMethod::BUILTIN.iter().any(|m| m.name() == name) {
+        return Err(coldvox_foundation::error::InjectionError::Other(format!(
+            "'{}' is a built-in injection method",
+            name
+        )));
+    }
+    let name = intern_method_name(name);
+    let mut registry = custom_registry()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    registry.info.insert(
+        name,
+        CustomMethodInfo {
+            name,
+            description: description.into(),
+        },
+    );
+    Ok(InjectionMethod::Custom(name))
+}
+
+/// All registered custom methods, sorted by name
+pub fn registered_custom_methods() -> Vec<CustomMethodInfo> {
+    let registry = custom_registry()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut infos: Vec<_> = registry.info.values().cloned().collect();
+    infos.sort_by_key(|info| info.name);
+    infos
 }
 
 /// Configuration for text injection system
@@ -59,6 +400,20 @@ pub struct InjectionConfig {
     /// Whether to allow enigo library usage (Wayland/libei paths)
     #[serde(default = "default_false")]
     pub allow_enigo: bool,
+    /// Whether to allow xdotool typing (external CLI, X11 sessions only)
+    #[serde(default = "default_false")]
+    pub allow_xdotool: bool,
+    /// Whether to allow typing through the XDG RemoteDesktop portal (Wayland;
+    /// the compositor asks for consent once)
+    #[serde(default = "default_false")]
+    pub allow_portal: bool,
+    /// Whether to allow injecting through the primary (middle-click) selection
+    #[serde(default = "default_false")]
+    pub allow_primary_selection: bool,
+    /// Middle-click after setting the primary selection (otherwise the user
+    /// pastes it themselves)
+    #[serde(default = "default_true")]
+    pub primary_selection_middle_click: bool,
 
     /// Whether to restore the clipboard content after injection
     // Clipboard restoration is unconditional now; removal of runtime toggle.
@@ -98,6 +453,13 @@ pub struct InjectionConfig {
     /// Maximum cooldown period to prevent excessively long waits.
     #[serde(default = "default_cooldown_max_ms")]
     pub cooldown_max_ms: u64,
+    /// Retries of the same method after a transient failure (timeout,
+    /// clipboard busy, window still activating) before falling back.
+    #[serde(default = "default_transient_retries")]
+    pub transient_retries: u32,
+    /// Delay before retrying after a transient failure.
+    #[serde(default = "default_transient_retry_delay_ms")]
+    pub transient_retry_delay_ms: u64,
 
     /// Mode for text injection: "keystroke", "paste", or "auto"
     #[serde(default = "default_injection_mode")]
@@ -105,6 +467,18 @@ pub struct InjectionConfig {
     /// Keystroke rate in characters per second (cps)
     #[serde(default = "default_keystroke_rate_cps")]
     pub keystroke_rate_cps: u32,
+    /// Learn a keystroke rate per app from whether typed text arrives intact
+    #[serde(default = "default_true")]
+    pub adaptive_pacing: bool,
+    /// Vary each typed injection's keystroke rate by up to this percentage
+    #[serde(default = "default_keystroke_jitter_pct")]
+    pub keystroke_jitter_pct: u32,
+    /// Slowest rate adaptive pacing may fall back to (cps)
+    #[serde(default = "default_min_keystroke_rate_cps")]
+    pub min_keystroke_rate_cps: u32,
+    /// Fastest rate adaptive pacing may climb to (cps)
+    #[serde(default = "default_max_keystroke_rate_cps")]
+    pub max_keystroke_rate_cps: u32,
     /// Maximum number of characters to send in a single burst
     #[serde(default = "default_max_burst_chars")]
     pub max_burst_chars: u32,
@@ -114,6 +488,21 @@ pub struct InjectionConfig {
     /// Delay between paste chunks in milliseconds
     #[serde(default = "default_chunk_delay_ms")]
     pub chunk_delay_ms: u64,
+    /// Keyboard layout typed on through uinput, e.g. `de` or `de(nodeadkeys)`
+    /// (None = detect; see [`crate::layout`])
+    #[serde(default)]
+    pub keyboard_layout: Option<String>,
+    /// Drop final transcripts longer than this many characters (0: no limit)
+    #[serde(default = "default_max_utterance_chars")]
+    pub max_utterance_chars: u32,
+    /// Drop injections that would exceed this many characters in a minute
+    /// (0: no limit)
+    #[serde(default = "default_max_chars_per_minute")]
+    pub max_chars_per_minute: u32,
+    /// Paste text longer than this even when `injection_mode` is keystroke
+    /// (0: never force)
+    #[serde(default = "default_paste_above_chars")]
+    pub paste_above_chars: u32,
 
     /// Cache duration for focus status (ms)
     #[serde(default = "default_focus_cache_duration_ms")]
@@ -134,22 +523,147 @@ pub struct InjectionConfig {
     /// Delay before restoring clipboard (ms)
     #[serde(default = "default_clipboard_restore_delay_ms")]
     pub clipboard_restore_delay_ms: Option<u64>,
+    /// Read the clipboard back after restoring it and retry this many times
+    /// if it doesn't match the backup (0 = don't verify)
+    #[serde(default = "default_clipboard_restore_retries")]
+    pub clipboard_restore_retries: u32,
+    /// Back up and restore every MIME type the clipboard offers, not just text
+    #[serde(default = "default_true")]
+    pub clipboard_preserve_mime_types: bool,
+    /// Mark seeded payloads with `x-kde-passwordManagerHint` so clipboard
+    /// managers keep them out of history
+    #[serde(default = "default_true")]
+    pub clipboard_manager_hint: bool,
+    /// Offer `text/html` next to plain text when pasting text laid out as
+    /// paragraphs or lists, so editors that prefer HTML keep the structure
+    #[serde(default)]
+    pub clipboard_html: bool,
+    /// Target patterns (allowlist syntax) offered HTML even when
+    /// `clipboard_html` is off
+    #[serde(default)]
+    pub clipboard_html_apps: Vec<String>,
+    /// Target patterns (allowlist syntax) that only ever get plain text
+    #[serde(default)]
+    pub clipboard_plain_apps: Vec<String>,
 
     /// Timeout for window discovery operations (ms)
     #[serde(default = "default_discovery_timeout_ms")]
     pub discovery_timeout_ms: u64,
 
-    /// Allowlist of application patterns (regex) for injection
+    /// How often to check that the display server is still there (ms, 0 =
+    /// off). Injection is held while it is gone and backends are rebuilt
+    /// when it returns.
+    #[serde(default = "default_display_watch_interval_ms")]
+    pub display_watch_interval_ms: u64,
+
+    /// Allowlist of target patterns (regex) for injection; matches the app id,
+    /// or the window title / focused page URL with a `title:` / `url:` prefix
     #[serde(default)]
     pub allowlist: Vec<String>,
 
-    /// Blocklist of application patterns (regex) to block injection
+    /// Blocklist of target patterns (regex, same prefixes) to block injection
     #[serde(default)]
     pub blocklist: Vec<String>,
 
     /// If true, exit the process immediately if all injection methods fail.
     #[serde(default = "default_fail_fast")]
     pub fail_fast: bool,
+
+    /// File to persist per-application injection statistics to (None = don't persist)
+    #[serde(default)]
+    pub stats_path: Option<std::path::PathBuf>,
+
+    /// File to append the injection audit log to (None = no audit log)
+    #[serde(default)]
+    pub audit_log_path: Option<std::path::PathBuf>,
+
+    /// Text kept in the audit log; None follows `redact_logs` (hashed when
+    /// redacting, full otherwise)
+    #[serde(default)]
+    pub audit_text: Option<AuditTextMode>,
+
+    /// What to do with final transcripts containing card numbers, email
+    /// addresses, API keys or `sensitive_patterns` matches
+    #[serde(default)]
+    pub sensitive_content: SensitiveContentAction,
+
+    /// Extra patterns for the content filter (regex with feature `regex`,
+    /// substrings otherwise)
+    #[serde(default)]
+    pub sensitive_patterns: Vec<String>,
+
+    /// Move the caret to the end of text inserted via AT-SPI
+    #[serde(default = "default_true")]
+    pub atspi_move_caret: bool,
+    /// Replace a selection at the caret when inserting via AT-SPI (otherwise
+    /// text goes in at the caret and the selection is left alone)
+    #[serde(default)]
+    pub atspi_replace_selection: bool,
+    /// Formatting control for AT-SPI insertion into rich text targets
+    #[serde(default)]
+    pub atspi_text_attributes: AtspiTextAttributes,
+
+    /// Recognize spoken commands ("enter", "undo", ...) and send them as key
+    /// chords instead of typing them
+    #[serde(default)]
+    pub voice_commands: bool,
+    /// Utterances longer than this never take the command fast path (ms)
+    #[serde(default = "default_command_max_duration_ms")]
+    pub command_max_duration_ms: u64,
+    /// Consecutive partials that must agree on a command before it is sent
+    /// ahead of the final transcript
+    #[serde(default = "default_command_min_stable_partials")]
+    pub command_min_stable_partials: u32,
+    /// Buffered dictation: hold transcripts in the session until a flush
+    /// (hotkey or "send it"); spoken edits apply to the held draft
+    #[serde(default)]
+    pub buffered_dictation: bool,
+    /// Dictation mode at startup (see [`crate::dictation_mode`]); switched
+    /// later by voice command or hotkey
+    #[serde(default)]
+    pub dictation_mode: DictationMode,
+    /// Finals arriving within this many ms of the previous one are merged
+    /// into a single injection (0 = inject each final on its own)
+    #[serde(default)]
+    pub final_merge_window_ms: u64,
+
+    /// Preferred order of custom (out-of-tree) methods by name. Custom methods
+    /// are tried after the built-in direct methods and before clipboard paste;
+    /// registered ones not listed here follow in name order.
+    #[serde(default)]
+    pub custom_method_order: Vec<String>,
+
+    /// Audio sources whose transcripts are injected (e.g. "mic"). Transcripts
+    /// from other sources (such as "loopback") only reach the transcript store.
+    #[serde(default = "default_inject_sources")]
+    pub inject_sources: Vec<String>,
+
+    /// Behavior while a screen cast or screen share is active
+    #[serde(default)]
+    pub screen_share_policy: ScreenSharePolicy,
+
+    /// Show a desktop notification when dictated text could not be injected
+    #[serde(default = "default_true")]
+    pub notify_on_failure: bool,
+    /// Put text that could not be injected on the clipboard
+    #[serde(default = "default_true")]
+    pub recover_failed_text: bool,
+
+    /// Utterances that may wait for injection; delivered in order
+    #[serde(default = "default_queue_capacity")]
+    pub queue_capacity: usize,
+    /// Merge an utterance into the queued one before it when they arrive
+    /// less than this many ms apart (0 = never merge)
+    #[serde(default)]
+    pub queue_coalesce_ms: u64,
+    /// What to do when the queue is full
+    #[serde(default)]
+    pub queue_drop_policy: QueueDropPolicy,
+
+    /// Hold back final transcripts whose mean word confidence is below this
+    /// (0.0-1.0) instead of typing them; None types everything
+    #[serde(default)]
+    pub min_confidence: Option<f32>,
 }
 
 fn default_false() -> bool {
@@ -188,6 +702,18 @@ fn default_keystroke_rate_cps() -> u32 {
     20 // 20 characters per second (human typing speed)
 }
 
+fn default_keystroke_jitter_pct() -> u32 {
+    0 // Off: type at the learned rate exactly
+}
+
+fn default_min_keystroke_rate_cps() -> u32 {
+    5
+}
+
+fn default_max_keystroke_rate_cps() -> u32 {
+    100
+}
+
 fn default_max_burst_chars() -> u32 {
     50 // Maximum 50 characters in a single burst
 }
@@ -200,6 +726,18 @@ fn default_chunk_delay_ms() -> u64 {
     30
 }
 
+fn default_max_utterance_chars() -> u32 {
+    4000
+}
+
+fn default_max_chars_per_minute() -> u32 {
+    10_000
+}
+
+fn default_paste_above_chars() -> u32 {
+    1000
+}
+
 fn default_focus_cache_duration_ms() -> u64 {
     200 // Cache focus status for 200ms
 }
@@ -212,6 +750,18 @@ fn default_min_sample_size() -> u32 {
     5 // Need at least 5 samples before trusting success rate
 }
 
+fn default_inject_sources() -> Vec<String> {
+    vec!["mic".to_string()]
+}
+
+fn default_command_max_duration_ms() -> u64 {
+    1500
+}
+
+fn default_command_min_stable_partials() -> u32 {
+    2
+}
+
 fn default_true() -> bool {
     true
 }
@@ -220,6 +770,10 @@ fn default_clipboard_restore_delay_ms() -> Option<u64> {
     Some(500) // Wait 500ms before restoring clipboard
 }
 
+fn default_clipboard_restore_retries() -> u32 {
+    3
+}
+
 fn default_max_total_latency_ms() -> u64 {
     800
 }
@@ -244,15 +798,35 @@ fn default_cooldown_max_ms() -> u64 {
     300_000 // 5 minutes
 }
 
+fn default_transient_retries() -> u32 {
+    1
+}
+
+fn default_transient_retry_delay_ms() -> u64 {
+    50
+}
+
 fn default_discovery_timeout_ms() -> u64 {
     1000 // 1 second
 }
 
+fn default_display_watch_interval_ms() -> u64 {
+    2000
+}
+
+fn default_queue_capacity() -> usize {
+    8
+}
+
 impl Default for InjectionConfig {
     fn default() -> Self {
         Self {
             allow_kdotool: default_false(),
+            allow_xdotool: default_false(),
+            allow_portal: default_false(),
             allow_enigo: default_false(),
+            allow_primary_selection: default_false(),
+            primary_selection_middle_click: true,
 
             // restore_clipboard removed - restoration is always performed by clipboard injectors
             inject_on_unknown_focus: default_inject_on_unknown_focus(),
@@ -265,20 +839,60 @@ impl Default for InjectionConfig {
             cooldown_initial_ms: default_cooldown_initial_ms(),
             cooldown_backoff_factor: default_cooldown_backoff_factor(),
             cooldown_max_ms: default_cooldown_max_ms(),
+            transient_retries: default_transient_retries(),
+            transient_retry_delay_ms: default_transient_retry_delay_ms(),
             injection_mode: default_injection_mode(),
             keystroke_rate_cps: default_keystroke_rate_cps(),
+            adaptive_pacing: default_true(),
+            keystroke_jitter_pct: default_keystroke_jitter_pct(),
+            min_keystroke_rate_cps: default_min_keystroke_rate_cps(),
+            max_keystroke_rate_cps: default_max_keystroke_rate_cps(),
             max_burst_chars: default_max_burst_chars(),
             paste_chunk_chars: default_paste_chunk_chars(),
             chunk_delay_ms: default_chunk_delay_ms(),
+            keyboard_layout: None,
+            max_utterance_chars: default_max_utterance_chars(),
+            max_chars_per_minute: default_max_chars_per_minute(),
+            paste_above_chars: default_paste_above_chars(),
             focus_cache_duration_ms: default_focus_cache_duration_ms(),
             min_success_rate: default_min_success_rate(),
             min_sample_size: default_min_sample_size(),
             enable_window_detection: default_true(),
             clipboard_restore_delay_ms: default_clipboard_restore_delay_ms(),
+            clipboard_restore_retries: default_clipboard_restore_retries(),
+            clipboard_preserve_mime_types: true,
+            clipboard_manager_hint: true,
+            clipboard_html: false,
+            clipboard_html_apps: Vec::new(),
+            clipboard_plain_apps: Vec::new(),
             discovery_timeout_ms: default_discovery_timeout_ms(),
+            display_watch_interval_ms: default_display_watch_interval_ms(),
             allowlist: default_allowlist(),
             blocklist: default_blocklist(),
             fail_fast: default_fail_fast(),
+            stats_path: None,
+            audit_log_path: None,
+            audit_text: None,
+            sensitive_content: SensitiveContentAction::Off,
+            sensitive_patterns: Vec::new(),
+            atspi_move_caret: true,
+            atspi_replace_selection: false,
+            atspi_text_attributes: AtspiTextAttributes::Inherit,
+            voice_commands: false,
+            command_max_duration_ms: default_command_max_duration_ms(),
+            command_min_stable_partials: default_command_min_stable_partials(),
+            buffered_dictation: false,
+            dictation_mode: DictationMode::Normal,
+            final_merge_window_ms: 0,
+            custom_method_order: Vec::new(),
+            inject_sources: default_inject_sources(),
+            screen_share_policy: ScreenSharePolicy::Ignore,
+            notify_on_failure: true,
+            recover_failed_text: true,
+            queue_capacity: default_queue_capacity(),
+            queue_coalesce_ms: 0,
+            queue_drop_policy: QueueDropPolicy::Block,
+            min_confidence: None,
         }
     }
 }
@@ -292,17 +906,146 @@ impl InjectionConfig {
         Duration::from_millis(self.per_method_timeout_ms)
     }
 
+    /// Text mode for the audit log, falling back to `redact_logs`
+    pub fn audit_text_mode(&self) -> AuditTextMode {
+        self.audit_text.unwrap_or(if self.redact_logs {
+            AuditTextMode::Hashed
+        } else {
+            AuditTextMode::Full
+        })
+    }
+
     pub fn paste_action_timeout(&self) -> Duration {
         Duration::from_millis(self.paste_action_timeout_ms)
     }
+
+    /// Whether transcripts from `source` should be injected
+    pub fn injects_source(&self, source: &str) -> bool {
+        self.inject_sources.iter().any(|s| s == source)
+    }
 }
 
 /// Result type for injection operations
 pub type InjectionResult<T> = Result<T, coldvox_foundation::error::InjectionError>;
 
-/// Metrics and telemetry data for injection attempts
-#[derive(Debug, Default, Clone)]
+/// Metrics and telemetry data for injection attempts, shared as
+/// `Arc<InjectionMetrics>` by the manager, session and processor.
+///
+/// Recording never takes a contended lock: totals are atomics, per-method
+/// counters sit in a read-mostly map that is only written the first time a
+/// method is seen, and sample histograms are sharded across writers. Read
+/// everything at once with [`InjectionMetrics::snapshot`].
+#[derive(Debug)]
 pub struct InjectionMetrics {
+    attempts: AtomicU64,
+    successes: AtomicU64,
+    failures: AtomicU64,
+    total_duration_ms: AtomicU64,
+    methods: RwLock<HashMap<InjectionMethod, Arc<MethodCounters>>>,
+    chars_buffered: AtomicU64,
+    chars_injected: AtomicU64,
+    flushes: AtomicU64,
+    paste_uses: AtomicU64,
+    keystroke_uses: AtomicU64,
+    backend_denied: AtomicU64,
+    focus_missing: AtomicU64,
+    rate_limited: AtomicU64,
+    latency_from_final_ms: ShardedSamples,
+    flush_size_chars: ShardedSamples,
+    /// Reference point for the timestamps below
+    epoch: Instant,
+    /// Nanoseconds after `epoch` plus one; 0 = never
+    last_injection_ns: AtomicU64,
+    stuck_buffer_age_ms: AtomicU64,
+    allowlist_regex_count: AtomicU64,
+    blocklist_regex_count: AtomicU64,
+    /// Requests per application this session
+    apps: Mutex<HashMap<String, AppInjections>>,
+}
+
+impl Default for InjectionMetrics {
+    fn default() -> Self {
+        Self {
+            attempts: AtomicU64::new(0),
+            successes: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+            total_duration_ms: AtomicU64::new(0),
+            methods: RwLock::new(HashMap::new()),
+            chars_buffered: AtomicU64::new(0),
+            chars_injected: AtomicU64::new(0),
+            flushes: AtomicU64::new(0),
+            paste_uses: AtomicU64::new(0),
+            keystroke_uses: AtomicU64::new(0),
+            backend_denied: AtomicU64::new(0),
+            focus_missing: AtomicU64::new(0),
+            rate_limited: AtomicU64::new(0),
+            latency_from_final_ms: ShardedSamples::default(),
+            flush_size_chars: ShardedSamples::default(),
+            epoch: Instant::now(),
+            last_injection_ns: AtomicU64::new(0),
+            stuck_buffer_age_ms: AtomicU64::new(0),
+            allowlist_regex_count: AtomicU64::new(0),
+            blocklist_regex_count: AtomicU64::new(0),
+            apps: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+/// Injection requests for one application this session
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AppInjections {
+    pub requests: u64,
+    /// Requests some method delivered
+    pub successes: u64,
+}
+
+/// Live counters for one method
+#[derive(Debug, Default)]
+struct MethodCounters {
+    attempts: AtomicU64,
+    successes: AtomicU64,
+    failures: AtomicU64,
+    total_duration_ms: AtomicU64,
+    /// Same encoding as `InjectionMetrics::last_injection_ns`
+    last_success_ns: AtomicU64,
+    /// Only touched when a method fails
+    last_failure: Mutex<Option<(Instant, String)>>,
+}
+
+/// Shards per sample histogram
+const SAMPLE_SHARDS: usize = 8;
+/// Samples kept per shard (the most recent `SAMPLE_SHARDS * SAMPLES_PER_SHARD` overall)
+const SAMPLES_PER_SHARD: usize = 128;
+
+/// Bounded sample store; writers are spread round-robin across shards so
+/// they rarely wait on each other
+#[derive(Debug, Default)]
+struct ShardedSamples {
+    next: AtomicUsize,
+    shards: [Mutex<VecDeque<u64>>; SAMPLE_SHARDS],
+}
+
+impl ShardedSamples {
+    fn record(&self, value: u64) {
+        let i = self.next.fetch_add(1, Ordering::Relaxed) % SAMPLE_SHARDS;
+        let mut shard = self.shards[i].lock();
+        if shard.len() == SAMPLES_PER_SHARD {
+            shard.pop_front();
+        }
+        shard.push_back(value);
+    }
+
+    fn snapshot(&self) -> Vec<u64> {
+        self.shards
+            .iter()
+            .flat_map(|shard| shard.lock().iter().copied().collect::<Vec<_>>())
+            .collect()
+    }
+}
+
+/// Point-in-time copy of [`InjectionMetrics`]
+#[derive(Debug, Default, Clone)]
+pub struct InjectionMetricsSnapshot {
     /// Total number of injection attempts
     pub attempts: u64,
     /// Number of successful injections
@@ -331,9 +1074,9 @@ pub struct InjectionMetrics {
     pub focus_missing: u64,
     /// Number of rate limited events
     pub rate_limited: u64,
-    /// Histogram of latency from final transcription to injection
+    /// Recent latencies from final transcription to injection (unordered)
     pub latency_from_final_ms: Vec<u64>,
-    /// Histogram of flush sizes
+    /// Recent flush sizes (unordered)
     pub flush_size_chars: Vec<u64>,
     /// Timestamp of last injection
     pub last_injection: Option<std::time::Instant>,
@@ -343,6 +1086,8 @@ pub struct InjectionMetrics {
     pub allowlist_regex_count: u64,
     /// Count of compiled blocklist regex patterns (feature `regex`)
     pub blocklist_regex_count: u64,
+    /// Requests per application this session, keyed by app id
+    pub app_injections: std::collections::HashMap<String, AppInjections>,
 }
 
 /// Metrics for a specific injection method
@@ -362,116 +1107,346 @@ pub struct MethodMetrics {
     pub last_failure: Option<(std::time::Instant, String)>,
 }
 
+/// Runtime view of one injection backend, as returned by
+/// [`StrategyManager::list_backends`](crate::manager::StrategyManager::list_backends)
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendStatus {
+    pub method: InjectionMethod,
+    /// Name reported by the injector, or the method name when none is registered
+    pub name: &'static str,
+    /// Compiled in, enabled in the config and detected at startup
+    pub registered: bool,
+    /// Registered and reporting itself available right now
+    pub available: bool,
+    /// In cooldown for at least one application
+    pub cooling_down: bool,
+    pub attempts: u64,
+    pub successes: u64,
+    pub failures: u64,
+    /// Most recent failure this session
+    pub last_error: Option<BackendError>,
+    /// Capability descriptor from [`TextInjector::backend_info`](crate::TextInjector::backend_info)
+    pub capabilities: Vec<(&'static str, String)>,
+}
+
+/// Why a backend is not available
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind", content = "detail")]
+pub enum UnavailableReason {
+    /// Built without this cargo feature
+    NotCompiled(&'static str),
+    /// Turned off by this `[injection]` setting
+    Disabled(&'static str),
+    /// Compiled in and enabled, but its prerequisites were missing at startup
+    NotDetected,
+    /// Registered, but its availability check fails now
+    CheckFailing,
+    /// Only registered when no other backend is
+    FallbackOnly,
+}
+
+impl std::fmt::Display for UnavailableReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnavailableReason::NotCompiled(feature) => {
+                write!(f, "not compiled in (build with the `{}` feature)", feature)
+            }
+            UnavailableReason::Disabled(key) => write!(f, "disabled (set {} = true)", key),
+            UnavailableReason::NotDetected => {
+                write!(f, "prerequisites missing at startup (see `coldvox doctor`)")
+            }
+            UnavailableReason::CheckFailing => write!(f, "availability check failing"),
+            UnavailableReason::FallbackOnly => {
+                write!(f, "only used when no other backend is available")
+            }
+        }
+    }
+}
+
+/// One backend's row in
+/// [`StrategyManager::capability_report`](crate::manager::StrategyManager::capability_report)
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendCapability {
+    pub status: BackendStatus,
+    /// Set when the backend is not available
+    pub unavailable_reason: Option<UnavailableReason>,
+    /// How the backend can deliver text
+    pub modes: &'static [InjectionMode],
+    /// Mean attempt duration this session
+    pub session_avg_ms: Option<u64>,
+    /// Outcomes and latency from the stats file over the last
+    /// [`CAPABILITY_HISTORY_DAYS`] days (None without `stats_path` or data)
+    pub history: Option<crate::app_stats::DailyMethodStats>,
+}
+
+/// Days of persisted per-backend stats a capability report covers
+pub const CAPABILITY_HISTORY_DAYS: i64 = 7;
+
+/// A backend failure and how long ago it happened
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendError {
+    pub message: String,
+    pub age: Duration,
+}
+
 impl InjectionMetrics {
     /// Record a new injection attempt
-    pub fn record_attempt(&mut self, method: InjectionMethod, duration_ms: u64) {
-        self.attempts += 1;
-        self.total_duration_ms += duration_ms;
+    pub fn record_attempt(&self, method: InjectionMethod, duration_ms: u64) {
+        self.attempts.fetch_add(1, Ordering::Relaxed);
+        self.total_duration_ms
+            .fetch_add(duration_ms, Ordering::Relaxed);
 
         // Update method-specific metrics
-        let method_metrics = self.method_metrics.entry(method).or_default();
-        method_metrics.attempts += 1;
-        method_metrics.total_duration_ms += duration_ms;
+        let counters = self.method_counters(method);
+        counters.attempts.fetch_add(1, Ordering::Relaxed);
+        counters
+            .total_duration_ms
+            .fetch_add(duration_ms, Ordering::Relaxed);
     }
 
     /// Record characters that have been buffered
-    pub fn record_buffered_chars(&mut self, count: u64) {
-        self.chars_buffered += count;
+    pub fn record_buffered_chars(&self, count: u64) {
+        self.chars_buffered.fetch_add(count, Ordering::Relaxed);
     }
 
     /// Record characters that have been successfully injected
-    pub fn record_injected_chars(&mut self, count: u64) {
-        self.chars_injected += count;
+    pub fn record_injected_chars(&self, count: u64) {
+        self.chars_injected.fetch_add(count, Ordering::Relaxed);
     }
 
     /// Record a flush event
-    pub fn record_flush(&mut self, size: u64) {
-        self.flushes += 1;
-        self.flush_size_chars.push(size);
+    pub fn record_flush(&self, size: u64) {
+        self.flushes.fetch_add(1, Ordering::Relaxed);
+        self.flush_size_chars.record(size);
     }
 
     /// Record a paste operation
-    pub fn record_paste(&mut self) {
-        self.paste_uses += 1;
+    pub fn record_paste(&self) {
+        self.paste_uses.fetch_add(1, Ordering::Relaxed);
     }
 
     /// Record a keystroke operation
-    pub fn record_keystroke(&mut self) {
-        self.keystroke_uses += 1;
+    pub fn record_keystroke(&self) {
+        self.keystroke_uses.fetch_add(1, Ordering::Relaxed);
     }
 
     /// Record a backend denial
-    pub fn record_backend_denied(&mut self) {
-        self.backend_denied += 1;
+    pub fn record_backend_denied(&self) {
+        self.backend_denied.fetch_add(1, Ordering::Relaxed);
     }
 
     /// Record a focus missing error
-    pub fn record_focus_missing(&mut self) {
-        self.focus_missing += 1;
+    pub fn record_focus_missing(&self) {
+        self.focus_missing.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record one injection request to `app_id`
+    pub fn record_app_request(&self, app_id: &str, success: bool) {
+        let mut apps = self.apps.lock();
+        let entry = apps.entry(app_id.to_string()).or_default();
+        entry.requests += 1;
+        if success {
+            entry.successes += 1;
+        }
     }
 
     /// Record a rate limited event
-    pub fn record_rate_limited(&mut self) {
-        self.rate_limited += 1;
+    pub fn record_rate_limited(&self) {
+        self.rate_limited.fetch_add(1, Ordering::Relaxed);
     }
 
     /// Record latency from final transcription to injection
-    pub fn record_latency_from_final(&mut self, latency_ms: u64) {
-        self.latency_from_final_ms.push(latency_ms);
+    pub fn record_latency_from_final(&self, latency_ms: u64) {
+        self.latency_from_final_ms.record(latency_ms);
     }
 
     /// Update the last injection timestamp
-    pub fn update_last_injection(&mut self) {
-        self.last_injection = Some(std::time::Instant::now());
+    pub fn update_last_injection(&self) {
+        self.last_injection_ns
+            .store(self.stamp(Instant::now()), Ordering::Relaxed);
     }
 
     /// Update the stuck buffer age
-    pub fn update_stuck_buffer_age(&mut self, age_ms: u64) {
-        self.stuck_buffer_age_ms = age_ms;
+    pub fn update_stuck_buffer_age(&self, age_ms: u64) {
+        self.stuck_buffer_age_ms.store(age_ms, Ordering::Relaxed);
     }
 
     /// Record a successful injection
-    pub fn record_success(&mut self, method: InjectionMethod, duration_ms: u64) {
-        self.successes += 1;
+    pub fn record_success(&self, method: InjectionMethod, duration_ms: u64) {
+        self.successes.fetch_add(1, Ordering::Relaxed);
         self.record_attempt(method, duration_ms);
 
         // Update method-specific success
-        if let Some(metrics) = self.method_metrics.get_mut(&method) {
-            metrics.successes += 1;
-            metrics.last_success = Some(std::time::Instant::now());
-        }
+        let counters = self.method_counters(method);
+        counters.successes.fetch_add(1, Ordering::Relaxed);
+        counters
+            .last_success_ns
+            .store(self.stamp(Instant::now()), Ordering::Relaxed);
     }
 
     /// Record a failed injection
-    pub fn record_failure(&mut self, method: InjectionMethod, duration_ms: u64, error: String) {
-        self.failures += 1;
+    pub fn record_failure(&self, method: InjectionMethod, duration_ms: u64, error: String) {
+        self.failures.fetch_add(1, Ordering::Relaxed);
         self.record_attempt(method, duration_ms);
 
         // Update method-specific failure
-        if let Some(metrics) = self.method_metrics.get_mut(&method) {
-            metrics.failures += 1;
-            metrics.last_failure = Some((std::time::Instant::now(), error));
+        let counters = self.method_counters(method);
+        counters.failures.fetch_add(1, Ordering::Relaxed);
+        *counters.last_failure.lock() = Some((Instant::now(), error));
+    }
+
+    /// Set the number of compiled allowlist regex patterns
+    pub fn set_allowlist_regex_count(&self, count: usize) {
+        self.allowlist_regex_count
+            .store(count as u64, Ordering::Relaxed);
+    }
+
+    /// Set the number of compiled blocklist regex patterns
+    pub fn set_blocklist_regex_count(&self, count: usize) {
+        self.blocklist_regex_count
+            .store(count as u64, Ordering::Relaxed);
+    }
+
+    pub fn attempts(&self) -> u64 {
+        self.attempts.load(Ordering::Relaxed)
+    }
+
+    pub fn successes(&self) -> u64 {
+        self.successes.load(Ordering::Relaxed)
+    }
+
+    pub fn failures(&self) -> u64 {
+        self.failures.load(Ordering::Relaxed)
+    }
+
+    /// Counters for one method (None until it has been attempted)
+    pub fn method(&self, method: InjectionMethod) -> Option<MethodMetrics> {
+        let methods = self.methods.read();
+        methods.get(&method).map(|c| self.method_snapshot(c))
+    }
+
+    /// Copy of every counter and histogram. Values are read one by one, so a
+    /// snapshot taken during an injection may be off by that injection.
+    pub fn snapshot(&self) -> InjectionMetricsSnapshot {
+        let load = |v: &AtomicU64| v.load(Ordering::Relaxed);
+        let attempts = load(&self.attempts);
+        let total_duration_ms = load(&self.total_duration_ms);
+        let method_metrics = self
+            .methods
+            .read()
+            .iter()
+            .map(|(method, c)| (*method, self.method_snapshot(c)))
+            .collect();
+        InjectionMetricsSnapshot {
+            attempts,
+            successes: load(&self.successes),
+            failures: load(&self.failures),
+            total_duration_ms,
+            avg_duration_ms: if attempts > 0 {
+                total_duration_ms as f64 / attempts as f64
+            } else {
+                0.0
+            },
+            method_metrics,
+            chars_buffered: load(&self.chars_buffered),
+            chars_injected: load(&self.chars_injected),
+            flushes: load(&self.flushes),
+            paste_uses: load(&self.paste_uses),
+            keystroke_uses: load(&self.keystroke_uses),
+            backend_denied: load(&self.backend_denied),
+            focus_missing: load(&self.focus_missing),
+            rate_limited: load(&self.rate_limited),
+            latency_from_final_ms: self.latency_from_final_ms.snapshot(),
+            flush_size_chars: self.flush_size_chars.snapshot(),
+            last_injection: self.instant(load(&self.last_injection_ns)),
+            stuck_buffer_age_ms: load(&self.stuck_buffer_age_ms),
+            allowlist_regex_count: load(&self.allowlist_regex_count),
+            blocklist_regex_count: load(&self.blocklist_regex_count),
+            app_injections: self.apps.lock().clone(),
         }
     }
 
-    /// Calculate average duration
-    pub fn calculate_avg_duration(&mut self) {
-        self.avg_duration_ms = if self.attempts > 0 {
-            self.total_duration_ms as f64 / self.attempts as f64
-        } else {
-            0.0
-        };
+    /// Counters for `method`, created on first use
+    fn method_counters(&self, method: InjectionMethod) -> Arc<MethodCounters> {
+        if let Some(counters) = self.methods.read().get(&method) {
+            return counters.clone();
+        }
+        self.methods.write().entry(method).or_default().clone()
     }
 
-    /// Set the number of compiled allowlist regex patterns
-    pub fn set_allowlist_regex_count(&mut self, count: usize) {
-        self.allowlist_regex_count = count as u64;
+    fn method_snapshot(&self, c: &MethodCounters) -> MethodMetrics {
+        MethodMetrics {
+            attempts: c.attempts.load(Ordering::Relaxed),
+            successes: c.successes.load(Ordering::Relaxed),
+            failures: c.failures.load(Ordering::Relaxed),
+            total_duration_ms: c.total_duration_ms.load(Ordering::Relaxed),
+            last_success: self.instant(c.last_success_ns.load(Ordering::Relaxed)),
+            last_failure: c.last_failure.lock().clone(),
+        }
     }
 
-    /// Set the number of compiled blocklist regex patterns
-    pub fn set_blocklist_regex_count(&mut self, count: usize) {
-        self.blocklist_regex_count = count as u64;
+    fn stamp(&self, at: Instant) -> u64 {
+        at.saturating_duration_since(self.epoch).as_nanos() as u64 + 1
+    }
+
+    fn instant(&self, stamp: u64) -> Option<Instant> {
+        stamp
+            .checked_sub(1)
+            .map(|ns| self.epoch + Duration::from_nanos(ns))
     }
 }
+
 // Note: The TextInjector trait has been moved to lib.rs to avoid conflicts.
 // Use crate::TextInjector for the canonical async trait definition.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics_record_concurrently_and_snapshot() {
+        let metrics = Arc::new(InjectionMetrics::default());
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let metrics = metrics.clone();
+                std::thread::spawn(move || {
+                    for i in 0..100 {
+                        metrics.record_success(InjectionMethod::AtspiInsert, 2);
+                        metrics.record_latency_from_final(i);
+                    }
+                    metrics.record_failure(InjectionMethod::NoOp, 4, "boom".to_string());
+                })
+            })
+            .collect();
+        for t in threads {
+            t.join().unwrap();
+        }
+
+        let snap = metrics.snapshot();
+        assert_eq!(snap.attempts, 404);
+        assert_eq!(snap.successes, 400);
+        assert_eq!(snap.failures, 4);
+        assert_eq!(snap.avg_duration_ms, 816.0 / 404.0);
+        assert_eq!(snap.latency_from_final_ms.len(), 400);
+        let atspi = &snap.method_metrics[&InjectionMethod::AtspiInsert];
+        assert_eq!(atspi.successes, 400);
+        assert!(atspi.last_success.is_some());
+        let noop = metrics.method(InjectionMethod::NoOp).unwrap();
+        assert_eq!(noop.last_failure.unwrap().1, "boom");
+        assert!(metrics.method(InjectionMethod::EnigoText).is_none());
+    }
+
+    #[test]
+    fn sample_histograms_are_bounded() {
+        let metrics = InjectionMetrics::default();
+        for i in 0..(SAMPLE_SHARDS * SAMPLES_PER_SHARD + 10) as u64 {
+            metrics.record_flush(i);
+        }
+        let snap = metrics.snapshot();
+        assert_eq!(
+            snap.flush_size_chars.len(),
+            SAMPLE_SHARDS * SAMPLES_PER_SHARD
+        );
+        assert!(!snap.flush_size_chars.contains(&0));
+    }
+}
@@ -1,10 +1,100 @@
 use coldvox_foundation::error::InjectionError;
+use std::collections::HashMap;
 use std::process::Command;
 use tracing::debug;
 
+/// Seat this session runs on (`XDG_SEAT`, "seat0" when unset)
+pub fn current_seat() -> String {
+    std::env::var("XDG_SEAT")
+        .ok()
+        .filter(|seat| !seat.is_empty())
+        .unwrap_or_else(|| "seat0".to_string())
+}
+
+/// Whether this session is on the default seat. udev assigns new input
+/// devices, including ydotool's uinput keyboard, to seat0, so keystrokes
+/// synthesized that way reach the default seat's session whichever seat
+/// asked for them.
+pub fn on_default_seat() -> bool {
+    current_seat() == "seat0"
+}
+
+fn on_wayland_session() -> bool {
+    std::env::var("XDG_SESSION_TYPE")
+        .map(|s| s == "wayland")
+        .unwrap_or(false)
+        || std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+fn on_gnome_session() -> bool {
+    let desktop = std::env::var("XDG_CURRENT_DESKTOP")
+        .unwrap_or_default()
+        .to_lowercase();
+    desktop.contains("gnome") || desktop.contains("ubuntu")
+}
+
+/// Run the KDE, X11 and Wayland lookups in turn and return the first answer.
+/// On Wayland sessions the compositor is asked before X11: `xprop` only sees
+/// XWayland clients there and reports the last of them that was active, not
+/// the window that has focus.
+fn query_active_window<T>(
+    kde: fn() -> Result<T, InjectionError>,
+    x11: fn() -> Result<T, InjectionError>,
+    wayland: fn() -> Result<T, InjectionError>,
+    error: &str,
+) -> Result<T, InjectionError> {
+    let lookups = if on_wayland_session() {
+        [kde, wayland, x11]
+    } else {
+        [kde, x11, wayland]
+    };
+    lookups
+        .iter()
+        .find_map(|lookup| lookup().ok())
+        .ok_or_else(|| InjectionError::Other(error.to_string()))
+}
+
+/// Properties of KWin's active window (`resourceClass`, `caption`, `x`, ...)
+fn kwin_active_window_info() -> Result<HashMap<String, String>, InjectionError> {
+    let window_id = get_kdotool_active_window()?;
+    let output = Command::new("qdbus")
+        .args([
+            "org.kde.KWin",
+            "/KWin",
+            "org.kde.KWin.getWindowInfo",
+            &window_id,
+        ])
+        .output()
+        .map_err(|e| InjectionError::Process(format!("qdbus failed: {}", e)))?;
+
+    if output.status.success() {
+        // One "key: value" pair per line
+        let info: HashMap<String, String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_once(": "))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect();
+        if !info.is_empty() {
+            return Ok(info);
+        }
+    }
+
+    Err(InjectionError::Other(
+        "KWin window info not available".to_string(),
+    ))
+}
+
 // Get KDE window class synchronously
 fn get_kde_window_class() -> Result<String, InjectionError> {
-    // Use KWin DBus interface
+    if let Some(class) = kwin_active_window_info()
+        .ok()
+        .and_then(|mut info| info.remove("resourceClass"))
+        .filter(|class| !class.is_empty())
+    {
+        return Ok(class);
+    }
+
+    // Older KWin: DBus interface with per-window objects
     let output = Command::new("qdbus")
         .args(["org.kde.KWin", "/KWin", "org.kde.KWin.activeClient"])
         .output()
@@ -69,62 +159,183 @@ fn get_x11_window_class() -> Result<String, InjectionError> {
     ))
 }
 
-// Get Wayland window class synchronously
-fn get_wayland_window_class() -> Result<String, InjectionError> {
-    // Try using wlr-foreign-toplevel-management protocol if available
-    // This requires compositor support (e.g., Sway, some KWin versions)
-
-    // For now, we'll try using swaymsg if Sway is running
+fn swaymsg_json(message_type: &str) -> Result<serde_json::Value, InjectionError> {
     let output = Command::new("swaymsg")
-        .args(["-t", "get_tree"])
+        .args(["-t", message_type])
         .output()
         .map_err(|e| InjectionError::Process(format!("swaymsg failed: {}", e)))?;
+    if !output.status.success() {
+        return Err(InjectionError::Process(format!(
+            "swaymsg -t {} failed",
+            message_type
+        )));
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| {
+        debug!("Failed to parse swaymsg JSON: {}", e);
+        InjectionError::Other(format!("Invalid swaymsg {} output: {}", message_type, e))
+    })
+}
 
-    if output.status.success() {
-        // Parse JSON to find focused window using serde_json
-        let tree = String::from_utf8_lossy(&output.stdout);
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&tree) {
-            // Depth-first search for focused node with app_id
-            fn dfs(node: &serde_json::Value) -> Option<String> {
-                if node
-                    .get("focused")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false)
-                {
-                    if let Some(app_id) = node.get("app_id").and_then(|v| v.as_str()) {
-                        return Some(app_id.to_string());
-                    }
-                    if let Some(window_props) = node.get("window_properties") {
-                        if let Some(class) = window_props.get("class").and_then(|v| v.as_str()) {
-                            return Some(class.to_string());
-                        }
-                    }
-                }
-                if let Some(nodes) = node.get("nodes").and_then(|v| v.as_array()) {
-                    for n in nodes {
-                        if let Some(found) = dfs(n) {
-                            return Some(found);
-                        }
-                    }
-                }
-                if let Some(floating_nodes) = node.get("floating_nodes").and_then(|v| v.as_array())
-                {
-                    for n in floating_nodes {
-                        if let Some(found) = dfs(n) {
-                            return Some(found);
-                        }
-                    }
-                }
-                None
-            }
-            if let Some(app_id) = dfs(&json) {
-                return Ok(app_id);
-            }
+/// Find the node with id `focus` (or, without one, the node flagged
+/// `focused`) and the name of the output it is on.
+pub(crate) fn find_sway_node(
+    tree: &serde_json::Value,
+    focus: Option<i64>,
+) -> Option<(&serde_json::Value, Option<&str>)> {
+    fn dfs<'a>(
+        node: &'a serde_json::Value,
+        focus: Option<i64>,
+        output: Option<&'a str>,
+    ) -> Option<(&'a serde_json::Value, Option<&'a str>)> {
+        let output = if node.get("type").and_then(|v| v.as_str()) == Some("output") {
+            node.get("name").and_then(|v| v.as_str())
         } else {
-            debug!("Failed to parse swaymsg JSON; falling back");
+            output
+        };
+        let is_target = match focus {
+            Some(id) => node.get("id").and_then(|v| v.as_i64()) == Some(id),
+            None => node
+                .get("focused")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+        };
+        if is_target {
+            return Some((node, output));
+        }
+        ["nodes", "floating_nodes"]
+            .iter()
+            .filter_map(|key| node.get(*key).and_then(|v| v.as_array()))
+            .flatten()
+            .find_map(|child| dfs(child, focus, output))
+    }
+    dfs(tree, focus, None)
+}
+
+/// Container id `seat` has focused, from sway's `get_seats` reply
+pub(crate) fn sway_seat_focus(seats: &serde_json::Value, seat: &str) -> Option<i64> {
+    seats
+        .as_array()?
+        .iter()
+        .find(|s| s.get("name").and_then(|v| v.as_str()) == Some(seat))?
+        .get("focus")?
+        .as_i64()
+        .filter(|id| *id != 0)
+}
+
+/// The sway window focused on this session's seat and its output. Each seat
+/// has its own focus; the tree's `focused` flag follows whichever seat moved
+/// it last.
+fn sway_focused_window() -> Result<(serde_json::Value, Option<String>), InjectionError> {
+    let tree = swaymsg_json("get_tree")?;
+    let seat_focus = swaymsg_json("get_seats")
+        .ok()
+        .and_then(|seats| sway_seat_focus(&seats, &current_seat()));
+    find_sway_node(&tree, seat_focus)
+        .map(|(node, output)| (node.clone(), output.map(str::to_string)))
+        .ok_or_else(|| InjectionError::Other("No focused sway window".to_string()))
+}
+
+/// The focused window as GNOME Shell reports it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct GnomeWindow {
+    /// Desktop file id without ".desktop", e.g. "org.gnome.TextEditor"
+    app_id: Option<String>,
+    wm_class: Option<String>,
+    title: Option<String>,
+}
+
+/// Find the focused window in `busctl --json` output of
+/// `org.gnome.Shell.Introspect.GetWindows` (`a{ta{sv}}`)
+fn parse_gnome_windows(json: &serde_json::Value) -> Option<GnomeWindow> {
+    let windows = json.get("data")?.get(0)?.as_object()?;
+    let string = |props: &serde_json::Value, key: &str| {
+        props
+            .get(key)
+            .and_then(|v| v.get("data"))
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    windows
+        .values()
+        .find(|props| {
+            props
+                .get("has-focus")
+                .and_then(|v| v.get("data"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false)
+        })
+        .map(|props| GnomeWindow {
+            app_id: string(props, "app-id").map(|id| {
+                id.strip_suffix(".desktop")
+                    .map(str::to_string)
+                    .unwrap_or(id)
+            }),
+            wm_class: string(props, "wm-class"),
+            title: string(props, "title"),
+        })
+}
+
+/// Ask GNOME Shell for the focused window. Mutter has no kdotool or
+/// swaymsg equivalent; its Introspect interface is the closest thing. Since
+/// GNOME 41 it only answers trusted callers unless introspection is allowed
+/// (`gsettings set org.gnome.shell introspect true` on GNOME 44+, or unsafe
+/// mode via Looking Glass on older versions).
+fn gnome_focused_window() -> Result<GnomeWindow, InjectionError> {
+    if !on_gnome_session() {
+        return Err(InjectionError::Other("Not a GNOME session".to_string()));
+    }
+    let output = Command::new("busctl")
+        .args([
+            "--user",
+            "--json=short",
+            "call",
+            "org.gnome.Shell",
+            "/org/gnome/Shell/Introspect",
+            "org.gnome.Shell.Introspect",
+            "GetWindows",
+        ])
+        .output()
+        .map_err(|e| InjectionError::Process(format!("busctl failed: {}", e)))?;
+
+    if !output.status.success() {
+        // AccessDenied unless introspection is allowed
+        debug!(
+            "GNOME Shell introspection unavailable: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return Err(InjectionError::PermissionDenied(
+            "GNOME Shell introspection is not allowed for this caller".to_string(),
+        ));
+    }
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| InjectionError::Other(format!("Invalid busctl output: {}", e)))?;
+    parse_gnome_windows(&json)
+        .ok_or_else(|| InjectionError::Other("GNOME reports no focused window".to_string()))
+}
+
+// Get Wayland window class synchronously
+fn get_wayland_window_class() -> Result<String, InjectionError> {
+    if let Ok(window) = gnome_focused_window() {
+        if let Some(class) = window.app_id.or(window.wm_class) {
+            return Ok(class);
         }
     }
 
+    // Compositors that expose their tree; for now that is sway
+    let (node, _) = sway_focused_window()?;
+    if let Some(app_id) = node.get("app_id").and_then(|v| v.as_str()) {
+        return Ok(app_id.to_string());
+    }
+    // XWayland windows have no app_id
+    if let Some(class) = node
+        .get("window_properties")
+        .and_then(|p| p.get("class"))
+        .and_then(|v| v.as_str())
+    {
+        return Ok(class.to_string());
+    }
+
     Err(InjectionError::Other(
         "Wayland window class not available".to_string(),
     ))
@@ -132,33 +343,52 @@ fn get_wayland_window_class() -> Result<String, InjectionError> {
 
 /// Get active window class using multiple methods
 pub fn get_active_window_class() -> Result<String, InjectionError> {
-    // Try KDE first
-    if let Ok(class) = get_kde_window_class() {
-        return Ok(class);
-    }
+    query_active_window(
+        get_kde_window_class,
+        get_x11_window_class,
+        get_wayland_window_class,
+        "Could not determine active window class",
+    )
+}
 
-    // Try X11
-    if let Ok(class) = get_x11_window_class() {
-        return Ok(class);
-    }
+/// Get the X11 id of the active window (e.g. "0x3a00007")
+pub fn get_active_x11_window_id() -> Result<String, InjectionError> {
+    let output = Command::new("xprop")
+        .args(["-root", "_NET_ACTIVE_WINDOW"])
+        .output()
+        .map_err(|e| InjectionError::Process(format!("xprop failed: {}", e)))?;
 
-    // Try Wayland
-    if let Ok(class) = get_wayland_window_class() {
-        return Ok(class);
+    if output.status.success() {
+        let window_str = String::from_utf8_lossy(&output.stdout);
+        // Format: _NET_ACTIVE_WINDOW(WINDOW): window id # 0x3a00007
+        if let Some(window_id) = window_str.split("# ").nth(1) {
+            let window_id = window_id.trim();
+            // 0x0 means no window has focus
+            if !window_id.is_empty() && window_id != "0x0" {
+                return Ok(window_id.to_string());
+            }
+        }
     }
 
     Err(InjectionError::Other(
-        "Could not determine active window class".to_string(),
+        "X11 active window not available".to_string(),
     ))
 }
 
 /// Get window information using multiple methods
 pub fn get_window_info() -> WindowInfo {
     let class = get_active_window_class().unwrap_or_else(|_| "unknown".to_string());
-    let title = get_window_title().unwrap_or_default();
+    let title = get_active_window_title().unwrap_or_default();
     let pid = get_window_pid().unwrap_or(0);
-
-    WindowInfo { class, title, pid }
+    let output = get_active_window_output().ok();
+
+    WindowInfo {
+        class,
+        title,
+        pid,
+        output,
+        seat: current_seat(),
+    }
 }
 
 /// Window information structure
@@ -167,45 +397,343 @@ pub struct WindowInfo {
     pub class: String,
     pub title: String,
     pub pid: u32,
+    /// Output (monitor) the window is on, e.g. "DP-1"
+    pub output: Option<String>,
+    /// Seat whose focus this describes
+    pub seat: String,
 }
 
-/// Get the title of the active window
-fn get_window_title() -> Result<String, InjectionError> {
-    // Try X11 method
-    let output = Command::new("xprop")
-        .args(["-root", "_NET_ACTIVE_WINDOW"])
+// Get KDE window title synchronously
+fn get_kde_window_title() -> Result<String, InjectionError> {
+    if let Some(caption) = kwin_active_window_info()
+        .ok()
+        .and_then(|mut info| info.remove("caption"))
+    {
+        return Ok(caption);
+    }
+
+    let output = Command::new("qdbus")
+        .args(["org.kde.KWin", "/KWin", "org.kde.KWin.activeClient"])
         .output()
-        .map_err(|e| InjectionError::Process(format!("xprop failed: {}", e)))?;
+        .map_err(|e| InjectionError::Process(format!("qdbus failed: {}", e)))?;
 
     if output.status.success() {
-        let window_str = String::from_utf8_lossy(&output.stdout);
-        if let Some(window_id) = window_str.split("# ").nth(1) {
-            let window_id = window_id.trim();
+        let window_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
 
-            // Get window title
-            let title_output = Command::new("xprop")
-                .args(["-id", window_id, "_NET_WM_NAME"])
-                .output()
-                .map_err(|e| InjectionError::Process(format!("xprop failed: {}", e)))?;
+        let caption_output = Command::new("qdbus")
+            .args([
+                "org.kde.KWin",
+                &format!("/Windows/{}", window_id),
+                "org.kde.KWin.Window.caption",
+            ])
+            .output()
+            .map_err(|e| InjectionError::Process(format!("qdbus failed: {}", e)))?;
 
-            if title_output.status.success() {
-                let title_str = String::from_utf8_lossy(&title_output.stdout);
-                // Parse title string
-                if let Some(title_start) = title_str.find(" = \"") {
-                    let title = &title_str[title_start + 4..];
-                    if let Some(title_end) = title.find('"') {
-                        return Ok(title[..title_end].to_string());
-                    }
-                }
+        if caption_output.status.success() {
+            return Ok(String::from_utf8_lossy(&caption_output.stdout)
+                .trim()
+                .to_string());
+        }
+    }
+
+    Err(InjectionError::Other(
+        "KDE window title not available".to_string(),
+    ))
+}
+
+// Get X11 window title synchronously
+fn get_x11_window_title() -> Result<String, InjectionError> {
+    let window_id = get_active_x11_window_id()?;
+
+    let title_output = Command::new("xprop")
+        .args(["-id", &window_id, "_NET_WM_NAME"])
+        .output()
+        .map_err(|e| InjectionError::Process(format!("xprop failed: {}", e)))?;
+
+    if title_output.status.success() {
+        let title_str = String::from_utf8_lossy(&title_output.stdout);
+        // Parse title string (format: _NET_WM_NAME(UTF8_STRING) = "title")
+        if let Some(title_start) = title_str.find(" = \"") {
+            let title = &title_str[title_start + 4..];
+            if let Some(title_end) = title.rfind('"') {
+                return Ok(title[..title_end].replace("\\\"", "\""));
+            }
+        }
+    }
+
+    Err(InjectionError::Other(
+        "X11 window title not available".to_string(),
+    ))
+}
+
+// Get Wayland (GNOME or sway) window title synchronously
+fn get_wayland_window_title() -> Result<String, InjectionError> {
+    if let Some(title) = gnome_focused_window().ok().and_then(|w| w.title) {
+        return Ok(title);
+    }
+
+    let (node, _) = sway_focused_window()?;
+    // Workspaces and outputs can hold the focus too; only windows have a pid
+    if node.get("pid").is_some() {
+        if let Some(name) = node.get("name").and_then(|v| v.as_str()) {
+            return Ok(name.to_string());
+        }
+    }
+
+    Err(InjectionError::Other(
+        "Wayland window title not available".to_string(),
+    ))
+}
+
+/// Get the title of the active window using multiple methods
+pub fn get_active_window_title() -> Result<String, InjectionError> {
+    query_active_window(
+        get_kde_window_title,
+        get_x11_window_title,
+        get_wayland_window_title,
+        "Could not get window title",
+    )
+}
+
+/// Position and size in global (logical) desktop coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    fn overlap_area(&self, other: &Rect) -> u64 {
+        let left = i64::from(self.x).max(i64::from(other.x));
+        let right = (i64::from(self.x) + i64::from(self.width))
+            .min(i64::from(other.x) + i64::from(other.width));
+        let top = i64::from(self.y).max(i64::from(other.y));
+        let bottom = (i64::from(self.y) + i64::from(self.height))
+            .min(i64::from(other.y) + i64::from(other.height));
+        if right <= left || bottom <= top {
+            0
+        } else {
+            ((right - left) * (bottom - top)) as u64
+        }
+    }
+}
+
+/// A monitor and where it sits on the desktop
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputInfo {
+    pub name: String,
+    pub geometry: Rect,
+}
+
+/// The output a window is on: the one it overlaps most
+pub fn output_for_window<'a>(window: &Rect, outputs: &'a [OutputInfo]) -> Option<&'a OutputInfo> {
+    outputs
+        .iter()
+        .map(|output| (output.geometry.overlap_area(window), output))
+        .filter(|(area, _)| *area > 0)
+        .max_by_key(|(area, _)| *area)
+        .map(|(_, output)| output)
+}
+
+/// Enabled outputs from `kscreen-doctor -j`, in logical coordinates
+fn parse_kscreen_outputs(json: &serde_json::Value) -> Vec<OutputInfo> {
+    let Some(outputs) = json.get("outputs").and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+    outputs
+        .iter()
+        .filter(|o| o.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false))
+        .filter_map(|o| {
+            let name = o.get("name")?.as_str()?.to_string();
+            let pos = o.get("pos")?;
+            let size = o.get("size")?;
+            let scale = o
+                .get("scale")
+                .and_then(|v| v.as_f64())
+                .filter(|s| *s > 0.0)
+                .unwrap_or(1.0);
+            let mut width = size.get("width")?.as_f64()? / scale;
+            let mut height = size.get("height")?.as_f64()? / scale;
+            // Rotated left (2) or right (8): the mode size is sideways
+            if matches!(o.get("rotation").and_then(|v| v.as_u64()), Some(2 | 8)) {
+                std::mem::swap(&mut width, &mut height);
             }
+            Some(OutputInfo {
+                name,
+                geometry: Rect {
+                    x: pos.get("x")?.as_i64()? as i32,
+                    y: pos.get("y")?.as_i64()? as i32,
+                    width: width.round() as u32,
+                    height: height.round() as u32,
+                },
+            })
+        })
+        .collect()
+}
+
+fn get_kde_window_output() -> Result<String, InjectionError> {
+    let info = kwin_active_window_info()?;
+    let number = |key: &str| info.get(key).and_then(|v| v.parse::<f64>().ok());
+    let (Some(x), Some(y), Some(width), Some(height)) =
+        (number("x"), number("y"), number("width"), number("height"))
+    else {
+        return Err(InjectionError::Other(
+            "KWin window geometry not available".to_string(),
+        ));
+    };
+    let window = Rect {
+        x: x.round() as i32,
+        y: y.round() as i32,
+        width: width.round() as u32,
+        height: height.round() as u32,
+    };
+
+    let output = Command::new("kscreen-doctor")
+        .arg("-j")
+        .output()
+        .map_err(|e| InjectionError::Process(format!("kscreen-doctor failed: {}", e)))?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| InjectionError::Other(format!("Invalid kscreen-doctor output: {}", e)))?;
+    output_for_window(&window, &parse_kscreen_outputs(&json))
+        .map(|o| o.name.clone())
+        .ok_or_else(|| InjectionError::Other("KDE window output not found".to_string()))
+}
+
+/// Monitors from `xrandr --listmonitors`, e.g.
+/// ` 1: +HDMI-1 1920/527x1080/296+2560+0  HDMI-1`
+fn parse_xrandr_monitors(text: &str) -> Vec<OutputInfo> {
+    text.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let geometry = fields.nth(2)?;
+            let name = fields.last()?.to_string();
+            // WIDTH/mm x HEIGHT/mm +X +Y
+            let (width, rest) = geometry.split_once('x')?;
+            let width = width.split('/').next()?.parse().ok()?;
+            let mut parts = rest.split('+');
+            let height = parts.next()?.split('/').next()?.parse().ok()?;
+            let x = parts.next()?.parse().ok()?;
+            let y = parts.next()?.parse().ok()?;
+            Some(OutputInfo {
+                name,
+                geometry: Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Window position and size from `xwininfo -id`
+fn parse_xwininfo(text: &str) -> Option<Rect> {
+    let field = |label: &str| -> Option<i64> {
+        text.lines()
+            .find_map(|line| line.trim().strip_prefix(label))
+            .and_then(|v| v.trim().parse().ok())
+    };
+    Some(Rect {
+        x: field("Absolute upper-left X:")? as i32,
+        y: field("Absolute upper-left Y:")? as i32,
+        width: field("Width:")? as u32,
+        height: field("Height:")? as u32,
+    })
+}
+
+fn get_x11_window_output() -> Result<String, InjectionError> {
+    let window_id = get_active_x11_window_id()?;
+    let info = Command::new("xwininfo")
+        .args(["-id", &window_id])
+        .output()
+        .map_err(|e| InjectionError::Process(format!("xwininfo failed: {}", e)))?;
+    let window = parse_xwininfo(&String::from_utf8_lossy(&info.stdout))
+        .ok_or_else(|| InjectionError::Other("X11 window geometry not available".to_string()))?;
+
+    let monitors = Command::new("xrandr")
+        .arg("--listmonitors")
+        .output()
+        .map_err(|e| InjectionError::Process(format!("xrandr failed: {}", e)))?;
+    let outputs = parse_xrandr_monitors(&String::from_utf8_lossy(&monitors.stdout));
+    output_for_window(&window, &outputs)
+        .map(|o| o.name.clone())
+        .ok_or_else(|| InjectionError::Other("X11 window output not found".to_string()))
+}
+
+fn get_wayland_window_output() -> Result<String, InjectionError> {
+    sway_focused_window()?
+        .1
+        .ok_or_else(|| InjectionError::Other("Wayland window output not found".to_string()))
+}
+
+/// Output (monitor) the active window is on, e.g. "DP-1"
+pub fn get_active_window_output() -> Result<String, InjectionError> {
+    query_active_window(
+        get_kde_window_output,
+        get_x11_window_output,
+        get_wayland_window_output,
+        "Could not determine the active window's output",
+    )
+}
+
+/// Identity of a window, enough to bring it back to the front later
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowRef {
+    pub class: String,
+    /// Title when captured (titles change, so it is not used to identify it)
+    pub title: String,
+    /// KWin window id, as used by kdotool
+    pub kwin_id: Option<String>,
+    /// X11 window id, as used by xdotool
+    pub x11_id: Option<String>,
+}
+
+impl WindowRef {
+    /// Whether `other` (e.g. the window active now) is this window. Compares
+    /// window ids where both sides have one, otherwise the class.
+    pub fn is_same_window(&self, other: &WindowRef) -> bool {
+        if let (Some(a), Some(b)) = (&self.kwin_id, &other.kwin_id) {
+            return a == b;
         }
+        if let (Some(a), Some(b)) = (&self.x11_id, &other.x11_id) {
+            return a == b;
+        }
+        self.class == other.class
+    }
+}
+
+// Get the KWin id of the active window via kdotool
+fn get_kdotool_active_window() -> Result<String, InjectionError> {
+    let output = Command::new("kdotool")
+        .arg("getactivewindow")
+        .output()
+        .map_err(|e| InjectionError::Process(format!("kdotool failed: {}", e)))?;
+
+    let window_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() && !window_id.is_empty() {
+        return Ok(window_id);
     }
 
     Err(InjectionError::Other(
-        "Could not get window title".to_string(),
+        "kdotool active window not available".to_string(),
     ))
 }
 
+/// Capture the active window so it can be re-activated later
+pub fn capture_active_window() -> Result<WindowRef, InjectionError> {
+    let class = get_active_window_class()?;
+    Ok(WindowRef {
+        class,
+        title: get_active_window_title().unwrap_or_default(),
+        kwin_id: get_kdotool_active_window().ok(),
+        x11_id: get_active_x11_window_id().ok(),
+    })
+}
+
 /// Get the PID of the active window
 fn get_window_pid() -> Result<u32, InjectionError> {
     // Try X11 method
@@ -259,4 +787,139 @@ mod tests {
         assert!(!info.class.is_empty());
         // Note: title and pid may be empty depending on environment
     }
+
+    #[test]
+    fn xrandr_monitors_and_xwininfo_locate_the_window() {
+        let monitors = "Monitors: 2\n 0: +*DP-1 2560/597x1440/336+0+0  DP-1\n 1: +HDMI-1 1920/527x1080/296+2560+0  HDMI-1\n";
+        let outputs = parse_xrandr_monitors(monitors);
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(
+            outputs[1].geometry,
+            Rect {
+                x: 2560,
+                y: 0,
+                width: 1920,
+                height: 1080
+            }
+        );
+
+        let xwininfo = "xwininfo: Window id: 0x3a00007 \"kate\"\n\n  Absolute upper-left X:  2400\n  Absolute upper-left Y:  100\n  Relative upper-left X:  0\n  Width: 1200\n  Height: 800\n  Border width: 0\n";
+        let window = parse_xwininfo(xwininfo).unwrap();
+        assert_eq!(window.x, 2400);
+        // Straddles both monitors, mostly on the second
+        assert_eq!(
+            output_for_window(&window, &outputs).map(|o| o.name.as_str()),
+            Some("HDMI-1")
+        );
+        let offscreen = Rect {
+            x: -5000,
+            y: 0,
+            width: 10,
+            height: 10,
+        };
+        assert!(output_for_window(&offscreen, &outputs).is_none());
+    }
+
+    #[test]
+    fn kscreen_outputs_use_logical_size() {
+        let json = serde_json::json!({
+            "outputs": [
+                {"name": "eDP-1", "enabled": true, "pos": {"x": 0, "y": 0},
+                 "size": {"width": 2880, "height": 1800}, "scale": 2.0, "rotation": 1},
+                {"name": "DP-2", "enabled": true, "pos": {"x": 1440, "y": 0},
+                 "size": {"width": 1920, "height": 1080}, "scale": 1.0, "rotation": 8},
+                {"name": "HDMI-1", "enabled": false, "pos": {"x": 0, "y": 0},
+                 "size": {"width": 1920, "height": 1080}}
+            ]
+        });
+        let outputs = parse_kscreen_outputs(&json);
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(
+            (outputs[0].geometry.width, outputs[0].geometry.height),
+            (1440, 900)
+        );
+        // Rotated: portrait
+        assert_eq!(
+            (outputs[1].geometry.width, outputs[1].geometry.height),
+            (1080, 1920)
+        );
+    }
+
+    #[test]
+    fn sway_lookup_follows_the_seat_focus() {
+        let tree = serde_json::json!({
+            "id": 1, "type": "root", "nodes": [
+                {"id": 2, "type": "output", "name": "DP-1", "nodes": [
+                    {"id": 10, "type": "con", "app_id": "foot", "focused": true}
+                ]},
+                {"id": 3, "type": "output", "name": "HDMI-A-1", "nodes": [
+                    {"id": 4, "type": "workspace", "floating_nodes": [
+                        {"id": 20, "type": "floating_con", "app_id": "firefox"}
+                    ]}
+                ]}
+            ]
+        });
+        // Without seat information: the node flagged focused
+        let (node, output) = find_sway_node(&tree, None).unwrap();
+        assert_eq!(node["app_id"], "foot");
+        assert_eq!(output, Some("DP-1"));
+        // This seat's focus is elsewhere
+        let (node, output) = find_sway_node(&tree, Some(20)).unwrap();
+        assert_eq!(node["app_id"], "firefox");
+        assert_eq!(output, Some("HDMI-A-1"));
+        assert!(find_sway_node(&tree, Some(99)).is_none());
+    }
+
+    #[test]
+    fn gnome_introspect_reports_the_focused_window() {
+        let json = serde_json::json!({
+            "type": "a{ta{sv}}",
+            "data": [{
+                "2123": {
+                    "app-id": {"type": "s", "data": "org.gnome.Terminal.desktop"},
+                    "wm-class": {"type": "s", "data": "gnome-terminal-server"},
+                    "title": {"type": "s", "data": "~/src"},
+                    "has-focus": {"type": "b", "data": false}
+                },
+                "2150": {
+                    "app-id": {"type": "s", "data": ""},
+                    "wm-class": {"type": "s", "data": "firefox"},
+                    "title": {"type": "s", "data": "News"},
+                    "has-focus": {"type": "b", "data": true}
+                }
+            }]
+        });
+        let window = parse_gnome_windows(&json).unwrap();
+        // Windows without a desktop file fall back to the WM class
+        assert_eq!(window.app_id, None);
+        assert_eq!(window.wm_class.as_deref(), Some("firefox"));
+        assert_eq!(window.title.as_deref(), Some("News"));
+
+        let mut json = json;
+        json["data"][0]["2150"]["has-focus"]["data"] = false.into();
+        json["data"][0]["2123"]["has-focus"]["data"] = true.into();
+        let window = parse_gnome_windows(&json).unwrap();
+        assert_eq!(window.app_id.as_deref(), Some("org.gnome.Terminal"));
+
+        json["data"][0]["2123"]["has-focus"]["data"] = false.into();
+        assert!(parse_gnome_windows(&json).is_none());
+    }
+
+    #[test]
+    fn same_window_prefers_ids_over_class() {
+        let window = |class: &str, kwin: Option<&str>, x11: Option<&str>| WindowRef {
+            class: class.to_string(),
+            title: String::new(),
+            kwin_id: kwin.map(str::to_string),
+            x11_id: x11.map(str::to_string),
+        };
+        let locked = window("kate", Some("{a1}"), Some("0x1"));
+        assert!(locked.is_same_window(&window("kate", Some("{a1}"), None)));
+        // Another window of the same app
+        assert!(!locked.is_same_window(&window("kate", Some("{b2}"), Some("0x2"))));
+        assert!(locked.is_same_window(&window("kate", None, Some("0x1"))));
+        // No ids to compare: fall back to the class
+        assert!(window("kate", None, None).is_same_window(&window("kate", Some("{b2}"), None)));
+        assert!(!locked.is_same_window(&window("firefox", None, None)));
+    }
 }
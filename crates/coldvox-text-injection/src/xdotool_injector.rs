@@ -0,0 +1,225 @@
+//! Xdotool injector for plain X11 sessions (i3, XFCE, ...)
+//!
+//! Types text with `xdotool type --clearmodifiers` into the window that was
+//! active when injection started. Text is sent in bursts of at most
+//! `max_burst_chars` characters with a per-key delay derived from
+//! `keystroke_rate_cps` (or the rate adaptive pacing learned for the target
+//! app), so long transcripts neither block on one huge command line nor
+//! outrun slow applications.
+
+use crate::detection::{detect_display_protocol, DisplayProtocol};
+use crate::keysym::{self, TypingReach};
+use crate::logging::utils;
+use crate::types::{InjectionConfig, InjectionContext, InjectionMethod, InjectionResult};
+use crate::window_manager;
+use crate::TextInjector;
+use async_trait::async_trait;
+use coldvox_foundation::error::InjectionError;
+use std::process::Command;
+use std::time::{Duration, Instant};
+use tokio::time::timeout;
+use tracing::{debug, trace};
+
+/// xdotool's own per-key delay, used when no keystroke rate is configured
+const DEFAULT_KEY_DELAY_MS: u64 = 12;
+
+/// Xdotool injector for synthetic typing on X11
+pub struct XdotoolInjector {
+    config: InjectionConfig,
+    /// Whether xdotool is installed and an X11 session is running
+    is_available: bool,
+}
+
+impl XdotoolInjector {
+    /// Create a new xdotool injector
+    pub fn new(config: InjectionConfig) -> Self {
+        let is_available = Self::check_xdotool();
+
+        Self {
+            config,
+            is_available,
+        }
+    }
+
+    /// xdotool only reaches X11 clients, so XWayland-only sessions don't count
+    fn check_xdotool() -> bool {
+        detect_display_protocol() == DisplayProtocol::X11
+            && Command::new("which")
+                .arg("xdotool")
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false)
+    }
+
+    /// Delay between keystrokes in milliseconds, preferring the rate learned
+    /// for the target app
+    fn key_delay_ms(&self, context: Option<&InjectionContext>) -> u64 {
+        let rate_cps = context
+            .and_then(|c| c.keystroke_rate_cps)
+            .unwrap_or(self.config.keystroke_rate_cps);
+        match rate_cps {
+            0 => DEFAULT_KEY_DELAY_MS,
+            cps => (1000 / u64::from(cps)).max(1),
+        }
+    }
+
+    /// Window to type into: the one active when injection starts
+    async fn target_window() -> Option<String> {
+        tokio::task::spawn_blocking(window_manager::get_active_x11_window_id)
+            .await
+            .ok()
+            .and_then(Result::ok)
+    }
+
+    /// Type one burst with `xdotool type`
+    async fn type_chunk(
+        &self,
+        chunk: &str,
+        window: Option<&str>,
+        delay_ms: u64,
+    ) -> InjectionResult<()> {
+        let mut command = tokio::process::Command::new("xdotool");
+        command.args(["type", "--clearmodifiers", "--delay", &delay_ms.to_string()]);
+        if let Some(window) = window {
+            command.args(["--window", window]);
+        }
+        // `--` keeps text starting with '-' from being read as an option
+        command.arg("--").arg(chunk);
+
+        // Budget for the keystrokes themselves on top of the per-method timeout
+        let budget_ms = self.config.per_method_timeout_ms + delay_ms * chunk.chars().count() as u64;
+        let output = timeout(Duration::from_millis(budget_ms), command.output())
+            .await
+            .map_err(|_| InjectionError::Timeout(budget_ms))?
+            .map_err(|e| InjectionError::Process(format!("Failed to execute xdotool: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(InjectionError::MethodFailed(format!(
+                "xdotool type failed: {}",
+                stderr
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Split `text` into bursts of at most `max_chars` characters
+fn split_bursts(text: &str, max_chars: usize) -> Vec<&str> {
+    let max_chars = max_chars.max(1);
+    let mut bursts = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let end = rest
+            .char_indices()
+            .nth(max_chars)
+            .map_or(rest.len(), |(i, _)| i);
+        bursts.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    bursts
+}
+
+#[async_trait]
+impl TextInjector for XdotoolInjector {
+    async fn inject_text(
+        &self,
+        text: &str,
+        context: Option<&InjectionContext>,
+    ) -> InjectionResult<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        if !self.is_available {
+            return Err(InjectionError::MethodUnavailable(
+                "xdotool is not available".to_string(),
+            ));
+        }
+
+        // xdotool remaps a spare keycode for characters off the layout, but
+        // can't type what has no keysym at all
+        if let Some(c) = keysym::first_untypeable(text, TypingReach::Keysym) {
+            return Err(InjectionError::MethodFailed(format!(
+                "xdotool cannot type {:?}; use clipboard paste",
+                c
+            )));
+        }
+
+        let start = Instant::now();
+        let window = Self::target_window().await;
+        if window.is_none() {
+            debug!("No active X11 window id; xdotool will type into the focused window");
+        }
+
+        let delay_ms = self.key_delay_ms(context);
+        let bursts = split_bursts(text, self.config.max_burst_chars as usize);
+        for (i, burst) in bursts.iter().enumerate() {
+            trace!(
+                "xdotool burst {}/{} ({} chars)",
+                i + 1,
+                bursts.len(),
+                burst.chars().count()
+            );
+            self.type_chunk(burst, window.as_deref(), delay_ms).await?;
+        }
+
+        utils::log_injection_success(
+            InjectionMethod::XdoToolType,
+            text,
+            start.elapsed(),
+            self.config.redact_logs,
+        );
+        Ok(())
+    }
+
+    async fn is_available(&self) -> bool {
+        self.is_available
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "Xdotool"
+    }
+
+    fn backend_info(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("type", "X11 automation".to_string()),
+            (
+                "description",
+                "Types text with xdotool into the active X11 window".to_string(),
+            ),
+            ("key_delay_ms", self.key_delay_ms(None).to_string()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bursts_respect_char_limit() {
+        assert_eq!(split_bursts("abcdefg", 3), vec!["abc", "def", "g"]);
+        assert_eq!(split_bursts("ééé", 2), vec!["éé", "é"]);
+        assert!(split_bursts("", 4).is_empty());
+    }
+
+    #[test]
+    fn key_delay_follows_rate() {
+        let config = InjectionConfig {
+            keystroke_rate_cps: 50,
+            ..Default::default()
+        };
+        let injector = XdotoolInjector {
+            config,
+            is_available: false,
+        };
+        assert_eq!(injector.key_delay_ms(None), 20);
+
+        // A rate learned for the target app wins
+        let context = InjectionContext {
+            keystroke_rate_cps: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(injector.key_delay_ms(Some(&context)), 100);
+    }
+}
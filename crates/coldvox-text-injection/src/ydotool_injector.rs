@@ -1,3 +1,5 @@
+use crate::keysym::{self, TypingReach};
+use crate::layout;
 use crate::types::{InjectionConfig, InjectionResult};
 use crate::TextInjector;
 use anyhow::Result;
@@ -91,6 +93,11 @@ pub(crate) fn apply_socket_env(command: &mut TokioCommand) {
 }
 
 pub(crate) fn ydotool_runtime_available() -> bool {
+    // uinput devices join seat0, so from another seat ydotool would type
+    // into the default seat's session
+    if !crate::window_manager::on_default_seat() {
+        return false;
+    }
     if YdotoolInjector::check_binary_permissions("ydotool").is_err() {
         return false;
     }
@@ -126,6 +133,15 @@ impl YdotoolInjector {
 
     /// Check if ydotool is available on the system
     fn check_ydotool() -> bool {
+        // uinput devices join seat0, so from another seat ydotool would type
+        // into the default seat's session
+        if !crate::window_manager::on_default_seat() {
+            warn!(
+                "ydotool disabled: session is on seat {}, not seat0",
+                crate::window_manager::current_seat()
+            );
+            return false;
+        }
         match Self::check_binary_permissions("ydotool") {
             Ok(()) => {
                 if let Some(socket) = ydotool_daemon_socket() {
@@ -239,7 +255,9 @@ impl YdotoolInjector {
 
         let mut command = TokioCommand::new("ydotool");
         apply_socket_env(&mut command);
-        command.args(["key", "ctrl+v"]);
+        command.arg("key").args(layout::ydotool_paste_args(
+            self.config.keyboard_layout.as_deref(),
+        ));
 
         let output = timeout(
             Duration::from_millis(self.config.paste_action_timeout_ms),
@@ -262,10 +280,118 @@ impl YdotoolInjector {
         Ok(())
     }
 
+    /// Erase `count` characters before the cursor by sending BackSpace key events.
+    ///
+    /// Used to undo the most recent injection. Whether this removes exactly the
+    /// injected text depends on the target application not having moved the cursor.
+    pub async fn erase_chars(&self, count: usize) -> Result<(), InjectionError> {
+        if count == 0 {
+            return Ok(());
+        }
+        if !self.is_available {
+            return Err(InjectionError::MethodUnavailable(
+                "ydotool is not available".to_string(),
+            ));
+        }
+
+        // Linux input keycode 14 = KEY_BACKSPACE
+        let events: Vec<String> = (0..count)
+            .flat_map(|_| ["14:1".to_string(), "14:0".to_string()])
+            .collect();
+        self.send_key_events(&events).await?;
+
+        info!("Erased {} characters via ydotool", count);
+
+        Ok(())
+    }
+
+    /// Press a key chord given as Linux input keycodes, e.g. `[29, 44]` for
+    /// Ctrl+Z: keys are pressed in order and released in reverse. Letter keys
+    /// are US positions and move to where the letter is on the active layout.
+    pub async fn send_key_chord(&self, keycodes: &[u16]) -> Result<(), InjectionError> {
+        if keycodes.is_empty() {
+            return Ok(());
+        }
+        if !self.is_available {
+            return Err(InjectionError::MethodUnavailable(
+                "ydotool is not available".to_string(),
+            ));
+        }
+
+        let keycodes: Vec<u16> = match layout::active(self.config.keyboard_layout.as_deref()) {
+            Ok(keymap) => keycodes.iter().map(|&c| keymap.shortcut_key(c)).collect(),
+            Err(_) => keycodes.to_vec(),
+        };
+        let events: Vec<String> = keycodes
+            .iter()
+            .map(|code| format!("{code}:1"))
+            .chain(keycodes.iter().rev().map(|code| format!("{code}:0")))
+            .collect();
+        self.send_key_events(&events).await?;
+
+        debug!("Sent key chord {:?} via ydotool", keycodes);
+        Ok(())
+    }
+
+    /// Run `ydotool key` with raw "<code>:<1|0>" press/release events
+    async fn send_key_events(&self, events: &[String]) -> Result<(), InjectionError> {
+        let mut command = TokioCommand::new("ydotool");
+        apply_socket_env(&mut command);
+        command.arg("key").args(events);
+
+        let output = timeout(
+            Duration::from_millis(self.config.max_total_latency_ms),
+            command.output(),
+        )
+        .await
+        .map_err(|_| InjectionError::Timeout(self.config.max_total_latency_ms))?
+        .map_err(|e| InjectionError::Process(format!("{e}")))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(InjectionError::MethodFailed(format!(
+                "ydotool key failed: {}",
+                stderr
+            )));
+        }
+        Ok(())
+    }
+
     /// Type text directly using ydotool
     async fn _type_text(&self, text: &str) -> Result<(), InjectionError> {
         let _start = std::time::Instant::now();
 
+        // uinput presses physical keys; what they produce depends on the layout
+        let keymap = layout::active(self.config.keyboard_layout.as_deref()).map_err(|reason| {
+            InjectionError::MethodFailed(format!(
+                "ydotool cannot type on {}; use clipboard paste",
+                reason
+            ))
+        })?;
+        if !keymap.is_us() {
+            let events = keymap.key_events(text).map_err(|c| {
+                InjectionError::MethodFailed(format!(
+                    "ydotool cannot type {:?} on keyboard layout {}; use clipboard paste",
+                    c,
+                    keymap.name()
+                ))
+            })?;
+            self.send_key_events(&events).await?;
+            info!(
+                "Successfully typed text via ydotool on layout {} ({} chars)",
+                keymap.name(),
+                text.len()
+            );
+            return Ok(());
+        }
+        // `ydotool type` assumes a US layout
+        if let Some(c) = keysym::first_untypeable(text, TypingReach::UsLayout) {
+            return Err(InjectionError::MethodFailed(format!(
+                "ydotool cannot type {:?}; use clipboard paste",
+                c
+            )));
+        }
+
         let mut command = TokioCommand::new("ydotool");
         apply_socket_env(&mut command);
         command.args(["type", "--delay", "10", text]);
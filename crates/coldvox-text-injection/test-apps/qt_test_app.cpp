@@ -0,0 +1,76 @@
+// Qt Widgets counterpart of gtk_test_app.c: a single QLineEdit whose text is
+// mirrored to /tmp/coldvox_qt_test_<pid>.txt on every change. Qt's AT-SPI
+// bridge, input methods and clipboard behave differently from GTK's, which is
+// what KDE applications exercise.
+//
+// Built against Qt 6 (or Qt 5) by build.rs. No moc step is needed: the
+// signals are connected to lambdas.
+
+#include <QApplication>
+#include <QLineEdit>
+#include <QTimer>
+
+#include <cstdio>
+#include <fcntl.h>
+#include <unistd.h>
+
+static void output_path(char *buf, size_t len) {
+    snprintf(buf, len, "/tmp/coldvox_qt_test_%d.txt", getpid());
+}
+
+static void write_text(const QString &text) {
+    char filepath[256];
+    output_path(filepath, sizeof(filepath));
+
+    FILE *f = fopen(filepath, "w");
+    if (f == NULL) {
+        perror("Error opening file for writing");
+        return;
+    }
+    QByteArray utf8 = text.toUtf8();
+    fwrite(utf8.constData(), 1, (size_t)utf8.size(), f);
+    fclose(f);
+}
+
+// Same contract as the GTK app: the file appears, holding our PID, once the
+// event loop runs
+static void create_ready_file() {
+    char filepath[256];
+    output_path(filepath, sizeof(filepath));
+
+    int fd = open(filepath, O_WRONLY | O_CREAT | O_EXCL, 0600);
+    if (fd == -1) {
+        perror("Failed to create ready file");
+        return;
+    }
+
+    char pidbuf[32];
+    int len = snprintf(pidbuf, sizeof(pidbuf), "%d", getpid());
+    if (len > 0) {
+        ssize_t w = write(fd, pidbuf, (size_t)len);
+        if (w < 0) {
+            perror("Failed to write PID to ready file");
+        }
+    }
+    close(fd);
+}
+
+int main(int argc, char *argv[]) {
+    // AT-SPI tests need the accessibility bridge even without a screen reader
+    qputenv("QT_LINUX_ACCESSIBILITY_ALWAYS_ON", "1");
+
+    QApplication app(argc, argv);
+
+    QLineEdit entry;
+    entry.setWindowTitle("Qt Test App");
+    entry.resize(200, 50);
+    QObject::connect(&entry, &QLineEdit::textChanged, write_text);
+
+    entry.show();
+    entry.activateWindow();
+    entry.setFocus();
+
+    QTimer::singleShot(0, create_ready_file);
+
+    return app.exec();
+}
@@ -0,0 +1,170 @@
+//! A text target without a widget toolkit, standing in for Electron-style and
+//! game-engine clients: a winit window that keeps its own text buffer fed by
+//! key events, IME commits and Ctrl+V, and mirrors it to
+//! `/tmp/coldvox_winit_test_<pid>.txt`. It has no accessibility tree, so
+//! AT-SPI cannot insert into it. On Wayland it is a native client.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::num::NonZeroU32;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use winit::application::ApplicationHandler;
+use winit::dpi::LogicalSize;
+use winit::event::{ElementState, Ime, KeyEvent, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use winit::window::{Window, WindowId};
+
+struct App {
+    output: PathBuf,
+    text: String,
+    modifiers: ModifiersState,
+    window: Option<Rc<Window>>,
+    /// Backs `surface`
+    _context: Option<softbuffer::Context<Rc<Window>>>,
+    surface: Option<softbuffer::Surface<Rc<Window>, Rc<Window>>>,
+    clipboard: Option<arboard::Clipboard>,
+    ready: bool,
+}
+
+impl App {
+    fn save(&self) {
+        if let Err(e) = fs::write(&self.output, &self.text) {
+            eprintln!("Error writing {}: {}", self.output.display(), e);
+        }
+    }
+
+    /// Same contract as the GTK app: the file appears, holding our PID, once
+    /// the window is on screen
+    fn create_ready_file(&mut self) {
+        self.ready = true;
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&self.output);
+        match file {
+            Ok(mut f) => {
+                let _ = write!(f, "{}", std::process::id());
+            }
+            Err(e) => eprintln!("Failed to create ready file: {}", e),
+        }
+    }
+
+    fn on_key(&mut self, event: KeyEvent) {
+        let paste = self.modifiers.control_key()
+            && matches!(&event.logical_key, Key::Character(c) if c.eq_ignore_ascii_case("v"));
+        match &event.logical_key {
+            _ if paste => {
+                let pasted = self.clipboard.as_mut().and_then(|c| c.get_text().ok());
+                match pasted {
+                    Some(text) => self.text.push_str(&text),
+                    None => return,
+                }
+            }
+            Key::Named(NamedKey::Enter) => self.text.push('\n'),
+            Key::Named(NamedKey::Tab) => self.text.push('\t'),
+            Key::Named(NamedKey::Backspace) => {
+                self.text.pop();
+            }
+            _ if self.modifiers.control_key() || self.modifiers.alt_key() => return,
+            _ => match &event.text {
+                Some(text) if !text.chars().any(char::is_control) => self.text.push_str(text),
+                _ => return,
+            },
+        }
+        self.save();
+    }
+
+    fn redraw(&mut self) {
+        let (Some(window), Some(surface)) = (&self.window, &mut self.surface) else {
+            return;
+        };
+        let size = window.inner_size();
+        let (Some(width), Some(height)) =
+            (NonZeroU32::new(size.width), NonZeroU32::new(size.height))
+        else {
+            return;
+        };
+        // A Wayland window is only mapped once it has a buffer
+        if surface.resize(width, height).is_err() {
+            return;
+        }
+        if let Ok(mut buffer) = surface.buffer_mut() {
+            buffer.fill(0x00ff_ffff);
+            let _ = buffer.present();
+        }
+        if !self.ready {
+            self.create_ready_file();
+        }
+    }
+}
+
+impl ApplicationHandler for App {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.window.is_some() {
+            return;
+        }
+        let attributes = Window::default_attributes()
+            .with_title("Winit Test App")
+            .with_inner_size(LogicalSize::new(200.0, 50.0));
+        let window = Rc::new(
+            event_loop
+                .create_window(attributes)
+                .expect("Failed to create window"),
+        );
+        window.set_ime_allowed(true);
+        let context =
+            softbuffer::Context::new(window.clone()).expect("Failed to create drawing context");
+        self.surface = Some(
+            softbuffer::Surface::new(&context, window.clone())
+                .expect("Failed to create drawing surface"),
+        );
+        window.focus_window();
+        window.request_redraw();
+        self._context = Some(context);
+        self.window = Some(window);
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
+        match event {
+            WindowEvent::CloseRequested => event_loop.exit(),
+            WindowEvent::RedrawRequested => self.redraw(),
+            WindowEvent::Resized(_) => {
+                if let Some(window) = &self.window {
+                    window.request_redraw();
+                }
+            }
+            WindowEvent::ModifiersChanged(modifiers) => self.modifiers = modifiers.state(),
+            WindowEvent::KeyboardInput { event, .. } if event.state == ElementState::Pressed => {
+                self.on_key(event)
+            }
+            WindowEvent::Ime(Ime::Commit(text)) => {
+                self.text.push_str(&text);
+                self.save();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn main() {
+    let mut app = App {
+        output: PathBuf::from(format!(
+            "/tmp/coldvox_winit_test_{}.txt",
+            std::process::id()
+        )),
+        text: String::new(),
+        modifiers: ModifiersState::empty(),
+        window: None,
+        _context: None,
+        surface: None,
+        clipboard: arboard::Clipboard::new().ok(),
+        ready: false,
+    };
+    let event_loop = EventLoop::new().expect("Failed to create event loop");
+    event_loop.run_app(&mut app).expect("Event loop failed");
+}
@@ -1,21 +1,118 @@
-use coldvox_vad::constants::FRAME_SIZE_SAMPLES;
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SileroConfig {
-    pub threshold: f32,
-    pub min_speech_duration_ms: u32,
-    pub min_silence_duration_ms: u32,
-    pub window_size_samples: usize,
+use std::fmt;
+use std::str::FromStr;
+
+// One definition shared with `UnifiedVadConfig::silero`, so tuned values
+// pass between the app and the engine unchanged
+pub use coldvox_vad::config::SileroConfig;
+
+/// Lowest and highest speech probability threshold accepted by the setters
+pub const THRESHOLD_RANGE: (f32, f32) = (0.05, 0.95);
+
+/// Clamp a speech probability threshold to [`THRESHOLD_RANGE`]
+pub fn clamp_threshold(threshold: f32) -> f32 {
+    threshold.clamp(THRESHOLD_RANGE.0, THRESHOLD_RANGE.1)
+}
+
+/// Named VAD sensitivity settings, from ignoring the most to catching the most
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensitivityPreset {
+    /// High threshold, short pauses end utterances: noisy rooms, close mics
+    Aggressive,
+    Balanced,
+    /// Low threshold, long pauses tolerated: quiet voices, distant mics
+    Relaxed,
+}
+
+impl SensitivityPreset {
+    pub const ALL: [SensitivityPreset; 3] = [Self::Aggressive, Self::Balanced, Self::Relaxed];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Aggressive => "aggressive",
+            Self::Balanced => "balanced",
+            Self::Relaxed => "relaxed",
+        }
+    }
+
+    /// `base` with this preset's threshold and timings
+    pub fn apply(self, base: &SileroConfig) -> SileroConfig {
+        let (threshold, min_speech_duration_ms, min_silence_duration_ms) = match self {
+            Self::Aggressive => (0.5, 250, 300),
+            Self::Balanced => (0.3, 150, 500),
+            Self::Relaxed => (0.1, 100, 500),
+        };
+        SileroConfig {
+            threshold,
+            min_speech_duration_ms,
+            min_silence_duration_ms,
+            ..base.clone()
+        }
+    }
+
+    /// The preset `config` corresponds to, if it hasn't been tuned away from one
+    pub fn matching(config: &SileroConfig) -> Option<Self> {
+        Self::ALL.into_iter().find(|preset| {
+            let p = preset.apply(config);
+            (p.threshold - config.threshold).abs() < 1e-4
+                && p.min_speech_duration_ms == config.min_speech_duration_ms
+                && p.min_silence_duration_ms == config.min_silence_duration_ms
+        })
+    }
+
+    /// The next preset, wrapping around (for cycling through them)
+    pub fn next(self) -> Self {
+        match self {
+            Self::Aggressive => Self::Balanced,
+            Self::Balanced => Self::Relaxed,
+            Self::Relaxed => Self::Aggressive,
+        }
+    }
 }
 
-impl Default for SileroConfig {
-    fn default() -> Self {
-        Self {
-            threshold: 0.3,
-            min_speech_duration_ms: 250,
-            min_silence_duration_ms: 100,
-            window_size_samples: FRAME_SIZE_SAMPLES,
+impl fmt::Display for SensitivityPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SensitivityPreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|p| p.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "unknown VAD sensitivity preset '{}' (expected aggressive, balanced or relaxed)",
+                    s
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_round_trip_and_keep_window_size() {
+        let base = SileroConfig {
+            window_size_samples: 1024,
+            ..SileroConfig::default()
+        };
+        for preset in SensitivityPreset::ALL {
+            let config = preset.apply(&base);
+            assert_eq!(config.window_size_samples, 1024);
+            assert_eq!(SensitivityPreset::matching(&config), Some(preset));
+            assert_eq!(preset.as_str().parse::<SensitivityPreset>(), Ok(preset));
         }
+        let tuned = SileroConfig {
+            threshold: 0.42,
+            ..SensitivityPreset::Balanced.apply(&base)
+        };
+        assert_eq!(SensitivityPreset::matching(&tuned), None);
+        assert!("loud".parse::<SensitivityPreset>().is_err());
+        assert_eq!(clamp_threshold(2.0), THRESHOLD_RANGE.1);
     }
 }
@@ -2,7 +2,7 @@ pub mod config;
 #[cfg(feature = "silero")]
 pub mod silero_wrapper;
 
-pub use config::SileroConfig;
+pub use config::{clamp_threshold, SensitivityPreset, SileroConfig, THRESHOLD_RANGE};
 
 #[cfg(feature = "silero")]
 pub use silero_wrapper::SileroEngine;
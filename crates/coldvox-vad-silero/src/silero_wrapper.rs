@@ -1,5 +1,5 @@
-use crate::config::SileroConfig;
-use coldvox_vad::{VadEngine, VadEvent, VadState};
+use crate::config::{clamp_threshold, SileroConfig};
+use coldvox_vad::{UnifiedVadConfig, VadEngine, VadEvent, VadState};
 use std::time::Instant;
 use voice_activity_detector::VoiceActivityDetector;
 
@@ -43,6 +43,28 @@ impl SileroEngine {
         })
     }
 
+    pub fn config(&self) -> &SileroConfig {
+        &self.config
+    }
+
+    /// Speech probability threshold, clamped to
+    /// [`THRESHOLD_RANGE`](crate::THRESHOLD_RANGE). Takes effect on the next frame.
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.config.threshold = clamp_threshold(threshold);
+    }
+
+    pub fn set_min_speech_ms(&mut self, ms: u32) {
+        self.config.min_speech_duration_ms = ms;
+    }
+
+    pub fn set_min_silence_ms(&mut self, ms: u32) {
+        self.config.min_silence_duration_ms = ms;
+    }
+
+    pub fn set_offset_hysteresis(&mut self, hysteresis: f32) {
+        self.config.offset_hysteresis = hysteresis.max(0.0);
+    }
+
     fn process_probability(&mut self, probability: f32) -> Option<VadEvent> {
         let timestamp_ms = self.frames_processed * 512 * 1000 / 16000;
 
@@ -70,7 +92,9 @@ impl SileroEngine {
                 }
             }
             VadState::Speech => {
-                if probability < self.config.threshold {
+                // Hysteresis: speech starts above `threshold` but only ends
+                // below the lower offset threshold
+                if probability < self.config.offset_threshold() {
                     if self.silence_start_time.is_none() {
                         self.silence_start_time = Some(Instant::now());
                     } else if let Some(start) = self.silence_start_time {
@@ -145,6 +169,19 @@ impl VadEngine for SileroEngine {
     fn required_frame_size_samples(&self) -> usize {
         512
     }
+
+    fn speech_probability(&self) -> Option<f32> {
+        Some(self.last_probability)
+    }
+
+    fn retune(&mut self, config: &UnifiedVadConfig) -> bool {
+        // The window size is fixed by the model; only detection parameters change
+        self.set_threshold(config.silero.threshold);
+        self.set_min_speech_ms(config.silero.min_speech_duration_ms);
+        self.set_min_silence_ms(config.silero.min_silence_duration_ms);
+        self.set_offset_hysteresis(config.silero.offset_hysteresis);
+        true
+    }
 }
 
 fn probability_to_db(probability: f32) -> f32 {
@@ -176,6 +213,45 @@ mod tests {
         assert!(evt.is_none(), "Silence should not emit VAD events");
     }
 
+    #[test]
+    fn silero_engine_retunes_without_reset() {
+        let mut engine = SileroEngine::new(SileroConfig::default()).unwrap();
+        engine.process(&[0i16; 512]).unwrap();
+        let mut unified = UnifiedVadConfig::default();
+        unified.silero.threshold = 5.0;
+        unified.silero.min_silence_duration_ms = 800;
+        assert!(engine.retune(&unified));
+        assert_eq!(engine.config().threshold, crate::THRESHOLD_RANGE.1);
+        assert_eq!(engine.config().min_silence_duration_ms, 800);
+        assert_eq!(engine.frames_processed, 1);
+    }
+
+    #[test]
+    fn silero_engine_ends_speech_below_the_offset_threshold() {
+        let cfg = SileroConfig {
+            threshold: 0.5,
+            min_speech_duration_ms: 0,
+            min_silence_duration_ms: 0,
+            ..SileroConfig::default()
+        };
+        let mut engine = SileroEngine::new(cfg).unwrap();
+        assert!(engine.process_probability(0.9).is_none());
+        assert!(matches!(
+            engine.process_probability(0.9),
+            Some(VadEvent::SpeechStart { .. })
+        ));
+        // Below the onset threshold but above the offset one: still speech
+        for _ in 0..5 {
+            assert!(engine.process_probability(0.4).is_none());
+        }
+        assert_eq!(engine.current_state(), VadState::Speech);
+        assert!(engine.process_probability(0.3).is_none());
+        assert!(matches!(
+            engine.process_probability(0.3),
+            Some(VadEvent::SpeechEnd { .. })
+        ));
+    }
+
     #[test]
     fn silero_engine_rejects_incorrect_frame_sizes() {
         let cfg = SileroConfig::default();
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::constants::{FRAME_SIZE_SAMPLES, SAMPLE_RATE_HZ};
+use super::types::VadConfig;
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
 pub enum VadMode {
@@ -8,6 +9,30 @@ pub enum VadMode {
     // Level3 (energy-based) VAD is disabled by default - see Level3Config.enabled
     #[default]
     Silero, // ML-based VAD using ONNX - DEFAULT ACTIVE VAD
+    /// Energy threshold VAD; also the fallback when Silero can't load
+    Energy,
+    /// Silero and the energy VAD side by side, combined per `UnifiedVadConfig::vote`
+    Vote,
+}
+
+/// How the two engines of `VadMode::Vote` are combined
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VotePolicy {
+    /// Speech only while both engines hear it (fewer false triggers from
+    /// keyboard clicks and bumps)
+    And,
+    /// Speech while either engine hears it (fewer missed quiet words)
+    Or,
+    /// Speech while `silero_weight * p_silero + (1 - silero_weight) * p_energy`
+    /// is at least 0.5, debounced like the energy VAD
+    Weighted { silero_weight: f32 },
+}
+
+impl Default for VotePolicy {
+    fn default() -> Self {
+        Self::Weighted { silero_weight: 0.7 }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +47,24 @@ pub struct SileroConfig {
     pub min_silence_duration_ms: u32,
     /// The number of samples in a single processing window.
     pub window_size_samples: usize,
+    /// How far the probability must fall below `threshold` before silence
+    /// starts counting toward the end of speech, so soft syllables hovering
+    /// around the threshold don't end an utterance. 0 = no hysteresis.
+    #[serde(default = "default_offset_hysteresis")]
+    pub offset_hysteresis: f32,
+}
+
+fn default_offset_hysteresis() -> f32 {
+    0.15
+}
+
+impl SileroConfig {
+    /// Probability below which an utterance in progress counts as silent:
+    /// `threshold` less the hysteresis, but never below half the threshold
+    /// so low thresholds can still end speech.
+    pub fn offset_threshold(&self) -> f32 {
+        (self.threshold - self.offset_hysteresis.max(0.0)).max(self.threshold / 2.0)
+    }
 }
 
 impl Default for SileroConfig {
@@ -31,6 +74,7 @@ impl Default for SileroConfig {
             min_speech_duration_ms: 250,
             min_silence_duration_ms: 100,
             window_size_samples: FRAME_SIZE_SAMPLES,
+            offset_hysteresis: default_offset_hysteresis(),
         }
     }
 }
@@ -40,6 +84,12 @@ pub struct UnifiedVadConfig {
     pub mode: VadMode,
 
     pub silero: SileroConfig,
+    /// Energy VAD settings (used in `VadMode::Energy`/`Vote` and as fallback)
+    #[serde(default)]
+    pub energy: VadConfig,
+    /// Combination policy in `VadMode::Vote`
+    #[serde(default)]
+    pub vote: VotePolicy,
     pub frame_size_samples: usize,
     pub sample_rate_hz: u32,
 }
@@ -50,6 +100,8 @@ impl Default for UnifiedVadConfig {
             mode: VadMode::default(), // Uses Silero by default now
 
             silero: SileroConfig::default(),
+            energy: VadConfig::default(),
+            vote: VotePolicy::default(),
             // Align default frame size with default engine (Silero) requirement
             // Both Silero and Level3 now use 512-sample windows at 16 kHz
             frame_size_samples: FRAME_SIZE_SAMPLES,
@@ -63,3 +115,30 @@ impl UnifiedVadConfig {
         (self.frame_size_samples as f32 * 1000.0) / self.sample_rate_hz as f32
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn silero_offset_threshold_applies_hysteresis() {
+        let config = SileroConfig {
+            threshold: 0.5,
+            ..Default::default()
+        };
+        assert!((config.offset_threshold() - 0.35).abs() < 1e-6);
+
+        // A low threshold keeps half of itself so speech can still end
+        let low = SileroConfig {
+            threshold: 0.1,
+            ..Default::default()
+        };
+        assert!((low.offset_threshold() - 0.05).abs() < 1e-6);
+
+        let off = SileroConfig {
+            offset_hysteresis: 0.0,
+            ..config
+        };
+        assert_eq!(off.offset_threshold(), off.threshold);
+    }
+}
@@ -1,5 +1,10 @@
 // Note: FRAME_SIZE_SAMPLES is used only in tests here; import within test module to avoid warnings.
 
+use crate::engine::VadEngine;
+use crate::state::VadStateMachine;
+use crate::threshold::AdaptiveThreshold;
+use crate::types::{VadConfig, VadEvent, VadState};
+
 pub struct EnergyCalculator {
     epsilon: f32,
 }
@@ -50,11 +55,286 @@ impl Default for EnergyCalculator {
     }
 }
 
+/// Detection thresholds derived from a stretch of ambient noise
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    /// Median frame level of the room
+    pub noise_floor_db: f32,
+    /// Level above the floor that starts speech
+    pub onset_threshold_db: f32,
+    /// Level above the floor below which speech ends (onset minus hysteresis)
+    pub offset_threshold_db: f32,
+    /// Frames the calibration is based on
+    pub frames: usize,
+}
+
+impl Calibration {
+    /// Write the derived floor and thresholds into `config`
+    pub fn apply(&self, config: &mut VadConfig) {
+        config.initial_floor_db = self.noise_floor_db;
+        config.onset_threshold_db = self.onset_threshold_db;
+        config.offset_threshold_db = self.offset_threshold_db;
+    }
+}
+
+/// Collects frame levels of ambient noise and derives a [`Calibration`].
+///
+/// The floor is the median level. How much the noise fluctuates (90th
+/// percentile above the median) widens the onset margin, so a fan or traffic
+/// doesn't open utterances, and the hysteresis, so speech over it doesn't
+/// flicker on and off.
+#[derive(Debug, Clone)]
+pub struct NoiseCalibrator {
+    levels_db: Vec<f32>,
+    target_frames: usize,
+}
+
+impl NoiseCalibrator {
+    pub fn new(target_frames: usize) -> Self {
+        Self {
+            levels_db: Vec::with_capacity(target_frames),
+            target_frames: target_frames.max(1),
+        }
+    }
+
+    pub fn push(&mut self, energy_db: f32) {
+        self.levels_db.push(energy_db);
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.levels_db.len() >= self.target_frames
+    }
+
+    /// None if no frames were collected
+    pub fn finish(mut self) -> Option<Calibration> {
+        if self.levels_db.is_empty() {
+            return None;
+        }
+        self.levels_db.sort_by(f32::total_cmp);
+        let percentile = |p: f32| {
+            let idx = ((self.levels_db.len() - 1) as f32 * p).round() as usize;
+            self.levels_db[idx]
+        };
+        // AdaptiveThreshold keeps its floor within this range too
+        let noise_floor_db = percentile(0.5).clamp(-80.0, -20.0);
+        let spread_db = (percentile(0.9) - percentile(0.5)).max(0.0);
+
+        let onset_threshold_db = (spread_db + 6.0).clamp(6.0, 20.0);
+        let hysteresis_db = (spread_db * 0.5 + 2.0).clamp(2.0, 6.0);
+        Some(Calibration {
+            noise_floor_db,
+            onset_threshold_db,
+            offset_threshold_db: (onset_threshold_db - hysteresis_db).max(2.0),
+            frames: self.levels_db.len(),
+        })
+    }
+}
+
+/// Energy threshold VAD: frame level against an adaptive noise floor, with
+/// onset/offset hysteresis and debouncing
+pub struct EnergyVad {
+    config: VadConfig,
+    calculator: EnergyCalculator,
+    threshold: AdaptiveThreshold,
+    state: VadStateMachine,
+    calibrator: Option<NoiseCalibrator>,
+    /// Calibration requested mid-utterance; starts once speech ends
+    pending_calibration_ms: Option<u32>,
+    last_calibration: Option<Calibration>,
+    last_energy_db: f32,
+}
+
+impl EnergyVad {
+    /// Calibrates from the first `config.startup_calibration_ms` of audio
+    /// unless that is 0
+    pub fn new(config: VadConfig) -> Self {
+        let mut vad = Self {
+            calculator: EnergyCalculator::new(),
+            threshold: AdaptiveThreshold::new(&config),
+            state: VadStateMachine::new(&config),
+            calibrator: None,
+            pending_calibration_ms: None,
+            last_calibration: None,
+            last_energy_db: -100.0,
+            config,
+        };
+        if vad.config.startup_calibration_ms > 0 {
+            vad.calibrate(vad.config.startup_calibration_ms);
+        }
+        vad
+    }
+
+    pub fn config(&self) -> &VadConfig {
+        &self.config
+    }
+
+    /// Result of the most recent completed calibration
+    pub fn last_calibration(&self) -> Option<Calibration> {
+        self.last_calibration
+    }
+
+    pub fn is_calibrating(&self) -> bool {
+        self.calibrator.is_some() || self.pending_calibration_ms.is_some()
+    }
+
+    fn start_calibration(&mut self, duration_ms: u32) {
+        let frames = (duration_ms as f32 / self.config.frame_duration_ms()).ceil() as usize;
+        self.calibrator = Some(NoiseCalibrator::new(frames));
+    }
+
+    fn finish_calibration(&mut self) {
+        let Some(calibration) = self.calibrator.take().and_then(NoiseCalibrator::finish) else {
+            return;
+        };
+        calibration.apply(&mut self.config);
+        self.threshold = AdaptiveThreshold::new(&self.config);
+        self.last_calibration = Some(calibration);
+    }
+}
+
+impl VadEngine for EnergyVad {
+    fn process(&mut self, frame: &[i16]) -> Result<Option<VadEvent>, String> {
+        if frame.len() != self.config.frame_size_samples {
+            return Err(format!(
+                "Energy VAD requires {} samples, got {}",
+                self.config.frame_size_samples,
+                frame.len()
+            ));
+        }
+        let energy_db = self.calculator.calculate_dbfs(frame);
+        self.last_energy_db = energy_db;
+
+        if self.state.current_state() == VadState::Silence {
+            if let Some(duration_ms) = self.pending_calibration_ms.take() {
+                self.start_calibration(duration_ms);
+            }
+        }
+        if let Some(calibrator) = &mut self.calibrator {
+            calibrator.push(energy_db);
+            if calibrator.is_complete() {
+                self.finish_calibration();
+            }
+            return Ok(None);
+        }
+
+        let is_speech = match self.state.current_state() {
+            VadState::Silence => self.threshold.should_activate(energy_db),
+            VadState::Speech => !self.threshold.should_deactivate(energy_db),
+        };
+        self.threshold.update(energy_db, is_speech);
+        Ok(self.state.process(is_speech, energy_db))
+    }
+
+    fn reset(&mut self) {
+        self.state.reset();
+        self.threshold.reset(self.config.initial_floor_db);
+    }
+
+    fn current_state(&self) -> VadState {
+        self.state.current_state()
+    }
+
+    fn required_sample_rate(&self) -> u32 {
+        self.config.sample_rate_hz
+    }
+
+    fn required_frame_size_samples(&self) -> usize {
+        self.config.frame_size_samples
+    }
+
+    /// 0 at the noise floor, 0.5 at the onset threshold, 1 at twice its margin
+    fn speech_probability(&self) -> Option<f32> {
+        if self.calibrator.is_some() {
+            return Some(0.0);
+        }
+        let floor = self.threshold.current_floor();
+        let margin = (self.threshold.onset_threshold() - floor).max(f32::EPSILON);
+        Some(((self.last_energy_db - floor) / (2.0 * margin)).clamp(0.0, 1.0))
+    }
+
+    fn calibrate(&mut self, duration_ms: u32) -> bool {
+        if duration_ms == 0 {
+            return false;
+        }
+        if self.state.current_state() == VadState::Speech {
+            self.pending_calibration_ms = Some(duration_ms);
+        } else {
+            self.start_calibration(duration_ms);
+        }
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::constants::FRAME_SIZE_SAMPLES;
 
+    /// A frame of a 440 Hz tone with the given peak amplitude
+    fn tone(amplitude: f32) -> Vec<i16> {
+        (0..FRAME_SIZE_SAMPLES)
+            .map(|i| {
+                let phase = 2.0 * std::f32::consts::PI * 440.0 * i as f32 / 16_000.0;
+                (phase.sin() * amplitude) as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn calibrator_derives_floor_and_widens_margins_for_unsteady_noise() {
+        let calc = EnergyCalculator::new();
+        let mut steady = NoiseCalibrator::new(10);
+        for _ in 0..10 {
+            steady.push(calc.calculate_dbfs(&tone(100.0)));
+        }
+        assert!(steady.is_complete());
+        let steady = steady.finish().unwrap();
+        assert!((steady.noise_floor_db - calc.calculate_dbfs(&tone(100.0))).abs() < 0.1);
+        assert_eq!(steady.onset_threshold_db, 6.0);
+        assert_eq!(steady.offset_threshold_db, 4.0);
+
+        let mut unsteady = NoiseCalibrator::new(10);
+        for i in 0..10 {
+            let amplitude = if i % 3 == 0 { 400.0 } else { 100.0 };
+            unsteady.push(calc.calculate_dbfs(&tone(amplitude)));
+        }
+        let unsteady = unsteady.finish().unwrap();
+        assert!(unsteady.onset_threshold_db > steady.onset_threshold_db);
+        assert!(unsteady.offset_threshold_db < unsteady.onset_threshold_db);
+        assert!(NoiseCalibrator::new(10).finish().is_none());
+    }
+
+    #[test]
+    fn energy_vad_calibrates_to_a_loud_room() {
+        let config = VadConfig {
+            startup_calibration_ms: 320,
+            ..Default::default()
+        };
+        let mut vad = EnergyVad::new(config);
+        assert!(vad.is_calibrating());
+
+        // Room noise around -38 dBFS is above the default onset (-50 + 9 dB)
+        let room = tone(600.0);
+        for _ in 0..10 {
+            assert_eq!(vad.process(&room).unwrap(), None);
+        }
+        assert!(!vad.is_calibrating());
+        assert!(vad.last_calibration().unwrap().noise_floor_db > -50.0);
+        for _ in 0..30 {
+            assert_eq!(vad.process(&room).unwrap(), None);
+        }
+
+        let speech = tone(6000.0);
+        let started = (0..30).any(|_| {
+            matches!(
+                vad.process(&speech).unwrap(),
+                Some(VadEvent::SpeechStart { .. })
+            )
+        });
+        assert!(started);
+        assert!(vad.process(&room[..100]).is_err());
+    }
+
     #[test]
     fn test_silence_returns_low_dbfs() {
         let calc = EnergyCalculator::new();
@@ -1,4 +1,7 @@
-use crate::types::{VadEvent, VadState};
+use crate::config::{UnifiedVadConfig, VotePolicy};
+use crate::energy::EnergyCalculator;
+use crate::state::VadStateMachine;
+use crate::types::{VadConfig, VadEvent, VadState};
 
 /// A trait for Voice Activity Detection (VAD) engines.
 ///
@@ -10,4 +13,246 @@ pub trait VadEngine: Send {
     fn current_state(&self) -> VadState;
     fn required_sample_rate(&self) -> u32;
     fn required_frame_size_samples(&self) -> usize;
+
+    /// Switch to the detection parameters in `config` without resetting the
+    /// stream. Returns false if the engine can't be retuned while running.
+    fn retune(&mut self, _config: &UnifiedVadConfig) -> bool {
+        false
+    }
+
+    /// Learn the ambient noise level from the next `duration_ms` of audio and
+    /// derive the detection thresholds from it. The room should be quiet
+    /// meanwhile; no speech is reported until it completes. Returns false if
+    /// the engine doesn't calibrate.
+    fn calibrate(&mut self, _duration_ms: u32) -> bool {
+        false
+    }
+
+    /// Likelihood (0..1) that the last frame was speech, for engines that
+    /// have one
+    fn speech_probability(&self) -> Option<f32> {
+        None
+    }
+}
+
+/// Runs two engines on every frame and combines their decisions with a
+/// [`VotePolicy`]. Built for Silero (primary) plus the energy VAD
+/// (secondary): the model rejects clicks that are merely loud, the energy
+/// VAD catches speech too quiet for the model.
+pub struct VotingVad {
+    primary: Box<dyn VadEngine>,
+    secondary: Box<dyn VadEngine>,
+    policy: VotePolicy,
+    /// Debounces the combined decision and produces the events
+    decision: VadStateMachine,
+    calculator: EnergyCalculator,
+}
+
+impl VotingVad {
+    /// `debounce` supplies the frame timing and, for `VotePolicy::Weighted`,
+    /// the speech/silence debounce. AND/OR combine states the engines have
+    /// already debounced, so they switch immediately.
+    pub fn new(
+        primary: Box<dyn VadEngine>,
+        secondary: Box<dyn VadEngine>,
+        policy: VotePolicy,
+        debounce: &VadConfig,
+    ) -> Result<Self, String> {
+        if primary.required_sample_rate() != secondary.required_sample_rate()
+            || primary.required_frame_size_samples() != secondary.required_frame_size_samples()
+        {
+            return Err(format!(
+                "VAD engines disagree on framing: {} samples @ {} Hz vs {} samples @ {} Hz",
+                primary.required_frame_size_samples(),
+                primary.required_sample_rate(),
+                secondary.required_frame_size_samples(),
+                secondary.required_sample_rate()
+            ));
+        }
+        let decision_config = match policy {
+            VotePolicy::Weighted { .. } => debounce.clone(),
+            VotePolicy::And | VotePolicy::Or => VadConfig {
+                speech_debounce_ms: 0,
+                silence_debounce_ms: 0,
+                ..debounce.clone()
+            },
+        };
+        Ok(Self {
+            primary,
+            secondary,
+            policy,
+            decision: VadStateMachine::new(&decision_config),
+            calculator: EnergyCalculator::new(),
+        })
+    }
+
+    fn is_speech(&self) -> bool {
+        let hears = |engine: &dyn VadEngine| engine.current_state() == VadState::Speech;
+        match self.policy {
+            VotePolicy::And => hears(&*self.primary) && hears(&*self.secondary),
+            VotePolicy::Or => hears(&*self.primary) || hears(&*self.secondary),
+            VotePolicy::Weighted { silero_weight } => {
+                let p = |engine: &dyn VadEngine| {
+                    engine
+                        .speech_probability()
+                        .unwrap_or(if hears(engine) { 1.0 } else { 0.0 })
+                };
+                let w = silero_weight.clamp(0.0, 1.0);
+                w * p(&*self.primary) + (1.0 - w) * p(&*self.secondary) >= 0.5
+            }
+        }
+    }
+}
+
+impl VadEngine for VotingVad {
+    fn process(&mut self, frame: &[i16]) -> Result<Option<VadEvent>, String> {
+        // The engines' own events only move their states; the vote decides
+        self.primary.process(frame)?;
+        self.secondary.process(frame)?;
+        let energy_db = self.calculator.calculate_dbfs(frame);
+        Ok(self.decision.process(self.is_speech(), energy_db))
+    }
+
+    fn reset(&mut self) {
+        self.primary.reset();
+        self.secondary.reset();
+        self.decision.reset();
+    }
+
+    fn current_state(&self) -> VadState {
+        self.decision.current_state()
+    }
+
+    fn required_sample_rate(&self) -> u32 {
+        self.primary.required_sample_rate()
+    }
+
+    fn required_frame_size_samples(&self) -> usize {
+        self.primary.required_frame_size_samples()
+    }
+
+    fn retune(&mut self, config: &UnifiedVadConfig) -> bool {
+        let primary = self.primary.retune(config);
+        let secondary = self.secondary.retune(config);
+        primary || secondary
+    }
+
+    fn calibrate(&mut self, duration_ms: u32) -> bool {
+        let primary = self.primary.calibrate(duration_ms);
+        let secondary = self.secondary.calibrate(duration_ms);
+        primary || secondary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::FRAME_SIZE_SAMPLES;
+
+    /// Replays a fixed sequence of states and probabilities
+    struct Scripted {
+        frames: Vec<(VadState, f32)>,
+        at: usize,
+    }
+
+    impl Scripted {
+        fn boxed(frames: &[(VadState, f32)]) -> Box<dyn VadEngine> {
+            Box::new(Self {
+                frames: frames.to_vec(),
+                at: 0,
+            })
+        }
+    }
+
+    impl VadEngine for Scripted {
+        fn process(&mut self, _frame: &[i16]) -> Result<Option<VadEvent>, String> {
+            self.at += 1;
+            Ok(None)
+        }
+        fn reset(&mut self) {}
+        fn current_state(&self) -> VadState {
+            self.frames[self.at.saturating_sub(1)].0
+        }
+        fn required_sample_rate(&self) -> u32 {
+            16_000
+        }
+        fn required_frame_size_samples(&self) -> usize {
+            FRAME_SIZE_SAMPLES
+        }
+        fn speech_probability(&self) -> Option<f32> {
+            Some(self.frames[self.at.saturating_sub(1)].1)
+        }
+    }
+
+    const S: VadState = VadState::Speech;
+    const Q: VadState = VadState::Silence;
+
+    /// States of a voting VAD over frames where the model hears a word and
+    /// the energy VAD hears the word plus a keyboard click before it
+    fn run(policy: VotePolicy) -> Vec<VadState> {
+        let silero = [(Q, 0.1), (Q, 0.2), (S, 0.9), (S, 0.8), (Q, 0.1)];
+        let energy = [(S, 0.9), (Q, 0.3), (S, 0.7), (S, 0.6), (Q, 0.2)];
+        let debounce = VadConfig {
+            speech_debounce_ms: 0,
+            silence_debounce_ms: 0,
+            ..Default::default()
+        };
+        let mut vad = VotingVad::new(
+            Scripted::boxed(&silero),
+            Scripted::boxed(&energy),
+            policy,
+            &debounce,
+        )
+        .unwrap();
+        let frame = [0i16; FRAME_SIZE_SAMPLES];
+        (0..silero.len())
+            .map(|_| {
+                vad.process(&frame).unwrap();
+                vad.current_state()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn policies_combine_engine_decisions() {
+        assert_eq!(run(VotePolicy::And), [Q, Q, S, S, Q]);
+        assert_eq!(run(VotePolicy::Or), [S, Q, S, S, Q]);
+        // The click alone (0.7*0.1 + 0.3*0.9 = 0.34) doesn't pass
+        assert_eq!(
+            run(VotePolicy::Weighted { silero_weight: 0.7 }),
+            [Q, Q, S, S, Q]
+        );
+        assert_eq!(
+            run(VotePolicy::Weighted { silero_weight: 0.0 }),
+            [S, Q, S, S, Q]
+        );
+    }
+
+    #[test]
+    fn mismatched_framing_is_rejected() {
+        struct Slow;
+        impl VadEngine for Slow {
+            fn process(&mut self, _frame: &[i16]) -> Result<Option<VadEvent>, String> {
+                Ok(None)
+            }
+            fn reset(&mut self) {}
+            fn current_state(&self) -> VadState {
+                VadState::Silence
+            }
+            fn required_sample_rate(&self) -> u32 {
+                8_000
+            }
+            fn required_frame_size_samples(&self) -> usize {
+                256
+            }
+        }
+        let energy = Scripted::boxed(&[(Q, 0.0)]);
+        assert!(VotingVad::new(
+            Box::new(Slow),
+            energy,
+            VotePolicy::Or,
+            &VadConfig::default()
+        )
+        .is_err());
+    }
 }
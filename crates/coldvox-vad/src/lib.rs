@@ -7,9 +7,10 @@ pub mod threshold;
 pub mod types;
 
 // Core exports - grouped and sorted alphabetically
-pub use config::{UnifiedVadConfig, VadMode};
+pub use config::{UnifiedVadConfig, VadMode, VotePolicy};
 pub use constants::{FRAME_DURATION_MS, FRAME_SIZE_SAMPLES, SAMPLE_RATE_HZ};
-pub use engine::VadEngine;
+pub use energy::{Calibration, EnergyVad, NoiseCalibrator};
+pub use engine::{VadEngine, VotingVad};
 pub use types::{VadConfig, VadEvent, VadMetrics, VadState};
 
 /// Main VAD trait for processing audio frames
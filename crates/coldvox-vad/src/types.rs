@@ -39,6 +39,15 @@ pub struct VadConfig {
     pub frame_size_samples: usize,
 
     pub sample_rate_hz: u32,
+
+    /// Ambient noise sampled at startup to derive the thresholds (0 = use
+    /// the configured ones)
+    #[serde(default = "default_startup_calibration_ms")]
+    pub startup_calibration_ms: u32,
+}
+
+fn default_startup_calibration_ms() -> u32 {
+    1500
 }
 
 impl Default for VadConfig {
@@ -52,6 +61,7 @@ impl Default for VadConfig {
             initial_floor_db: -50.0,
             frame_size_samples: FRAME_SIZE_SAMPLES,
             sample_rate_hz: SAMPLE_RATE_HZ,
+            startup_calibration_ms: default_startup_calibration_ms(),
         }
     }
 }
@@ -386,7 +386,7 @@ function Invoke-Smoke {
         -FilePath 'cargo' `
         -WorkingDirectory $RepoRoot `
         -ArgumentList @(
-            'run',
+            'build',
             '-p', 'coldvox-gui',
             '--quiet'
         )